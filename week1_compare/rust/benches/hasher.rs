@@ -0,0 +1,66 @@
+// cargo bench --bench hasher
+// 한 텍스트의 같은 토큰 열을 --hasher fx와 sip의 빈도표로 세어 걸린 시간을 비교한다.
+// 토큰은 지프 분포에 가깝게 뽑아(흔한 단어가 아주 많이 반복) 실제 글을 셀 때와 비슷하게 한다.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use week1_rust_word_count::counter::{Counter, Hasher};
+
+const TOKENS: usize = 2_000_000;
+const VOCAB: usize = 50_000;
+const ROUNDS: usize = 5;
+
+// 고정 씨앗의 xorshift. 실행마다 같은 토큰 열이 나온다.
+fn text() -> String {
+    let vocabulary: Vec<String> = (0..VOCAB).map(|k| format!("w{k:x}ord")).collect();
+    let mut state: u64 = 0x5EED_C0DE;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut text = String::new();
+    for _ in 0..TOKENS {
+        // 0..1 사이 값의 세제곱을 순위로 써서 앞 순위에 몰리게 한다.
+        let u = (next() >> 11) as f64 / (1u64 << 53) as f64;
+        text.push_str(&vocabulary[((u * u * u) * VOCAB as f64) as usize]);
+        text.push(' ');
+    }
+    text
+}
+
+fn fastest(tokens: &[&str], hasher: Hasher) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            let mut counts = Counter::with_hasher(hasher);
+            for token in tokens {
+                counts.add(token, 1);
+            }
+            black_box(counts.len());
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn main() {
+    let text = text();
+    let tokens: Vec<&str> = text.split(' ').filter(|t| !t.is_empty()).collect();
+    let fx = fastest(&tokens, Hasher::Fx);
+    let sip = fastest(&tokens, Hasher::Sip);
+    for (hasher, time) in [(Hasher::Fx, fx), (Hasher::Sip, sip)] {
+        println!(
+            "{:<4} {:>8.2} ms  {:>6.1} ns/token",
+            hasher.as_str(),
+            time.as_secs_f64() * 1e3,
+            time.as_secs_f64() * 1e9 / TOKENS as f64
+        );
+    }
+    println!(
+        "fx is {:.2}x faster than sip",
+        sip.as_secs_f64() / fx.as_secs_f64()
+    );
+}
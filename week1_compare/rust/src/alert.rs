@@ -0,0 +1,174 @@
+// --alert RULE: --follow로 지켜보는 동안 단어가 나오는 빈도를 최근 구간(미끄러지는 창)에서 세고,
+// 규칙이 참이 되거나(FIRING) 다시 거짓이 되면(RESOLVED) 한 줄을 찍는다. 작은 로그 감시기다.
+//   error > 100 per 5m       최근 5분 동안 error가 100번을 넘으면
+//   heartbeat < 1 per 10m    최근 10분 동안 heartbeat가 한 번도 없으면
+// 단어는 본문과 같은 규칙으로 정규화한다(--assert와 같음). 연산자는 <, <=, >, >=.
+// 시각은 줄이 도착한 시각이다(줄 안의 타임스탬프는 읽지 않는다). < 규칙은 지켜보기 시작한 뒤
+// 창 길이만큼 지나야 판단한다. 처음부터 "한 번도 없음"으로 울리지 않게 하려는 것이다.
+// --alert-cmd CMD는 상태가 바뀔 때마다 sh -c CMD를 ALERT_RULE, ALERT_STATE(firing/resolved),
+// ALERT_COUNT 환경 변수와 함께 실행한다.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::schedule;
+use crate::{normalize_words, Tokenizer};
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    spec: String,
+    word: String,
+    op: Op,
+    limit: usize,
+    window: Duration,
+}
+
+impl Rule {
+    pub fn parse(spec: &str, tok: Tokenizer) -> Result<Rule, String> {
+        let err = || format!("--alert expects e.g. 'WORD > N per 5m', got {spec:?}");
+        // 두 글자 연산자를 먼저 찾아야 "<="가 "<"로 잘리지 않는다.
+        const OPS: [(&str, Op); 4] = [("<=", Op::Le), (">=", Op::Ge), ("<", Op::Lt), (">", Op::Gt)];
+        let (pos, symbol, op) = OPS
+            .iter()
+            .find_map(|(symbol, op)| spec.find(symbol).map(|pos| (pos, *symbol, *op)))
+            .ok_or_else(err)?;
+        let word = spec[..pos].trim();
+        let (limit, window) = spec[pos + symbol.len()..]
+            .split_once(" per ")
+            .ok_or_else(err)?;
+        let limit = limit.trim().parse::<usize>().map_err(|_| err())?;
+        let window = crate::subtitles::parse_duration(window.trim())?;
+        let word = match normalize_words(word, tok).as_slice() {
+            [w] => w.clone(),
+            _ => return Err(format!("--alert watches a single word, got {word:?}")),
+        };
+        Ok(Rule {
+            spec: spec.trim().to_string(),
+            word,
+            op,
+            limit,
+            window: Duration::from_millis(window),
+        })
+    }
+
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    fn holds(&self, count: usize) -> bool {
+        match self.op {
+            Op::Lt => count < self.limit,
+            Op::Le => count <= self.limit,
+            Op::Gt => count > self.limit,
+            Op::Ge => count >= self.limit,
+        }
+    }
+}
+
+struct Watch {
+    rule: Rule,
+    // 창 안의 (도착 시각, 그 줄에서 나온 횟수). 오래된 것이 앞이다.
+    hits: VecDeque<(Instant, usize)>,
+    count: usize,
+    firing: bool,
+}
+
+pub struct Monitor {
+    watches: Vec<Watch>,
+    tokenizer: Tokenizer,
+    hook: Option<String>,
+    started: Instant,
+}
+
+impl Monitor {
+    pub fn new(rules: &[Rule], tokenizer: Tokenizer, hook: Option<String>) -> Monitor {
+        let watches = rules
+            .iter()
+            .map(|rule| Watch {
+                rule: rule.clone(),
+                hits: VecDeque::new(),
+                count: 0,
+                firing: false,
+            })
+            .collect();
+        Monitor {
+            watches,
+            tokenizer,
+            hook,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn line(&mut self, line: &str, now: Instant) {
+        let words = normalize_words(line, self.tokenizer);
+        for watch in &mut self.watches {
+            let n = words.iter().filter(|w| **w == watch.rule.word).count();
+            if n > 0 {
+                watch.hits.push_back((now, n));
+                watch.count += n;
+            }
+        }
+    }
+
+    // 창에서 밀려난 줄을 빼고 규칙을 다시 판단한다. 상태가 바뀐 규칙마다 한 줄을 쓴다.
+    pub fn tick(&mut self, now: Instant, out: &mut dyn Write) {
+        for watch in &mut self.watches {
+            while let Some(&(at, n)) = watch.hits.front() {
+                if now.duration_since(at) < watch.rule.window {
+                    break;
+                }
+                watch.hits.pop_front();
+                watch.count -= n;
+            }
+            let settled = now.duration_since(self.started) >= watch.rule.window;
+            let below = matches!(watch.rule.op, Op::Lt | Op::Le);
+            if below && !settled {
+                continue;
+            }
+            let holds = watch.rule.holds(watch.count);
+            if holds == watch.firing {
+                continue;
+            }
+            watch.firing = holds;
+            let state = if holds { "firing" } else { "resolved" };
+            let _ = writeln!(
+                out,
+                "{} {} {} (count {})",
+                schedule::utc_timestamp(SystemTime::now()),
+                state.to_ascii_uppercase(),
+                watch.rule.spec,
+                watch.count
+            );
+            let _ = out.flush();
+            if let Some(cmd) = &self.hook {
+                run_hook(cmd, &watch.rule, state, watch.count);
+            }
+        }
+    }
+}
+
+// 훅이 실패해도 감시는 계속한다.
+fn run_hook(cmd: &str, rule: &Rule, state: &str, count: usize) {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .env("ALERT_RULE", &rule.spec)
+        .env("ALERT_STATE", state)
+        .env("ALERT_COUNT", count.to_string())
+        .status();
+    match status {
+        Ok(s) if s.success() => {}
+        Ok(s) => eprintln!("--alert-cmd exited with {s}"),
+        Err(e) => eprintln!("--alert-cmd failed to start: {e}"),
+    }
+}
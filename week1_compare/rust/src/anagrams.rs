@@ -0,0 +1,26 @@
+// 애너그램 묶기: 글자를 정렬한 문자열(시그니처)이 같은 단어끼리 모은다.
+// Python의 defaultdict(list)에 ''.join(sorted(w))를 키로 쓰는 방식과 같다.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{normalize_words, Tokenizer};
+
+pub fn groups(text: &str, tok: Tokenizer) -> Vec<Vec<String>> {
+    // BTreeSet으로 같은 단어의 중복 등장을 없애면서 그룹 안 순서도 정렬해 둔다.
+    let mut by_signature: HashMap<String, BTreeSet<String>> = HashMap::new();
+    for word in normalize_words(text, tok) {
+        let mut chars: Vec<char> = word.chars().collect();
+        chars.sort_unstable();
+        let signature: String = chars.into_iter().collect();
+        by_signature.entry(signature).or_default().insert(word);
+    }
+
+    let mut result: Vec<Vec<String>> = by_signature
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .map(|members| members.into_iter().collect())
+        .collect();
+    // 큰 그룹 먼저, 같은 크기면 첫 단어 알파벳순.
+    result.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    result
+}
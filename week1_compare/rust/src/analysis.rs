@@ -0,0 +1,362 @@
+// 상위 단어 분석의 설정을 한 값으로 묶는다. 옵션이 늘 때마다 함수 인자가 열 개씩 늘지 않도록
+// 빌더로 만든다(Python이라면 키워드 인자 기본값으로 해결할 부분이다).
+//   let top = Analysis::builder().top(10).stopwords(["the", "a"]).case(Case::Preserve).run(text);
+// 명령행 도구도 플래그를 읽어 같은 빌더로 Analysis를 만들고, 순위는 모두 여기서 매긴다.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use crate::collate::Collation;
+use crate::counter::Counter;
+use crate::json::{self, FromJson, ToJson, Value};
+use crate::{
+    count_words, glob_match, is_glob, normalize_words, Case, Contractions, Hyphens, Normalization,
+    Numbers, Stem, Summary, Tokenizer,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    // 빈도 내림차순, 동률은 알파벳순.
+    #[default]
+    Count,
+    // 상위 N개를 고른 뒤 알파벳순으로 다시 정렬.
+    Alpha,
+}
+
+// --tie-break: 빈도가 같은 항목의 순서.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    // --collate 규칙을 따른 알파벳순(기본값).
+    #[default]
+    Alpha,
+    // 입력에서 처음 등장한 순서(Python Counter.most_common()과 같음).
+    FirstSeen,
+    // 긴 단어 먼저, 길이도 같으면 알파벳순.
+    Length,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordCount {
+    pub word: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    top: usize,
+    sort: SortOrder,
+    tie_break: TieBreak,
+    collation: Collation,
+    stopwords: WordList,
+    // 있으면 여기 든 단어만 순위에 남긴다(--keep-file).
+    keep: Option<WordList>,
+    tokenizer: Tokenizer,
+}
+
+// 정규화한 단어와 와일드카드 패턴(glob.rs). 패턴은 정규화하면 *가 지워지므로 대소문자만 맞춘다.
+#[derive(Debug, Clone, Default)]
+struct WordList {
+    words: HashSet<String>,
+    patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnalysisBuilder {
+    top: usize,
+    sort: SortOrder,
+    tie_break: TieBreak,
+    collation: Collation,
+    stopwords: Vec<String>,
+    keep: Option<Vec<String>>,
+    tokenizer: Tokenizer,
+}
+
+impl Analysis {
+    // 기본값은 CLI와 같다: 상위 5개, 빈도순, 동률은 바이트 순서 알파벳순.
+    pub fn builder() -> AnalysisBuilder {
+        AnalysisBuilder {
+            top: 5,
+            sort: SortOrder::Count,
+            tie_break: TieBreak::Alpha,
+            collation: Collation::Bytes,
+            stopwords: Vec::new(),
+            keep: None,
+            tokenizer: Tokenizer::default(),
+        }
+    }
+
+    // 0이면 전체.
+    pub fn top(&self) -> usize {
+        self.top
+    }
+
+    pub fn tokenizer(&self) -> Tokenizer {
+        self.tokenizer
+    }
+
+    pub fn sort(&self) -> SortOrder {
+        self.sort
+    }
+
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    pub fn collation(&self) -> Collation {
+        self.collation
+    }
+
+    // 패턴도 한 개로 센다.
+    pub fn stopword_count(&self) -> usize {
+        self.stopwords.len()
+    }
+
+    pub fn keep_count(&self) -> Option<usize> {
+        self.keep.as_ref().map(WordList::len)
+    }
+
+    // 순위에서 뺄 단어인지: 불용어이거나, 남길 목록이 있는데 거기 없는 단어.
+    pub fn is_stopword(&self, word: &str) -> bool {
+        self.stopwords.contains(word) || self.keep.as_ref().is_some_and(|keep| !keep.contains(word))
+    }
+
+    // 단어 빈도(불용어 포함, 등장 순서 유지).
+    pub fn count(&self, text: &str) -> Counter {
+        count_words(text, self.tokenizer)
+    }
+
+    pub fn without_stopwords(&self, counts: Counter) -> Counter {
+        if self.stopwords.is_empty() && self.keep.is_none() {
+            return counts;
+        }
+        let mut kept = Counter::new();
+        for (word, n) in counts.iter() {
+            if !self.is_stopword(word) {
+                kept.add(word, n);
+            }
+        }
+        kept
+    }
+
+    // 동률 처리와 SortOrder::Alpha 모두 같은 Collation을 거치므로 로캘 규칙이 한 곳에서 정해진다.
+    // top이 0이면 자르지 않고 전체 순위를 돌려준다.
+    pub fn rank(&self, counts: Counter) -> Vec<(String, usize)> {
+        let mut items = self.rank_all(counts);
+        if self.top > 0 {
+            items.truncate(self.top);
+        }
+        if self.sort == SortOrder::Alpha {
+            items.sort_by(|a, b| self.collation.compare(&a.0, &b.0));
+        }
+        items
+    }
+
+    // 빈도 내림차순 전체 순위(top, sort 적용 전). zipf처럼 전체 분포가 필요한 곳에서 쓴다.
+    // sort_by는 안정 정렬이라 비교가 같으면 Counter의 첫 등장 순서가 남는다. 그래서 같은 입력은
+    // 어떤 TieBreak에서도 실행마다 같은 순서로 나온다.
+    pub fn rank_all(&self, counts: Counter) -> Vec<(String, usize)> {
+        let collation = self.collation;
+        let mut items = counts.into_vec();
+        items.sort_by(|a, b| {
+            b.1.cmp(&a.1).then_with(|| match self.tie_break {
+                TieBreak::Alpha => collation.compare(&a.0, &b.0),
+                TieBreak::FirstSeen => Ordering::Equal,
+                TieBreak::Length => (b.0.chars().count())
+                    .cmp(&a.0.chars().count())
+                    .then_with(|| collation.compare(&a.0, &b.0)),
+            })
+        });
+        items
+    }
+
+    // run()의 순위에 전체 토큰 수와 서로 다른 단어 수를 붙인다(Display, HTML 표는 summary.rs).
+    pub fn summarize(&self, text: &str) -> Summary {
+        let counts = self.count(text);
+        let tokens = counts.iter().map(|(_, n)| n).sum();
+        let distinct = counts.len();
+        let top_words = self
+            .rank(self.without_stopwords(counts))
+            .into_iter()
+            .map(|(word, count)| WordCount { word, count })
+            .collect();
+        Summary {
+            tokens,
+            distinct,
+            top_words,
+        }
+    }
+
+    // 불용어를 뺀 상위 단어.
+    pub fn run(&self, text: &str) -> Vec<WordCount> {
+        self.rank(self.without_stopwords(self.count(text)))
+            .into_iter()
+            .map(|(word, count)| WordCount { word, count })
+            .collect()
+    }
+}
+
+impl ToJson for WordCount {
+    fn to_json(&self) -> Value {
+        Value::Object(vec![
+            ("word".to_string(), Value::String(self.word.clone())),
+            ("count".to_string(), Value::Number(self.count as f64)),
+        ])
+    }
+}
+
+impl FromJson for WordCount {
+    fn from_json(value: &Value) -> Result<WordCount, String> {
+        Ok(WordCount {
+            word: json::str_field(value, "word")?,
+            count: json::count_field(value, "count")?,
+        })
+    }
+}
+
+impl WordList {
+    fn new(entries: &[String], tok: Tokenizer) -> WordList {
+        let mut list = WordList::default();
+        for entry in entries {
+            if is_glob(entry) {
+                list.patterns.push(match tok.case {
+                    Case::Fold => entry.to_lowercase(),
+                    Case::Preserve => entry.clone(),
+                });
+            } else {
+                list.words.extend(normalize_words(entry, tok));
+            }
+        }
+        list
+    }
+
+    fn len(&self) -> usize {
+        self.words.len() + self.patterns.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words.is_empty() && self.patterns.is_empty()
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.words.contains(word) || self.patterns.iter().any(|p| glob_match(p, word))
+    }
+}
+
+impl AnalysisBuilder {
+    pub fn top(mut self, n: usize) -> AnalysisBuilder {
+        self.top = n;
+        self
+    }
+
+    pub fn sort(mut self, order: SortOrder) -> AnalysisBuilder {
+        self.sort = order;
+        self
+    }
+
+    pub fn tie_break(mut self, rule: TieBreak) -> AnalysisBuilder {
+        self.tie_break = rule;
+        self
+    }
+
+    pub fn collation(mut self, collation: Collation) -> AnalysisBuilder {
+        self.collation = collation;
+        self
+    }
+
+    // 불용어는 build()에서 최종 토큰화 규칙으로 정규화하므로 "The"를 넣어도 "the"가 빠진다.
+    // *나 ?가 든 항목은 패턴이다: "http*"는 http, https, httpclient를 모두 뺀다.
+    pub fn stopwords<I, S>(mut self, words: I) -> AnalysisBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.stopwords.extend(words.into_iter().map(Into::into));
+        self
+    }
+
+    // 여기 든 단어(패턴 포함)만 순위에 남긴다. 여러 번 부르면 목록이 합쳐진다.
+    pub fn keep<I, S>(mut self, words: I) -> AnalysisBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.keep
+            .get_or_insert_with(Vec::new)
+            .extend(words.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn tokenizer(mut self, tokenizer: Tokenizer) -> AnalysisBuilder {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    pub fn case(mut self, case: Case) -> AnalysisBuilder {
+        self.tokenizer.case = case;
+        self
+    }
+
+    pub fn unicode(mut self, enabled: bool) -> AnalysisBuilder {
+        self.tokenizer.unicode = enabled;
+        self
+    }
+
+    pub fn emoji(mut self, enabled: bool) -> AnalysisBuilder {
+        self.tokenizer.emoji = enabled;
+        self
+    }
+
+    pub fn hyphens(mut self, rule: Hyphens) -> AnalysisBuilder {
+        self.tokenizer.hyphens = rule;
+        self
+    }
+
+    pub fn contractions(mut self, rule: Contractions) -> AnalysisBuilder {
+        self.tokenizer.contractions = rule;
+        self
+    }
+
+    pub fn normalization(mut self, form: Normalization) -> AnalysisBuilder {
+        self.tokenizer.normalization = form;
+        self
+    }
+
+    pub fn strip_accents(mut self, enabled: bool) -> AnalysisBuilder {
+        self.tokenizer.strip_accents = enabled;
+        self
+    }
+
+    pub fn transliterate(mut self, enabled: bool) -> AnalysisBuilder {
+        self.tokenizer.transliterate = enabled;
+        self
+    }
+
+    pub fn numbers(mut self, rule: Numbers) -> AnalysisBuilder {
+        self.tokenizer.numbers = rule;
+        self
+    }
+
+    pub fn stem(mut self, stem: Stem) -> AnalysisBuilder {
+        self.tokenizer.stem = stem;
+        self
+    }
+
+    pub fn build(self) -> Analysis {
+        let tokenizer = self.tokenizer;
+        let stopwords = WordList::new(&self.stopwords, tokenizer);
+        let keep = self.keep.map(|words| WordList::new(&words, tokenizer));
+        Analysis {
+            top: self.top,
+            sort: self.sort,
+            tie_break: self.tie_break,
+            collation: self.collation,
+            stopwords,
+            keep,
+            tokenizer,
+        }
+    }
+
+    pub fn run(self, text: &str) -> Vec<WordCount> {
+        self.build().run(text)
+    }
+}
@@ -0,0 +1,131 @@
+// --external-analyzer CMD: --script처럼 프로세스 안에 넣는 훅 대신, 아무 언어로나 쓴 분석기를
+// 자식 프로세스로 돌리고 그 결과를 상위 단어 출력에 표로 합친다. 주고받는 것은 NDJSON 뿐이라
+// 이 크레이트의 내부 구조가 바뀌어도 분석기는 그대로 쓸 수 있다(protocol 번호가 바뀌지 않는 한).
+// 명령은 --extract-cmd처럼 셸을 거치지 않고 공백으로만 나눈다.
+//
+// 자식의 stdin, 첫 줄은 머리, 그 뒤로 입력 줄마다 한 줄(tokens는 본문과 같은 규칙으로 정규화한 단어):
+//   {"protocol": 1}
+//   {"file": "notes.txt", "line": 1, "text": "The cat sat.", "tokens": ["the", "cat", "sat"]}
+// 자식의 stdout, 한 줄에 행 하나. table을 빼면 명령 문자열이 표 이름이다. 같은 표의 같은 word는 더한다:
+//   {"table": "Sentiment", "word": "positive", "count": 3}
+// 빈 줄은 건너뛰고, 그 밖의 줄이나 0이 아닌 종료 상태는 에러다.
+
+use std::io::{BufWriter, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use crate::counter::Counter;
+use crate::json::{self, Value};
+use crate::{normalize_words, Tokenizer};
+
+pub const PROTOCOL: usize = 1;
+
+// 분석기가 낸 표: (표 이름, 빈도). 처음 나온 순서대로.
+pub fn run(
+    command: &str,
+    documents: &[(String, String)],
+    tok: Tokenizer,
+) -> Result<Vec<(String, Counter)>, String> {
+    let mut parts = command.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| "--external-analyzer must not be empty".to_string())?;
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run analyzer {program}: {e}"))?;
+    // 자식이 stdout을 다 쓰기 전에 stdin에서 막히지 않도록 입력은 따로 흘려보낸다.
+    // 자식이 입력을 다 읽지 않고 끝나면 쓰기가 실패하는데, 그것은 자식이 정할 일이다.
+    let stdin = child.stdin.take();
+    let output = thread::scope(|scope| {
+        if let Some(stdin) = stdin {
+            scope.spawn(move || {
+                let _ = write_records(BufWriter::new(stdin), documents, tok);
+            });
+        }
+        child.wait_with_output()
+    })
+    .map_err(|e| format!("failed to run analyzer {program}: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or("").trim();
+        let status = match output.status.code() {
+            Some(code) => format!("exit status {code}"),
+            None => "terminated by signal".to_string(),
+        };
+        return Err(if reason.is_empty() {
+            format!("analyzer {program} failed: {status}")
+        } else {
+            format!("analyzer {program} failed: {status}: {reason}")
+        });
+    }
+    let report = String::from_utf8(output.stdout)
+        .map_err(|_| format!("analyzer {program} wrote output that is not UTF-8"))?;
+    parse(&report, command).map_err(|e| format!("analyzer {program}: {e}"))
+}
+
+fn write_records(
+    mut out: impl Write,
+    documents: &[(String, String)],
+    tok: Tokenizer,
+) -> std::io::Result<()> {
+    let header = Value::Object(vec![(
+        "protocol".to_string(),
+        Value::Number(PROTOCOL as f64),
+    )]);
+    writeln!(out, "{}", json::to_line(&header))?;
+    for (name, text) in documents {
+        for (i, line) in text.lines().enumerate() {
+            let tokens = normalize_words(line, tok)
+                .into_iter()
+                .map(Value::String)
+                .collect();
+            let record = Value::Object(vec![
+                ("file".to_string(), Value::String(name.clone())),
+                ("line".to_string(), Value::Number((i + 1) as f64)),
+                ("text".to_string(), Value::String(line.to_string())),
+                ("tokens".to_string(), Value::Array(tokens)),
+            ]);
+            writeln!(out, "{}", json::to_line(&record))?;
+        }
+    }
+    out.flush()
+}
+
+fn parse(report: &str, command: &str) -> Result<Vec<(String, Counter)>, String> {
+    let mut tables: Vec<(String, Counter)> = Vec::new();
+    for (i, line) in report.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = json::parse(line)
+            .and_then(|row| {
+                let table = match row.get("table") {
+                    Some(name) => name
+                        .as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| "table must be a string".to_string())?,
+                    None => command.to_string(),
+                };
+                Ok((
+                    table,
+                    json::str_field(&row, "word")?,
+                    json::count_field(&row, "count")?,
+                ))
+            })
+            .map_err(|e| format!("line {}: {e}", i + 1))?;
+        let (table, word, count) = row;
+        match tables.iter_mut().find(|(name, _)| *name == table) {
+            Some((_, counts)) => counts.add(&word, count),
+            None => {
+                let mut counts = Counter::new();
+                counts.add(&word, count);
+                tables.push((table, counts));
+            }
+        }
+    }
+    Ok(tables)
+}
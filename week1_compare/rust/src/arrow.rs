@@ -0,0 +1,273 @@
+// --format arrow: 열 형식 표를 Arrow IPC 파일(Feather v2)로 쓴다(pandas.read_feather, polars.read_ipc).
+// 레코드 배치 하나에 null 없는 열만 쓴다. 문자열은 Utf8(int32 오프셋 + 바이트), 개수는 Int64다.
+// 메시지와 꼬리말 메타데이터는 FlatBuffers이므로 작은 직렬화기를 여기 둔다.
+// 형식: https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format
+// (파일 = "ARROW1" 스키마 메시지, 배치 메시지, 끝 표시, 꼬리말, 꼬리말 길이, "ARROW1").
+
+use crate::columnar::{Column, Field};
+
+const MAGIC: &[u8] = b"ARROW1";
+const CONTINUATION: u32 = 0xFFFF_FFFF;
+// MetadataVersion.V5
+const VERSION: i16 = 4;
+// MessageHeader와 Type 공용체의 번호.
+const HEADER_SCHEMA: u8 = 1;
+const HEADER_RECORD_BATCH: u8 = 3;
+const TYPE_INT: u8 = 2;
+const TYPE_UTF8: u8 = 5;
+
+pub fn encode(fields: &[Field], rows: usize) -> Vec<u8> {
+    let mut file = MAGIC.to_vec();
+    file.extend_from_slice(&[0, 0]);
+    let header = message(HEADER_SCHEMA, schema(fields), 0);
+    write_message(&mut file, &header, &[]);
+
+    let (batch, body) = record_batch(fields, rows);
+    let offset = file.len();
+    let meta = message(HEADER_RECORD_BATCH, batch, body.len());
+    let meta_len = write_message(&mut file, &meta, &body);
+    file.extend_from_slice(&CONTINUATION.to_le_bytes());
+    file.extend_from_slice(&0u32.to_le_bytes());
+
+    // Block { offset: long, metaDataLength: int, (4바이트 채움), bodyLength: long }
+    let mut block = Vec::with_capacity(24);
+    block.extend_from_slice(&(offset as i64).to_le_bytes());
+    block.extend_from_slice(&(meta_len as i32).to_le_bytes());
+    block.extend_from_slice(&[0; 4]);
+    block.extend_from_slice(&(body.len() as i64).to_le_bytes());
+    let footer = finish(Node::Table(vec![
+        (0, Value::I16(VERSION)),
+        (1, Value::Child(schema(fields))),
+        (2, Value::Child(Node::Structs(0, Vec::new()))),
+        (3, Value::Child(Node::Structs(1, block))),
+    ]));
+    file.extend_from_slice(&footer);
+    file.extend_from_slice(&(footer.len() as u32).to_le_bytes());
+    file.extend_from_slice(MAGIC);
+    file
+}
+
+fn schema(fields: &[Field]) -> Node {
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let (kind, detail) = match field.column {
+                Column::Utf8(_) => (TYPE_UTF8, Node::Table(Vec::new())),
+                Column::Int64(_) => (
+                    TYPE_INT,
+                    Node::Table(vec![(0, Value::I32(64)), (1, Value::Bool(true))]),
+                ),
+            };
+            Node::Table(vec![
+                (0, Value::Child(Node::Str(field.name.to_string()))),
+                (1, Value::Bool(false)),
+                (2, Value::U8(kind)),
+                (3, Value::Child(detail)),
+                (5, Value::Child(Node::Tables(Vec::new()))),
+            ])
+        })
+        .collect();
+    // endianness는 기본값(Little)이라 비워 둔다.
+    Node::Table(vec![(1, Value::Child(Node::Tables(fields)))])
+}
+
+// 열마다 버퍼: 유효 비트맵(null이 없어 길이 0), Utf8이면 오프셋과 바이트, Int64면 값.
+// 버퍼는 본문 안에서 8바이트 경계에 놓는다.
+fn record_batch(fields: &[Field], rows: usize) -> (Node, Vec<u8>) {
+    let mut body = Vec::new();
+    let mut buffers = Vec::new();
+    let mut nodes = Vec::new();
+    let mut push = |body: &mut Vec<u8>, data: &[u8]| {
+        let start = body.len();
+        body.extend_from_slice(data);
+        while !body.len().is_multiple_of(8) {
+            body.push(0);
+        }
+        buffers.extend_from_slice(&(start as i64).to_le_bytes());
+        buffers.extend_from_slice(&(data.len() as i64).to_le_bytes());
+    };
+    for field in fields {
+        // FieldNode { length, null_count }
+        nodes.extend_from_slice(&(rows as i64).to_le_bytes());
+        nodes.extend_from_slice(&0i64.to_le_bytes());
+        push(&mut body, &[]);
+        match &field.column {
+            Column::Utf8(values) => {
+                let mut offsets = Vec::with_capacity((values.len() + 1) * 4);
+                let mut data = Vec::new();
+                offsets.extend_from_slice(&0i32.to_le_bytes());
+                for value in values {
+                    data.extend_from_slice(value.as_bytes());
+                    offsets.extend_from_slice(&(data.len() as i32).to_le_bytes());
+                }
+                push(&mut body, &offsets);
+                push(&mut body, &data);
+            }
+            Column::Int64(values) => {
+                let data: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+                push(&mut body, &data);
+            }
+        }
+    }
+    let batch = Node::Table(vec![
+        (0, Value::I64(rows as i64)),
+        (1, Value::Child(Node::Structs(fields.len(), nodes))),
+        (2, Value::Child(Node::Structs(buffers.len() / 16, buffers))),
+    ]);
+    (batch, body)
+}
+
+fn message(kind: u8, header: Node, body_len: usize) -> Vec<u8> {
+    finish(Node::Table(vec![
+        (0, Value::I16(VERSION)),
+        (1, Value::U8(kind)),
+        (2, Value::Child(header)),
+        (3, Value::I64(body_len as i64)),
+    ]))
+}
+
+// 연속 표시, 메타데이터 길이, 8바이트로 채운 메타데이터, 본문. 메타데이터 쪽 길이를 돌려준다.
+fn write_message(file: &mut Vec<u8>, meta: &[u8], body: &[u8]) -> usize {
+    let padded = meta.len().next_multiple_of(8);
+    file.extend_from_slice(&CONTINUATION.to_le_bytes());
+    file.extend_from_slice(&(padded as u32).to_le_bytes());
+    file.extend_from_slice(meta);
+    file.resize(file.len() + padded - meta.len(), 0);
+    file.extend_from_slice(body);
+    8 + padded
+}
+
+// FlatBuffers 직렬화. 보통은 뒤에서부터 쌓지만, 여기서는 표를 먼저 쓰고 그 뒤에 자식들을 써서
+// uoffset(항상 앞으로만 가리킨다)을 나중에 채운다. 표 바로 앞에 vtable을 둔다.
+// 스칼라는 절대 위치 기준으로 제 크기에 맞춰 정렬한다(버퍼 자체는 파일의 8바이트 경계에서 시작한다).
+enum Node {
+    Table(Vec<(u16, Value)>),
+    Str(String),
+    Tables(Vec<Node>),
+    // 8바이트로 정렬되는 구조체 n개의 바이트.
+    Structs(usize, Vec<u8>),
+}
+
+enum Value {
+    U8(u8),
+    Bool(bool),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Child(Node),
+}
+
+impl Value {
+    fn size(&self) -> usize {
+        match self {
+            Value::U8(_) | Value::Bool(_) => 1,
+            Value::I16(_) => 2,
+            Value::I32(_) | Value::Child(_) => 4,
+            Value::I64(_) => 8,
+        }
+    }
+}
+
+fn finish(root: Node) -> Vec<u8> {
+    let mut buf = vec![0; 4];
+    let at = write(&mut buf, &root);
+    patch(&mut buf, 0, at);
+    buf
+}
+
+fn pad(buf: &mut Vec<u8>, align: usize) {
+    buf.resize(buf.len().next_multiple_of(align), 0);
+}
+
+// at 자리의 uoffset이 target을 가리키게 한다.
+fn patch(buf: &mut [u8], at: usize, target: usize) {
+    buf[at..at + 4].copy_from_slice(&((target - at) as u32).to_le_bytes());
+}
+
+// node를 쓰고 그 시작 위치(uoffset이 가리킬 곳)를 돌려준다.
+fn write(buf: &mut Vec<u8>, node: &Node) -> usize {
+    match node {
+        Node::Str(text) => {
+            pad(buf, 4);
+            let at = buf.len();
+            buf.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            buf.extend_from_slice(text.as_bytes());
+            buf.push(0);
+            at
+        }
+        Node::Structs(count, bytes) => {
+            // 길이(4바이트) 바로 뒤의 원소가 8바이트 경계에 오도록 맞춘다.
+            pad(buf, 4);
+            if buf.len().is_multiple_of(8) {
+                buf.extend_from_slice(&[0; 4]);
+            }
+            let at = buf.len();
+            buf.extend_from_slice(&(*count as u32).to_le_bytes());
+            buf.extend_from_slice(bytes);
+            at
+        }
+        Node::Tables(items) => {
+            pad(buf, 4);
+            let at = buf.len();
+            buf.extend_from_slice(&(items.len() as u32).to_le_bytes());
+            let slots = buf.len();
+            buf.resize(slots + 4 * items.len(), 0);
+            for (i, item) in items.iter().enumerate() {
+                let target = write(buf, item);
+                patch(buf, slots + 4 * i, target);
+            }
+            at
+        }
+        Node::Table(fields) => {
+            // 표 안의 자리: soffset(4바이트) 뒤로 필드마다 제 크기에 맞춰 놓는다.
+            let mut layout = Vec::with_capacity(fields.len());
+            let mut size = 4usize;
+            for (_, value) in fields {
+                size = size.next_multiple_of(value.size());
+                layout.push(size);
+                size += value.size();
+            }
+            let slots = fields
+                .iter()
+                .map(|(slot, _)| *slot as usize + 1)
+                .max()
+                .unwrap_or(0);
+            let mut vtable = vec![0u16; 2 + slots];
+            vtable[0] = (4 + 2 * slots) as u16;
+            vtable[1] = size as u16;
+            for ((slot, _), &offset) in fields.iter().zip(&layout) {
+                vtable[2 + *slot as usize] = offset as u16;
+            }
+            pad(buf, 2);
+            let vtable_at = buf.len();
+            for entry in vtable {
+                buf.extend_from_slice(&entry.to_le_bytes());
+            }
+            pad(buf, 8);
+            let at = buf.len();
+            buf.resize(at + size, 0);
+            buf[at..at + 4].copy_from_slice(&((at - vtable_at) as i32).to_le_bytes());
+            let mut children = Vec::new();
+            for ((_, value), &offset) in fields.iter().zip(&layout) {
+                let place = at + offset;
+                let bytes = match value {
+                    Value::U8(v) => vec![*v],
+                    Value::Bool(v) => vec![u8::from(*v)],
+                    Value::I16(v) => v.to_le_bytes().to_vec(),
+                    Value::I32(v) => v.to_le_bytes().to_vec(),
+                    Value::I64(v) => v.to_le_bytes().to_vec(),
+                    Value::Child(child) => {
+                        children.push((place, child));
+                        continue;
+                    }
+                };
+                buf[place..place + bytes.len()].copy_from_slice(&bytes);
+            }
+            for (place, child) in children {
+                let target = write(buf, child);
+                patch(buf, place, target);
+            }
+            at
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// --assert: 분석이 끝난 뒤 단어 수 규칙을 검사해 CI를 실패시킨다.
+//   count(TODO) <= 50     단어 빈도 (본문과 같은 규칙으로 정규화하므로 "todo"로 센다)
+//   tokens >= 100         전체 토큰 수
+//   distinct < 5000       서로 다른 단어 수
+// 비교 연산자는 <, <=, >, >=, ==, !=. Python이라면 eval()로 때울 수 있겠지만
+// 임의 코드를 실행하지 않도록 이 세 가지 형태만 받는다.
+
+use crate::counter::Counter;
+use crate::{normalize_words, Tokenizer};
+
+#[derive(Debug, Clone)]
+enum Metric {
+    Count(String),
+    Tokens,
+    Distinct,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone)]
+pub struct Assertion {
+    spec: String,
+    metric: Metric,
+    op: Op,
+    value: usize,
+}
+
+impl Assertion {
+    pub fn parse(spec: &str, tok: Tokenizer) -> Result<Assertion, String> {
+        let err = || format!("--assert expects e.g. 'count(WORD) <= N', got {spec:?}");
+        // 두 글자 연산자를 먼저 찾아야 "<="가 "<"로 잘리지 않는다.
+        const OPS: [(&str, Op); 6] = [
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+        ];
+        let (pos, symbol, op) = OPS
+            .iter()
+            .find_map(|(symbol, op)| spec.find(symbol).map(|pos| (pos, *symbol, *op)))
+            .ok_or_else(err)?;
+        let left = spec[..pos].trim();
+        let right = spec[pos + symbol.len()..].trim();
+        let value = right.parse::<usize>().map_err(|_| err())?;
+        let metric = match left {
+            "tokens" => Metric::Tokens,
+            "distinct" => Metric::Distinct,
+            _ => {
+                let word = left
+                    .strip_prefix("count(")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .ok_or_else(err)?;
+                match normalize_words(word, tok).as_slice() {
+                    [w] => Metric::Count(w.clone()),
+                    _ => {
+                        return Err(format!(
+                            "--assert count() takes a single word, got {word:?}"
+                        ))
+                    }
+                }
+            }
+        };
+        Ok(Assertion {
+            spec: spec.trim().to_string(),
+            metric,
+            op,
+            value,
+        })
+    }
+
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    // 위반하면 사람이 읽을 메시지를 돌려준다.
+    pub fn check(&self, counts: &Counter) -> Result<(), String> {
+        let actual = match &self.metric {
+            Metric::Count(word) => counts.get(word),
+            Metric::Tokens => counts.iter().map(|(_, n)| n).sum(),
+            Metric::Distinct => counts.len(),
+        };
+        let ok = match self.op {
+            Op::Lt => actual < self.value,
+            Op::Le => actual <= self.value,
+            Op::Gt => actual > self.value,
+            Op::Ge => actual >= self.value,
+            Op::Eq => actual == self.value,
+            Op::Ne => actual != self.value,
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(format!("assertion failed: {} (actual {actual})", self.spec))
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// 여러 입력을 한 번에 돌릴 때의 실패 처리.
+// 파일 하나를 못 읽었다고 밤새 돈 배치 전체를 버리지 않도록, 입력이 둘 이상이면
+// 실패한 입력은 건너뛰고 기록만 한 뒤 마지막에 성공/실패 표를 stderr에 찍는다.
+// --fail-fast면 예전처럼 첫 실패에서 바로 중단한다. timing.rs처럼 전역 기록부를 쓴다.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static FAIL_FAST: AtomicBool = AtomicBool::new(false);
+// (입력 이름, 실패했다면 에러). 같은 입력을 두 번 읽는 모드(--per-chapter 등)가 있어
+// 이름마다 처음 한 번만 기록한다.
+static OUTCOMES: Mutex<Vec<(String, Option<String>)>> = Mutex::new(Vec::new());
+
+pub fn set_fail_fast(enabled: bool) {
+    FAIL_FAST.store(enabled, Ordering::Relaxed);
+}
+
+pub fn fail_fast() -> bool {
+    FAIL_FAST.load(Ordering::Relaxed)
+}
+
+// 입력별 결과에서 성공한 것만 남긴다. 입력이 하나뿐이거나 --fail-fast면 첫 에러를 그대로 돌려준다.
+pub fn settle<T>(results: Vec<(String, Result<T, String>)>) -> Result<Vec<(String, T)>, String> {
+    if results.len() <= 1 || FAIL_FAST.load(Ordering::Relaxed) {
+        return results
+            .into_iter()
+            .map(|(name, result)| result.map(|value| (name, value)))
+            .collect();
+    }
+    let total = results.len();
+    let mut kept = Vec::with_capacity(total);
+    let mut outcomes = OUTCOMES.lock().unwrap_or_else(|e| e.into_inner());
+    for (name, result) in results {
+        let error = result.as_ref().err().cloned();
+        if !outcomes.iter().any(|(n, _)| *n == name) {
+            outcomes.push((name.clone(), error));
+        }
+        if let Ok(value) = result {
+            kept.push((name, value));
+        }
+    }
+    if kept.is_empty() {
+        return Err(format!("none of the {total} inputs could be read"));
+    }
+    Ok(kept)
+}
+
+// 입력을 하나씩 열며 읽는 곳(input::stream)용 settle. 입력이 하나뿐이거나 --fail-fast면 에러를
+// 그대로 돌려주고, 아니면 기록만 하고 None(건너뛴다). total은 전체 입력 수다.
+pub fn settle_one<T>(
+    name: &str,
+    result: Result<T, String>,
+    total: usize,
+) -> Result<Option<T>, String> {
+    if total <= 1 || FAIL_FAST.load(Ordering::Relaxed) {
+        return result.map(Some);
+    }
+    let mut outcomes = OUTCOMES.lock().unwrap_or_else(|e| e.into_inner());
+    if !outcomes.iter().any(|(n, _)| n == name) {
+        outcomes.push((name.to_string(), result.as_ref().err().cloned()));
+    }
+    Ok(result.ok())
+}
+
+// 지금까지 읽지 못한 입력이 있었는지(표는 찍지 않는다).
+pub fn failed() -> bool {
+    let outcomes = OUTCOMES.lock().unwrap_or_else(|e| e.into_inner());
+    outcomes.iter().any(|(_, e)| e.is_some())
+}
+
+// 실패가 하나라도 있었으면 표를 찍고 true(호출한 쪽이 종료 코드를 1로 만든다).
+pub fn report() -> bool {
+    let outcomes = OUTCOMES.lock().unwrap_or_else(|e| e.into_inner());
+    let failed = outcomes.iter().filter(|(_, e)| e.is_some()).count();
+    if failed == 0 {
+        return false;
+    }
+    eprintln!("[Inputs]");
+    for (name, error) in outcomes.iter() {
+        match error {
+            None => eprintln!("ok      {name}"),
+            Some(e) => eprintln!("FAILED  {name}: {e}"),
+        }
+    }
+    eprintln!("{} ok, {failed} failed", outcomes.len() - failed);
+    true
+}
@@ -0,0 +1,93 @@
+// bursts 서브커맨드: 토큰 --window개짜리 미끄럼 창 안에서 전체 비율보다 훨씬 자주 나오는 단어.
+// 긴 녹취록이나 로그에서 잠깐 몰려 나왔다 사라지는 주제를 찾으려는 것이다.
+// 단어마다 창 안 횟수가 가장 큰 자리를 잡아 기댓값(창 길이 * 전체 비율)과 견주고,
+// RATIO배 이상이면서 MIN_COUNT번 이상 나온 단어만 남긴다. 불용어는 창을 재기 전에 뺀다(graph와 같다).
+
+use std::collections::HashMap;
+
+use crate::{normalize_words, Analysis};
+
+pub const RATIO: f64 = 3.0;
+pub const MIN_COUNT: usize = 3;
+
+pub struct Burst {
+    pub word: String,
+    // 가장 붐빈 창 안의 횟수.
+    pub count: usize,
+    pub expected: f64,
+    // 그 창 안에서 이 단어가 처음/마지막으로 나온 줄(1부터).
+    pub first_line: usize,
+    pub last_line: usize,
+}
+
+impl Burst {
+    pub fn ratio(&self) -> f64 {
+        self.count as f64 / self.expected
+    }
+}
+
+// 본문 전체가 창 하나에 들어가면 전체 비율과 같아지므로 아무것도 내지 않는다.
+// 비율이 큰 순서, 같으면 횟수가 큰 순서. analysis.top()개로 자른다(0이면 전체).
+pub fn find(text: &str, analysis: &Analysis, window: usize) -> Vec<Burst> {
+    let mut words: Vec<(String, usize)> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        for word in normalize_words(line, analysis.tokenizer()) {
+            if !analysis.is_stopword(&word) {
+                words.push((word, i + 1));
+            }
+        }
+    }
+    if words.len() <= window {
+        return Vec::new();
+    }
+    let mut totals: HashMap<&str, usize> = HashMap::new();
+    for (word, _) in &words {
+        *totals.entry(word).or_default() += 1;
+    }
+    // 단어마다 (가장 큰 창 안 횟수, 그 창의 마지막 토큰 위치).
+    let mut local: HashMap<&str, usize> = HashMap::new();
+    let mut best: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (i, (word, _)) in words.iter().enumerate() {
+        if i >= window {
+            if let Some(n) = local.get_mut(words[i - window].0.as_str()) {
+                *n -= 1;
+            }
+        }
+        let n = local.entry(word).or_default();
+        *n += 1;
+        let peak = best.entry(word).or_insert((0, i));
+        if *n > peak.0 {
+            *peak = (*n, i);
+        }
+    }
+
+    let total = words.len() as f64;
+    let mut found: Vec<Burst> = best
+        .into_iter()
+        .filter_map(|(word, (count, end))| {
+            let expected = window as f64 * totals[word] as f64 / total;
+            if count < MIN_COUNT || (count as f64) < RATIO * expected {
+                return None;
+            }
+            let start = (end + 1).saturating_sub(window);
+            let first = words[start..=end].iter().find(|(w, _)| w == word)?;
+            Some(Burst {
+                word: word.to_string(),
+                count,
+                expected,
+                first_line: first.1,
+                last_line: words[end].1,
+            })
+        })
+        .collect();
+    found.sort_by(|a, b| {
+        b.ratio()
+            .total_cmp(&a.ratio())
+            .then(b.count.cmp(&a.count))
+            .then_with(|| a.word.cmp(&b.word))
+    });
+    if analysis.top() > 0 {
+        found.truncate(analysis.top());
+    }
+    found
+}
@@ -0,0 +1,363 @@
+// --cache DIR: 같은 입력과 같은 옵션이면 지난 결과를 그대로 돌려준다.
+// Python이라면 joblib.Memory처럼 (입력 내용, 인자)의 해시를 키로 결과를 저장해 두는 방식이다.
+//   DIR/results/<sha256>.txt  분석 결과. 키는 도구 버전, 명령행 옵션, 각 입력과
+//                             옵션으로 준 파일(--dict 등)의 내용으로 만든다.
+//   DIR/counts.json           파일에서 읽는 기본 상위 단어 표의 파일별 빈도(증분 집계).
+// 증분 집계 쪽은 계속 덧붙기만 하는 수 GB짜리 로그를 매번 처음부터 토큰화하지 않으려는 것이다.
+// 형식은 {"tokenizer": "...", "files": [{"path", "bytes", "check", "counts": {단어: 수}}]}이고,
+// bytes까지 센 뒤 check(그 직전 4KB의 FNV-1a 해시)가 지금 파일과 같으면 앞부분은 그대로라고 본다.
+// 파일이 줄었거나(로테이션) check가 다르거나 토큰화 옵션이 바뀌었으면 처음부터 다시 센다.
+// 단어가 중간에 잘리지 않도록 마지막 줄바꿈까지만 저장하고, 끝의 덜 쓴 줄은 이번 결과에만 더한다.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::batch;
+use crate::cli::{Args, Command};
+use crate::counter::Counter;
+use crate::input::{self, Decoder, Source};
+use crate::interrupt;
+use crate::json::{self, Value};
+use crate::meta;
+use crate::output;
+use crate::sha256::Sha256;
+use crate::{count_words, Stem, Tokenizer};
+
+const CHECK: u64 = 4096;
+
+// 키에 넣지 않는 옵션(값을 받는 것은 true). 결과 내용을 바꾸지 않는다.
+const IGNORED: [(&str, bool); 12] = [
+    ("--cache", true),
+    ("--output", true),
+    ("--out", true),
+    ("--append", false),
+    ("--timing", false),
+    ("--resource-stats", false),
+    ("--summary", false),
+    ("--no-pager", false),
+    ("--fail-fast", false),
+    ("--journal", true),
+    ("--threads", true),
+    ("--hasher", true),
+];
+
+// 내용이 결과를 바꾸는 파일 옵션.
+const FILE_OPTIONS: [&str; 11] = [
+    "--stopwords-file",
+    "--keep-file",
+    "--only-words",
+    "--script",
+    "--dict",
+    "--reference",
+    "--flag-list",
+    "--baseline",
+    "--config",
+    "--template",
+    "--wordlist",
+];
+
+// 파일에서 읽는 기본 상위 단어 표. 이때는 결과 캐시 대신 counts.json으로 증분 집계한다.
+// --stem auto는 덧붙은 꼬리만 보고 언어를 다시 고르게 되므로, --script는 counts.json이 스크립트
+// 내용을 모르므로, --min-df/--max-df는 파일마다 따로 세야 하므로, --by paragraph는 문단 본문이
+// 있어야 하므로 뺀다.
+pub fn incremental(args: &Args) -> bool {
+    args.command == Command::Count
+        && !args.log
+        && matches!(args.decoder, Decoder::Plain)
+        && args.char_ngrams.is_none()
+        && args.min_df.is_none()
+        && args.max_df.is_none()
+        && args.speaker.is_none()
+        && args.bucket.is_none()
+        && !args.by_paragraph
+        && !args.filter.is_active()
+        && !(args.proper_nouns || args.acronyms || args.urls || args.emails)
+        && args.flag_list.is_none()
+        && args.export_stopwords.is_none()
+        && !args.recursive
+        && args.tokenizer.stem != Stem::Auto
+        && args.script.is_none()
+        && matches!(&args.source, Source::Files(paths) if !paths.iter().any(|p| p == "-"))
+}
+
+// 결과를 저장해도 되는 명령. 대화형/상주 명령, 디렉터리를 훑는 todos, lint-ws와 -r,
+// 종료 코드로 결과를 알리는 drift, 파일을 따로 쓰는 replace, gen-corpus, --export-stopwords와 --convert,
+// 수정 시각을 싣는 --provenance는 매번 실제로 돌린다. 난수를 쓰는 sample-words, generate와
+// --sample-matches도 --seed가 없으면 실행마다 결과가 달라야 하므로 저장하지 않는다.
+fn cacheable(args: &Args) -> bool {
+    let random = matches!(args.command, Command::SampleWords | Command::Generate)
+        || args.filter.sample_matches.is_some();
+    if random && args.seed.is_none() {
+        return false;
+    }
+    !matches!(
+        args.command,
+        Command::Pick
+            | Command::Daemon
+            | Command::Todos
+            | Command::LintWs
+            | Command::NearDupes
+            | Command::Replace
+            | Command::GenCorpus
+            | Command::Split
+            | Command::CompareImpl
+            | Command::Drift
+            | Command::Cache
+            | Command::Stopwords
+            | Command::SelfUpdate
+    ) && args.export_stopwords.is_none()
+        && args.analyzers.is_empty()
+        && !args.format.is_columnar()
+        && !args.provenance
+        && args.convert.is_none()
+        && !args.recursive
+        && !incremental(args)
+}
+
+// --cache가 있으면 결과 캐시를 거쳐 run을 부른다. 입력을 하나라도 못 읽었으면 저장하지 않는다.
+pub fn cached(
+    args: &Args,
+    out: &mut String,
+    run: impl FnOnce(&Args, &mut String) -> Result<(), String>,
+) -> Result<(), String> {
+    let Some(dir) = args.cache.as_deref().filter(|_| cacheable(args)) else {
+        return run(args, out);
+    };
+    let Some(key) = key(args) else {
+        return run(args, out);
+    };
+    let path = Path::new(dir).join("results").join(format!("{key}.txt"));
+    if let Ok(saved) = std::fs::read_to_string(&path) {
+        out.push_str(&saved);
+        return Ok(());
+    }
+    run(args, out)?;
+    // 건너뛴 입력이 있거나 도중에 끊긴 결과는 저장하지 않는다.
+    if !batch::failed() && !interrupt::interrupted() {
+        std::fs::create_dir_all(Path::new(dir).join("results"))
+            .map_err(|e| format!("failed to create cache {dir}: {e}"))?;
+        output::write(&path.display().to_string(), out, false)?;
+    }
+    Ok(())
+}
+
+// 못 읽는 입력이 있으면 None(캐시를 건너뛰고 실제로 돌려 평소처럼 에러를 낸다).
+fn key(args: &Args) -> Option<String> {
+    let mut hasher = Sha256::new();
+    let mut field = |bytes: &[u8]| {
+        // 길이를 앞에 붙여 ("ab","c")와 ("a","bc")가 같은 키가 되지 않게 한다.
+        hasher.update(&(bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+    };
+    field(env!("CARGO_PKG_VERSION").as_bytes());
+    // --color auto는 출력이 터미널인지에 따라 결과가 달라진다.
+    field(if args.color { b"color" } else { b"plain" });
+    // 터미널 폭에 맞춰 자른 줄도 마찬가지다.
+    field(&args.truncate.unwrap_or(0).to_le_bytes());
+    let argv = &args.argv;
+    let mut i = 0;
+    while i < argv.len() {
+        let arg = argv[i].as_str();
+        if let Some((_, takes_value)) = IGNORED.iter().find(|(flag, _)| *flag == arg) {
+            i += if *takes_value { 2 } else { 1 };
+            continue;
+        }
+        field(arg.as_bytes());
+        if FILE_OPTIONS.contains(&arg) {
+            if let Some(path) = argv.get(i + 1) {
+                field(path.as_bytes());
+                field(&std::fs::read(path).ok()?);
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+    for (name, data) in input::read_parts(&args.source) {
+        field(name.as_bytes());
+        field(&data.ok()?);
+        // report는 입력 옆의 .meta.toml도 읽으므로 그 내용이 바뀌어도 결과가 바뀐다.
+        if args.command == Command::Report {
+            if let Some(path) = meta::sidecar(&name) {
+                field(&std::fs::read(path).ok()?);
+            }
+        }
+    }
+    Some(hasher.hex_digest())
+}
+
+// cache clear: 이 도구가 만든 파일만 지운다(DIR 자체나 다른 파일은 건드리지 않는다).
+pub fn clear(dir: &str) -> Result<usize, String> {
+    let fail = |e: std::io::Error| format!("failed to clear cache {dir}: {e}");
+    let mut removed = 0;
+    let results = Path::new(dir).join("results");
+    if results.is_dir() {
+        for entry in std::fs::read_dir(&results).map_err(fail)? {
+            let path = entry.map_err(fail)?.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                std::fs::remove_file(&path).map_err(fail)?;
+                removed += 1;
+            }
+        }
+        let _ = std::fs::remove_dir(&results);
+    }
+    let counts = Path::new(dir).join("counts.json");
+    if counts.exists() {
+        std::fs::remove_file(&counts).map_err(fail)?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+// counts.json의 파일 하나.
+struct Entry {
+    path: String,
+    bytes: u64,
+    check: String,
+    counts: Counter,
+}
+
+// 증분 집계. 여러 파일의 빈도를 합쳐 돌려주고 counts.json을 새로 쓴다.
+pub fn count(paths: &[String], tok: Tokenizer, dir: &str) -> Result<Counter, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("failed to create cache {dir}: {e}"))?;
+    let cache = Path::new(dir).join("counts.json").display().to_string();
+    let cache = cache.as_str();
+    let signature = format!("{tok:?}");
+    let mut entries = load(cache, &signature)?;
+    let results = paths
+        .iter()
+        .map(|path| {
+            let previous = entries.iter().find(|e| e.path == *path);
+            let result =
+                update(path, previous, tok).map_err(|e| format!("failed to read file {path}: {e}"));
+            (path.clone(), result)
+        })
+        .collect::<Vec<_>>();
+    let mut total = Counter::new();
+    // 이번에 읽지 못한 파일의 예전 기록은 버리지 않고 남겨 둔다.
+    let counted = batch::settle(results)?;
+    entries.retain(|e| !counted.iter().any(|(path, _)| *path == e.path));
+    for (_, (entry, tail)) in counted {
+        for (word, n) in entry.counts.iter().chain(tail.iter()) {
+            total.add(word, n);
+        }
+        entries.push(entry);
+    }
+    save(cache, &signature, &entries)?;
+    Ok(total)
+}
+
+// (저장할 기록, 마지막 줄바꿈 뒤의 덜 쓴 꼬리 빈도).
+fn update(
+    path: &str,
+    previous: Option<&Entry>,
+    tok: Tokenizer,
+) -> std::io::Result<(Entry, Counter)> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let (start, mut counts) = match previous {
+        Some(e) if e.bytes <= len && fingerprint(&mut file, e.bytes)? == e.check => {
+            (e.bytes, e.counts.clone())
+        }
+        _ => (0, Counter::new()),
+    };
+    file.seek(SeekFrom::Start(start))?;
+    let mut suffix = Vec::new();
+    file.read_to_end(&mut suffix)?;
+    let suffix = String::from_utf8(suffix).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        )
+    })?;
+    let boundary = suffix.rfind('\n').map_or(0, |i| i + 1);
+    for (word, n) in count_words(&suffix[..boundary], tok).iter() {
+        counts.add(word, n);
+    }
+    let tail = count_words(&suffix[boundary..], tok);
+    let bytes = start + boundary as u64;
+    let entry = Entry {
+        path: path.to_string(),
+        bytes,
+        check: fingerprint(&mut file, bytes)?,
+        counts,
+    };
+    Ok((entry, tail))
+}
+
+// end 직전 최대 CHECK바이트의 FNV-1a 64비트 해시(16진수). 파일 전체를 읽지 않고 바뀌었는지 본다.
+fn fingerprint(file: &mut File, end: u64) -> std::io::Result<String> {
+    let start = end.saturating_sub(CHECK);
+    file.seek(SeekFrom::Start(start))?;
+    let mut window = Vec::new();
+    file.take(end - start).read_to_end(&mut window)?;
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in window {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    Ok(format!("{hash:016x}"))
+}
+
+// 캐시가 없으면 빈 목록. 토큰화 옵션이 다르면 저장된 빈도를 쓸 수 없으므로 역시 빈 목록.
+fn load(cache: &str, signature: &str) -> Result<Vec<Entry>, String> {
+    let text = match std::fs::read_to_string(cache) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("failed to read cache {cache}: {e}")),
+    };
+    let invalid = || format!("{cache}: not a --cache counts file");
+    let root = json::parse(&text).map_err(|e| format!("{cache}: {e}"))?;
+    if root.get("tokenizer").and_then(Value::as_str) != Some(signature) {
+        return Ok(Vec::new());
+    }
+    let files = root
+        .get("files")
+        .and_then(Value::as_array)
+        .ok_or_else(invalid)?;
+    let mut entries = Vec::with_capacity(files.len());
+    for file in files {
+        let field = |key: &str| file.get(key).ok_or_else(invalid);
+        let mut counts = Counter::new();
+        let Value::Object(words) = field("counts")? else {
+            return Err(invalid());
+        };
+        for (word, n) in words {
+            counts.add(word, n.as_f64().ok_or_else(invalid)? as usize);
+        }
+        entries.push(Entry {
+            path: field("path")?.as_str().ok_or_else(invalid)?.to_string(),
+            bytes: field("bytes")?.as_f64().ok_or_else(invalid)? as u64,
+            check: field("check")?.as_str().ok_or_else(invalid)?.to_string(),
+            counts,
+        });
+    }
+    Ok(entries)
+}
+
+fn save(cache: &str, signature: &str, entries: &[Entry]) -> Result<(), String> {
+    let files = entries
+        .iter()
+        .map(|e| {
+            let counts = e
+                .counts
+                .iter()
+                .map(|(word, n)| (word.to_string(), Value::Number(n as f64)))
+                .collect();
+            Value::Object(vec![
+                ("path".to_string(), Value::String(e.path.clone())),
+                ("bytes".to_string(), Value::Number(e.bytes as f64)),
+                ("check".to_string(), Value::String(e.check.clone())),
+                ("counts".to_string(), Value::Object(counts)),
+            ])
+        })
+        .collect();
+    let root = Value::Object(vec![
+        (
+            "tokenizer".to_string(),
+            Value::String(signature.to_string()),
+        ),
+        ("files".to_string(), Value::Array(files)),
+    ]);
+    output::write(cache, &(json::to_line(&root) + "\n"), false)
+}
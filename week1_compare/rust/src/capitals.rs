@@ -0,0 +1,125 @@
+// 소문자로 바꾸기 전의 원문에서 대문자 표기를 보고 뽑는 보고서.
+// 문장 중간에 대문자로 시작하는 단어는 대개 사람/장소/제품 이름이다(--proper-nouns).
+// 문장 첫 단어는 원래 대문자라 구분할 수 없으므로 건너뛴다.
+// 전부 대문자인 2~6글자 단어는 약어로 따로 센다(--acronyms). 용어집 초안을 만들 때 쓴다.
+// Python이라면 spaCy의 NER을 쓰겠지만, 여기서는 표기만 보는 값싼 근사다.
+
+use std::collections::HashMap;
+
+use crate::counter::Counter;
+use crate::Tokenizer;
+
+// 원래 대소문자를 유지한 단어와, 그 단어가 문장 첫 단어인지.
+fn raw_words(text: &str, tok: Tokenizer) -> Vec<(&str, bool)> {
+    let is_word = |c: char| {
+        if tok.unicode {
+            c.is_alphabetic()
+        } else {
+            c.is_ascii_alphabetic()
+        }
+    };
+    let mut words = Vec::new();
+    let mut sentence_start = true;
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        if is_word(c) {
+            start.get_or_insert(i);
+            continue;
+        }
+        if let Some(s) = start.take() {
+            words.push((&text[s..i], sentence_start));
+            sentence_start = false;
+        }
+        if matches!(c, '.' | '!' | '?') {
+            sentence_start = true;
+        }
+    }
+    if let Some(s) = start {
+        words.push((&text[s..], sentence_start));
+    }
+    words
+}
+
+// 첫 글자만 대문자이고 소문자가 섞인 두 글자 이상 단어("Seoul", "McDonald").
+// "I"나 "NASA" 같은 전부 대문자 단어는 제외한다.
+fn is_capitalized(word: &str) -> bool {
+    let mut chars = word.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    first.is_uppercase() && chars.any(|c| c.is_lowercase())
+}
+
+pub fn proper_nouns(text: &str, tok: Tokenizer) -> Counter {
+    let mut counts = Counter::new();
+    for (word, sentence_start) in raw_words(text, tok) {
+        if !sentence_start && is_capitalized(word) {
+            counts.add(word, 1);
+        }
+    }
+    counts
+}
+
+// "API", "HTTP", "UTF"처럼 전부 대문자인 2~6글자. 문장 위치와 상관없이 센다.
+fn is_acronym(word: &str) -> bool {
+    let len = word.chars().count();
+    (2..=6).contains(&len) && word.chars().all(|c| c.is_uppercase())
+}
+
+pub fn acronyms(text: &str, tok: Tokenizer) -> Counter {
+    let mut counts = Counter::new();
+    for (word, _) in raw_words(text, tok) {
+        if is_acronym(word) {
+            counts.add(word, 1);
+        }
+    }
+    counts
+}
+
+// stats --case: 한 단어가 어떤 표기로 쓰였는지. 제목에만 대문자로 쓰였는지, 본문에서
+// 전부 대문자로 "외치는"지를 스타일 가이드 점검에서 본다.
+#[derive(Debug, Default)]
+pub struct CaseForms {
+    pub word: String,
+    pub lower: usize,
+    // "Seoul". 한 글자 대문자("I", "A")도 여기 센다.
+    pub capitalized: usize,
+    // 두 글자 이상 전부 대문자("NASA").
+    pub upper: usize,
+    // 그 밖의 섞인 표기("iPhone", "McDonald"는 capitalized).
+    pub mixed: usize,
+}
+
+impl CaseForms {
+    pub fn total(&self) -> usize {
+        self.lower + self.capitalized + self.upper + self.mixed
+    }
+}
+
+// 소문자로 접은 단어마다 표기별 횟수. 많이 나온 단어부터, 같으면 알파벳순.
+pub fn case_forms(text: &str, tok: Tokenizer) -> Vec<CaseForms> {
+    let mut forms: Vec<CaseForms> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (word, _) in raw_words(text, tok) {
+        let key = word.to_lowercase();
+        let i = *index.entry(key.clone()).or_insert_with(|| {
+            forms.push(CaseForms {
+                word: key,
+                ..CaseForms::default()
+            });
+            forms.len() - 1
+        });
+        let entry = &mut forms[i];
+        if word.chars().all(|c| !c.is_uppercase()) {
+            entry.lower += 1;
+        } else if word.chars().count() > 1 && word.chars().all(|c| !c.is_lowercase()) {
+            entry.upper += 1;
+        } else if is_capitalized(word) || word.chars().count() == 1 {
+            entry.capitalized += 1;
+        } else {
+            entry.mixed += 1;
+        }
+    }
+    forms.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.word.cmp(&b.word)));
+    forms
+}
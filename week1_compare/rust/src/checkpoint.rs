@@ -0,0 +1,161 @@
+// --checkpoint PATH / --resume PATH: 몇 시간씩 도는 말뭉치 집계를 중간에 끊겨도 이어 가게 한다.
+// 파일을 하나씩 세면서 EVERY마다 지금까지의 빈도와 다음에 읽을 파일 번호를 PATH에 쓰고,
+// --resume은 그 파일을 읽어 남은 파일부터 센다. 끝까지 세면 PATH를 지운다.
+// Ctrl-C나 SIGTERM으로 멈출 때도 그때까지를 저장한다(interrupt.rs).
+// 입력 목록이나 토큰화/디코딩 옵션이 저장할 때와 다르면 합친 빈도가 뜻이 없으므로 거절한다.
+// 저장은 PATH.tmp에 쓴 뒤 이름을 바꾸므로 쓰는 도중에 끊겨도 앞의 체크포인트가 남는다.
+
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::batch;
+use crate::cli::Args;
+use crate::count_words;
+use crate::counter::Counter;
+use crate::input::{self, Source};
+use crate::interrupt;
+use crate::json::{self, Value};
+use crate::script;
+use crate::search;
+use crate::trailer;
+
+const EVERY: Duration = Duration::from_secs(30);
+
+struct State {
+    // 다음에 셀 파일의 번호.
+    cursor: usize,
+    counts: Counter,
+}
+
+// 빈도 합계. files는 셀 순서대로의 입력 파일(-r이면 펼친 목록)이다.
+pub fn count(files: &[String], args: &Args, path: &str, resume: bool) -> Result<Counter, String> {
+    let signature = format!("{:?} {:?}", args.tokenizer, args.decoder);
+    let mut state = if resume {
+        load(path, &signature, files)?
+    } else {
+        State {
+            cursor: 0,
+            counts: Counter::new(),
+        }
+    };
+    let mut outcomes = Vec::new();
+    let mut saved = Instant::now();
+    while state.cursor < files.len() {
+        // 신호를 받으면 여기까지를 저장해 두고 읽은 데까지의 빈도를 낸다. --resume으로 이어 간다.
+        if interrupt::interrupted() {
+            save(path, &signature, files, &state)?;
+            interrupt::skipped(files.len() - state.cursor);
+            break;
+        }
+        let name = &files[state.cursor];
+        match read(name, args) {
+            Ok(text) => {
+                for (word, n) in count_words(&text, args.tokenizer).iter() {
+                    state.counts.add(word, n);
+                }
+                outcomes.push((name.clone(), Ok(())));
+            }
+            Err(e) if batch::fail_fast() => {
+                save(path, &signature, files, &state)?;
+                return Err(e);
+            }
+            Err(e) => outcomes.push((name.clone(), Err(e))),
+        }
+        state.cursor += 1;
+        if saved.elapsed() >= EVERY && state.cursor < files.len() {
+            save(path, &signature, files, &state)?;
+            saved = Instant::now();
+        }
+    }
+    if !outcomes.is_empty() {
+        batch::settle(outcomes)?;
+    }
+    if state.cursor < files.len() {
+        return Ok(state.counts);
+    }
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            return Err(format!("failed to remove checkpoint {path}: {e}"));
+        }
+        _ => {}
+    }
+    Ok(state.counts)
+}
+
+// -r은 트리 입력 규칙(바이너리는 빈 본문, HTML/Markdown은 본문만), 아니면 --decoder 규칙으로 읽는다.
+fn read(name: &str, args: &Args) -> Result<String, String> {
+    if args.recursive {
+        let path = std::path::PathBuf::from(name);
+        let (_, text) = search::read(&[path], None)
+            .pop()
+            .ok_or_else(|| format!("failed to read file {name}"))?;
+        trailer::files(1);
+        return script::keep_lines(text?.unwrap_or_default());
+    }
+    let source = Source::Files(vec![name.to_string()]);
+    let documents = input::read_documents(&source, &args.decoder)?;
+    Ok(input::join_documents(&documents))
+}
+
+fn load(path: &str, signature: &str, files: &[String]) -> Result<State, String> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("failed to read checkpoint {path}: {e}"))?;
+    let invalid = || format!("{path}: not a --checkpoint file");
+    let root = json::parse(&text).map_err(|e| format!("{path}: {e}"))?;
+    if root.get("signature").and_then(Value::as_str) != Some(signature) {
+        return Err(format!(
+            "{path}: saved with different tokenizer or input options; rerun with --checkpoint"
+        ));
+    }
+    let saved: Vec<&str> = root
+        .get("inputs")
+        .and_then(Value::as_array)
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|v| v.as_str().ok_or_else(invalid))
+        .collect::<Result<_, _>>()?;
+    if saved != files {
+        return Err(format!(
+            "{path}: the input files changed since the checkpoint; rerun with --checkpoint"
+        ));
+    }
+    let cursor = root
+        .get("cursor")
+        .and_then(Value::as_f64)
+        .ok_or_else(invalid)? as usize;
+    let Some(Value::Object(words)) = root.get("counts") else {
+        return Err(invalid());
+    };
+    let mut counts = Counter::new();
+    for (word, n) in words {
+        counts.add(word, n.as_f64().ok_or_else(invalid)? as usize);
+    }
+    Ok(State {
+        cursor: cursor.min(files.len()),
+        counts,
+    })
+}
+
+fn save(path: &str, signature: &str, files: &[String], state: &State) -> Result<(), String> {
+    let counts = state
+        .counts
+        .iter()
+        .map(|(word, n)| (word.to_string(), Value::Number(n as f64)))
+        .collect();
+    let root = Value::Object(vec![
+        (
+            "signature".to_string(),
+            Value::String(signature.to_string()),
+        ),
+        (
+            "inputs".to_string(),
+            Value::Array(files.iter().cloned().map(Value::String).collect()),
+        ),
+        ("cursor".to_string(), Value::Number(state.cursor as f64)),
+        ("counts".to_string(), Value::Object(counts)),
+    ]);
+    let fail = |e: std::io::Error| format!("failed to write checkpoint {path}: {e}");
+    let temporary = format!("{path}.tmp");
+    fs::write(&temporary, json::to_line(&root)).map_err(fail)?;
+    fs::rename(&temporary, path).map_err(fail)
+}
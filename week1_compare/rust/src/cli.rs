@@ -0,0 +1,2764 @@
+// 명령행 인자 파싱.
+// Python argparse와 달리 라이브러리 없이 직접 파싱하며, 서브커맨드(`keywords` 등)는
+// 첫 번째 위치 인자로 구분한다. 서브커맨드가 없으면 기존 top words/filter 모드다.
+
+use std::collections::HashSet;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::time::{Duration, SystemTime};
+
+use crate::alert;
+use crate::assertion::Assertion;
+use crate::collate::Collation;
+use crate::config::{self, Config};
+use crate::corpus;
+use crate::counter::Hasher;
+use crate::crosscheck;
+use crate::diagnostic::Diagnostic;
+use crate::docfreq::Bound;
+use crate::encoding::Encoding;
+use crate::environment;
+use crate::filter::{FileList, LineFilter, Near};
+use crate::format::Format;
+use crate::highlight::ColorMode;
+use crate::input::{Decoder, Source};
+use crate::limits::Rate;
+use crate::logline::LevelRange;
+use crate::meta;
+use crate::numfmt::NumberFormat;
+use crate::preset;
+use crate::query;
+use crate::regex;
+use crate::render::{Layout, Template};
+use crate::sandbox::Root;
+use crate::script::{self, Script};
+use crate::sql::{self, Statement};
+use crate::stats::Ending;
+use crate::subtitles;
+use crate::term;
+use crate::timefmt::TimeFormat;
+use crate::walk::{self, WalkOptions};
+use crate::wordlist;
+use crate::xml::ElementPath;
+use crate::{is_glob, normalize_words};
+use crate::{
+    Analysis, Case, Contractions, Hyphens, Normalization, Numbers, SortOrder, TieBreak, Tokenizer,
+};
+use crate::{Language, Stem, Step};
+
+pub const USAGE: &str = "\
+usage: cargo run -- [COMMAND] <file>... [options]
+       cargo run -- [COMMAND] --clipboard [options]
+
+files are analyzed together in the order given; - reads stdin at that position.
+unreadable files are skipped and listed on stderr at the end (exit status 1)
+with several files, Ctrl-C or SIGTERM stops after the current file and prints the partial
+results marked incomplete (exit status 130)
+WORDTOOL_<OPTION> environment variables set options below --config and the command line:
+WORDTOOL_TOP, FORMAT, COLOR, CONFIG, LANG, CASE, STEM, SORT, TIE_BREAK, NUMBER_FORMAT,
+STOPWORDS_FILE, THREADS, HASHER, MAX_VOCAB, SEED, CACHE; switches UNICODE, PLAIN, NO_PAGER (1/0);
+for daemon also SOCKET, METRICS, RATE_LIMIT, and for daemon and --jsonrpc ROOT, MAX_REQUEST.
+a variable is skipped where its option does not apply (FORMAT for stats, PLAIN for keywords, ...)
+
+commands:
+  (none)      top words, plus filtered lines when a filter option is given
+  keywords    RAKE key phrases (stopword-delimited candidates)
+  summarize   extractive summary: top-scoring sentences in document order
+  generate    synthetic text from a word-level Markov chain built from the input
+  gen-corpus  reproducible Zipf-distributed benchmark text written to PATH (- = stdout)
+  split       cut the input on line boundaries into --by-tokens N chunks written to
+              PREFIX.001, PREFIX.002, ... (--stratify: same keyword mix in every chunk)
+  compare-impl
+              run python/word_count.py and this program on FILE; check the outputs match
+              and compare wall time and peak RSS
+  sample-words
+              --n random distinct words, weighted by frequency (--uniform: equally likely)
+  spellcheck  words missing from --dict, with edit-distance suggestions
+  suggest     probable typos: rare words within edit distance 1-2 of a 10x more frequent word
+  anagrams    groups of distinct words made of the same letters
+  weblog      Apache/Nginx access log: top paths, status codes, user agents
+  stats       line/token/vocabulary totals and mean word length
+  compare     keyness vs --reference: words over/under-represented (log-likelihood G2)
+  zipf        rank/frequency table with log-log columns and fitted Zipf exponent
+  social      #hashtags and @mentions, counted apart from the remaining words
+  trend       counts of --word in each of --buckets equal slices of the text
+  heatmap     share of lines matching the filter options in each of --buckets line ranges
+  merge       combined top words from count files saved with --all --format csv
+  subtract    words of file A that are absent from file B (or rarer than --below N)
+  intersect   words found in every given file, with per-file counts
+  report      per-file token count, top words, and filter matches (--format json for CI)
+  query       run --sql over the word counts as table words(rank, word, count)
+  positions   line, column, and byte offset of every occurrence of --word (--format json)
+  graph       co-occurrence network of the top words (--format dot for Graphviz, gexf for Gephi)
+  bursts      words far more frequent within some --window tokens than in the whole text
+  rank        count, rank, and percentile of each --word (repeatable) among all counted words
+  difficulty  share of tokens and words at each level of a --wordlist (CEFR A1-C2 etc.),
+              plus the most frequent unlisted words (--format json)
+  todos       TODO/FIXME/HACK markers in files or directory trees, by marker and by file
+  near-dupes  paragraph pairs in different files (or directory trees) whose word --shingle
+              sets overlap at least --threshold (Jaccard, default 0.8); directories are
+              walked as for todos (--ext, --hidden, --follow-symlinks, --modified-since)
+  lint-ws     trailing whitespace and runs of 3+ blank lines per file (--fix writes FILE.clean)
+  replace     preview --from REGEX --to TEXT on each line as a -/+ diff (--write: FILE.replaced)
+  drift       top words whose relative frequency moved past --threshold vs --baseline (exit 1)
+  pick        interactive fuzzy finder over the lines; prints the chosen line to stdout
+  stopwords list
+              print the built-in stopword list (--lang en)
+  stopwords diff FILE
+              compare a --stopwords-file list with the built-in one, as normalized for counting
+  cache clear remove the results saved under --cache DIR
+  daemon      keep the inputs tokenized in memory; answer NDJSON queries on --socket
+  self-update download the newest release from --release-url, verify it, and replace this
+              executable (--check: only report whether one is available)
+
+options:
+  --top N                    number of rows to print (default 5, 0 = all)
+  --all                      print the full ranking (same as --top 0)
+  --format text|csv|json|markdown|html|parquet|arrow|dot|gexf
+                             output format: csv for top words, merge, zipf, query; json for top
+                             words, merge, report; markdown (tables, filtered lines fenced)
+                             and a single-file html report (sortable table, bar chart,
+                             filtered lines) for top words and merge; parquet and arrow (IPC
+                             file, also feather) write the top words table for pandas/Polars,
+                             the whole ranking unless --top is given, to --output or a pipe;
+                             dot and gexf for graph
+  --provenance               with json or html output of top words, merge, and report: add each
+                             input's size, SHA-256, and modification time (json top words and
+                             merge become an object {inputs, tables})
+  --tag TAG                  with report: keep files whose FILE.meta.toml sidecar lists TAG
+                             in tags (repeat to require several)
+  --since DATE, --until DATE with report: keep files whose sidecar date is in the range
+                             (inclusive; 2020, 2020-05, or 2020-05-17)
+  --group-by tag|author|year with report: one entry per sidecar tag, author, or year with
+                             summed tokens and matches and top words of the combined files
+  --template FILE            render top words and merge with FILE: {{word}}, {{count}}, {{rank}}
+                             per row, {{#header}}...{{/header}} and {{#footer}}...{{/footer}}
+  --table                    with text output of top words and merge: aligned columns with a
+                             header row and right-aligned counts
+  --only top|filter|stats    with default text output: print just these sections (comma-separated,
+                             e.g. --only filter,stats); stats adds line/token/vocabulary totals
+  --no-top                   with default text output: leave out the [Top words] section
+  --plain                    with text output of top words and merge: data rows only, without
+                             [section] headers, blank separator lines, or colors
+  --rank                     with --table: add a rank column
+  --padding N                with --table: spaces between columns (default 2)
+  --sentences N              sentences to keep for summarize (default 5)
+  --words N                  words to emit for generate (default 200)
+  --order N                  with generate: preceding words the next word depends on (default 2)
+  --n N                      with sample-words: words to draw (default 50)
+  --uniform                  with sample-words: draw from the vocabulary ignoring frequency
+  --size SIZE                with gen-corpus: bytes to write (4096, 512K, 10M, 1G; default 10M)
+  --vocab N                  with gen-corpus: distinct words to draw from (default 50000)
+  --zipf S                   with gen-corpus: Zipf exponent, rank k drawn as 1/k^S (default 1.1)
+  --python CMD               with compare-impl: Python interpreter (default python3)
+  --python-script PATH       with compare-impl: script to run (default the bundled word_count.py)
+  --runs N                   with compare-impl: runs per side, fastest kept (default 3)
+  --baseline PATH            with drift: saved report --all --format json output
+  --threshold F              with drift: relative change that counts as drift (default 0.2);
+                             with near-dupes: minimum paragraph similarity, 0-1 (default 0.8)
+  --shingle N                with near-dupes: words per shingle (default 5)
+  --by-tokens N              with split: tokens per chunk
+  --stratify WORD,...        with split: spread the lines containing each WORD (and the rest)
+                             evenly over the chunks instead of cutting in order
+  --prefix PATH              with split: chunk file prefix (default: the input file path)
+  --socket PATH              with daemon: Unix socket to listen on
+  --metrics ADDR             with daemon: serve Prometheus metrics at http://ADDR/metrics
+  --rate-limit N/PERIOD      with daemon: at most N requests per client (socket peer uid) per
+                             PERIOD (s, m, h, or a duration like 10s); extra ones get status 429
+  --max-request SIZE         with daemon: largest request line (default 1M); larger get status 413;
+                             with --jsonrpc: largest Content-Length, larger get an error reply
+  --release-url URL          with self-update: release server (URL/latest, URL/VERSION/SHA256SUMS)
+  --pubkey KEY               with self-update: minisign public key for SHA256SUMS.minisig
+  --check                    with self-update: report the newest version without installing it
+  --root DIR                 with daemon or --jsonrpc: refuse to read files outside DIR
+                             (.. and symlinks are resolved before the check)
+  --word WORD                word to follow for trend, to locate for positions, or to look up
+                             for rank (repeat for several)
+  --buckets N                slices for trend and heatmap (default 10)
+  --window N                 with graph: words at most N tokens apart co-occur (default 5);
+                             graph keeps the top 30 words unless --top is given;
+                             with bursts: sliding window length in tokens (default 500)
+  --below N                  with subtract: keep words seen fewer than N times in B (default 1)
+  --dict PATH                word list for spellcheck (one word per line)
+  --wordlist PATH            difficulty: CSV with word and level columns (e.g. word,level)
+  --reference PATH           reference corpus for compare
+  --contains KEYWORD         keep lines containing KEYWORD (case-insensitive; repeat for any-of)
+  --match-stems              with --contains and --stem: match keywords by stem like counting
+                             does, so run finds running and runs
+  --query EXPR               boolean filter, e.g. '(error OR warn) AND NOT timeout'
+  --phrase TEXT              keep lines containing TEXT as a token sequence
+  --near WORD,WORD,N         keep lines where both words occur within N tokens
+  --regex PATTERN            keep lines matching PATTERN ((?i) prefix ignores case)
+  -o, --only-matching        print each --regex or --contains match instead of the whole line
+  --replace TEMPLATE         with -o and --regex: print TEMPLATE per match ($1, ${2}, $0, $$)
+  --count-matching           count top words over the lines kept by the filter options only
+                             (instead of printing those lines)
+  --clipboard                read the text from the system clipboard instead of a file
+  --char-ngrams N             count character N-grams (spaces shown as _) instead of words
+  --min-df N|RATIO           with several input files: drop words found in fewer than N files
+                             (0.1 = 10% of them)
+  --max-df N|RATIO           with several input files: drop words found in more than N files
+                             (0.9 = 90% of them)
+  --proper-nouns             also report capitalized words seen mid-sentence (names, places)
+  --acronyms                 also report all-caps words of 2-6 letters (API, HTTP)
+  --urls                     also report URLs (http://, https://, ftp://, www.) with counts
+  --emails                   also report email addresses (lowercased) with counts
+  --external-analyzer CMD    also report the tables CMD prints: it reads one NDJSON record per
+                             input line (file, line, text, tokens) on stdin and writes
+                             table/word/count records to stdout (repeatable; no shell)
+  --lang en|de|fr|ko         locale for --number-format separators; with stopwords, the built-in
+                             list to show or compare against (only en)
+  --number-format grouped|plain[:N]
+                             numbers in text, markdown, stats, weblog, drift, heatmap output:
+                             grouped adds thousands separators (1,234,567); :N sets decimals
+  --stopwords-file PATH      leave the words listed in PATH (one per line) out of top words;
+                             lines with * or ? are patterns (http*, *_id) matched per token
+  --keep-file PATH           rank only the words listed in PATH (same format, patterns too)
+  --only-words PATH          top words: count only the vocabulary in PATH and list every word
+                             in it, with 0 for words that never appear (all rows unless --top)
+  --cache DIR                reuse saved results when the inputs and options are unchanged;
+                             for top words, files that only grew are counted from where the
+                             last run stopped
+  --no-cache                 ignore --cache (always analyze, save nothing)
+  --checkpoint PATH          top words over many files: count one file at a time and save the
+                             counts so far and the next file to PATH every 30s (removed when done)
+  --resume PATH              continue an interrupted --checkpoint run from PATH, with the same
+                             input files and options (and keep checkpointing to PATH)
+  --export-stopwords PATH    write the printed top words to PATH in --stopwords-file format
+  --with-positions           add each top word's first and last line number (single input)
+  --flag-list PATH           report each occurrence (LINE: word) of words listed in PATH
+  --length-histogram         with stats: bar chart of token counts by word length
+  --whitespace               with stats: tab vs space indentation per file, indentation
+                             widths, and lines that mix tabs and spaces
+  --longest-lines N          with stats: the N longest lines with file, line number, length
+  --line-endings             with stats: LF, CRLF and CR counts per file; flags mixed files
+  --convert lf|crlf          with --line-endings: rewrite each file in place (atomically)
+                             with every line ending converted
+  --case                     with stats (no value): how often each word is written lowercase,
+                             Capitalized, or ALLCAPS, counted before case folding
+  --vocab-growth             with stats: distinct words seen after every --growth-step tokens
+  --growth-step N            token interval for --vocab-growth (default 1000)
+  --max-matches N            stop printing filtered lines after N hits; without the top-words
+                             and stats sections (--no-top, --only filter, --print0) input after
+                             the Nth hit is not read at all
+  --sample-matches N         print a uniform random sample of N filtered lines (in file order)
+  --line-numbers             prefix filtered lines with LINE: (FILE:LINE: for several files)
+  -0, --null                 filter NUL-terminated records instead of lines (find -print0)
+  -l, --files-with-matches   print only the names of files with a filtered line, once each
+  --files-without-match      print only the names of files without any filtered line
+  --print0                   print only the filtered lines, each ended by NUL (xargs -0)
+  -r, --recursive            walk the given directories: with filter options print only the
+                             matching lines as PATH:LINE: text (files searched in parallel),
+                             otherwise count top words over every text file in the tree
+  --threads N                worker threads for -r reading and searching, for counting a
+                             large file in line chunks, and for daemon connections (default:
+                             one per CPU); output is identical for any N
+  --hasher fx|sip            key hashing for word counts: fx (default, fast) or sip (SipHash
+                             with random keys, for a daemon fed untrusted text)
+  --max-vocab N              keep at most N distinct words per count, evicting the lowest counts
+                             (least recently seen first); counts become approximate
+  --seed N                   fixed random seed for --sample-matches, sample-words, generate,
+                             and gen-corpus: the same N gives the same output on any platform
+  --max-filesize SIZE        with -r: skip files larger than SIZE (bytes, or 512K, 10M, 1G)
+  --follow-symlinks          with -r, todos, lint-ws: descend into symlinked files and
+                             directories
+                             (each directory is visited once, so link cycles end)
+  --hidden                   with -r, todos, lint-ws: include dotfiles and dot-directories
+  --ext LIST                 with -r, todos, lint-ws: only walk files with these extensions
+                             (md,txt,rs)
+  --exclude-ext LIST         with -r, todos, lint-ws: skip files with these extensions
+                             (json,lock)
+  --modified-since TIME      with -r, todos, lint-ws: only files modified at or after TIME:
+                             2024-06-01, 2024-06-01T09:30 (UTC), or an age such as 7d, 12h, 2w
+  --modified-before TIME     with -r, todos, lint-ws: only files modified before TIME
+  --from REGEX               with replace: pattern to substitute on each line
+  --to TEXT                  with replace: replacement, with $1, ${2}, $0 as in --replace
+  --write                    with replace: write the substituted text to FILE.replaced
+  --sql STATEMENT            with query: SELECT ... FROM words [WHERE] [GROUP BY] [ORDER BY]
+                             [LIMIT], e.g. 'SELECT word, count FROM words WHERE count > 10'
+  --fix                      with lint-ws: write a cleaned copy of each flagged file to
+                             FILE.clean (trailing whitespace removed, blank runs cut to 2)
+  --json-field PATH          NDJSON input: analyze only the string field at PATH (a.b.c)
+  --xml                      XML input: analyze text nodes only
+  --xml-path PATH            with --xml: only text under PATH (/a/b, a/b, //b)
+  --epub                     EPUB input: analyze the book text (chapters in reading order)
+  --group-by-speaker REGEX   also print top words per speaker: group 1 of REGEX (e.g. ^(\\w+):)
+                             names who is talking; unmatched lines continue the last speaker
+  --per-chapter              with --epub: also print top words for each chapter
+  --by paragraph             also print top words for each blank-line separated paragraph,
+                             numbered per file with its first line (and matching line counts)
+  --docx                     Word input: analyze the paragraphs of word/document.xml
+  --pdf                      PDF input: analyze the text layer of each page (no OCR)
+  --extract-cmd CMD          run CMD per file and analyze its stdout; {} is the path
+                             (e.g. 'pandoc -t plain {}'; stdin is piped in with {} as -)
+  --subtitles                SRT/VTT input: analyze spoken text only (no cue numbers/timings)
+  --bucket DURATION          with --subtitles or --timestamp-format: also print top words per
+                             time range (90s, 5m, 1h)
+  --timestamp-format FMT     read the time at the start of each line with FMT (%Y-%m-%d %H:%M:%S;
+                             also %b %e %f %z); lines without one join the previous line's range
+  --log                      parse log lines; count message words per level
+  --level SPEC               with --log: keep levels in SPEC (warn, warn+, debug..warn)
+  --sort count|alpha         order of the top-N rows (default count)
+  --tie-break alpha|first-seen|length
+                             order of equal counts (default alpha; length = longest first)
+  --collate ko|de|en|bytes   locale rules for alphabetical ordering (default bytes)
+  --unicode                  treat all Unicode letters as word characters
+  --nfkc                     normalize input to NFKC instead of NFC before tokenizing
+                             (ligatures, full-width and circled letters count as plain)
+  --strip-accents            drop accents before tokenizing so cafe and café are one word
+  --transliterate            map Cyrillic, Greek, Hangul and kana to ASCII before counting
+                             (Москва -> moskva, 한국 -> hanguk); implies --strip-accents
+  --emoji                    also count emoji (incl. ZWJ sequences, flags) as tokens
+  --hyphens keep|split|join  hyphenated words as one token, parts (default), or joined
+  --contractions keep|expand|split
+                             don't as one token, do + not, or don + t (default)
+  --case fold|preserve       count Rust and rust as one word (default) or apart
+  --numbers keep|drop|bucket numbers (404, 3.14, 1,000) as tokens, ignored (default), or all
+                             counted as one <NUM> token
+  --stem auto|LANG           count inflected forms as one stem (runs, running -> run); auto
+                             picks the language from the text
+  --stem-lang en|ko|de|fr    stem with one language's rules (same as --stem LANG)
+  --script PATH              run keep_line(line) and map_token(word) from a script in a small
+                             subset of rhai (no loops, arrays or floats): drop lines that
+                             return false, count words as returned
+  --preset logs|prose|code|social
+                             option bundle for the kind of text (options given after it win):
+                             logs = --log --hyphens keep --numbers drop --top 20;
+                             prose = --unicode --contractions expand --top 10;
+                             code = --case preserve --hyphens keep --numbers drop
+                             --tie-break alpha --top 20;
+                             social = --unicode --emoji --contractions expand --top 20
+  --config PATH              read settings from PATH; normalize = [lowercase, strip_diacritics,
+                             stem:en, stopwords:en] runs those steps on each token in order
+                             (instead of --case and --stem)
+  --every DURATION           re-run every DURATION (90s, 5m, 1h); one timestamped NDJSON line
+                             per run, appended to --output PATH when given
+  --follow                   wait at the end of FILE like tail -f and print new lines that pass
+                             the filter options (or, with --alert, only alert changes)
+  --jsonrpc                  serve JSON-RPC 2.0 on stdin/stdout with LSP Content-Length framing
+                             for editor plugins: analyze, top, filter, positions on the text
+                             or path sent in each request (no input files)
+  --alert RULE               with --follow: print FIRING/RESOLVED when RULE starts or stops
+                             holding, e.g. 'error > 100 per 5m' (repeatable; <, <=, >, >=)
+  --alert-cmd CMD            with --alert: run sh -c CMD on each change, with ALERT_RULE,
+                             ALERT_STATE and ALERT_COUNT set
+  --journal PATH             append one JSON line per run to PATH: time, options, input sha256
+                             hashes, token counts, and whether it succeeded
+  --output, --out PATH       write results to PATH (atomically replaced) instead of stdout
+  --append                   with --output: add results to the end of PATH
+  --output-encoding NAME     write results as utf-8 (default), utf-16le, utf-16be, latin1, or
+                             euc-kr; characters that don't fit are written as ?
+  --assert RULE              fail (exit 1) unless RULE holds: count(WORD) <= N, tokens > N,
+                             distinct == N (repeatable)
+  --fail-fast                stop at the first unreadable input instead of skipping it
+  --timing                   report per-stage throughput (bytes/s, tokens/s) on stderr
+  --resource-stats           report wall time, peak RSS, and heap allocations on stderr
+  --summary                  print one grep-friendly line to stderr at exit:
+                             files=N tokens=N unique=N matches=N elapsed=1.8s
+  --color auto|always|never  highlight --contains keywords in filtered lines (default auto)
+  --no-truncate              print long filtered lines in full on a terminal (by default they
+                             are cut to the terminal width with …, keeping the first match)
+  --no-pager                 never pipe long output through $PAGER
+  --page-size N              on a terminal: show N lines at a time without $PAGER and wait for
+                             a key (space: next page, enter: next line, q: quit)
+  --page K                   with --page-size: print only rows of page K of the top words
+                             (the whole ranking unless --top is given) and a page marker line
+  --page-marker TEXT         marker printed under --page output; {page}, {pages}, {first},
+                             {last}, and {total} are filled in
+                             (default looks like: -- page 2 of 5 (rows 21-40 of 97) --)
+  --explain                  print the resolved pipeline (input, decoder, tokenizer, filters,
+                             analysis, renderer) without reading any input";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Count,
+    Keywords,
+    Summarize,
+    Generate,
+    GenCorpus,
+    CompareImpl,
+    SampleWords,
+    Spellcheck,
+    Suggest,
+    Anagrams,
+    Weblog,
+    Stats,
+    Zipf,
+    Compare,
+    Social,
+    Trend,
+    Heatmap,
+    Merge,
+    Subtract,
+    Intersect,
+    Report,
+    Todos,
+    LintWs,
+    NearDupes,
+    Split,
+    Replace,
+    Drift,
+    Pick,
+    Daemon,
+    Cache,
+    Stopwords,
+    Query,
+    Graph,
+    Positions,
+    Bursts,
+    Rank,
+    Difficulty,
+    SelfUpdate,
+}
+
+// stopwords list / stopwords diff FILE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopwordsAction {
+    List,
+    Diff,
+}
+
+// 기본 모드 text 출력에서 쓸 구역: [Top words](--proper-nouns 등 딸린 표 포함), [Stats],
+// [Filtered lines]. --only로 고르거나 --no-top으로 상위 단어만 뺀다. [Stats]는 고를 때만 쓴다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sections {
+    pub top: bool,
+    pub stats: bool,
+    pub filter: bool,
+}
+
+impl Default for Sections {
+    fn default() -> Sections {
+        Sections {
+            top: true,
+            stats: false,
+            filter: true,
+        }
+    }
+}
+
+impl Sections {
+    // "filter,stats" -> filter와 stats만.
+    fn only(spec: &str) -> Result<Sections, String> {
+        let mut sections = Sections {
+            top: false,
+            stats: false,
+            filter: false,
+        };
+        for name in spec.split(',').map(str::trim) {
+            match name {
+                "top" => sections.top = true,
+                "stats" => sections.stats = true,
+                "filter" => sections.filter = true,
+                other => {
+                    return Err(format!(
+                        "--only takes top, filter, and stats separated by commas, got {other:?}"
+                    ))
+                }
+            }
+        }
+        Ok(sections)
+    }
+}
+
+impl Command {
+    pub fn from_name(name: &str) -> Option<Command> {
+        match name {
+            "keywords" => Some(Command::Keywords),
+            "summarize" => Some(Command::Summarize),
+            "generate" => Some(Command::Generate),
+            "gen-corpus" => Some(Command::GenCorpus),
+            "compare-impl" => Some(Command::CompareImpl),
+            "sample-words" => Some(Command::SampleWords),
+            "spellcheck" => Some(Command::Spellcheck),
+            "suggest" => Some(Command::Suggest),
+            "anagrams" => Some(Command::Anagrams),
+            "weblog" => Some(Command::Weblog),
+            "stats" => Some(Command::Stats),
+            "zipf" => Some(Command::Zipf),
+            "compare" => Some(Command::Compare),
+            "social" => Some(Command::Social),
+            "trend" => Some(Command::Trend),
+            "heatmap" => Some(Command::Heatmap),
+            "merge" => Some(Command::Merge),
+            "subtract" => Some(Command::Subtract),
+            "intersect" => Some(Command::Intersect),
+            "report" => Some(Command::Report),
+            "todos" => Some(Command::Todos),
+            "lint-ws" => Some(Command::LintWs),
+            "near-dupes" => Some(Command::NearDupes),
+            "split" => Some(Command::Split),
+            "replace" => Some(Command::Replace),
+            "drift" => Some(Command::Drift),
+            "pick" => Some(Command::Pick),
+            "daemon" => Some(Command::Daemon),
+            "cache" => Some(Command::Cache),
+            "stopwords" => Some(Command::Stopwords),
+            "query" => Some(Command::Query),
+            "graph" => Some(Command::Graph),
+            "positions" => Some(Command::Positions),
+            "bursts" => Some(Command::Bursts),
+            "rank" => Some(Command::Rank),
+            "difficulty" => Some(Command::Difficulty),
+            "self-update" => Some(Command::SelfUpdate),
+            _ => None,
+        }
+    }
+
+    // from_name의 반대. 기본 모드는 이름이 없으므로 "top words"라고 부른다.
+    pub fn name(self) -> &'static str {
+        match self {
+            Command::Count => "top words",
+            Command::Keywords => "keywords",
+            Command::Summarize => "summarize",
+            Command::Generate => "generate",
+            Command::GenCorpus => "gen-corpus",
+            Command::CompareImpl => "compare-impl",
+            Command::SampleWords => "sample-words",
+            Command::Spellcheck => "spellcheck",
+            Command::Suggest => "suggest",
+            Command::Anagrams => "anagrams",
+            Command::Weblog => "weblog",
+            Command::Stats => "stats",
+            Command::Zipf => "zipf",
+            Command::Compare => "compare",
+            Command::Social => "social",
+            Command::Trend => "trend",
+            Command::Heatmap => "heatmap",
+            Command::Merge => "merge",
+            Command::Subtract => "subtract",
+            Command::Intersect => "intersect",
+            Command::Report => "report",
+            Command::Todos => "todos",
+            Command::LintWs => "lint-ws",
+            Command::NearDupes => "near-dupes",
+            Command::Split => "split",
+            Command::Replace => "replace",
+            Command::Drift => "drift",
+            Command::Pick => "pick",
+            Command::Daemon => "daemon",
+            Command::Cache => "cache",
+            Command::Stopwords => "stopwords",
+            Command::Query => "query",
+            Command::Graph => "graph",
+            Command::Positions => "positions",
+            Command::Bursts => "bursts",
+            Command::Rank => "rank",
+            Command::Difficulty => "difficulty",
+            Command::SelfUpdate => "self-update",
+        }
+    }
+}
+
+// Python argparse의 Namespace에 해당하는 구조체.
+// 옵션이 늘어나도 튜플 순서를 외울 필요 없이 이름으로 꺼내 쓸 수 있다.
+pub struct Args {
+    // --preset과 WORDTOOL_* 변수를 펼친 뒤의 명령행(프로그램 이름은 뺀다). 캐시 키와 --journal이 쓴다.
+    pub argv: Vec<String>,
+    pub command: Command,
+    pub source: Source,
+    pub decoder: Decoder,
+    pub sentences: usize,
+    // generate: 뽑을 단어 수와 마르코프 연쇄의 차수.
+    pub words: usize,
+    pub order: usize,
+    // gen-corpus --size --vocab --zipf.
+    pub corpus: corpus::Spec,
+    // sample-words --n: 뽑을 단어 수. --uniform이면 빈도와 관계없이 균등하게 뽑는다.
+    pub sample: usize,
+    pub uniform: bool,
+    // compare-impl --python --python-script --runs.
+    pub python: String,
+    pub python_script: String,
+    pub runs: usize,
+    pub word: Option<String>,
+    // rank의 --word들(정규화한 값, 준 순서대로).
+    pub lookup: Vec<String>,
+    pub buckets: usize,
+    // graph와 bursts의 --window.
+    pub window: usize,
+    pub below: usize,
+    pub dict: Option<String>,
+    // difficulty: word,level CSV.
+    pub wordlist: Option<String>,
+    pub reference: Option<String>,
+    pub baseline: Option<String>,
+    pub stopwords_action: StopwordsAction,
+    // --number-format와 --lang: 사람이 읽는 출력의 숫자 모양.
+    pub number_format: NumberFormat,
+    pub threshold: f64,
+    // near-dupes: 문단을 비교하는 단어 묶음의 길이.
+    pub shingle: usize,
+    // split: 조각당 토큰 수, 층을 나눌 단어(정규화한 것), 조각 파일 이름 앞부분.
+    pub by_tokens: usize,
+    pub stratify: Vec<String>,
+    pub prefix: Option<String>,
+    pub socket: Option<String>,
+    pub metrics: Option<String>,
+    // --root: daemon과 --jsonrpc가 읽을 수 있는 디렉터리(sandbox.rs).
+    pub root: Option<Root>,
+    // self-update의 릴리스 서버, 서명 공개 키, 확인만 할지(selfupdate.rs).
+    pub release_url: Option<String>,
+    pub pubkey: Option<String>,
+    pub check_update: bool,
+    // --rate-limit / --max-request: daemon의 클라이언트별 요청 제한(limits.rs).
+    // --max-request는 --jsonrpc 메시지 본문의 최대 길이로도 쓴다.
+    pub rate_limit: Option<Rate>,
+    pub max_request: Option<usize>,
+    pub filter: LineFilter,
+    pub pager: bool,
+    // --page-size: 내장 쪽 넘김의 줄 수, --page가 있으면 쪽당 순위 행 수.
+    pub page_size: Option<usize>,
+    // --page: 1부터.
+    pub page: Option<usize>,
+    pub page_marker: String,
+    pub explain: bool,
+    // 상위 단어의 개수, 순서, 불용어. tokenizer는 analysis.tokenizer()와 같은 값이다.
+    pub analysis: Analysis,
+    pub tokenizer: Tokenizer,
+    // --script. main이 script::install()로 넣어 두면 집계와 입력 읽기가 훅을 부른다.
+    pub script: Option<Script>,
+    // --template. 있으면 --format 대신 이 틀로 순위표를 찍는다.
+    pub template: Option<Template>,
+    // --table(--rank, --padding): text 순위표를 열 맞춘 표로.
+    pub table: Option<Layout>,
+    // --plain: 머리글, 빈 줄, 색 없이 데이터 행만(cut, awk에 바로 넘길 때).
+    pub plain: bool,
+    pub log: bool,
+    pub level: Option<LevelRange>,
+    pub per_chapter: bool,
+    // --by paragraph.
+    pub by_paragraph: bool,
+    // --group-by-speaker: 줄 머리에서 말한 사람을 뽑는 정규식.
+    pub speaker: Option<regex::Regex>,
+    // --bucket 구간 길이(ms).
+    pub bucket: Option<u64>,
+    // --bucket을 자막 큐 대신 줄 앞 타임스탬프로 나눌 때의 형식.
+    pub timestamp_format: Option<TimeFormat>,
+    pub timing: bool,
+    pub fail_fast: bool,
+    pub assertions: Vec<Assertion>,
+    pub resource_stats: bool,
+    // --provenance: json/html 보고서에 입력 해시, 크기, 수정 시각을 싣는다.
+    pub provenance: bool,
+    // report의 사이드카 거르기와 묶기(--tag, --since, --until, --group-by).
+    pub sidecars: meta::Selection,
+    // --summary: 끝날 때 stderr에 쓰는 한 줄 요약(trailer.rs).
+    pub summary: bool,
+    pub output: Option<String>,
+    pub append: bool,
+    pub output_encoding: Encoding,
+    pub every: Option<Duration>,
+    // --follow: 파일 끝에서 새 줄을 기다린다. --alert 규칙은 최근 구간의 단어 빈도로 판단한다.
+    pub follow: bool,
+    // --jsonrpc: 입력 파일 대신 stdin으로 요청을 받는다(jsonrpc.rs).
+    pub jsonrpc: bool,
+    pub alerts: Vec<alert::Rule>,
+    pub alert_cmd: Option<String>,
+    // 실행마다 입력 해시와 옵션을 덧붙이는 NDJSON 감사 기록.
+    pub journal: Option<String>,
+    pub length_histogram: bool,
+    // stats --case: 단어마다 소문자/첫 글자 대문자/전부 대문자로 쓰인 횟수.
+    pub case_report: bool,
+    // stats --whitespace: 파일별 탭/공백 들여쓰기와 들여쓰기 폭 분포.
+    pub whitespace: bool,
+    // lint-ws --fix: 고친 사본을 FILE.clean으로 쓴다.
+    pub fix: bool,
+    // replace --from REGEX --to TEXT, --write는 FILE.replaced로 쓴다.
+    pub substitution: Option<(regex::Regex, String)>,
+    // query --sql.
+    pub sql: Option<Statement>,
+    pub write: bool,
+    // stats --line-endings: 파일별 LF/CRLF/CR 개수. --convert를 주면 그 줄바꿈으로 파일을 고쳐 쓴다.
+    // stats --longest-lines N.
+    pub longest_lines: Option<usize>,
+    pub line_endings: bool,
+    pub convert: Option<Ending>,
+    pub vocab_growth: Option<usize>,
+    pub char_ngrams: Option<usize>,
+    // --min-df/--max-df: 입력 파일을 문서로 보고 문서 빈도로 단어를 거른다.
+    pub min_df: Option<Bound>,
+    pub max_df: Option<Bound>,
+    // --only-words 목록(정규화한 단어). 나오지 않은 단어도 0으로 싣는다.
+    pub vocabulary: Vec<String>,
+    pub format: Format,
+    pub line_numbers: bool,
+    // 필터를 출력 대신 상위 단어의 입력 범위로 쓴다.
+    pub count_matching: bool,
+    pub print0: bool,
+    // -l / --files-without-match: 걸린 줄 대신 파일 이름만.
+    pub file_list: Option<FileList>,
+    // -o: 걸린 줄 대신 일치한 부분만. replace는 --replace 틀($1 등).
+    pub only_matching: bool,
+    pub replace: Option<String>,
+    // -r: 디렉터리를 재귀로 훑어 걸린 줄만 출력한다.
+    pub recursive: bool,
+    // --max-filesize(바이트). 이보다 큰 파일은 -r에서 건너뛴다.
+    pub max_filesize: Option<u64>,
+    // --threads: -r의 작업 스레드 수이자 큰 파일 하나를 나눠 셀 스레드 수, daemon의 연결을 맡을
+    // 스레드 수. None이면 CPU 수만큼.
+    pub threads: Option<usize>,
+    // --hasher: 빈도표의 키 해시 방식.
+    pub hasher: Hasher,
+    // --max-vocab: 빈도표 하나에 둘 서로 다른 단어 수의 상한.
+    pub max_vocab: Option<usize>,
+    // --seed: 난수를 쓰는 기능(rng.rs)의 씨앗. 없으면 실행마다 다르다(gen-corpus는 고정 씨앗).
+    pub seed: Option<u64>,
+    // -r와 todos가 디렉터리를 훑는 규칙.
+    pub walk: WalkOptions,
+    pub proper_nouns: bool,
+    pub acronyms: bool,
+    pub urls: bool,
+    pub emails: bool,
+    // --external-analyzer 명령들(analyzer.rs). 준 순서대로 돌려 표를 덧붙인다.
+    pub analyzers: Vec<String>,
+    pub flag_list: Option<String>,
+    pub export_stopwords: Option<String>,
+    // --with-positions: 상위 단어 표에 처음/마지막으로 나온 줄 번호 열을 붙인다.
+    pub with_positions: bool,
+    pub cache: Option<String>,
+    // --checkpoint / --resume 파일. resume이면 처음에 그 파일을 읽어 이어 센다.
+    pub checkpoint: Option<String>,
+    pub resume: bool,
+    // --only / --no-top.
+    pub sections: Sections,
+    // --color와 출력 대상을 보고 정한 최종 결과.
+    pub color: bool,
+    // 터미널에 쓸 때 걸린 줄을 자를 폭(칸). 파일, 파이프, --print0, --no-truncate면 None.
+    pub truncate: Option<usize>,
+}
+
+// "md,.txt, RS" -> ["md", "txt", "rs"].
+fn extension_list(spec: &str, flag: &str) -> Result<Vec<String>, String> {
+    let list: Vec<String> = spec
+        .split(',')
+        .map(|ext| ext.trim().trim_start_matches('.').to_ascii_lowercase())
+        .collect();
+    if list.iter().any(String::is_empty) {
+        return Err(format!(
+            "{flag} expects extensions like md,txt,rs, got {spec:?}"
+        ));
+    }
+    Ok(list)
+}
+
+// "10M" 같은 크기. 단위는 ripgrep처럼 1024배씩이고 대소문자를 가리지 않는다.
+fn parse_size(spec: &str) -> Result<u64, String> {
+    let lower = spec.trim().to_ascii_lowercase();
+    let body = lower.strip_suffix('b').unwrap_or(&lower);
+    let (digits, shift) = match body.as_bytes().last() {
+        Some(b'k') => (&body[..body.len() - 1], 10),
+        Some(b'm') => (&body[..body.len() - 1], 20),
+        Some(b'g') => (&body[..body.len() - 1], 30),
+        _ => (body, 0),
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(1 << shift))
+        .ok_or_else(|| format!("invalid size: {spec} (expected e.g. 4096, 512K, 10M, 1G)"))
+}
+
+// `--flag VALUE` 형태에서 VALUE를 꺼낸다. 값이 없으면 어떤 플래그인지 알려준다.
+fn stem_language(flag: &str, code: &str) -> Result<Language, String> {
+    let auto = if flag == "--stem" { "auto, " } else { "" };
+    Language::parse(code)
+        .ok_or_else(|| format!("{flag} must be {auto}en, ko, de, or fr, got {code}"))
+}
+
+// 이 명령이 --format으로 이 형식을 낼 수 있는지. WORDTOOL_FORMAT을 넣을지도 이것으로 정한다.
+pub fn format_applies(command: Command, format: Format, log: bool) -> bool {
+    if format == Format::Text {
+        return true;
+    }
+    if command == Command::Report {
+        return format == Format::Json;
+    }
+    if matches!(format, Format::Dot | Format::Gexf) {
+        return command == Command::Graph;
+    }
+    let zipf = format == Format::Csv && matches!(command, Command::Zipf | Command::Query);
+    let merge = command == Command::Merge && !format.is_columnar();
+    let per_word = format == Format::Json
+        && matches!(
+            command,
+            Command::Positions | Command::Rank | Command::Difficulty | Command::NearDupes
+        );
+    (command == Command::Count || merge || zipf || per_word) && !log
+}
+
+fn take_value<'a>(args: &'a [String], i: &mut usize, flag: &str) -> Result<&'a String, String> {
+    *i += 1;
+    args.get(*i)
+        .ok_or_else(|| format!("missing value for {flag}"))
+}
+
+// Python argparse와 달리 라이브러리 없이 직접 파싱한 버전.
+// 학습 포인트: Option<String>으로 "있을 수도/없을 수도" 있는 값을 타입으로 표현한다.
+pub fn parse_args() -> Result<Args, String> {
+    let argv: Vec<String> = env::args().collect();
+    if argv.len() < 2 {
+        return Err(USAGE.to_string());
+    }
+    let (mut args, preset) = preset::expand(argv)?;
+    // 환경 변수는 --preset 묶음보다도 앞에 둬서 명령행에서 준 것이 모두 이기게 한다.
+    let (from_env, variables) = environment::flags(&args)?;
+    args.splice(1..1, from_env);
+    // 묶음이나 환경 변수가 넣은 옵션 때문에 난 에러라면 무엇이 들어갔는지 함께 보여 준다.
+    parse(args).map_err(|mut e| {
+        if let Some(preset) = preset {
+            let flags = preset.flags().join(" ");
+            e.push_str(&format!("\n(--preset {} adds: {flags})", preset.name()));
+        }
+        if !variables.is_empty() {
+            e.push_str(&format!("\n(environment sets: {})", variables.join(" ")));
+        }
+        e
+    })
+}
+
+fn parse(args: Vec<String>) -> Result<Args, String> {
+    let mut command = Command::Count;
+    let mut positionals: Vec<String> = Vec::new();
+    let mut top_n = 5usize;
+    let mut top_given = false;
+    let mut page_size = None;
+    let mut page = None;
+    let mut page_marker = None;
+    let mut sentences = 5usize;
+    let mut words = None;
+    let mut order = None;
+    let mut size = None;
+    let mut vocab = None;
+    let mut zipf = None;
+    let mut sample = None;
+    let mut uniform = false;
+    let mut python = None;
+    let mut lang: Option<String> = None;
+    let mut number_spec = None;
+    let mut python_script = None;
+    let mut runs = None;
+    let mut word_args: Vec<String> = Vec::new();
+    let mut buckets = None;
+    let mut window = None;
+    let mut below = None;
+    let mut dict: Option<String> = None;
+    let mut wordlist: Option<String> = None;
+    let mut reference: Option<String> = None;
+    let mut baseline: Option<String> = None;
+    let mut threshold = None;
+    let mut shingle = None;
+    let mut by_tokens = None;
+    let mut stratify = None;
+    let mut prefix = None;
+    let mut socket: Option<String> = None;
+    let mut metrics: Option<String> = None;
+    let mut contains: Vec<String> = Vec::new();
+    let mut query = None;
+    let mut phrase: Option<String> = None;
+    let mut match_stems = false;
+    let mut near: Option<String> = None;
+    let mut regex = None;
+    let mut only_matching = false;
+    let mut replace: Option<String> = None;
+    let mut pager = true;
+    let mut no_truncate = false;
+    let mut explain = false;
+    let mut sort = SortOrder::Count;
+    let mut collation = Collation::Bytes;
+    let mut unicode = false;
+    let mut emoji = false;
+    let mut hyphens = Hyphens::Split;
+    let mut contractions = Contractions::Split;
+    let mut numbers = Numbers::Drop;
+    let mut normalization = Normalization::Nfc;
+    let mut strip_accents = false;
+    let mut transliterate = false;
+    let mut case = Case::Fold;
+    let mut stem = Stem::Off;
+    let mut config_path = None;
+    let mut script_path = None;
+    let mut template_path = None;
+    let mut table = false;
+    let mut plain = false;
+    let mut rank = false;
+    let mut padding = None;
+    let mut template = None;
+    let mut log = false;
+    let mut decoder = Decoder::Plain;
+    let mut level = None;
+    let mut per_chapter = false;
+    let mut by_paragraph = false;
+    let mut speaker = None;
+    let mut bucket = None;
+    let mut timestamp_format = None;
+    let mut clipboard = false;
+    let mut timing = false;
+    let mut fail_fast = false;
+    let mut assert_specs = Vec::new();
+    let mut resource_stats = false;
+    let mut summary = false;
+    let mut provenance = false;
+    let mut sidecars = meta::Selection::default();
+    let mut output: Option<String> = None;
+    let mut append = false;
+    let mut output_encoding = Encoding::Utf8;
+    let mut every = None;
+    let mut follow = false;
+    let mut jsonrpc = false;
+    let mut root = None;
+    let mut release_url = None;
+    let mut pubkey = None;
+    let mut check_update = false;
+    let mut rate_limit = None;
+    let mut max_request = None;
+    let mut only = None;
+    let mut no_top = false;
+    let mut alert_specs: Vec<String> = Vec::new();
+    let mut alert_cmd = None;
+    let mut journal = None;
+    let mut length_histogram = false;
+    let mut case_report = false;
+    let mut whitespace = false;
+    let mut fix = false;
+    let mut from = None;
+    let mut to: Option<String> = None;
+    let mut write = false;
+    let mut sql_statement = None;
+    let mut longest_lines = None;
+    let mut line_endings = false;
+    let mut convert = None;
+    let mut vocab_growth = false;
+    let mut growth_step = None;
+    let mut char_ngrams = None;
+    let mut min_df = None;
+    let mut max_df = None;
+    let mut format = Format::Text;
+    let mut tie_break = TieBreak::Alpha;
+    let mut line_numbers = false;
+    let mut nul = false;
+    let mut print0 = false;
+    let mut file_list = None;
+    let mut recursive = false;
+    let mut max_filesize = None;
+    let mut threads = None;
+    let mut hasher = Hasher::Fx;
+    let mut max_vocab = None;
+    let mut seed = None;
+    let mut walk = WalkOptions::default();
+    let mut count_matching = false;
+    let mut proper_nouns = false;
+    let mut acronyms = false;
+    let mut urls = false;
+    let mut emails = false;
+    let mut analyzers = Vec::new();
+    let mut flag_list = None;
+    let mut stopwords_file = None;
+    let mut keep_file = None;
+    let mut only_words = None;
+    let mut export_stopwords = None;
+    let mut with_positions = false;
+    let mut cache = None;
+    let mut no_cache = false;
+    let mut checkpoint = None;
+    let mut resume = false;
+    let mut max_matches = None;
+    let mut sample_matches = None;
+    let mut color_mode = ColorMode::Auto;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--top" => {
+                let value = take_value(&args, &mut i, "--top")?;
+                top_n = value
+                    .parse::<usize>()
+                    .map_err(|_| "--top must be a non-negative integer".to_string())?;
+                top_given = true;
+            }
+            "--all" => {
+                top_n = 0;
+                top_given = true;
+            }
+            "--format" => {
+                let value = take_value(&args, &mut i, "--format")?;
+                format = Format::parse(value)?;
+            }
+            "--sentences" => {
+                let value = take_value(&args, &mut i, "--sentences")?;
+                sentences = value
+                    .parse::<usize>()
+                    .map_err(|_| "--sentences must be a positive integer".to_string())?;
+            }
+            "--words" => {
+                let value = take_value(&args, &mut i, "--words")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--words must be a positive integer".to_string())?;
+                words = Some(n);
+            }
+            "--order" => {
+                let value = take_value(&args, &mut i, "--order")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--order must be a positive integer".to_string())?;
+                order = Some(n);
+            }
+            "--n" => {
+                let value = take_value(&args, &mut i, "--n")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--n must be a positive integer".to_string())?;
+                sample = Some(n);
+            }
+            "--uniform" => uniform = true,
+            "--python" => {
+                let value = take_value(&args, &mut i, "--python")?;
+                python = Some(value.clone());
+            }
+            "--python-script" => {
+                let value = take_value(&args, &mut i, "--python-script")?;
+                python_script = Some(value.clone());
+            }
+            "--runs" => {
+                let value = take_value(&args, &mut i, "--runs")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--runs must be a positive integer".to_string())?;
+                runs = Some(n);
+            }
+            "--size" => {
+                let value = take_value(&args, &mut i, "--size")?;
+                size = Some(parse_size(value)?).filter(|n| *n > 0);
+                if size.is_none() {
+                    return Err("--size must be greater than 0".to_string());
+                }
+            }
+            "--vocab" => {
+                let value = take_value(&args, &mut i, "--vocab")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--vocab must be a positive integer".to_string())?;
+                vocab = Some(n);
+            }
+            "--zipf" => {
+                let value = take_value(&args, &mut i, "--zipf")?;
+                let s = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|s| s.is_finite() && *s > 0.0)
+                    .ok_or_else(|| "--zipf must be a positive number".to_string())?;
+                zipf = Some(s);
+            }
+            "--baseline" => {
+                let value = take_value(&args, &mut i, "--baseline")?;
+                baseline = Some(value.clone());
+            }
+            "--socket" => {
+                let value = take_value(&args, &mut i, "--socket")?;
+                socket = Some(value.clone());
+            }
+            "--metrics" => {
+                let value = take_value(&args, &mut i, "--metrics")?;
+                metrics = Some(value.clone());
+            }
+            "--rate-limit" => {
+                rate_limit = Some(Rate::parse(take_value(&args, &mut i, "--rate-limit")?)?);
+            }
+            "--max-request" => {
+                let value = take_value(&args, &mut i, "--max-request")?;
+                let n = parse_size(value)?;
+                if n == 0 {
+                    return Err("--max-request must be positive".to_string());
+                }
+                max_request = Some(n as usize);
+            }
+            "--release-url" => {
+                release_url = Some(take_value(&args, &mut i, "--release-url")?.clone());
+            }
+            "--pubkey" => pubkey = Some(take_value(&args, &mut i, "--pubkey")?.clone()),
+            "--check" => check_update = true,
+            "--root" => {
+                root = Some(Root::open(take_value(&args, &mut i, "--root")?)?);
+            }
+            "--threshold" => {
+                let value = take_value(&args, &mut i, "--threshold")?;
+                let t = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|t| t.is_finite() && *t >= 0.0)
+                    .ok_or_else(|| "--threshold must be a non-negative number".to_string())?;
+                threshold = Some(t);
+            }
+            "--shingle" => {
+                let value = take_value(&args, &mut i, "--shingle")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--shingle must be a positive integer".to_string())?;
+                shingle = Some(n);
+            }
+            "--by-tokens" => {
+                let value = take_value(&args, &mut i, "--by-tokens")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--by-tokens must be a positive integer".to_string())?;
+                by_tokens = Some(n);
+            }
+            "--stratify" => {
+                let value = take_value(&args, &mut i, "--stratify")?;
+                stratify = Some(value.clone());
+            }
+            "--prefix" => {
+                let value = take_value(&args, &mut i, "--prefix")?;
+                prefix = Some(value.clone());
+            }
+            "--word" => {
+                let value = take_value(&args, &mut i, "--word")?;
+                word_args.push(value.clone());
+            }
+            "--buckets" => {
+                let value = take_value(&args, &mut i, "--buckets")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--buckets must be a positive integer".to_string())?;
+                buckets = Some(n);
+            }
+            "--window" => {
+                let value = take_value(&args, &mut i, "--window")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--window must be a positive integer".to_string())?;
+                window = Some(n);
+            }
+            "--below" => {
+                let value = take_value(&args, &mut i, "--below")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--below must be a positive integer".to_string())?;
+                below = Some(n);
+            }
+            "--dict" => {
+                let value = take_value(&args, &mut i, "--dict")?;
+                dict = Some(value.clone());
+            }
+            "--wordlist" => {
+                let value = take_value(&args, &mut i, "--wordlist")?;
+                wordlist = Some(value.clone());
+            }
+            "--reference" => {
+                let value = take_value(&args, &mut i, "--reference")?;
+                reference = Some(value.clone());
+            }
+            "--contains" => {
+                let value = take_value(&args, &mut i, "--contains")?;
+                contains.push(value.clone());
+            }
+            "--match-stems" => match_stems = true,
+            "--query" => {
+                let value = take_value(&args, &mut i, "--query")?;
+                let expr = query::parse(value).map_err(|e| {
+                    Diagnostic {
+                        label: "--query",
+                        source: value,
+                        message: &e.message,
+                        start: e.start,
+                        end: e.end,
+                        hint: e.hint,
+                    }
+                    .render()
+                })?;
+                query = Some(expr);
+            }
+            "--regex" => {
+                let value = take_value(&args, &mut i, "--regex")?;
+                let pattern = regex::parse(value).map_err(|e| {
+                    Diagnostic {
+                        label: "--regex",
+                        source: value,
+                        message: &e.message,
+                        start: e.start,
+                        end: e.end,
+                        hint: e.hint,
+                    }
+                    .render()
+                })?;
+                regex = Some(pattern);
+            }
+            "--group-by-speaker" => {
+                let value = take_value(&args, &mut i, "--group-by-speaker")?;
+                let pattern = regex::parse(value).map_err(|e| {
+                    Diagnostic {
+                        label: "--group-by-speaker",
+                        source: value,
+                        message: &e.message,
+                        start: e.start,
+                        end: e.end,
+                        hint: e.hint,
+                    }
+                    .render()
+                })?;
+                speaker = Some(pattern);
+            }
+            "-o" | "--only-matching" => only_matching = true,
+            "--replace" => {
+                let value = take_value(&args, &mut i, "--replace")?;
+                replace = Some(value.clone());
+            }
+            "--phrase" => {
+                let value = take_value(&args, &mut i, "--phrase")?;
+                phrase = Some(value.clone());
+            }
+            "--near" => {
+                let value = take_value(&args, &mut i, "--near")?;
+                near = Some(value.clone());
+            }
+            "--no-pager" => pager = false,
+            "--tag" => {
+                let value = take_value(&args, &mut i, "--tag")?;
+                sidecars.tags.push(value.clone());
+            }
+            "--since" | "--until" => {
+                let flag = args[i].clone();
+                let value = take_value(&args, &mut i, &flag)?;
+                if !meta::valid_date(value) {
+                    return Err(format!(
+                        "{flag} must look like 2020, 2020-05, or 2020-05-17, got {value}"
+                    ));
+                }
+                if flag == "--since" {
+                    sidecars.since = Some(value.clone());
+                } else {
+                    sidecars.until = Some(value.clone());
+                }
+            }
+            "--group-by" => {
+                let value = take_value(&args, &mut i, "--group-by")?;
+                sidecars.group_by = Some(meta::GroupBy::parse(value).ok_or_else(|| {
+                    format!("--group-by must be tag, author, or year, got {value}")
+                })?);
+            }
+            "--page-size" => {
+                let value = take_value(&args, &mut i, "--page-size")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--page-size must be a positive integer".to_string())?;
+                page_size = Some(n);
+            }
+            "--page" => {
+                let value = take_value(&args, &mut i, "--page")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--page must be a positive integer".to_string())?;
+                page = Some(n);
+            }
+            "--page-marker" => {
+                let value = take_value(&args, &mut i, "--page-marker")?;
+                page_marker = Some(value.clone());
+            }
+            "--no-truncate" => no_truncate = true,
+            "--explain" => explain = true,
+            "--color" => {
+                let value = take_value(&args, &mut i, "--color")?;
+                color_mode = ColorMode::parse(value)?;
+            }
+            "--timing" => timing = true,
+            "--fail-fast" => fail_fast = true,
+            "--assert" => {
+                let value = take_value(&args, &mut i, "--assert")?;
+                assert_specs.push(value.clone());
+            }
+            "--resource-stats" => resource_stats = true,
+            "--summary" => summary = true,
+            "--provenance" => provenance = true,
+            "--output" | "--out" => {
+                let value = take_value(&args, &mut i, "--output")?;
+                output = Some(value.clone());
+            }
+            "--append" => append = true,
+            "--output-encoding" => {
+                let value = take_value(&args, &mut i, "--output-encoding")?;
+                output_encoding = Encoding::parse(value)?;
+            }
+            "--length-histogram" => length_histogram = true,
+            "--vocab-growth" => vocab_growth = true,
+            "--whitespace" => whitespace = true,
+            "--longest-lines" => {
+                let value = take_value(&args, &mut i, "--longest-lines")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--longest-lines must be a positive integer".to_string())?;
+                longest_lines = Some(n);
+            }
+            "--line-endings" => line_endings = true,
+            "--convert" => {
+                let value = take_value(&args, &mut i, "--convert")?;
+                convert = Some(match value.as_str() {
+                    "lf" => Ending::Lf,
+                    "crlf" => Ending::Crlf,
+                    other => return Err(format!("--convert must be lf or crlf, got {other}")),
+                });
+            }
+            "--growth-step" => {
+                let value = take_value(&args, &mut i, "--growth-step")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--growth-step must be a positive integer".to_string())?;
+                growth_step = Some(n);
+            }
+            "--char-ngrams" => {
+                let value = take_value(&args, &mut i, "--char-ngrams")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| "--char-ngrams must be a positive integer".to_string())?;
+                char_ngrams = Some(n);
+            }
+            "--min-df" => {
+                let value = take_value(&args, &mut i, "--min-df")?;
+                min_df = Some(Bound::parse(value).ok_or_else(|| {
+                    format!("--min-df must be a file count or a ratio from 0.0 to 1.0, got {value}")
+                })?);
+            }
+            "--max-df" => {
+                let value = take_value(&args, &mut i, "--max-df")?;
+                max_df = Some(Bound::parse(value).ok_or_else(|| {
+                    format!("--max-df must be a file count or a ratio from 0.0 to 1.0, got {value}")
+                })?);
+            }
+            "--sort" => {
+                let value = take_value(&args, &mut i, "--sort")?;
+                sort = match value.as_str() {
+                    "count" => SortOrder::Count,
+                    "alpha" => SortOrder::Alpha,
+                    other => return Err(format!("--sort must be count or alpha, got {other}")),
+                };
+            }
+            "--tie-break" => {
+                let value = take_value(&args, &mut i, "--tie-break")?;
+                tie_break = match value.as_str() {
+                    "alpha" => TieBreak::Alpha,
+                    "first-seen" => TieBreak::FirstSeen,
+                    "length" => TieBreak::Length,
+                    other => {
+                        return Err(format!(
+                            "--tie-break must be alpha, first-seen, or length, got {other}"
+                        ))
+                    }
+                };
+            }
+            "--nfkc" => normalization = Normalization::Nfkc,
+            "--strip-accents" => strip_accents = true,
+            "--transliterate" => transliterate = true,
+            "--numbers" => {
+                let value = take_value(&args, &mut i, "--numbers")?;
+                numbers = match value.as_str() {
+                    "keep" => Numbers::Keep,
+                    "drop" => Numbers::Drop,
+                    "bucket" => Numbers::Bucket,
+                    other => {
+                        return Err(format!(
+                            "--numbers must be keep, drop, or bucket, got {other}"
+                        ))
+                    }
+                };
+            }
+            // 값 없이 쓰면(stats --case) 대소문자 표기 보고서다. 검사는 명령을 다 읽은 뒤에 한다.
+            "--case" => match args.get(i + 1).map(String::as_str) {
+                Some("fold" | "preserve") => {
+                    let value = take_value(&args, &mut i, "--case")?;
+                    case = if value == "fold" {
+                        Case::Fold
+                    } else {
+                        Case::Preserve
+                    };
+                }
+                _ => case_report = true,
+            },
+            "--stem" => {
+                let value = take_value(&args, &mut i, "--stem")?;
+                stem = match value.as_str() {
+                    "auto" => Stem::Auto,
+                    code => Stem::Lang(stem_language("--stem", code)?),
+                };
+            }
+            "--table" => table = true,
+            "--plain" => plain = true,
+            "--only" => {
+                let value = take_value(&args, &mut i, "--only")?;
+                only = Some(Sections::only(value)?);
+            }
+            "--no-top" => no_top = true,
+            "--rank" => rank = true,
+            "--padding" => {
+                let value = take_value(&args, &mut i, "--padding")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--padding must be a positive integer".to_string())?;
+                padding = Some(n);
+            }
+            "--template" => {
+                let value = take_value(&args, &mut i, "--template")?;
+                template_path = Some(value.clone());
+            }
+            "--script" => {
+                let value = take_value(&args, &mut i, "--script")?;
+                script_path = Some(value.clone());
+            }
+            "--config" => {
+                let value = take_value(&args, &mut i, "--config")?;
+                config_path = Some(value.clone());
+            }
+            "--lang" => {
+                let value = take_value(&args, &mut i, "--lang")?;
+                if !matches!(value.as_str(), "en" | "de" | "fr" | "ko") {
+                    return Err(format!("--lang must be en, de, fr, or ko, got {value}"));
+                }
+                lang = Some(value.clone());
+            }
+            "--number-format" => {
+                let value = take_value(&args, &mut i, "--number-format")?;
+                number_spec = Some(value.clone());
+            }
+            "--stem-lang" => {
+                let value = take_value(&args, &mut i, "--stem-lang")?;
+                stem = Stem::Lang(stem_language("--stem-lang", value)?);
+            }
+            "--hyphens" => {
+                let value = take_value(&args, &mut i, "--hyphens")?;
+                hyphens = match value.as_str() {
+                    "keep" => Hyphens::Keep,
+                    "split" => Hyphens::Split,
+                    "join" => Hyphens::Join,
+                    other => {
+                        return Err(format!(
+                            "--hyphens must be keep, split, or join, got {other}"
+                        ))
+                    }
+                };
+            }
+            "--contractions" => {
+                let value = take_value(&args, &mut i, "--contractions")?;
+                contractions = match value.as_str() {
+                    "keep" => Contractions::Keep,
+                    "expand" => Contractions::Expand,
+                    "split" => Contractions::Split,
+                    other => {
+                        return Err(format!(
+                            "--contractions must be keep, expand, or split, got {other}"
+                        ))
+                    }
+                };
+            }
+            "--collate" => {
+                let value = take_value(&args, &mut i, "--collate")?;
+                collation = Collation::parse(value)?;
+            }
+            "--unicode" => unicode = true,
+            "--emoji" => emoji = true,
+            "--log" => log = true,
+            "--xml" => {
+                if !matches!(decoder, Decoder::Xml(_)) {
+                    decoder = Decoder::Xml(None);
+                }
+            }
+            "--xml-path" => {
+                let value = take_value(&args, &mut i, "--xml-path")?;
+                decoder = Decoder::Xml(Some(ElementPath::parse(value)?));
+            }
+            "--epub" => decoder = Decoder::Epub,
+            "--docx" => decoder = Decoder::Docx,
+            "--pdf" => decoder = Decoder::Pdf,
+            "--extract-cmd" => {
+                let value = take_value(&args, &mut i, "--extract-cmd")?;
+                if value.trim().is_empty() {
+                    return Err("--extract-cmd must not be empty".to_string());
+                }
+                decoder = Decoder::Extract(value.clone());
+            }
+            "--per-chapter" => per_chapter = true,
+            "--by" => match take_value(&args, &mut i, "--by")?.as_str() {
+                "paragraph" | "paragraphs" => by_paragraph = true,
+                other => return Err(format!("--by must be paragraph, got {other}")),
+            },
+            "--clipboard" => clipboard = true,
+            "--line-numbers" => line_numbers = true,
+            "-0" | "--null" => nul = true,
+            "--print0" => print0 = true,
+            "-l" | "--files-with-matches" => file_list = Some(FileList::Matching),
+            "--files-without-match" => file_list = Some(FileList::NotMatching),
+            "-r" | "--recursive" => recursive = true,
+            "--follow-symlinks" => walk.follow_symlinks = true,
+            "--hidden" => walk.hidden = true,
+            "--modified-since" => {
+                let value = take_value(&args, &mut i, "--modified-since")?;
+                walk.modified_since = Some(walk::parse_time(value, SystemTime::now())?);
+            }
+            "--modified-before" => {
+                let value = take_value(&args, &mut i, "--modified-before")?;
+                walk.modified_before = Some(walk::parse_time(value, SystemTime::now())?);
+            }
+            "--ext" => {
+                let value = take_value(&args, &mut i, "--ext")?;
+                walk.extensions.extend(extension_list(value, "--ext")?);
+            }
+            "--exclude-ext" => {
+                let value = take_value(&args, &mut i, "--exclude-ext")?;
+                walk.excluded
+                    .extend(extension_list(value, "--exclude-ext")?);
+            }
+            "--threads" => {
+                let value = take_value(&args, &mut i, "--threads")?;
+                let n = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--threads must be a positive integer".to_string())?;
+                threads = Some(n);
+            }
+            "--hasher" => {
+                let value = take_value(&args, &mut i, "--hasher")?;
+                hasher = Hasher::parse(value)
+                    .ok_or_else(|| format!("--hasher must be fx or sip, got {value}"))?;
+            }
+            "--max-vocab" => {
+                let n = take_value(&args, &mut i, "--max-vocab")?
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--max-vocab must be a positive integer".to_string())?;
+                max_vocab = Some(n);
+            }
+            "--seed" => {
+                let value = take_value(&args, &mut i, "--seed")?;
+                let n = value
+                    .parse::<u64>()
+                    .map_err(|_| format!("--seed must be a non-negative integer, got {value}"))?;
+                seed = Some(n);
+            }
+            "--max-filesize" => {
+                let value = take_value(&args, &mut i, "--max-filesize")?;
+                max_filesize = Some(parse_size(value)?);
+            }
+            "--count-matching" => count_matching = true,
+            "--proper-nouns" => proper_nouns = true,
+            "--external-analyzer" => {
+                let value = take_value(&args, &mut i, "--external-analyzer")?;
+                if value.trim().is_empty() {
+                    return Err("--external-analyzer must not be empty".to_string());
+                }
+                analyzers.push(value.clone());
+            }
+            "--acronyms" => acronyms = true,
+            "--urls" => urls = true,
+            "--emails" => emails = true,
+            "--stopwords-file" => {
+                let value = take_value(&args, &mut i, "--stopwords-file")?;
+                stopwords_file = Some(value.clone());
+            }
+            "--keep-file" => {
+                let value = take_value(&args, &mut i, "--keep-file")?;
+                keep_file = Some(value.clone());
+            }
+            "--only-words" => {
+                let value = take_value(&args, &mut i, "--only-words")?;
+                only_words = Some(value.clone());
+            }
+            "--export-stopwords" => {
+                let value = take_value(&args, &mut i, "--export-stopwords")?;
+                export_stopwords = Some(value.clone());
+            }
+            "--with-positions" => with_positions = true,
+            "--flag-list" => {
+                let value = take_value(&args, &mut i, "--flag-list")?;
+                flag_list = Some(value.clone());
+            }
+            "--max-matches" => {
+                let value = take_value(&args, &mut i, "--max-matches")?;
+                let n = value
+                    .parse::<usize>()
+                    .map_err(|_| "--max-matches must be a non-negative integer".to_string())?;
+                max_matches = Some(n);
+            }
+            "--sample-matches" => {
+                let value = take_value(&args, &mut i, "--sample-matches")?;
+                let n = value
+                    .parse::<usize>()
+                    .map_err(|_| "--sample-matches must be a non-negative integer".to_string())?;
+                sample_matches = Some(n);
+            }
+            "--subtitles" => decoder = Decoder::Subtitles,
+            "--every" => {
+                let value = take_value(&args, &mut i, "--every")?;
+                every = Some(Duration::from_millis(subtitles::parse_duration(value)?));
+            }
+            "--follow" => follow = true,
+            "--jsonrpc" => jsonrpc = true,
+            "--alert" => {
+                let value = take_value(&args, &mut i, "--alert")?;
+                alert_specs.push(value.clone());
+            }
+            "--alert-cmd" => {
+                let value = take_value(&args, &mut i, "--alert-cmd")?;
+                alert_cmd = Some(value.clone());
+            }
+            "--journal" => {
+                let value = take_value(&args, &mut i, "--journal")?;
+                journal = Some(value.clone());
+            }
+            "--cache" => {
+                let value = take_value(&args, &mut i, "--cache")?;
+                cache = Some(value.clone());
+            }
+            "--no-cache" => no_cache = true,
+            "--checkpoint" | "--resume" => {
+                let flag = args[i].clone();
+                let value = take_value(&args, &mut i, &flag)?;
+                if checkpoint.is_some() {
+                    return Err("give only one of --checkpoint and --resume".to_string());
+                }
+                checkpoint = Some(value.clone());
+                resume = flag == "--resume";
+            }
+            "--fix" => fix = true,
+            "--from" => {
+                let value = take_value(&args, &mut i, "--from")?;
+                let pattern = regex::parse(value).map_err(|e| {
+                    Diagnostic {
+                        label: "--from",
+                        source: value,
+                        message: &e.message,
+                        start: e.start,
+                        end: e.end,
+                        hint: e.hint,
+                    }
+                    .render()
+                })?;
+                from = Some(pattern);
+            }
+            "--to" => {
+                let value = take_value(&args, &mut i, "--to")?;
+                to = Some(value.clone());
+            }
+            "--write" => write = true,
+            "--sql" => {
+                let value = take_value(&args, &mut i, "--sql")?;
+                let statement = sql::parse(value).map_err(|e| {
+                    Diagnostic {
+                        label: "--sql",
+                        source: value,
+                        message: &e.message,
+                        start: e.start,
+                        end: e.end,
+                        hint: e.hint,
+                    }
+                    .render()
+                })?;
+                sql_statement = Some(statement);
+            }
+            "--timestamp-format" => {
+                let value = take_value(&args, &mut i, "--timestamp-format")?;
+                timestamp_format = Some(TimeFormat::parse(value)?);
+            }
+            "--bucket" => {
+                let value = take_value(&args, &mut i, "--bucket")?;
+                bucket = Some(subtitles::parse_duration(value)?);
+            }
+            "--json-field" => {
+                let value = take_value(&args, &mut i, "--json-field")?;
+                let path: Vec<String> = value.split('.').map(String::from).collect();
+                if path.iter().any(String::is_empty) {
+                    return Err(format!("invalid --json-field path: {value}"));
+                }
+                decoder = Decoder::JsonField(path);
+            }
+            "--level" => {
+                let value = take_value(&args, &mut i, "--level")?;
+                level = Some(LevelRange::parse(value)?);
+            }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            unknown if unknown.starts_with('-') && unknown != "-" => {
+                return Err(format!("unknown argument: {unknown}"));
+            }
+            positional => {
+                // 첫 위치 인자가 서브커맨드 이름이면 모드로, 아니면 파일로 본다.
+                match Command::from_name(positional) {
+                    Some(c) if positionals.is_empty() && command == Command::Count => command = c,
+                    _ => positionals.push(positional.to_string()),
+                }
+            }
+        }
+        i += 1;
+    }
+
+    // 서로 맞지 않는 옵션 짝은 첫 번째에서 멈추지 않고 한꺼번에 모아, 고칠 방법과 함께 알린다.
+    let filtered = !contains.is_empty()
+        || query.is_some()
+        || phrase.is_some()
+        || near.is_some()
+        || regex.is_some();
+    let config = match &config_path {
+        Some(path) => config::load(path)?,
+        None => Config::default(),
+    };
+    let text_layout = format != Format::Text || template_path.is_some();
+    let formatted = format != Format::Text && command != Command::Report;
+    let split = per_chapter || bucket.is_some() || by_paragraph;
+    let word_options = [
+        (stem != Stem::Off, "--stem"),
+        (contractions != Contractions::Split, "--contractions"),
+        (hyphens != Hyphens::Split, "--hyphens"),
+        (numbers != Numbers::Drop, "--numbers"),
+        (emoji, "--emoji"),
+    ];
+    let ignored: Vec<&str> = word_options
+        .iter()
+        .filter(|(given, _)| *given)
+        .map(|(_, flag)| *flag)
+        .collect();
+    let checks = [
+        (
+            max_matches.is_some() && sample_matches.is_some(),
+            "--max-matches and --sample-matches: keep one (the first N hits, or a random N)"
+                .to_string(),
+        ),
+        (
+            table && text_layout,
+            "--table with --format or --template: --table is a text layout; drop it or the other"
+                .to_string(),
+        ),
+        (
+            plain && text_layout,
+            "--plain with --format or --template: --plain only trims text output; drop it"
+                .to_string(),
+        ),
+        (
+            by_paragraph && (per_chapter || bucket.is_some()),
+            "--by paragraph with --per-chapter or --bucket: choose one way to split the input"
+                .to_string(),
+        ),
+        (
+            every.is_some() && output_encoding != Encoding::Utf8,
+            "--output-encoding with --every: NDJSON runs are UTF-8; drop --output-encoding"
+                .to_string(),
+        ),
+        (
+            follow && (every.is_some() || output.is_some()),
+            "--follow with --every or --output: --follow already keeps running; \
+             redirect stdout instead of --output"
+                .to_string(),
+        ),
+        (
+            provenance && sidecars.group_by.is_some(),
+            "--provenance with --group-by: run the grouped report and the provenance report \
+             separately"
+                .to_string(),
+        ),
+        (
+            checkpoint.is_some() && (cache.is_some() || follow || every.is_some()),
+            "--checkpoint/--resume with --cache, --follow, or --every: a checkpoint already \
+             saves progress; drop the other option"
+                .to_string(),
+        ),
+        (
+            jsonrpc && (output.is_some() || cache.is_some()),
+            "--jsonrpc with --output or --cache: --jsonrpc answers on stdout; drop the other \
+             option"
+                .to_string(),
+        ),
+        (
+            timestamp_format.is_some() && matches!(decoder, Decoder::Subtitles),
+            "--timestamp-format with --subtitles: cues have their own times; drop \
+             --timestamp-format"
+                .to_string(),
+        ),
+        (
+            char_ngrams.is_some() && !ignored.is_empty(),
+            format!(
+                "--char-ngrams with {}: character n-grams do not split words, so these have \
+                 no effect; drop them",
+                ignored.join(", ")
+            ),
+        ),
+        (
+            template_path.is_some() && filtered && !count_matching,
+            "--template with line filters: a template lays out the top words only; add \
+             --count-matching or drop the filter"
+                .to_string(),
+        ),
+        (
+            template_path.is_some() && (flag_list.is_some() || print0),
+            "--template with --flag-list or --print0: a template lays out the top words only; \
+             drop the other option"
+                .to_string(),
+        ),
+        (
+            formatted
+                && filtered
+                && !count_matching
+                && !matches!(format, Format::Html | Format::Markdown),
+            format!(
+                "--format {} with line filters: only text, html, and markdown show matched \
+                 lines; add --count-matching or drop the filter",
+                format.name()
+            ),
+        ),
+        (
+            formatted && flag_list.is_some(),
+            format!(
+                "--format {} with --flag-list: flagged words are a text report; drop one",
+                format.name()
+            ),
+        ),
+        (
+            recursive && !matches!(decoder, Decoder::Plain),
+            "--recursive with input decoders: -r reads plain text files; drop the decoder"
+                .to_string(),
+        ),
+        (
+            print0 && (count_matching || format != Format::Text),
+            "--print0 with --count-matching or --format: --print0 prints the matched lines \
+             themselves; drop the other option"
+                .to_string(),
+        ),
+        (
+            file_list.is_some() && (count_matching || line_numbers || format != Format::Text),
+            "--files-with-matches with --count-matching, --line-numbers, or --format: -l/-L \
+             print file names only; drop the other option"
+                .to_string(),
+        ),
+        (
+            only_matching && (count_matching || file_list.is_some()),
+            "--only-matching with --count-matching or --files-with-matches: choose one kind \
+             of filter output"
+                .to_string(),
+        ),
+        (
+            count_matching && split,
+            "--count-matching with --per-chapter, --bucket, or --by: the match count is one \
+             table for the whole input; drop the split"
+                .to_string(),
+        ),
+        (
+            (only.is_some() || no_top) && (print0 || file_list.is_some()),
+            "--only/--no-top with --print0, -l, or -L: those print bare lines or file names \
+             without sections; drop one"
+                .to_string(),
+        ),
+        (
+            every.is_some() && matches!(command, Command::Pick | Command::Daemon),
+            "--every with pick or daemon: those already keep running; drop --every".to_string(),
+        ),
+        (
+            format.is_columnar()
+                && (every.is_some() || append || output_encoding != Encoding::Utf8),
+            "--format parquet/arrow with --every, --append, or --output-encoding: a columnar \
+             file is written once as binary; drop the other option"
+                .to_string(),
+        ),
+        (
+            page.is_some() && (split || speaker.is_some()),
+            "--page with --per-chapter, --bucket, --by, or --group-by-speaker: --page pages \
+             the main top words table; drop one"
+                .to_string(),
+        ),
+        (
+            speaker.is_some() && (split || count_matching),
+            "--group-by-speaker with --per-chapter, --bucket, --by, or --count-matching: \
+             choose one way to split the counts"
+                .to_string(),
+        ),
+        (
+            clipboard && !positionals.is_empty(),
+            format!(
+                "--clipboard with a file ({}): read the clipboard or the files, not both",
+                positionals.first().map_or("", String::as_str)
+            ),
+        ),
+        (
+            config.normalize.is_some() && (case != Case::Fold || stem != Stem::Off),
+            "--case or --stem with a normalize pipeline in --config: the pipeline already \
+             decides case and stems; drop the options or the pipeline"
+                .to_string(),
+        ),
+    ];
+    let conflicts: Vec<&String> = checks
+        .iter()
+        .filter(|(conflict, _)| *conflict)
+        .map(|(_, message)| message)
+        .collect();
+    match conflicts.as_slice() {
+        [] => {}
+        [one] => return Err(format!("conflicting options: {one}")),
+        many => {
+            let lines: Vec<String> = many.iter().map(|m| format!("  {m}")).collect();
+            return Err(format!(
+                "{} conflicting option combinations:\n{}",
+                many.len(),
+                lines.join("\n")
+            ));
+        }
+    }
+
+    if level.is_some() && !log {
+        return Err("--level requires --log".to_string());
+    }
+
+    if per_chapter && !matches!(decoder, Decoder::Epub) {
+        return Err("--per-chapter requires --epub".to_string());
+    }
+    if by_paragraph && (command != Command::Count || log) {
+        return Err("--by paragraph only applies to the default top words mode".to_string());
+    }
+    if longest_lines.is_some() && command != Command::Stats {
+        return Err("--longest-lines requires the stats command".to_string());
+    }
+    if line_endings && command != Command::Stats {
+        return Err("--line-endings requires the stats command".to_string());
+    }
+    if convert.is_some() && !line_endings {
+        return Err("--convert requires stats --line-endings".to_string());
+    }
+    if whitespace && command != Command::Stats {
+        return Err("--whitespace requires the stats command".to_string());
+    }
+    if case_report && command != Command::Stats {
+        return Err(
+            "--case must be fold or preserve (bare --case is the stats report)".to_string(),
+        );
+    }
+    if length_histogram && command != Command::Stats {
+        return Err("--length-histogram requires the stats command".to_string());
+    }
+    if vocab_growth && command != Command::Stats {
+        return Err("--vocab-growth requires the stats command".to_string());
+    }
+    if growth_step.is_some() && !vocab_growth {
+        return Err("--growth-step requires --vocab-growth".to_string());
+    }
+    if char_ngrams.is_some() && (command != Command::Count || log) {
+        return Err("--char-ngrams only applies to the default top words mode".to_string());
+    }
+    if (min_df.is_some() || max_df.is_some()) && (command != Command::Count || log) {
+        return Err("--min-df and --max-df only apply to the default top words mode".to_string());
+    }
+    if (min_df.is_some() || max_df.is_some()) && char_ngrams.is_some() {
+        return Err("--min-df and --max-df count words, not --char-ngrams".to_string());
+    }
+    if only_words.is_some() && (command != Command::Count || log || char_ngrams.is_some()) {
+        return Err("--only-words only applies to the default top words mode".to_string());
+    }
+    if with_positions
+        && (command != Command::Count
+            || log
+            || char_ngrams.is_some()
+            || count_matching
+            || speaker.is_some())
+    {
+        return Err(
+            "--with-positions only applies to the default top words mode (not --char-ngrams, \
+             --count-matching, or --speaker)"
+                .to_string(),
+        );
+    }
+    if with_positions
+        && (template_path.is_some()
+            || format.is_columnar()
+            || cache.is_some()
+            || checkpoint.is_some())
+    {
+        return Err(
+            "--with-positions needs the text, csv, json, markdown, or html table and cannot be \
+             used with --template, --cache, or --checkpoint"
+                .to_string(),
+        );
+    }
+    if command == Command::Report && !matches!(format, Format::Text | Format::Json) {
+        return Err("report supports --format text or json".to_string());
+    }
+    if (rank || padding.is_some()) && !table {
+        return Err("--rank and --padding require --table".to_string());
+    }
+    if table && (!matches!(command, Command::Count | Command::Merge) || log) {
+        return Err("--table only applies to top words and merge".to_string());
+    }
+    if plain && !matches!(command, Command::Count | Command::Merge) {
+        return Err("--plain only applies to top words and merge".to_string());
+    }
+    let table = table.then(|| Layout {
+        rank,
+        padding: padding.unwrap_or(2),
+        plain,
+    });
+    if let Some(path) = &template_path {
+        if format != Format::Text {
+            return Err("--template replaces --format; use one or the other".to_string());
+        }
+        if !matches!(command, Command::Count | Command::Merge) || log {
+            return Err("--template only applies to top words and merge".to_string());
+        }
+        template = Some(Template::load(path)?);
+    }
+    if format != Format::Text && command != Command::Report {
+        let (name, allowed) = match format {
+            Format::Csv => ("csv", "top words, merge, zipf, and query"),
+            Format::Json => (
+                "json",
+                "top words, merge, report, positions, rank, difficulty, and near-dupes",
+            ),
+            Format::Html => ("html", "top words and merge"),
+            Format::Parquet => ("parquet", "top words"),
+            Format::Arrow => ("arrow", "top words"),
+            Format::Dot => ("dot", "graph"),
+            Format::Gexf => ("gexf", "graph"),
+            _ => ("markdown", "top words and merge"),
+        };
+        if !format_applies(command, format, log) {
+            return Err(format!("--format {name} only applies to {allowed}"));
+        }
+    }
+    if command != Command::Count || log || format == Format::Csv || format.is_columnar() {
+        if proper_nouns {
+            return Err(
+                "--proper-nouns only applies to the default top words text output".to_string(),
+            );
+        }
+        if acronyms {
+            return Err("--acronyms only applies to the default top words text output".to_string());
+        }
+        if urls || emails {
+            return Err(
+                "--urls/--emails only apply to the default top words text output".to_string(),
+            );
+        }
+        if !analyzers.is_empty() {
+            return Err(
+                "--external-analyzer only applies to the default top words text output".to_string(),
+            );
+        }
+    }
+    if nul && !filtered {
+        return Err("-0 requires --contains, --query, --phrase, --near, or --regex".to_string());
+    }
+    let substitution = match (from, to) {
+        (Some(from), Some(to)) if command == Command::Replace => {
+            regex::check_template(&to, &from).map_err(|e| format!("--to {e}"))?;
+            Some((from, to))
+        }
+        (None, None) if command != Command::Replace => None,
+        _ if command == Command::Replace => {
+            return Err("replace requires --from REGEX and --to TEXT".to_string());
+        }
+        _ => return Err("--from and --to require the replace command".to_string()),
+    };
+    if write && command != Command::Replace {
+        return Err("--write requires the replace command".to_string());
+    }
+    match (&sql_statement, command) {
+        (None, Command::Query) => return Err("query requires --sql STATEMENT".to_string()),
+        (Some(_), c) if c != Command::Query => {
+            return Err("--sql requires the query command".to_string())
+        }
+        _ => {}
+    }
+    let walks_trees = matches!(
+        command,
+        Command::Todos | Command::LintWs | Command::NearDupes
+    );
+    if fix && command != Command::LintWs {
+        return Err("--fix requires the lint-ws command".to_string());
+    }
+    if walk.follow_symlinks && !recursive && !walks_trees {
+        return Err(
+            "--follow-symlinks requires --recursive, todos, lint-ws, or near-dupes".to_string(),
+        );
+    }
+    if walk.hidden && !recursive && !walks_trees {
+        return Err("--hidden requires --recursive, todos, lint-ws, or near-dupes".to_string());
+    }
+    let by_extension = !walk.extensions.is_empty() || !walk.excluded.is_empty();
+    if by_extension && !recursive && !walks_trees {
+        return Err(
+            "--ext and --exclude-ext require --recursive, todos, lint-ws, or near-dupes"
+                .to_string(),
+        );
+    }
+    let by_mtime = walk.modified_since.is_some() || walk.modified_before.is_some();
+    if by_mtime && !recursive && !walks_trees {
+        return Err(
+            "--modified-since and --modified-before require --recursive, todos, lint-ws, or near-dupes"
+                .to_string(),
+        );
+    }
+    if max_filesize.is_some() && !recursive {
+        return Err("--max-filesize requires --recursive".to_string());
+    }
+    if recursive {
+        if command != Command::Count || log {
+            return Err("--recursive only applies to the default mode".to_string());
+        }
+        if clipboard || positionals.iter().any(|p| p == "-") {
+            return Err(
+                "--recursive reads files and directories, not stdin or the clipboard".to_string(),
+            );
+        }
+    }
+    if print0 && (command != Command::Count || log || !filtered) {
+        return Err(
+            "--print0 requires the default mode with --contains, --query, --phrase, --near, or --regex"
+                .to_string(),
+        );
+    }
+    if file_list.is_some() {
+        if command != Command::Count || log || !filtered {
+            return Err(
+                "--files-with-matches requires the default mode with --contains, --query, --phrase, --near, or --regex"
+                    .to_string(),
+            );
+        }
+        if max_matches.is_some() || sample_matches.is_some() {
+            return Err("--max-matches and --sample-matches count lines, not files".to_string());
+        }
+    }
+    if only_matching
+        && (command != Command::Count || log || (contains.is_empty() && regex.is_none()))
+    {
+        return Err(
+            "--only-matching requires the default mode with --contains or --regex".to_string(),
+        );
+    }
+    if let Some(template) = &replace {
+        let (Some(pattern), true) = (&regex, only_matching) else {
+            return Err("--replace requires --only-matching and --regex".to_string());
+        };
+        regex::check_template(template, pattern).map_err(|e| format!("--replace {e}"))?;
+    }
+    if count_matching {
+        if command != Command::Count || log {
+            return Err("--count-matching only applies to the default top words mode".to_string());
+        }
+        if !filtered {
+            return Err(
+                "--count-matching requires --contains, --query, --phrase, --near, or --regex"
+                    .to_string(),
+            );
+        }
+        if line_numbers {
+            return Err("--line-numbers has no effect with --count-matching".to_string());
+        }
+    }
+    if line_numbers && (command != Command::Count || log) {
+        return Err("--line-numbers only applies to the default filter output".to_string());
+    }
+    if follow {
+        if command != Command::Count || log || recursive {
+            return Err("--follow only applies to the default mode".to_string());
+        }
+        if positionals.len() != 1 || positionals[0] == "-" || clipboard {
+            return Err("--follow watches exactly one FILE".to_string());
+        }
+    }
+    if provenance {
+        let reports = matches!(command, Command::Count | Command::Merge | Command::Report) && !log;
+        if !reports || !matches!(format, Format::Json | Format::Html) || template_path.is_some() {
+            return Err(
+                "--provenance applies to json or html output of top words, merge, and report"
+                    .to_string(),
+            );
+        }
+    }
+    if sidecars.is_active() && command != Command::Report {
+        return Err(
+            "--tag, --since, --until, and --group-by require the report command".to_string(),
+        );
+    }
+    let sections_given = only.is_some() || no_top;
+    let mut sections = only.unwrap_or_default();
+    if no_top {
+        sections.top = false;
+    }
+    if sections_given {
+        let prints_lines = filtered && !count_matching;
+        if command != Command::Count || log || format != Format::Text || template_path.is_some() {
+            return Err("--only and --no-top apply to the default text output".to_string());
+        }
+        if only.is_some_and(|only| only.filter) && !prints_lines {
+            return Err(
+                "--only filter requires --contains, --query, --phrase, --near, or --regex"
+                    .to_string(),
+            );
+        }
+        if !(sections.top || sections.stats || sections.filter && prints_lines) {
+            return Err(
+                "--no-top leaves nothing to print; add a filter option or --only stats".to_string(),
+            );
+        }
+    }
+    if checkpoint.is_some() {
+        let plain = command == Command::Count
+            && !log
+            && !filtered
+            && char_ngrams.is_none()
+            && min_df.is_none()
+            && max_df.is_none()
+            && !per_chapter
+            && bucket.is_none()
+            && !by_paragraph
+            && speaker.is_none()
+            && !(proper_nouns || acronyms || urls || emails)
+            && analyzers.is_empty()
+            && flag_list.is_none()
+            && export_stopwords.is_none();
+        if !plain {
+            return Err(
+                "--checkpoint and --resume apply to plain top words (no filters, sections, \
+                 or extra tables)"
+                    .to_string(),
+            );
+        }
+        if clipboard || positionals.is_empty() || positionals.iter().any(|p| p == "-") {
+            return Err(
+                "--checkpoint and --resume read files, not stdin or the clipboard".to_string(),
+            );
+        }
+    }
+    if jsonrpc {
+        if command != Command::Count || log || follow || every.is_some() {
+            return Err("--jsonrpc only applies to the default mode".to_string());
+        }
+        if !positionals.is_empty() || clipboard {
+            return Err(
+                "--jsonrpc reads requests on stdin; send text or path in each request instead of input files"
+                    .to_string(),
+            );
+        }
+    }
+    if !alert_specs.is_empty() && !follow {
+        return Err("--alert requires --follow".to_string());
+    }
+    if alert_cmd.is_some() && alert_specs.is_empty() {
+        return Err("--alert-cmd requires --alert".to_string());
+    }
+    if append && output.is_none() {
+        return Err("--append requires --output".to_string());
+    }
+    if format.is_columnar() {
+        if output.is_none() && io::stdout().is_terminal() {
+            return Err(
+                "--format parquet and arrow write binary data; use --output PATH or redirect stdout"
+                    .to_string(),
+            );
+        }
+        // 열 형식 파일은 표 전체를 읽으려고 쓰므로 --top을 주지 않으면 순위 전체를 담는다.
+        if !top_given {
+            top_n = 0;
+        }
+    }
+    // 노드 5개짜리 그래프는 볼 것이 없으므로 graph의 기본 노드 수는 따로 둔다.
+    if command == Command::Graph && !top_given {
+        top_n = 30;
+    }
+    if page.is_some() {
+        if page_size.is_none() {
+            return Err("--page requires --page-size".to_string());
+        }
+        if command != Command::Count || log {
+            return Err("--page only applies to the default top words mode".to_string());
+        }
+        if !top_given {
+            top_n = 0;
+        }
+    }
+    if page_marker.is_some() && page.is_none() {
+        return Err("--page-marker requires --page".to_string());
+    }
+    // 용어 목록 점검은 0인 단어까지 봐야 하므로 목록 전체를 싣는다.
+    if only_words.is_some() && !top_given {
+        top_n = 0;
+    }
+    if speaker.is_some() && (command != Command::Count || log) {
+        return Err("--group-by-speaker only applies to the default top words mode".to_string());
+    }
+    if timestamp_format.is_some() && bucket.is_none() {
+        return Err("--timestamp-format requires --bucket".to_string());
+    }
+    if bucket.is_some() && !matches!(decoder, Decoder::Subtitles) && timestamp_format.is_none() {
+        return Err("--bucket requires --subtitles or --timestamp-format".to_string());
+    }
+
+    if (size.is_some() || vocab.is_some() || zipf.is_some()) && command != Command::GenCorpus {
+        return Err("--size, --vocab and --zipf require the gen-corpus command".to_string());
+    }
+    if command == Command::GenCorpus && (positionals.len() != 1 || clipboard) {
+        return Err("usage: gen-corpus PATH [--size SIZE] [--vocab N] [--zipf S]".to_string());
+    }
+    if command == Command::Split {
+        if by_tokens.is_none() {
+            return Err("split requires --by-tokens N".to_string());
+        }
+        let one_file = positionals.len() == 1 && positionals[0] != "-" && !clipboard;
+        if prefix.is_none() && !one_file {
+            return Err("split needs --prefix PATH unless the input is a single file".to_string());
+        }
+    } else if by_tokens.is_some() || stratify.is_some() || prefix.is_some() {
+        return Err("--by-tokens, --stratify, and --prefix require the split command".to_string());
+    }
+    if (sample.is_some() || uniform) && command != Command::SampleWords {
+        return Err("--n and --uniform require the sample-words command".to_string());
+    }
+    if (python.is_some() || python_script.is_some() || runs.is_some())
+        && command != Command::CompareImpl
+    {
+        return Err("--python, --python-script and --runs require compare-impl".to_string());
+    }
+    if command == Command::Difficulty && wordlist.is_none() {
+        return Err("difficulty requires --wordlist PATH".to_string());
+    }
+    if command != Command::Difficulty && wordlist.is_some() {
+        return Err("--wordlist requires the difficulty command".to_string());
+    }
+    if command == Command::SelfUpdate {
+        if release_url.is_none() {
+            return Err("self-update requires --release-url URL".to_string());
+        }
+        if !positionals.is_empty() || clipboard {
+            return Err("self-update takes no input files".to_string());
+        }
+    } else if release_url.is_some() || pubkey.is_some() || check_update {
+        return Err("--release-url, --pubkey, and --check require self-update".to_string());
+    }
+    if command == Command::CompareImpl {
+        if positionals.len() != 1 || positionals[0] == "-" || clipboard {
+            return Err("usage: compare-impl FILE [--top N] [--contains KEYWORD]".to_string());
+        }
+        // Python 스크립트는 이 두 옵션만 안다. most_common(0)은 빈 목록이라 --top 0도 뜻이 다르다.
+        if top_n == 0 {
+            return Err("compare-impl needs --top N above 0".to_string());
+        }
+        if contains.len() > 1 || (filtered && contains.is_empty()) {
+            return Err("compare-impl accepts a single --contains and no other filter".to_string());
+        }
+    }
+    if lang.is_some() && command != Command::Stopwords && number_spec.is_none() {
+        return Err("--lang requires the stopwords command or --number-format".to_string());
+    }
+    if command == Command::Stopwords && lang.as_deref().is_some_and(|l| l != "en") {
+        return Err("--lang: only en stopwords are built in".to_string());
+    }
+    let number_format = match &number_spec {
+        Some(spec) => NumberFormat::parse(spec, lang.as_deref().unwrap_or("en"))?,
+        None => NumberFormat::PLAIN,
+    };
+    let stopwords_action = match positionals.first().map(String::as_str) {
+        _ if command != Command::Stopwords => StopwordsAction::List,
+        Some("list") if positionals.len() == 1 => StopwordsAction::List,
+        Some("diff") if positionals.len() == 2 && positionals[1] != "-" => StopwordsAction::Diff,
+        _ => return Err("usage: stopwords list [--lang en] | stopwords diff FILE".to_string()),
+    };
+    if command == Command::Stopwords {
+        positionals.remove(0);
+    }
+    if command == Command::Cache {
+        if positionals != ["clear"] {
+            return Err("usage: cache clear --cache DIR".to_string());
+        }
+        if cache.is_none() {
+            return Err("cache clear requires --cache DIR".to_string());
+        }
+        positionals.clear();
+    }
+    // --no-cache는 별칭 등으로 붙은 --cache를 이번 실행에만 끈다.
+    if no_cache && command != Command::Cache {
+        cache = None;
+    }
+
+    let source = match (positionals.as_slice(), clipboard) {
+        ([], false)
+            if jsonrpc
+                || matches!(
+                    command,
+                    Command::Cache | Command::Stopwords | Command::SelfUpdate
+                ) =>
+        {
+            Source::Files(Vec::new())
+        }
+        ([], true) => Source::Clipboard,
+        ([], false) => return Err(format!("missing input file\n{USAGE}")),
+        // --clipboard와 파일을 함께 준 경우는 위의 충돌 검사에서 걸렀다.
+        (files, _) => {
+            if files.iter().filter(|f| *f == "-").count() > 1 {
+                return Err("stdin (-) can only be given once".to_string());
+            }
+            Source::Files(files.to_vec())
+        }
+    };
+    if command == Command::Subtract && positionals.len() != 2 {
+        return Err("subtract requires exactly two files: A B".to_string());
+    }
+    if command == Command::Intersect && positionals.len() < 2 {
+        return Err("intersect requires at least two files".to_string());
+    }
+
+    if matches!(command, Command::Trend | Command::Positions | Command::Rank) {
+        if word_args.is_empty() {
+            return Err(format!("{} requires --word WORD", command.name()));
+        }
+        if word_args.len() > 1 && command != Command::Rank {
+            return Err(format!("{} takes a single --word", command.name()));
+        }
+    } else if !word_args.is_empty() {
+        return Err("--word requires the trend, positions, or rank command".to_string());
+    }
+    if (words.is_some() || order.is_some()) && command != Command::Generate {
+        return Err("--words and --order require the generate command".to_string());
+    }
+    if buckets.is_some() && !matches!(command, Command::Trend | Command::Heatmap) {
+        return Err("--buckets requires the trend or heatmap command".to_string());
+    }
+    if window.is_some() && !matches!(command, Command::Graph | Command::Bursts) {
+        return Err("--window requires the graph or bursts command".to_string());
+    }
+    if command == Command::Drift && baseline.is_none() {
+        return Err("drift requires --baseline PATH".to_string());
+    }
+    if command != Command::Drift && baseline.is_some() {
+        return Err("--baseline requires the drift command".to_string());
+    }
+    if !matches!(command, Command::Drift | Command::NearDupes) && threshold.is_some() {
+        return Err("--threshold requires the drift or near-dupes command".to_string());
+    }
+    if command == Command::NearDupes && threshold.is_some_and(|t| t > 1.0) {
+        return Err("--threshold for near-dupes is a similarity between 0 and 1".to_string());
+    }
+    if command != Command::NearDupes && shingle.is_some() {
+        return Err("--shingle requires the near-dupes command".to_string());
+    }
+    if command == Command::Daemon && socket.is_none() {
+        return Err("daemon requires --socket PATH".to_string());
+    }
+    if command != Command::Daemon && (socket.is_some() || metrics.is_some()) {
+        return Err("--socket and --metrics require the daemon command".to_string());
+    }
+    if command != Command::Daemon && rate_limit.is_some() {
+        return Err("--rate-limit requires the daemon command".to_string());
+    }
+    if command != Command::Daemon && !jsonrpc && max_request.is_some() {
+        return Err("--max-request only applies to daemon and --jsonrpc".to_string());
+    }
+    if root.is_some() && command != Command::Daemon && !jsonrpc {
+        return Err("--root only applies to daemon and --jsonrpc".to_string());
+    }
+    if below.is_some() && command != Command::Subtract {
+        return Err("--below requires the subtract command".to_string());
+    }
+    if command == Command::Heatmap && !filtered {
+        return Err(
+            "heatmap requires a filter (--contains, --query, --phrase, --near, --regex)"
+                .to_string(),
+        );
+    }
+
+    if stem == Stem::Lang(Language::Korean) && !unicode {
+        return Err("--stem ko requires --unicode (Hangul is not an ASCII letter)".to_string());
+    }
+    let script = script_path.as_deref().map(script::load).transpose()?;
+    if let Some(pipeline) = &config.normalize {
+        let needs_unicode = pipeline
+            .steps
+            .iter()
+            .any(|s| matches!(s, Step::StripDiacritics | Step::Stem(Language::Korean)));
+        if needs_unicode && !unicode {
+            return Err(
+                "strip_diacritics and stem:ko in normalize require --unicode (accented letters and Hangul are not ASCII letters)"
+                    .to_string(),
+            );
+        }
+    }
+    // 파싱한 파이프라인은 실행이 끝날 때까지 쓰므로 한 번 leak해 Tokenizer가 Copy로 들고 다니게 한다.
+    let pipeline = config.normalize.map(|p| &*Box::leak(Box::new(p)));
+    let tokenizer = Tokenizer {
+        unicode,
+        emoji,
+        hyphens,
+        contractions,
+        case,
+        numbers,
+        normalization,
+        strip_accents,
+        transliterate,
+        stem,
+        pipeline,
+    };
+
+    if !assert_specs.is_empty() && command == Command::Merge {
+        return Err("--assert checks the input text and cannot be used with merge".to_string());
+    }
+    let assertions = assert_specs
+        .iter()
+        .map(|spec| Assertion::parse(spec, tokenizer))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let alerts = alert_specs
+        .iter()
+        .map(|spec| alert::Rule::parse(spec, tokenizer))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // 정규화는 빌더가 맡는다(패턴 줄은 그대로 넘겨야 *가 남는다).
+    let stopwords = match &stopwords_file {
+        Some(path) => wordlist::entries(path, "stopwords file")?,
+        None => Vec::new(),
+    };
+    let mut analysis = Analysis::builder()
+        .top(top_n)
+        .sort(sort)
+        .tie_break(tie_break)
+        .collation(collation)
+        .stopwords(stopwords)
+        .tokenizer(tokenizer);
+    if let Some(path) = &keep_file {
+        analysis = analysis.keep(wordlist::entries(path, "keep file")?);
+    }
+    // 패턴 줄은 거르기만 하고 0 행은 만들지 않는다(어떤 단어를 적을지 알 수 없다).
+    let mut vocabulary = Vec::new();
+    if let Some(path) = &only_words {
+        let entries = wordlist::entries(path, "word list")?;
+        let mut seen = HashSet::new();
+        for entry in entries.iter().filter(|entry| !is_glob(entry)) {
+            for word in normalize_words(entry, tokenizer) {
+                if seen.insert(word.clone()) {
+                    vocabulary.push(word);
+                }
+            }
+        }
+        analysis = analysis.keep(entries);
+    }
+    let analysis = analysis.build();
+
+    // --word도 본문과 같은 규칙으로 정규화해 두어야 "Rust"가 "rust"와 맞는다.
+    let mut lookup = Vec::new();
+    for text in &word_args {
+        match normalize_words(text, tokenizer).as_slice() {
+            [single] => lookup.push(single.clone()),
+            _ => return Err(format!("--word must be a single word, got {text:?}")),
+        }
+    }
+    let word = lookup.first().cloned();
+    let mut strata = Vec::new();
+    for text in stratify.iter().flat_map(|list| list.split(',')) {
+        match normalize_words(text, tokenizer).as_slice() {
+            [single] if !strata.contains(single) => strata.push(single.clone()),
+            [_] => {}
+            _ => return Err(format!("--stratify takes single words, got {text:?}")),
+        }
+    }
+
+    // 구문은 --unicode 설정이 확정된 뒤에 같은 규칙으로 토큰화해야 줄 쪽과 비교가 맞는다.
+    let phrase = match phrase {
+        Some(text) => {
+            let words = normalize_words(&text, tokenizer);
+            if words.is_empty() {
+                return Err(format!("--phrase has no words: {text:?}"));
+            }
+            Some(words)
+        }
+        None => None,
+    };
+
+    let near = match near {
+        Some(spec) => Some(Near::parse(&spec, tokenizer)?),
+        None => None,
+    };
+
+    if match_stems && (contains.is_empty() || stem == Stem::Off) {
+        return Err("--match-stems requires --contains and --stem".to_string());
+    }
+    let contains_stems = if match_stems {
+        let mut stems = Vec::new();
+        for keyword in &contains {
+            let words = normalize_words(keyword, tokenizer);
+            if words.is_empty() {
+                return Err(format!("--contains has no words to stem: {keyword:?}"));
+            }
+            stems.push(words);
+        }
+        Some(stems)
+    } else {
+        None
+    };
+
+    // HTML 보고서에 ANSI 색 코드가 섞이지 않게 한다.
+    let color = color_mode.enabled(output.is_some()) && format != Format::Html && !plain;
+    let truncate =
+        (!no_truncate && !print0 && !plain && output.is_none() && io::stdout().is_terminal())
+            .then(term::terminal_cols)
+            .flatten();
+
+    Ok(Args {
+        argv: args[1..].to_vec(),
+        command,
+        source,
+        decoder,
+        sentences,
+        words: words.unwrap_or(200),
+        order: order.unwrap_or(2),
+        corpus: corpus::Spec {
+            size: size.unwrap_or(10 << 20),
+            vocab: vocab.unwrap_or(50_000),
+            exponent: zipf.unwrap_or(1.1),
+        },
+        sample: sample.unwrap_or(50),
+        uniform,
+        python: python.unwrap_or_else(|| "python3".to_string()),
+        python_script: python_script.unwrap_or_else(|| crosscheck::SCRIPT.to_string()),
+        runs: runs.unwrap_or(3),
+        word,
+        lookup,
+        buckets: buckets.unwrap_or(10),
+        window: window.unwrap_or(if command == Command::Bursts { 500 } else { 5 }),
+        below: below.unwrap_or(1),
+        dict,
+        wordlist,
+        reference,
+        baseline,
+        stopwords_action,
+        number_format,
+        threshold: threshold.unwrap_or(if command == Command::NearDupes {
+            0.8
+        } else {
+            0.2
+        }),
+        shingle: shingle.unwrap_or(5),
+        by_tokens: by_tokens.unwrap_or(0),
+        stratify: strata,
+        prefix,
+        socket,
+        metrics,
+        filter: LineFilter {
+            contains,
+            contains_stems,
+            query,
+            phrase,
+            near,
+            regex,
+            tokenizer,
+            max_matches,
+            sample_matches,
+            nul,
+        },
+        pager,
+        page_size,
+        page,
+        page_marker: page_marker.unwrap_or_else(|| {
+            "-- page {page} of {pages} (rows {first}-{last} of {total}) --".to_string()
+        }),
+        explain,
+        analysis,
+        tokenizer,
+        script,
+        template,
+        table,
+        plain,
+        log,
+        level,
+        per_chapter,
+        by_paragraph,
+        speaker,
+        bucket,
+        timestamp_format,
+        timing,
+        fail_fast,
+        assertions,
+        resource_stats,
+        summary,
+        provenance,
+        sidecars,
+        output,
+        append,
+        output_encoding,
+        every,
+        follow,
+        jsonrpc,
+        root,
+        release_url,
+        pubkey,
+        check_update,
+        rate_limit,
+        max_request,
+        sections,
+        alerts,
+        alert_cmd,
+        journal,
+        length_histogram,
+        case_report,
+        whitespace,
+        fix,
+        substitution,
+        sql: sql_statement,
+        write,
+        longest_lines,
+        line_endings,
+        convert,
+        vocab_growth: vocab_growth.then(|| growth_step.unwrap_or(1000)),
+        char_ngrams,
+        min_df,
+        vocabulary,
+        max_df,
+        format,
+        line_numbers,
+        count_matching,
+        print0,
+        file_list,
+        only_matching,
+        replace,
+        recursive,
+        max_filesize,
+        threads,
+        hasher,
+        max_vocab,
+        seed,
+        walk,
+        proper_nouns,
+        acronyms,
+        urls,
+        emails,
+        analyzers,
+        flag_list,
+        export_stopwords,
+        with_positions,
+        cache,
+        checkpoint,
+        resume,
+        color,
+        truncate,
+    })
+}
@@ -0,0 +1,225 @@
+// 로캘 인지 정렬(collation).
+// Python이라면 locale.strxfrm()이나 PyICU를 쓰겠지만, 여기서는 외부 의존성 없이
+// "1차 키(기본 글자) → 2차 키(악센트) → 바이트 순서" 3단계 비교를 직접 구현한다.
+
+use std::cmp::Ordering;
+
+use crate::normalization;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collation {
+    // 기존 동작: UTF-8 바이트 순서.
+    Bytes,
+    // 라틴 악센트를 기본 글자로 접어서 비교 (café는 cafe 바로 뒤).
+    Latin,
+    // Latin 규칙 + 독일어 ß = ss.
+    German,
+    // Latin 규칙 + 한글 자모 분해 순서 (ㄱ < 가 < 각 < 나).
+    Korean,
+}
+
+impl Collation {
+    pub fn parse(name: &str) -> Result<Collation, String> {
+        match name {
+            "bytes" | "none" => Ok(Collation::Bytes),
+            "en" | "latin" => Ok(Collation::Latin),
+            "de" => Ok(Collation::German),
+            "ko" => Ok(Collation::Korean),
+            other => Err(format!(
+                "unknown collation: {other} (expected ko, de, en or bytes)"
+            )),
+        }
+    }
+
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        if *self == Collation::Bytes {
+            return a.cmp(b);
+        }
+        let ka = self.sort_key(a);
+        let kb = self.sort_key(b);
+        ka.cmp(&kb).then_with(|| a.cmp(b))
+    }
+
+    // (1차 가중치 열, 2차 가중치 열). 1차가 같을 때만 2차를 본다.
+    fn sort_key(&self, s: &str) -> (Vec<u32>, Vec<u32>) {
+        let mut primary = Vec::with_capacity(s.len());
+        let mut secondary = Vec::with_capacity(s.len());
+        for c in s.chars() {
+            if *self == Collation::Korean {
+                if let Some(jamo) = hangul_weights(c) {
+                    primary.extend(jamo);
+                    secondary.push(0);
+                    continue;
+                }
+            }
+            if *self == Collation::German && c == 'ß' {
+                primary.extend([letter_weight('s'), letter_weight('s')]);
+                secondary.push(1);
+                continue;
+            }
+            match fold_latin(c) {
+                Some((base, accent)) => {
+                    for b in base.chars() {
+                        primary.push(letter_weight(b));
+                    }
+                    secondary.push(accent);
+                }
+                None => {
+                    primary.push(other_weight(c));
+                    secondary.push(0);
+                }
+            }
+        }
+        (primary, secondary)
+    }
+}
+
+// 숫자 < 라틴 글자 < 그 밖의 문자(한글 포함) 순서가 되도록 가중치 구간을 나눈다.
+const DIGIT_BASE: u32 = 0x100;
+const LETTER_BASE: u32 = 0x200;
+const HANGUL_BASE: u32 = 0x1000;
+const OTHER_BASE: u32 = 0x10_0000;
+
+fn letter_weight(c: char) -> u32 {
+    let lower = c.to_ascii_lowercase();
+    if lower.is_ascii_digit() {
+        DIGIT_BASE + (lower as u32 - '0' as u32)
+    } else if lower.is_ascii_lowercase() {
+        LETTER_BASE + (lower as u32 - 'a' as u32)
+    } else {
+        other_weight(c)
+    }
+}
+
+fn other_weight(c: char) -> u32 {
+    if c.is_ascii_alphanumeric() {
+        letter_weight(c)
+    } else {
+        OTHER_BASE + c as u32
+    }
+}
+
+// 자주 쓰이는 라틴 확장 문자를 (기본 글자, 악센트 번호)로 접는다.
+// 악센트 번호는 2차 비교에만 쓰이므로 "악센트 없는 쪽이 먼저"만 보장하면 된다.
+pub fn fold_latin(c: char) -> Option<(&'static str, u32)> {
+    if c.is_ascii_alphanumeric() {
+        return None;
+    }
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    let folded = match lower {
+        'à' => ("a", 1),
+        'á' => ("a", 2),
+        'â' => ("a", 3),
+        'ã' => ("a", 4),
+        'ä' => ("a", 5),
+        'å' => ("a", 6),
+        'ā' => ("a", 7),
+        'ą' => ("a", 8),
+        'ă' => ("a", 9),
+        'æ' => ("ae", 1),
+        'ç' => ("c", 1),
+        'ć' => ("c", 2),
+        'č' => ("c", 3),
+        'ď' => ("d", 1),
+        'đ' => ("d", 2),
+        'ð' => ("d", 3),
+        'è' => ("e", 1),
+        'é' => ("e", 2),
+        'ê' => ("e", 3),
+        'ë' => ("e", 4),
+        'ē' => ("e", 5),
+        'ę' => ("e", 6),
+        'ě' => ("e", 7),
+        'ğ' => ("g", 1),
+        'ì' => ("i", 1),
+        'í' => ("i", 2),
+        'î' => ("i", 3),
+        'ï' => ("i", 4),
+        'ī' => ("i", 5),
+        'ı' => ("i", 6),
+        'ł' => ("l", 1),
+        'ñ' => ("n", 1),
+        'ń' => ("n", 2),
+        'ň' => ("n", 3),
+        'ò' => ("o", 1),
+        'ó' => ("o", 2),
+        'ô' => ("o", 3),
+        'õ' => ("o", 4),
+        'ö' => ("o", 5),
+        'ø' => ("o", 6),
+        'ō' => ("o", 7),
+        'ő' => ("o", 8),
+        'œ' => ("oe", 1),
+        'ř' => ("r", 1),
+        'ś' => ("s", 1),
+        'š' => ("s", 2),
+        'ş' => ("s", 3),
+        'ß' => ("ss", 1),
+        'ť' => ("t", 1),
+        'ţ' => ("t", 2),
+        'þ' => ("th", 1),
+        'ù' => ("u", 1),
+        'ú' => ("u", 2),
+        'û' => ("u", 3),
+        'ü' => ("u", 4),
+        'ū' => ("u", 5),
+        'ů' => ("u", 6),
+        'ű' => ("u", 7),
+        'ý' => ("y", 1),
+        'ÿ' => ("y", 2),
+        'ź' => ("z", 1),
+        'ż' => ("z", 2),
+        'ž' => ("z", 3),
+        _ => return None,
+    };
+    Some(folded)
+}
+
+// "Café" -> "Cafe". fold_latin()이 아는 글자를 먼저 바꾸고, 모르는 글자("ậ", "ώ")는
+// 표준 분해로 악센트를 떼어 낸다. 대문자는 대문자로 둔다.
+// 분해형(e + U+0301)으로 들어온 결합 악센트도 지운다.
+pub fn strip_diacritics(word: &str) -> String {
+    let mut out = String::with_capacity(word.len());
+    for c in word.chars() {
+        if ('\u{0300}'..='\u{036F}').contains(&c) {
+            continue;
+        }
+        match fold_latin(c) {
+            Some((base, _)) if c.is_uppercase() => out.push_str(&base.to_uppercase()),
+            Some((base, _)) => out.push_str(base),
+            None => out.push(normalization::base_letter(c).unwrap_or(c)),
+        }
+    }
+    out
+}
+
+// 한글 음절(가..힣)은 초성/중성/종성 인덱스로, 호환 자모(ㄱ..ㅎ)는 해당 초성으로 분해한다.
+// 종성 없는 음절은 종성 가중치 0이라 "가 < 각"이 되고, 자모 단독은 중성이 없어 "ㄱ < 가"가 된다.
+fn hangul_weights(c: char) -> Option<Vec<u32>> {
+    const S_BASE: u32 = 0xAC00;
+    const S_COUNT: u32 = 11172;
+    const V_COUNT: u32 = 21;
+    const T_COUNT: u32 = 28;
+
+    let code = c as u32;
+    if (S_BASE..S_BASE + S_COUNT).contains(&code) {
+        let index = code - S_BASE;
+        let l = index / (V_COUNT * T_COUNT);
+        let v = (index % (V_COUNT * T_COUNT)) / T_COUNT;
+        let t = index % T_COUNT;
+        return Some(vec![
+            HANGUL_BASE + l * 0x100,
+            HANGUL_BASE + 0x80 + v,
+            HANGUL_BASE + t,
+        ]);
+    }
+    // 호환 자모 중 초성으로 쓰이는 자음의 순서 (ㄱ ㄲ ㄴ ㄷ ㄸ ㄹ ㅁ ㅂ ㅃ ㅅ ㅆ ㅇ ㅈ ㅉ ㅊ ㅋ ㅌ ㅍ ㅎ).
+    const CHOSEONG: [char; 19] = [
+        'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ',
+        'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+    ];
+    CHOSEONG
+        .iter()
+        .position(|&j| j == c)
+        .map(|l| vec![HANGUL_BASE + l as u32 * 0x100])
+}
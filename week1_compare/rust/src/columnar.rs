@@ -0,0 +1,71 @@
+// --format parquet|arrow: 순위표를 pandas/Polars가 형 추측 없이 바로 읽는 열 형식 파일로 쓴다.
+// 열은 csv와 같다: "word"(또는 ngram), "count". 장/구간 표가 있으면 앞에 "section" 열
+// (전체 집계는 "all")이 붙는다. 바이트 출력이라 --output PATH로 쓰거나 stdout을 파일로 돌린다.
+// --top을 주지 않으면 순위 전체를 쓴다.
+
+use std::io::{self, Write};
+
+use crate::arrow;
+use crate::format::Format;
+use crate::output;
+use crate::parquet;
+use crate::render::Table;
+
+pub enum Column<'a> {
+    Utf8(Vec<&'a str>),
+    Int64(Vec<i64>),
+}
+
+pub struct Field<'a> {
+    pub name: &'a str,
+    pub column: Column<'a>,
+}
+
+pub fn encode(format: Format, tables: &[Table]) -> Vec<u8> {
+    let Some(first) = tables.first() else {
+        return Vec::new();
+    };
+    let rows: Vec<(&str, &str, usize)> = tables
+        .iter()
+        .flat_map(|table| {
+            let label = table.section.as_deref().unwrap_or("all");
+            table
+                .rows
+                .iter()
+                .map(move |(word, count)| (label, word.as_str(), *count))
+        })
+        .collect();
+    let mut fields = Vec::new();
+    if tables.iter().any(|t| t.section.is_some()) {
+        fields.push(Field {
+            name: "section",
+            column: Column::Utf8(rows.iter().map(|r| r.0).collect()),
+        });
+    }
+    fields.push(Field {
+        name: first.column,
+        column: Column::Utf8(rows.iter().map(|r| r.1).collect()),
+    });
+    fields.push(Field {
+        name: "count",
+        column: Column::Int64(rows.iter().map(|r| r.2 as i64).collect()),
+    });
+    match format {
+        Format::Arrow => arrow::encode(&fields, rows.len()),
+        _ => parquet::encode(&fields, rows.len()),
+    }
+}
+
+pub fn write(format: Format, tables: &[Table], output: Option<&str>) -> Result<(), String> {
+    let data = encode(format, tables);
+    match output {
+        Some(path) => output::write_bytes(path, &data, false),
+        None => {
+            let mut stdout = io::stdout().lock();
+            stdout
+                .write_all(&data)
+                .and_then(|()| stdout.flush())
+                .map_err(|e| format!("failed to write to stdout: {e}"))
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// --config PATH: 저장소에 함께 올려 두는 설정 파일. TOML의 작은 부분만 읽는다:
+// `key = value`, 한 줄 또는 여러 줄에 걸친 배열, # 주석. 지금 아는 키는 normalize 하나다.
+//   normalize = ["lowercase", "strip_diacritics", "stem:en", "stopwords:en"]
+// Python이라면 tomllib.load()로 읽을 자리지만 외부 크레이트 없이 필요한 만큼만 파싱한다.
+
+use std::collections::BTreeSet;
+use std::fs;
+
+use crate::stopwords;
+use crate::{Language, Pipeline, Step};
+
+#[derive(Debug, Default)]
+pub struct Config {
+    pub normalize: Option<Pipeline>,
+}
+
+pub fn load(path: &str) -> Result<Config, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("failed to read config: {e}"))?;
+    parse(&text).map_err(|(line, message)| format!("{path}:{line}: {message}"))
+}
+
+// 에러는 (줄 번호, 메시지).
+fn parse(text: &str) -> Result<Config, (usize, String)> {
+    let mut config = Config::default();
+    let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+    while let Some((n, line)) = lines.next() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            return Err((n, format!("sections are not supported: {line}")));
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err((n, format!("expected key = value, got {line}")));
+        };
+        // 여러 줄 배열은 닫는 ]가 나올 때까지 이어 붙인다.
+        let mut value = value.trim().to_string();
+        while value.starts_with('[') && !value.ends_with(']') {
+            let Some((_, next)) = lines.next() else {
+                return Err((n, "unclosed [ in array".to_string()));
+            };
+            value.push(' ');
+            value.push_str(strip_comment(next).trim());
+        }
+        match key.trim() {
+            "normalize" => {
+                let steps = array(&value)
+                    .ok_or_else(|| (n, "normalize must be an array of steps".to_string()))?
+                    .iter()
+                    .map(|spec| step(spec).map_err(|e| (n, e)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                config.normalize = Some(Pipeline { steps });
+            }
+            other => return Err((n, format!("unknown key {other}"))),
+        }
+    }
+    Ok(config)
+}
+
+// 따옴표 안의 #은 주석이 아니다.
+pub fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+// ["a", "b"]. 예시처럼 따옴표 없이 [lowercase, stem:en]으로 적어도 받는다.
+pub fn array(value: &str) -> Option<Vec<String>> {
+    let body = value.strip_prefix('[')?.strip_suffix(']')?;
+    Some(
+        body.split(',')
+            .map(|item| item.trim().trim_matches('"').to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
+
+// "stopwords:en"은 내장 영어 목록, 그 밖의 "stopwords:PATH"는 한 줄에 하나씩 적은 파일이다.
+// 목록의 단어는 정규화하지 않고 그 단계에 도착한 토큰과 그대로 비교한다.
+fn step(spec: &str) -> Result<Step, String> {
+    let (name, arg) = match spec.split_once(':') {
+        Some((name, arg)) => (name, Some(arg)),
+        None => (spec, None),
+    };
+    match (name, arg) {
+        ("lowercase", None) => Ok(Step::Lowercase),
+        ("strip_diacritics", None) => Ok(Step::StripDiacritics),
+        ("stem", Some(code)) => Language::parse(code)
+            .map(Step::Stem)
+            .ok_or_else(|| format!("stem step must be stem:en, stem:ko, stem:de, or stem:fr, got {spec}")),
+        ("stopwords", Some("en")) => Ok(Step::Stopwords(
+            "en".to_string(),
+            stopwords::ENGLISH.iter().map(|w| w.to_string()).collect(),
+        )),
+        ("stopwords", Some(path)) => {
+            let content = fs::read_to_string(path)
+                .map_err(|e| format!("failed to read stopwords {path}: {e}"))?;
+            let words: BTreeSet<String> = content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(String::from)
+                .collect();
+            Ok(Step::Stopwords(path.to_string(), words))
+        }
+        _ => Err(format!(
+            "unknown normalize step {spec} (expected lowercase, strip_diacritics, stem:LANG, or stopwords:en|PATH)"
+        )),
+    }
+}
@@ -0,0 +1,47 @@
+// 영어 축약형 풀기(--contractions expand).
+// Python이라면 contractions 패키지의 사전을 쓰겠지만, 여기서는 접미사 규칙과
+// 규칙에서 벗어나는 몇 단어만 직접 둔다. 입력은 이미 소문자이고 아포스트로피는 '로 맞춰져 있다.
+
+// 접미사 규칙으로 풀면 틀리는 단어("won't" → "wo not").
+const IRREGULAR: [(&str, &[&str]); 5] = [
+    ("won't", &["will", "not"]),
+    ("can't", &["can", "not"]),
+    ("shan't", &["shall", "not"]),
+    ("ain't", &["is", "not"]),
+    ("let's", &["let", "us"]),
+];
+
+// 's가 "is"인 경우. 나머지 's는 소유격으로 보고 떼어 낸다("john's" → "john").
+const IS_BEFORE_S: [&str; 10] = [
+    "it", "he", "she", "that", "what", "there", "here", "who", "where", "how",
+];
+
+const SUFFIXES: [(&str, &str); 6] = [
+    ("n't", "not"),
+    ("'re", "are"),
+    ("'ve", "have"),
+    ("'ll", "will"),
+    ("'d", "would"),
+    ("'m", "am"),
+];
+
+pub fn expand(word: &str) -> Vec<String> {
+    if let Some((_, parts)) = IRREGULAR.iter().find(|(w, _)| *w == word) {
+        return parts.iter().map(|p| p.to_string()).collect();
+    }
+    for (suffix, full) in SUFFIXES {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if !stem.is_empty() {
+                return vec![stem.to_string(), full.to_string()];
+            }
+        }
+    }
+    if let Some(stem) = word.strip_suffix("'s") {
+        if IS_BEFORE_S.contains(&stem) {
+            return vec![stem.to_string(), "is".to_string()];
+        }
+        return vec![stem.to_string()];
+    }
+    // "o'clock", "rock'n'roll"처럼 축약형이 아닌 단어는 그대로 둔다.
+    vec![word.to_string()]
+}
@@ -0,0 +1,76 @@
+// gen-corpus: 벤치마크용 합성 말뭉치. k번째로 흔한 단어가 1/k^s에 비례해 나오도록(지프 법칙)
+// 단어를 뽑아 정확히 --size 바이트를 쓴다. 씨앗이 고정이라(--seed로 바꿀 수 있다) 같은 옵션이면
+// 언제 돌려도 바이트까지 같은 파일이 나온다. Rust 쪽과 Python 쪽 벤치마크에 같은 입력을 주려는 용도다.
+
+use std::io::{self, Write};
+
+use crate::rng::{self, Rng};
+
+pub const SEED: u64 = 0x5EED_C0DE;
+const WORDS_PER_LINE: usize = 12;
+const CONSONANTS: &[u8] = b"bdfghjklmnprstvz";
+const VOWELS: &[u8] = b"aeiou";
+
+pub struct Spec {
+    pub size: u64,
+    pub vocab: usize,
+    pub exponent: f64,
+}
+
+// 순위 k(0부터)의 단어. 자음+모음 음절(80가지)을 k의 80진 자릿수마다 하나씩 붙인다.
+// 흔한 단어일수록 짧아서 실제 글처럼 짧은 단어가 많이 나온다.
+fn word(rank: usize) -> String {
+    let syllables = CONSONANTS.len() * VOWELS.len();
+    let mut n = rank;
+    let mut out = String::new();
+    loop {
+        let s = n % syllables;
+        out.push(CONSONANTS[s / VOWELS.len()] as char);
+        out.push(VOWELS[s % VOWELS.len()] as char);
+        n /= syllables;
+        if n == 0 {
+            return out;
+        }
+        n -= 1;
+    }
+}
+
+// 쓴 단어 수를 돌려준다. 마지막 줄은 크기에 맞춰 자르고 줄바꿈으로 끝낸다.
+pub fn write(spec: &Spec, out: &mut dyn Write) -> io::Result<u64> {
+    let vocabulary: Vec<String> = (0..spec.vocab).map(word).collect();
+    // 누적 가중치에서 이분 탐색으로 뽑는다(단어마다 O(log V)).
+    let mut cumulative = Vec::with_capacity(spec.vocab);
+    let mut total = 0.0;
+    for k in 1..=spec.vocab {
+        total += 1.0 / (k as f64).powf(spec.exponent);
+        cumulative.push(total);
+    }
+    let mut rng = Rng::new(rng::seed().unwrap_or(SEED));
+    let mut written = 0u64;
+    let mut words = 0u64;
+    let mut line = String::new();
+    while written < spec.size {
+        line.clear();
+        for i in 0..WORDS_PER_LINE {
+            if i > 0 {
+                line.push(' ');
+            }
+            let target = rng.unit() * total;
+            let rank = cumulative
+                .partition_point(|&c| c <= target)
+                .min(spec.vocab - 1);
+            line.push_str(&vocabulary[rank]);
+        }
+        line.push('\n');
+        let left = (spec.size - written) as usize;
+        if line.len() > left {
+            // ASCII만 쓰므로 바이트 단위로 잘라도 글자가 깨지지 않는다.
+            line.truncate(left - 1);
+            line.push('\n');
+        }
+        words += line.split_whitespace().count() as u64;
+        out.write_all(line.as_bytes())?;
+        written += line.len() as u64;
+    }
+    Ok(words)
+}
@@ -0,0 +1,266 @@
+// 처음 등장한 순서를 기억하는 빈도표.
+// Python의 Counter(dict 기반)는 삽입 순서를 보존해서 most_common()의 동률이 등장 순서로
+// 정해진다. HashMap은 순서가 실행마다 달라질 수 있으므로 Vec에 (단어, 개수)를 순서대로 두고,
+// 위치만 해시 표로 찾는다. --tie-break first-seen은 이 순서를 그대로 쓴다.
+// 단어는 하나의 큰 String(arena)에 이어 붙여 두고 표에는 번호만 넣는다(interning).
+// 어휘가 수백만 개여도 단어마다 String을 두 번(Vec와 HashMap 키) 할당하지 않는다.
+// --max-vocab N이면 서로 다른 단어가 N개를 넘을 때 개수가 가장 작은 항목부터(같으면 가장 오래 안 나온
+// 것부터) 버린다. 버린 단어가 다시 나오면 0부터 세므로 그 뒤의 개수는 근삿값이다.
+
+use std::cmp::Reverse;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[derive(Debug, Clone)]
+pub struct Counter {
+    // 모든 단어를 처음 본 순서대로 이어 붙인 글자들.
+    arena: String,
+    items: Vec<Entry>,
+    // 열린 주소법 해시 표. 칸마다 items의 번호, 빈 칸은 EMPTY. 길이는 2의 거듭제곱.
+    slots: Vec<u32>,
+    hashing: Hashing,
+    // 서로 다른 단어 수의 상한(--max-vocab).
+    cap: Option<usize>,
+    // add()를 부를 때마다 하나씩 오르는 시각. 같은 개수 중 오래 안 나온 것을 고를 때 쓴다.
+    clock: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    start: usize,
+    len: usize,
+    count: usize,
+    last: u64,
+}
+
+const EMPTY: u32 = u32::MAX;
+
+// 키 해시 방식(--hasher). Fx는 빠르지만 키가 고정이라 해시가 겹치는 단어를 일부러 만들 수 있다.
+// 아무나 질의를 보낼 수 있는 daemon처럼 입력을 믿을 수 없으면 무작위 키의 SipHash를 쓴다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hasher {
+    Fx,
+    Sip,
+}
+
+impl Hasher {
+    pub fn parse(name: &str) -> Option<Hasher> {
+        match name {
+            "fx" => Some(Hasher::Fx),
+            "sip" => Some(Hasher::Sip),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Hasher::Fx => "fx",
+            Hasher::Sip => "sip",
+        }
+    }
+}
+
+static SIP: AtomicBool = AtomicBool::new(false);
+// 0이면 상한이 없다.
+static MAX_VOCAB: AtomicUsize = AtomicUsize::new(0);
+// 모든 Counter가 버린 항목 수의 합.
+static EVICTED: AtomicUsize = AtomicUsize::new(0);
+
+// 이후 만드는 Counter가 쓸 해시 방식. 이미 만든 Counter는 그대로다.
+pub fn use_hasher(hasher: Hasher) {
+    SIP.store(hasher == Hasher::Sip, Ordering::Relaxed);
+}
+
+// 이후 만드는 Counter의 어휘 상한. use_hasher()와 같이 이미 만든 Counter는 그대로다.
+pub fn cap_vocabulary(max: Option<usize>) {
+    MAX_VOCAB.store(max.unwrap_or(0), Ordering::Relaxed);
+}
+
+// 지금까지 상한 때문에 버린 항목 수. 0이 아니면 결과의 개수는 근삿값이다.
+pub fn evicted() -> usize {
+    EVICTED.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone)]
+enum Hashing {
+    Fx,
+    // 표마다 키가 다르다(RandomState::new()).
+    Sip(RandomState),
+}
+
+impl Hashing {
+    fn hash(&self, key: &str) -> usize {
+        match self {
+            Hashing::Fx => fx(key),
+            Hashing::Sip(state) => state.hash_one(key) as usize,
+        }
+    }
+}
+
+// rustc의 FxHash처럼 8바이트씩 곱해 섞는다. 순서는 items가 정하므로 해시 값은 출력에 드러나지 않는다.
+// 곱셈은 아래 비트를 위로만 퍼뜨리므로, 표 크기만큼 아래 비트를 잘라 쓰기 전에 위쪽 비트를 돌려 내린다.
+fn fx(key: &str) -> usize {
+    const K: u64 = 0xf135_7aea_2e62_a9c5;
+    let mut h: u64 = 0;
+    let mut chunks = key.as_bytes().chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(chunk);
+        h = (h ^ u64::from_le_bytes(word)).wrapping_mul(K);
+    }
+    let rest = chunks.remainder();
+    let mut tail = [0u8; 8];
+    tail[..rest.len()].copy_from_slice(rest);
+    h = (h ^ u64::from_le_bytes(tail) ^ key.len() as u64).wrapping_mul(K);
+    h.rotate_left(26) as usize
+}
+
+impl Default for Counter {
+    fn default() -> Counter {
+        let hasher = if SIP.load(Ordering::Relaxed) {
+            Hasher::Sip
+        } else {
+            Hasher::Fx
+        };
+        Counter::with_hasher(hasher)
+    }
+}
+
+impl Counter {
+    pub fn new() -> Counter {
+        Counter::default()
+    }
+
+    pub fn with_hasher(hasher: Hasher) -> Counter {
+        Counter {
+            arena: String::new(),
+            items: Vec::new(),
+            slots: Vec::new(),
+            hashing: match hasher {
+                Hasher::Fx => Hashing::Fx,
+                Hasher::Sip => Hashing::Sip(RandomState::new()),
+            },
+            cap: Some(MAX_VOCAB.load(Ordering::Relaxed)).filter(|&n| n > 0),
+            clock: 0,
+        }
+    }
+
+    pub fn add(&mut self, key: &str, n: usize) {
+        if self.cap.is_some_and(|cap| self.items.len() >= cap) && self.find(key).is_err() {
+            self.evict();
+        }
+        // 채움률 3/4를 넘기 전에 표를 두 배로 늘린다.
+        if (self.items.len() + 1) * 4 > self.slots.len() * 3 {
+            self.grow();
+        }
+        self.clock += 1;
+        match self.find(key) {
+            Ok(id) => {
+                self.items[id].count += n;
+                self.items[id].last = self.clock;
+            }
+            Err(slot) => {
+                self.slots[slot] = self.items.len() as u32;
+                self.items.push(Entry {
+                    start: self.arena.len(),
+                    len: key.len(),
+                    count: n,
+                    last: self.clock,
+                });
+                self.arena.push_str(key);
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> usize {
+        self.find(key).map_or(0, |id| self.items[id].count)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.items.iter().map(|e| (self.key(e), e.count))
+    }
+
+    // 첫 등장 순서의 (키, 개수) 목록. 순위를 매길 때 한 번만 String으로 꺼낸다.
+    pub fn into_vec(self) -> Vec<(String, usize)> {
+        self.items
+            .iter()
+            .map(|e| (self.key(e).to_string(), e.count))
+            .collect()
+    }
+
+    fn key(&self, entry: &Entry) -> &str {
+        &self.arena[entry.start..entry.start + entry.len]
+    }
+
+    // 있으면 Ok(번호), 없으면 Err(넣을 빈 칸).
+    fn find(&self, key: &str) -> Result<usize, usize> {
+        if self.slots.is_empty() {
+            return Err(0);
+        }
+        let mask = self.slots.len() - 1;
+        let mut slot = self.hashing.hash(key) & mask;
+        loop {
+            match self.slots[slot] {
+                EMPTY => return Err(slot),
+                id if self.key(&self.items[id as usize]) == key => return Ok(id as usize),
+                _ => slot = (slot + 1) & mask,
+            }
+        }
+    }
+
+    fn grow(&mut self) {
+        self.rehash((self.slots.len() * 2).max(16));
+    }
+
+    // 상한의 9/10(적어도 하나는 비운다)만 남긴다. 한 번에 여럿을 버려야 새 단어마다 정렬하지 않는다.
+    // 남은 항목은 첫 등장 순서를 그대로 지키고, arena도 남은 단어만으로 다시 채운다.
+    fn evict(&mut self) {
+        let cap = self.cap.unwrap_or(usize::MAX);
+        let keep = (cap - cap / 10).min(cap - 1);
+        let mut order: Vec<usize> = (0..self.items.len()).collect();
+        order.sort_unstable_by_key(|&id| {
+            let entry = &self.items[id];
+            (Reverse(entry.count), Reverse(entry.last))
+        });
+        let mut kept = vec![false; self.items.len()];
+        for &id in &order[..keep] {
+            kept[id] = true;
+        }
+        let mut arena = String::with_capacity(self.arena.len());
+        let items: Vec<Entry> = self
+            .items
+            .iter()
+            .zip(kept)
+            .filter(|(_, kept)| *kept)
+            .map(|(entry, _)| {
+                let start = arena.len();
+                arena.push_str(self.key(entry));
+                Entry { start, ..*entry }
+            })
+            .collect();
+        EVICTED.fetch_add(self.items.len() - items.len(), Ordering::Relaxed);
+        self.arena = arena;
+        self.items = items;
+        self.rehash(self.slots.len());
+    }
+
+    fn rehash(&mut self, size: usize) {
+        self.slots = vec![EMPTY; size];
+        let mask = size - 1;
+        for (id, entry) in self.items.iter().enumerate() {
+            let mut slot = self.hashing.hash(self.key(entry)) & mask;
+            while self.slots[slot] != EMPTY {
+                slot = (slot + 1) & mask;
+            }
+            self.slots[slot] = id as u32;
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// 저장해 둔 빈도표(count file) 읽기. merge 서브커맨드가 쓴다.
+// 형식은 `--all --format csv`의 출력 그대로다: 헤더 "word,count"(또는 "ngram,count").
+// 섹션 열이 있는 표("section,word,count")는 section이 "all"인 행만 읽는다.
+// 원문을 다시 읽지 않고도 샤드별 결과를 합칠 수 있게 하려는 것이다.
+
+use crate::counter::Counter;
+use crate::format::parse_csv_line;
+
+pub fn parse(text: &str, name: &str) -> Result<Counter, String> {
+    let mut lines = text.lines().enumerate();
+    let header = match lines.next() {
+        Some((_, line)) => parse_csv_line(line),
+        None => return Err(format!("{name}: empty count file")),
+    };
+    let column = |wanted: &[&str]| header.iter().position(|h| wanted.contains(&h.as_str()));
+    let (Some(key), Some(count)) = (column(&["word", "ngram"]), column(&["count"])) else {
+        return Err(format!(
+            "{name}: expected a word,count header (from --format csv), got {:?}",
+            header.join(",")
+        ));
+    };
+    let section = column(&["section"]);
+
+    let mut counts = Counter::new();
+    for (i, line) in lines {
+        if line.is_empty() {
+            continue;
+        }
+        let fields = parse_csv_line(line);
+        if section.is_some_and(|s| fields.get(s).map(String::as_str) != Some("all")) {
+            continue;
+        }
+        let (Some(word), Some(n)) = (fields.get(key), fields.get(count)) else {
+            return Err(format!("{name}:{}: missing column", i + 1));
+        };
+        let n = n.parse::<usize>().map_err(|_| {
+            format!(
+                "{name}:{}: count must be a non-negative integer, got {n}",
+                i + 1
+            )
+        })?;
+        counts.add(word, n);
+    }
+    Ok(counts)
+}
@@ -0,0 +1,154 @@
+// compare-impl: 같은 입력으로 함께 들어 있는 Python 스크립트(python/word_count.py)와 이 프로그램을
+// 돌려 출력이 같은지 확인하고 걸린 시간과 최대 RSS를 나란히 보여준다. 이 저장소의 목적인
+// "같은 도구를 두 언어로 짰을 때의 차이"를 손으로 time과 diff를 돌리지 않고 보려는 것이다.
+// 두 쪽 모두 새 프로세스로 띄우므로 시간에는 인터프리터/프로그램 시작 비용이 들어간다.
+// Python 스크립트가 아는 옵션(FILE --top N --contains KW)만 넘기고, Rust 쪽에는 Counter.most_common()과
+// 같은 순서가 되도록 --tie-break first-seen을 더한다.
+
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::resources;
+
+// 빌드한 저장소 안의 스크립트. 다른 곳에서 돌릴 때는 --python-script로 바꾼다.
+pub const SCRIPT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../python/word_count.py");
+
+// 결과를 다 출력한 뒤 종료 코드를 정할 수 있게 main에 알린다(drift.rs와 같은 방식).
+static MISMATCH: AtomicBool = AtomicBool::new(false);
+
+pub fn mismatched() -> bool {
+    MISMATCH.load(Ordering::Relaxed)
+}
+
+pub struct Setup<'a> {
+    pub file: &'a str,
+    pub top: usize,
+    pub contains: Option<&'a str>,
+    pub python: &'a str,
+    pub script: &'a str,
+    pub runs: usize,
+}
+
+pub struct Run {
+    // 여러 번 돌린 것 중 가장 빠른 시간(timeit과 같은 기준)과 가장 큰 RSS.
+    pub wall: Duration,
+    pub peak_rss: Option<usize>,
+    pub output: String,
+}
+
+pub struct Difference {
+    pub line: usize,
+    pub python: String,
+    pub rust: String,
+}
+
+pub struct Comparison {
+    pub python: Run,
+    pub rust: Run,
+    pub difference: Option<Difference>,
+}
+
+pub fn run(setup: &Setup) -> Result<Comparison, String> {
+    let rust =
+        std::env::current_exe().map_err(|e| format!("failed to locate this program: {e}"))?;
+    let mut options = vec![
+        setup.file.to_string(),
+        "--top".to_string(),
+        setup.top.to_string(),
+    ];
+    if let Some(keyword) = setup.contains {
+        options.extend(["--contains".to_string(), keyword.to_string()]);
+    }
+    let python = measure(setup.runs, || {
+        let mut cmd = Command::new(setup.python);
+        cmd.arg(setup.script).args(&options);
+        cmd
+    })
+    .map_err(|e| format!("python ({} {}): {e}", setup.python, setup.script))?;
+    let rust = measure(setup.runs, || {
+        let mut cmd = Command::new(&rust);
+        cmd.args(&options)
+            .args(["--tie-break", "first-seen", "--no-pager"]);
+        cmd
+    })
+    .map_err(|e| format!("rust: {e}"))?;
+    let difference = difference(&python.output, &rust.output);
+    if difference.is_some() {
+        MISMATCH.store(true, Ordering::Relaxed);
+    }
+    Ok(Comparison {
+        python,
+        rust,
+        difference,
+    })
+}
+
+fn measure(runs: usize, command: impl Fn() -> Command) -> Result<Run, String> {
+    let mut best: Option<Run> = None;
+    for _ in 0..runs {
+        let started = Instant::now();
+        let mut child = command()
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to start: {e}"))?;
+        // stderr를 따로 읽지 않으면 자식이 stderr 파이프를 채우고 멈출 수 있다.
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let errors = thread::spawn(move || {
+            let mut text = String::new();
+            let _ = stderr.read_to_string(&mut text);
+            text
+        });
+        let mut output = Vec::new();
+        let _ = child
+            .stdout
+            .take()
+            .expect("stdout is piped")
+            .read_to_end(&mut output);
+        let (ok, peak_rss) =
+            resources::wait_child(&mut child).map_err(|e| format!("failed to wait: {e}"))?;
+        let wall = started.elapsed();
+        let errors = errors.join().unwrap_or_default();
+        if !ok {
+            return Err(format!("exited with an error: {}", errors.trim_end()));
+        }
+        let output = String::from_utf8_lossy(&output).replace("\r\n", "\n");
+        best = Some(match best {
+            Some(b) => Run {
+                wall: b.wall.min(wall),
+                peak_rss: b.peak_rss.max(peak_rss),
+                output: b.output,
+            },
+            None => Run {
+                wall,
+                peak_rss,
+                output,
+            },
+        });
+    }
+    best.ok_or_else(|| "no runs".to_string())
+}
+
+// 처음으로 다른 줄. 한쪽이 먼저 끝났으면 그쪽은 빈 문자열이다.
+fn difference(python: &str, rust: &str) -> Option<Difference> {
+    let mut a = python.lines();
+    let mut b = rust.lines();
+    for line in 1.. {
+        match (a.next(), b.next()) {
+            (None, None) => return None,
+            (x, y) if x == y => continue,
+            (x, y) => {
+                return Some(Difference {
+                    line,
+                    python: x.unwrap_or_default().to_string(),
+                    rust: y.unwrap_or_default().to_string(),
+                })
+            }
+        }
+    }
+    None
+}
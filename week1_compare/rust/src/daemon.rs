@@ -0,0 +1,422 @@
+// daemon 서브커맨드: 입력을 한 번 읽어 토큰화한 결과를 메모리에 올려 두고 Unix 소켓으로 질의에 답한다.
+// 편집기 플러그인이나 스크립트가 부를 때마다 파일을 다시 읽고 토큰화하지 않게 하려는 것이다.
+// Python이라면 socketserver.UnixStreamServer를 쓸 자리다. 요청과 응답 모두 한 줄에 JSON 하나(NDJSON):
+//   {"op":"top","n":10,"file":"a.txt"}      상위 단어 (n 기본값은 --top, file을 주면 그 파일만)
+//   {"op":"count","word":"Rust"}            단어 빈도 (본문과 같은 규칙으로 정규화)
+//   {"op":"lines","query":"error AND NOT timeout","limit":20}
+//                                           --query 문법으로 걸린 줄
+//   {"op":"stats"}                          파일 수, 토큰 수, 서로 다른 단어 수, --max-vocab로 버린 수
+//   {"op":"reload"}                         입력을 다시 읽는다
+//   {"op":"shutdown"}                       소켓 파일을 지우고 끝낸다
+// --metrics ADDR를 주면 ADDR(127.0.0.1:9187 등)의 HTTP /metrics에 Prometheus 계수기를 낸다(metrics.rs).
+// --root DIR를 주면 입력 파일이 DIR 밖에 있을 때 띄우지도, 다시 읽지도 않는다(sandbox.rs).
+// 응답은 {"ok":true,...} 또는 {"ok":false,"error":"..."}.
+// --rate-limit과 --max-request를 넘은 요청은 {"ok":false,"status":429|413,...}로 돌려보낸다(limits.rs).
+// 연결은 --threads개(기본 CPU 수)의 작업 스레드가 나눠 맡는다. 모두 바쁘면 그만큼만 더 받아 두고
+// 나머지는 커널의 대기열에서 기다리게 한다. 요청 없이 READ_TIMEOUT을 넘기거나 응답을
+// WRITE_TIMEOUT 안에 받아 가지 않는 연결은 끊어서, 붙어만 있는 클라이언트가 스레드를 붙잡지 못한다.
+// 질의는 읽기 잠금으로 함께 돌고, reload만 새로 읽은 입력으로 바꿔 끼울 때 잠깐 쓰기 잠금을 잡는다.
+// shutdown은 소켓 파일을 바로 지우고, 이미 열린 다른 연결이 끝나기를 기다려 끝낸다.
+
+use std::collections::HashMap;
+use std::io::{BufReader, ErrorKind, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex, RwLock, RwLockReadGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cli::Args;
+use crate::counter::{self, Counter};
+use crate::filter::{self, LineFilter};
+use crate::input::{self, Source};
+use crate::json::{self, Value};
+use crate::limits::{self, Limiter, Request};
+use crate::metrics;
+use crate::query;
+use crate::search;
+use crate::{count_words, normalize_words, rank_all};
+
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Document {
+    name: String,
+    text: String,
+    counts: Counter,
+    tokens: usize,
+}
+
+struct Corpus {
+    documents: Vec<Document>,
+    total: Counter,
+}
+
+fn load(args: &Args) -> Result<Corpus, String> {
+    // --root: 다시 읽을 때 그 사이 링크로 바뀌었을 수도 있으므로 reload마다 확인하고, 확인한 실제
+    // 경로를 그대로 읽는다(sandbox.rs). 응답의 파일 이름은 명령행에 준 그대로 둔다.
+    let mut names: HashMap<String, String> = HashMap::new();
+    let source = match (&args.root, &args.source) {
+        (Some(root), Source::Files(paths)) => {
+            let mut real = Vec::with_capacity(paths.len());
+            for path in paths {
+                if path == "-" {
+                    real.push(path.clone());
+                    continue;
+                }
+                let resolved = root.resolve(path)?;
+                let resolved = resolved
+                    .to_str()
+                    .ok_or_else(|| format!("{path}: resolved path is not UTF-8"))?
+                    .to_string();
+                names
+                    .entry(resolved.clone())
+                    .or_insert_with(|| path.clone());
+                real.push(resolved);
+            }
+            Source::Files(real)
+        }
+        (_, source) => source.clone(),
+    };
+    let mut documents = Vec::new();
+    let mut total = Counter::new();
+    for (name, text) in input::read_documents(&source, &args.decoder)? {
+        let name = names.get(&name).cloned().unwrap_or(name);
+        let counts = count_words(&text, args.tokenizer);
+        for (word, n) in counts.iter() {
+            total.add(word, n);
+        }
+        let tokens = counts.iter().map(|(_, n)| n).sum();
+        metrics::add_bytes(text.len());
+        documents.push(Document {
+            name,
+            text,
+            counts,
+            tokens,
+        });
+    }
+    Ok(Corpus { documents, total })
+}
+
+pub fn serve(args: &Args, socket: &str) -> Result<(), String> {
+    let corpus = load(args)?;
+    let listener = bind(socket)?;
+    if let Some(addr) = &args.metrics {
+        metrics::serve(addr)?;
+        eprintln!("daemon: metrics on http://{addr}/metrics");
+    }
+    let workers = search::workers(args.threads);
+    eprintln!(
+        "daemon: {} file(s) loaded, listening on {socket} ({workers} worker(s))",
+        corpus.documents.len()
+    );
+    let corpus = RwLock::new(corpus);
+    let limiter = Mutex::new(args.rate_limit.map(Limiter::new));
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::sync_channel::<(u64, UnixStream)>(workers);
+    let receiver = Mutex::new(receiver);
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let next = receiver.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                let Ok((connection, stream)) = next else {
+                    break;
+                };
+                if stop.load(Ordering::SeqCst) {
+                    continue;
+                }
+                // 클라이언트 하나의 I/O 에러로 데몬 전체가 죽지 않도록 연결 단위로만 끊는다.
+                match handle(stream, args, &corpus, &limiter, connection) {
+                    Ok(true) => {
+                        stop.store(true, Ordering::SeqCst);
+                        // accept에서 기다리는 본 스레드를 깨운다.
+                        let _ = UnixStream::connect(socket);
+                    }
+                    Ok(false) => {}
+                    // 시간 안에 요청을 보내거나 응답을 받아 가지 않은 연결.
+                    Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                    Err(e) => eprintln!("daemon: connection error: {e}"),
+                }
+            });
+        }
+        let mut connection = 0;
+        for stream in listener.incoming() {
+            if stop.load(Ordering::SeqCst) {
+                break;
+            }
+            connection += 1;
+            let stream = match stream {
+                Ok(s) => s,
+                Err(e) => {
+                    eprintln!("daemon: accept failed: {e}");
+                    continue;
+                }
+            };
+            let timeouts = stream
+                .set_read_timeout(Some(READ_TIMEOUT))
+                .and_then(|()| stream.set_write_timeout(Some(WRITE_TIMEOUT)));
+            if let Err(e) = timeouts {
+                eprintln!("daemon: connection error: {e}");
+                continue;
+            }
+            if sender.send((connection, stream)).is_err() {
+                break;
+            }
+        }
+        let _ = std::fs::remove_file(socket);
+        // 보낼 쪽이 사라지면 작업 스레드는 맡은 연결을 끝내고 빠져나온다.
+        drop(sender);
+    });
+    Ok(())
+}
+
+// 이전 실행이 남긴 소켓 파일은 지우고 다시 만든다. 누가 듣고 있다면 이미 떠 있는 데몬이다.
+fn bind(socket: &str) -> Result<UnixListener, String> {
+    if Path::new(socket).exists() {
+        if UnixStream::connect(socket).is_ok() {
+            return Err(format!("{socket}: a daemon is already listening"));
+        }
+        std::fs::remove_file(socket).map_err(|e| format!("{socket}: {e}"))?;
+    }
+    UnixListener::bind(socket).map_err(|e| format!("{socket}: {e}"))
+}
+
+// shutdown을 받으면 true.
+fn handle(
+    stream: UnixStream,
+    args: &Args,
+    corpus: &RwLock<Corpus>,
+    limiter: &Mutex<Option<Limiter>>,
+    connection: u64,
+) -> std::io::Result<bool> {
+    let client = limits::client(&stream, connection);
+    let max = args.max_request.unwrap_or(limits::DEFAULT_MAX_REQUEST);
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    while let Some(request) = limits::read_request(&mut reader, max)? {
+        let started = Instant::now();
+        let line = match request {
+            Request::Line(line) if line.trim().is_empty() => continue,
+            Request::Line(line) => line,
+            Request::TooLarge(n) => {
+                metrics::record("rejected", false, started.elapsed());
+                let message = format!("request of {n} bytes is larger than --max-request {max}");
+                writeln!(
+                    writer,
+                    "{}",
+                    json::to_line(&refusal(413, message, Vec::new()))
+                )?;
+                continue;
+            }
+        };
+        let refused = match limiter.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            Some(limiter) => limiter
+                .admit(client)
+                .err()
+                .map(|wait| (wait, limiter.rate())),
+            None => None,
+        };
+        if let Some((wait, rate)) = refused {
+            metrics::record("rejected", false, started.elapsed());
+            let message = format!("rate limit exceeded ({})", rate.describe());
+            let retry = ("retry_after".to_string(), Value::Number(wait.as_secs_f64()));
+            writeln!(
+                writer,
+                "{}",
+                json::to_line(&refusal(429, message, vec![retry]))
+            )?;
+            continue;
+        }
+        let (reply, stop) = match json::parse(&line) {
+            Ok(request) => answer(&request, args, corpus, started),
+            Err(e) => {
+                metrics::record("unknown", false, started.elapsed());
+                (failure(format!("invalid JSON: {e}")), false)
+            }
+        };
+        writeln!(writer, "{}", json::to_line(&reply))?;
+        if stop {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn failure(message: String) -> Value {
+    Value::Object(vec![
+        ("ok".to_string(), Value::Bool(false)),
+        ("error".to_string(), Value::String(message)),
+    ])
+}
+
+// 처리하지 않고 돌려보내는 요청. status는 HTTP와 같은 뜻이다(413 너무 큼, 429 너무 잦음).
+fn refusal(status: u16, message: String, extra: Vec<(String, Value)>) -> Value {
+    let mut fields = vec![
+        ("ok".to_string(), Value::Bool(false)),
+        ("status".to_string(), Value::Number(f64::from(status))),
+        ("error".to_string(), Value::String(message)),
+    ];
+    fields.extend(extra);
+    Value::Object(fields)
+}
+
+fn success(mut fields: Vec<(String, Value)>) -> Value {
+    fields.insert(0, ("ok".to_string(), Value::Bool(true)));
+    Value::Object(fields)
+}
+
+fn number(n: usize) -> Value {
+    Value::Number(n as f64)
+}
+
+// 질의하는 동안 잡는 읽기 잠금. 다른 스레드가 잠근 채 죽었어도 입력은 그대로이므로 계속 쓴다.
+fn read(corpus: &RwLock<Corpus>) -> RwLockReadGuard<'_, Corpus> {
+    corpus.read().unwrap_or_else(|e| e.into_inner())
+}
+
+// (응답, 끝낼지).
+fn answer(
+    request: &Value,
+    args: &Args,
+    corpus: &RwLock<Corpus>,
+    started: Instant,
+) -> (Value, bool) {
+    let op = request
+        .get("op")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    // 지표 라벨로 쓸 이름. 모르는 op는 하나로 묶는다.
+    let label = match op {
+        "top" => "top",
+        "count" => "count",
+        "lines" => "lines",
+        "stats" => "stats",
+        "reload" => "reload",
+        "shutdown" => "shutdown",
+        _ => "unknown",
+    };
+    let result = match op {
+        "top" => top(request, args, &read(corpus)),
+        "count" => count(request, args, &read(corpus)),
+        "lines" => lines(request, args, &read(corpus)),
+        "stats" => {
+            let corpus = read(corpus);
+            Ok(success(vec![
+                ("files".to_string(), number(corpus.documents.len())),
+                (
+                    "tokens".to_string(),
+                    number(corpus.documents.iter().map(|d| d.tokens).sum()),
+                ),
+                ("distinct".to_string(), number(corpus.total.len())),
+                // --max-vocab로 버린 항목 수. 0이 아니면 개수는 근삿값이다.
+                ("evicted".to_string(), number(counter::evicted())),
+            ]))
+        }
+        // 새 입력은 잠금 밖에서 읽어, 그동안에도 다른 연결의 질의는 지난 입력으로 답한다.
+        "reload" => load(args).map(|fresh| {
+            let files = fresh.documents.len();
+            *corpus.write().unwrap_or_else(|e| e.into_inner()) = fresh;
+            success(vec![("files".to_string(), number(files))])
+        }),
+        "shutdown" => Ok(success(Vec::new())),
+        "" => Err("request needs an \"op\" field".to_string()),
+        other => Err(format!("unknown op {other:?}")),
+    };
+    metrics::record(label, result.is_ok(), started.elapsed());
+    (result.unwrap_or_else(failure), op == "shutdown")
+}
+
+// 음수나 소수는 거절한다. 없으면 기본값.
+fn size(request: &Value, key: &str, default: usize) -> Result<usize, String> {
+    match request.get(key) {
+        None | Some(Value::Null) => Ok(default),
+        Some(value) => value
+            .as_f64()
+            .filter(|n| *n >= 0.0 && n.fract() == 0.0)
+            .map(|n| n as usize)
+            .ok_or_else(|| format!("{key:?} must be a non-negative integer")),
+    }
+}
+
+fn top(request: &Value, args: &Args, corpus: &Corpus) -> Result<Value, String> {
+    let n = size(request, "n", args.analysis.top())?;
+    let counts = match request.get("file").and_then(Value::as_str) {
+        Some(name) => {
+            &corpus
+                .documents
+                .iter()
+                .find(|d| d.name == name)
+                .ok_or_else(|| format!("no loaded file named {name:?}"))?
+                .counts
+        }
+        None => &corpus.total,
+    };
+    let mut kept = Counter::new();
+    for (word, count) in counts.iter() {
+        if !args.analysis.is_stopword(word) {
+            kept.add(word, count);
+        }
+    }
+    let mut items = rank_all(kept, args);
+    if n > 0 {
+        items.truncate(n);
+    }
+    let words = items
+        .into_iter()
+        .map(|(word, count)| {
+            Value::Object(vec![
+                ("word".to_string(), Value::String(word)),
+                ("count".to_string(), number(count)),
+            ])
+        })
+        .collect();
+    Ok(success(vec![(
+        "top_words".to_string(),
+        Value::Array(words),
+    )]))
+}
+
+fn count(request: &Value, args: &Args, corpus: &Corpus) -> Result<Value, String> {
+    let raw = request
+        .get("word")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "count needs a \"word\" string".to_string())?;
+    let word = match normalize_words(raw, args.tokenizer).as_slice() {
+        [w] => w.clone(),
+        _ => return Err(format!("count takes a single word, got {raw:?}")),
+    };
+    let count = number(corpus.total.get(&word));
+    Ok(success(vec![
+        ("word".to_string(), Value::String(word)),
+        ("count".to_string(), count),
+    ]))
+}
+
+fn lines(request: &Value, args: &Args, corpus: &Corpus) -> Result<Value, String> {
+    let text = request
+        .get("query")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "lines needs a \"query\" string".to_string())?;
+    let expr = query::parse(text).map_err(|e| format!("invalid query: {}", e.message))?;
+    let limit = size(request, "limit", 0)?;
+    let filter = LineFilter {
+        query: Some(expr),
+        tokenizer: args.tokenizer,
+        ..LineFilter::default()
+    };
+    let hits = corpus
+        .documents
+        .iter()
+        .flat_map(|d| filter::matching_lines(&d.text, &filter).map(move |hit| (&d.name, hit)))
+        .take(if limit == 0 { usize::MAX } else { limit })
+        .map(|(name, (line, text))| {
+            Value::Object(vec![
+                ("path".to_string(), Value::String(name.clone())),
+                ("line".to_string(), number(line)),
+                ("text".to_string(), Value::String(text.to_string())),
+            ])
+        })
+        .collect();
+    Ok(success(vec![("matches".to_string(), Value::Array(hits))]))
+}
@@ -0,0 +1,29 @@
+// --flag-list: 금지어 목록에 있는 단어가 어디에 나오는지 줄번호와 함께 보고한다.
+// 목록 파일 형식은 wordlist.rs를 따른다.
+// 본문과 같은 토큰화 규칙으로 정규화하므로 "Master"도 "master"에 걸린다.
+
+use crate::wordlist;
+use crate::{normalize_words, Tokenizer};
+use std::collections::HashSet;
+
+pub struct Hit {
+    pub line: usize,
+    pub word: String,
+}
+
+pub fn load(path: &str, tok: Tokenizer) -> Result<HashSet<String>, String> {
+    wordlist::load(path, "flag list", tok)
+}
+
+// 한 줄에 같은 단어가 여러 번 나오면 그만큼 보고한다.
+pub fn scan(text: &str, list: &HashSet<String>, tok: Tokenizer) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        for word in normalize_words(line, tok) {
+            if list.contains(&word) {
+                hits.push(Hit { line: i + 1, word });
+            }
+        }
+    }
+    hits
+}
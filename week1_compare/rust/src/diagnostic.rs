@@ -0,0 +1,76 @@
+// 패턴 파싱 에러를 원문 아래 밑줄(^^^)로 보여준다. rustc 에러 메시지와 같은 모양이다:
+//   invalid --query: unmatched ')'
+//     |
+//     | error OR warn)
+//     |              ^
+//     = hint: remove it, or add a matching '(' before it
+// 위치는 바이트로 받고, 밑줄은 화면 칸으로 맞춘다(한글, 한자는 두 칸).
+
+pub struct Diagnostic<'a> {
+    // 에러 머리에 붙는 이름(예: "--query").
+    pub label: &'a str,
+    pub source: &'a str,
+    pub message: &'a str,
+    // source 안의 바이트 범위. 비어 있으면(입력 끝 등) 그 자리에 ^ 하나.
+    pub start: usize,
+    pub end: usize,
+    pub hint: Option<&'a str>,
+}
+
+impl Diagnostic<'_> {
+    pub fn render(&self) -> String {
+        let start = self.start.min(self.source.len());
+        let end = self.end.clamp(start, self.source.len());
+        // 밑줄이 줄바꿈을 넘지 않도록 제어 문자는 공백 한 칸으로 바꿔서 보여준다.
+        let shown: String = self
+            .source
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        let pad = width(&self.source[..start]);
+        let carets = width(&self.source[start..end]).max(1);
+        let mut out = format!(
+            "invalid {}: {}\n  |\n  | {shown}\n  | {}{}",
+            self.label,
+            self.message,
+            " ".repeat(pad),
+            "^".repeat(carets)
+        );
+        if let Some(hint) = self.hint {
+            out.push_str("\n  = hint: ");
+            out.push_str(hint);
+        }
+        out
+    }
+}
+
+// 터미널에서 차지하는 칸 수. 동아시아 전각 문자만 두 칸으로 센다(결합 문자 등은 다루지 않는다).
+pub fn width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+pub fn char_width(c: char) -> usize {
+    if is_wide(c) {
+        2
+    } else {
+        1
+    }
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x115F
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA960..=0xA97F
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x20000..=0x3FFFD
+    )
+}
@@ -0,0 +1,150 @@
+// difficulty: 본문의 단어를 --wordlist의 수준(CEFR A1~C2 등)에 맞춰 보고 수준별 비율을 낸다.
+// 읽기 자료를 등급별로 만들 때 "A2까지로 몇 %가 읽히는가"를 보려는 것이다.
+// 목록은 헤더가 있는 CSV다: word(또는 lemma, headword)와 level(또는 cefr) 열. 다른 열은 무시한다.
+//   word,level
+//   house,A1
+//   however,B1
+// 단어는 본문과 같은 규칙으로 정규화한다. 단어 하나가 되지 않는 항목(look after 같은 구)은 건너뛴다.
+// 같은 단어가 여러 수준에 있으면(뜻마다 다른 목록) 가장 쉬운 수준으로 친다.
+// 수준 순서: 모두 숫자면 수의 크기, 아니면 글자순(A1 < A2 < B1 ... < C2).
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+
+use crate::counter::Counter;
+use crate::format::parse_csv_line;
+use crate::{normalize_words, Tokenizer};
+
+pub struct Wordlist {
+    // 쉬운 것부터.
+    pub levels: Vec<String>,
+    // 단어 -> levels의 위치.
+    words: HashMap<String, usize>,
+}
+
+pub fn load(path: &str, tok: Tokenizer) -> Result<Wordlist, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("failed to read wordlist: {e}"))?;
+    let mut lines = text.lines().enumerate();
+    let header = match lines.next() {
+        Some((_, line)) => parse_csv_line(line),
+        None => return Err(format!("{path}: empty wordlist")),
+    };
+    let column = |wanted: &[&str]| {
+        header
+            .iter()
+            .position(|h| wanted.contains(&h.trim().to_ascii_lowercase().as_str()))
+    };
+    let (Some(word_at), Some(level_at)) = (
+        column(&["word", "lemma", "headword"]),
+        column(&["level", "cefr"]),
+    ) else {
+        return Err(format!(
+            "{path}: expected a header with word and level columns, got {:?}",
+            header.join(",")
+        ));
+    };
+    let mut entries = Vec::new();
+    for (i, line) in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = parse_csv_line(line);
+        let (Some(word), Some(level)) = (fields.get(word_at), fields.get(level_at)) else {
+            return Err(format!("{path}:{}: missing column", i + 1));
+        };
+        let level = level.trim();
+        if level.is_empty() {
+            return Err(format!("{path}:{}: empty level for {word:?}", i + 1));
+        }
+        if let [word] = normalize_words(word, tok).as_slice() {
+            entries.push((word.clone(), level.to_string()));
+        }
+    }
+    if entries.is_empty() {
+        return Err(format!("{path}: no words in wordlist"));
+    }
+    let mut levels: Vec<String> = entries.iter().map(|(_, level)| level.clone()).collect();
+    let numeric = numeric(&entries);
+    levels.sort_by(|a, b| level_order(a, b, numeric));
+    levels.dedup();
+    let place: HashMap<&str, usize> = levels
+        .iter()
+        .enumerate()
+        .map(|(i, level)| (level.as_str(), i))
+        .collect();
+    let mut words: HashMap<String, usize> = HashMap::new();
+    for (word, level) in &entries {
+        let at = place[level.as_str()];
+        words
+            .entry(word.clone())
+            .and_modify(|known| *known = (*known).min(at))
+            .or_insert(at);
+    }
+    Ok(Wordlist { levels, words })
+}
+
+fn numeric(entries: &[(String, String)]) -> bool {
+    entries
+        .iter()
+        .all(|(_, level)| level.parse::<f64>().is_ok())
+}
+
+fn level_order(a: &str, b: &str, numeric: bool) -> Ordering {
+    if numeric {
+        let (x, y) = (a.parse::<f64>(), b.parse::<f64>());
+        if let (Ok(x), Ok(y)) = (x, y) {
+            return x.total_cmp(&y);
+        }
+    }
+    a.to_ascii_lowercase()
+        .cmp(&b.to_ascii_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+// 수준 하나(또는 목록에 없는 단어)에 든 토큰 수와 서로 다른 단어 수.
+#[derive(Debug, Clone, Default)]
+pub struct Band {
+    pub tokens: usize,
+    pub words: usize,
+}
+
+pub struct Profile {
+    // wordlist.levels와 같은 순서.
+    pub bands: Vec<Band>,
+    pub unlisted: Band,
+    // 목록에 없는 단어의 빈도. 순위는 호출하는 쪽이 매긴다.
+    pub unlisted_words: Counter,
+}
+
+impl Profile {
+    pub fn tokens(&self) -> usize {
+        self.bands.iter().map(|b| b.tokens).sum::<usize>() + self.unlisted.tokens
+    }
+
+    pub fn words(&self) -> usize {
+        self.bands.iter().map(|b| b.words).sum::<usize>() + self.unlisted.words
+    }
+}
+
+pub fn profile(counts: &Counter, wordlist: &Wordlist) -> Profile {
+    let mut bands = vec![Band::default(); wordlist.levels.len()];
+    let mut unlisted = Band::default();
+    let mut unlisted_words = Counter::new();
+    for (word, n) in counts.iter() {
+        let band = match wordlist.words.get(word) {
+            Some(&at) => &mut bands[at],
+            None => {
+                unlisted_words.add(word, n);
+                &mut unlisted
+            }
+        };
+        band.tokens += n;
+        band.words += 1;
+    }
+    Profile {
+        bands,
+        unlisted,
+        unlisted_words,
+    }
+}
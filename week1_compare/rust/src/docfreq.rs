@@ -0,0 +1,88 @@
+// --min-df / --max-df: 입력 파일 하나를 문서 하나로 보고, 너무 적은 문서나 너무 많은 문서에
+// 나오는 단어를 상위 단어에서 뺀다(scikit-learn의 min_df/max_df와 같은 규칙).
+// 정수는 문서 수, 소수점이 있으면 전체 문서에 대한 비율이다(0.5 = 절반).
+
+use crate::cli::Args;
+use crate::count_words;
+use crate::counter::Counter;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    Documents(usize),
+    Ratio(f64),
+}
+
+impl Bound {
+    pub fn parse(value: &str) -> Option<Bound> {
+        if value.contains('.') {
+            let ratio = value.parse::<f64>().ok()?;
+            (0.0..=1.0).contains(&ratio).then_some(Bound::Ratio(ratio))
+        } else {
+            value.parse::<usize>().ok().map(Bound::Documents)
+        }
+    }
+
+    // --explain에 쓰는 설명.
+    pub fn describe(self) -> String {
+        match self {
+            Bound::Documents(n) => format!("{n} files"),
+            Bound::Ratio(ratio) => format!("{}% of files", ratio * 100.0),
+        }
+    }
+
+    // 문서 수로 바꾼다. 비율은 하한이면 올리고 상한이면 내린다.
+    fn resolve(self, documents: usize, lower: bool) -> usize {
+        match self {
+            Bound::Documents(n) => n,
+            Bound::Ratio(ratio) if lower => (ratio * documents as f64).ceil() as usize,
+            Bound::Ratio(ratio) => (ratio * documents as f64).floor() as usize,
+        }
+    }
+}
+
+// 단어마다 그 단어가 나온 문서 수와 남길 범위.
+pub struct Limits {
+    frequencies: Counter,
+    min: usize,
+    max: usize,
+}
+
+impl Limits {
+    // --min-df/--max-df가 없으면 None이다. 본문과 같은 토큰화로 문서마다 따로 센다.
+    pub fn new(documents: &[(String, String)], args: &Args) -> Result<Option<Limits>, String> {
+        if args.min_df.is_none() && args.max_df.is_none() {
+            return Ok(None);
+        }
+        let total = documents.len();
+        let min = args.min_df.map_or(0, |bound| bound.resolve(total, true));
+        let max = args
+            .max_df
+            .map_or(total, |bound| bound.resolve(total, false));
+        if min > max {
+            return Err(format!(
+                "--min-df ({min} documents) is above --max-df ({max} documents) for {total} files"
+            ));
+        }
+        let mut frequencies = Counter::new();
+        for (_, text) in documents {
+            for (word, _) in count_words(text, args.tokenizer).iter() {
+                frequencies.add(word, 1);
+            }
+        }
+        Ok(Some(Limits {
+            frequencies,
+            min,
+            max,
+        }))
+    }
+
+    pub fn apply(&self, counts: Counter) -> Counter {
+        let mut kept = Counter::new();
+        for (word, n) in counts.iter() {
+            if (self.min..=self.max).contains(&self.frequencies.get(word)) {
+                kept.add(word, n);
+            }
+        }
+        kept
+    }
+}
@@ -0,0 +1,38 @@
+// Word 문서(.docx)에서 문단 텍스트를 꺼낸다(--docx).
+// DOCX는 ZIP 안의 OOXML이고 본문은 word/document.xml에 있다. 문단은 <w:p>, 그 안의
+// 글자 조각(run)은 <w:r><w:t>...</w:t></w:r>로 잘게 나뉘어 있어서 한 단어가 여러 run에
+// 걸치기도 한다. 그래서 xml::text_lines 대신 <w:t> 안의 글자만 이어 붙이고 <w:p>가 닫힐 때
+// 줄을 끊는다. 필드 코드(<w:instrText>)와 변경 추적으로 지운 글자(<w:delText>)는 본문이 아니다.
+// Python이라면 python-docx의 [p.text for p in Document(path).paragraphs]에 해당한다.
+
+use crate::xml::{self, Event};
+use crate::zip::Archive;
+
+pub fn paragraphs(data: &[u8]) -> Result<Vec<String>, String> {
+    let archive = Archive::parse(data).map_err(|e| format!("invalid DOCX: {e}"))?;
+    let document = archive
+        .read_string("word/document.xml")
+        .map_err(|e| format!("invalid DOCX: {e}"))?;
+
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    for event in xml::events(&document) {
+        match event {
+            Event::Start("w:t", _) => in_text = true,
+            Event::End("w:t") => in_text = false,
+            Event::Text(text) if in_text => current.push_str(&text),
+            // 탭과 줄바꿈은 단어가 붙지 않도록 공백으로 본다.
+            Event::Empty("w:tab" | "w:br" | "w:cr", _) => current.push(' '),
+            Event::End("w:p") | Event::Empty("w:p", _) => {
+                let line = current.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !line.is_empty() {
+                    lines.push(line);
+                }
+                current.clear();
+            }
+            _ => {}
+        }
+    }
+    Ok(lines)
+}
@@ -0,0 +1,95 @@
+// drift 서브커맨드: 저장해 둔 기준선과 지금의 상대 빈도(빈도 / 전체 토큰)를 비교한다.
+// 기준선은 `report --all --format json` 출력이다. 파일이 여러 개면 합쳐서 하나로 본다.
+// 변화율 = |지금 - 기준| / 기준. 기준선에 없던 단어는 변화율이 무한대라 항상 걸린다.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::counter::Counter;
+use crate::json::{self, FromJson, Value};
+use crate::FileReport;
+
+// 결과를 다 출력한 뒤 종료 코드를 정할 수 있게 main에 알린다(batch.rs와 같은 방식).
+static DETECTED: AtomicBool = AtomicBool::new(false);
+
+pub fn detected() -> bool {
+    DETECTED.load(Ordering::Relaxed)
+}
+
+pub struct Baseline {
+    pub tokens: usize,
+    pub counts: Counter,
+}
+
+pub struct Shift {
+    pub word: String,
+    pub before: f64,
+    pub after: f64,
+    // 상대 변화율. 기준선에 없던 단어는 f64::INFINITY.
+    pub change: f64,
+}
+
+pub fn parse_baseline(text: &str, name: &str) -> Result<Baseline, String> {
+    let value = json::parse(text).map_err(|e| format!("{name}: {e}"))?;
+    let files = match &value {
+        Value::Array(items) => items.as_slice(),
+        Value::Object(_) => std::slice::from_ref(&value),
+        _ => {
+            return Err(format!(
+                "{name}: expected report JSON (an array of file objects)"
+            ))
+        }
+    };
+    let mut baseline = Baseline {
+        tokens: 0,
+        counts: Counter::new(),
+    };
+    for file in files {
+        let report = FileReport::from_json(file).map_err(|e| format!("{name}: {e}"))?;
+        baseline.tokens += report.tokens;
+        for entry in report.top_words {
+            baseline.counts.add(&entry.word, entry.count);
+        }
+    }
+    Ok(baseline)
+}
+
+fn relative(count: usize, tokens: usize) -> f64 {
+    count as f64 / tokens.max(1) as f64
+}
+
+// candidates의 단어 중 변화율이 threshold를 넘는 것. 변화가 큰 순서.
+pub fn shifts(
+    baseline: &Baseline,
+    current: &Counter,
+    current_tokens: usize,
+    candidates: &[String],
+    threshold: f64,
+) -> Vec<Shift> {
+    let mut shifts: Vec<Shift> = candidates
+        .iter()
+        .filter_map(|word| {
+            let before = relative(baseline.counts.get(word), baseline.tokens);
+            let after = relative(current.get(word), current_tokens);
+            let change = if before == 0.0 {
+                f64::INFINITY
+            } else {
+                (after - before).abs() / before
+            };
+            (change > threshold).then(|| Shift {
+                word: word.clone(),
+                before,
+                after,
+                change,
+            })
+        })
+        .collect();
+    shifts.sort_by(|a, b| {
+        b.change
+            .total_cmp(&a.change)
+            .then_with(|| a.word.cmp(&b.word))
+    });
+    if !shifts.is_empty() {
+        DETECTED.store(true, Ordering::Relaxed);
+    }
+    shifts
+}
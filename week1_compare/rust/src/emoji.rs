@@ -0,0 +1,78 @@
+// 이모지 시퀀스 인식(--emoji).
+// 눈에 보이는 이모지 하나가 여러 코드 포인트일 수 있어 한 덩어리로 묶어야 한다.
+//   👍🏽       = 기본 + 피부색 수식자
+//   👩‍💻       = 👩 + ZWJ(U+200D) + 💻
+//   🇰🇷       = 지역 표시 문자 두 개(국기)
+//   1️⃣       = 숫자 + VS16(U+FE0F) + 키캡(U+20E3)
+// Python이라면 regex 모듈의 \X나 emoji 패키지를 쓰겠지만, 여기서는 자주 쓰는 블록의
+// 범위표만으로 판단한다. 완전한 UTS #51 구현은 아니다.
+
+const ZWJ: char = '\u{200D}';
+const KEYCAP: char = '\u{20E3}';
+
+fn is_base(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF   // 마작/카드, 그림 문자, 이모티콘, 교통, 보충 기호
+        | 0x2600..=0x27BF   // 기타 기호, 딩뱃
+        | 0x2300..=0x23FF   // ⌚ ⏰ 같은 기술 기호
+        | 0x2B00..=0x2BFF   // ⭐ ⬛ 같은 화살표/도형
+        | 0x00A9 | 0x00AE | 0x203C | 0x2049 | 0x2122 | 0x2139 | 0x24C2
+        | 0x3030 | 0x303D | 0x3297 | 0x3299)
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+// 앞 글자에 붙는 표시: 변형 선택자, 피부색, 키캡, 태그(🏴 지역기).
+fn is_modifier(c: char) -> bool {
+    matches!(c as u32, 0xFE0E | 0xFE0F | 0x1F3FB..=0x1F3FF | 0x20E3 | 0xE0020..=0xE007F)
+}
+
+// s가 이모지로 시작하면 그 시퀀스의 바이트 길이.
+pub fn sequence_len(s: &str) -> Option<usize> {
+    let mut chars = s.char_indices().peekable();
+    let (_, first) = chars.next()?;
+
+    // 키캡: "1", "#", "*" 뒤에 (VS16) + U+20E3가 와야만 이모지다.
+    if first.is_ascii_digit() || first == '#' || first == '*' {
+        let rest = &s[first.len_utf8()..];
+        let rest = rest.strip_prefix('\u{FE0F}').unwrap_or(rest);
+        return rest
+            .starts_with(KEYCAP)
+            .then(|| s.len() - rest.len() + KEYCAP.len_utf8());
+    }
+    if is_regional_indicator(first) {
+        let second = s[first.len_utf8()..].chars().next();
+        let len = match second {
+            Some(c) if is_regional_indicator(c) => first.len_utf8() + c.len_utf8(),
+            _ => first.len_utf8(),
+        };
+        return Some(len);
+    }
+    if !is_base(first) {
+        return None;
+    }
+
+    let mut end = first.len_utf8();
+    while let Some(&(i, c)) = chars.peek() {
+        if is_modifier(c) {
+            chars.next();
+            end = i + c.len_utf8();
+        } else if c == ZWJ {
+            // ZWJ 뒤에 이모지가 이어질 때만 시퀀스를 늘린다.
+            let next = s[i + c.len_utf8()..].chars().next();
+            match next {
+                Some(n) if is_base(n) => {
+                    chars.next();
+                    chars.next();
+                    end = i + c.len_utf8() + n.len_utf8();
+                }
+                _ => break,
+            }
+        } else {
+            break;
+        }
+    }
+    Some(end)
+}
@@ -0,0 +1,292 @@
+// --output-encoding: 결과를 UTF-8이 아닌 인코딩으로 내보낸다. UTF-8을 못 읽는 Windows의
+// 오래된 도구(메모장 이전 버전, 일부 ERP 가져오기 등)에 보고서를 넘길 때 쓴다.
+// Python이라면 open(path, "w", encoding="euc-kr", errors="replace")로 끝날 일이다.
+// 표현할 수 없는 문자는 '?'로 바꾸고 몇 개였는지 알려준다(errors="replace"와 같음).
+//
+// EUC-KR은 KS X 1001 완성형이다: 한글 2350자는 유니코드 순서 그대로 0xB0A1부터 94자씩 배치되므로
+// 11172자 중 어느 글자가 들어 있는지만 비트맵으로 두고, 앞에 있는 글자 수로 코드를 계산한다.
+// 호환 자모(ㄱ..ㆎ, U+3131..U+318E)는 0xA4A1부터 같은 순서다. 한자와 특수 기호는 다루지 않는다.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    // ISO-8859-1: U+0000..U+00FF를 바이트 하나로.
+    Latin1,
+    EucKr,
+}
+
+impl Encoding {
+    pub fn parse(name: &str) -> Result<Encoding, String> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Ok(Encoding::Utf8),
+            "utf-16le" | "utf16le" => Ok(Encoding::Utf16Le),
+            "utf-16be" | "utf16be" => Ok(Encoding::Utf16Be),
+            "latin1" | "latin-1" | "iso-8859-1" => Ok(Encoding::Latin1),
+            "euc-kr" | "euckr" => Ok(Encoding::EucKr),
+            _ => Err(format!(
+                "unknown --output-encoding: {name} (expected utf-8, utf-16le, utf-16be, latin1 or euc-kr)"
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Latin1 => "ISO-8859-1",
+            Encoding::EucKr => "EUC-KR",
+        }
+    }
+}
+
+// (바이트, '?'로 바꾼 문자 수). UTF-16은 bom이면 앞에 BOM을 붙인다. 메모장과 Excel은 BOM이
+// 있어야 UTF-16으로 알아보지만, 이미 있는 파일 뒤에 붙일 때(--append) 중간에 들어가면 안 된다.
+pub fn encode(text: &str, encoding: Encoding, bom: bool) -> (Vec<u8>, usize) {
+    let mut out = Vec::with_capacity(text.len() * 2);
+    let mut replaced = 0;
+    match encoding {
+        Encoding::Utf8 => out.extend_from_slice(text.as_bytes()),
+        Encoding::Utf16Le | Encoding::Utf16Be => {
+            let little = encoding == Encoding::Utf16Le;
+            let units = bom.then_some(0xFEFF).into_iter().chain(text.encode_utf16());
+            for unit in units {
+                let bytes = if little {
+                    unit.to_le_bytes()
+                } else {
+                    unit.to_be_bytes()
+                };
+                out.extend_from_slice(&bytes);
+            }
+        }
+        Encoding::Latin1 => {
+            for c in text.chars() {
+                match u8::try_from(u32::from(c)) {
+                    Ok(byte) => out.push(byte),
+                    Err(_) => {
+                        out.push(b'?');
+                        replaced += 1;
+                    }
+                }
+            }
+        }
+        Encoding::EucKr => {
+            for c in text.chars() {
+                match euc_kr(c) {
+                    Some([lead, trail]) => out.extend_from_slice(&[lead, trail]),
+                    None if c.is_ascii() => out.push(c as u8),
+                    None => {
+                        out.push(b'?');
+                        replaced += 1;
+                    }
+                }
+            }
+        }
+    }
+    (out, replaced)
+}
+
+const SYLLABLES: u32 = 0xAC00;
+const SYLLABLE_COUNT: u32 = 11172;
+
+fn euc_kr(c: char) -> Option<[u8; 2]> {
+    let code = u32::from(c);
+    if (0x3131..=0x318E).contains(&code) {
+        return Some([0xA4, 0xA1 + (code - 0x3131) as u8]);
+    }
+    let index = code
+        .checked_sub(SYLLABLES)
+        .filter(|&i| i < SYLLABLE_COUNT)? as usize;
+    let (word, bit) = (index / 64, index % 64);
+    if KS_X_1001[word] >> bit & 1 == 0 {
+        return None;
+    }
+    // 이 글자 앞에 있는 완성형 글자 수가 곧 순번이다.
+    let before = KS_X_1001[..word]
+        .iter()
+        .map(|w| w.count_ones())
+        .sum::<u32>()
+        + (KS_X_1001[word] & ((1u64 << bit) - 1)).count_ones();
+    Some([0xB0 + (before / 94) as u8, 0xA1 + (before % 94) as u8])
+}
+
+// U+AC00부터 한 글자당 1비트(하위 비트부터): KS X 1001 완성형 2350자에 들어 있으면 1.
+const KS_X_1001: [u64; 175] = [
+    0x1303b0113eff0793,
+    0x0593000011102801,
+    0x3b019703b0111e7b,
+    0x306b959300a01112,
+    0x113032011102b051,
+    0xb879300a011102b0,
+    0x0080001030011306,
+    0x93000011100b0113,
+    0x0593000000102b03,
+    0x3b011323b051746b,
+    0x7000000000001030,
+    0x111029001303b011,
+    0xb015300000012180,
+    0x020000303001030e,
+    0x1300000010230111,
+    0x0113030010106b81,
+    0x0000010030111013,
+    0x3000000022b85530,
+    0x113afb079702b011,
+    0x00000021011303b0,
+    0x03b011383b0d1b00,
+    0x1300000111330113,
+    0x00000100111c2b05,
+    0x2a011300b0111000,
+    0x1010000102b01930,
+    0x1030030111000000,
+    0x0011146b07130230,
+    0x8fb8f9742b051300,
+    0x00000000103b0113,
+    0x01134ab0d9700000,
+    0x000011030011103b,
+    0x100001112ab15930,
+    0x00100b0111010000,
+    0x0000102b01130000,
+    0x02a0111020000101,
+    0x0102b05930210111,
+    0x011307b019300000,
+    0x00000003b011383b,
+    0x383b0d1300000000,
+    0x000010000103b011,
+    0x0010102001130000,
+    0x0000011000000100,
+    0x0002181130000000,
+    0x0111000000100000,
+    0x0b01930000000023,
+    0x302b011100301110,
+    0x01303b0113c7b011,
+    0xb011300000000280,
+    0x03b011302b011383,
+    0x1102b011300a0011,
+    0x0111010000002000,
+    0x2b011302a011102b,
+    0x3000000101000010,
+    0x11302b0113029011,
+    0xb0113000000066b0,
+    0x07b0113a6b07d302,
+    0x1300000000200103,
+    0x011303b011386b05,
+    0x2b051b00000010b8,
+    0x1000000003000110,
+    0x79700a011102a011,
+    0x0000100a0111a2b0,
+    0x0090111000011100,
+    0x9300000000090111,
+    0x011322b0f9f2bb05,
+    0x000000002001323b,
+    0x303b019306b05930,
+    0x117000001123a011,
+    0x00001010001102b0,
+    0x0000011003011301,
+    0x01010010162b0793,
+    0x0111020011300000,
+    0x00000000b0113029,
+    0x383b05130eb05130,
+    0x000001000303b011,
+    0x0000103901930000,
+    0x000000003b000302,
+    0x0000000000230113,
+    0x0001000000100000,
+    0x0000000290113020,
+    0x1000000000000000,
+    0x0000030111020000,
+    0xb079b02b01130000,
+    0x02b011303b011323,
+    0x1343b0d9f0210111,
+    0x011103b011303b01,
+    0x20011322b0517020,
+    0x300b011101901110,
+    0x0016ab019302b011,
+    0xb011302101130100,
+    0x02b0313029010302,
+    0x1b42b81930000000,
+    0x0000033011383301,
+    0x3305130000000020,
+    0x0000000000001110,
+    0x0130230593000001,
+    0x3011101000010100,
+    0x0230113000000100,
+    0x1100000010100001,
+    0x8513020000000000,
+    0x2b01130010111003,
+    0x303b011363b87730,
+    0x7b30020111a2b091,
+    0xf0d1702b011357f0,
+    0x0ab971301b0111e3,
+    0x13029001303b0113,
+    0x071302b011302b01,
+    0x230113033011302b,
+    0x30ab011302b01130,
+    0x7130090111feb411,
+    0xb011307b05d347b8,
+    0x0000111021015303,
+    0x1102b011306b0513,
+    0x0513000000103301,
+    0x30000102a01038eb,
+    0x3020001302b01110,
+    0x001010000102b071,
+    0x1011100b01130000,
+    0x000000002b011300,
+    0x1303b095366b0593,
+    0x0000020001103b01,
+    0x20000103b0113000,
+    0x3000000001000010,
+    0x00101001030ab011,
+    0x0000000301110100,
+    0x0300001023011302,
+    0x0100000010000000,
+    0x0000029000100000,
+    0x7b01538630113000,
+    0x0021015103b01130,
+    0x11303b0113000000,
+    0x00011010001102b0,
+    0x020011102b011302,
+    0x0102b01110000000,
+    0x000102b011300100,
+    0x2b01110000011010,
+    0x002b011302101110,
+    0x11302b0393000000,
+    0x0000303b011302b0,
+    0x03b0193000000002,
+    0x0103b011102b0113,
+    0x011302b011300000,
+    0x0001010200001021,
+    0x102b011300000010,
+    0x1130200001020011,
+    0x30113001011102b0,
+    0x02b0113000000002,
+    0x0103b011303b0313,
+    0x0513000000002000,
+    0x10001102b011303b,
+    0x142b011300000110,
+    0x0110000001000001,
+    0xb011300000010280,
+    0x0000001010000102,
+    0x9302101110230113,
+    0x0113003011100b05,
+    0x3b011323b051702b,
+    0x3000000000000030,
+    0x11102b011303b011,
+    0xb011300a01010330,
+    0x0000000020000102,
+    0x9300a01110000011,
+    0x0000020000102b05,
+    0x2901110090111000,
+    0x3000000000b01110,
+    0x11302b211302b011,
+    0x00000020000103b0,
+    0x02b011302b051300,
+    0x13002011103b0113,
+    0x0013028011322b21,
+    0x0a011102a0113028,
+    0x3021011102921130,
+    0x11302b0113020011,
+    0x3011122b03d30290,
+    0x000000002b011302,
+];
@@ -0,0 +1,149 @@
+// WORDTOOL_* 환경 변수: --config 파일과 명령행 사이의 설정 층. 컨테이너로 daemon을 띄울 때
+// 이미지의 명령행은 그대로 두고 배포마다 값만 바꾸려는 것이다.
+//   WORDTOOL_TOP=20 WORDTOOL_FORMAT=json WORDTOOL_COLOR=never
+// 변수 하나는 옵션 하나로 바뀌어 명령행 맨 앞(--preset 묶음보다도 앞)에 들어가므로,
+// 같은 옵션을 명령행에 주면 그쪽이 이긴다. 켜고 끄는 옵션은 1/true/yes/on일 때만 들어간다.
+// 옵션을 받지 않는 명령에는 넣지 않는다(stats에 --plain, top words에 --socket을 넣으면 에러가 되므로).
+// 명령은 명령행에서 처음 나오는 서브커맨드 이름으로 본다.
+
+use std::env;
+
+use crate::cli::{self, Command};
+use crate::format::Format;
+
+const PREFIX: &str = "WORDTOOL_";
+
+enum Kind {
+    Value,
+    Switch,
+}
+
+// 옵션을 넣을 명령.
+enum Scope {
+    Any,
+    // cli::format_applies가 받는 명령이고 --template이 없을 때.
+    Format,
+    // top words와 merge의 텍스트 출력.
+    Plain,
+    // stopwords 명령이나 --number-format과 함께.
+    Lang,
+    // --jsonrpc, --checkpoint/--resume, --with-positions와는 함께 쓰지 못한다.
+    Cache,
+    Daemon,
+    // daemon 또는 --jsonrpc.
+    Server,
+}
+
+// (변수 이름에서 WORDTOOL_을 뺀 부분, 옵션, 종류, 넣을 명령).
+const VARIABLES: &[(&str, &str, Kind, Scope)] = &[
+    ("TOP", "--top", Kind::Value, Scope::Any),
+    ("FORMAT", "--format", Kind::Value, Scope::Format),
+    ("COLOR", "--color", Kind::Value, Scope::Any),
+    ("CONFIG", "--config", Kind::Value, Scope::Any),
+    ("LANG", "--lang", Kind::Value, Scope::Lang),
+    ("CASE", "--case", Kind::Value, Scope::Any),
+    ("STEM", "--stem", Kind::Value, Scope::Any),
+    ("SORT", "--sort", Kind::Value, Scope::Any),
+    ("TIE_BREAK", "--tie-break", Kind::Value, Scope::Any),
+    ("NUMBER_FORMAT", "--number-format", Kind::Value, Scope::Any),
+    (
+        "STOPWORDS_FILE",
+        "--stopwords-file",
+        Kind::Value,
+        Scope::Any,
+    ),
+    ("THREADS", "--threads", Kind::Value, Scope::Any),
+    ("HASHER", "--hasher", Kind::Value, Scope::Any),
+    ("MAX_VOCAB", "--max-vocab", Kind::Value, Scope::Any),
+    ("SEED", "--seed", Kind::Value, Scope::Any),
+    ("CACHE", "--cache", Kind::Value, Scope::Cache),
+    ("UNICODE", "--unicode", Kind::Switch, Scope::Any),
+    ("PLAIN", "--plain", Kind::Switch, Scope::Plain),
+    ("NO_PAGER", "--no-pager", Kind::Switch, Scope::Any),
+    ("SOCKET", "--socket", Kind::Value, Scope::Daemon),
+    ("METRICS", "--metrics", Kind::Value, Scope::Daemon),
+    ("ROOT", "--root", Kind::Value, Scope::Server),
+    ("RATE_LIMIT", "--rate-limit", Kind::Value, Scope::Daemon),
+    ("MAX_REQUEST", "--max-request", Kind::Value, Scope::Server),
+];
+
+// 명령행 앞에 끼울 옵션들과, 에러 메시지에 보여 줄 NAME=VALUE 목록.
+// 모르는 WORDTOOL_ 변수는 오타일 가능성이 크므로 에러다. 값이 잘못된 변수는 명령에 맞으면
+// 그대로 넣어 평소처럼 에러가 나게 한다.
+pub fn flags(argv: &[String]) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut names: Vec<(String, String)> = env::vars()
+        .filter(|(name, _)| name.starts_with(PREFIX))
+        .collect();
+    // 변수 순서는 플랫폼마다 다르므로 이름순으로 펼친다.
+    names.sort();
+    let mut found: Vec<(&Scope, Vec<String>, String)> = Vec::new();
+    for (name, value) in names {
+        let key = &name[PREFIX.len()..];
+        let Some((_, flag, kind, scope)) = VARIABLES.iter().find(|(k, _, _, _)| *k == key) else {
+            return Err(format!(
+                "unknown environment variable {name} (known: {})",
+                VARIABLES
+                    .iter()
+                    .map(|(k, _, _, _)| format!("{PREFIX}{k}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        };
+        let option = match kind {
+            Kind::Value => vec![flag.to_string(), value.clone()],
+            Kind::Switch => match value.to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => vec![flag.to_string()],
+                "" | "0" | "false" | "no" | "off" => continue,
+                _ => return Err(format!("{name} must be 1 or 0, got {value}")),
+            },
+        };
+        found.push((scope, option, format!("{name}={value}")));
+    }
+
+    let command = argv
+        .iter()
+        .skip(1)
+        .find_map(|a| Command::from_name(a))
+        .unwrap_or(Command::Count);
+    let given = |flag: &str| {
+        argv.iter().any(|a| a == flag) || found.iter().any(|(_, option, _)| option[0] == flag)
+    };
+    // 명령행의 --format이 환경 변수보다 이긴다. PLAIN은 FORMAT 뒤에 오므로 그때 이미 정해져 있다.
+    let mut format = argv
+        .iter()
+        .rposition(|a| a == "--format")
+        .and_then(|i| argv.get(i + 1))
+        .cloned();
+    let mut flags = Vec::new();
+    let mut used = Vec::new();
+    for (scope, option, shown) in found.iter() {
+        let applies = match scope {
+            Scope::Any => true,
+            Scope::Format => {
+                let fits = !given("--template")
+                    && Format::parse(&option[1])
+                        .map_or(true, |f| cli::format_applies(command, f, given("--log")));
+                if fits && format.is_none() {
+                    format = Some(option[1].clone());
+                }
+                fits
+            }
+            Scope::Plain => {
+                matches!(command, Command::Count | Command::Merge)
+                    && !given("--template")
+                    && format.as_deref().is_none_or(|f| f == "text")
+            }
+            Scope::Lang => command == Command::Stopwords || given("--number-format"),
+            Scope::Cache => !["--jsonrpc", "--checkpoint", "--resume", "--with-positions"]
+                .iter()
+                .any(|flag| given(flag)),
+            Scope::Daemon => command == Command::Daemon,
+            Scope::Server => command == Command::Daemon || given("--jsonrpc"),
+        };
+        if applies {
+            flags.extend(option.iter().cloned());
+            used.push(shown.clone());
+        }
+    }
+    Ok((flags, used))
+}
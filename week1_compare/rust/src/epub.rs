@@ -0,0 +1,132 @@
+// EPUB 책에서 본문 텍스트를 장(chapter) 단위로 꺼낸다.
+// EPUB은 ZIP 안에 XHTML 파일을 넣은 형식이라 다음 순서로 따라간다.
+//   META-INF/container.xml → OPF 패키지 문서 경로
+//   OPF <manifest>         → id별 파일 경로, <spine> → 읽는 순서
+// 각 장의 <body>는 xml::text_lines로 문단(p, li, h1 ...)마다 한 줄씩 만든다.
+
+use crate::xml::{self, ElementPath, Event};
+use crate::zip::Archive;
+
+// 이 요소가 닫히면 줄을 끊는다. 인라인 요소(<em>, <span> 등)는 문단 안에 이어 붙는다.
+const BLOCKS: &[&str] = &[
+    "p",
+    "div",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "dt",
+    "dd",
+    "blockquote",
+    "pre",
+    "tr",
+    "th",
+    "td",
+    "br",
+    "hr",
+    "section",
+    "figcaption",
+    "caption",
+];
+
+pub struct Chapter {
+    pub href: String,
+    pub text: String,
+}
+
+pub fn chapters(data: &[u8]) -> Result<Vec<Chapter>, String> {
+    let archive = Archive::parse(data).map_err(|e| format!("invalid EPUB: {e}"))?;
+    let container = archive.read_string("META-INF/container.xml")?;
+    let opf_path = xml::events(&container)
+        .into_iter()
+        .find_map(|event| match event {
+            Event::Start("rootfile", attrs) | Event::Empty("rootfile", attrs) => {
+                xml::attribute(attrs, "full-path")
+            }
+            _ => None,
+        })
+        .ok_or_else(|| "invalid EPUB: container.xml has no rootfile".to_string())?;
+    let opf = archive.read_string(&opf_path)?;
+    let base = opf_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+
+    let mut manifest: Vec<(String, String)> = Vec::new();
+    let mut spine: Vec<String> = Vec::new();
+    for event in xml::events(&opf) {
+        let (name, attrs) = match event {
+            Event::Start(name, attrs) | Event::Empty(name, attrs) => (name, attrs),
+            _ => continue,
+        };
+        // 일부 EPUB은 opf: 접두사를 붙인다.
+        match name.rsplit(':').next().unwrap_or(name) {
+            "item" => {
+                if let (Some(id), Some(href)) =
+                    (xml::attribute(attrs, "id"), xml::attribute(attrs, "href"))
+                {
+                    manifest.push((id, href));
+                }
+            }
+            "itemref" => spine.extend(xml::attribute(attrs, "idref")),
+            _ => {}
+        }
+    }
+
+    let body = ElementPath::parse("body").expect("static element path");
+    let mut out = Vec::new();
+    for idref in &spine {
+        let Some((_, href)) = manifest.iter().find(|(id, _)| id == idref) else {
+            continue;
+        };
+        let path = resolve(base, href);
+        let xhtml = archive.read_string(&path)?;
+        let lines = xml::text_lines(&xhtml, Some(&body), Some(BLOCKS));
+        out.push(Chapter {
+            href: href.clone(),
+            text: lines.join("\n"),
+        });
+    }
+    if out.is_empty() {
+        return Err("invalid EPUB: spine lists no readable chapters".to_string());
+    }
+    Ok(out)
+}
+
+// OPF 위치를 기준으로 href("../Text/ch1.xhtml", "ch%201.xhtml")를 아카이브 경로로 바꾼다.
+fn resolve(base: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or(href);
+    let mut parts: Vec<&str> = base.split('/').filter(|p| !p.is_empty()).collect();
+    for part in href.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    percent_decode(&parts.join("/"))
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = s
+            .get(i + 1..i + 3)
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
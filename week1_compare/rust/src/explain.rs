@@ -0,0 +1,347 @@
+// --explain: 파일을 하나도 읽지 않고, 플래그를 해석한 결과를 처리 순서대로 보여준다.
+// 입력 -> 디코더 -> 토큰화 -> 필터 -> 분석 -> 출력. 긴 작업을 돌리기 전에 옵션이
+// 생각한 대로 맞물렸는지 확인하는 용도다(SQL의 EXPLAIN처럼 실행 계획만 찍는다).
+
+use std::fmt::Write as _;
+
+use crate::cli::{Args, Command};
+use crate::collate::Collation;
+use crate::encoding::Encoding;
+use crate::filter::{FileList, LineFilter};
+use crate::format::Format;
+use crate::input::{Decoder, Source};
+use crate::query::Expr;
+use crate::{Case, Contractions, Hyphens, Normalization, Numbers, SortOrder, Stem, TieBreak};
+
+pub fn explain(args: &Args) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "[Pipeline]");
+    let mut step = |name: &str, detail: String| {
+        let _ = writeln!(out, "{name:<10} {detail}");
+    };
+    let mut sources = input(&args.source);
+    if args.recursive {
+        sources.push_str(" (directories walked recursively)");
+    }
+    if args.walk.follow_symlinks {
+        sources.push_str(", symlinks followed");
+    }
+    if args.walk.hidden {
+        sources.push_str(", hidden files included");
+    }
+    if !args.walk.extensions.is_empty() {
+        let _ = write!(sources, ", only .{}", args.walk.extensions.join(" ."));
+    }
+    if !args.walk.excluded.is_empty() {
+        let _ = write!(sources, ", except .{}", args.walk.excluded.join(" ."));
+    }
+    step("input", sources);
+    step("decoder", decoder(args));
+    step("tokenizer", tokenizer(args));
+    if let Some(script) = &args.script {
+        step(
+            "script",
+            format!("{} ({})", script.path, script.hooks().join(", ")),
+        );
+    }
+    let mut kept = filter(&args.filter);
+    if args.count_matching {
+        kept.push_str("; top words over matching lines only");
+    }
+    step("filter", kept);
+    step("analysis", analysis(args));
+    let mut limits = Vec::new();
+    if let Some(bound) = args.min_df {
+        limits.push(format!("at least {}", bound.describe()));
+    }
+    if let Some(bound) = args.max_df {
+        limits.push(format!("at most {}", bound.describe()));
+    }
+    if !limits.is_empty() {
+        step(
+            "documents",
+            format!("words found in {}", limits.join(" and ")),
+        );
+    }
+    if let (Some(size), Some(format)) = (args.bucket, &args.timestamp_format) {
+        step(
+            "buckets",
+            format!(
+                "one table per {}s of line timestamps read as {}",
+                size / 1000,
+                format.as_str()
+            ),
+        );
+    }
+    if let Some(regex) = &args.speaker {
+        step(
+            "speakers",
+            format!("one table per speaker named by /{}/", regex.as_str()),
+        );
+    }
+    for assertion in &args.assertions {
+        step("assert", assertion.spec().to_string());
+    }
+    step("renderer", renderer(args));
+    step("output", output(args));
+    if let Some(dir) = &args.cache {
+        step("cache", dir.clone());
+    }
+    if let Some(every) = args.every {
+        step(
+            "schedule",
+            format!("every {}s, one NDJSON line per run", every.as_secs_f64()),
+        );
+    }
+    if args.follow {
+        step(
+            "follow",
+            "wait for lines appended to the file (tail -f)".to_string(),
+        );
+    }
+    for rule in &args.alerts {
+        step("alert", rule.spec().to_string());
+    }
+    if let Some(path) = &args.journal {
+        step(
+            "journal",
+            format!("{path}, one NDJSON record appended per run"),
+        );
+    }
+    out
+}
+
+fn input(source: &Source) -> String {
+    match source {
+        Source::Clipboard => "system clipboard".to_string(),
+        Source::Files(paths) => {
+            let names: Vec<&str> = paths
+                .iter()
+                .map(|p| if p == "-" { "stdin" } else { p.as_str() })
+                .collect();
+            format!("{} file(s): {}", names.len(), names.join(", "))
+        }
+    }
+}
+
+fn decoder(args: &Args) -> String {
+    let mut detail = match &args.decoder {
+        Decoder::Plain if args.recursive && (!args.filter.is_active() || args.count_matching) => {
+            "sniffed per file: plain text, HTML and Markdown body text, binary skipped".to_string()
+        }
+        Decoder::Plain => "plain text".to_string(),
+        Decoder::JsonField(path) => format!("NDJSON, string field {}", path.join(".")),
+        Decoder::Xml(None) => "XML text nodes".to_string(),
+        Decoder::Xml(Some(path)) => format!("XML text nodes under {}", path.describe()),
+        Decoder::Epub => "EPUB chapters in reading order".to_string(),
+        Decoder::Subtitles => "SRT/VTT cue text".to_string(),
+        Decoder::Docx => "DOCX paragraphs from word/document.xml".to_string(),
+        Decoder::Pdf => "PDF text layer in page order".to_string(),
+        Decoder::Extract(command) => format!("stdout of `{command}` per file"),
+    };
+    if args.log {
+        detail.push_str(", then log lines split into level and message");
+        if let Some(range) = args.level {
+            let _ = write!(detail, " (levels {:?}..{:?})", range.min, range.max);
+        }
+    }
+    detail
+}
+
+fn tokenizer(args: &Args) -> String {
+    let tok = args.tokenizer;
+    let mut parts = vec![if tok.unicode {
+        "unicode letters"
+    } else {
+        "ascii letters"
+    }];
+    parts.push(match tok.normalization {
+        Normalization::Nfc => "NFC",
+        Normalization::Nfkc => "NFKC",
+    });
+    if tok.transliterate {
+        parts.push("transliterated to ASCII");
+    } else if tok.strip_accents {
+        parts.push("accents stripped");
+    }
+    let steps;
+    match tok.pipeline {
+        Some(pipeline) => {
+            steps = format!("normalize {}", pipeline.describe());
+            parts.push(&steps);
+        }
+        None => parts.push(match tok.case {
+            Case::Fold => "case folded",
+            Case::Preserve => "case preserved",
+        }),
+    }
+    parts.push(match tok.hyphens {
+        Hyphens::Keep => "hyphens kept",
+        Hyphens::Split => "hyphens split",
+        Hyphens::Join => "hyphens joined",
+    });
+    parts.push(match tok.contractions {
+        Contractions::Keep => "contractions kept",
+        Contractions::Expand => "contractions expanded",
+        Contractions::Split => "contractions split",
+    });
+    if tok.emoji {
+        parts.push("emoji counted");
+    }
+    match tok.numbers {
+        Numbers::Drop => {}
+        Numbers::Keep => parts.push("numbers counted"),
+        Numbers::Bucket => parts.push("numbers counted as <NUM>"),
+    }
+    let stemmed;
+    match tok.stem {
+        Stem::Off => {}
+        Stem::Auto => parts.push("stemmed (language detected per text)"),
+        Stem::Lang(lang) => {
+            stemmed = format!("stemmed ({})", lang.code());
+            parts.push(&stemmed);
+        }
+    }
+    let mut detail = parts.join(", ");
+    if let Some(n) = args.char_ngrams {
+        let _ = write!(detail, "; character {n}-grams instead of words");
+    }
+    detail
+}
+
+fn filter(filter: &LineFilter) -> String {
+    if !filter.is_active() {
+        return "none (all lines)".to_string();
+    }
+    let mut parts = Vec::new();
+    if let Some(stems) = &filter.contains_stems {
+        let stems: Vec<String> = stems.iter().map(|words| words.join(" ")).collect();
+        parts.push(format!("contains any of the stems {}", stems.join(", ")));
+    } else if !filter.contains.is_empty() {
+        parts.push(format!("contains any of {}", filter.contains.join(", ")));
+    }
+    if let Some(expr) = &filter.query {
+        parts.push(format!("query {}", describe(expr)));
+    }
+    if let Some(phrase) = &filter.phrase {
+        parts.push(format!("phrase \"{}\"", phrase.join(" ")));
+    }
+    if let Some(near) = &filter.near {
+        parts.push(format!(
+            "{} within {} tokens of {}",
+            near.first, near.distance, near.second
+        ));
+    }
+    if let Some(regex) = &filter.regex {
+        parts.push(format!("regex /{}/", regex.as_str()));
+    }
+    let mut detail = parts.join(" AND ");
+    if filter.nul {
+        detail.push_str("; NUL-terminated records");
+    }
+    if let Some(n) = filter.max_matches {
+        let _ = write!(detail, "; first {n} matches");
+    }
+    if let Some(n) = filter.sample_matches {
+        let _ = write!(detail, "; random sample of {n} matches");
+    }
+    detail
+}
+
+// 파서가 정한 결합 순서가 보이도록 모든 이항 연산을 괄호로 감싼다.
+fn describe(expr: &Expr) -> String {
+    match expr {
+        Expr::Word(w) => w.clone(),
+        Expr::Text(t) => format!("\"{t}\""),
+        Expr::And(a, b) => format!("({} AND {})", describe(a), describe(b)),
+        Expr::Or(a, b) => format!("({} OR {})", describe(a), describe(b)),
+        Expr::Not(a) => format!("NOT {}", describe(a)),
+    }
+}
+
+fn analysis(args: &Args) -> String {
+    let a = &args.analysis;
+    let top = match a.top() {
+        0 => "all".to_string(),
+        n => format!("top {n}"),
+    };
+    let sort = match a.sort() {
+        SortOrder::Count => "by count",
+        SortOrder::Alpha => "alphabetical",
+    };
+    let ties = match a.tie_break() {
+        TieBreak::Alpha => "alphabetical",
+        TieBreak::FirstSeen => "first seen",
+        TieBreak::Length => "longest first",
+    };
+    let collation = match a.collation() {
+        Collation::Bytes => "bytes",
+        Collation::Latin => "en",
+        Collation::German => "de",
+        Collation::Korean => "ko",
+    };
+    let mut ranking = format!(
+        "{top}, {sort}, ties {ties}, collate {collation}, {} stopword(s)",
+        a.stopword_count()
+    );
+    if let Some(n) = a.keep_count() {
+        ranking.push_str(&format!(", keep {n} word(s)"));
+    }
+    let command = match args.command {
+        Command::Count if args.log => "words per log level",
+        other => other.name(),
+    };
+    format!("{command} ({ranking})")
+}
+
+fn renderer(args: &Args) -> String {
+    let end = if args.print0 { "NUL" } else { "newline" };
+    match args.file_list {
+        Some(FileList::Matching) => {
+            return format!("names of files with a match, each ended by {end}")
+        }
+        Some(FileList::NotMatching) => {
+            return format!("names of files without a match, each ended by {end}")
+        }
+        None => {}
+    }
+    if args.print0 {
+        return "filtered lines only, each ended by NUL".to_string();
+    }
+    if let Some(template) = &args.template {
+        return format!("template {}", template.path);
+    }
+    let name = match args.format {
+        Format::Text if args.table.is_some_and(|t| t.rank) => "text table with ranks",
+        Format::Text if args.table.is_some() => "text table",
+        Format::Text => "text",
+        Format::Csv => "csv",
+        Format::Json => "json",
+        Format::Markdown => "markdown",
+        Format::Html => "html",
+        Format::Parquet => "parquet",
+        Format::Arrow => "arrow ipc",
+        Format::Dot => "graphviz dot",
+        Format::Gexf => "gexf",
+    };
+    if args.plain {
+        format!("{name}, data rows only")
+    } else if args.color {
+        format!("{name}, keywords highlighted")
+    } else {
+        name.to_string()
+    }
+}
+
+fn output(args: &Args) -> String {
+    if args.output_encoding != Encoding::Utf8 {
+        let target = args.output.as_deref().unwrap_or("stdout");
+        return format!("{target}, encoded as {}", args.output_encoding.name());
+    }
+    match (&args.output, args.append) {
+        (Some(path), true) => format!("append to {path}"),
+        (Some(path), false) => format!("replace {path}"),
+        (None, _) if args.pager => "stdout ($PAGER when long)".to_string(),
+        (None, _) => "stdout".to_string(),
+    }
+}
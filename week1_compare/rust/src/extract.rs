@@ -0,0 +1,62 @@
+// --extract-cmd 'pandoc -t plain {}': 직접 읽지 못하는 형식(odt, rtf, OCR이 필요한 그림 등)을
+// 바깥 도구에 맡기고 그 stdout을 평문으로 분석한다. find -exec처럼 {} 자리에 파일 경로가 들어간다.
+// 명령은 셸을 거치지 않고 공백으로만 나눈다($PAGER와 같은 방식). 그래서 경로에 공백이나
+// 따옴표가 있어도 인자 하나로 전달되고, 파이프나 리다이렉션은 쓸 수 없다.
+// stdin과 클립보드처럼 경로가 없는 입력은 자식의 stdin으로 흘려보내고 {}는 "-"가 된다.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+
+// {}가 없으면 경로를 마지막 인자로 붙인다(stdin 입력이면 붙이지 않는다).
+pub fn run(command: &str, path: Option<&str>, data: Vec<u8>) -> Result<String, String> {
+    let mut parts = command.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| "--extract-cmd must not be empty".to_string())?;
+    let target = path.unwrap_or("-");
+    let mut args: Vec<String> = parts.map(|arg| arg.replace("{}", target)).collect();
+    if let (Some(path), false) = (path, command.contains("{}")) {
+        args.push(path.to_string());
+    }
+    let name = path.unwrap_or("(stdin)");
+
+    let mut child = Command::new(program)
+        .args(&args)
+        .stdin(if path.is_some() {
+            Stdio::null()
+        } else {
+            Stdio::piped()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run extractor {program}: {e}"))?;
+    // 자식이 stdout을 다 쓰기 전에 stdin에서 막히지 않도록 입력은 따로 흘려보낸다.
+    let writer = child.stdin.take().map(|mut stdin| {
+        thread::spawn(move || {
+            let _ = stdin.write_all(&data);
+        })
+    });
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("failed to run extractor {program}: {e}"))?;
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or("").trim();
+        let status = match output.status.code() {
+            Some(code) => format!("exit status {code}"),
+            None => "terminated by signal".to_string(),
+        };
+        return Err(if reason.is_empty() {
+            format!("extractor {program} failed on {name}: {status}")
+        } else {
+            format!("extractor {program} failed on {name}: {status}: {reason}")
+        });
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|_| format!("extractor {program} printed invalid UTF-8 for {name}"))
+}
@@ -0,0 +1,413 @@
+// 줄 필터링. --contains, --query 같은 조건을 LineFilter 하나로 묶어서
+// "이 줄을 출력할까?"라는 판단을 한 곳(matches)에서만 하도록 만든다.
+
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use crate::query::Expr;
+use crate::regex::{self, Regex};
+use crate::rng::{self, Rng};
+use crate::{normalize_words, Tokenizer};
+
+#[derive(Debug, Default)]
+pub struct LineFilter {
+    // --contains를 여러 번 주면 그중 하나라도 들어 있는 줄(grep -e와 같음).
+    pub contains: Vec<String>,
+    // --match-stems: --contains 키워드를 본문과 같이 토큰화·어간 추출한 토큰 열.
+    // 있으면 부분 문자열 대신 이것으로 비교해 "run"이 "running", "runs"에도 걸린다.
+    pub contains_stems: Option<Vec<Vec<String>>>,
+    pub query: Option<Expr>,
+    // --phrase: 정규화된 토큰 열. 원문 부분 문자열이 아니라 토큰 순서로 비교한다.
+    pub phrase: Option<Vec<String>>,
+    pub near: Option<Near>,
+    pub regex: Option<Regex>,
+    pub tokenizer: Tokenizer,
+    // --max-matches: 이만큼 찾으면 나머지 줄은 검사하지 않는다(본문 전체가 필요 없으면 읽지도 않는다).
+    pub max_matches: Option<usize>,
+    // --sample-matches: 걸린 줄 전체에서 이만큼 무작위로 뽑는다(끝까지 검사해야 한다).
+    pub sample_matches: Option<usize>,
+    // -0: 줄 대신 NUL로 끝나는 레코드를 거른다(find -print0 출력처럼 안에 줄바꿈이 있어도 된다).
+    pub nul: bool,
+}
+
+// -l / --files-without-match: 걸린 줄 대신 파일 경로만 쓴다(grep -l, grep -L).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileList {
+    Matching,
+    NotMatching,
+}
+
+impl FileList {
+    // 이 파일의 경로를 쓸지. hit는 파일에 걸린 줄이 하나라도 있었는지다.
+    pub fn lists(self, hit: bool) -> bool {
+        hit == (self == FileList::Matching)
+    }
+}
+
+// --near "error,disk,5": 두 단어가 토큰 N개 거리 안에서 함께 등장하는 줄.
+#[derive(Debug, Clone)]
+pub struct Near {
+    pub first: String,
+    pub second: String,
+    pub distance: usize,
+}
+
+impl Near {
+    pub fn parse(spec: &str, tok: Tokenizer) -> Result<Near, String> {
+        let parts: Vec<&str> = spec.split(',').map(str::trim).collect();
+        let [first, second, distance] = parts.as_slice() else {
+            return Err(format!("--near expects WORD,WORD,N, got {spec:?}"));
+        };
+        let word = |raw: &str| -> Result<String, String> {
+            match normalize_words(raw, tok).as_slice() {
+                [w] => Ok(w.clone()),
+                _ => Err(format!("--near expects single words, got {raw:?}")),
+            }
+        };
+        let distance = distance.parse::<usize>().map_err(|_| {
+            format!("--near distance must be a non-negative integer, got {distance:?}")
+        })?;
+        Ok(Near {
+            first: word(first)?,
+            second: word(second)?,
+            distance,
+        })
+    }
+
+    // 각 단어의 위치 목록을 모은 뒤, 가장 가까운 쌍의 거리만 확인한다.
+    fn matches(&self, words: &[String]) -> bool {
+        let positions = |target: &str| -> Vec<usize> {
+            words
+                .iter()
+                .enumerate()
+                .filter(|(_, w)| w.as_str() == target)
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let a = positions(&self.first);
+        let b = positions(&self.second);
+        a.iter()
+            .any(|&i| b.iter().any(|&j| i != j && i.abs_diff(j) <= self.distance))
+    }
+}
+
+impl LineFilter {
+    // --max-matches(앞에서부터) 또는 --sample-matches(저수지 표본)를 적용한다.
+    pub fn limit<T>(&self, hits: impl Iterator<Item = T>) -> Vec<T> {
+        match self.sample_matches {
+            Some(k) => rng::reservoir(hits, k, &mut Rng::seeded()),
+            None => hits.take(self.max_matches.unwrap_or(usize::MAX)).collect(),
+        }
+    }
+
+    // 필터가 한 번에 보는 단위. 마지막 구분자 뒤의 빈 조각은 레코드로 치지 않는다(lines()와 같다).
+    pub fn records<'a>(&self, text: &'a str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        if self.nul {
+            let text = text.strip_suffix('\0').unwrap_or(text);
+            Box::new(text.split('\0').filter(move |_| !text.is_empty()))
+        } else {
+            Box::new(text.lines())
+        }
+    }
+
+    // 조건이 하나도 없으면 [Filtered lines] 섹션 자체를 생략한다.
+    pub fn is_active(&self) -> bool {
+        !self.contains.is_empty()
+            || self.query.is_some()
+            || self.phrase.is_some()
+            || self.near.is_some()
+            || self.regex.is_some()
+    }
+
+    // 여러 조건은 모두 만족해야 한다(AND).
+    pub fn matches(&self, line: &str) -> bool {
+        let lowered = line.to_lowercase();
+        if let Some(stems) = &self.contains_stems {
+            let words = normalize_words(line, self.tokenizer);
+            if !stems.iter().any(|stem| contains_phrase(&words, stem)) {
+                return false;
+            }
+        } else if !self.contains.is_empty()
+            && !self
+                .contains
+                .iter()
+                .any(|keyword| lowered.contains(&keyword.to_lowercase()))
+        {
+            return false;
+        }
+        if let Some(regex) = &self.regex {
+            if !regex.is_match(line) {
+                return false;
+            }
+        }
+        if self.query.is_none() && self.phrase.is_none() && self.near.is_none() {
+            return true;
+        }
+        let words = normalize_words(line, self.tokenizer);
+        if let Some(phrase) = &self.phrase {
+            if !contains_phrase(&words, phrase) {
+                return false;
+            }
+        }
+        if let Some(near) = &self.near {
+            if !near.matches(&words) {
+                return false;
+            }
+        }
+        if let Some(expr) = &self.query {
+            let tokens: HashSet<String> = words.into_iter().collect();
+            if !expr.matches(&tokens, &lowered) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// "Memory-safety!"와 "memory safety"는 원문은 달라도 토큰 열은 [memory, safety]로 같다.
+fn contains_phrase(words: &[String], phrase: &[String]) -> bool {
+    if phrase.is_empty() || phrase.len() > words.len() {
+        return false;
+    }
+    words.windows(phrase.len()).any(|w| w == phrase)
+}
+
+// -o: 줄 전체 대신 걸린 부분만. --regex가 있으면 일치마다 하나(--replace가 있으면 그 틀을 채운
+// 결과), 없으면 --contains 키워드가 나온 자리를 원문 대소문자 그대로 돌려준다.
+// 키워드 위치는 highlight와 같이 ASCII 소문자로 찾는다. a*처럼 빈 일치는 빈 줄이 되므로 뺀다.
+pub fn matched_parts(line: &str, filter: &LineFilter, replace: Option<&str>) -> Vec<String> {
+    if let Some(re) = &filter.regex {
+        return re
+            .captures_all(line)
+            .iter()
+            .filter(|caps| matches!(caps[0], Some((start, end)) if start < end))
+            .filter_map(|caps| match replace {
+                Some(template) => Some(regex::expand(template, line, caps)),
+                None => caps[0].map(|(start, end)| line[start..end].to_string()),
+            })
+            .collect();
+    }
+    let lowered = line.to_ascii_lowercase();
+    let mut spans = Vec::new();
+    for keyword in &filter.contains {
+        let needle = keyword.to_ascii_lowercase();
+        if needle.is_empty() {
+            continue;
+        }
+        let mut from = 0;
+        while let Some(pos) = lowered[from..].find(&needle) {
+            spans.push((from + pos, from + pos + needle.len()));
+            from += pos + needle.len();
+        }
+    }
+    // 같은 자리에서 시작하면 긴 쪽을 쓰고, 겹치는 뒤쪽 일치는 버린다.
+    spans.sort_by_key(|&(start, end)| (start, Reverse(end)));
+    let mut parts = Vec::new();
+    let mut last = 0;
+    for (start, end) in spans {
+        if start >= last {
+            parts.push(line[start..end].to_string());
+            last = end;
+        }
+    }
+    parts
+}
+
+// 줄이 길어 잘라야 할 때 보이게 둘 첫 일치의 바이트 위치. --regex나 --contains가 없으면 0.
+pub fn first_match(line: &str, filter: &LineFilter) -> usize {
+    if let Some(re) = &filter.regex {
+        if let Some(Some((start, _))) = re.captures_all(line).first().map(|caps| caps[0]) {
+            return start;
+        }
+    }
+    let lowered = line.to_ascii_lowercase();
+    filter
+        .contains
+        .iter()
+        .filter(|keyword| !keyword.is_empty())
+        .filter_map(|keyword| lowered.find(&keyword.to_ascii_lowercase()))
+        .min()
+        .unwrap_or(0)
+}
+
+// Python 리스트 컴프리헨션과 비슷한 필터 로직.
+// 파일 읽기(I/O 에러 처리)는 input 모듈이 맡고, 여기서는 이미 읽은 텍스트만 거른다.
+pub fn filter_lines(text: &str, filter: &LineFilter) -> Vec<String> {
+    numbered_lines(text, filter)
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect()
+}
+
+// --line-numbers용: (1부터 시작하는 줄 번호, 줄).
+// 이미 읽은 본문 안에서 take()가 다 차면 그 뒤 줄은 matches()를 부르지 않을 뿐, 읽기는 이미 끝났다.
+// 읽는 중에 멈추는 것은 scan이다.
+pub fn numbered_lines(text: &str, filter: &LineFilter) -> Vec<(usize, String)> {
+    filter.limit(matching_lines(text, filter).map(|(n, line)| (n, line.to_string())))
+}
+
+// matching_lines의 바이트판. 파일 전체를 UTF-8로 확인하지 않고 레코드마다 따로 본다.
+// 온전한 줄은 빌려 쓰고, 깨진 바이트가 있는 줄만 U+FFFD로 바꿔 새로 만든다(lines()처럼 \r\n도 뗀다).
+pub fn matching_lines_lossy<'a>(
+    data: &'a [u8],
+    filter: &'a LineFilter,
+) -> impl Iterator<Item = (usize, Cow<'a, str>)> {
+    let sep = if filter.nul { 0 } else { b'\n' };
+    let data = data.strip_suffix(&[sep]).unwrap_or(data);
+    data.split(move |&b| b == sep)
+        .filter(move |_| !data.is_empty())
+        .enumerate()
+        .map(move |(i, raw)| {
+            let raw = if filter.nul {
+                raw
+            } else {
+                raw.strip_suffix(b"\r").unwrap_or(raw)
+            };
+            (i + 1, String::from_utf8_lossy(raw))
+        })
+        .filter(|(_, line)| filter.matches(line))
+}
+
+// 읽으면서 거르는 matching_lines_lossy. 레코드를 하나씩 읽어 keep(--script의 keep_line)을 지난
+// 줄만 번호를 매기고(통째로 읽을 때 keep_lines가 먼저 줄을 빼는 것과 같다), 걸린 것을 found에
+// 넘긴다. found가 false를 돌려주면 나머지는 읽지 않는다. 돌려주는 값은 (읽은 바이트 수, 계속할지).
+// name은 읽기 에러 메시지에 쓰는 입력 이름이다.
+pub fn scan(
+    name: &str,
+    reader: &mut dyn BufRead,
+    filter: &LineFilter,
+    mut keep: impl FnMut(&str) -> Result<bool, String>,
+    mut found: impl FnMut(usize, &str) -> bool,
+) -> Result<(usize, bool), String> {
+    let sep = if filter.nul { 0 } else { b'\n' };
+    let mut record = Vec::new();
+    let mut bytes = 0;
+    let mut n = 0;
+    loop {
+        record.clear();
+        let read = reader
+            .read_until(sep, &mut record)
+            .map_err(|e| format!("failed to read {name}: {e}"))?;
+        if read == 0 {
+            return Ok((bytes, true));
+        }
+        bytes += read;
+        let mut raw = record.strip_suffix(&[sep]).unwrap_or(&record);
+        if !filter.nul {
+            raw = raw.strip_suffix(b"\r").unwrap_or(raw);
+        }
+        let line = String::from_utf8_lossy(raw);
+        if !keep(&line)? {
+            continue;
+        }
+        n += 1;
+        if filter.matches(&line) && !found(n, &line) {
+            return Ok((bytes, false));
+        }
+    }
+}
+
+// 필터에 걸린 (줄번호, 줄). 개수 제한 없이 지연 평가되므로 여러 파일을 이어 붙여
+// 전체에 한 번만 limit()을 적용할 수 있다. -0이면 줄 대신 레코드와 레코드 번호다.
+pub fn matching_lines<'a>(
+    text: &'a str,
+    filter: &'a LineFilter,
+) -> impl Iterator<Item = (usize, &'a str)> {
+    filter
+        .records(text)
+        .enumerate()
+        .filter(|(_, line)| filter.matches(line))
+        .map(|(i, line)| (i + 1, line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Read};
+
+    // "error\n"를 끝없이 내는 stdin 대신. limit 줄을 넘겨 읽으려 하면 멈추지 않은 것이다.
+    struct Endless {
+        lines: usize,
+        limit: usize,
+    }
+
+    impl Read for Endless {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            assert!(self.lines < self.limit, "read past line {}", self.limit);
+            let line = b"error\n";
+            let n = (buf.len() / line.len()).min(self.limit - self.lines);
+            for chunk in buf.chunks_exact_mut(line.len()).take(n) {
+                chunk.copy_from_slice(line);
+            }
+            self.lines += n;
+            Ok(n * line.len())
+        }
+    }
+
+    fn contains(word: &str) -> LineFilter {
+        LineFilter {
+            contains: vec![word.to_string()],
+            ..LineFilter::default()
+        }
+    }
+
+    #[test]
+    fn scan_stops_reading_an_endless_input() {
+        let mut reader = BufReader::new(Endless {
+            lines: 0,
+            limit: 100_000,
+        });
+        let mut hits = Vec::new();
+        let (bytes, more) = scan(
+            "(stdin)",
+            &mut reader,
+            &contains("error"),
+            |_| Ok(true),
+            |n, line| {
+                hits.push((n, line.to_string()));
+                hits.len() < 3
+            },
+        )
+        .unwrap();
+        assert!(!more);
+        assert_eq!(bytes, 3 * "error\n".len());
+        assert_eq!(
+            hits,
+            [
+                (1, "error".to_string()),
+                (2, "error".to_string()),
+                (3, "error".to_string())
+            ]
+        );
+        assert!(reader.get_ref().lines < 100_000);
+    }
+
+    #[test]
+    fn scan_numbers_kept_lines_like_matching_lines() {
+        let data: &[u8] = b"skip me\r\nan error\n\nbad \xFF error\nok";
+        let filter = contains("error");
+        let mut hits = Vec::new();
+        let (bytes, more) = scan(
+            "a.txt",
+            &mut &data[..],
+            &filter,
+            |line| Ok(line != "skip me"),
+            |n, line| {
+                hits.push((n, line.to_string()));
+                true
+            },
+        )
+        .unwrap();
+        assert!(more);
+        assert_eq!(bytes, data.len());
+        assert_eq!(
+            hits,
+            [
+                (1, "an error".to_string()),
+                (3, "bad \u{FFFD} error".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+// --follow: tail -f처럼 파일 끝에서 기다리며 새로 덧붙은 줄을 하나씩 넘긴다.
+// inotify 같은 OS 기능 없이 짧은 간격으로 크기를 확인한다(표준 라이브러리만 쓴다).
+// 파일이 줄었으면(로테이션, truncate) 처음부터 다시 읽는다. 끝의 덜 쓴 줄은 줄바꿈이 올 때까지 둔다.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::thread;
+use std::time::Duration;
+
+const POLL: Duration = Duration::from_millis(250);
+
+// 돌아오지 않는다. 새 줄마다 각각 Some(줄)로, 확인할 때마다(줄이 없어도) None으로 부른다.
+pub fn run(path: &str, mut on_event: impl FnMut(Option<&str>)) -> Result<(), String> {
+    let open = || File::open(path).map_err(|e| format!("failed to read file {path}: {e}"));
+    let mut file = open()?;
+    // 이미 있던 내용은 건너뛴다(tail -f -n 0).
+    let mut offset = file
+        .seek(SeekFrom::End(0))
+        .map_err(|e| format!("failed to read file {path}: {e}"))?;
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        let len = std::fs::metadata(path).map_or(0, |m| m.len());
+        if len < offset {
+            file = open()?;
+            offset = 0;
+            pending.clear();
+        }
+        if len > offset {
+            let mut chunk = Vec::new();
+            file.seek(SeekFrom::Start(offset))
+                .and_then(|_| file.by_ref().take(len - offset).read_to_end(&mut chunk))
+                .map_err(|e| format!("failed to read file {path}: {e}"))?;
+            offset += chunk.len() as u64;
+            pending.extend_from_slice(&chunk);
+            if let Some(end) = pending.iter().rposition(|&b| b == b'\n') {
+                let complete: Vec<u8> = pending.drain(..=end).collect();
+                for line in String::from_utf8_lossy(&complete).lines() {
+                    on_event(Some(line));
+                }
+            }
+        }
+        on_event(None);
+        thread::sleep(POLL);
+    }
+}
@@ -0,0 +1,91 @@
+// 출력 형식(--format).
+// text는 사람이 읽는 "[섹션] + word: count" 형식이고, csv는 표 계산기나
+// Python의 csv.reader/pandas.read_csv로 바로 읽을 수 있는 RFC 4180 형식이다.
+// json은 report 서브커맨드가 CI에서 후처리할 수 있도록 파일별 객체 배열을 쓴다.
+// markdown은 순위표를 README나 PR 설명에 바로 붙일 수 있는 GitHub 표로, 걸린 줄은 코드 블록으로 쓴다.
+// html은 티켓이나 메일에 붙일 수 있는 파일 하나짜리 보고서다(정렬되는 표, 막대, 걸린 줄).
+// parquet과 arrow는 pandas/Polars가 바로 읽는 열 형식 바이너리다(columnar.rs).
+// dot과 gexf는 graph 서브커맨드의 동시 출현 네트워크를 Graphviz와 Gephi용으로 쓴다(graph.rs).
+// 순위표를 실제로 찍는 일은 render.rs의 Renderer가 맡는다.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Csv,
+    Json,
+    Markdown,
+    Html,
+    Parquet,
+    Arrow,
+    Dot,
+    Gexf,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Result<Format, String> {
+        match name {
+            "text" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            "parquet" => Ok(Format::Parquet),
+            "arrow" | "feather" => Ok(Format::Arrow),
+            "dot" | "graphviz" => Ok(Format::Dot),
+            "gexf" => Ok(Format::Gexf),
+            other => Err(format!(
+                "--format must be text, csv, json, markdown, html, parquet, arrow, dot, or gexf, got {other}"
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Csv => "csv",
+            Format::Json => "json",
+            Format::Markdown => "markdown",
+            Format::Html => "html",
+            Format::Parquet => "parquet",
+            Format::Arrow => "arrow",
+            Format::Dot => "dot",
+            Format::Gexf => "gexf",
+        }
+    }
+
+    // 텍스트가 아니라 바이트로 쓰는 형식. 출력 버퍼와 pager를 거치지 않는다.
+    pub fn is_columnar(self) -> bool {
+        matches!(self, Format::Parquet | Format::Arrow)
+    }
+}
+
+// 쉼표, 따옴표, 줄바꿈이 있을 때만 따옴표로 감싸고 안의 따옴표는 두 번 쓴다.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// csv_field()로 쓴 한 줄을 다시 필드로 나눈다. 따옴표 안의 쉼표와 ""를 처리한다.
+// 필드 안의 줄바꿈은 지원하지 않는다(단어/n-gram에는 나오지 않는다).
+pub fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
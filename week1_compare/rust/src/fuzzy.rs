@@ -0,0 +1,46 @@
+// fzf처럼 질의 글자가 순서대로(떨어져 있어도) 들어 있으면 일치로 본다.
+// 점수는 연속으로 맞은 글자와 단어 첫머리에서 맞은 글자에 가산점을 주고,
+// 맞은 구간이 길게 흩어질수록 깎는다. 대소문자는 구분하지 않는다.
+
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    let mut qi = 0;
+    let mut score = 0i64;
+    let mut prev_match: Option<usize> = None;
+    let mut first_match = None;
+    let mut prev_char = ' ';
+    for (i, c) in candidate.chars().enumerate() {
+        if qi < query.len() && c.to_lowercase().eq(std::iter::once(query[qi])) {
+            score += 1;
+            if prev_match == Some(i.wrapping_sub(1)) {
+                score += 5;
+            }
+            if !prev_char.is_alphanumeric() {
+                score += 3;
+            }
+            first_match.get_or_insert(i);
+            prev_match = Some(i);
+            qi += 1;
+        }
+        prev_char = c;
+    }
+    if qi < query.len() {
+        return None;
+    }
+    let span = prev_match.unwrap_or(0) - first_match.unwrap_or(0);
+    Some(score * 10 - span as i64)
+}
+
+// 일치하는 후보의 번호를 점수 내림차순으로. 점수가 같으면 원래 순서를 지킨다.
+pub fn rank(query: &str, candidates: &[&str]) -> Vec<usize> {
+    let mut scored: Vec<(i64, usize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| score(query, c).map(|s| (s, i)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, i)| i).collect()
+}
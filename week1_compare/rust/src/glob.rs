@@ -0,0 +1,35 @@
+// 불용어와 --keep-file 목록의 와일드카드 항목. "http*", "*_id"처럼 단어 무리를 한 줄로 적는다.
+// *는 글자 0개 이상, ?는 글자 하나다. 토큰 단위로 대어 보므로 단어 전체가 맞아야 한다.
+
+pub fn is_glob(word: &str) -> bool {
+    word.contains(['*', '?'])
+}
+
+// 마지막 *로 돌아가 한 글자씩 더 삼키는 방식이라 패턴 길이 * 단어 길이 안에 끝난다.
+pub fn glob_match(pattern: &str, word: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let word: Vec<char> = word.chars().collect();
+    let (mut p, mut w) = (0, 0);
+    let mut star = None;
+    while w < word.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, w));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == word[w] => {
+                p += 1;
+                w += 1;
+            }
+            _ => match star {
+                Some((sp, sw)) => {
+                    star = Some((sp, sw + 1));
+                    p = sp + 1;
+                    w = sw + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,129 @@
+// graph 서브커맨드: 상위 단어끼리의 동시 출현 네트워크.
+// 노드는 상위 --top 단어(기본 30), 간선은 두 단어가 --window 토큰 안에 함께 나온 횟수다.
+// 불용어는 창을 재기 전에 빼므로 창은 남은 단어 기준으로 잰다.
+// --format dot은 Graphviz(neato, sfdp), --format gexf는 Gephi가 그대로 연다.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::counter::Counter;
+use crate::Analysis;
+use crate::{escape_html, normalize_words};
+
+pub struct Graph {
+    // (단어, 빈도), 순위 순서.
+    pub nodes: Vec<(String, usize)>,
+    // (노드 번호, 노드 번호, 동시 출현 횟수). 무게가 큰 순서, 같으면 노드 번호 순서.
+    pub edges: Vec<(usize, usize, usize)>,
+}
+
+pub fn build(text: &str, analysis: &Analysis, window: usize) -> Graph {
+    let words: Vec<String> = normalize_words(text, analysis.tokenizer())
+        .into_iter()
+        .filter(|word| !analysis.is_stopword(word))
+        .collect();
+    let mut counts = Counter::new();
+    for word in &words {
+        counts.add(word, 1);
+    }
+    let nodes = analysis.rank(counts);
+    let ids: HashMap<&str, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(id, (word, _))| (word.as_str(), id))
+        .collect();
+    let stream: Vec<Option<usize>> = words.iter().map(|w| ids.get(w.as_str()).copied()).collect();
+
+    let mut weights: HashMap<(usize, usize), usize> = HashMap::new();
+    for (i, a) in stream.iter().enumerate() {
+        let Some(a) = *a else { continue };
+        for b in stream[i + 1..].iter().take(window).flatten() {
+            if a != *b {
+                *weights.entry((a.min(*b), a.max(*b))).or_default() += 1;
+            }
+        }
+    }
+    let mut edges: Vec<(usize, usize, usize)> =
+        weights.into_iter().map(|((a, b), n)| (a, b, n)).collect();
+    edges.sort_by(|x, y| y.2.cmp(&x.2).then((x.0, x.1).cmp(&(y.0, y.1))));
+    Graph { nodes, edges }
+}
+
+pub fn text(graph: &Graph, out: &mut String) {
+    let _ = writeln!(out, "[Co-occurrence]");
+    for &(a, b, n) in &graph.edges {
+        let _ = writeln!(out, "{} -- {}: {n}", graph.nodes[a].0, graph.nodes[b].0);
+    }
+}
+
+// 무방향 그래프. 노드 크기는 빈도, 간선 굵기는 무게에 비례하게 둔다(가장 큰 값이 각각 3, 5).
+pub fn dot(graph: &Graph, out: &mut String) {
+    let max_count = graph.nodes.iter().map(|(_, n)| *n).max().unwrap_or(1);
+    let max_weight = graph.edges.iter().map(|e| e.2).max().unwrap_or(1);
+    let _ = writeln!(out, "graph cooccurrence {{");
+    let _ = writeln!(out, "  node [shape=circle, fixedsize=true];");
+    for (word, n) in &graph.nodes {
+        let size = 0.5 + 2.5 * *n as f64 / max_count as f64;
+        let _ = writeln!(
+            out,
+            "  {} [count={n}, width={size:.2}, tooltip=\"{n}\"];",
+            dot_id(word)
+        );
+    }
+    for &(a, b, n) in &graph.edges {
+        let width = 0.5 + 4.5 * n as f64 / max_weight as f64;
+        let _ = writeln!(
+            out,
+            "  {} -- {} [weight={n}, penwidth={width:.2}];",
+            dot_id(&graph.nodes[a].0),
+            dot_id(&graph.nodes[b].0)
+        );
+    }
+    let _ = writeln!(out, "}}");
+}
+
+fn dot_id(word: &str) -> String {
+    format!("\"{}\"", word.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// GEXF 1.3: 노드 속성 count와 간선 weight를 싣는다. Gephi에서 크기/굵기 순위로 바로 쓸 수 있다.
+pub fn gexf(graph: &Graph, out: &mut String) {
+    let _ = writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    let _ = writeln!(out, "<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">");
+    let _ = writeln!(out, "  <meta>");
+    let _ = writeln!(
+        out,
+        "    <creator>week1_rust_word_count {}</creator>",
+        env!("CARGO_PKG_VERSION")
+    );
+    let _ = writeln!(out, "  </meta>");
+    let _ = writeln!(
+        out,
+        "  <graph mode=\"static\" defaultedgetype=\"undirected\">"
+    );
+    let _ = writeln!(out, "    <attributes class=\"node\">");
+    let _ = writeln!(
+        out,
+        "      <attribute id=\"count\" title=\"count\" type=\"long\"/>"
+    );
+    let _ = writeln!(out, "    </attributes>");
+    let _ = writeln!(out, "    <nodes>");
+    for (id, (word, n)) in graph.nodes.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "      <node id=\"{id}\" label=\"{}\"><attvalues><attvalue for=\"count\" value=\"{n}\"/></attvalues></node>",
+            escape_html(word)
+        );
+    }
+    let _ = writeln!(out, "    </nodes>");
+    let _ = writeln!(out, "    <edges>");
+    for (id, &(a, b, n)) in graph.edges.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "      <edge id=\"{id}\" source=\"{a}\" target=\"{b}\" weight=\"{n}\"/>"
+        );
+    }
+    let _ = writeln!(out, "    </edges>");
+    let _ = writeln!(out, "  </graph>");
+    let _ = writeln!(out, "</gexf>");
+}
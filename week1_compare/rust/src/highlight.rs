@@ -0,0 +1,88 @@
+// 필터된 줄에서 --contains 키워드를 ANSI 색으로 강조한다.
+// 키워드마다 다른 색을 쓰고, 겹치는 구간("rust"와 "rustacean")은 앞에 준 키워드의 색이
+// 먼저 칠해지고 나머지 글자만 뒤 키워드 색이 되어 경계가 드러난다.
+// 위치 계산은 ASCII 소문자 기준이다(바이트 길이가 그대로라 원문 오프셋과 일치한다).
+
+use std::env;
+use std::io::{self, IsTerminal};
+
+const PALETTE: [&str; 6] = [
+    "\x1b[1;31m", // 빨강
+    "\x1b[1;32m", // 초록
+    "\x1b[1;33m", // 노랑
+    "\x1b[1;34m", // 파랑
+    "\x1b[1;35m", // 자홍
+    "\x1b[1;36m", // 청록
+];
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    pub fn parse(name: &str) -> Result<ColorMode, String> {
+        match name {
+            "auto" => Ok(ColorMode::Auto),
+            "always" => Ok(ColorMode::Always),
+            "never" => Ok(ColorMode::Never),
+            other => Err(format!(
+                "--color must be auto, always, or never, got {other}"
+            )),
+        }
+    }
+
+    // auto는 stdout이 터미널이고 파일로 쓰지 않으며 NO_COLOR가 없을 때만 켠다.
+    pub fn enabled(self, to_file: bool) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => {
+                !to_file && env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+pub fn highlight(line: &str, keywords: &[String]) -> String {
+    let lowered = line.to_ascii_lowercase();
+    // 바이트마다 칠할 키워드 번호.
+    let mut paint: Vec<Option<usize>> = vec![None; line.len()];
+    for (k, keyword) in keywords.iter().enumerate() {
+        let needle = keyword.to_ascii_lowercase();
+        if needle.is_empty() {
+            continue;
+        }
+        let mut from = 0;
+        while let Some(pos) = lowered[from..].find(&needle) {
+            let start = from + pos;
+            for slot in &mut paint[start..start + needle.len()] {
+                slot.get_or_insert(k);
+            }
+            from = start + needle.len();
+        }
+    }
+
+    let mut out = String::with_capacity(line.len() + 16);
+    let mut current = None;
+    for (i, c) in line.char_indices() {
+        let color = paint[i];
+        if color != current {
+            if current.is_some() {
+                out.push_str(RESET);
+            }
+            if let Some(k) = color {
+                out.push_str(PALETTE[k % PALETTE.len()]);
+            }
+            current = color;
+        }
+        out.push(c);
+    }
+    if current.is_some() {
+        out.push_str(RESET);
+    }
+    out
+}
@@ -0,0 +1,72 @@
+// 대화형 모드의 질의 기록(~/.wordtool_history).
+// 셸의 HISTFILE처럼 한 줄에 질의 하나, 오래된 것이 위에 있다. 세션을 다시 열어도
+// 위 화살표로 이전 질의를 불러오고 Ctrl-R로 검색할 수 있게 한다.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::output;
+
+// 이보다 길어지면 오래된 기록부터 버린다.
+const LIMIT: usize = 1000;
+
+fn path() -> Option<PathBuf> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(".wordtool_history"))
+}
+
+pub struct History {
+    entries: Vec<String>,
+}
+
+impl History {
+    // 파일이 없거나 읽을 수 없으면 빈 기록으로 시작한다(기록은 있으면 좋은 부가 기능이다).
+    pub fn load() -> History {
+        let entries = path()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .map(|text| {
+                text.lines()
+                    .filter(|l| !l.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        History { entries }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(String::as_str)
+    }
+
+    // before보다 앞(오래된 쪽)에서 needle을 포함하는 가장 최근 기록의 번호.
+    pub fn search(&self, needle: &str, before: usize) -> Option<usize> {
+        self.entries[..before.min(self.entries.len())]
+            .iter()
+            .rposition(|e| e.contains(needle))
+    }
+
+    // 바로 앞 기록과 같으면 다시 넣지 않는다(HISTCONTROL=ignoredups). 저장 실패는 무시한다.
+    pub fn push(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty()
+            || query.contains('\n')
+            || self.entries.last().is_some_and(|e| e == query)
+        {
+            return;
+        }
+        self.entries.push(query.to_string());
+        if self.entries.len() > LIMIT {
+            self.entries.drain(..self.entries.len() - LIMIT);
+        }
+        if let Some(p) = path().as_deref().and_then(|p| p.to_str()) {
+            let mut text = self.entries.join("\n");
+            text.push('\n');
+            let _ = output::write(p, &text, false);
+        }
+    }
+}
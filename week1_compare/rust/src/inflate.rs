@@ -0,0 +1,233 @@
+// DEFLATE(RFC 1951) 압축 해제.
+// Python이라면 zlib.decompress() 한 줄이지만, 외부 crate 없이 ZIP/EPUB/DOCX/PDF를 읽으려면
+// 직접 구현해야 한다. 저장(stored), 고정 허프만, 동적 허프만 블록을 모두 지원한다.
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+// 동적 블록에서 코드 길이 부호의 길이가 저장되는 순서.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bit: u32,
+}
+
+impl BitReader<'_> {
+    fn bits(&mut self, n: u32) -> Result<u32, String> {
+        let mut value = 0u32;
+        for i in 0..n {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or_else(|| "deflate: unexpected end of data".to_string())?;
+            value |= u32::from((byte >> self.bit) & 1) << i;
+            self.bit += 1;
+            if self.bit == 8 {
+                self.bit = 0;
+                self.pos += 1;
+            }
+        }
+        Ok(value)
+    }
+
+    fn align_to_byte(&mut self) {
+        if self.bit != 0 {
+            self.bit = 0;
+            self.pos += 1;
+        }
+    }
+}
+
+// 정준(canonical) 허프만 부호: 길이별 개수와 길이 순으로 정렬된 심벌 목록.
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut offsets = [0u16; 16];
+        for len in 1..16 {
+            offsets[len] = offsets[len - 1] + counts[len - 1];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[offsets[len as usize] as usize] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+        Huffman { counts, symbols }
+    }
+
+    // 비트를 하나씩 읽으며 길이 1부터 해당 길이의 부호 구간에 들어가는지 확인한다.
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, String> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for len in 1..16 {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - count < first {
+                return Ok(self.symbols[(index + (code - first)) as usize]);
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        Err("deflate: invalid Huffman code".to_string())
+    }
+}
+
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut reader = BitReader {
+        data,
+        pos: 0,
+        bit: 0,
+    };
+    let mut out: Vec<u8> = Vec::with_capacity(data.len() * 3);
+    loop {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => stored_block(&mut reader, &mut out)?,
+            1 => {
+                let (lit, dist) = fixed_tables();
+                compressed_block(&mut reader, &mut out, &lit, &dist)?;
+            }
+            2 => {
+                let (lit, dist) = dynamic_tables(&mut reader)?;
+                compressed_block(&mut reader, &mut out, &lit, &dist)?;
+            }
+            _ => return Err("deflate: invalid block type".to_string()),
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+fn stored_block(reader: &mut BitReader, out: &mut Vec<u8>) -> Result<(), String> {
+    reader.align_to_byte();
+    let header = reader
+        .data
+        .get(reader.pos..reader.pos + 4)
+        .ok_or_else(|| "deflate: truncated stored block".to_string())?;
+    let len = u16::from_le_bytes([header[0], header[1]]) as usize;
+    let nlen = u16::from_le_bytes([header[2], header[3]]) as usize;
+    if len != !nlen & 0xFFFF {
+        return Err("deflate: stored block length mismatch".to_string());
+    }
+    reader.pos += 4;
+    let bytes = reader
+        .data
+        .get(reader.pos..reader.pos + len)
+        .ok_or_else(|| "deflate: truncated stored block".to_string())?;
+    out.extend_from_slice(bytes);
+    reader.pos += len;
+    Ok(())
+}
+
+fn fixed_tables() -> (Huffman, Huffman) {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    (Huffman::new(&lengths), Huffman::new(&[5u8; 30]))
+}
+
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    let hlit = reader.bits(5)? as usize + 257;
+    let hdist = reader.bits(5)? as usize + 1;
+    let hclen = reader.bits(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for &index in CODE_LENGTH_ORDER.iter().take(hclen) {
+        code_lengths[index] = reader.bits(3)? as u8;
+    }
+    let code_huffman = Huffman::new(&code_lengths);
+
+    let mut lengths = vec![0u8; hlit + hdist];
+    let mut i = 0;
+    while i < lengths.len() {
+        let symbol = code_huffman.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let prev = *lengths
+                    .get(i.wrapping_sub(1))
+                    .ok_or_else(|| "deflate: repeat with no previous length".to_string())?;
+                (prev, 3 + reader.bits(2)? as usize)
+            }
+            17 => (0, 3 + reader.bits(3)? as usize),
+            18 => (0, 11 + reader.bits(7)? as usize),
+            _ => return Err("deflate: invalid code length symbol".to_string()),
+        };
+        if i + repeat > lengths.len() {
+            return Err("deflate: code lengths overflow".to_string());
+        }
+        lengths[i..i + repeat].fill(value);
+        i += repeat;
+    }
+    Ok((
+        Huffman::new(&lengths[..hlit]),
+        Huffman::new(&lengths[hlit..]),
+    ))
+}
+
+fn compressed_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    lit: &Huffman,
+    dist: &Huffman,
+) -> Result<(), String> {
+    loop {
+        let symbol = lit.decode(reader)? as usize;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            257..=285 => {
+                let idx = symbol - 257;
+                let length =
+                    LENGTH_BASE[idx] as usize + reader.bits(u32::from(LENGTH_EXTRA[idx]))? as usize;
+                let dsym = dist.decode(reader)? as usize;
+                if dsym >= 30 {
+                    return Err("deflate: invalid distance symbol".to_string());
+                }
+                let distance =
+                    DIST_BASE[dsym] as usize + reader.bits(u32::from(DIST_EXTRA[dsym]))? as usize;
+                if distance > out.len() {
+                    return Err("deflate: distance points before start of output".to_string());
+                }
+                // 겹치는 복사(distance < length)가 가능하므로 한 바이트씩 복사한다.
+                let start = out.len() - distance;
+                for k in 0..length {
+                    out.push(out[start + k]);
+                }
+            }
+            _ => return Err("deflate: invalid literal/length symbol".to_string()),
+        }
+    }
+}
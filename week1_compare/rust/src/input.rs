@@ -0,0 +1,345 @@
+// 입력 읽기와 디코딩.
+// 모든 모드는 파일을 여기서 한 번만 읽고, --json-field 같은 입력 형식 옵션이 있으면
+// "분석할 텍스트"로 바꾼 결과(한 레코드당 한 줄)를 받는다.
+// Python이라면 open().read() 뒤에 if/elif로 처리할 부분을 Decoder 열거형 하나로 모았다.
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use crate::batch;
+use crate::docx;
+use crate::epub::{self, Chapter};
+use crate::extract;
+use crate::interrupt;
+use crate::journal;
+use crate::json;
+use crate::pdf;
+use crate::script;
+use crate::subtitles::{self, Cue};
+use crate::timing;
+use crate::trailer;
+use crate::xml::{self, ElementPath};
+
+#[derive(Debug, Clone, Default)]
+pub enum Decoder {
+    #[default]
+    Plain,
+    // NDJSON의 각 줄에서 점 경로(a.b.c)로 고른 문자열 필드만 남긴다.
+    JsonField(Vec<String>),
+    // XML 텍스트 노드. 요소 경로가 있으면 그 요소 안의 텍스트만.
+    Xml(Option<ElementPath>),
+    // EPUB 책: spine 순서대로 각 장의 본문 문단.
+    Epub,
+    // SRT/VTT 자막: 큐 번호와 타이밍 줄을 빼고 큐 하나당 한 줄.
+    Subtitles,
+    // Word 문서(DOCX): word/document.xml의 문단마다 한 줄.
+    Docx,
+    // PDF의 텍스트 층. 쪽 순서대로 이어 붙인다.
+    Pdf,
+    // --extract-cmd: 바깥 명령의 stdout을 평문으로 받는다.
+    Extract(String),
+}
+
+// 분석할 원본이 어디서 오는가.
+#[derive(Debug, Clone)]
+pub enum Source {
+    // 명령행 순서 그대로의 파일 목록. "-"는 그 자리에 stdin을 끼워 넣는다.
+    Files(Vec<String>),
+    // --clipboard: 시스템 클립보드(pbpaste, wl-paste, xclip, xsel, PowerShell 순으로 시도).
+    Clipboard,
+}
+
+// 입력 단위(파일 하나, stdin, 클립보드)마다 따로 읽는다. XML이나 EPUB처럼 한 파일이
+// 한 문서인 형식은 이어 붙이기 전에 각각 디코딩해야 하기 때문이다.
+// 실패도 입력별로 돌려주고, 건너뛸지 중단할지는 batch::settle()이 정한다.
+pub fn read_parts(source: &Source) -> Vec<(String, Result<Vec<u8>, String>)> {
+    match source {
+        Source::Files(paths) => {
+            let mut parts = Vec::with_capacity(paths.len());
+            for path in paths {
+                // 신호를 받았으면 읽던 파일까지만 쓰고 나머지는 읽지 않는다.
+                if interrupt::interrupted() {
+                    interrupt::skipped(paths.len() - parts.len());
+                    break;
+                }
+                if path == "-" {
+                    parts.push(("(stdin)".to_string(), read_stdin()));
+                } else {
+                    let data =
+                        fs::read(path).map_err(|e| format!("failed to read file {path}: {e}"));
+                    parts.push((path.clone(), data));
+                }
+            }
+            parts
+        }
+        Source::Clipboard => vec![("(clipboard)".to_string(), read_clipboard())],
+    }
+}
+
+// 입력을 통째로 읽지 않고 단위마다 BufRead로 넘긴다. --max-matches처럼 앞쪽만 보면 되는 줄 필터가
+// 다 찾은 뒤에는 나머지를 읽지 않도록 each가 false를 돌려주면 그 자리에서 멈춘다(뒤 파일은 열지도
+// 않는다). 그래서 stdin은 read_stdin처럼 끝까지 읽어 보관하지 않고, 끝나지 않는 파이프에서도 멈춘다.
+// 클립보드는 명령 출력을 한 번에 받을 수밖에 없다.
+pub fn stream(
+    source: &Source,
+    mut each: impl FnMut(&str, &mut dyn BufRead) -> Result<bool, String>,
+) -> Result<(), String> {
+    let Source::Files(paths) = source else {
+        let data = read_clipboard()?;
+        trailer::files(1);
+        each("(clipboard)", &mut data.as_slice())?;
+        return Ok(());
+    };
+    let mut opened = 0;
+    for (i, path) in paths.iter().enumerate() {
+        if interrupt::interrupted() {
+            interrupt::skipped(paths.len() - i);
+            return Ok(());
+        }
+        let (name, reader): (&str, Result<Box<dyn BufRead>, String>) = if path == "-" {
+            ("(stdin)", Ok(Box::new(io::stdin().lock())))
+        } else {
+            let file = fs::File::open(path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|e| format!("failed to read file {path}: {e}"));
+            (path, file)
+        };
+        let Some(mut reader) = batch::settle_one(name, reader, paths.len())? else {
+            continue;
+        };
+        opened += 1;
+        trailer::files(1);
+        if !each(name, &mut reader)? {
+            return Ok(());
+        }
+    }
+    if opened == 0 && paths.len() > 1 {
+        return Err(format!("none of the {} inputs could be read", paths.len()));
+    }
+    Ok(())
+}
+
+// 내용을 쓰려고 읽을 때. 캐시 키처럼 바이트만 보는 곳은 read_parts를 바로 부른다.
+fn read_counted(source: &Source) -> Vec<(String, Result<Vec<u8>, String>)> {
+    let parts = read_parts(source);
+    trailer::files(parts.iter().filter(|(_, data)| data.is_ok()).count());
+    journal::inputs(&parts);
+    parts
+}
+
+fn total_bytes(parts: &[(String, Result<Vec<u8>, String>)]) -> usize {
+    parts
+        .iter()
+        .filter_map(|(_, data)| data.as_ref().ok())
+        .map(Vec::len)
+        .sum()
+}
+
+// --per-chapter처럼 같은 입력을 두 번 읽는 모드가 있어서, stdin은 처음 한 번만 읽고 보관한다.
+fn read_stdin() -> Result<Vec<u8>, String> {
+    static STDIN: OnceLock<Result<Vec<u8>, String>> = OnceLock::new();
+    STDIN
+        .get_or_init(|| {
+            let mut data = Vec::new();
+            io::stdin()
+                .read_to_end(&mut data)
+                .map(|_| data)
+                .map_err(|e| format!("failed to read stdin: {e}"))
+        })
+        .clone()
+}
+
+fn into_string(data: Vec<u8>) -> Result<String, String> {
+    String::from_utf8(data)
+        .map_err(|_| "failed to read file: stream did not contain valid UTF-8".to_string())
+}
+
+// Python의 pyperclip처럼 플랫폼별 명령을 차례로 시도한다. 설치되지 않은 명령은 건너뛴다.
+fn read_clipboard() -> Result<Vec<u8>, String> {
+    const TOOLS: &[(&str, &[&str])] = &[
+        ("pbpaste", &[]),
+        ("wl-paste", &["--no-newline"]),
+        ("xclip", &["-selection", "clipboard", "-o"]),
+        ("xsel", &["--clipboard", "--output"]),
+        ("powershell", &["-NoProfile", "-Command", "Get-Clipboard"]),
+    ];
+    for (program, tool_args) in TOOLS {
+        let Ok(output) = Command::new(program).args(*tool_args).output() else {
+            continue;
+        };
+        if output.status.success() {
+            return Ok(output.stdout);
+        }
+    }
+    Err(
+        "failed to read clipboard: install pbpaste, wl-paste, xclip, or xsel (or pass a file)"
+            .to_string(),
+    )
+}
+
+// Rust는 실패 가능성을 Result로 타입에 드러내며,
+// 파일 읽기 실패를 컴파일러가 인지 가능한 흐름으로 강제한다.
+pub fn read(source: &Source, decoder: &Decoder) -> Result<String, String> {
+    Ok(join_documents(&read_documents(source, decoder)?))
+}
+
+pub fn join_documents(documents: &[(String, String)]) -> String {
+    let mut text = String::new();
+    for (_, decoded) in documents {
+        // 앞 파일이 줄바꿈 없이 끝나도 다음 파일 첫 줄과 붙지 않게 한다.
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(decoded);
+    }
+    text
+}
+
+// 입력 단위마다 (이름, 디코딩된 텍스트). --line-numbers가 파일별 줄 번호를 매길 때 쓴다.
+// 이름은 파일 경로이고, stdin은 "(stdin)", 클립보드는 "(clipboard)"다.
+pub fn read_documents(source: &Source, decoder: &Decoder) -> Result<Vec<(String, String)>, String> {
+    let start = Instant::now();
+    let parts = read_counted(source);
+    let bytes = total_bytes(&parts);
+    timing::record("read", start, bytes, None);
+
+    let start = Instant::now();
+    let results = parts
+        .into_iter()
+        .map(|(name, data)| {
+            let text = data.and_then(|part| match decoder {
+                // EPUB은 ZIP 바이너리라 UTF-8 문자열로 읽을 수 없다.
+                Decoder::Epub => Ok(join_lines(
+                    epub::chapters(&part)?.into_iter().map(|c| c.text).collect(),
+                )),
+                Decoder::Docx => Ok(join_lines(docx::paragraphs(&part)?)),
+                Decoder::Pdf => pdf::text(&part),
+                Decoder::Extract(command) => {
+                    let path =
+                        (name != "(stdin)" && name != "(clipboard)").then_some(name.as_str());
+                    extract::run(command, path, part)
+                }
+                _ => decode(&into_string(part)?, decoder),
+            });
+            let text = text.and_then(script::keep_lines);
+            (name, text)
+        })
+        .collect();
+    let documents = batch::settle(results)?;
+    timing::record("decode", start, bytes, None);
+    Ok(documents)
+}
+
+// 줄 필터용 평문 읽기. 긴 로그 깊숙이 깨진 바이트 하나 때문에 검색 전체가 멈추지 않도록
+// 잘못된 UTF-8은 U+FFFD로 바꾼다. 온전한 파일은 복사하지 않고 그대로 쓴다.
+pub fn read_documents_lossy(source: &Source) -> Result<Vec<(String, String)>, String> {
+    let start = Instant::now();
+    let parts = read_counted(source);
+    let bytes = total_bytes(&parts);
+    timing::record("read", start, bytes, None);
+    let results = parts
+        .into_iter()
+        .map(|(name, data)| {
+            let text = data
+                .map(|part| match String::from_utf8(part) {
+                    Ok(text) => text,
+                    Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+                })
+                .and_then(script::keep_lines);
+            (name, text)
+        })
+        .collect();
+    batch::settle(results)
+}
+
+pub fn read_chapters(source: &Source) -> Result<Vec<Chapter>, String> {
+    let start = Instant::now();
+    let parts = read_counted(source);
+    let bytes = total_bytes(&parts);
+    timing::record("read", start, bytes, None);
+    let start = Instant::now();
+    let results = parts
+        .into_iter()
+        .map(|(name, data)| (name, data.and_then(|part| epub::chapters(&part))))
+        .collect();
+    let chapters = batch::settle(results)?
+        .into_iter()
+        .flat_map(|(_, chapters)| chapters)
+        .collect();
+    timing::record("decode", start, bytes, None);
+    Ok(chapters)
+}
+
+pub fn read_cues(source: &Source) -> Result<Vec<Cue>, String> {
+    let results = read_counted(source)
+        .into_iter()
+        .map(|(name, data)| {
+            let cues = data
+                .and_then(into_string)
+                .map(|text| subtitles::parse(&text));
+            (name, cues)
+        })
+        .collect();
+    Ok(batch::settle(results)?
+        .into_iter()
+        .flat_map(|(_, cues)| cues)
+        .collect())
+}
+
+pub fn decode(content: &str, decoder: &Decoder) -> Result<String, String> {
+    match decoder {
+        Decoder::Plain => Ok(content.to_string()),
+        Decoder::Epub => Err("EPUB input must be read with input::read".to_string()),
+        Decoder::Docx => Err("DOCX input must be read with input::read".to_string()),
+        Decoder::Pdf => Err("PDF input must be read with input::read".to_string()),
+        Decoder::Extract(_) => Err("--extract-cmd input must be read with input::read".to_string()),
+        Decoder::JsonField(path) => Ok(json_field(content, path)),
+        Decoder::Xml(path) => Ok(join_lines(xml::text_lines(content, path.as_ref(), None))),
+        Decoder::Subtitles => Ok(join_lines(
+            subtitles::parse(content)
+                .into_iter()
+                .map(|c| c.text)
+                .collect(),
+        )),
+    }
+}
+
+fn json_field(content: &str, path: &[String]) -> String {
+    let mut text = String::new();
+    let mut invalid = 0usize;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let value = match json::parse(line) {
+            Ok(v) => v,
+            Err(_) => {
+                invalid += 1;
+                continue;
+            }
+        };
+        let mut selected = Vec::new();
+        value.select(path, &mut selected);
+        for s in selected.into_iter().filter_map(json::Value::as_str) {
+            // 필드 안의 줄바꿈은 공백으로 바꿔 "레코드 하나 = 한 줄"을 유지한다.
+            text.push_str(&s.replace(['\n', '\r'], " "));
+            text.push('\n');
+        }
+    }
+    if invalid > 0 {
+        eprintln!("skipped {invalid} line(s) that are not valid JSON");
+    }
+    text
+}
+
+fn join_lines(lines: Vec<String>) -> String {
+    let mut text = lines
+        .into_iter()
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,65 @@
+// 여러 파일을 세는 도중의 Ctrl-C(SIGINT)와 SIGTERM. 곧바로 죽으면 몇 시간 센 결과가 통째로 사라지고
+// 쓰던 출력 파일도 반쯤 남으므로, 처리기는 깃발만 세운다. 파일을 읽는 곳(input, search, checkpoint)이
+// 다음 파일로 넘어가기 전에 이를 보고 멈추고, main은 읽은 데까지의 결과에 미완성 표시를 붙여 쓴 뒤
+// EXIT_CODE로 끝낸다. 두 번째 신호는 기본 동작으로 되돌려 두었으므로 바로 끝난다.
+// 파일이 하나뿐이거나 stdin, 클립보드를 읽을 때는 설치하지 않는다(기다리는 stdin을 Ctrl-C로 끊을 수 있게).
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+// 128 + SIGINT. 셸이 Ctrl-C로 끝난 명령에 주는 값과 같다.
+pub const EXIT_CODE: i32 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+// 읽지 않고 건너뛴 파일 수. 같은 입력을 두 번 읽는 모드가 있어 가장 큰 값을 둔다.
+static SKIPPED: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(unix)]
+extern "C" {
+    fn signal(signum: i32, handler: usize) -> usize;
+}
+
+#[cfg(unix)]
+const SIGINT: i32 = 2;
+#[cfg(unix)]
+const SIGTERM: i32 = 15;
+#[cfg(unix)]
+const SIG_DFL: usize = 0;
+
+// 신호 처리기 안에서는 원자적 저장과 signal()처럼 async-signal-safe한 호출만 한다.
+#[cfg(unix)]
+extern "C" fn on_signal(signum: i32) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+    // SAFETY: SIG_DFL로 되돌리는 것뿐이다.
+    unsafe {
+        signal(signum, SIG_DFL);
+    }
+}
+
+#[cfg(unix)]
+pub fn install() {
+    let handler: extern "C" fn(i32) = on_signal;
+    for signum in [SIGINT, SIGTERM] {
+        // SAFETY: 처리기는 위의 extern "C" fn이고 깃발만 바꾼다.
+        unsafe {
+            signal(signum, handler as usize);
+        }
+    }
+}
+
+// 다른 플랫폼에서는 예전처럼 바로 끝난다.
+#[cfg(not(unix))]
+pub fn install() {}
+
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+pub fn skipped(n: usize) {
+    SKIPPED.fetch_max(n, Ordering::Relaxed);
+}
+
+// 건너뛴 파일이 있을 때만 결과가 미완성이다(마지막 파일을 다 읽은 뒤 받은 신호는 종료 코드만 바꾼다).
+pub fn incomplete() -> Option<usize> {
+    let n = SKIPPED.load(Ordering::Relaxed);
+    (interrupted() && n > 0).then_some(n)
+}
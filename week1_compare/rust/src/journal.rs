@@ -0,0 +1,135 @@
+// --journal PATH: 실행마다 무엇을 어떤 설정으로 분석했는지 NDJSON 한 줄로 덧붙인다.
+// 오래 걸리는 실험에서 나중에 "이 결과는 어느 입력, 어느 옵션이었나"를 되짚는 감사 기록이다.
+// 한 줄: {"time", "command", "options": [...], "inputs": [{"name", "bytes", "sha256"}],
+//         "summary": {"bytes", "tokens", "distinct", "output_lines", "elapsed_ms"}, "ok"}
+// 실패한 실행도 남긴다(ok가 false이고 "error"에 메시지). 입력을 읽을 수 없으면 해시는 null이다.
+// 기록하려고 입력을 다시 읽고 세지 않는다. 해시는 input.rs가 입력을 읽을 때 그 바이트로 내고,
+// tokens와 distinct는 이번 실행이 센 것 중 가장 큰 집계다(--summary와 같다. 세지 않는 명령은 0).
+// 캐시에서 결과를 돌려준 실행처럼 입력을 읽지 않았을 때만 여기서 읽어 해시한다.
+// 기록 파일에는 줄을 O_APPEND로 붙이므로 같은 파일에 여러 실행이 동시에 써도 된다.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::cli::Args;
+use crate::input;
+use crate::json::{self, Value};
+use crate::output;
+use crate::schedule;
+use crate::sha256::Sha256;
+
+struct Input {
+    name: String,
+    // 읽지 못했으면 None.
+    hashed: Option<(usize, String)>,
+}
+
+// --every는 회차마다 기록하므로 record가 비우고 다시 모은다.
+static INPUTS: Mutex<Vec<Input>> = Mutex::new(Vec::new());
+static TOKENS: AtomicUsize = AtomicUsize::new(0);
+static DISTINCT: AtomicUsize = AtomicUsize::new(0);
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// main이 --journal일 때 켠다. 꺼져 있으면 inputs는 해시를 내지 않는다.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+// 읽은 입력 단위. --per-chapter처럼 같은 입력을 두 번 읽어도 한 번만 남긴다.
+pub fn inputs(parts: &[(String, Result<Vec<u8>, String>)]) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut inputs = INPUTS.lock().unwrap_or_else(|e| e.into_inner());
+    for (name, data) in parts {
+        if !inputs.iter().any(|input| input.name == *name) {
+            inputs.push(hash(name, data));
+        }
+    }
+}
+
+pub fn counted(tokens: usize, distinct: usize) {
+    TOKENS.fetch_max(tokens, Ordering::Relaxed);
+    DISTINCT.fetch_max(distinct, Ordering::Relaxed);
+}
+
+fn hash(name: &str, data: &Result<Vec<u8>, String>) -> Input {
+    let hashed = data.as_ref().ok().map(|data| {
+        let mut hasher = Sha256::new();
+        hasher.update(data);
+        (data.len(), hasher.hex_digest())
+    });
+    Input {
+        name: name.to_string(),
+        hashed,
+    }
+}
+
+fn field(name: &str, value: Value) -> (String, Value) {
+    (name.to_string(), value)
+}
+
+fn number(n: usize) -> Value {
+    Value::Number(n as f64)
+}
+
+pub fn record(
+    path: &str,
+    args: &Args,
+    elapsed: Duration,
+    outcome: Result<&str, &str>,
+) -> Result<(), String> {
+    let options = args.argv.iter().cloned().map(Value::String).collect();
+    let mut seen = std::mem::take(&mut *INPUTS.lock().unwrap_or_else(|e| e.into_inner()));
+    if seen.is_empty() {
+        seen = input::read_parts(&args.source)
+            .iter()
+            .map(|(name, data)| hash(name, data))
+            .collect();
+    }
+    let total: usize = seen
+        .iter()
+        .filter_map(|input| input.hashed.as_ref())
+        .map(|(bytes, _)| bytes)
+        .sum();
+    let inputs = seen
+        .into_iter()
+        .map(|input| {
+            let (bytes, hash) = match input.hashed {
+                Some((bytes, hash)) => (number(bytes), Value::String(hash)),
+                None => (Value::Null, Value::Null),
+            };
+            Value::Object(vec![
+                field("name", Value::String(input.name)),
+                field("bytes", bytes),
+                field("sha256", hash),
+            ])
+        })
+        .collect();
+    let mut summary = vec![
+        field("bytes", number(total)),
+        field("tokens", number(TOKENS.swap(0, Ordering::Relaxed))),
+        field("distinct", number(DISTINCT.swap(0, Ordering::Relaxed))),
+    ];
+    if let Ok(out) = outcome {
+        summary.push(field("output_lines", number(out.lines().count())));
+    }
+    summary.push(field("elapsed_ms", number(elapsed.as_millis() as usize)));
+    let mut record = vec![
+        field(
+            "time",
+            Value::String(schedule::utc_timestamp(SystemTime::now())),
+        ),
+        field("command", Value::String(args.command.name().to_string())),
+        field("options", Value::Array(options)),
+        field("inputs", Value::Array(inputs)),
+        field("summary", Value::Object(summary)),
+        field("ok", Value::Bool(outcome.is_ok())),
+    ];
+    if let Err(e) = outcome {
+        record.push(field("error", Value::String(e.to_string())));
+    }
+    output::append_line(path, &(json::to_line(&Value::Object(record)) + "\n"))
+        .map_err(|e| format!("--journal: {e}"))
+}
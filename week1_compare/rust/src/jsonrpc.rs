@@ -0,0 +1,328 @@
+// --jsonrpc: stdin/stdout으로 JSON-RPC 2.0을 주고받는 상주 모드. 편집기 플러그인이 자식 프로세스로
+// 띄워 두고 버퍼 내용을 보내 분석 결과를 받는 용도다. 메시지는 LSP처럼 머리글로 나눈다:
+//   Content-Length: N\r\n\r\n{...N바이트 JSON...}
+// 메서드(params의 text는 버퍼 내용, 없으면 path의 파일을 읽는다):
+//   initialize                          서버 이름, 버전, 메서드 목록
+//   analyze   {text|path, n}            토큰 수, 서로 다른 단어 수, 상위 단어
+//   top       {text|path, n}            상위 단어 [{word, count}] (n 기본값은 --top, 0 = 전체)
+//   filter    {text|path, query|contains, limit}
+//                                       걸린 줄 [{line, text}] (query는 --query 문법)
+//   positions {text|path, word}         [{line, column, offset}] (positions 서브커맨드와 같다)
+//   shutdown                            null로 답하고, 뒤이은 exit 알림에서 끝낸다
+// id가 없는 요청은 알림이라 답하지 않는다. 토큰화와 불용어는 명령행 옵션을 따른다.
+// --root DIR를 주면 path는 DIR 안의 파일만 받는다(sandbox.rs).
+// Content-Length가 --max-request(기본 1M)를 넘으면 본문을 담지 않고 읽어 버린 뒤 에러로 답한다.
+
+use std::io::{self, BufRead, Read, Write};
+
+use crate::cli::Args;
+use crate::filter::{self, LineFilter};
+use crate::json::{self, ToJson, Value};
+use crate::limits;
+use crate::positions;
+use crate::query;
+use crate::sandbox;
+use crate::{count_words, rank_all};
+
+// JSON-RPC 2.0 오류 코드.
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+
+const METHODS: [&str; 6] = [
+    "initialize",
+    "analyze",
+    "top",
+    "filter",
+    "positions",
+    "shutdown",
+];
+
+pub fn serve(args: &Args) -> Result<(), String> {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let mut stdout = io::stdout().lock();
+    let max = args.max_request.unwrap_or(limits::DEFAULT_MAX_REQUEST);
+    while let Some(message) = read_message(&mut reader, max).map_err(|e| format!("jsonrpc: {e}"))? {
+        let parsed = match message {
+            Message::Body(body) => json::parse(&body),
+            Message::TooLarge(length) => {
+                let message = format!("message of {length} bytes exceeds the limit of {max}");
+                let reply = Some(failure(Value::Null, INVALID_REQUEST, message));
+                write_reply(&mut stdout, reply)?;
+                continue;
+            }
+        };
+        let (reply, exit) = match parsed {
+            Ok(request) => answer(&request, args),
+            Err(e) => (
+                Some(failure(
+                    Value::Null,
+                    PARSE_ERROR,
+                    format!("invalid JSON: {e}"),
+                )),
+                false,
+            ),
+        };
+        write_reply(&mut stdout, reply)?;
+        if exit {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn write_reply(stdout: &mut impl Write, reply: Option<Value>) -> Result<(), String> {
+    if let Some(reply) = reply {
+        let reply = json::to_line(&reply);
+        write!(stdout, "Content-Length: {}\r\n\r\n{reply}", reply.len())
+            .and_then(|()| stdout.flush())
+            .map_err(|e| format!("jsonrpc: {e}"))?;
+    }
+    Ok(())
+}
+
+enum Message {
+    Body(String),
+    // 읽어 버린 본문의 바이트 수.
+    TooLarge(usize),
+}
+
+// 머리글을 빈 줄까지 읽고 Content-Length만큼 본문을 읽는다. 입력이 끝나면 None.
+// 길이가 max를 넘으면 버퍼를 잡지 않고 그만큼 건너뛰어 다음 메시지의 머리글에 맞춘다.
+fn read_message(reader: &mut impl BufRead, max: usize) -> io::Result<Option<Message>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                // usize를 넘는 숫자도 한도를 넘는 길이로 본다.
+                let value = value.trim();
+                length = value.parse::<usize>().ok().or_else(|| {
+                    (!value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()))
+                        .then_some(usize::MAX)
+                });
+            }
+        }
+    }
+    let length = length.unwrap_or(0);
+    if length > max {
+        let skipped = io::copy(&mut reader.take(length as u64), &mut io::sink())?;
+        if skipped < length as u64 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        return Ok(Some(Message::TooLarge(length)));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(Message::Body(
+        String::from_utf8_lossy(&body).into_owned(),
+    )))
+}
+
+fn success(id: Value, result: Value) -> Value {
+    Value::Object(vec![
+        ("jsonrpc".to_string(), Value::String("2.0".to_string())),
+        ("id".to_string(), id),
+        ("result".to_string(), result),
+    ])
+}
+
+fn failure(id: Value, code: i32, message: String) -> Value {
+    Value::Object(vec![
+        ("jsonrpc".to_string(), Value::String("2.0".to_string())),
+        ("id".to_string(), id),
+        (
+            "error".to_string(),
+            Value::Object(vec![
+                ("code".to_string(), Value::Number(f64::from(code))),
+                ("message".to_string(), Value::String(message)),
+            ]),
+        ),
+    ])
+}
+
+fn number(n: usize) -> Value {
+    Value::Number(n as f64)
+}
+
+// (응답, 끝낼지). 알림에는 응답이 없다.
+fn answer(request: &Value, args: &Args) -> (Option<Value>, bool) {
+    let id = request.get("id").cloned();
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        let id = id.unwrap_or(Value::Null);
+        let message = "request needs a \"method\" string".to_string();
+        return (Some(failure(id, INVALID_REQUEST, message)), false);
+    };
+    if method == "exit" {
+        return (None, true);
+    }
+    let empty = Value::Object(Vec::new());
+    let params = request.get("params").unwrap_or(&empty);
+    let result = match method {
+        "initialize" => Ok(initialize()),
+        "analyze" => analyze(params, args),
+        "top" => top(params, args),
+        "filter" => lines(params, args),
+        "positions" => find(params, args),
+        "shutdown" => Ok(Value::Null),
+        other => {
+            let message = format!("unknown method {other:?}");
+            return (id.map(|id| failure(id, METHOD_NOT_FOUND, message)), false);
+        }
+    };
+    let reply = id.map(|id| match result {
+        Ok(value) => success(id, value),
+        Err(message) => failure(id, INVALID_PARAMS, message),
+    });
+    (reply, false)
+}
+
+fn initialize() -> Value {
+    Value::Object(vec![
+        (
+            "serverInfo".to_string(),
+            Value::Object(vec![
+                (
+                    "name".to_string(),
+                    Value::String("week1_rust_word_count".to_string()),
+                ),
+                (
+                    "version".to_string(),
+                    Value::String(env!("CARGO_PKG_VERSION").to_string()),
+                ),
+            ]),
+        ),
+        (
+            "methods".to_string(),
+            Value::Array(
+                METHODS
+                    .iter()
+                    .map(|m| Value::String(m.to_string()))
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+// params.text가 있으면 그것을, 없으면 params.path 파일을 읽는다.
+fn text(params: &Value, args: &Args) -> Result<String, String> {
+    if let Some(text) = params.get("text").and_then(Value::as_str) {
+        return Ok(text.to_string());
+    }
+    let path = params
+        .get("path")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "params need a \"text\" or \"path\" string".to_string())?;
+    let real = sandbox::resolve(args.root.as_ref(), path)?;
+    let bytes = std::fs::read(real).map_err(|e| format!("{path}: {e}"))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// 음수나 소수는 거절한다. 없으면 기본값.
+fn size(params: &Value, key: &str, default: usize) -> Result<usize, String> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(default),
+        Some(value) => value
+            .as_f64()
+            .filter(|n| *n >= 0.0 && n.fract() == 0.0)
+            .map(|n| n as usize)
+            .ok_or_else(|| format!("{key:?} must be a non-negative integer")),
+    }
+}
+
+// (토큰 수, 서로 다른 단어 수, 불용어를 뺀 상위 n개).
+fn ranked(params: &Value, args: &Args) -> Result<(usize, usize, Value), String> {
+    let n = size(params, "n", args.analysis.top())?;
+    let counts = count_words(&text(params, args)?, args.tokenizer);
+    let tokens = counts.iter().map(|(_, n)| n).sum();
+    let distinct = counts.len();
+    let mut items = rank_all(args.analysis.without_stopwords(counts), args);
+    if n > 0 {
+        items.truncate(n);
+    }
+    let words = items
+        .into_iter()
+        .map(|(word, count)| {
+            Value::Object(vec![
+                ("word".to_string(), Value::String(word)),
+                ("count".to_string(), number(count)),
+            ])
+        })
+        .collect();
+    Ok((tokens, distinct, Value::Array(words)))
+}
+
+fn analyze(params: &Value, args: &Args) -> Result<Value, String> {
+    let (tokens, distinct, words) = ranked(params, args)?;
+    Ok(Value::Object(vec![
+        ("tokens".to_string(), number(tokens)),
+        ("distinct".to_string(), number(distinct)),
+        ("top_words".to_string(), words),
+    ]))
+}
+
+fn top(params: &Value, args: &Args) -> Result<Value, String> {
+    ranked(params, args).map(|(_, _, words)| words)
+}
+
+// contains는 문자열 하나나 문자열 배열이다(--contains를 여러 번 준 것과 같다).
+fn lines(params: &Value, args: &Args) -> Result<Value, String> {
+    let expr = match params.get("query").and_then(Value::as_str) {
+        Some(text) => {
+            Some(query::parse(text).map_err(|e| format!("invalid query: {}", e.message))?)
+        }
+        None => None,
+    };
+    let contains: Vec<String> = match params.get("contains") {
+        Some(Value::String(word)) => vec![word.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| item.as_str().map(String::from))
+            .collect::<Option<_>>()
+            .ok_or_else(|| "\"contains\" must hold strings".to_string())?,
+        Some(_) => return Err("\"contains\" must be a string or an array".to_string()),
+        None => Vec::new(),
+    };
+    if expr.is_none() && contains.is_empty() {
+        return Err("filter needs a \"query\" or \"contains\"".to_string());
+    }
+    let limit = size(params, "limit", 0)?;
+    let filter = LineFilter {
+        contains,
+        query: expr,
+        tokenizer: args.tokenizer,
+        ..LineFilter::default()
+    };
+    let text = text(params, args)?;
+    let hits = filter::matching_lines(&text, &filter)
+        .take(if limit == 0 { usize::MAX } else { limit })
+        .map(|(line, text)| {
+            Value::Object(vec![
+                ("line".to_string(), number(line)),
+                ("text".to_string(), Value::String(text.to_string())),
+            ])
+        })
+        .collect();
+    Ok(Value::Array(hits))
+}
+
+fn find(params: &Value, args: &Args) -> Result<Value, String> {
+    let word = params
+        .get("word")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "positions needs a \"word\" string".to_string())?;
+    let target = positions::target(word, args.tokenizer)?;
+    Ok(positions::find(&text(params, args)?, &target, args.tokenizer).to_json())
+}
@@ -0,0 +1,61 @@
+// 기준 말뭉치 대비 키니스(keyness): Dunning의 로그 우도 G² (Rayson & Garside 방식).
+//
+//   a, b = 대상/기준 말뭉치에서 단어의 빈도,  c, d = 각 말뭉치의 총 토큰 수
+//   E1 = c·(a+b)/(c+d),  E2 = d·(a+b)/(c+d)
+//   G² = 2·(a·ln(a/E1) + b·ln(b/E2))   (빈도가 0인 항은 0)
+//
+// G²가 3.84 이상이면 p < 0.05, 6.63 이상이면 p < 0.01. 상대 빈도 a/c와 b/d를 비교해
+// 대상에서 더 많이 쓰인 단어(과대 표현)와 덜 쓰인 단어(과소 표현)로 나눈다.
+
+use crate::counter::Counter;
+
+pub struct Keyword {
+    pub word: String,
+    pub target: usize,
+    pub reference: usize,
+    pub g2: f64,
+}
+
+// (과대 표현, 과소 표현). 각각 G² 내림차순, 같으면 단어순.
+pub fn compare(target: &Counter, reference: &Counter) -> (Vec<Keyword>, Vec<Keyword>) {
+    let c: usize = target.iter().map(|(_, n)| n).sum();
+    let d: usize = reference.iter().map(|(_, n)| n).sum();
+    let mut union = Counter::new();
+    for (word, _) in target.iter().chain(reference.iter()) {
+        union.add(word, 0);
+    }
+
+    let mut over = Vec::new();
+    let mut under = Vec::new();
+    for (word, _) in union.iter() {
+        let a = target.get(word);
+        let b = reference.get(word);
+        let keyword = Keyword {
+            word: word.to_string(),
+            target: a,
+            reference: b,
+            g2: log_likelihood(a, b, c, d),
+        };
+        if a as f64 * d as f64 >= b as f64 * c as f64 {
+            over.push(keyword);
+        } else {
+            under.push(keyword);
+        }
+    }
+    let by_score =
+        |x: &Keyword, y: &Keyword| y.g2.total_cmp(&x.g2).then_with(|| x.word.cmp(&y.word));
+    over.sort_by(by_score);
+    under.sort_by(by_score);
+    (over, under)
+}
+
+fn log_likelihood(a: usize, b: usize, c: usize, d: usize) -> f64 {
+    let (a, b, c, d) = (a as f64, b as f64, c as f64, d as f64);
+    if c + d == 0.0 {
+        return 0.0;
+    }
+    let e1 = c * (a + b) / (c + d);
+    let e2 = d * (a + b) / (c + d);
+    let term = |o: f64, e: f64| if o > 0.0 { o * (o / e).ln() } else { 0.0 };
+    2.0 * (term(a, e1) + term(b, e2))
+}
@@ -0,0 +1,464 @@
+// 라이브러리로 쓸 때의 입구. 명령행 도구(main.rs)도 토큰화는 여기 것을 그대로 쓰므로
+// 라이브러리와 CLI가 같은 단어를 센다.
+// Python 패키지로 치면 __init__.py에서 공개 함수만 골라 내보내는 자리다.
+
+mod analysis;
+pub mod collate;
+mod contractions;
+pub mod counter;
+mod emoji;
+mod glob;
+pub mod json;
+mod normalization;
+mod pipeline;
+mod report;
+mod simd;
+mod stem;
+mod stream;
+mod summary;
+mod translit;
+
+use std::borrow::Cow;
+
+use counter::Counter;
+
+pub use analysis::{Analysis, AnalysisBuilder, SortOrder, TieBreak, WordCount};
+pub use glob::{glob_match, is_glob};
+pub use normalization::Normalization;
+pub use pipeline::{Pipeline, Step};
+pub use report::{FileReport, FilterResult};
+pub use stem::{Language, Stem};
+pub use stream::{Token, WordStream};
+pub use summary::{escape_html, to_html_table, Summary};
+
+// 토큰화 규칙. 옵션이 늘어도 함수마다 bool 인자를 하나씩 늘리지 않도록 한 값으로 묶어
+// 넘긴다(Copy라서 참조 없이 그대로 전달).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tokenizer {
+    // [A-Za-z]+ 대신 모든 문자(한글, 악센트 포함)를 단어 글자로 본다.
+    pub unicode: bool,
+    // 이모지 시퀀스도 토큰으로 센다.
+    pub emoji: bool,
+    pub hyphens: Hyphens,
+    pub contractions: Contractions,
+    pub case: Case,
+    pub numbers: Numbers,
+    // 토큰화 전에 맞출 유니코드 정규형(기본 NFC).
+    pub normalization: Normalization,
+    // 정규화한 뒤 악센트를 지운다("café" -> "cafe"). 영문자만 세는 기본 규칙에서도 합쳐진다.
+    pub strip_accents: bool,
+    // 키릴, 그리스, 한글, 가나를 ASCII 로마자로 바꾼다(악센트 지우기 포함).
+    pub transliterate: bool,
+    pub stem: Stem,
+    // 설정 파일의 normalize. 있으면 대소문자와 어간 처리는 여기 적힌 단계가 맡는다.
+    // 실행 내내 바뀌지 않는 값이라 Copy를 유지하려고 'static 참조로 들고 다닌다.
+    pub pipeline: Option<&'static Pipeline>,
+}
+
+// 대소문자를 어떻게 셀지(--case).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Case {
+    // "Rust"와 "rust"를 같은 단어로(기본값, 기존 동작).
+    #[default]
+    Fold,
+    // 원문 그대로. "Apple"(회사)과 "apple"(과일)을 나눠 센다.
+    Preserve,
+}
+
+// "404", "3.14" 같은 숫자를 어떻게 셀지(--numbers).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Numbers {
+    // 숫자는 단어 글자가 아니므로 세지 않는다(기본값, 기존 동작).
+    #[default]
+    Drop,
+    // 숫자마다 따로 센다.
+    Keep,
+    // 모든 숫자를 NUMBER_TOKEN 하나로 모아 센다. 수천 개의 서로 다른 숫자가 흩어지지 않는다.
+    Bucket,
+}
+
+// --numbers bucket이 숫자 대신 세는 토큰. 꺾쇠는 단어 글자가 아니라 실제 단어와 겹치지 않는다.
+pub const NUMBER_TOKEN: &str = "<NUM>";
+
+// "state-of-the-art"를 어떻게 셀지(--hyphens).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Hyphens {
+    // "state-of-the-art" 한 토큰.
+    Keep,
+    // "state", "of", "the", "art" (기본값, 기존 동작).
+    #[default]
+    Split,
+    // "stateoftheart".
+    Join,
+}
+
+// "don't"를 어떻게 셀지(--contractions).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Contractions {
+    // "don't" 한 토큰.
+    Keep,
+    // "do", "not". 소유격 's는 떼어 버린다.
+    Expand,
+    // "don", "t" (기본값, 기존 동작).
+    #[default]
+    Split,
+}
+
+impl Tokenizer {
+    // 토큰이 항상 원문의 연속된 조각인 규칙(하이픈/아포스트로피를 자르고 이모지를 세지 않는다).
+    // 이때만 tokens()로 원문을 빌려 쓸 수 있다. "do"+"not"이나 "stateoftheart", 어간 "happi"는
+    // 원문에 없다.
+    pub fn is_plain(self) -> bool {
+        !self.emoji
+            && self.hyphens == Hyphens::Split
+            && self.contractions == Contractions::Split
+            && self.numbers == Numbers::Drop
+            && self.stem == Stem::Off
+            && self.pipeline.is_none()
+    }
+}
+
+// 기본 규칙의 토큰을 원문에서 빌린 &str 조각으로 내놓는다. 소문자로 바꾸지 않으므로
+// 대소문자 정규화는 받는 쪽에서 한다(count_words는 대문자가 있는 단어만 새로 할당한다).
+pub enum Tokens<'a> {
+    Ascii(simd::AsciiWords<'a>),
+    Unicode(std::str::Split<'a, fn(char) -> bool>),
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        match self {
+            Tokens::Ascii(words) => words.next(),
+            Tokens::Unicode(parts) => parts.find(|w| !w.is_empty()),
+        }
+    }
+}
+
+pub fn tokens(text: &str, unicode: bool) -> Tokens<'_> {
+    if unicode {
+        let separator: fn(char) -> bool = |c| !c.is_alphabetic();
+        Tokens::Unicode(text.split(separator))
+    } else {
+        Tokens::Ascii(simd::AsciiWords::new(text))
+    }
+}
+
+// Python의 정규식 토큰화와 비슷한 역할이지만,
+// Rust에서는 &str 슬라이스를 순회한 뒤 String으로 명시적으로 소유권을 만든다.
+pub fn normalize_words(text: &str, tok: Tokenizer) -> Vec<String> {
+    let text = prepare(text, tok);
+    if !tok.is_plain() {
+        return scan_tokens(&text, tok);
+    }
+    tokens(&text, tok.unicode)
+        .map(|w| lowercase(w, tok))
+        .collect()
+}
+
+// 토큰화 전에 텍스트 전체에 한 번 적용하는 단계: 유니코드 정규화, 악센트 지우기, 로마자 표기.
+fn prepare(text: &str, tok: Tokenizer) -> Cow<'_, str> {
+    let text = normalization::normalize(text, tok.normalization);
+    if text.is_ascii() {
+        text
+    } else if tok.transliterate {
+        Cow::Owned(translit::to_ascii(&text))
+    } else if tok.strip_accents {
+        Cow::Owned(collate::strip_diacritics(&text))
+    } else {
+        text
+    }
+}
+
+// 이미 ASCII 소문자인 단어는 빌린 그대로 돌려준다. 대부분의 단어가 여기에 해당한다.
+// ASCII가 아닌 단어(--unicode)는 대소문자 규칙이 복잡하므로 따지지 않고 새로 만든다.
+pub fn folded(word: &str, tok: Tokenizer) -> Cow<'_, str> {
+    if tok.case == Case::Preserve || word.bytes().all(|b| b.is_ascii_lowercase()) {
+        Cow::Borrowed(word)
+    } else {
+        Cow::Owned(lowercase(word, tok))
+    }
+}
+
+// 단어 빈도. 기본 규칙이면 토큰마다 String을 만들지 않는다(Counter는 처음 보는 단어만 복사한다).
+pub fn count_words(text: &str, tok: Tokenizer) -> Counter {
+    let text = prepare(text, tok);
+    let mut counts = Counter::new();
+    if tok.is_plain() {
+        for word in tokens(&text, tok.unicode) {
+            counts.add(&folded(word, tok), 1);
+        }
+    } else {
+        for word in scan_tokens(&text, tok) {
+            counts.add(&word, 1);
+        }
+    }
+    counts
+}
+
+// 이보다 작은 텍스트는 스레드를 띄우는 비용이 더 크므로 한 번에 센다.
+const PARALLEL_MIN: usize = 4 << 20;
+const CHUNK_MIN: usize = 1 << 20;
+
+// count_words()를 줄 경계에서 자른 조각마다 스레드 하나로 돌리고 조각 순서대로 합친다.
+// 토큰은 줄을 넘지 않으므로 세는 결과도, Counter의 첫 등장 순서(동률 순위)도 한 번에 센 것과 같다.
+// --stem auto는 조각마다 언어가 달라지지 않도록 첫 조각으로 한 번만 정해 둔다.
+pub fn count_words_parallel(text: &str, tok: Tokenizer, threads: usize) -> Counter {
+    let pieces = threads.min(text.len() / CHUNK_MIN);
+    if text.len() < PARALLEL_MIN || pieces <= 1 {
+        return count_words(text, tok);
+    }
+    let chunks = line_chunks(text, pieces);
+    let tok = Tokenizer {
+        stem: match tok.stem.resolve(&prepare(chunks[0], tok)) {
+            Some(lang) => Stem::Lang(lang),
+            None => Stem::Off,
+        },
+        ..tok
+    };
+    let partial: Vec<Counter> = std::thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .iter()
+            .map(|chunk| scope.spawn(move || count_words(chunk, tok)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    });
+    let mut partial = partial.into_iter();
+    let mut counts = partial.next().unwrap_or_default();
+    for chunk in partial {
+        for (word, n) in chunk.iter() {
+            counts.add(word, n);
+        }
+    }
+    counts
+}
+
+// 거의 같은 크기의 n조각. 자르는 자리는 그 뒤의 첫 줄바꿈이라 조각 수가 n보다 적을 수 있다.
+fn line_chunks(text: &str, n: usize) -> Vec<&str> {
+    let size = text.len().div_ceil(n);
+    let mut chunks = Vec::with_capacity(n);
+    let mut rest = text;
+    while rest.len() > size {
+        let Some(newline) = rest.as_bytes()[size..].iter().position(|&b| b == b'\n') else {
+            break;
+        };
+        let (chunk, tail) = rest.split_at(size + newline + 1);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+// split()은 구분 문자를 전부 버리므로, 이모지나 단어 안의 하이픈/아포스트로피를 살리려면
+// 한 글자씩 훑으며 직접 자른다.
+fn scan_tokens(text: &str, tok: Tokenizer) -> Vec<String> {
+    // --stem auto는 넘겨받은 텍스트 전체를 보고 언어를 한 번만 정한다.
+    let lang = tok.stem.resolve(text);
+    let is_word = |c: char| {
+        if tok.unicode {
+            c.is_alphabetic()
+        } else {
+            c.is_ascii_alphabetic()
+        }
+    };
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        let next = text[i + c.len_utf8()..].chars().next();
+        if is_word(c) {
+            word.push(c);
+            i += c.len_utf8();
+            continue;
+        }
+        // 숫자는 앞 단어와 떼어 토큰 하나로 읽는다. 숫자 사이의 . 과 ,("3.14", "1,000")는 숫자의 일부다.
+        if c.is_ascii_digit() && tok.numbers != Numbers::Drop {
+            if !word.is_empty() {
+                push_word(&mut tokens, &word, tok, lang);
+                word.clear();
+            }
+            let len = number_len(&text[i..]);
+            tokens.push(match tok.numbers {
+                Numbers::Bucket => NUMBER_TOKEN.to_string(),
+                _ => text[i..i + len].to_string(),
+            });
+            i += len;
+            continue;
+        }
+        // 단어 글자 사이에 낀 하이픈만 단어의 일부로 본다("-rust", "rust-"는 그대로 나뉜다).
+        if c == '-' && !word.is_empty() && next.is_some_and(is_word) {
+            match tok.hyphens {
+                Hyphens::Keep => {
+                    word.push(c);
+                    i += 1;
+                    continue;
+                }
+                Hyphens::Join => {
+                    i += 1;
+                    continue;
+                }
+                Hyphens::Split => {}
+            }
+        }
+        // 둥근 따옴표(’)는 곧은 따옴표로 맞춰 두어야 "don’t"와 "don't"가 같은 토큰이 된다.
+        if (c == '\'' || c == '\u{2019}')
+            && tok.contractions != Contractions::Split
+            && !word.is_empty()
+            && next.is_some_and(is_word)
+        {
+            word.push('\'');
+            i += c.len_utf8();
+            continue;
+        }
+        if !word.is_empty() {
+            push_word(&mut tokens, &word, tok, lang);
+            word.clear();
+        }
+        match emoji::sequence_len(&text[i..]).filter(|_| tok.emoji) {
+            Some(len) => {
+                tokens.push(text[i..i + len].to_string());
+                i += len;
+            }
+            None => i += c.len_utf8(),
+        }
+    }
+    if !word.is_empty() {
+        push_word(&mut tokens, &word, tok, lang);
+    }
+    tokens
+}
+
+fn number_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() {
+        let separator = matches!(bytes[len], b'.' | b',')
+            && len > 0
+            && bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+        if !bytes[len].is_ascii_digit() && !separator {
+            break;
+        }
+        len += 1;
+    }
+    len
+}
+
+fn push_word(tokens: &mut Vec<String>, word: &str, tok: Tokenizer, lang: Option<Language>) {
+    if let Some(pipeline) = tok.pipeline {
+        let words = if tok.contractions == Contractions::Expand && word.contains('\'') {
+            contractions::expand(&word.to_lowercase())
+        } else {
+            vec![word.to_string()]
+        };
+        tokens.extend(words.iter().filter_map(|w| pipeline.apply(w)));
+        return;
+    }
+    let word = lowercase(word, tok);
+    let words = if tok.contractions == Contractions::Expand && word.contains('\'') {
+        contractions::expand(&word)
+    } else {
+        vec![word]
+    };
+    match lang {
+        Some(lang) => tokens.extend(words.iter().map(|w| stem::stem(w, lang))),
+        None => tokens.extend(words),
+    }
+}
+
+fn lowercase(word: &str, tok: Tokenizer) -> String {
+    if tok.case == Case::Preserve {
+        word.to_string()
+    } else if tok.unicode {
+        word.to_lowercase()
+    } else {
+        word.to_ascii_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // PARALLEL_MIN을 넘는 결정적인 텍스트. 줄 길이가 제각각이라 조각을 나누는 자리가 단어 한가운데에 떨어진다.
+    fn corpus(bytes: usize) -> String {
+        const WORDS: [&str; 12] = [
+            "the",
+            "Rust",
+            "rust",
+            "can't",
+            "well-known",
+            "O'Brien",
+            "data",
+            "x",
+            "counting",
+            "Line",
+            "2024",
+            "data-driven",
+        ];
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n as u64) as usize
+        };
+        let mut text = String::with_capacity(bytes + 64);
+        while text.len() < bytes {
+            for i in 0..1 + next(40) {
+                if i > 0 {
+                    text.push_str([" ", ", ", " -- ", "\t"][next(4)]);
+                }
+                text.push_str(WORDS[next(WORDS.len())]);
+            }
+            text.push_str(["\n", "\r\n", ".\n", "\n\n"][next(4)]);
+        }
+        text
+    }
+
+    fn ranked(counts: &Counter) -> Vec<(String, usize)> {
+        counts.iter().map(|(w, n)| (w.to_string(), n)).collect()
+    }
+
+    fn same_for_any_thread_count(text: &str, tok: Tokenizer) {
+        let single = ranked(&count_words(text, tok));
+        for threads in [2, 3, 4, 7] {
+            let parallel = ranked(&count_words_parallel(text, tok, threads));
+            // 첫 등장 순서(동률 순위)까지 같아야 한다.
+            assert_eq!(parallel, single, "{threads} threads");
+        }
+    }
+
+    #[test]
+    fn parallel_count_matches_single_thread() {
+        let text = corpus(PARALLEL_MIN + CHUNK_MIN);
+        // 나누는 자리 중 적어도 하나는 단어 안이어야 경계 처리를 시험하는 것이 된다.
+        let size = text.len().div_ceil(4);
+        assert!((1..4).any(|k| text.as_bytes()[k * size].is_ascii_alphabetic()));
+        same_for_any_thread_count(&text, Tokenizer::default());
+    }
+
+    #[test]
+    fn chunks_end_at_line_breaks() {
+        let text = corpus(PARALLEL_MIN + CHUNK_MIN);
+        let chunks = line_chunks(&text, 5);
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks.concat(), text);
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!(chunk.ends_with('\n'));
+        }
+    }
+
+    #[test]
+    fn single_long_line_is_not_split() {
+        let text = "word ".repeat((PARALLEL_MIN + CHUNK_MIN) / 5);
+        assert_eq!(line_chunks(&text, 4).len(), 1);
+        same_for_any_thread_count(&text, Tokenizer::default());
+    }
+}
@@ -0,0 +1,204 @@
+// daemon의 요청 제한. 작업 스레드는 몇 개뿐이므로 쉬지 않고 질의를 쏟는 클라이언트 하나가
+// 분석 시간을 다 차지하거나, 줄바꿈 없는 거대한 요청 한 줄로 메모리를 채우지 못하게 한다.
+//   --rate-limit 20/s        클라이언트마다 1초에 20개(토큰 버킷이라 잠깐 몰려도 20개까지는 받는다)
+//   --max-request 64K        요청 한 줄의 최대 바이트(기본 1M)
+// 넘으면 요청을 처리하지 않고 HTTP 상태 코드를 빌린 status 429/413으로 답한다.
+// 클라이언트는 소켓 상대의 uid로 가린다. 얻지 못하면 연결마다 따로 센다.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::UnixStream;
+use std::time::{Duration, Instant};
+
+use crate::subtitles;
+
+pub const DEFAULT_MAX_REQUEST: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rate {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl Rate {
+    // N/s, N/m, N/h 또는 N/10s처럼 --every와 같은 길이.
+    pub fn parse(spec: &str) -> Result<Rate, String> {
+        let invalid = || format!("--rate-limit expects N/s, N/m, N/h, or N/DURATION, got {spec}");
+        let (count, period) = spec.split_once('/').ok_or_else(invalid)?;
+        let requests = count
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(invalid)?;
+        let per = match period.trim() {
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(3600),
+            other => {
+                Duration::from_millis(subtitles::parse_duration(other).map_err(|_| invalid())?)
+            }
+        };
+        if per.is_zero() {
+            return Err(invalid());
+        }
+        Ok(Rate { requests, per })
+    }
+
+    pub fn describe(&self) -> String {
+        format!("{} per {:?}", self.requests, self.per)
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+// 클라이언트별 토큰 버킷. 가득 찬 버킷은 requests개이고 per 동안 그만큼 다시 찬다.
+pub struct Limiter {
+    rate: Rate,
+    buckets: HashMap<u64, Bucket>,
+}
+
+impl Limiter {
+    pub fn new(rate: Rate) -> Limiter {
+        Limiter {
+            rate,
+            buckets: HashMap::new(),
+        }
+    }
+
+    pub fn rate(&self) -> Rate {
+        self.rate
+    }
+
+    // 받을 수 있으면 Ok, 아니면 다음 토큰까지 기다릴 시간.
+    pub fn admit(&mut self, client: u64) -> Result<(), Duration> {
+        let capacity = f64::from(self.rate.requests);
+        let per_token = self.rate.per.as_secs_f64() / capacity;
+        let now = Instant::now();
+        let bucket = self.buckets.entry(client).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let refill = now.duration_since(bucket.updated).as_secs_f64() / per_token;
+        bucket.tokens = (bucket.tokens + refill).min(capacity);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) * per_token))
+        }
+    }
+}
+
+// 상대 uid. 연결 번호는 uid를 모를 때 uid와 겹치지 않는 키를 만드는 데 쓴다.
+pub fn client(stream: &UnixStream, connection: u64) -> u64 {
+    match peer_uid(stream) {
+        Some(uid) => u64::from(uid),
+        None => (1 << 32) + connection,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn peer_uid(stream: &UnixStream) -> Option<u32> {
+    #[repr(C)]
+    struct Ucred {
+        pid: i32,
+        uid: u32,
+        gid: u32,
+    }
+
+    extern "C" {
+        fn getsockopt(
+            fd: i32,
+            level: i32,
+            name: i32,
+            value: *mut std::os::raw::c_void,
+            len: *mut u32,
+        ) -> i32;
+    }
+
+    const SOL_SOCKET: i32 = 1;
+    const SO_PEERCRED: i32 = 17;
+
+    let mut cred = Ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut len = std::mem::size_of::<Ucred>() as u32;
+    // SAFETY: cred는 커널의 struct ucred와 같은 레이아웃이고 len은 그 크기다.
+    let rc = unsafe {
+        getsockopt(
+            stream.as_raw_fd(),
+            SOL_SOCKET,
+            SO_PEERCRED,
+            &mut cred as *mut Ucred as *mut std::os::raw::c_void,
+            &mut len,
+        )
+    };
+    (rc == 0).then_some(cred.uid)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peer_uid(stream: &UnixStream) -> Option<u32> {
+    extern "C" {
+        fn getpeereid(fd: i32, uid: *mut u32, gid: *mut u32) -> i32;
+    }
+
+    let (mut uid, mut gid) = (0, 0);
+    // SAFETY: 두 포인터 모두 살아 있는 지역 변수다.
+    let rc = unsafe { getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) };
+    (rc == 0).then_some(uid)
+}
+
+pub enum Request {
+    Line(String),
+    // 버린 바이트 수.
+    TooLarge(usize),
+}
+
+// 줄바꿈까지 한 줄. max를 넘으면 그 줄의 나머지는 쌓지 않고 읽어 버린다. 입력이 끝나면 None.
+pub fn read_request(reader: &mut impl BufRead, max: usize) -> io::Result<Option<Request>> {
+    let mut line = Vec::new();
+    let mut total = 0;
+    let mut newline = false;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        let (chunk, done) = match buf.iter().position(|&b| b == b'\n') {
+            Some(i) => (&buf[..=i], true),
+            None => (buf, false),
+        };
+        let n = chunk.len();
+        total += n;
+        if total <= max + 1 {
+            line.extend_from_slice(chunk);
+        }
+        reader.consume(n);
+        if done {
+            newline = true;
+            break;
+        }
+    }
+    if total == 0 {
+        return Ok(None);
+    }
+    let body = total - usize::from(newline);
+    if body > max {
+        return Ok(Some(Request::TooLarge(body)));
+    }
+    String::from_utf8(line)
+        .map(|text| {
+            Some(Request::Line(
+                text.trim_end_matches(['\n', '\r']).to_string(),
+            ))
+        })
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "request is not valid UTF-8"))
+}
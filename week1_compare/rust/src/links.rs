@@ -0,0 +1,105 @@
+// URL과 이메일 주소 추출(--urls, --emails).
+// 단어 분리기는 "https://example.com/a-b"를 "https", "example", "com", "a", "b"로 부숴 버리므로
+// 원문을 직접 훑는다. Python이라면 re.findall(r"https?://\S+")로 끝나겠지만,
+// 문장 끝의 마침표나 괄호를 떼어 내는 규칙은 어느 쪽이든 따로 필요하다.
+
+use crate::counter::Counter;
+
+const SCHEMES: [&str; 4] = ["https://", "http://", "ftp://", "www."];
+
+// URL 안에 올 수 없는 글자(공백과 흔한 구분 기호).
+fn ends_url(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '<' | '>' | '"' | '\'' | '`' | '{' | '}' | '|' | '\\')
+}
+
+// "(see https://x.org/a)."처럼 문장 부호가 붙은 경우 끝에서 떼어 낸다.
+// 닫는 괄호는 URL 안에 여는 괄호가 있을 때만 남긴다(위키백과 주소 "Rust_(language)").
+fn trim_url(url: &str) -> &str {
+    let mut url = url;
+    loop {
+        let Some(last) = url.chars().last() else {
+            return url;
+        };
+        let keep = match last {
+            '.' | ',' | ';' | ':' | '!' | '?' | '*' => false,
+            ')' => url.matches('(').count() >= url.matches(')').count(),
+            ']' => url.matches('[').count() >= url.matches(']').count(),
+            _ => true,
+        };
+        if keep {
+            return url;
+        }
+        url = &url[..url.len() - last.len_utf8()];
+    }
+}
+
+pub fn urls(text: &str) -> Counter {
+    let mut counts = Counter::new();
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let at_boundary = text[..i]
+            .chars()
+            .last()
+            .is_none_or(|c| !c.is_alphanumeric());
+        let scheme = SCHEMES.iter().find(|s| {
+            rest.get(..s.len())
+                .is_some_and(|p| p.eq_ignore_ascii_case(s))
+        });
+        match scheme {
+            Some(scheme) if at_boundary => {
+                let end = rest.find(ends_url).unwrap_or(rest.len());
+                let url = trim_url(&rest[..end]);
+                // 스킴만 있고 주소가 없으면("http://") 세지 않는다.
+                if url.len() > scheme.len() {
+                    counts.add(url, 1);
+                }
+                i += end.max(1);
+            }
+            _ => i += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    counts
+}
+
+fn is_local_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-')
+}
+
+fn is_domain_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '-')
+}
+
+// 도메인은 점으로 나뉜 두 칸 이상이고, 마지막 칸(TLD)은 두 글자 이상의 영문자여야 한다.
+fn valid_domain(domain: &str) -> bool {
+    let labels: Vec<&str> = domain.split('.').collect();
+    labels.len() >= 2
+        && labels
+            .iter()
+            .all(|l| !l.is_empty() && !l.starts_with('-') && !l.ends_with('-'))
+        && labels
+            .last()
+            .is_some_and(|tld| tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+// '@'마다 왼쪽으로 로컬 부분, 오른쪽으로 도메인을 넓힌다. 주소는 소문자로 맞춰 센다.
+pub fn emails(text: &str) -> Counter {
+    let mut counts = Counter::new();
+    for (at, _) in text.match_indices('@') {
+        let local_start = text[..at]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| is_local_char(c))
+            .last()
+            .map_or(at, |(i, _)| i);
+        let local = text[local_start..at].trim_start_matches('.');
+        let after = &text[at + 1..];
+        let domain_end = after.find(|c| !is_domain_char(c)).unwrap_or(after.len());
+        // "mail me at a@b.com." 끝의 마침표는 도메인이 아니다.
+        let domain = after[..domain_end].trim_end_matches(['.', '-']);
+        if !local.is_empty() && !local.ends_with('.') && valid_domain(domain) {
+            counts.add(&format!("{local}@{domain}").to_ascii_lowercase(), 1);
+        }
+    }
+    counts
+}
@@ -0,0 +1,82 @@
+// lint-ws 서브커맨드: 줄 끝 공백과 3줄 이상 이어진 빈 줄을 찾는다.
+// 공백과 탭만 있는 줄도 빈 줄로 본다. 줄 끝의 \r(CRLF)은 공백이 아니라 줄바꿈의 일부로 둔다.
+// --fix는 원본을 건드리지 않고 고친 사본(FILE.clean)을 쓴다. 빈 줄은 두 줄까지 남긴다.
+
+pub const MAX_BLANK: usize = 2;
+
+#[derive(Debug, Default)]
+pub struct Lint {
+    // 줄 끝 공백이 있는 줄 번호(1부터).
+    pub trailing: Vec<usize>,
+    // MAX_BLANK보다 길게 이어진 빈 줄의 (첫 줄, 마지막 줄).
+    pub blank_runs: Vec<(usize, usize)>,
+}
+
+impl Lint {
+    pub fn is_clean(&self) -> bool {
+        self.trailing.is_empty() && self.blank_runs.is_empty()
+    }
+}
+
+fn body(line: &str) -> &str {
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+fn is_blank(line: &str) -> bool {
+    body(line).trim_matches([' ', '\t']).is_empty()
+}
+
+pub fn scan(text: &str) -> Lint {
+    let mut lint = Lint::default();
+    let mut run_start = None;
+    let mut last = 0;
+    for (i, line) in text.lines().enumerate() {
+        let n = i + 1;
+        last = n;
+        let line = body(line);
+        if line.ends_with([' ', '\t']) {
+            lint.trailing.push(n);
+        }
+        if is_blank(line) {
+            run_start.get_or_insert(n);
+        } else if let Some(start) = run_start.take() {
+            if n - start > MAX_BLANK {
+                lint.blank_runs.push((start, n - 1));
+            }
+        }
+    }
+    if let Some(start) = run_start {
+        if last + 1 - start > MAX_BLANK {
+            lint.blank_runs.push((start, last));
+        }
+    }
+    lint
+}
+
+// 줄 끝 공백을 지우고 긴 빈 줄 묶음을 MAX_BLANK줄로 줄인다. 줄바꿈 모양(\n, \r\n)과
+// 마지막 줄바꿈 유무는 원문 그대로 둔다.
+pub fn clean(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blanks = 0;
+    for line in text.split_inclusive('\n') {
+        let (content, ending) = match line.strip_suffix("\r\n") {
+            Some(content) => (content, "\r\n"),
+            None => match line.strip_suffix('\n') {
+                Some(content) => (content, "\n"),
+                None => (line, ""),
+            },
+        };
+        let content = content.trim_end_matches([' ', '\t']);
+        if content.is_empty() {
+            blanks += 1;
+            if blanks > MAX_BLANK {
+                continue;
+            }
+        } else {
+            blanks = 0;
+        }
+        out.push_str(content);
+        out.push_str(ending);
+    }
+    out
+}
@@ -0,0 +1,481 @@
+// 로그 줄 파서: "타임스탬프 + 레벨 + 메시지" 모양을 몇 가지 흔한 형식에서 뽑아낸다.
+//
+//   2024-05-01T12:00:00Z INFO server started
+//   2024-05-01 12:00:00,123 [WARN] disk almost full
+//   [2024-05-01T12:00:00Z ERROR app::db] connection lost     (env_logger)
+//   May  1 12:00:00 host sshd[42]: Accepted publickey          (syslog, 레벨 없음)
+//   ERROR:root:division by zero                                (Python logging 기본 형식)
+//   time=2024-05-01T12:00:00Z level=warn msg="slow query"     (logfmt)
+//
+// Python이라면 정규식 몇 개로 처리하겠지만, 여기서는 앞에서부터 토큰을 하나씩 소비하는
+// 작은 수작업 파서로 만든다. 인식 못 한 줄도 버리지 않고 message만 채워서 돌려준다.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl Level {
+    pub const ALL: [Level; 6] = [
+        Level::Trace,
+        Level::Debug,
+        Level::Info,
+        Level::Warn,
+        Level::Error,
+        Level::Fatal,
+    ];
+
+    pub fn from_word(word: &str) -> Option<Level> {
+        let level = match word.to_ascii_lowercase().as_str() {
+            "trace" | "trc" => Level::Trace,
+            "debug" | "dbg" => Level::Debug,
+            "info" | "inf" | "notice" => Level::Info,
+            "warn" | "warning" | "wrn" => Level::Warn,
+            "error" | "err" | "eror" => Level::Error,
+            "fatal" | "critical" | "crit" | "panic" | "emerg" | "alert" => Level::Fatal,
+            _ => return None,
+        };
+        Some(level)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Trace => "trace",
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+            Level::Fatal => "fatal",
+        }
+    }
+}
+
+// --level 인자: "warn"(정확히), "warn+"(이상), "debug..warn"(구간, 양끝 포함).
+#[derive(Debug, Clone, Copy)]
+pub struct LevelRange {
+    pub min: Level,
+    pub max: Level,
+}
+
+impl LevelRange {
+    pub fn parse(spec: &str) -> Result<LevelRange, String> {
+        let level = |w: &str| {
+            Level::from_word(w).ok_or_else(|| {
+                format!("unknown log level: {w} (expected trace, debug, info, warn, error, fatal)")
+            })
+        };
+        if let Some(min) = spec.strip_suffix('+') {
+            return Ok(LevelRange {
+                min: level(min)?,
+                max: Level::Fatal,
+            });
+        }
+        if let Some((lo, hi)) = spec.split_once("..") {
+            let (min, max) = (level(lo)?, level(hi)?);
+            if min > max {
+                return Err(format!("--level range is reversed: {spec}"));
+            }
+            return Ok(LevelRange { min, max });
+        }
+        let exact = level(spec)?;
+        Ok(LevelRange {
+            min: exact,
+            max: exact,
+        })
+    }
+
+    pub fn contains(&self, level: Level) -> bool {
+        self.min <= level && level <= self.max
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogLine<'a> {
+    pub raw: &'a str,
+    pub timestamp: Option<&'a str>,
+    pub level: Option<Level>,
+    pub message: &'a str,
+    // 타임스탬프도 레벨도 없는 줄(스택 트레이스 등)은 앞선 기록의 연속으로 본다.
+    pub continuation: bool,
+}
+
+// 연속 줄은 바로 앞 기록의 레벨을 물려받는다.
+pub fn parse_log(text: &str) -> Vec<LogLine<'_>> {
+    let mut records = Vec::new();
+    let mut last_level = None;
+    for raw in text.lines() {
+        let mut record = parse_line(raw);
+        if record.timestamp.is_none() && record.level.is_none() {
+            record.continuation = true;
+            record.level = last_level;
+        } else {
+            last_level = record.level;
+        }
+        records.push(record);
+    }
+    records
+}
+
+pub fn parse_line(raw: &str) -> LogLine<'_> {
+    if let Some(record) = parse_logfmt(raw) {
+        return record;
+    }
+
+    let mut rest = raw.trim_start();
+    // env_logger처럼 "[타임스탬프 레벨 모듈]"을 대괄호 하나로 감싼 형식.
+    let bracketed = rest.starts_with('[') && !rest[1..].starts_with(|c: char| c.is_alphabetic());
+    if bracketed {
+        rest = &rest[1..];
+    }
+
+    let timestamp = take_timestamp(&mut rest);
+    // "[타임스탬프] LEVEL msg"처럼 타임스탬프만 감싼 경우는 여기서 괄호가 닫힌다.
+    let mut open = bracketed;
+    if open {
+        if let Some(after) = rest.strip_prefix(']') {
+            rest = after;
+            open = false;
+        }
+    }
+    let level = take_level(&mut rest);
+    if open {
+        if let Some(end) = rest.find(']') {
+            rest = &rest[end + 1..];
+        }
+    }
+    let message = rest
+        .trim_start_matches([' ', ':', '-', '|', '\t'])
+        .trim_end();
+
+    LogLine {
+        raw,
+        timestamp,
+        level,
+        message,
+        continuation: false,
+    }
+}
+
+// ISO 8601("2024-05-01T12:00:00.5Z", "2024-05-01 12:00:00,123")과
+// syslog("May  1 12:00:00")을 인식한다. 성공하면 rest를 그 뒤로 옮긴다.
+fn take_timestamp<'a>(rest: &mut &'a str) -> Option<&'a str> {
+    let s = *rest;
+    let len = iso_timestamp_len(s).or_else(|| syslog_timestamp_len(s))?;
+    *rest = &s[len..];
+    Some(&s[..len])
+}
+
+fn iso_timestamp_len(s: &str) -> Option<usize> {
+    let b = s.as_bytes();
+    let digits = |from: usize, n: usize| {
+        b.len() >= from + n && b[from..from + n].iter().all(u8::is_ascii_digit)
+    };
+    if !(digits(0, 4)
+        && b.get(4) == Some(&b'-')
+        && digits(5, 2)
+        && b.get(7) == Some(&b'-')
+        && digits(8, 2))
+    {
+        return None;
+    }
+    let mut end = 10;
+    if matches!(b.get(10), Some(b'T' | b' '))
+        && digits(11, 2)
+        && b.get(13) == Some(&b':')
+        && digits(14, 2)
+    {
+        end = 16;
+        if b.get(16) == Some(&b':') && digits(17, 2) {
+            end = 19;
+        }
+        // 소수 초: ".123" 또는 ",123"
+        if matches!(b.get(end), Some(b'.' | b',')) && digits(end + 1, 1) {
+            end += 1;
+            while b.get(end).is_some_and(u8::is_ascii_digit) {
+                end += 1;
+            }
+        }
+        // 시간대: "Z", "+09:00", "-0500"
+        match b.get(end) {
+            Some(b'Z') => end += 1,
+            Some(b'+' | b'-') if digits(end + 1, 2) => {
+                end += 3;
+                if b.get(end) == Some(&b':') {
+                    end += 1;
+                }
+                if digits(end, 2) {
+                    end += 2;
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(end)
+}
+
+fn syslog_timestamp_len(s: &str) -> Option<usize> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let month = s.get(..3)?;
+    if !MONTHS.contains(&month) || s.as_bytes().get(3) != Some(&b' ') {
+        return None;
+    }
+    // "May  1 12:00:00" / "May 11 12:00:00"
+    let after = s[4..].trim_start();
+    let offset = s.len() - after.len();
+    let day_len = after.bytes().take_while(u8::is_ascii_digit).count();
+    if day_len == 0 || day_len > 2 {
+        return None;
+    }
+    let time = after.get(day_len + 1..day_len + 9)?;
+    let tb = time.as_bytes();
+    let ok = after.as_bytes()[day_len] == b' '
+        && tb[2] == b':'
+        && tb[5] == b':'
+        && [0, 1, 3, 4, 6, 7].iter().all(|&i| tb[i].is_ascii_digit());
+    ok.then_some(offset + day_len + 9)
+}
+
+// 타임스탬프 바로 다음 토큰에서 "[WARN]", "WARN:", "<error>", "ERROR" 같은 레벨 표기를 찾는다.
+// 메시지 중간의 "error" 같은 단어를 레벨로 오인하지 않도록 첫 토큰만 본다.
+fn take_level(rest: &mut &str) -> Option<Level> {
+    let s = *rest;
+    let token_start = s.len() - s.trim_start().len();
+    let token = s[token_start..].split(char::is_whitespace).next()?;
+    // Python logging "ERROR:root:msg"는 첫 콜론 앞까지가 레벨이다.
+    let word = token
+        .trim_matches(|c: char| matches!(c, '[' | ']' | '<' | '>' | '(' | ')'))
+        .split(':')
+        .next()
+        .unwrap_or("");
+    let level = Level::from_word(word)?;
+
+    let mut consumed = token_start + token.len();
+    if let Some(colon) = token.find(':') {
+        // "ERROR:root:msg" → 메시지는 토큰 안 마지막 콜론 뒤부터.
+        let last = token.rfind(':').unwrap_or(colon);
+        if last + 1 < token.len() {
+            consumed = token_start + last + 1;
+        }
+    }
+    *rest = &s[consumed..];
+    Some(level)
+}
+
+// logfmt: "level=warn msg=\"slow query\"" — level= 키가 있을 때만 이 형식으로 본다.
+fn parse_logfmt(raw: &str) -> Option<LogLine<'_>> {
+    let mut timestamp = None;
+    let mut level = None;
+    let mut message = None;
+    for (key, value) in logfmt_pairs(raw) {
+        match key {
+            "level" | "lvl" | "severity" => level = Level::from_word(value),
+            "time" | "ts" | "timestamp" => timestamp = Some(value),
+            "msg" | "message" => message = Some(value),
+            _ => {}
+        }
+    }
+    level?;
+    Some(LogLine {
+        raw,
+        timestamp,
+        level,
+        message: message.unwrap_or(raw),
+        continuation: false,
+    })
+}
+
+fn logfmt_pairs(raw: &str) -> Vec<(&str, &str)> {
+    let mut pairs = Vec::new();
+    let mut rest = raw.trim();
+    while let Some(eq) = rest.find('=') {
+        let key_start = rest[..eq].rfind(' ').map_or(0, |i| i + 1);
+        let key = &rest[key_start..eq];
+        let after = &rest[eq + 1..];
+        let (value, remaining) = if let Some(quoted) = after.strip_prefix('"') {
+            match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            }
+        } else {
+            let end = after.find(' ').unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        if key.is_empty() || key.contains(' ') {
+            break;
+        }
+        pairs.push((key, value));
+        rest = remaining.trim_start();
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(raw: &str) -> (Option<&str>, Option<Level>, &str) {
+        let record = parse_line(raw);
+        (record.timestamp, record.level, record.message)
+    }
+
+    #[test]
+    fn iso_timestamp_and_level() {
+        assert_eq!(
+            fields("2024-05-01T12:00:00Z INFO server started"),
+            (
+                Some("2024-05-01T12:00:00Z"),
+                Some(Level::Info),
+                "server started"
+            )
+        );
+    }
+
+    #[test]
+    fn fractional_seconds_and_bracketed_level() {
+        assert_eq!(
+            fields("2024-05-01 12:00:00,123 [WARN] disk almost full"),
+            (
+                Some("2024-05-01 12:00:00,123"),
+                Some(Level::Warn),
+                "disk almost full"
+            )
+        );
+    }
+
+    #[test]
+    fn timezone_offsets() {
+        assert_eq!(
+            fields("2024-05-01T12:00:00.5+09:00 error boom").0,
+            Some("2024-05-01T12:00:00.5+09:00")
+        );
+        assert_eq!(
+            fields("2024-05-01T12:00:00-0500 error boom").0,
+            Some("2024-05-01T12:00:00-0500")
+        );
+    }
+
+    #[test]
+    fn env_logger() {
+        assert_eq!(
+            fields("[2024-05-01T12:00:00Z ERROR app::db] connection lost"),
+            (
+                Some("2024-05-01T12:00:00Z"),
+                Some(Level::Error),
+                "connection lost"
+            )
+        );
+    }
+
+    #[test]
+    fn bracketed_timestamp_only() {
+        assert_eq!(
+            fields("[2024-05-01 12:00:00] DEBUG cache warmed"),
+            (
+                Some("2024-05-01 12:00:00"),
+                Some(Level::Debug),
+                "cache warmed"
+            )
+        );
+    }
+
+    #[test]
+    fn syslog_without_level() {
+        assert_eq!(
+            fields("May  1 12:00:00 host sshd[42]: Accepted publickey"),
+            (
+                Some("May  1 12:00:00"),
+                None,
+                "host sshd[42]: Accepted publickey"
+            )
+        );
+        assert_eq!(
+            fields("May 11 12:00:00 host cron").0,
+            Some("May 11 12:00:00")
+        );
+    }
+
+    #[test]
+    fn python_logging() {
+        assert_eq!(
+            fields("ERROR:root:division by zero"),
+            (None, Some(Level::Error), "division by zero")
+        );
+    }
+
+    #[test]
+    fn logfmt() {
+        assert_eq!(
+            fields(r#"time=2024-05-01T12:00:00Z level=warn msg="slow query" ms=812"#),
+            (
+                Some("2024-05-01T12:00:00Z"),
+                Some(Level::Warn),
+                "slow query"
+            )
+        );
+        // level= 키가 없으면 logfmt로 보지 않는다.
+        assert_eq!(fields("user=bob action=login").1, None);
+    }
+
+    #[test]
+    fn level_only_in_first_token() {
+        assert_eq!(
+            fields("2024-05-01 12:00:00 request failed with error"),
+            (
+                Some("2024-05-01 12:00:00"),
+                None,
+                "request failed with error"
+            )
+        );
+    }
+
+    #[test]
+    fn malformed_lines_keep_message() {
+        // 잘린 타임스탬프, 없는 달, 두 자리를 넘는 날짜는 타임스탬프가 아니다.
+        for raw in [
+            "2024-05 broken stamp",
+            "Foo  1 12:00:00 not syslog",
+            "May 123 12:00:00 bad day",
+            "   ",
+        ] {
+            let record = parse_line(raw);
+            assert_eq!(record.timestamp, None, "{raw:?}");
+            assert_eq!(record.raw, raw);
+        }
+        assert_eq!(
+            fields("no structure at all"),
+            (None, None, "no structure at all")
+        );
+        // 닫는 따옴표가 없는 logfmt 값은 줄 끝까지다.
+        assert_eq!(fields(r#"level=error msg="unterminated"#).2, "unterminated");
+    }
+
+    #[test]
+    fn continuation_inherits_level() {
+        let text = "2024-05-01 12:00:00 ERROR panic\n    at main.rs:10\nplain line";
+        let records = parse_log(text);
+        assert!(!records[0].continuation);
+        assert!(records[1].continuation);
+        assert_eq!(records[1].level, Some(Level::Error));
+        assert_eq!(records[2].level, Some(Level::Error));
+    }
+
+    #[test]
+    fn level_words_and_ranges() {
+        assert_eq!(Level::from_word("WARNING"), Some(Level::Warn));
+        assert_eq!(Level::from_word("crit"), Some(Level::Fatal));
+        assert_eq!(Level::from_word("verbose"), None);
+        let range = LevelRange::parse("warn+").unwrap();
+        assert!(range.contains(Level::Fatal) && !range.contains(Level::Info));
+        let range = LevelRange::parse("debug..warn").unwrap();
+        assert!(range.contains(Level::Info) && !range.contains(Level::Error));
+        assert!(LevelRange::parse("error..info").is_err());
+        assert!(LevelRange::parse("loud").is_err());
+    }
+}
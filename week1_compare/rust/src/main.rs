@@ -1,123 +1,2489 @@
-use std::collections::HashMap;
-use std::env;
-use std::fs;
+mod alert;
+mod anagrams;
+mod analyzer;
+mod arrow;
+mod assertion;
+mod batch;
+mod bursts;
+mod cache;
+mod capitals;
+mod checkpoint;
+mod cli;
+mod columnar;
+mod config;
+mod corpus;
+mod counts;
+mod crosscheck;
+mod daemon;
+mod denylist;
+mod diagnostic;
+mod difficulty;
+mod docfreq;
+mod docx;
+mod drift;
+mod encoding;
+mod environment;
+mod epub;
+mod explain;
+mod extract;
+mod filter;
+mod follow;
+mod format;
+mod fuzzy;
+mod graph;
+mod highlight;
+mod history;
+mod inflate;
+mod input;
+mod interrupt;
+mod journal;
+mod jsonrpc;
+mod keyness;
+mod limits;
+mod links;
+mod lintws;
+mod logline;
+mod markov;
+mod meta;
+mod metrics;
+mod neardupes;
+mod ngrams;
+mod numfmt;
+mod output;
+mod pager;
+mod paragraphs;
+mod parquet;
+mod pdf;
+mod pick;
+mod positions;
+mod preset;
+mod provenance;
+mod query;
+mod rake;
+mod regex;
+mod render;
+mod replace;
+mod resources;
+mod rng;
+mod sandbox;
+mod schedule;
+mod script;
+mod search;
+mod selfupdate;
+mod sentences;
+mod setops;
+mod sha256;
+mod sniff;
+mod social;
+mod speakers;
+mod spell;
+mod split;
+mod sql;
+mod stats;
+mod stopwords;
+mod subtitles;
+mod summarize;
+mod term;
+mod timefmt;
+mod timing;
+mod todos;
+mod trailer;
+mod trend;
+mod tty;
+mod walk;
+mod weblog;
+mod wordlist;
+mod xml;
+mod zip;
+mod zipf;
 
-// Python의 정규식 토큰화와 비슷한 역할이지만,
-// Rust에서는 &str 슬라이스를 순회한 뒤 String으로 명시적으로 소유권을 만든다.
-fn normalize_words(text: &str) -> Vec<String> {
-    text.split(|c: char| !c.is_ascii_alphabetic())
-        .filter(|w| !w.is_empty())
-        .map(|w| w.to_ascii_lowercase())
-        .collect()
-}
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use cli::{parse_args, Args, Command, StopwordsAction};
+use counter::Counter;
+use encoding::Encoding;
+use filter::{filter_lines, FileList};
+use format::{csv_field, Format};
+use input::{Decoder, Source};
+use json::{ToJson, Value};
+use logline::Level;
+use render::Renderer as _;
+use rng::Rng;
+use week1_rust_word_count::{collate, counter, json};
+use week1_rust_word_count::{escape_html, FileReport, FilterResult, WordCount};
+use week1_rust_word_count::{is_glob, normalize_words, tokens, Tokenizer};
+use week1_rust_word_count::{Analysis, Case, Contractions, Hyphens, Numbers, SortOrder, TieBreak};
+use week1_rust_word_count::{Language, Normalization, Pipeline, Stem, Step};
+
+#[global_allocator]
+static ALLOCATOR: resources::CountingAllocator = resources::CountingAllocator;
+
+// 큰 파일 하나를 셀 때 띄울 스레드 수(--threads, 기본은 CPU 수).
+static THREADS: OnceLock<usize> = OnceLock::new();
 
 // Python Counter와 유사한 빈도 집계.
-// 차이점: Rust는 실패 가능성을 Result로 타입에 드러내며,
-// 파일 읽기 실패를 컴파일러가 인지 가능한 흐름으로 강제한다.
-fn top_words(path: &str, top_n: usize) -> Result<Vec<(String, usize)>, String> {
-    let content = fs::read_to_string(path).map_err(|e| format!("failed to read file: {e}"))?;
-    let mut counts: HashMap<String, usize> = HashMap::new();
+// --char-ngrams가 있으면 단어 대신 문자 n-gram을 센다. 순위 매기기는 같다.
+fn top_words(text: &str, args: &Args) -> Vec<(String, usize)> {
+    match args.char_ngrams {
+        Some(n) => rank(ngrams::char_ngrams(text, n, args.tokenizer.unicode), args),
+        None => top_counts(count_words(text, args.tokenizer), args),
+    }
+}
+
+// 이미 센 단어 빈도에서 --stopwords-file 단어를 빼고 순위를 매긴다.
+fn top_counts(counts: Counter, args: &Args) -> Vec<(String, usize)> {
+    rank(
+        args.analysis
+            .without_stopwords(with_vocabulary(counts, args)),
+        args,
+    )
+}
+
+// --only-words: 목록에 있는데 본문에 나오지 않은 단어를 0으로 채운다.
+fn with_vocabulary(mut counts: Counter, args: &Args) -> Counter {
+    for word in &args.vocabulary {
+        counts.add(word, 0);
+    }
+    counts
+}
+
+fn top_heading(args: &Args) -> String {
+    let heading = match args.char_ngrams {
+        Some(n) => format!("Top character {n}-grams"),
+        None => "Top words".to_string(),
+    };
+    if args.count_matching {
+        heading + " in matching lines"
+    } else {
+        heading
+    }
+}
+
+// --count-matching: 필터에 걸린 줄만 이어 붙인 본문. 한 번 훑으면서 고르므로 grep을 한 번 더
+// 돌려 파이프로 넘기는 것과 결과가 같다. --max-matches/--sample-matches도 전체 합계로 적용한다.
+fn matching_text(documents: &[(String, String)], args: &Args) -> String {
+    let start = Instant::now();
+    let hits = documents
+        .iter()
+        .flat_map(|(_, text)| filter::matching_lines(text, &args.filter).map(|(_, line)| line));
+    let kept = args.filter.limit(hits);
+    trailer::matched(kept.len());
+    let mut text = kept.join("\n");
+    text.push('\n');
+    timing::record("filter", start, text.len(), None);
+    text
+}
+
+fn count_words(text: &str, tok: Tokenizer) -> Counter {
+    let start = Instant::now();
+    let counts = script::map_counts(week1_rust_word_count::count_words_parallel(
+        text,
+        tok,
+        THREADS.get().copied().unwrap_or(1),
+    ));
+    let total = counts.iter().map(|(_, n)| n).sum();
+    timing::record("tokenize", start, text.len(), Some(total));
+    trailer::counted(total, counts.len());
+    journal::counted(total, counts.len());
+    counts
+}
+// 순위 규칙(--top, --sort, --tie-break, --collate)은 Analysis가 정한다.
+fn rank(counts: Counter, args: &Args) -> Vec<(String, usize)> {
+    let start = Instant::now();
+    let distinct = counts.len();
+    let items = args.analysis.rank(counts);
+    timing::record("rank", start, 0, Some(distinct));
+    items
+}
+
+fn rank_all(counts: Counter, args: &Args) -> Vec<(String, usize)> {
+    args.analysis.rank_all(counts)
+}
+
+// 기본 모드: [Top words] + (필터 옵션이 있으면) [Filtered lines].
+fn run_count(args: &Args, out: &mut String) -> Result<(), String> {
+    if args.log {
+        return run_log(args, out);
+    }
+    if args.recursive && args.filter.is_active() && !args.count_matching {
+        return run_search(args, out);
+    }
+    // 덧붙는 파일은 지난번에 센 곳부터만 토큰화한다(cache.rs). 원문 전체를 다시 읽지 않는다.
+    if let (Some(cache), Source::Files(paths), true) =
+        (&args.cache, &args.source, cache::incremental(args))
+    {
+        let counts = cache::count(paths, args.tokenizer, cache)?;
+        trailer::files(paths.len());
+        return write_counts(counts, args, out);
+    }
+    if let Some(path) = &args.checkpoint {
+        let files = match &args.source {
+            _ if args.recursive => tree_files(args)?
+                .files
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect(),
+            Source::Files(paths) => paths.clone(),
+            _ => Vec::new(),
+        };
+        let counts = checkpoint::count(&files, args, path, args.resume)?;
+        return write_counts(counts, args, out);
+    }
+    if streams(args) {
+        return stream_filtered(args, out);
+    }
+    // 줄을 거를 때는 깨진 바이트가 있어도 멈추지 않는다(상위 단어는 U+FFFD에서 끊길 뿐이다).
+    let documents = if args.recursive {
+        tree_documents(args)?
+    } else if args.filter.is_active() && matches!(args.decoder, Decoder::Plain) {
+        input::read_documents_lossy(&args.source)?
+    } else {
+        input::read_documents(&args.source, &args.decoder)?
+    };
+    if let Some(list) = args.file_list {
+        write_file_list(&documents, list, args, out);
+        return Ok(());
+    }
+    // --print0은 xargs -0에 바로 넘기는 용도라 머리글과 상위 단어 없이 걸린 레코드만 쓴다.
+    if args.print0 {
+        write_filtered(&documents, &input::join_documents(&documents), args, out);
+        return Ok(());
+    }
+    let content = if args.count_matching {
+        matching_text(&documents, args)
+    } else {
+        input::join_documents(&documents)
+    };
+    let (content, speakers) = match &args.speaker {
+        Some(regex) => {
+            let grouped = speakers::group(&content, regex);
+            (grouped.text, grouped.speakers)
+        }
+        None => (content, Vec::new()),
+    };
+    // --no-top이면 [Top words]와 딸린 표를 쓰지 않으므로 빈도를 세지도 않는다.
+    let (tables, marker) = if args.sections.top || args.export_stopwords.is_some() {
+        top_tables(&documents, &content, speakers, args)?
+    } else {
+        (Vec::new(), None)
+    };
+    let filtered = args.filter.is_active() && !args.count_matching;
+    if args.format.is_columnar() {
+        return columnar::write(args.format, &tables, args.output.as_deref());
+    }
+    if args.format == Format::Html {
+        // 걸린 줄은 text처럼 뒤에 덧붙이지 않고 보고서 안에 넣는다.
+        let mut lines = String::new();
+        if filtered {
+            write_filtered(&documents, &content, args, &mut lines);
+        }
+        let filtered = lines.lines().map(String::from).collect();
+        render_tables(&tables, filtered, args, out);
+        return Ok(());
+    }
+    if args.sections.top {
+        render_tables(&tables, Vec::new(), args, out);
+        write_marker(marker, args, out);
+    }
+    if args.format == Format::Markdown && filtered {
+        let mut lines = String::new();
+        write_filtered(&documents, &content, args, &mut lines);
+        render::fenced("Filtered lines", &lines, out);
+        return Ok(());
+    }
+
+    if args.sections.stats {
+        if !args.plain {
+            section_heading(out, "Stats");
+        }
+        write_totals(&stats::summarize(&content, args.tokenizer), out);
+    }
+
+    if let Some(path) = &args.flag_list {
+        // --line-numbers와 같은 규칙: 파일이 여러 개면 "파일:줄번호".
+        let list = denylist::load(path, args.tokenizer)?;
+        let with_name = documents.len() > 1;
+        if !args.plain {
+            section_heading(out, "Flagged words");
+        }
+        for (name, text) in &documents {
+            for hit in denylist::scan(text, &list, args.tokenizer) {
+                if with_name {
+                    let _ = writeln!(out, "{name}:{}: {}", hit.line, hit.word);
+                } else {
+                    let _ = writeln!(out, "{}: {}", hit.line, hit.word);
+                }
+            }
+        }
+    }
+
+    if filtered && args.sections.filter {
+        if !args.plain {
+            section_heading(out, "Filtered lines");
+        }
+        write_filtered(&documents, &content, args, out);
+    }
+    Ok(())
+}
+
+// [Top words] 표와 --per-chapter, --proper-nouns 등 딸린 표, --page 표시.
+fn top_tables(
+    documents: &[(String, String)],
+    content: &str,
+    speakers: Vec<(String, String)>,
+    args: &Args,
+) -> Result<(Vec<render::Table>, Option<String>), String> {
+    let heading = top_heading(args);
+    let column = if args.char_ngrams.is_some() {
+        "ngram"
+    } else {
+        "word"
+    };
+    let limits = docfreq::Limits::new(documents, args)?;
+    let top_of = |text: &str| match &limits {
+        Some(limits) => rank(
+            limits.apply(
+                args.analysis
+                    .without_stopwords(with_vocabulary(count_words(text, args.tokenizer), args)),
+            ),
+            args,
+        ),
+        None => top_words(text, args),
+    };
+    let mut top = top_of(content);
+    if let Some(path) = &args.export_stopwords {
+        let list = wordlist::format(top.iter().map(|(word, _)| word.as_str()));
+        output::write(path, &list, false)?;
+    }
+    let marker = paginate(&mut top, args)?;
+    let mut tables = vec![table(&heading, column, top)];
+    // 줄 번호는 입력 하나 안에서만 뜻이 있다. 섹션 표와 부가 표에는 붙이지 않는다.
+    if args.with_positions {
+        if documents.len() > 1 {
+            return Err("--with-positions needs a single input file".to_string());
+        }
+        tables[0].lines = positions::spans(content, &tables[0].rows, args.tokenizer);
+    }
+    for (label, text) in sections(documents, args)?.into_iter().chain(speakers) {
+        let mut section = table(&format!("{heading}: {label}"), column, top_of(&text));
+        section.section = Some(label);
+        tables.push(section);
+    }
+    if args.proper_nouns {
+        let found = capitals::proper_nouns(content, args.tokenizer);
+        tables.push(table("Proper nouns", "word", rank(found, args)));
+    }
+    if args.acronyms {
+        let found = capitals::acronyms(content, args.tokenizer);
+        tables.push(table("Acronyms", "word", rank(found, args)));
+    }
+    if args.urls {
+        tables.push(table("URLs", "url", rank(links::urls(content), args)));
+    }
+    if args.emails {
+        let found = links::emails(content);
+        tables.push(table("Email addresses", "email", rank(found, args)));
+    }
+    for command in &args.analyzers {
+        for (name, counts) in analyzer::run(command, documents, args.tokenizer)? {
+            tables.push(table(&name, "word", rank(counts, args)));
+        }
+    }
+    Ok((tables, marker))
+}
+
+// 구역 사이에는 빈 줄을 둔다. --no-top으로 첫 구역이 되면 머리글만 쓴다.
+fn section_heading(out: &mut String, title: &str) {
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    let _ = writeln!(out, "[{title}]");
+}
+
+fn tree_files(args: &Args) -> Result<search::Selection, String> {
+    let Source::Files(roots) = &args.source else {
+        return Err("--recursive needs files or directories".to_string());
+    };
+    let selection = search::files(roots, args.max_filesize, &args.walk)?;
+    for (path, size) in &selection.oversized {
+        eprintln!(
+            "skipped {}: {size} bytes is over --max-filesize",
+            path.display()
+        );
+    }
+    Ok(selection)
+}
+
+// 필터 없는 -r: 트리의 텍스트 파일 전체가 한 입력이다(바이너리는 조용히 뺀다).
+fn tree_documents(args: &Args) -> Result<Vec<(String, String)>, String> {
+    let selection = tree_files(args)?;
+    let start = Instant::now();
+    let documents: Vec<(String, String)> =
+        batch::settle(search::read(&selection.files, args.threads))?
+            .into_iter()
+            .filter_map(|(name, text)| Some((name, text?)))
+            .map(|(name, text)| Ok((name, script::keep_lines(text)?)))
+            .collect::<Result<_, String>>()?;
+    let bytes = documents.iter().map(|(_, text)| text.len()).sum();
+    timing::record("read", start, bytes, None);
+    trailer::files(documents.len());
+    Ok(documents)
+}
+
+// -r: 트리 전체에서 걸린 줄만 "경로:줄번호: 줄"로 쓴다. --max-matches는 경로순 전체 합계로 적용한다.
+fn run_search(args: &Args, out: &mut String) -> Result<(), String> {
+    let selection = tree_files(args)?;
+    let start = Instant::now();
+    let found = batch::settle(search::search(&selection.files, &args.filter, args.threads))?;
+    trailer::files(found.len());
+    let end = if args.print0 { '\0' } else { '\n' };
+    if let Some(list) = args.file_list {
+        for (path, hits) in &found {
+            if list.lists(!hits.is_empty()) {
+                let _ = write!(out, "{path}{end}");
+            }
+        }
+        timing::record("filter", start, 0, None);
+        return Ok(());
+    }
+    let hits = found
+        .iter()
+        .flat_map(|(path, hits)| hits.iter().map(move |(n, line)| (path, n, line)));
+    for (path, n, line) in args.filter.limit(hits) {
+        trailer::matched(1);
+        let prefix = format!("{path}:{n}: ");
+        for part in shown_parts(line, args, diagnostic::width(&prefix)) {
+            let _ = write!(out, "{prefix}{part}{end}");
+        }
+    }
+    timing::record("filter", start, 0, None);
+    Ok(())
+}
+
+// --max-matches N으로 걸린 줄 앞쪽 N개만 쓰고 본문 전체가 필요한 것(상위 단어, [Stats], -l,
+// --flag-list, --speaker, 디코더 등)이 없으면 입력을 통째로 읽지 않고 읽으면서 거른다.
+// N개를 찾으면 나머지는 읽지 않으므로 `yes error | wordtool - --contains error --max-matches 1
+// --no-top`도 끝난다. --journal은 입력 전체의 해시를 남겨야 하므로 통째로 읽는다.
+fn streams(args: &Args) -> bool {
+    args.filter.max_matches.is_some()
+        && args.filter.sample_matches.is_none()
+        && args.filter.is_active()
+        && !args.count_matching
+        && (args.print0 || !args.sections.top && !args.sections.stats)
+        && matches!(args.decoder, Decoder::Plain)
+        && args.format == Format::Text
+        && args.template.is_none()
+        && args.file_list.is_none()
+        && args.flag_list.is_none()
+        && args.speaker.is_none()
+        && args.export_stopwords.is_none()
+        && args.journal.is_none()
+}
+
+// write_filtered와 같은 모양으로 쓴다. --max-matches는 여기서도 전체 합계다.
+fn stream_filtered(args: &Args, out: &mut String) -> Result<(), String> {
+    let end = if args.print0 { '\0' } else { '\n' };
+    let start = Instant::now();
+    let with_name =
+        args.line_numbers && matches!(&args.source, Source::Files(paths) if paths.len() > 1);
+    if !args.print0 && !args.plain {
+        section_heading(out, "Filtered lines");
+    }
+    let mut left = args.filter.max_matches.unwrap_or(usize::MAX);
+    let mut bytes = 0;
+    input::stream(&args.source, |name, reader| {
+        if left == 0 {
+            return Ok(false);
+        }
+        let (read, more) = filter::scan(name, reader, &args.filter, script::keeps, |n, line| {
+            trailer::matched(1);
+            let prefix = match (args.line_numbers, with_name) {
+                (false, _) => String::new(),
+                (true, false) => format!("{n}:"),
+                (true, true) => format!("{name}:{n}:"),
+            };
+            for part in shown_parts(line, args, diagnostic::width(&prefix)) {
+                let _ = write!(out, "{prefix}{part}{end}");
+            }
+            left -= 1;
+            left > 0
+        })?;
+        bytes += read;
+        Ok(more)
+    })?;
+    timing::record("filter", start, bytes, None);
+    Ok(())
+}
+
+// 걸린 줄(레코드)을 하나씩 쓴다. --print0이면 줄바꿈 대신 NUL로 끝낸다.
+fn write_filtered(documents: &[(String, String)], content: &str, args: &Args, out: &mut String) {
+    let end = if args.print0 { '\0' } else { '\n' };
+    let start = Instant::now();
+    let mut shown = 0;
+    if args.line_numbers {
+        // grep -n처럼 "줄번호:줄", 파일이 여러 개면 "파일:줄번호:줄".
+        // --max-matches는 파일별이 아니라 전체 합계로 센다.
+        let with_name = documents.len() > 1;
+        let hits = documents.iter().flat_map(|(name, text)| {
+            filter::matching_lines(text, &args.filter).map(move |(n, line)| (name, n, line))
+        });
+        for (name, n, line) in args.filter.limit(hits) {
+            trailer::matched(1);
+            shown += 1;
+            let prefix = if with_name {
+                format!("{name}:{n}:")
+            } else {
+                format!("{n}:")
+            };
+            for part in shown_parts(line, args, diagnostic::width(&prefix)) {
+                let _ = write!(out, "{prefix}{part}{end}");
+            }
+        }
+    } else {
+        for line in filter_lines(content, &args.filter) {
+            trailer::matched(1);
+            shown += 1;
+            for part in shown_parts(&line, args, 0) {
+                let _ = write!(out, "{part}{end}");
+            }
+        }
+    }
+    // --max-matches로 중간에 멈췄으면 본문을 다 훑지 않았으므로 전체 바이트로 나눈 처리량은
+    // 터무니없이 커진다. 그때는 처리량을 쓰지 않는다(0이면 '-').
+    let stopped = args.filter.sample_matches.is_none() && args.filter.max_matches == Some(shown);
+    let bytes = if stopped { 0 } else { content.len() };
+    timing::record("filter", start, bytes, None);
+}
+
+// -l / --files-without-match: 파일마다 첫 번째로 걸린 줄에서 멈추고 이름만 쓴다.
+fn write_file_list(documents: &[(String, String)], list: FileList, args: &Args, out: &mut String) {
+    let end = if args.print0 { '\0' } else { '\n' };
+    let start = Instant::now();
+    for (name, text) in documents {
+        let hit = filter::matching_lines(text, &args.filter).next().is_some();
+        if list.lists(hit) {
+            let _ = write!(out, "{name}{end}");
+        }
+    }
+    timing::record("filter", start, 0, None);
+}
+
+// 걸린 줄 하나에서 쓸 조각. -o면 일치마다 하나(색은 칠하지 않는다), 아니면 줄 전체.
+// 터미널에서는 긴 줄을 앞에 붙는 prefix 칸을 뺀 폭으로 자른 뒤에 색을 칠한다.
+fn shown_parts(line: &str, args: &Args, prefix: usize) -> Vec<String> {
+    if args.only_matching {
+        return filter::matched_parts(line, &args.filter, args.replace.as_deref());
+    }
+    match args.truncate {
+        Some(cols) => {
+            let focus = filter::first_match(line, &args.filter);
+            let cells = cols.saturating_sub(prefix).max(10);
+            vec![display_line(&term::ellipsize(line, cells, focus), args)]
+        }
+        None => vec![display_line(line, args)],
+    }
+}
+
+// 색이 켜져 있으면 --contains 키워드를 키워드별 색으로 칠한다.
+fn display_line(line: &str, args: &Args) -> String {
+    if args.color && !args.filter.contains.is_empty() {
+        highlight::highlight(line, &args.filter.contains)
+    } else {
+        line.to_string()
+    }
+}
+
+// 상위 단어와 merge의 표 출력. --provenance면 json은 {"inputs", "tables"}가 되고 html은 입력 표가 붙는다.
+fn render_tables(tables: &[render::Table], filtered: Vec<String>, args: &Args, out: &mut String) {
+    let inputs = if args.provenance {
+        provenance::inputs(&args.source)
+    } else {
+        Vec::new()
+    };
+    match args.format {
+        Format::Html => render::Html { filtered, inputs }.render(tables, out),
+        Format::Json if args.provenance => {
+            let report = Value::Object(vec![
+                ("inputs".to_string(), inputs.to_json()),
+                ("tables".to_string(), render::json_tables(tables)),
+            ]);
+            out.push_str(&json::to_pretty(&report));
+        }
+        _ => render::renderer(args.format, args.template.as_ref(), args.table, args.plain)
+            .render(tables, out),
+    }
+}
+
+// 이미 합친 빈도(--cache 증분 집계, --checkpoint)의 상위 단어 표 하나.
+fn write_counts(counts: Counter, args: &Args, out: &mut String) -> Result<(), String> {
+    trailer::counted(counts.iter().map(|(_, n)| n).sum(), counts.len());
+    let mut top = top_counts(counts, args);
+    let marker = paginate(&mut top, args)?;
+    if args.format.is_columnar() {
+        let tables = [table("Top words", "word", top)];
+        return columnar::write(args.format, &tables, args.output.as_deref());
+    }
+    render::renderer(args.format, args.template.as_ref(), args.table, args.plain)
+        .render(&[table("Top words", "word", top)], out);
+    write_marker(marker, args, out);
+    Ok(())
+}
+
+// --page K: 순위를 --page-size행씩 나눈 K번째 쪽만 남기고, 표 아래에 붙일 --page-marker 줄을
+// 돌려준다. 마지막 쪽을 넘으면 빈 표 대신 오류를 낸다(쪽 번호를 잘못 준 것이다).
+fn paginate(rows: &mut Vec<(String, usize)>, args: &Args) -> Result<Option<String>, String> {
+    let (Some(page), Some(size)) = (args.page, args.page_size) else {
+        return Ok(None);
+    };
+    let total = rows.len();
+    let pages = total.div_ceil(size).max(1);
+    if page > pages {
+        return Err(format!("--page {page} is past the last page ({pages})"));
+    }
+    let skipped = (page - 1) * size;
+    rows.drain(..skipped);
+    rows.truncate(size);
+    let first = if rows.is_empty() { 0 } else { skipped + 1 };
+    let marker = args
+        .page_marker
+        .replace("{pages}", &pages.to_string())
+        .replace("{page}", &page.to_string())
+        .replace("{first}", &first.to_string())
+        .replace("{last}", &(skipped + rows.len()).to_string())
+        .replace("{total}", &total.to_string());
+    Ok(Some(marker))
+}
+
+// 쪽 표시는 사람이 읽는 text 출력에만 붙인다(--plain이면 순위 줄만).
+fn write_marker(marker: Option<String>, args: &Args, out: &mut String) {
+    if let Some(marker) = marker {
+        if args.format == Format::Text && !args.plain {
+            let _ = writeln!(out, "{marker}");
+        }
+    }
+}
+
+fn table(title: &str, column: &'static str, rows: Vec<(String, usize)>) -> render::Table {
+    render::Table {
+        title: title.to_string(),
+        section: None,
+        column,
+        rows,
+        lines: Vec::new(),
+    }
+}
+
+// --per-chapter / --bucket / --by paragraph: 전체 표 뒤에 장, 시간 구간, 문단마다 같은 표를 하나씩 붙인다.
+fn sections(documents: &[(String, String)], args: &Args) -> Result<Vec<(String, String)>, String> {
+    if args.by_paragraph {
+        return Ok(paragraph_sections(documents, args));
+    }
+    if args.per_chapter {
+        // 빈 장(표지 등)은 번호를 매기지 않고 건너뛴다.
+        let chapters = input::read_chapters(&args.source)?;
+        return Ok(chapters
+            .iter()
+            .filter(|c| !c.text.trim().is_empty())
+            .enumerate()
+            .map(|(i, c)| {
+                (
+                    format!("chapter {} ({})", i + 1, chapter_title(c)),
+                    c.text.clone(),
+                )
+            })
+            .collect());
+    }
+    if let (Some(size), Some(format)) = (args.bucket, &args.timestamp_format) {
+        let content = input::read(&args.source, &args.decoder)?;
+        let size = (size / 1000).max(1);
+        return Ok(timefmt::buckets(&content, format, size)
+            .into_iter()
+            .map(|(start, text)| (timefmt::label(start, size), text))
+            .collect());
+    }
+    if let Some(size) = args.bucket {
+        let cues = input::read_cues(&args.source)?;
+        return Ok(subtitles::buckets(&cues, size)
+            .into_iter()
+            .map(|(start, text)| {
+                let range = format!(
+                    "{}-{}",
+                    subtitles::format_clock(start),
+                    subtitles::format_clock(start + size)
+                );
+                (range, text)
+            })
+            .collect());
+    }
+    Ok(Vec::new())
+}
+
+// "paragraph 3 (line 12)". 필터가 있으면 걸린 줄 수를, 파일이 여러 개면 파일 이름을 붙인다.
+fn paragraph_sections(documents: &[(String, String)], args: &Args) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    for (name, text) in documents {
+        for paragraph in paragraphs::split(text) {
+            let mut label = format!("paragraph {} (line {}", paragraph.index, paragraph.line);
+            if args.filter.is_active() {
+                let hits = filter::matching_lines(paragraph.text, &args.filter).count();
+                label.push_str(&format!(", {hits} matching line(s)"));
+            }
+            label.push(')');
+            if documents.len() > 1 {
+                label = format!("{name}: {label}");
+            }
+            found.push((label, paragraph.text.to_string()));
+        }
+    }
+    found
+}
+
+// 장 제목은 대개 첫 줄(h1 등)이다. 너무 길면 잘라서 머리글에 쓴다.
+fn chapter_title(chapter: &epub::Chapter) -> String {
+    let first = chapter.text.lines().next().unwrap_or("").trim();
+    if first.is_empty() {
+        return chapter.href.clone();
+    }
+    let mut title: String = first.chars().take(40).collect();
+    if first.chars().count() > 40 {
+        title.push('…');
+    }
+    title
+}
+
+// --log: 메시지 부분만 레벨별로 집계하고, --level 범위로 줄을 거른다.
+fn run_log(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let records: Vec<logline::LogLine> = logline::parse_log(&content)
+        .into_iter()
+        .filter(|r| match (&args.level, r.level) {
+            (Some(range), Some(level)) => range.contains(level),
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .collect();
+
+    // 심각한 레벨부터 보여준다. 레벨을 알 수 없는 줄은 마지막에 따로 모은다.
+    let mut groups: Vec<(&str, Option<logline::Level>)> = Level::ALL
+        .iter()
+        .rev()
+        .map(|&l| (l.name(), Some(l)))
+        .collect();
+    groups.push(("unleveled", None));
+
+    let mut first = true;
+    for (name, level) in groups {
+        let mut counts = Counter::new();
+        for record in records.iter().filter(|r| r.level == level) {
+            for (word, n) in count_words(record.message, args.tokenizer).iter() {
+                counts.add(word, n);
+            }
+        }
+        if counts.is_empty() {
+            continue;
+        }
+        if !args.plain {
+            let _ = writeln!(out, "{}[Top words: {name}]", if first { "" } else { "\n" });
+        }
+        first = false;
+        for (word, count) in rank(counts, args) {
+            let _ = writeln!(out, "{word}: {count}");
+        }
+    }
+
+    if args.filter.is_active() || args.level.is_some() {
+        if !args.plain {
+            let _ = writeln!(out, "\n[Filtered lines]");
+        }
+        let hits = records.iter().filter(|r| args.filter.matches(r.raw));
+        for record in args.filter.limit(hits) {
+            let _ = writeln!(out, "{}", display_line(record.raw, args));
+        }
+    }
+    Ok(())
+}
+
+fn run_weblog(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let mut entries = Vec::new();
+    let mut skipped = 0usize;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match weblog::parse_line(line) {
+            Some(entry) => entries.push(entry),
+            None => skipped += 1,
+        }
+    }
+    if skipped > 0 {
+        eprintln!("skipped {skipped} line(s) not in common/combined log format");
+    }
+
+    let _ = writeln!(out, "[Top paths]");
+    for (path, count) in rank(weblog::count_field(&entries, |e| e.path.to_string()), args) {
+        let _ = writeln!(out, "{path}: {}", numfmt::count(count));
+    }
+
+    // 상태 코드는 종류가 적으므로 잘라내지 않고 코드 순서로 전부 보여준다.
+    let _ = writeln!(out, "\n[Status codes]");
+    let mut statuses: Vec<(String, usize)> =
+        weblog::count_field(&entries, |e| e.status.to_string()).into_vec();
+    statuses.sort();
+    let total = entries.len().max(1) as f64;
+    for (status, count) in statuses {
+        let pct = count as f64 * 100.0 / total;
+        let _ = writeln!(
+            out,
+            "{status}: {} ({})",
+            numfmt::count(count),
+            numfmt::percent(pct, 1)
+        );
+    }
+
+    let _ = writeln!(out, "\n[Top user agents]");
+    for (agent, count) in rank(weblog::count_field(&entries, |e| e.agent.to_string()), args) {
+        let _ = writeln!(out, "{agent}: {count}");
+    }
+    Ok(())
+}
+
+fn run_keywords(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let _ = writeln!(out, "[Keywords]");
+    for (phrase, score) in rake::extract(&content, args.tokenizer, args.analysis.top()) {
+        let _ = writeln!(out, "{phrase}: {score:.2}");
+    }
+    Ok(())
+}
+
+fn run_summarize(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let _ = writeln!(out, "[Summary]");
+    for sentence in summarize::summarize(&content, args.tokenizer, args.sentences) {
+        let _ = writeln!(out, "{sentence}");
+    }
+    Ok(())
+}
+
+fn run_generate(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let text = markov::generate(&content, args.order, args.words, &mut Rng::seeded())?;
+    out.push_str(&text);
+    Ok(())
+}
+
+// 경로가 -이면 stdout으로 바로 흘려보낸다(요약 줄은 섞이지 않게 쓰지 않는다).
+fn run_gen_corpus(args: &Args, out: &mut String) -> Result<(), String> {
+    let Source::Files(paths) = &args.source else {
+        return Err("gen-corpus writes to a PATH, not the clipboard".to_string());
+    };
+    let path = paths.first().map(String::as_str).unwrap_or("-");
+    let spec = &args.corpus;
+    if path == "-" {
+        let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+        return corpus::write(spec, &mut stdout)
+            .and_then(|_| stdout.flush())
+            .map_err(|e| format!("failed to write stdout: {e}"));
+    }
+    let words = output::write_streamed(path, |w| corpus::write(spec, w))?;
+    let _ = writeln!(
+        out,
+        "wrote {} bytes ({words} words, vocabulary {}, zipf {}) to {path}",
+        spec.size, spec.vocab, spec.exponent
+    );
+    Ok(())
+}
+
+// split: 조각 파일을 쓰고 파일마다 줄 수와 토큰 수(--stratify면 층별 줄 수)를 한 줄씩 알린다.
+fn run_split(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let prefix = match (&args.prefix, &args.source) {
+        (Some(prefix), _) => prefix.clone(),
+        (None, Source::Files(paths)) => paths.first().cloned().unwrap_or_default(),
+        (None, Source::Clipboard) => return Err("split needs --prefix PATH".to_string()),
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let (chunks, strata) = split::split(&lines, args.by_tokens, &args.stratify, args.tokenizer);
+    let _ = writeln!(
+        out,
+        "[Split into {} chunks of about {} tokens]",
+        chunks.len(),
+        numfmt::count(args.by_tokens)
+    );
+    for (i, chunk) in chunks.iter().enumerate() {
+        let path = split::chunk_path(&prefix, i, chunks.len());
+        let mut text = String::new();
+        for &line in &chunk.lines {
+            text.push_str(lines[line]);
+            text.push('\n');
+        }
+        output::write(&path, &text, false)?;
+        let _ = write!(
+            out,
+            "{path}: {} lines, {} tokens",
+            numfmt::count(chunk.lines.len()),
+            numfmt::count(chunk.tokens)
+        );
+        if !strata.is_empty() {
+            let layers: Vec<String> = strata
+                .iter()
+                .zip(&chunk.strata)
+                .map(|(name, n)| format!("{name} {}", numfmt::count(*n)))
+                .collect();
+            let _ = write!(out, " ({})", layers.join(", "));
+        }
+        out.push('\n');
+    }
+    Ok(())
+}
+
+// 단어장을 만들 때 쓴다. --stopwords-file의 단어는 뽑지 않는다.
+fn run_sample_words(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let counts = args
+        .analysis
+        .without_stopwords(count_words(&content, args.tokenizer));
+    let weighted = counts
+        .into_vec()
+        .into_iter()
+        .map(|(word, n)| {
+            let weight = if args.uniform { 1.0 } else { n as f64 };
+            ((word, n), weight)
+        })
+        .collect();
+    let _ = writeln!(out, "[Sampled words]");
+    for (word, n) in rng::weighted(weighted, args.sample, &mut Rng::seeded()) {
+        let _ = writeln!(out, "{word}: {n}");
+    }
+    Ok(())
+}
+
+fn run_compare_impl(args: &Args, out: &mut String) -> Result<(), String> {
+    let Source::Files(paths) = &args.source else {
+        return Err("compare-impl reads a FILE, not the clipboard".to_string());
+    };
+    let setup = crosscheck::Setup {
+        file: &paths[0],
+        top: args.analysis.top(),
+        contains: args.filter.contains.first().map(String::as_str),
+        python: &args.python,
+        script: &args.python_script,
+        runs: args.runs,
+    };
+    let result = crosscheck::run(&setup)?;
+    let ms = |d: Duration| format!("{:.1} ms", d.as_secs_f64() * 1000.0);
+    let rss = |r: Option<usize>| r.map_or("unavailable".to_string(), resources::human_bytes);
+    let _ = writeln!(out, "[Implementations (fastest of {} runs)]", args.runs);
+    let _ = writeln!(out, "{:<8} {:>12} {:>12}", "", "wall time", "peak RSS");
+    for (name, run) in [("python", &result.python), ("rust", &result.rust)] {
+        let _ = writeln!(
+            out,
+            "{name:<8} {:>12} {:>12}",
+            ms(run.wall),
+            rss(run.peak_rss)
+        );
+    }
+    let speedup = result.python.wall.as_secs_f64() / result.rust.wall.as_secs_f64().max(1e-9);
+    let _ = write!(out, "rust is {speedup:.1}x faster");
+    if let (Some(p), Some(r)) = (result.python.peak_rss, result.rust.peak_rss) {
+        let _ = write!(
+            out,
+            " and uses {:.1}x less memory",
+            p as f64 / r.max(1) as f64
+        );
+    }
+    let _ = writeln!(out);
+    let _ = writeln!(out, "\n[Output]");
+    match &result.difference {
+        None => {
+            let lines = result.rust.output.lines().count();
+            let _ = writeln!(out, "identical ({lines} lines)");
+        }
+        Some(d) => {
+            let _ = writeln!(out, "differs at line {}", d.line);
+            let _ = writeln!(out, "  python: {}", d.python);
+            let _ = writeln!(out, "  rust:   {}", d.rust);
+        }
+    }
+    Ok(())
+}
+
+fn run_spellcheck(args: &Args, out: &mut String) -> Result<(), String> {
+    let dict_path = args
+        .dict
+        .as_deref()
+        .ok_or_else(|| "spellcheck requires --dict PATH".to_string())?;
+    let dictionary = spell::load_dictionary(dict_path, args.tokenizer)?;
+    let content = input::read(&args.source, &args.decoder)?;
+
+    let _ = writeln!(out, "[Misspelled words]");
+    for m in spell::check(&content, &dictionary, args.tokenizer) {
+        if m.suggestions.is_empty() {
+            let _ = writeln!(out, "{}: {}", m.word, m.count);
+        } else {
+            let _ = writeln!(
+                out,
+                "{}: {} (did you mean: {})",
+                m.word,
+                m.count,
+                m.suggestions.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+// 빈도 비율이 큰 순. --top으로 줄 수를 제한한다(0이면 전부).
+fn run_suggest(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let top = match args.analysis.top() {
+        0 => usize::MAX,
+        n => n,
+    };
+    let _ = writeln!(out, "[Probable typos]");
+    for typo in spell::typos(&content, args.tokenizer).into_iter().take(top) {
+        let _ = writeln!(
+            out,
+            "{}: {} -> {}: {} ({:.1}x, distance {})",
+            typo.word,
+            typo.count,
+            typo.likely,
+            typo.likely_count,
+            typo.ratio(),
+            typo.distance
+        );
+    }
+    Ok(())
+}
+
+fn run_anagrams(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let _ = writeln!(out, "[Anagram groups]");
+    for group in anagrams::groups(&content, args.tokenizer) {
+        let _ = writeln!(out, "{}", group.join(", "));
+    }
+    Ok(())
+}
+
+// stats와 --only stats가 같이 쓰는 합계 네 줄.
+fn write_totals(summary: &stats::Summary, out: &mut String) {
+    let _ = writeln!(out, "lines: {}", numfmt::count(summary.lines));
+    let _ = writeln!(out, "tokens: {}", numfmt::count(summary.tokens));
+    let _ = writeln!(out, "distinct words: {}", numfmt::count(summary.distinct));
+    let _ = writeln!(
+        out,
+        "mean word length: {}",
+        numfmt::decimal(summary.mean_length, 2)
+    );
+}
+
+fn run_stats(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let summary = stats::summarize(&content, args.tokenizer);
+    trailer::counted(summary.tokens, summary.distinct);
+    let _ = writeln!(out, "[Stats]");
+    write_totals(&summary, out);
+
+    if args.length_histogram {
+        const BAR_WIDTH: usize = 50;
+        let _ = writeln!(out, "\n[Word length histogram]");
+        let max = summary.length_counts.iter().copied().max().unwrap_or(0);
+        let label_width = (summary.length_counts.len() - 1).to_string().len();
+        for (len, &count) in summary.length_counts.iter().enumerate().skip(1) {
+            let bar = stats::bar(count, max, BAR_WIDTH);
+            let _ = writeln!(out, "{len:>label_width$} | {bar:<BAR_WIDTH$} {count}");
+        }
+    }
+
+    if args.whitespace {
+        let documents = input::read_documents_lossy(&args.source)?;
+        let report = stats::indentation(&documents);
+        let _ = writeln!(out, "\n[Indentation by file]");
+        for file in &report.files {
+            let _ = writeln!(
+                out,
+                "{}: {} tab, {} space, {} mixed",
+                file.name, file.tabs, file.spaces, file.mixed
+            );
+        }
+        if report.widths.len() > 1 {
+            const BAR_WIDTH: usize = 50;
+            let _ = writeln!(out, "\n[Indentation widths (spaces)]");
+            let max = report.widths.iter().copied().max().unwrap_or(0);
+            let label_width = (report.widths.len() - 1).to_string().len();
+            for (width, &count) in report.widths.iter().enumerate().skip(1) {
+                if count > 0 {
+                    let bar = stats::bar(count, max, BAR_WIDTH);
+                    let _ = writeln!(out, "{width:>label_width$} | {bar:<BAR_WIDTH$} {count}");
+                }
+            }
+        }
+        if !report.mixed_lines.is_empty() {
+            let _ = writeln!(out, "\n[Mixed indentation]");
+            for (name, line) in &report.mixed_lines {
+                let _ = writeln!(out, "{name}:{line}");
+            }
+        }
+    }
+
+    if let Some(n) = args.longest_lines {
+        let documents = input::read_documents_lossy(&args.source)?;
+        let _ = writeln!(out, "\n[Longest lines]");
+        for long in stats::longest_lines(&documents, n) {
+            let _ = writeln!(
+                out,
+                "{}:{}: {} chars: {}",
+                long.name, long.line, long.chars, long.preview
+            );
+        }
+    }
+
+    if args.line_endings {
+        let _ = writeln!(out, "\n[Line endings]");
+        let mut converted = Vec::new();
+        for (name, data) in input::read_parts(&args.source) {
+            let Ok(data) = data else {
+                continue;
+            };
+            let endings = stats::line_endings(&data);
+            let _ = writeln!(
+                out,
+                "{name}: {} LF, {} CRLF, {} CR{}",
+                endings.lf,
+                endings.crlf,
+                endings.cr,
+                if endings.is_mixed() { " (mixed)" } else { "" }
+            );
+            let Some(target) = args.convert else {
+                continue;
+            };
+            let fixed = stats::convert(&data, target);
+            // stdin과 클립보드는 되돌려 쓸 곳이 없다.
+            if fixed == data || name.starts_with('(') {
+                continue;
+            }
+            // 원자적으로 바꿔치기하면 새 파일이 되므로 실행 권한 같은 원래 권한을 되돌려 놓는다.
+            let permissions = std::fs::metadata(&name).map(|m| m.permissions());
+            output::write_bytes(&name, &fixed, false)?;
+            if let Ok(permissions) = permissions {
+                let _ = std::fs::set_permissions(&name, permissions);
+            }
+            converted.push(name);
+        }
+        if args.convert.is_some() {
+            let _ = writeln!(out, "\n[Converted]");
+            for name in converted {
+                let _ = writeln!(out, "{name}");
+            }
+        }
+    }
 
-    for word in normalize_words(&content) {
-        *counts.entry(word).or_insert(0) += 1;
+    if args.case_report {
+        let _ = writeln!(out, "\n[Capitalization]");
+        let forms = capitals::case_forms(&content, args.tokenizer);
+        let shown = match args.analysis.top() {
+            0 => forms.len(),
+            n => n.min(forms.len()),
+        };
+        for forms in &forms[..shown] {
+            let _ = write!(
+                out,
+                "{}: {} lower, {} Capitalized, {} ALLCAPS",
+                forms.word, forms.lower, forms.capitalized, forms.upper
+            );
+            if forms.mixed > 0 {
+                let _ = write!(out, ", {} mixed", forms.mixed);
+            }
+            out.push('\n');
+        }
     }
 
-    let mut items: Vec<(String, usize)> = counts.into_iter().collect();
-    items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
-    items.truncate(top_n);
-    Ok(items)
+    if let Some(step) = args.vocab_growth {
+        let _ = writeln!(out, "\n[Vocabulary growth]");
+        for (tokens, distinct) in stats::vocab_growth(&content, args.tokenizer, step) {
+            let _ = writeln!(out, "{tokens}: {distinct}");
+        }
+    }
+    Ok(())
 }
 
-// Python 리스트 컴프리헨션과 비슷한 필터 로직.
-// 여기서도 I/O 에러를 예외(throw) 대신 Result로 반환한다.
-fn filter_lines(path: &str, keyword: &str) -> Result<Vec<String>, String> {
-    let content = fs::read_to_string(path).map_err(|e| format!("failed to read file: {e}"))?;
-    let key = keyword.to_ascii_lowercase();
+// 입력 파일은 원문이 아니라 빈도표이므로 --xml 같은 디코더를 적용하지 않는다.
+// --format csv로 쓰면 결과를 다시 merge에 넣을 수 있다.
+fn run_merge(args: &Args, out: &mut String) -> Result<(), String> {
+    let mut merged = Counter::new();
+    for (name, text) in input::read_documents(&args.source, &Decoder::Plain)? {
+        for (word, n) in counts::parse(&text, &name)?.iter() {
+            merged.add(word, n);
+        }
+    }
+    let ranked = rank(merged, args);
+    render_tables(&[table("Top words", "word", ranked)], Vec::new(), args, out);
+    Ok(())
+}
 
-    Ok(content
+// A의 빈도순으로 순위를 매긴다. --below가 1보다 크면 B쪽 빈도도 함께 보여준다.
+fn run_subtract(args: &Args, out: &mut String) -> Result<(), String> {
+    let documents = input::read_documents(&args.source, &args.decoder)?;
+    let [(a_name, a_text), (b_name, b_text)] = documents.as_slice() else {
+        return Err("subtract requires exactly two files: A B".to_string());
+    };
+    let a = count_words(a_text, args.tokenizer);
+    let b = count_words(b_text, args.tokenizer);
+    let _ = writeln!(out, "[In {a_name}, not in {b_name}]");
+    for (word, count) in rank(setops::subtract(&a, &b, args.below), args) {
+        if args.below > 1 {
+            let _ = writeln!(out, "{word}: {count} (B: {})", b.get(&word));
+        } else {
+            let _ = writeln!(out, "{word}: {count}");
+        }
+    }
+    Ok(())
+}
+
+// 필터 옵션을 주면 걸린 줄만 후보로 삼는다. 아무것도 고르지 않으면 종료 코드 1.
+fn run_pick(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let lines: Vec<&str> = content
         .lines()
-        .filter(|line| line.to_ascii_lowercase().contains(&key))
-        .map(String::from)
-        .collect())
+        .filter(|line| !line.trim().is_empty())
+        .filter(|line| !args.filter.is_active() || args.filter.matches(line))
+        .collect();
+    match pick::pick(&lines)? {
+        Some(line) => {
+            let _ = writeln!(out, "{line}");
+            Ok(())
+        }
+        None => Err("no line selected".to_string()),
+    }
+}
+
+// 후보는 기준선과 지금의 상위 --top 단어를 합친 것이다. 드문 단어는 조금만 바뀌어도
+// 변화율이 커지므로 전체 어휘가 아니라 상위 단어만 본다.
+fn run_drift(args: &Args, out: &mut String) -> Result<(), String> {
+    let path = args.baseline.as_deref().unwrap_or_default();
+    let saved = input::read(&Source::Files(vec![path.to_string()]), &Decoder::Plain)?;
+    let baseline = drift::parse_baseline(&saved, path)?;
+    let content = input::read(&args.source, &args.decoder)?;
+    let tokens = normalize_words(&content, args.tokenizer).len();
+    let current = count_words(&content, args.tokenizer);
+
+    let mut candidates: Vec<String> = Vec::new();
+    for (word, _) in rank(baseline.counts.clone(), args)
+        .into_iter()
+        .chain(rank(current.clone(), args))
+    {
+        if !candidates.contains(&word) {
+            candidates.push(word);
+        }
+    }
+    let shifts = drift::shifts(&baseline, &current, tokens, &candidates, args.threshold);
+
+    let percent = |x: f64| numfmt::percent(x * 100.0, 2);
+    let _ = writeln!(
+        out,
+        "[Drift vs {path} (threshold {:.0}%)]",
+        args.threshold * 100.0
+    );
+    if shifts.is_empty() {
+        let _ = writeln!(out, "no top word moved more than the threshold");
+    }
+    for s in shifts {
+        let change = if s.change.is_infinite() {
+            "new".to_string()
+        } else {
+            let delta = (s.after - s.before) / s.before * 100.0;
+            let sign = if delta >= 0.0 { "+" } else { "" };
+            format!("{sign}{}", numfmt::percent(delta, 1))
+        };
+        let _ = writeln!(
+            out,
+            "{}: {} -> {} ({change})",
+            s.word,
+            percent(s.before),
+            percent(s.after)
+        );
+    }
+    Ok(())
+}
+
+// todos와 lint-ws의 입력. 디렉터리는 재귀로 펼치고, 그 안의 UTF-8이 아닌 파일(이미지, 바이너리)은
+// 조용히 건너뛴다. 직접 지정한 파일은 다른 모드처럼 input에서 읽는다.
+fn walked_documents(args: &Args) -> Result<Vec<(String, String)>, String> {
+    let mut documents = Vec::new();
+    let mut direct = Vec::new();
+    if let Source::Files(paths) = &args.source {
+        for path in paths {
+            let root = std::path::Path::new(path);
+            if path == "-" || !root.is_dir() {
+                direct.push(path.clone());
+                continue;
+            }
+            for file in walk::files(root, &args.walk)? {
+                if let Ok(text) = std::fs::read_to_string(&file) {
+                    documents.push((file.display().to_string(), text));
+                }
+            }
+        }
+    }
+    let direct = match &args.source {
+        Source::Files(_) if direct.is_empty() => Vec::new(),
+        Source::Files(_) => input::read_documents(&Source::Files(direct), &Decoder::Plain)?,
+        Source::Clipboard => input::read_documents(&args.source, &Decoder::Plain)?,
+    };
+    documents.extend(direct);
+    Ok(documents)
+}
+
+// 필터 옵션을 주면 표시 뒤의 설명에 대해 적용한다.
+fn run_todos(args: &Args, out: &mut String) -> Result<(), String> {
+    let documents = walked_documents(args)?;
+    let mut by_marker = Counter::new();
+    let mut by_file = Counter::new();
+    let mut found = Vec::new();
+    for (name, text) in &documents {
+        for m in todos::scan(text) {
+            if args.filter.is_active() && !args.filter.matches(&m.note) {
+                continue;
+            }
+            by_marker.add(m.marker, 1);
+            by_file.add(name, 1);
+            found.push((name, m));
+        }
+    }
+
+    let _ = writeln!(out, "[By marker]");
+    for (marker, count) in rank_all(by_marker.clone(), args) {
+        let _ = writeln!(out, "{marker}: {count}");
+    }
+    let _ = writeln!(out, "\n[By file]");
+    for (file, count) in rank(by_file, args) {
+        let _ = writeln!(out, "{file}: {count}");
+    }
+    for marker in todos::MARKERS {
+        if by_marker.get(marker) == 0 {
+            continue;
+        }
+        let _ = writeln!(out, "\n[{marker}]");
+        for (name, m) in found.iter().filter(|(_, m)| m.marker == marker) {
+            if m.note.is_empty() {
+                let _ = writeln!(out, "{name}:{}", m.line);
+            } else {
+                let _ = writeln!(out, "{name}:{}: {}", m.line, m.note);
+            }
+        }
+    }
+    Ok(())
+}
+
+// near-dupes: 유사도가 높은 짝부터 "0.93  a.md:12  b.md:40"과 첫 문단의 첫 줄.
+fn run_near_dupes(args: &Args, out: &mut String) -> Result<(), String> {
+    let documents = walked_documents(args)?;
+    let passages = neardupes::passages(&documents, args.shingle, args.tokenizer);
+    let pairs = neardupes::pairs(&passages, args.threshold);
+    let place = |i: usize| {
+        let passage = &passages[i];
+        (documents[passage.document].0.as_str(), passage.line)
+    };
+    if args.format == Format::Json {
+        let side = |i: usize| {
+            let (path, line) = place(i);
+            Value::Object(vec![
+                ("path".to_string(), Value::String(path.to_string())),
+                ("line".to_string(), Value::Number(line as f64)),
+                ("text".to_string(), Value::String(passages[i].text.clone())),
+            ])
+        };
+        let items = pairs
+            .iter()
+            .map(|pair| {
+                Value::Object(vec![
+                    ("similarity".to_string(), Value::Number(pair.similarity)),
+                    ("a".to_string(), side(pair.a)),
+                    ("b".to_string(), side(pair.b)),
+                ])
+            })
+            .collect();
+        out.push_str(&json::to_pretty(&Value::Array(items)));
+        return Ok(());
+    }
+    let _ = writeln!(
+        out,
+        "[Near-duplicate paragraphs: {} pairs, similarity >= {:.2}]",
+        pairs.len(),
+        args.threshold
+    );
+    for pair in &pairs {
+        let ((a, a_line), (b, b_line)) = (place(pair.a), place(pair.b));
+        let first = passages[pair.a].text.lines().next().unwrap_or_default();
+        let _ = writeln!(out, "{:.2}  {a}:{a_line}  {b}:{b_line}", pair.similarity);
+        let _ = writeln!(out, "      {}", term::ellipsize(first, 72, 0));
+    }
+    Ok(())
+}
+
+// 파일별 합계, 줄 끝 공백 줄, 긴 빈 줄 묶음 순. --fix면 문제가 있던 파일마다 FILE.clean을 쓴다.
+fn run_lint_ws(args: &Args, out: &mut String) -> Result<(), String> {
+    let documents = walked_documents(args)?;
+    let found: Vec<(&String, &String, lintws::Lint)> = documents
+        .iter()
+        .map(|(name, text)| (name, text, lintws::scan(text)))
+        .collect();
+
+    let _ = writeln!(out, "[By file]");
+    for (name, _, lint) in &found {
+        let _ = writeln!(
+            out,
+            "{name}: {} trailing whitespace, {} blank runs",
+            lint.trailing.len(),
+            lint.blank_runs.len()
+        );
+    }
+    if found.iter().any(|(_, _, lint)| !lint.trailing.is_empty()) {
+        let _ = writeln!(out, "\n[Trailing whitespace]");
+        for (name, _, lint) in &found {
+            for line in &lint.trailing {
+                let _ = writeln!(out, "{name}:{line}");
+            }
+        }
+    }
+    if found.iter().any(|(_, _, lint)| !lint.blank_runs.is_empty()) {
+        let _ = writeln!(out, "\n[Blank line runs]");
+        for (name, _, lint) in &found {
+            for (first, last) in &lint.blank_runs {
+                let _ = writeln!(out, "{name}:{first}-{last} ({} lines)", last - first + 1);
+            }
+        }
+    }
+    if args.fix {
+        let _ = writeln!(out, "\n[Fixed copies]");
+        for (name, text, lint) in &found {
+            // stdin과 클립보드는 옆에 쓸 파일이 없다.
+            if lint.is_clean() || name.starts_with('(') {
+                continue;
+            }
+            let path = format!("{name}.clean");
+            output::write(&path, &lintws::clean(text), false)?;
+            let _ = writeln!(out, "{path}");
+        }
+    }
+    Ok(())
+}
+
+// replace: 바뀔 줄마다 FILE:LINE: 아래에 바꾸기 전(-)과 뒤(+)를 쓴다.
+fn run_replace(args: &Args, out: &mut String) -> Result<(), String> {
+    let Some((regex, template)) = &args.substitution else {
+        return Err("replace requires --from REGEX and --to TEXT".to_string());
+    };
+    let documents = input::read_documents(&args.source, &Decoder::Plain)?;
+    let mut lines = 0;
+    let mut files = Vec::new();
+    let _ = writeln!(out, "[Changes]");
+    for (name, text) in &documents {
+        let changes = replace::changes(text, regex, template);
+        for change in &changes {
+            let _ = write!(
+                out,
+                "{name}:{}:\n{}",
+                change.line,
+                replace::render(change, args.color)
+            );
+        }
+        if !changes.is_empty() {
+            lines += changes.len();
+            files.push((name, text));
+        }
+    }
+    // 포기한 줄은 바뀌지 않은 것처럼 보이므로 반쪽짜리 결과를 쓰지 않는다.
+    regex::exhausted()?;
+    let verb = if args.write {
+        "changed"
+    } else {
+        "would change"
+    };
+    let _ = writeln!(out, "\n{lines} line(s) in {} file(s) {verb}", files.len());
+    if args.write {
+        let _ = writeln!(out, "\n[Written]");
+        for (name, text) in files {
+            // stdin과 클립보드는 옆에 쓸 파일이 없다.
+            if name.starts_with('(') {
+                continue;
+            }
+            let path = format!("{name}.replaced");
+            output::write(&path, &replace::apply(text, regex, template), false)?;
+            let _ = writeln!(out, "{path}");
+        }
+    }
+    Ok(())
+}
+
+// 파일마다 따로 분석한다. json은 파일별 객체의 배열이고, 필터 옵션이 없으면 matches는 빈 배열이다.
+fn run_report(args: &Args, out: &mut String) -> Result<(), String> {
+    let documents = input::read_documents(&args.source, &args.decoder)?;
+    let mut reports = Vec::with_capacity(documents.len());
+    let mut metas = Vec::with_capacity(documents.len());
+    let mut texts = Vec::with_capacity(documents.len());
+    for (name, text) in &documents {
+        let meta = meta::load_for(name)?;
+        if !args.sidecars.accepts(meta.as_ref()) {
+            continue;
+        }
+        let matches = if args.filter.is_active() {
+            filter::numbered_lines(text, &args.filter)
+        } else {
+            Vec::new()
+        };
+        reports.push(FileReport {
+            path: name.clone(),
+            tokens: normalize_words(text, args.tokenizer).len(),
+            top_words: top_words(text, args)
+                .into_iter()
+                .map(|(word, count)| WordCount { word, count })
+                .collect(),
+            matches: matches
+                .into_iter()
+                .map(|(line, text)| FilterResult { line, text })
+                .collect(),
+        });
+        metas.push(meta);
+        texts.push(text.as_str());
+    }
+    if let Some(group_by) = args.sidecars.group_by {
+        report_groups(group_by, &reports, &metas, &texts, args, out);
+        return Ok(());
+    }
+
+    if args.format == Format::Json {
+        let mut value = reports.to_json();
+        if let Value::Array(items) = &mut value {
+            for (item, meta) in items.iter_mut().zip(&metas) {
+                if let (Value::Object(fields), Some(meta)) = (item, meta) {
+                    fields.push(("meta".to_string(), meta.to_json()));
+                }
+            }
+        }
+        if let (true, Value::Array(items)) = (args.provenance, &mut value) {
+            // 파일별 객체에 size, sha256, mtime을 더한다. 못 읽은 파일은 보고서에 없다.
+            let inputs = provenance::inputs(&args.source);
+            for (item, report) in items.iter_mut().zip(&reports) {
+                let found = inputs.iter().find(|input| input.path == report.path);
+                if let (Value::Object(fields), Some(input)) = (item, found) {
+                    if let Value::Object(extra) = input.to_json() {
+                        fields.extend(extra.into_iter().filter(|(key, _)| key != "path"));
+                    }
+                }
+            }
+        }
+        out.push_str(&json::to_pretty(&value));
+        return Ok(());
+    }
+
+    for (i, (report, meta)) in reports.into_iter().zip(&metas).enumerate() {
+        let _ = writeln!(out, "{}[{}]", if i == 0 { "" } else { "\n" }, report.path);
+        if let Some(meta) = meta {
+            write_meta(meta, out);
+        }
+        let _ = writeln!(out, "tokens: {}", report.tokens);
+        for entry in report.top_words {
+            let _ = writeln!(out, "{}: {}", entry.word, entry.count);
+        }
+        if args.filter.is_active() {
+            let _ = writeln!(out, "matches: {}", report.matches.len());
+            for m in report.matches {
+                let _ = writeln!(out, "{}:{}", m.line, display_line(&m.text, args));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 사이드카에 적힌 값만 쓴다.
+fn write_meta(meta: &meta::Meta, out: &mut String) {
+    for (key, value) in [
+        ("title", &meta.title),
+        ("author", &meta.author),
+        ("date", &meta.date),
+    ] {
+        if let Some(value) = value {
+            let _ = writeln!(out, "{key}: {value}");
+        }
+    }
+    if !meta.tags.is_empty() {
+        let _ = writeln!(out, "tags: {}", meta.tags.join(", "));
+    }
+}
+
+// report --group-by: 묶음마다 파일 수, 토큰 합, 합친 빈도의 상위 단어, 걸린 줄 수. 묶음은 이름순이다.
+fn report_groups(
+    group_by: meta::GroupBy,
+    reports: &[FileReport],
+    metas: &[Option<meta::Meta>],
+    texts: &[&str],
+    args: &Args,
+    out: &mut String,
+) {
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, meta) in metas.iter().enumerate() {
+        for key in group_by.keys(meta.as_ref()) {
+            groups.entry(key).or_default().push(i);
+        }
+    }
+    let label = match group_by {
+        meta::GroupBy::Tag => "tag",
+        meta::GroupBy::Author => "author",
+        meta::GroupBy::Year => "year",
+    };
+    let mut items = Vec::new();
+    for (i, (key, members)) in groups.iter().enumerate() {
+        let mut counts = Counter::new();
+        for &m in members {
+            for (word, n) in count_words(texts[m], args.tokenizer).iter() {
+                counts.add(word, n);
+            }
+        }
+        let top = top_counts(counts, args);
+        let tokens: usize = members.iter().map(|&m| reports[m].tokens).sum();
+        let matches: usize = members.iter().map(|&m| reports[m].matches.len()).sum();
+        if args.format == Format::Json {
+            let words = top
+                .into_iter()
+                .map(|(word, count)| WordCount { word, count }.to_json())
+                .collect();
+            let files = members
+                .iter()
+                .map(|&m| Value::String(reports[m].path.clone()))
+                .collect();
+            let mut fields = vec![
+                (label.to_string(), Value::String(key.clone())),
+                ("files".to_string(), Value::Array(files)),
+                ("tokens".to_string(), Value::Number(tokens as f64)),
+                ("top_words".to_string(), Value::Array(words)),
+            ];
+            if args.filter.is_active() {
+                fields.push(("matches".to_string(), Value::Number(matches as f64)));
+            }
+            items.push(Value::Object(fields));
+            continue;
+        }
+        let _ = writeln!(out, "{}[{label}: {key}]", if i == 0 { "" } else { "\n" });
+        let _ = writeln!(out, "files: {}", members.len());
+        let _ = writeln!(out, "tokens: {tokens}");
+        for (word, count) in top {
+            let _ = writeln!(out, "{word}: {count}");
+        }
+        if args.filter.is_active() {
+            let _ = writeln!(out, "matches: {matches}");
+        }
+    }
+    if args.format == Format::Json {
+        out.push_str(&json::to_pretty(&Value::Array(items)));
+    }
+}
+
+// 전체 합계로 순위를 매기고, 괄호 안에 입력 순서대로 파일별 빈도를 붙인다.
+fn run_intersect(args: &Args, out: &mut String) -> Result<(), String> {
+    let documents = input::read_documents(&args.source, &args.decoder)?;
+    let corpora: Vec<Counter> = documents
+        .iter()
+        .map(|(_, text)| count_words(text, args.tokenizer))
+        .collect();
+    let _ = writeln!(out, "[Shared by all {} files]", documents.len());
+    for (word, total) in rank(setops::intersect(&corpora), args) {
+        let per_file: Vec<String> = documents
+            .iter()
+            .zip(&corpora)
+            .map(|((name, _), counts)| format!("{name}: {}", counts.get(&word)))
+            .collect();
+        let _ = writeln!(out, "{word}: {total} ({})", per_file.join(", "));
+    }
+    Ok(())
+}
+
+// 태그를 뺀 나머지 본문으로 일반 단어를 세므로 "#rust"가 "rust"로 한 번 더 세지지 않는다.
+fn run_social(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let tally = social::tally(&content);
+    let sections = [
+        ("Hashtags", tally.hashtags),
+        ("Mentions", tally.mentions),
+        ("Top words", count_words(&tally.rest, args.tokenizer)),
+    ];
+    for (n, (heading, counts)) in sections.into_iter().enumerate() {
+        if n > 0 {
+            let _ = writeln!(out);
+        }
+        let _ = writeln!(out, "[{heading}]");
+        for (word, count) in rank(counts, args) {
+            let _ = writeln!(out, "{word}: {count}");
+        }
+    }
+    Ok(())
+}
+
+// 구간 라벨은 1부터 세는 토큰 번호 범위다. 막대는 가장 많은 구간을 기준으로 맞춘다.
+fn run_trend(args: &Args, out: &mut String) -> Result<(), String> {
+    const BAR_WIDTH: usize = 40;
+    let word = args.word.as_deref().unwrap_or_default();
+    let content = input::read(&args.source, &args.decoder)?;
+    let tokens = normalize_words(&content, args.tokenizer);
+    let rows: Vec<(String, usize)> = trend::counts(&tokens, word, args.buckets)
+        .into_iter()
+        .map(|(range, n)| (format!("{}-{}", range.start + 1, range.end), n))
+        .collect();
+    let max = rows.iter().map(|(_, n)| *n).max().unwrap_or(0);
+    let label_width = rows.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
+    let _ = writeln!(out, "[Trend: {word}]");
+    for (label, n) in rows {
+        let bar = stats::bar(n, max, BAR_WIDTH);
+        let _ = writeln!(out, "{label:>label_width$} | {bar:<BAR_WIDTH$} {n}");
+    }
+    Ok(())
 }
 
-// Python argparse와 달리 라이브러리 없이 직접 파싱한 버전.
-// 학습 포인트: Option<String>으로 "있을 수도/없을 수도" 있는 값을 타입으로 표현한다.
-fn parse_args() -> Result<(String, usize, Option<String>), String> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        return Err("usage: cargo run -- <file> [--top N] [--contains KEYWORD]".to_string());
+// 줄 범위마다 필터에 걸린 줄의 비율. 구간 길이가 조금씩 달라도 비교할 수 있게
+// 횟수가 아니라 비율로 막대를 그린다.
+fn run_heatmap(args: &Args, out: &mut String) -> Result<(), String> {
+    const BAR_WIDTH: usize = 40;
+    let content = input::read(&args.source, &args.decoder)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let rows: Vec<(String, usize, usize)> = trend::split(lines.len(), args.buckets)
+        .into_iter()
+        .map(|range| {
+            let label = format!("{}-{}", range.start + 1, range.end);
+            let hits = lines[range.clone()]
+                .iter()
+                .filter(|line| args.filter.matches(line))
+                .count();
+            (label, hits, range.len())
+        })
+        .collect();
+    let density = |hits: usize, total: usize| hits as f64 / total.max(1) as f64;
+    let max = rows
+        .iter()
+        .map(|(_, hits, total)| density(*hits, *total))
+        .fold(0.0, f64::max);
+    let label_width = rows.iter().map(|(l, _, _)| l.len()).max().unwrap_or(0);
+    let _ = writeln!(out, "[Match density by line range]");
+    for (label, hits, total) in rows {
+        let d = density(hits, total);
+        let len = if max > 0.0 {
+            (d / max * BAR_WIDTH as f64).round() as usize
+        } else {
+            0
+        };
+        let bar = "#".repeat(if hits > 0 { len.max(1) } else { 0 });
+        let _ = writeln!(
+            out,
+            "{label:>label_width$} | {bar:<BAR_WIDTH$} {:>6} ({}/{})",
+            numfmt::percent(d * 100.0, 1),
+            numfmt::count(hits),
+            numfmt::count(total)
+        );
     }
+    Ok(())
+}
 
-    let file = args[1].clone();
-    let mut top_n = 5usize;
-    let mut contains: Option<String> = None;
+// positions: 파일이 여러 개면 --flag-list처럼 "파일:줄:열"로 쓴다. json은 파일별 위치 배열이다.
+fn run_positions(args: &Args, out: &mut String) -> Result<(), String> {
+    let word = args.word.as_deref().unwrap_or_default();
+    let target = positions::target(word, args.tokenizer)?;
+    let documents = input::read_documents(&args.source, &args.decoder)?;
+    if args.format == Format::Json {
+        let files: Vec<Value> = documents
+            .iter()
+            .map(|(name, text)| {
+                let found = positions::find(text, &target, args.tokenizer);
+                Value::Object(vec![
+                    ("path".to_string(), Value::String(name.clone())),
+                    ("positions".to_string(), found.to_json()),
+                ])
+            })
+            .collect();
+        out.push_str(&json::to_pretty(&Value::Array(files)));
+        return Ok(());
+    }
+    let with_name = documents.len() > 1;
+    let _ = writeln!(out, "[Positions: {target}]");
+    for (name, text) in &documents {
+        for at in positions::find(text, &target, args.tokenizer) {
+            if with_name {
+                let _ = write!(out, "{name}:");
+            }
+            let _ = writeln!(out, "{}:{}: byte {}", at.line, at.column, at.offset);
+        }
+    }
+    Ok(())
+}
 
-    let mut i = 2;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--top" => {
-                i += 1;
-                let value = args
-                    .get(i)
-                    .ok_or_else(|| "missing value for --top".to_string())?;
-                top_n = value
-                    .parse::<usize>()
-                    .map_err(|_| "--top must be a positive integer".to_string())?;
+// rank: --word마다 빈도, 순위(1부터, 상위 단어 표와 같은 순서), 백분위(빈도가 더 낮은 단어의 비율).
+// 불용어는 순위에 없으므로 빈도만 내고 순위와 백분위는 비운다(json에서는 null).
+fn run_rank(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let counts = count_words(&content, args.tokenizer);
+    let found: Vec<usize> = args.lookup.iter().map(|word| counts.get(word)).collect();
+    let ranked = rank_all(args.analysis.without_stopwords(counts), args);
+    let total = ranked.len();
+    let places: HashMap<&str, usize> = ranked
+        .iter()
+        .enumerate()
+        .map(|(i, (word, _))| (word.as_str(), i + 1))
+        .collect();
+    // ranked는 빈도 내림차순이므로 빈도가 n보다 낮은 단어 수는 n 이상인 단어 수를 빼서 얻는다.
+    let percentile = |n: usize| {
+        let lower = total - ranked.partition_point(|(_, count)| *count >= n);
+        100.0 * lower as f64 / total as f64
+    };
+    let rows: Vec<(&String, usize, Option<usize>)> = args
+        .lookup
+        .iter()
+        .zip(found)
+        .map(|(word, count)| (word, count, places.get(word.as_str()).copied()))
+        .collect();
+    if args.format == Format::Json {
+        let items = rows
+            .iter()
+            .map(|&(word, count, place)| {
+                Value::Object(vec![
+                    ("word".to_string(), Value::String(word.clone())),
+                    ("count".to_string(), Value::Number(count as f64)),
+                    (
+                        "rank".to_string(),
+                        place.map_or(Value::Null, |r| Value::Number(r as f64)),
+                    ),
+                    (
+                        "percentile".to_string(),
+                        place.map_or(Value::Null, |_| Value::Number(percentile(count))),
+                    ),
+                ])
+            })
+            .collect();
+        let report = Value::Object(vec![
+            ("words".to_string(), Value::Number(total as f64)),
+            ("ranks".to_string(), Value::Array(items)),
+        ]);
+        out.push_str(&json::to_pretty(&report));
+        return Ok(());
+    }
+    let _ = writeln!(out, "[Rank among {total} words]");
+    for (word, count, place) in rows {
+        match place {
+            Some(place) => {
+                let _ = writeln!(
+                    out,
+                    "{word}: count {count}, rank {place}, percentile {:.1}",
+                    percentile(count)
+                );
             }
-            "--contains" => {
-                i += 1;
-                let value = args
-                    .get(i)
-                    .ok_or_else(|| "missing value for --contains".to_string())?;
-                contains = Some(value.clone());
+            None if count > 0 => {
+                let _ = writeln!(out, "{word}: count {count}, not ranked (stopword)");
             }
-            unknown => {
-                return Err(format!("unknown argument: {unknown}"));
+            None => {
+                let _ = writeln!(out, "{word}: not found");
             }
         }
-        i += 1;
     }
+    Ok(())
+}
+
+// difficulty: 수준마다 토큰 비율과 그 수준까지의 누적 비율, 서로 다른 단어 수. 불용어도 읽어야 하는
+// 단어이므로 빼지 않는다. 목록에 없는 단어는 빈도순으로 --top개.
+fn run_difficulty(args: &Args, out: &mut String) -> Result<(), String> {
+    let wordlist = difficulty::load(args.wordlist.as_deref().unwrap_or_default(), args.tokenizer)?;
+    let content = input::read(&args.source, &args.decoder)?;
+    let profile = difficulty::profile(&count_words(&content, args.tokenizer), &wordlist);
+    let (tokens, words) = (profile.tokens(), profile.words());
+    let share = |n: usize| 100.0 * n as f64 / tokens.max(1) as f64;
+    let mut cumulative = 0;
+    let mut bands = Vec::new();
+    for (level, band) in wordlist.levels.iter().zip(&profile.bands) {
+        cumulative += band.tokens;
+        bands.push((level.as_str(), band, cumulative));
+    }
+    bands.push(("unlisted", &profile.unlisted, tokens));
+    let unlisted = rank(profile.unlisted_words, args);
+    if args.format == Format::Json {
+        let levels = bands
+            .iter()
+            .map(|&(level, band, cumulative)| {
+                Value::Object(vec![
+                    ("level".to_string(), Value::String(level.to_string())),
+                    ("tokens".to_string(), Value::Number(band.tokens as f64)),
+                    ("words".to_string(), Value::Number(band.words as f64)),
+                    ("share".to_string(), Value::Number(share(band.tokens))),
+                    ("cumulative".to_string(), Value::Number(share(cumulative))),
+                ])
+            })
+            .collect();
+        let unlisted: Vec<WordCount> = unlisted
+            .into_iter()
+            .map(|(word, count)| WordCount { word, count })
+            .collect();
+        let report = Value::Object(vec![
+            ("tokens".to_string(), Value::Number(tokens as f64)),
+            ("words".to_string(), Value::Number(words as f64)),
+            ("levels".to_string(), Value::Array(levels)),
+            ("unlisted_words".to_string(), unlisted.to_json()),
+        ]);
+        out.push_str(&json::to_pretty(&report));
+        return Ok(());
+    }
+    let _ = writeln!(
+        out,
+        "[Vocabulary by level: {} tokens, {} words]",
+        numfmt::count(tokens),
+        numfmt::count(words)
+    );
+    for (level, band, cumulative) in bands {
+        let _ = writeln!(
+            out,
+            "{level}: {} tokens ({:.1}%, cumulative {:.1}%), {} words",
+            numfmt::count(band.tokens),
+            share(band.tokens),
+            share(cumulative),
+            numfmt::count(band.words)
+        );
+    }
+    let _ = writeln!(out, "\n[Unlisted words]");
+    for (word, count) in unlisted {
+        let _ = writeln!(out, "{word}: {}", numfmt::count(count));
+    }
+    Ok(())
+}
+
+// graph: 상위 단어의 동시 출현 간선. text는 "a -- b: n" 한 줄씩, dot/gexf는 파일 하나.
+fn run_graph(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let graph = graph::build(&content, &args.analysis, args.window);
+    match args.format {
+        Format::Dot => graph::dot(&graph, out),
+        Format::Gexf => graph::gexf(&graph, out),
+        _ => graph::text(&graph, out),
+    }
+    Ok(())
+}
+
+fn run_bursts(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let _ = writeln!(out, "[Bursts: window {}]", args.window);
+    for burst in bursts::find(&content, &args.analysis, args.window) {
+        let lines = if burst.first_line == burst.last_line {
+            format!("line {}", burst.first_line)
+        } else {
+            format!("lines {}-{}", burst.first_line, burst.last_line)
+        };
+        let _ = writeln!(
+            out,
+            "{}: {} in {lines} (expected {:.1}, {:.1}x)",
+            burst.word,
+            burst.count,
+            burst.expected,
+            burst.ratio()
+        );
+    }
+    Ok(())
+}
 
-    Ok((file, top_n, contains))
+// query --sql: 순위 전체를 words 표로 두고 문장을 돌린다. text는 열을 맞춘 표, csv는 머리글과 행.
+fn run_query(args: &Args, out: &mut String) -> Result<(), String> {
+    let Some(statement) = &args.sql else {
+        return Ok(());
+    };
+    let content = input::read(&args.source, &args.decoder)?;
+    let ranked = rank_all(count_words(&content, args.tokenizer), args);
+    let rows: Vec<sql::Row> = ranked
+        .iter()
+        .enumerate()
+        .map(|(i, (word, count))| sql::Row {
+            rank: i + 1,
+            word,
+            count: *count,
+        })
+        .collect();
+    let result = statement.run(&rows).map_err(|e| format!("--sql: {e}"))?;
+    if args.format == Format::Csv {
+        let header: Vec<String> = result.columns.iter().map(|c| csv_field(c)).collect();
+        let _ = writeln!(out, "{}", header.join(","));
+        for row in &result.rows {
+            let fields: Vec<String> = row.iter().map(|v| csv_field(&v.to_string())).collect();
+            let _ = writeln!(out, "{}", fields.join(","));
+        }
+        return Ok(());
+    }
+    let cells: Vec<Vec<String>> = result
+        .rows
+        .iter()
+        .map(|row| row.iter().map(|v| v.to_string()).collect())
+        .collect();
+    let widths: Vec<usize> = result
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            cells
+                .iter()
+                .map(|row| diagnostic::width(&row[i]))
+                .chain([diagnostic::width(name)])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    // 숫자 열은 오른쪽으로 맞춘다(첫 행의 값으로 정한다).
+    let numeric: Vec<bool> = (0..widths.len())
+        .map(|i| result.rows.first().is_some_and(|row| row[i].is_number()))
+        .collect();
+    let line = |cells: &[String], out: &mut String| {
+        let mut text = String::new();
+        for (i, cell) in cells.iter().enumerate() {
+            let pad = " ".repeat(widths[i] - diagnostic::width(cell));
+            if i > 0 {
+                text.push_str("  ");
+            }
+            if numeric[i] {
+                text.push_str(&pad);
+                text.push_str(cell);
+            } else {
+                text.push_str(cell);
+                text.push_str(&pad);
+            }
+        }
+        let _ = writeln!(out, "{}", text.trim_end());
+    };
+    line(&result.columns, out);
+    for row in &cells {
+        line(row, out);
+    }
+    let _ = writeln!(
+        out,
+        "({} row{})",
+        result.rows.len(),
+        if result.rows.len() == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+// 적합은 전체 순위로 하고, 표는 --top 줄만 보여준다(--all이면 전부).
+fn run_zipf(args: &Args, out: &mut String) -> Result<(), String> {
+    let content = input::read(&args.source, &args.decoder)?;
+    let ranked = rank_all(count_words(&content, args.tokenizer), args);
+    let counts: Vec<usize> = ranked.iter().map(|(_, c)| *c).collect();
+    let fit = zipf::fit(&counts);
+    let shown = if args.analysis.top() == 0 {
+        ranked.len()
+    } else {
+        args.analysis.top().min(ranked.len())
+    };
+
+    if args.format == Format::Csv {
+        // CSV는 표만 담고, 적합 결과는 stderr로 보낸다.
+        let _ = writeln!(out, "rank,word,count,log10_rank,log10_count");
+        for (i, (word, count)) in ranked.iter().take(shown).enumerate() {
+            let r = i + 1;
+            let (lr, lc) = ((r as f64).log10(), (*count as f64).log10());
+            let _ = writeln!(out, "{r},{},{count},{lr:.4},{lc:.4}", csv_field(word));
+        }
+        if let Some(fit) = &fit {
+            eprintln!(
+                "zipf exponent: {:.3} (R^2 {:.3})",
+                fit.exponent, fit.r_squared
+            );
+        }
+        return Ok(());
+    }
+
+    let _ = writeln!(out, "[Zipf fit]");
+    match &fit {
+        Some(fit) => {
+            let _ = writeln!(out, "exponent: {:.3}", fit.exponent);
+            let _ = writeln!(out, "r^2: {:.3}", fit.r_squared);
+        }
+        None => {
+            let _ = writeln!(out, "not enough distinct words to fit");
+        }
+    }
+    let _ = writeln!(out, "distinct words: {}", ranked.len());
+
+    let _ = writeln!(out, "\n[Rank/frequency]");
+    let word_width = ranked
+        .iter()
+        .take(shown)
+        .map(|(w, _)| w.chars().count())
+        .max()
+        .unwrap_or(4)
+        .max(4);
+    let _ = writeln!(
+        out,
+        "{:>5}  {:<word_width$}  {:>8}  {:>11}  {:>12}",
+        "rank", "word", "count", "log10(rank)", "log10(count)"
+    );
+    for (i, (word, count)) in ranked.iter().take(shown).enumerate() {
+        let r = i + 1;
+        let _ = writeln!(
+            out,
+            "{r:>5}  {word:<word_width$}  {count:>8}  {:>11.4}  {:>12.4}",
+            (r as f64).log10(),
+            (*count as f64).log10()
+        );
+    }
+    Ok(())
+}
+
+// --top은 두 표 각각의 줄 수다.
+fn run_compare(args: &Args, out: &mut String) -> Result<(), String> {
+    let reference_path = args
+        .reference
+        .as_deref()
+        .ok_or_else(|| "compare requires --reference PATH".to_string())?;
+    let content = input::read(&args.source, &args.decoder)?;
+    let reference = input::read(
+        &Source::Files(vec![reference_path.to_string()]),
+        &args.decoder,
+    )?;
+    let target_counts = count_words(&content, args.tokenizer);
+    let reference_counts = count_words(&reference, args.tokenizer);
+    let (over, under) = keyness::compare(&target_counts, &reference_counts);
+
+    let limit = if args.analysis.top() == 0 {
+        usize::MAX
+    } else {
+        args.analysis.top()
+    };
+    let tables = [("Overrepresented", over), ("Underrepresented", under)];
+    for (i, (heading, rows)) in tables.into_iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "{}[{heading} (log-likelihood)]",
+            if i == 0 { "" } else { "\n" }
+        );
+        for k in rows.iter().take(limit) {
+            let _ = writeln!(
+                out,
+                "{}: {:.2} (target {}, reference {})",
+                k.word, k.g2, k.target, k.reference
+            );
+        }
+    }
+    Ok(())
+}
+
+// 위반한 규칙을 모두 stderr에 찍는다. 하나라도 어기면 false.
+// 목록 파일은 본문과 같은 규칙으로 정규화해서 쓰이므로(wordlist.rs) 비교도 정규화한 뒤에 한다.
+// 한 줄이 다른 모양이 되거나 여러 토큰으로 갈리는 항목은 따로 보여준다(Don't -> don t).
+fn run_stopwords(args: &Args, out: &mut String) -> Result<(), String> {
+    let builtin: BTreeSet<&str> = stopwords::ENGLISH.iter().copied().collect();
+    let path = match (args.stopwords_action, &args.source) {
+        (StopwordsAction::Diff, Source::Files(paths)) => &paths[0],
+        _ => {
+            let _ = writeln!(out, "[Built-in stopwords: en ({})]", builtin.len());
+            out.push_str(&wordlist::format(builtin));
+            return Ok(());
+        }
+    };
+    let custom: BTreeSet<String> = wordlist::load(path, "stopwords file", args.tokenizer)?
+        .into_iter()
+        .collect();
+    let content = input::read(&args.source, &Decoder::Plain)?;
+    let mut reshaped = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let words = normalize_words(line, args.tokenizer);
+        // 대소문자만 접힌 것은 당연하므로 보여주지 않는다.
+        if words.len() != 1 || words[0] != line.to_lowercase() {
+            reshaped.push(format!("{line} -> {}", words.join(" ")));
+        }
+    }
+    let only_custom: Vec<&str> = custom
+        .iter()
+        .map(String::as_str)
+        .filter(|w| !builtin.contains(w))
+        .collect();
+    let only_builtin: Vec<&str> = builtin
+        .iter()
+        .copied()
+        .filter(|w| !custom.contains(*w))
+        .collect();
+    let _ = writeln!(out, "[Only in {path} ({})]", only_custom.len());
+    out.push_str(&wordlist::format(only_custom));
+    let _ = writeln!(out, "\n[Only in built-in en ({})]", only_builtin.len());
+    out.push_str(&wordlist::format(only_builtin));
+    if !reshaped.is_empty() {
+        let _ = writeln!(out, "\n[Normalized differently]");
+        for entry in reshaped {
+            let _ = writeln!(out, "{entry}");
+        }
+    }
+    let shared = custom
+        .iter()
+        .filter(|w| builtin.contains(w.as_str()))
+        .count();
+    let _ = writeln!(out, "\n{shared} word(s) in both");
+    Ok(())
+}
+
+fn run_cache_clear(args: &Args, out: &mut String) -> Result<(), String> {
+    let dir = args.cache.as_deref().unwrap_or_default();
+    let removed = cache::clear(dir)?;
+    let _ = writeln!(out, "removed {removed} cached file(s) from {dir}");
+    Ok(())
+}
+
+fn encoded(text: &str, encoding: Encoding, bom: bool) -> Vec<u8> {
+    let (bytes, replaced) = encoding::encode(text, encoding, bom);
+    if replaced > 0 {
+        eprintln!(
+            "{replaced} character(s) have no {} form and were written as ?",
+            encoding.name()
+        );
+    }
+    bytes
+}
+
+fn check_assertions(args: &Args) -> bool {
+    let counts = match input::read(&args.source, &args.decoder) {
+        Ok(content) => count_words(&content, args.tokenizer),
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        }
+    };
+    let mut passed = true;
+    for assertion in &args.assertions {
+        if let Err(message) = assertion.check(&counts) {
+            eprintln!("{message}");
+            passed = false;
+        }
+    }
+    passed
+}
+
+fn dispatch(args: &Args, out: &mut String) -> Result<(), String> {
+    match args.command {
+        Command::Count => run_count(args, out),
+        Command::Keywords => run_keywords(args, out),
+        Command::Summarize => run_summarize(args, out),
+        Command::Generate => run_generate(args, out),
+        Command::GenCorpus => run_gen_corpus(args, out),
+        Command::CompareImpl => run_compare_impl(args, out),
+        Command::SampleWords => run_sample_words(args, out),
+        Command::Spellcheck => run_spellcheck(args, out),
+        Command::Suggest => run_suggest(args, out),
+        Command::Anagrams => run_anagrams(args, out),
+        Command::Weblog => run_weblog(args, out),
+        Command::Stats => run_stats(args, out),
+        Command::Zipf => run_zipf(args, out),
+        Command::Compare => run_compare(args, out),
+        Command::Social => run_social(args, out),
+        Command::Trend => run_trend(args, out),
+        Command::Heatmap => run_heatmap(args, out),
+        Command::Merge => run_merge(args, out),
+        Command::Subtract => run_subtract(args, out),
+        Command::Intersect => run_intersect(args, out),
+        Command::Report => run_report(args, out),
+        Command::Todos => run_todos(args, out),
+        Command::LintWs => run_lint_ws(args, out),
+        Command::NearDupes => run_near_dupes(args, out),
+        Command::Split => run_split(args, out),
+        Command::Replace => run_replace(args, out),
+        Command::Drift => run_drift(args, out),
+        Command::Pick => run_pick(args, out),
+        Command::Daemon => daemon::serve(args, args.socket.as_deref().unwrap_or_default()),
+        Command::Cache => run_cache_clear(args, out),
+        Command::Stopwords => run_stopwords(args, out),
+        Command::Query => run_query(args, out),
+        Command::Graph => run_graph(args, out),
+        Command::Positions => run_positions(args, out),
+        Command::Bursts => run_bursts(args, out),
+        Command::Rank => run_rank(args, out),
+        Command::Difficulty => run_difficulty(args, out),
+        Command::SelfUpdate => selfupdate::run(
+            args.release_url.as_deref().unwrap_or_default(),
+            args.pubkey.as_deref(),
+            args.check_update,
+            out,
+        ),
+    }
+}
+
+// --follow: 결과를 모아 두지 않고 줄이 올 때마다 바로 쓴다.
+fn run_follow(args: &Args) -> Result<(), String> {
+    let Source::Files(paths) = &args.source else {
+        return Err("--follow watches a FILE, not the clipboard".to_string());
+    };
+    let mut monitor = alert::Monitor::new(&args.alerts, args.tokenizer, args.alert_cmd.clone());
+    let alerting = !args.alerts.is_empty();
+    let mut stdout = std::io::stdout();
+    follow::run(&paths[0], |event| match event {
+        Some(line) if args.filter.is_active() && !args.filter.matches(line) => {}
+        Some(line) if alerting => monitor.line(line, Instant::now()),
+        Some(line) => {
+            let _ = writeln!(stdout, "{line}");
+            let _ = stdout.flush();
+        }
+        None => monitor.tick(Instant::now(), &mut stdout),
+    })
+}
+
+// --journal이 있으면 성공이든 실패든 이번 실행을 한 줄 남긴다.
+fn run_journaled(args: &Args, out: &mut String) -> Result<(), String> {
+    let started = Instant::now();
+    let result = cache::cached(args, out, dispatch);
+    if let Some(path) = &args.journal {
+        let outcome = result
+            .as_ref()
+            .map(|_| out.as_str())
+            .map_err(String::as_str);
+        journal::record(path, args, started.elapsed(), outcome)?;
+    }
+    result
 }
 
 fn main() {
+    let started = Instant::now();
     // Python이라면 예외 처리(try/except)로 둘 수 있는 부분을,
     // Rust에서는 match로 성공/실패를 명시적으로 분기한다.
-    let (file, top_n, contains) = match parse_args() {
+    let args = match parse_args() {
         Ok(v) => v,
         Err(e) => {
             eprintln!("{e}");
             std::process::exit(1);
         }
     };
+    if args.explain {
+        print!("{}", explain::explain(&args));
+        return;
+    }
 
-    println!("[Top words]");
-    match top_words(&file, top_n) {
-        Ok(words) => {
-            for (word, count) in words {
-                println!("{word}: {count}");
-            }
+    if args.timing {
+        timing::enable();
+    }
+    numfmt::install(args.number_format);
+    let _ = THREADS.set(search::workers(args.threads));
+    counter::use_hasher(args.hasher);
+    counter::cap_vocabulary(args.max_vocab);
+    rng::fix_seed(args.seed);
+    if args.journal.is_some() {
+        journal::enable();
+    }
+    batch::set_fail_fast(args.fail_fast);
+    if let Some(script) = &args.script {
+        script::install(script.clone());
+    }
+
+    if args.follow {
+        if let Err(e) = run_follow(&args) {
+            eprintln!("{e}");
+            std::process::exit(1);
         }
-        Err(e) => {
+        return;
+    }
+
+    if args.jsonrpc {
+        if let Err(e) = jsonrpc::serve(&args) {
             eprintln!("{e}");
             std::process::exit(1);
         }
+        return;
     }
 
-    if let Some(keyword) = contains {
-        println!("\n[Filtered lines]");
-        match filter_lines(&file, &keyword) {
-            Ok(lines) => {
-                for line in lines {
-                    println!("{line}");
-                }
-            }
-            Err(e) => {
-                eprintln!("{e}");
-                std::process::exit(1);
+    if let Some(every) = args.every {
+        let task = |out: &mut String| run_journaled(&args, out);
+        if let Err(e) = schedule::run(every, args.output.as_deref(), task) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // 여러 파일을 읽을 때만 신호를 받아 읽은 데까지의 결과를 낸다.
+    if args.recursive || matches!(&args.source, Source::Files(files) if files.len() > 1) {
+        interrupt::install();
+    }
+    // 결과를 바로 println! 하지 않고 버퍼에 모은 뒤,
+    // 길이에 따라 pager로 보낼지 stdout에 바로 쓸지 마지막에 결정한다.
+    let mut out = String::new();
+    let result = run_journaled(&args, &mut out);
+    if let Err(e) = result {
+        batch::report();
+        eprintln!("{e}");
+        if args.summary {
+            trailer::report(started.elapsed());
+        }
+        std::process::exit(1);
+    }
+    let incomplete = interrupt::incomplete();
+    if let Some(skipped) = incomplete {
+        eprintln!("interrupted: results are incomplete ({skipped} input file(s) not read)");
+        // 표로 읽는 텍스트 출력에는 맨 앞에도 적는다. json 등은 형식을 깨지 않도록 stderr와 종료 코드로만 알린다.
+        if args.format == Format::Text && !args.plain {
+            out.insert_str(
+                0,
+                &format!("[Incomplete: interrupted, {skipped} input file(s) not read]\n"),
+            );
+        }
+    }
+
+    let start = Instant::now();
+    // parquet과 arrow는 실행 중에 이미 바이트로 썼다.
+    let written = match (&args.output, args.output_encoding) {
+        _ if args.format.is_columnar() => Ok(()),
+        (Some(path), Encoding::Utf8) => output::write(path, &out, args.append),
+        (Some(path), encoding) => {
+            output::write_bytes(path, &encoded(&out, encoding, !args.append), args.append)
+        }
+        (None, Encoding::Utf8) => {
+            match (args.page_size, args.page) {
+                (Some(size), None) => pager::more(&out, size),
+                _ => pager::emit(&out, args.pager),
             }
+            Ok(())
         }
+        // 다른 인코딩은 터미널이 아니라 다른 프로그램이 읽을 것이므로 pager를 거치지 않는다.
+        (None, encoding) => {
+            let mut stdout = std::io::stdout().lock();
+            let _ = stdout.write_all(&encoded(&out, encoding, true));
+            let _ = stdout.flush();
+            Ok(())
+        }
+    };
+    if let Err(e) = written {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+    timing::record("output", start, out.len(), None);
+    timing::report();
+    if args.resource_stats {
+        resources::report(started.elapsed());
+    }
+    if args.summary {
+        trailer::report(started.elapsed());
+    }
+    let evicted = counter::evicted();
+    if evicted > 0 {
+        eprintln!(
+            "note: --max-vocab evicted {evicted} low-count word(s); counts are approximate \
+             (a word seen again after eviction was counted from 0)"
+        );
+    }
+    if interrupt::interrupted() {
+        batch::report();
+        std::process::exit(interrupt::EXIT_CODE);
+    }
+    // 걸음 한도로 포기한 줄은 걸리지 않은 것으로 셌으므로 결과가 틀렸을 수 있다.
+    if let Err(e) = regex::exhausted() {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+    if drift::detected() || crosscheck::mismatched() {
+        std::process::exit(1);
+    }
+    // 결과를 다 낸 뒤에 검사해야 CI 로그에서 무엇 때문에 실패했는지 함께 볼 수 있다.
+    if !args.assertions.is_empty() && !check_assertions(&args) {
+        std::process::exit(1);
+    }
+    // 결과는 남은 입력으로 냈지만, 배치가 완전히 성공한 것은 아니라고 알린다.
+    if batch::report() {
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,66 @@
+// generate: 입력에서 단어 단위 마르코프 연쇄를 만들고 그럴듯한 가짜 글을 뽑는다.
+// 상태는 바로 앞 --order개의 단어이고, 다음 단어는 입력에서 그 상태 뒤에 나온 단어 중
+// 나온 횟수에 비례해 고른다. 상위 단어 표가 세는 빈도가 그대로 글의 "통계"가 되는 셈이다.
+// 구두점과 대소문자가 살아 있어야 글처럼 보이므로 토큰화하지 않은 공백 단위 단어를 쓴다.
+
+use std::collections::HashMap;
+
+use crate::rng::Rng;
+
+// 출력 한 줄의 너비(글자 수). 단어는 자르지 않는다.
+const WRAP: usize = 72;
+
+pub fn generate(text: &str, order: usize, count: usize, rng: &mut Rng) -> Result<String, String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() <= order {
+        return Err(format!(
+            "generate needs more than --order {order} words of input, got {}",
+            words.len()
+        ));
+    }
+    // 같은 다음 단어를 중복해서 넣어 두면 균등하게 하나를 골라도 빈도에 비례한다.
+    let mut followers: HashMap<&[&str], Vec<&str>> = HashMap::new();
+    for window in words.windows(order + 1) {
+        followers
+            .entry(&window[..order])
+            .or_default()
+            .push(window[order]);
+    }
+    let starts = words.len() - order;
+    let mut output: Vec<&str> = Vec::with_capacity(count);
+    while output.len() < count {
+        // 처음, 그리고 입력 끝처럼 뒤따르는 단어가 없는 상태에 이르면 임의의 자리에서 다시 시작한다.
+        let start = rng.below(starts);
+        output.extend(&words[start..start + order]);
+        while output.len() < count {
+            let state = &output[output.len() - order..];
+            let Some(next) = followers.get(state) else {
+                break;
+            };
+            output.push(next[rng.below(next.len())]);
+        }
+    }
+    output.truncate(count);
+    Ok(wrap(&output))
+}
+
+fn wrap(words: &[&str]) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for word in words {
+        let len = word.chars().count();
+        if width > 0 && width + 1 + len > WRAP {
+            out.push('\n');
+            width = 0;
+        } else if width > 0 {
+            out.push(' ');
+            width += 1;
+        }
+        out.push_str(word);
+        width += len;
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,192 @@
+// report의 메타데이터 사이드카: 입력 파일 옆의 FILE.meta.toml(또는 확장자를 바꾼 NAME.meta.toml).
+//   title = "On Liberty"
+//   author = "J. S. Mill"
+//   date = 1859-02-01          # 따옴표는 있어도 없어도 된다. 2020, 2020-05도 받는다
+//   tags = ["essay", "politics"]
+// 폴더 하나의 글 묶음을 --tag, --since/--until로 거르고 --group-by로 묶어 보는 작은 말뭉치로 쓴다.
+// 문법은 --config와 같은 TOML의 작은 부분이다(config.rs).
+
+use std::fs;
+use std::path::Path;
+
+use crate::config;
+use crate::json::{ToJson, Value};
+
+#[derive(Debug, Clone, Default)]
+pub struct Meta {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub date: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    // 태그마다 한 묶음. 태그가 여럿인 파일은 여러 묶음에 들어간다.
+    Tag,
+    Author,
+    // date의 앞 네 자리.
+    Year,
+}
+
+impl GroupBy {
+    pub fn parse(name: &str) -> Option<GroupBy> {
+        match name {
+            "tag" | "tags" => Some(GroupBy::Tag),
+            "author" => Some(GroupBy::Author),
+            "year" => Some(GroupBy::Year),
+            _ => None,
+        }
+    }
+
+    // 값이 없는 파일은 "(none)" 묶음이다.
+    pub fn keys(self, meta: Option<&Meta>) -> Vec<String> {
+        let keys = match (self, meta) {
+            (GroupBy::Tag, Some(meta)) => meta.tags.clone(),
+            (GroupBy::Author, Some(meta)) => meta.author.iter().cloned().collect(),
+            (GroupBy::Year, Some(meta)) => meta
+                .date
+                .iter()
+                .map(|d| d.chars().take(4).collect())
+                .collect(),
+            (_, None) => Vec::new(),
+        };
+        if keys.is_empty() {
+            vec!["(none)".to_string()]
+        } else {
+            keys
+        }
+    }
+}
+
+// --tag / --since / --until / --group-by.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    // 모두 달린 파일만 남긴다.
+    pub tags: Vec<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub group_by: Option<GroupBy>,
+}
+
+impl Selection {
+    pub fn is_active(&self) -> bool {
+        !self.tags.is_empty()
+            || self.since.is_some()
+            || self.until.is_some()
+            || self.group_by.is_some()
+    }
+
+    // 날짜 범위가 있으면 date가 없는 파일은 뺀다. 범위는 양끝을 포함하고, 짧은 경계(2020)는
+    // date의 같은 길이 앞부분과 비교하므로 --until 2020은 2020-12-31까지 들어간다.
+    pub fn accepts(&self, meta: Option<&Meta>) -> bool {
+        let tags = meta.map_or(&[][..], |m| m.tags.as_slice());
+        if !self.tags.iter().all(|tag| tags.contains(tag)) {
+            return false;
+        }
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let Some(date) = meta.and_then(|m| m.date.as_deref()) else {
+            return false;
+        };
+        let head = |bound: &str| date.get(..bound.len()).unwrap_or(date).to_string();
+        self.since
+            .as_deref()
+            .is_none_or(|since| head(since).as_str() >= since)
+            && self
+                .until
+                .as_deref()
+                .is_none_or(|until| head(until).as_str() <= until)
+    }
+}
+
+// 2020, 2020-05, 2020-05-17.
+pub fn valid_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('-').collect();
+    let widths = [4, 2, 2];
+    parts.len() <= 3
+        && parts
+            .iter()
+            .zip(widths)
+            .all(|(part, width)| part.len() == width && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+// 있는 사이드카 경로. stdin과 클립보드에는 없다.
+pub fn sidecar(input: &str) -> Option<String> {
+    let beside = format!("{input}.meta.toml");
+    if Path::new(&beside).is_file() {
+        return Some(beside);
+    }
+    let renamed = Path::new(input).with_extension("meta.toml");
+    renamed
+        .is_file()
+        .then(|| renamed.display().to_string())
+        .filter(|path| path != input)
+}
+
+// 사이드카가 없으면 None, 있는데 잘못 적었으면 오류다.
+pub fn load_for(input: &str) -> Result<Option<Meta>, String> {
+    let Some(path) = sidecar(input) else {
+        return Ok(None);
+    };
+    let text = fs::read_to_string(&path).map_err(|e| format!("failed to read {path}: {e}"))?;
+    parse(&text)
+        .map(Some)
+        .map_err(|(line, message)| format!("{path}:{line}: {message}"))
+}
+
+// 에러는 (줄 번호, 메시지). 모르는 키는 다른 도구가 쓰는 것일 수 있어 건너뛴다.
+fn parse(text: &str) -> Result<Meta, (usize, String)> {
+    let mut meta = Meta::default();
+    for (i, line) in text.lines().enumerate() {
+        let n = i + 1;
+        let line = config::strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err((n, format!("expected key = value, got {line}")));
+        };
+        let value = value.trim();
+        let text = || value.trim_matches('"').to_string();
+        match key.trim() {
+            "title" => meta.title = Some(text()),
+            "author" => meta.author = Some(text()),
+            "date" => {
+                let date = text();
+                // 1979-05-27T07:32:00 같은 TOML 날짜시각은 날짜 부분만 쓴다.
+                let day = date
+                    .split(['T', ' '])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                if !valid_date(&day) {
+                    return Err((n, format!("date must look like 2020-05-17, got {date}")));
+                }
+                meta.date = Some(day);
+            }
+            "tags" => {
+                meta.tags = config::array(value)
+                    .ok_or_else(|| (n, "tags must be an array of strings".to_string()))?;
+            }
+            _ => {}
+        }
+    }
+    Ok(meta)
+}
+
+impl ToJson for Meta {
+    fn to_json(&self) -> Value {
+        let text = |value: &Option<String>| value.clone().map_or(Value::Null, Value::String);
+        Value::Object(vec![
+            ("title".to_string(), text(&self.title)),
+            ("author".to_string(), text(&self.author)),
+            ("date".to_string(), text(&self.date)),
+            (
+                "tags".to_string(),
+                Value::Array(self.tags.iter().cloned().map(Value::String).collect()),
+            ),
+        ])
+    }
+}
@@ -0,0 +1,147 @@
+// daemon --metrics: Prometheus가 긁어 갈 수 있도록 HTTP /metrics에 계수기를 내놓는다.
+// Python이라면 prometheus_client.start_http_server()를 부를 자리다. 텍스트 노출 형식
+// (version 0.0.4)은 줄 단위라 직접 쓰기 쉽고, HTTP도 GET 한 가지만 답하면 된다.
+// timing.rs처럼 전역 기록부에 모아서 요청 처리 코드가 계수기를 들고 다니지 않게 한다.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+// 초 단위 히스토그램 경계. 질의는 대부분 밀리초 안쪽이고 reload만 길다.
+const BUCKETS: [f64; 10] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.5, 1.0,
+];
+
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static OPS: Mutex<Vec<OpStats>> = Mutex::new(Vec::new());
+
+struct OpStats {
+    op: &'static str,
+    ok: u64,
+    failed: u64,
+    // BUCKETS마다 그 경계 이하였던 요청 수(누적이 아니라 구간별, 출력할 때 누적한다).
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+}
+
+// 입력을 읽어 토큰화할 때마다(시작, reload) 부른다.
+pub fn add_bytes(n: usize) {
+    BYTES.fetch_add(n as u64, Ordering::Relaxed);
+}
+
+// op는 알려진 이름만 넘겨야 한다. 클라이언트가 보낸 문자열을 그대로 라벨로 쓰면
+// 시계열 수가 끝없이 늘어난다.
+pub fn record(op: &'static str, ok: bool, elapsed: Duration) {
+    let secs = elapsed.as_secs_f64();
+    let mut ops = OPS.lock().unwrap_or_else(|e| e.into_inner());
+    let index = match ops.iter().position(|s| s.op == op) {
+        Some(i) => i,
+        None => {
+            ops.push(OpStats {
+                op,
+                ok: 0,
+                failed: 0,
+                buckets: [0; BUCKETS.len()],
+                sum: 0.0,
+            });
+            ops.len() - 1
+        }
+    };
+    let stats = &mut ops[index];
+    if ok {
+        stats.ok += 1;
+    } else {
+        stats.failed += 1;
+    }
+    if let Some(b) = BUCKETS.iter().position(|&le| secs <= le) {
+        stats.buckets[b] += 1;
+    }
+    stats.sum += secs;
+}
+
+pub fn render() -> String {
+    let ops = OPS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut out = String::new();
+    out.push_str("# HELP wordtool_requests_total Daemon requests by op and outcome.\n");
+    out.push_str("# TYPE wordtool_requests_total counter\n");
+    for s in ops.iter() {
+        out.push_str(&format!(
+            "wordtool_requests_total{{op=\"{}\",status=\"ok\"}} {}\n",
+            s.op, s.ok
+        ));
+        out.push_str(&format!(
+            "wordtool_requests_total{{op=\"{}\",status=\"error\"}} {}\n",
+            s.op, s.failed
+        ));
+    }
+    out.push_str("# HELP wordtool_bytes_processed_total Input bytes read and tokenized.\n");
+    out.push_str("# TYPE wordtool_bytes_processed_total counter\n");
+    out.push_str(&format!(
+        "wordtool_bytes_processed_total {}\n",
+        BYTES.load(Ordering::Relaxed)
+    ));
+    out.push_str("# HELP wordtool_request_duration_seconds Time spent answering a request.\n");
+    out.push_str("# TYPE wordtool_request_duration_seconds histogram\n");
+    for s in ops.iter() {
+        let mut cumulative = 0;
+        for (le, n) in BUCKETS.iter().zip(s.buckets) {
+            cumulative += n;
+            out.push_str(&format!(
+                "wordtool_request_duration_seconds_bucket{{op=\"{}\",le=\"{le}\"}} {cumulative}\n",
+                s.op
+            ));
+        }
+        let count = s.ok + s.failed;
+        out.push_str(&format!(
+            "wordtool_request_duration_seconds_bucket{{op=\"{}\",le=\"+Inf\"}} {count}\n",
+            s.op
+        ));
+        out.push_str(&format!(
+            "wordtool_request_duration_seconds_sum{{op=\"{}\"}} {}\n",
+            s.op, s.sum
+        ));
+        out.push_str(&format!(
+            "wordtool_request_duration_seconds_count{{op=\"{}\"}} {count}\n",
+            s.op
+        ));
+    }
+    out
+}
+
+// 주소에 묶은 뒤 별도 스레드에서 답한다. 묶기에 실패하면(포트 사용 중 등) 바로 에러.
+pub fn serve(addr: &str) -> Result<(), String> {
+    let listener = TcpListener::bind(addr).map_err(|e| format!("--metrics {addr}: {e}"))?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = respond(stream);
+        }
+    });
+    Ok(())
+}
+
+// 요청 줄만 보고 헤더는 읽어 버린다. 연결은 응답마다 닫는다.
+fn respond(stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+    let mut parts = request.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", render()),
+        (Some("GET"), _) => ("404 Not Found", "not found\n".to_string()),
+        _ => ("405 Method Not Allowed", "method not allowed\n".to_string()),
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,126 @@
+// near-dupes: 문서 트리에서 다른 파일끼리 거의 같은 문단 짝을 찾는다(복사해 붙인 설명 찾기).
+// 문단(paragraphs.rs)마다 연속한 --shingle개 단어 묶음의 해시 집합을 만들고, 두 집합의
+// Jaccard 유사도가 --threshold 이상인 짝을 낸다. 모든 짝을 비교하지 않도록 MinHash 서명을
+// 띠(band)로 나눠 한 띠라도 같은 짝만 후보로 삼고(LSH), 후보는 실제 집합으로 다시 잰다.
+// 그래서 보고하는 유사도는 추정값이 아니라 정확한 값이다. 띠 32개 x 4행이면 유사도 0.5인 짝도
+// 열에 아홉은 후보가 되고, 0.7 이상은 거의 놓치지 않는다.
+// --shingle개보다 단어가 적은 문단(제목, 한 줄짜리)은 건너뛴다.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::paragraphs;
+use crate::rng::Rng;
+use crate::{normalize_words, Tokenizer};
+
+const BANDS: usize = 32;
+const ROWS: usize = 4;
+// 실행마다 같은 짝이 나오도록 해시 함수의 씨앗은 고정한다.
+const SEED: u64 = 0x6E65_6172_6475_7065;
+
+pub struct Passage {
+    pub document: usize,
+    // 문단 첫 줄(1부터).
+    pub line: usize,
+    pub text: String,
+    shingles: HashSet<u64>,
+}
+
+pub struct Pair {
+    pub a: usize,
+    pub b: usize,
+    pub similarity: f64,
+}
+
+// documents의 순서대로 문단을 모은다. Passage::document는 documents의 위치다.
+pub fn passages(documents: &[(String, String)], shingle: usize, tok: Tokenizer) -> Vec<Passage> {
+    let mut found = Vec::new();
+    for (document, (_, text)) in documents.iter().enumerate() {
+        for paragraph in paragraphs::split(text) {
+            let words = normalize_words(paragraph.text, tok);
+            if words.len() < shingle {
+                continue;
+            }
+            let shingles = words.windows(shingle).map(fnv).collect();
+            found.push(Passage {
+                document,
+                line: paragraph.line,
+                text: paragraph.text.trim().to_string(),
+                shingles,
+            });
+        }
+    }
+    found
+}
+
+// 다른 파일에 있는 문단 짝 중 유사도가 threshold 이상인 것. 유사도가 높은 순.
+pub fn pairs(passages: &[Passage], threshold: f64) -> Vec<Pair> {
+    let seeds: Vec<u64> = {
+        let mut rng = Rng::new(SEED);
+        (0..BANDS * ROWS).map(|_| rng.next_u64()).collect()
+    };
+    let signatures: Vec<Vec<u64>> = passages
+        .iter()
+        .map(|p| signature(&p.shingles, &seeds))
+        .collect();
+    let mut candidates = HashSet::new();
+    for band in 0..BANDS {
+        let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+        for (i, signature) in signatures.iter().enumerate() {
+            let key = &signature[band * ROWS..(band + 1) * ROWS];
+            buckets.entry(key).or_default().push(i);
+        }
+        for members in buckets.values() {
+            for (n, &a) in members.iter().enumerate() {
+                for &b in &members[n + 1..] {
+                    if passages[a].document != passages[b].document {
+                        candidates.insert((a, b));
+                    }
+                }
+            }
+        }
+    }
+    let mut found: Vec<Pair> = candidates
+        .into_iter()
+        .filter_map(|(a, b)| {
+            let similarity = jaccard(&passages[a].shingles, &passages[b].shingles);
+            (similarity >= threshold).then_some(Pair { a, b, similarity })
+        })
+        .collect();
+    found.sort_by(|x, y| {
+        y.similarity
+            .total_cmp(&x.similarity)
+            .then((x.a, x.b).cmp(&(y.a, y.b)))
+    });
+    found
+}
+
+// 해시 함수 i는 shingle 해시에 seeds[i]를 섞어 SplitMix64 한 번을 돌린 값이다.
+fn signature(shingles: &HashSet<u64>, seeds: &[u64]) -> Vec<u64> {
+    seeds
+        .iter()
+        .map(|&seed| {
+            shingles
+                .iter()
+                .map(|&h| Rng::new(h ^ seed).next_u64())
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let shared = a.intersection(b).count();
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+// 단어 사이에 0xFF를 넣어 ["ab", "c"]와 ["a", "bc"]가 같은 해시가 되지 않게 한다.
+fn fnv(words: &[String]) -> u64 {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for word in words {
+        for &byte in word.as_bytes().iter().chain([&0xFF]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01B3);
+        }
+    }
+    hash
+}
@@ -0,0 +1,39 @@
+// 문자 n-gram 집계(--char-ngrams N).
+// 문체 분석과 언어 식별에서 쓰는 방식대로 구두점까지 포함한 원문을 소문자로 바꾸고,
+// 연속된 공백은 하나로 줄여 단어 경계도 n-gram에 들어가게 한다.
+// 출력에서 보이도록 공백은 '_'로 적는다("the cat" → "the", "he_", "e_c", ...).
+
+use std::collections::VecDeque;
+
+use crate::counter::Counter;
+
+pub fn char_ngrams(text: &str, n: usize, unicode: bool) -> Counter {
+    let mut counts = Counter::new();
+    // 줄 경계도 공백 하나로 본다. 줄마다 따로 자르면 "end\nstart"의 경계 n-gram이 사라진다.
+    let mut window: VecDeque<char> = VecDeque::with_capacity(n);
+    let mut last_space = true;
+    for c in text.chars() {
+        let c = if c.is_whitespace() {
+            if last_space {
+                continue;
+            }
+            last_space = true;
+            '_'
+        } else {
+            last_space = false;
+            if unicode {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                c.to_ascii_lowercase()
+            }
+        };
+        if window.len() == n {
+            window.pop_front();
+        }
+        window.push_back(c);
+        if window.len() == n {
+            counts.add(&window.iter().collect::<String>(), 1);
+        }
+    }
+    counts
+}
@@ -0,0 +1,611 @@
+// 유니코드 정규화(NFC, NFKC). 같은 "é"라도 합친 글자 하나(U+00E9)와 e + 결합 악센트(U+0301)
+// 두 가지로 적을 수 있고, 한글도 완성형 "한"과 자모를 이어 쓴 "ᄒ ᅡ ᆫ"이 있다. macOS 파일 이름이나
+// 일부 편집기는 분해형을 쓰므로 정규화하지 않으면 같은 단어가 두 줄로 갈라져 세어진다.
+// Python이라면 unicodedata.normalize("NFC", text) 한 줄이다.
+// 전체 유니코드 표 대신 이 도구가 다루는 문자만 담았다: 라틴, 그리스, 키릴 문자의 악센트 조합과
+// 가나의 탁점, 한글 음절(표 없이 계산). NFKC는 여기에 합자(ﬁ), 전각 문자(Ａ), 위첨자(²),
+// 원문자(①) 같은 호환 문자를 평범한 글자로 푸는 단계가 더해진다.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+// 토큰화 전에 입력을 어떤 형태로 맞출지(--nfkc).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Normalization {
+    // 표준 합성(기본값). 보이는 모양이 같은 글자만 합친다.
+    #[default]
+    Nfc,
+    // 호환 분해 후 합성. "ﬁle"과 "file", "Ｒｕｓｔ"와 "Rust"도 같은 단어가 된다.
+    Nfkc,
+}
+
+// 한글 음절 = 0xAC00 + (초성 * 21 + 중성) * 28 + 종성.
+const S_BASE: u32 = 0xAC00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const S_COUNT: u32 = L_COUNT * V_COUNT * T_COUNT;
+
+pub fn normalize(text: &str, form: Normalization) -> Cow<'_, str> {
+    // 대부분의 입력은 이미 정규형이다. 건드릴 글자가 하나도 없으면 빌린 그대로 돌려준다.
+    if text.is_ascii() || !text.chars().any(|c| needs_work(c, form)) {
+        return Cow::Borrowed(text);
+    }
+    let mut chars = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        decompose(c, form, &mut chars);
+    }
+    reorder(&mut chars);
+    Cow::Owned(compose(&chars).into_iter().collect())
+}
+
+// "ậ" -> 'a'. 표준 분해가 기본 글자 하나와 악센트(U+0300..036F)뿐일 때만 기본 글자를 돌려준다.
+// 한글 음절이나 가나의 탁점처럼 지우면 다른 글자가 되는 것은 건드리지 않는다.
+pub(crate) fn base_letter(c: char) -> Option<char> {
+    let mut parts = Vec::new();
+    decompose(c, Normalization::Nfc, &mut parts);
+    match parts.split_first() {
+        Some((&base, marks))
+            if !marks.is_empty() && marks.iter().all(|m| (0x0300..=0x036F).contains(m)) =>
+        {
+            char::from_u32(base)
+        }
+        _ => None,
+    }
+}
+
+fn needs_work(c: char, form: Normalization) -> bool {
+    let code = c as u32;
+    combining_class(code) != 0
+        || (V_BASE..V_BASE + V_COUNT).contains(&code)
+        || (T_BASE + 1..T_BASE + T_COUNT).contains(&code)
+        || tables().singletons.contains_key(&code)
+        // 합친 글자의 기본 글자가 호환 문자일 수 있다("ϓ" = "ϒ" + 악센트, "ϒ" -> "Υ").
+        || (form == Normalization::Nfkc
+            && (compat(code).is_some() || tables().decompose.contains_key(&code)))
+}
+
+struct Tables {
+    compose: HashMap<(u32, u32), u32>,
+    decompose: HashMap<u32, (u32, u32)>,
+    singletons: HashMap<u32, u32>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(|| Tables {
+        compose: COMPOSE.iter().map(|&(a, b, c)| ((a, b), c)).collect(),
+        decompose: COMPOSE
+            .iter()
+            .chain(DECOMPOSE_ONLY)
+            .map(|&(a, b, c)| (c, (a, b)))
+            .collect(),
+        singletons: SINGLETONS.iter().copied().collect(),
+    })
+}
+
+fn combining_class(code: u32) -> u8 {
+    COMBINING_CLASS
+        .iter()
+        .find(|&&(lo, hi, _)| (lo..=hi).contains(&code))
+        .map_or(0, |&(_, _, class)| class)
+}
+
+fn compat(code: u32) -> Option<Cow<'static, str>> {
+    // 전각 ASCII는 U+FEE0만큼 떨어져 있다.
+    if (0xFF01..=0xFF5E).contains(&code) {
+        return char::from_u32(code - 0xFEE0).map(|c| Cow::Owned(c.to_string()));
+    }
+    COMPAT
+        .binary_search_by_key(&code, |&(c, _)| c)
+        .ok()
+        .map(|i| Cow::Borrowed(COMPAT[i].1))
+}
+
+// 합친 글자를 끝까지 분해한다("ǘ" -> u + ̈ + ́).
+fn decompose(c: char, form: Normalization, out: &mut Vec<u32>) {
+    let code = c as u32;
+    if (S_BASE..S_BASE + S_COUNT).contains(&code) {
+        let index = code - S_BASE;
+        out.push(L_BASE + index / (V_COUNT * T_COUNT));
+        out.push(V_BASE + index % (V_COUNT * T_COUNT) / T_COUNT);
+        if !index.is_multiple_of(T_COUNT) {
+            out.push(T_BASE + index % T_COUNT);
+        }
+        return;
+    }
+    if form == Normalization::Nfkc {
+        if let Some(text) = compat(code) {
+            for c in text.chars() {
+                decompose(c, form, out);
+            }
+            return;
+        }
+    }
+    let tables = tables();
+    if let Some(&single) = tables.singletons.get(&code) {
+        decompose(char::from_u32(single).unwrap_or(c), form, out);
+    } else if let Some(&(base, mark)) = tables.decompose.get(&code) {
+        decompose(char::from_u32(base).unwrap_or(c), form, out);
+        out.push(mark);
+    } else {
+        out.push(code);
+    }
+}
+
+// 기본 글자 뒤에 이어진 결합 부호는 정렬 순서대로 놓는다. 아래 점과 위 악센트를 어느 순서로
+// 적었든 같은 글자가 되어야 하기 때문이다. 같은 순위끼리는 원래 순서를 지킨다(안정 정렬).
+fn reorder(chars: &mut [u32]) {
+    let mut start = 0;
+    while start < chars.len() {
+        if combining_class(chars[start]) == 0 {
+            start += 1;
+            continue;
+        }
+        let end = chars[start..]
+            .iter()
+            .position(|&c| combining_class(c) == 0)
+            .map_or(chars.len(), |i| start + i);
+        chars[start..end].sort_by_key(|&c| combining_class(c));
+        start = end;
+    }
+}
+
+fn compose(chars: &[u32]) -> Vec<char> {
+    let tables = tables();
+    let mut out: Vec<u32> = Vec::with_capacity(chars.len());
+    // 마지막 기본 글자의 위치와, 그 뒤로 합쳐지지 않고 남은 부호 중 가장 높은 순위.
+    let mut starter: Option<usize> = None;
+    let mut last_class = 0;
+    for &c in chars {
+        let class = combining_class(c);
+        if let Some(s) = starter {
+            let base = out[s];
+            // 중간에 같은 순위 이상의 부호가 끼어 있으면 막힌 것이다.
+            let blocked = last_class != 0 && last_class >= class;
+            let blocked = blocked || (last_class == 0 && s + 1 != out.len());
+            if !blocked {
+                if let Some(composed) = compose_pair(tables, base, c) {
+                    out[s] = composed;
+                    continue;
+                }
+            }
+        }
+        if class == 0 {
+            starter = Some(out.len());
+            last_class = 0;
+        } else {
+            last_class = class;
+        }
+        out.push(c);
+    }
+    out.into_iter().filter_map(char::from_u32).collect()
+}
+
+fn compose_pair(tables: &Tables, a: u32, b: u32) -> Option<u32> {
+    // 초성 + 중성 -> 받침 없는 음절, 받침 없는 음절 + 종성 -> 받침 있는 음절.
+    if (L_BASE..L_BASE + L_COUNT).contains(&a) && (V_BASE..V_BASE + V_COUNT).contains(&b) {
+        return Some(S_BASE + ((a - L_BASE) * V_COUNT + (b - V_BASE)) * T_COUNT);
+    }
+    if (S_BASE..S_BASE + S_COUNT).contains(&a)
+        && (a - S_BASE).is_multiple_of(T_COUNT)
+        && (T_BASE + 1..T_BASE + T_COUNT).contains(&b)
+    {
+        return Some(a + (b - T_BASE));
+    }
+    tables.compose.get(&(a, b)).copied()
+}
+
+// (기본 글자, 결합 부호, 합친 글자). (기본 글자, 결합 부호) 순으로 정렬되어 있다.
+#[rustfmt::skip]
+const COMPOSE: &[(u32, u32, u32)] = &[
+    (0x0041, 0x0300, 0x00C0), (0x0041, 0x0301, 0x00C1), (0x0041, 0x0302, 0x00C2), (0x0041, 0x0303, 0x00C3),
+    (0x0041, 0x0304, 0x0100), (0x0041, 0x0306, 0x0102), (0x0041, 0x0307, 0x0226), (0x0041, 0x0308, 0x00C4),
+    (0x0041, 0x0309, 0x1EA2), (0x0041, 0x030A, 0x00C5), (0x0041, 0x030C, 0x01CD), (0x0041, 0x030F, 0x0200),
+    (0x0041, 0x0311, 0x0202), (0x0041, 0x0323, 0x1EA0), (0x0041, 0x0325, 0x1E00), (0x0041, 0x0328, 0x0104),
+    (0x0042, 0x0307, 0x1E02), (0x0042, 0x0323, 0x1E04), (0x0042, 0x0331, 0x1E06), (0x0043, 0x0301, 0x0106),
+    (0x0043, 0x0302, 0x0108), (0x0043, 0x0307, 0x010A), (0x0043, 0x030C, 0x010C), (0x0043, 0x0327, 0x00C7),
+    (0x0044, 0x0307, 0x1E0A), (0x0044, 0x030C, 0x010E), (0x0044, 0x0323, 0x1E0C), (0x0044, 0x0327, 0x1E10),
+    (0x0044, 0x032D, 0x1E12), (0x0044, 0x0331, 0x1E0E), (0x0045, 0x0300, 0x00C8), (0x0045, 0x0301, 0x00C9),
+    (0x0045, 0x0302, 0x00CA), (0x0045, 0x0303, 0x1EBC), (0x0045, 0x0304, 0x0112), (0x0045, 0x0306, 0x0114),
+    (0x0045, 0x0307, 0x0116), (0x0045, 0x0308, 0x00CB), (0x0045, 0x0309, 0x1EBA), (0x0045, 0x030C, 0x011A),
+    (0x0045, 0x030F, 0x0204), (0x0045, 0x0311, 0x0206), (0x0045, 0x0323, 0x1EB8), (0x0045, 0x0327, 0x0228),
+    (0x0045, 0x0328, 0x0118), (0x0045, 0x032D, 0x1E18), (0x0045, 0x0330, 0x1E1A), (0x0046, 0x0307, 0x1E1E),
+    (0x0047, 0x0301, 0x01F4), (0x0047, 0x0302, 0x011C), (0x0047, 0x0304, 0x1E20), (0x0047, 0x0306, 0x011E),
+    (0x0047, 0x0307, 0x0120), (0x0047, 0x030C, 0x01E6), (0x0047, 0x0327, 0x0122), (0x0048, 0x0302, 0x0124),
+    (0x0048, 0x0307, 0x1E22), (0x0048, 0x0308, 0x1E26), (0x0048, 0x030C, 0x021E), (0x0048, 0x0323, 0x1E24),
+    (0x0048, 0x0327, 0x1E28), (0x0048, 0x032E, 0x1E2A), (0x0049, 0x0300, 0x00CC), (0x0049, 0x0301, 0x00CD),
+    (0x0049, 0x0302, 0x00CE), (0x0049, 0x0303, 0x0128), (0x0049, 0x0304, 0x012A), (0x0049, 0x0306, 0x012C),
+    (0x0049, 0x0307, 0x0130), (0x0049, 0x0308, 0x00CF), (0x0049, 0x0309, 0x1EC8), (0x0049, 0x030C, 0x01CF),
+    (0x0049, 0x030F, 0x0208), (0x0049, 0x0311, 0x020A), (0x0049, 0x0323, 0x1ECA), (0x0049, 0x0328, 0x012E),
+    (0x0049, 0x0330, 0x1E2C), (0x004A, 0x0302, 0x0134), (0x004B, 0x0301, 0x1E30), (0x004B, 0x030C, 0x01E8),
+    (0x004B, 0x0323, 0x1E32), (0x004B, 0x0327, 0x0136), (0x004B, 0x0331, 0x1E34), (0x004C, 0x0301, 0x0139),
+    (0x004C, 0x030C, 0x013D), (0x004C, 0x0323, 0x1E36), (0x004C, 0x0327, 0x013B), (0x004C, 0x032D, 0x1E3C),
+    (0x004C, 0x0331, 0x1E3A), (0x004D, 0x0301, 0x1E3E), (0x004D, 0x0307, 0x1E40), (0x004D, 0x0323, 0x1E42),
+    (0x004E, 0x0300, 0x01F8), (0x004E, 0x0301, 0x0143), (0x004E, 0x0303, 0x00D1), (0x004E, 0x0307, 0x1E44),
+    (0x004E, 0x030C, 0x0147), (0x004E, 0x0323, 0x1E46), (0x004E, 0x0327, 0x0145), (0x004E, 0x032D, 0x1E4A),
+    (0x004E, 0x0331, 0x1E48), (0x004F, 0x0300, 0x00D2), (0x004F, 0x0301, 0x00D3), (0x004F, 0x0302, 0x00D4),
+    (0x004F, 0x0303, 0x00D5), (0x004F, 0x0304, 0x014C), (0x004F, 0x0306, 0x014E), (0x004F, 0x0307, 0x022E),
+    (0x004F, 0x0308, 0x00D6), (0x004F, 0x0309, 0x1ECE), (0x004F, 0x030B, 0x0150), (0x004F, 0x030C, 0x01D1),
+    (0x004F, 0x030F, 0x020C), (0x004F, 0x0311, 0x020E), (0x004F, 0x031B, 0x01A0), (0x004F, 0x0323, 0x1ECC),
+    (0x004F, 0x0328, 0x01EA), (0x0050, 0x0301, 0x1E54), (0x0050, 0x0307, 0x1E56), (0x0052, 0x0301, 0x0154),
+    (0x0052, 0x0307, 0x1E58), (0x0052, 0x030C, 0x0158), (0x0052, 0x030F, 0x0210), (0x0052, 0x0311, 0x0212),
+    (0x0052, 0x0323, 0x1E5A), (0x0052, 0x0327, 0x0156), (0x0052, 0x0331, 0x1E5E), (0x0053, 0x0301, 0x015A),
+    (0x0053, 0x0302, 0x015C), (0x0053, 0x0307, 0x1E60), (0x0053, 0x030C, 0x0160), (0x0053, 0x0323, 0x1E62),
+    (0x0053, 0x0326, 0x0218), (0x0053, 0x0327, 0x015E), (0x0054, 0x0307, 0x1E6A), (0x0054, 0x030C, 0x0164),
+    (0x0054, 0x0323, 0x1E6C), (0x0054, 0x0326, 0x021A), (0x0054, 0x0327, 0x0162), (0x0054, 0x032D, 0x1E70),
+    (0x0054, 0x0331, 0x1E6E), (0x0055, 0x0300, 0x00D9), (0x0055, 0x0301, 0x00DA), (0x0055, 0x0302, 0x00DB),
+    (0x0055, 0x0303, 0x0168), (0x0055, 0x0304, 0x016A), (0x0055, 0x0306, 0x016C), (0x0055, 0x0308, 0x00DC),
+    (0x0055, 0x0309, 0x1EE6), (0x0055, 0x030A, 0x016E), (0x0055, 0x030B, 0x0170), (0x0055, 0x030C, 0x01D3),
+    (0x0055, 0x030F, 0x0214), (0x0055, 0x0311, 0x0216), (0x0055, 0x031B, 0x01AF), (0x0055, 0x0323, 0x1EE4),
+    (0x0055, 0x0324, 0x1E72), (0x0055, 0x0328, 0x0172), (0x0055, 0x032D, 0x1E76), (0x0055, 0x0330, 0x1E74),
+    (0x0056, 0x0303, 0x1E7C), (0x0056, 0x0323, 0x1E7E), (0x0057, 0x0300, 0x1E80), (0x0057, 0x0301, 0x1E82),
+    (0x0057, 0x0302, 0x0174), (0x0057, 0x0307, 0x1E86), (0x0057, 0x0308, 0x1E84), (0x0057, 0x0323, 0x1E88),
+    (0x0058, 0x0307, 0x1E8A), (0x0058, 0x0308, 0x1E8C), (0x0059, 0x0300, 0x1EF2), (0x0059, 0x0301, 0x00DD),
+    (0x0059, 0x0302, 0x0176), (0x0059, 0x0303, 0x1EF8), (0x0059, 0x0304, 0x0232), (0x0059, 0x0307, 0x1E8E),
+    (0x0059, 0x0308, 0x0178), (0x0059, 0x0309, 0x1EF6), (0x0059, 0x0323, 0x1EF4), (0x005A, 0x0301, 0x0179),
+    (0x005A, 0x0302, 0x1E90), (0x005A, 0x0307, 0x017B), (0x005A, 0x030C, 0x017D), (0x005A, 0x0323, 0x1E92),
+    (0x005A, 0x0331, 0x1E94), (0x0061, 0x0300, 0x00E0), (0x0061, 0x0301, 0x00E1), (0x0061, 0x0302, 0x00E2),
+    (0x0061, 0x0303, 0x00E3), (0x0061, 0x0304, 0x0101), (0x0061, 0x0306, 0x0103), (0x0061, 0x0307, 0x0227),
+    (0x0061, 0x0308, 0x00E4), (0x0061, 0x0309, 0x1EA3), (0x0061, 0x030A, 0x00E5), (0x0061, 0x030C, 0x01CE),
+    (0x0061, 0x030F, 0x0201), (0x0061, 0x0311, 0x0203), (0x0061, 0x0323, 0x1EA1), (0x0061, 0x0325, 0x1E01),
+    (0x0061, 0x0328, 0x0105), (0x0062, 0x0307, 0x1E03), (0x0062, 0x0323, 0x1E05), (0x0062, 0x0331, 0x1E07),
+    (0x0063, 0x0301, 0x0107), (0x0063, 0x0302, 0x0109), (0x0063, 0x0307, 0x010B), (0x0063, 0x030C, 0x010D),
+    (0x0063, 0x0327, 0x00E7), (0x0064, 0x0307, 0x1E0B), (0x0064, 0x030C, 0x010F), (0x0064, 0x0323, 0x1E0D),
+    (0x0064, 0x0327, 0x1E11), (0x0064, 0x032D, 0x1E13), (0x0064, 0x0331, 0x1E0F), (0x0065, 0x0300, 0x00E8),
+    (0x0065, 0x0301, 0x00E9), (0x0065, 0x0302, 0x00EA), (0x0065, 0x0303, 0x1EBD), (0x0065, 0x0304, 0x0113),
+    (0x0065, 0x0306, 0x0115), (0x0065, 0x0307, 0x0117), (0x0065, 0x0308, 0x00EB), (0x0065, 0x0309, 0x1EBB),
+    (0x0065, 0x030C, 0x011B), (0x0065, 0x030F, 0x0205), (0x0065, 0x0311, 0x0207), (0x0065, 0x0323, 0x1EB9),
+    (0x0065, 0x0327, 0x0229), (0x0065, 0x0328, 0x0119), (0x0065, 0x032D, 0x1E19), (0x0065, 0x0330, 0x1E1B),
+    (0x0066, 0x0307, 0x1E1F), (0x0067, 0x0301, 0x01F5), (0x0067, 0x0302, 0x011D), (0x0067, 0x0304, 0x1E21),
+    (0x0067, 0x0306, 0x011F), (0x0067, 0x0307, 0x0121), (0x0067, 0x030C, 0x01E7), (0x0067, 0x0327, 0x0123),
+    (0x0068, 0x0302, 0x0125), (0x0068, 0x0307, 0x1E23), (0x0068, 0x0308, 0x1E27), (0x0068, 0x030C, 0x021F),
+    (0x0068, 0x0323, 0x1E25), (0x0068, 0x0327, 0x1E29), (0x0068, 0x032E, 0x1E2B), (0x0068, 0x0331, 0x1E96),
+    (0x0069, 0x0300, 0x00EC), (0x0069, 0x0301, 0x00ED), (0x0069, 0x0302, 0x00EE), (0x0069, 0x0303, 0x0129),
+    (0x0069, 0x0304, 0x012B), (0x0069, 0x0306, 0x012D), (0x0069, 0x0308, 0x00EF), (0x0069, 0x0309, 0x1EC9),
+    (0x0069, 0x030C, 0x01D0), (0x0069, 0x030F, 0x0209), (0x0069, 0x0311, 0x020B), (0x0069, 0x0323, 0x1ECB),
+    (0x0069, 0x0328, 0x012F), (0x0069, 0x0330, 0x1E2D), (0x006A, 0x0302, 0x0135), (0x006A, 0x030C, 0x01F0),
+    (0x006B, 0x0301, 0x1E31), (0x006B, 0x030C, 0x01E9), (0x006B, 0x0323, 0x1E33), (0x006B, 0x0327, 0x0137),
+    (0x006B, 0x0331, 0x1E35), (0x006C, 0x0301, 0x013A), (0x006C, 0x030C, 0x013E), (0x006C, 0x0323, 0x1E37),
+    (0x006C, 0x0327, 0x013C), (0x006C, 0x032D, 0x1E3D), (0x006C, 0x0331, 0x1E3B), (0x006D, 0x0301, 0x1E3F),
+    (0x006D, 0x0307, 0x1E41), (0x006D, 0x0323, 0x1E43), (0x006E, 0x0300, 0x01F9), (0x006E, 0x0301, 0x0144),
+    (0x006E, 0x0303, 0x00F1), (0x006E, 0x0307, 0x1E45), (0x006E, 0x030C, 0x0148), (0x006E, 0x0323, 0x1E47),
+    (0x006E, 0x0327, 0x0146), (0x006E, 0x032D, 0x1E4B), (0x006E, 0x0331, 0x1E49), (0x006F, 0x0300, 0x00F2),
+    (0x006F, 0x0301, 0x00F3), (0x006F, 0x0302, 0x00F4), (0x006F, 0x0303, 0x00F5), (0x006F, 0x0304, 0x014D),
+    (0x006F, 0x0306, 0x014F), (0x006F, 0x0307, 0x022F), (0x006F, 0x0308, 0x00F6), (0x006F, 0x0309, 0x1ECF),
+    (0x006F, 0x030B, 0x0151), (0x006F, 0x030C, 0x01D2), (0x006F, 0x030F, 0x020D), (0x006F, 0x0311, 0x020F),
+    (0x006F, 0x031B, 0x01A1), (0x006F, 0x0323, 0x1ECD), (0x006F, 0x0328, 0x01EB), (0x0070, 0x0301, 0x1E55),
+    (0x0070, 0x0307, 0x1E57), (0x0072, 0x0301, 0x0155), (0x0072, 0x0307, 0x1E59), (0x0072, 0x030C, 0x0159),
+    (0x0072, 0x030F, 0x0211), (0x0072, 0x0311, 0x0213), (0x0072, 0x0323, 0x1E5B), (0x0072, 0x0327, 0x0157),
+    (0x0072, 0x0331, 0x1E5F), (0x0073, 0x0301, 0x015B), (0x0073, 0x0302, 0x015D), (0x0073, 0x0307, 0x1E61),
+    (0x0073, 0x030C, 0x0161), (0x0073, 0x0323, 0x1E63), (0x0073, 0x0326, 0x0219), (0x0073, 0x0327, 0x015F),
+    (0x0074, 0x0307, 0x1E6B), (0x0074, 0x0308, 0x1E97), (0x0074, 0x030C, 0x0165), (0x0074, 0x0323, 0x1E6D),
+    (0x0074, 0x0326, 0x021B), (0x0074, 0x0327, 0x0163), (0x0074, 0x032D, 0x1E71), (0x0074, 0x0331, 0x1E6F),
+    (0x0075, 0x0300, 0x00F9), (0x0075, 0x0301, 0x00FA), (0x0075, 0x0302, 0x00FB), (0x0075, 0x0303, 0x0169),
+    (0x0075, 0x0304, 0x016B), (0x0075, 0x0306, 0x016D), (0x0075, 0x0308, 0x00FC), (0x0075, 0x0309, 0x1EE7),
+    (0x0075, 0x030A, 0x016F), (0x0075, 0x030B, 0x0171), (0x0075, 0x030C, 0x01D4), (0x0075, 0x030F, 0x0215),
+    (0x0075, 0x0311, 0x0217), (0x0075, 0x031B, 0x01B0), (0x0075, 0x0323, 0x1EE5), (0x0075, 0x0324, 0x1E73),
+    (0x0075, 0x0328, 0x0173), (0x0075, 0x032D, 0x1E77), (0x0075, 0x0330, 0x1E75), (0x0076, 0x0303, 0x1E7D),
+    (0x0076, 0x0323, 0x1E7F), (0x0077, 0x0300, 0x1E81), (0x0077, 0x0301, 0x1E83), (0x0077, 0x0302, 0x0175),
+    (0x0077, 0x0307, 0x1E87), (0x0077, 0x0308, 0x1E85), (0x0077, 0x030A, 0x1E98), (0x0077, 0x0323, 0x1E89),
+    (0x0078, 0x0307, 0x1E8B), (0x0078, 0x0308, 0x1E8D), (0x0079, 0x0300, 0x1EF3), (0x0079, 0x0301, 0x00FD),
+    (0x0079, 0x0302, 0x0177), (0x0079, 0x0303, 0x1EF9), (0x0079, 0x0304, 0x0233), (0x0079, 0x0307, 0x1E8F),
+    (0x0079, 0x0308, 0x00FF), (0x0079, 0x0309, 0x1EF7), (0x0079, 0x030A, 0x1E99), (0x0079, 0x0323, 0x1EF5),
+    (0x007A, 0x0301, 0x017A), (0x007A, 0x0302, 0x1E91), (0x007A, 0x0307, 0x017C), (0x007A, 0x030C, 0x017E),
+    (0x007A, 0x0323, 0x1E93), (0x007A, 0x0331, 0x1E95), (0x00A8, 0x0300, 0x1FED), (0x00A8, 0x0301, 0x0385),
+    (0x00A8, 0x0342, 0x1FC1), (0x00C2, 0x0300, 0x1EA6), (0x00C2, 0x0301, 0x1EA4), (0x00C2, 0x0303, 0x1EAA),
+    (0x00C2, 0x0309, 0x1EA8), (0x00C4, 0x0304, 0x01DE), (0x00C5, 0x0301, 0x01FA), (0x00C6, 0x0301, 0x01FC),
+    (0x00C6, 0x0304, 0x01E2), (0x00C7, 0x0301, 0x1E08), (0x00CA, 0x0300, 0x1EC0), (0x00CA, 0x0301, 0x1EBE),
+    (0x00CA, 0x0303, 0x1EC4), (0x00CA, 0x0309, 0x1EC2), (0x00CF, 0x0301, 0x1E2E), (0x00D4, 0x0300, 0x1ED2),
+    (0x00D4, 0x0301, 0x1ED0), (0x00D4, 0x0303, 0x1ED6), (0x00D4, 0x0309, 0x1ED4), (0x00D5, 0x0301, 0x1E4C),
+    (0x00D5, 0x0304, 0x022C), (0x00D5, 0x0308, 0x1E4E), (0x00D6, 0x0304, 0x022A), (0x00D8, 0x0301, 0x01FE),
+    (0x00DC, 0x0300, 0x01DB), (0x00DC, 0x0301, 0x01D7), (0x00DC, 0x0304, 0x01D5), (0x00DC, 0x030C, 0x01D9),
+    (0x00E2, 0x0300, 0x1EA7), (0x00E2, 0x0301, 0x1EA5), (0x00E2, 0x0303, 0x1EAB), (0x00E2, 0x0309, 0x1EA9),
+    (0x00E4, 0x0304, 0x01DF), (0x00E5, 0x0301, 0x01FB), (0x00E6, 0x0301, 0x01FD), (0x00E6, 0x0304, 0x01E3),
+    (0x00E7, 0x0301, 0x1E09), (0x00EA, 0x0300, 0x1EC1), (0x00EA, 0x0301, 0x1EBF), (0x00EA, 0x0303, 0x1EC5),
+    (0x00EA, 0x0309, 0x1EC3), (0x00EF, 0x0301, 0x1E2F), (0x00F4, 0x0300, 0x1ED3), (0x00F4, 0x0301, 0x1ED1),
+    (0x00F4, 0x0303, 0x1ED7), (0x00F4, 0x0309, 0x1ED5), (0x00F5, 0x0301, 0x1E4D), (0x00F5, 0x0304, 0x022D),
+    (0x00F5, 0x0308, 0x1E4F), (0x00F6, 0x0304, 0x022B), (0x00F8, 0x0301, 0x01FF), (0x00FC, 0x0300, 0x01DC),
+    (0x00FC, 0x0301, 0x01D8), (0x00FC, 0x0304, 0x01D6), (0x00FC, 0x030C, 0x01DA), (0x0102, 0x0300, 0x1EB0),
+    (0x0102, 0x0301, 0x1EAE), (0x0102, 0x0303, 0x1EB4), (0x0102, 0x0309, 0x1EB2), (0x0103, 0x0300, 0x1EB1),
+    (0x0103, 0x0301, 0x1EAF), (0x0103, 0x0303, 0x1EB5), (0x0103, 0x0309, 0x1EB3), (0x0112, 0x0300, 0x1E14),
+    (0x0112, 0x0301, 0x1E16), (0x0113, 0x0300, 0x1E15), (0x0113, 0x0301, 0x1E17), (0x014C, 0x0300, 0x1E50),
+    (0x014C, 0x0301, 0x1E52), (0x014D, 0x0300, 0x1E51), (0x014D, 0x0301, 0x1E53), (0x015A, 0x0307, 0x1E64),
+    (0x015B, 0x0307, 0x1E65), (0x0160, 0x0307, 0x1E66), (0x0161, 0x0307, 0x1E67), (0x0168, 0x0301, 0x1E78),
+    (0x0169, 0x0301, 0x1E79), (0x016A, 0x0308, 0x1E7A), (0x016B, 0x0308, 0x1E7B), (0x017F, 0x0307, 0x1E9B),
+    (0x01A0, 0x0300, 0x1EDC), (0x01A0, 0x0301, 0x1EDA), (0x01A0, 0x0303, 0x1EE0), (0x01A0, 0x0309, 0x1EDE),
+    (0x01A0, 0x0323, 0x1EE2), (0x01A1, 0x0300, 0x1EDD), (0x01A1, 0x0301, 0x1EDB), (0x01A1, 0x0303, 0x1EE1),
+    (0x01A1, 0x0309, 0x1EDF), (0x01A1, 0x0323, 0x1EE3), (0x01AF, 0x0300, 0x1EEA), (0x01AF, 0x0301, 0x1EE8),
+    (0x01AF, 0x0303, 0x1EEE), (0x01AF, 0x0309, 0x1EEC), (0x01AF, 0x0323, 0x1EF0), (0x01B0, 0x0300, 0x1EEB),
+    (0x01B0, 0x0301, 0x1EE9), (0x01B0, 0x0303, 0x1EEF), (0x01B0, 0x0309, 0x1EED), (0x01B0, 0x0323, 0x1EF1),
+    (0x01B7, 0x030C, 0x01EE), (0x01EA, 0x0304, 0x01EC), (0x01EB, 0x0304, 0x01ED), (0x0226, 0x0304, 0x01E0),
+    (0x0227, 0x0304, 0x01E1), (0x0228, 0x0306, 0x1E1C), (0x0229, 0x0306, 0x1E1D), (0x022E, 0x0304, 0x0230),
+    (0x022F, 0x0304, 0x0231), (0x0292, 0x030C, 0x01EF), (0x0391, 0x0300, 0x1FBA), (0x0391, 0x0301, 0x0386),
+    (0x0391, 0x0304, 0x1FB9), (0x0391, 0x0306, 0x1FB8), (0x0391, 0x0313, 0x1F08), (0x0391, 0x0314, 0x1F09),
+    (0x0391, 0x0345, 0x1FBC), (0x0395, 0x0300, 0x1FC8), (0x0395, 0x0301, 0x0388), (0x0395, 0x0313, 0x1F18),
+    (0x0395, 0x0314, 0x1F19), (0x0397, 0x0300, 0x1FCA), (0x0397, 0x0301, 0x0389), (0x0397, 0x0313, 0x1F28),
+    (0x0397, 0x0314, 0x1F29), (0x0397, 0x0345, 0x1FCC), (0x0399, 0x0300, 0x1FDA), (0x0399, 0x0301, 0x038A),
+    (0x0399, 0x0304, 0x1FD9), (0x0399, 0x0306, 0x1FD8), (0x0399, 0x0308, 0x03AA), (0x0399, 0x0313, 0x1F38),
+    (0x0399, 0x0314, 0x1F39), (0x039F, 0x0300, 0x1FF8), (0x039F, 0x0301, 0x038C), (0x039F, 0x0313, 0x1F48),
+    (0x039F, 0x0314, 0x1F49), (0x03A1, 0x0314, 0x1FEC), (0x03A5, 0x0300, 0x1FEA), (0x03A5, 0x0301, 0x038E),
+    (0x03A5, 0x0304, 0x1FE9), (0x03A5, 0x0306, 0x1FE8), (0x03A5, 0x0308, 0x03AB), (0x03A5, 0x0314, 0x1F59),
+    (0x03A9, 0x0300, 0x1FFA), (0x03A9, 0x0301, 0x038F), (0x03A9, 0x0313, 0x1F68), (0x03A9, 0x0314, 0x1F69),
+    (0x03A9, 0x0345, 0x1FFC), (0x03AC, 0x0345, 0x1FB4), (0x03AE, 0x0345, 0x1FC4), (0x03B1, 0x0300, 0x1F70),
+    (0x03B1, 0x0301, 0x03AC), (0x03B1, 0x0304, 0x1FB1), (0x03B1, 0x0306, 0x1FB0), (0x03B1, 0x0313, 0x1F00),
+    (0x03B1, 0x0314, 0x1F01), (0x03B1, 0x0342, 0x1FB6), (0x03B1, 0x0345, 0x1FB3), (0x03B5, 0x0300, 0x1F72),
+    (0x03B5, 0x0301, 0x03AD), (0x03B5, 0x0313, 0x1F10), (0x03B5, 0x0314, 0x1F11), (0x03B7, 0x0300, 0x1F74),
+    (0x03B7, 0x0301, 0x03AE), (0x03B7, 0x0313, 0x1F20), (0x03B7, 0x0314, 0x1F21), (0x03B7, 0x0342, 0x1FC6),
+    (0x03B7, 0x0345, 0x1FC3), (0x03B9, 0x0300, 0x1F76), (0x03B9, 0x0301, 0x03AF), (0x03B9, 0x0304, 0x1FD1),
+    (0x03B9, 0x0306, 0x1FD0), (0x03B9, 0x0308, 0x03CA), (0x03B9, 0x0313, 0x1F30), (0x03B9, 0x0314, 0x1F31),
+    (0x03B9, 0x0342, 0x1FD6), (0x03BF, 0x0300, 0x1F78), (0x03BF, 0x0301, 0x03CC), (0x03BF, 0x0313, 0x1F40),
+    (0x03BF, 0x0314, 0x1F41), (0x03C1, 0x0313, 0x1FE4), (0x03C1, 0x0314, 0x1FE5), (0x03C5, 0x0300, 0x1F7A),
+    (0x03C5, 0x0301, 0x03CD), (0x03C5, 0x0304, 0x1FE1), (0x03C5, 0x0306, 0x1FE0), (0x03C5, 0x0308, 0x03CB),
+    (0x03C5, 0x0313, 0x1F50), (0x03C5, 0x0314, 0x1F51), (0x03C5, 0x0342, 0x1FE6), (0x03C9, 0x0300, 0x1F7C),
+    (0x03C9, 0x0301, 0x03CE), (0x03C9, 0x0313, 0x1F60), (0x03C9, 0x0314, 0x1F61), (0x03C9, 0x0342, 0x1FF6),
+    (0x03C9, 0x0345, 0x1FF3), (0x03CA, 0x0300, 0x1FD2), (0x03CA, 0x0301, 0x0390), (0x03CA, 0x0342, 0x1FD7),
+    (0x03CB, 0x0300, 0x1FE2), (0x03CB, 0x0301, 0x03B0), (0x03CB, 0x0342, 0x1FE7), (0x03CE, 0x0345, 0x1FF4),
+    (0x03D2, 0x0301, 0x03D3), (0x03D2, 0x0308, 0x03D4), (0x0406, 0x0308, 0x0407), (0x0410, 0x0306, 0x04D0),
+    (0x0410, 0x0308, 0x04D2), (0x0413, 0x0301, 0x0403), (0x0415, 0x0300, 0x0400), (0x0415, 0x0306, 0x04D6),
+    (0x0415, 0x0308, 0x0401), (0x0416, 0x0306, 0x04C1), (0x0416, 0x0308, 0x04DC), (0x0417, 0x0308, 0x04DE),
+    (0x0418, 0x0300, 0x040D), (0x0418, 0x0304, 0x04E2), (0x0418, 0x0306, 0x0419), (0x0418, 0x0308, 0x04E4),
+    (0x041A, 0x0301, 0x040C), (0x041E, 0x0308, 0x04E6), (0x0423, 0x0304, 0x04EE), (0x0423, 0x0306, 0x040E),
+    (0x0423, 0x0308, 0x04F0), (0x0423, 0x030B, 0x04F2), (0x0427, 0x0308, 0x04F4), (0x042B, 0x0308, 0x04F8),
+    (0x042D, 0x0308, 0x04EC), (0x0430, 0x0306, 0x04D1), (0x0430, 0x0308, 0x04D3), (0x0433, 0x0301, 0x0453),
+    (0x0435, 0x0300, 0x0450), (0x0435, 0x0306, 0x04D7), (0x0435, 0x0308, 0x0451), (0x0436, 0x0306, 0x04C2),
+    (0x0436, 0x0308, 0x04DD), (0x0437, 0x0308, 0x04DF), (0x0438, 0x0300, 0x045D), (0x0438, 0x0304, 0x04E3),
+    (0x0438, 0x0306, 0x0439), (0x0438, 0x0308, 0x04E5), (0x043A, 0x0301, 0x045C), (0x043E, 0x0308, 0x04E7),
+    (0x0443, 0x0304, 0x04EF), (0x0443, 0x0306, 0x045E), (0x0443, 0x0308, 0x04F1), (0x0443, 0x030B, 0x04F3),
+    (0x0447, 0x0308, 0x04F5), (0x044B, 0x0308, 0x04F9), (0x044D, 0x0308, 0x04ED), (0x0456, 0x0308, 0x0457),
+    (0x0474, 0x030F, 0x0476), (0x0475, 0x030F, 0x0477), (0x04D8, 0x0308, 0x04DA), (0x04D9, 0x0308, 0x04DB),
+    (0x04E8, 0x0308, 0x04EA), (0x04E9, 0x0308, 0x04EB), (0x1E36, 0x0304, 0x1E38), (0x1E37, 0x0304, 0x1E39),
+    (0x1E5A, 0x0304, 0x1E5C), (0x1E5B, 0x0304, 0x1E5D), (0x1E62, 0x0307, 0x1E68), (0x1E63, 0x0307, 0x1E69),
+    (0x1EA0, 0x0302, 0x1EAC), (0x1EA0, 0x0306, 0x1EB6), (0x1EA1, 0x0302, 0x1EAD), (0x1EA1, 0x0306, 0x1EB7),
+    (0x1EB8, 0x0302, 0x1EC6), (0x1EB9, 0x0302, 0x1EC7), (0x1ECC, 0x0302, 0x1ED8), (0x1ECD, 0x0302, 0x1ED9),
+    (0x1F00, 0x0300, 0x1F02), (0x1F00, 0x0301, 0x1F04), (0x1F00, 0x0342, 0x1F06), (0x1F00, 0x0345, 0x1F80),
+    (0x1F01, 0x0300, 0x1F03), (0x1F01, 0x0301, 0x1F05), (0x1F01, 0x0342, 0x1F07), (0x1F01, 0x0345, 0x1F81),
+    (0x1F02, 0x0345, 0x1F82), (0x1F03, 0x0345, 0x1F83), (0x1F04, 0x0345, 0x1F84), (0x1F05, 0x0345, 0x1F85),
+    (0x1F06, 0x0345, 0x1F86), (0x1F07, 0x0345, 0x1F87), (0x1F08, 0x0300, 0x1F0A), (0x1F08, 0x0301, 0x1F0C),
+    (0x1F08, 0x0342, 0x1F0E), (0x1F08, 0x0345, 0x1F88), (0x1F09, 0x0300, 0x1F0B), (0x1F09, 0x0301, 0x1F0D),
+    (0x1F09, 0x0342, 0x1F0F), (0x1F09, 0x0345, 0x1F89), (0x1F0A, 0x0345, 0x1F8A), (0x1F0B, 0x0345, 0x1F8B),
+    (0x1F0C, 0x0345, 0x1F8C), (0x1F0D, 0x0345, 0x1F8D), (0x1F0E, 0x0345, 0x1F8E), (0x1F0F, 0x0345, 0x1F8F),
+    (0x1F10, 0x0300, 0x1F12), (0x1F10, 0x0301, 0x1F14), (0x1F11, 0x0300, 0x1F13), (0x1F11, 0x0301, 0x1F15),
+    (0x1F18, 0x0300, 0x1F1A), (0x1F18, 0x0301, 0x1F1C), (0x1F19, 0x0300, 0x1F1B), (0x1F19, 0x0301, 0x1F1D),
+    (0x1F20, 0x0300, 0x1F22), (0x1F20, 0x0301, 0x1F24), (0x1F20, 0x0342, 0x1F26), (0x1F20, 0x0345, 0x1F90),
+    (0x1F21, 0x0300, 0x1F23), (0x1F21, 0x0301, 0x1F25), (0x1F21, 0x0342, 0x1F27), (0x1F21, 0x0345, 0x1F91),
+    (0x1F22, 0x0345, 0x1F92), (0x1F23, 0x0345, 0x1F93), (0x1F24, 0x0345, 0x1F94), (0x1F25, 0x0345, 0x1F95),
+    (0x1F26, 0x0345, 0x1F96), (0x1F27, 0x0345, 0x1F97), (0x1F28, 0x0300, 0x1F2A), (0x1F28, 0x0301, 0x1F2C),
+    (0x1F28, 0x0342, 0x1F2E), (0x1F28, 0x0345, 0x1F98), (0x1F29, 0x0300, 0x1F2B), (0x1F29, 0x0301, 0x1F2D),
+    (0x1F29, 0x0342, 0x1F2F), (0x1F29, 0x0345, 0x1F99), (0x1F2A, 0x0345, 0x1F9A), (0x1F2B, 0x0345, 0x1F9B),
+    (0x1F2C, 0x0345, 0x1F9C), (0x1F2D, 0x0345, 0x1F9D), (0x1F2E, 0x0345, 0x1F9E), (0x1F2F, 0x0345, 0x1F9F),
+    (0x1F30, 0x0300, 0x1F32), (0x1F30, 0x0301, 0x1F34), (0x1F30, 0x0342, 0x1F36), (0x1F31, 0x0300, 0x1F33),
+    (0x1F31, 0x0301, 0x1F35), (0x1F31, 0x0342, 0x1F37), (0x1F38, 0x0300, 0x1F3A), (0x1F38, 0x0301, 0x1F3C),
+    (0x1F38, 0x0342, 0x1F3E), (0x1F39, 0x0300, 0x1F3B), (0x1F39, 0x0301, 0x1F3D), (0x1F39, 0x0342, 0x1F3F),
+    (0x1F40, 0x0300, 0x1F42), (0x1F40, 0x0301, 0x1F44), (0x1F41, 0x0300, 0x1F43), (0x1F41, 0x0301, 0x1F45),
+    (0x1F48, 0x0300, 0x1F4A), (0x1F48, 0x0301, 0x1F4C), (0x1F49, 0x0300, 0x1F4B), (0x1F49, 0x0301, 0x1F4D),
+    (0x1F50, 0x0300, 0x1F52), (0x1F50, 0x0301, 0x1F54), (0x1F50, 0x0342, 0x1F56), (0x1F51, 0x0300, 0x1F53),
+    (0x1F51, 0x0301, 0x1F55), (0x1F51, 0x0342, 0x1F57), (0x1F59, 0x0300, 0x1F5B), (0x1F59, 0x0301, 0x1F5D),
+    (0x1F59, 0x0342, 0x1F5F), (0x1F60, 0x0300, 0x1F62), (0x1F60, 0x0301, 0x1F64), (0x1F60, 0x0342, 0x1F66),
+    (0x1F60, 0x0345, 0x1FA0), (0x1F61, 0x0300, 0x1F63), (0x1F61, 0x0301, 0x1F65), (0x1F61, 0x0342, 0x1F67),
+    (0x1F61, 0x0345, 0x1FA1), (0x1F62, 0x0345, 0x1FA2), (0x1F63, 0x0345, 0x1FA3), (0x1F64, 0x0345, 0x1FA4),
+    (0x1F65, 0x0345, 0x1FA5), (0x1F66, 0x0345, 0x1FA6), (0x1F67, 0x0345, 0x1FA7), (0x1F68, 0x0300, 0x1F6A),
+    (0x1F68, 0x0301, 0x1F6C), (0x1F68, 0x0342, 0x1F6E), (0x1F68, 0x0345, 0x1FA8), (0x1F69, 0x0300, 0x1F6B),
+    (0x1F69, 0x0301, 0x1F6D), (0x1F69, 0x0342, 0x1F6F), (0x1F69, 0x0345, 0x1FA9), (0x1F6A, 0x0345, 0x1FAA),
+    (0x1F6B, 0x0345, 0x1FAB), (0x1F6C, 0x0345, 0x1FAC), (0x1F6D, 0x0345, 0x1FAD), (0x1F6E, 0x0345, 0x1FAE),
+    (0x1F6F, 0x0345, 0x1FAF), (0x1F70, 0x0345, 0x1FB2), (0x1F74, 0x0345, 0x1FC2), (0x1F7C, 0x0345, 0x1FF2),
+    (0x1FB6, 0x0345, 0x1FB7), (0x1FBF, 0x0300, 0x1FCD), (0x1FBF, 0x0301, 0x1FCE), (0x1FBF, 0x0342, 0x1FCF),
+    (0x1FC6, 0x0345, 0x1FC7), (0x1FF6, 0x0345, 0x1FF7), (0x1FFE, 0x0300, 0x1FDD), (0x1FFE, 0x0301, 0x1FDE),
+    (0x1FFE, 0x0342, 0x1FDF), (0x3046, 0x3099, 0x3094), (0x304B, 0x3099, 0x304C), (0x304D, 0x3099, 0x304E),
+    (0x304F, 0x3099, 0x3050), (0x3051, 0x3099, 0x3052), (0x3053, 0x3099, 0x3054), (0x3055, 0x3099, 0x3056),
+    (0x3057, 0x3099, 0x3058), (0x3059, 0x3099, 0x305A), (0x305B, 0x3099, 0x305C), (0x305D, 0x3099, 0x305E),
+    (0x305F, 0x3099, 0x3060), (0x3061, 0x3099, 0x3062), (0x3064, 0x3099, 0x3065), (0x3066, 0x3099, 0x3067),
+    (0x3068, 0x3099, 0x3069), (0x306F, 0x3099, 0x3070), (0x306F, 0x309A, 0x3071), (0x3072, 0x3099, 0x3073),
+    (0x3072, 0x309A, 0x3074), (0x3075, 0x3099, 0x3076), (0x3075, 0x309A, 0x3077), (0x3078, 0x3099, 0x3079),
+    (0x3078, 0x309A, 0x307A), (0x307B, 0x3099, 0x307C), (0x307B, 0x309A, 0x307D), (0x309D, 0x3099, 0x309E),
+    (0x30A6, 0x3099, 0x30F4), (0x30AB, 0x3099, 0x30AC), (0x30AD, 0x3099, 0x30AE), (0x30AF, 0x3099, 0x30B0),
+    (0x30B1, 0x3099, 0x30B2), (0x30B3, 0x3099, 0x30B4), (0x30B5, 0x3099, 0x30B6), (0x30B7, 0x3099, 0x30B8),
+    (0x30B9, 0x3099, 0x30BA), (0x30BB, 0x3099, 0x30BC), (0x30BD, 0x3099, 0x30BE), (0x30BF, 0x3099, 0x30C0),
+    (0x30C1, 0x3099, 0x30C2), (0x30C4, 0x3099, 0x30C5), (0x30C6, 0x3099, 0x30C7), (0x30C8, 0x3099, 0x30C9),
+    (0x30CF, 0x3099, 0x30D0), (0x30CF, 0x309A, 0x30D1), (0x30D2, 0x3099, 0x30D3), (0x30D2, 0x309A, 0x30D4),
+    (0x30D5, 0x3099, 0x30D6), (0x30D5, 0x309A, 0x30D7), (0x30D8, 0x3099, 0x30D9), (0x30D8, 0x309A, 0x30DA),
+    (0x30DB, 0x3099, 0x30DC), (0x30DB, 0x309A, 0x30DD), (0x30EF, 0x3099, 0x30F7), (0x30F0, 0x3099, 0x30F8),
+    (0x30F1, 0x3099, 0x30F9), (0x30F2, 0x3099, 0x30FA), (0x30FD, 0x3099, 0x30FE),
+];
+
+// 다른 글자 하나로 바뀌는 글자(옹스트롬 기호 -> Å, 그리스어 물음표 -> ;).
+#[rustfmt::skip]
+const SINGLETONS: &[(u32, u32)] = &[
+    (0x0340, 0x0300), (0x0341, 0x0301), (0x0343, 0x0313), (0x0374, 0x02B9), (0x037E, 0x003B), (0x0387, 0x00B7),
+    (0x1F71, 0x03AC), (0x1F73, 0x03AD), (0x1F75, 0x03AE), (0x1F77, 0x03AF), (0x1F79, 0x03CC), (0x1F7B, 0x03CD),
+    (0x1F7D, 0x03CE), (0x1FBB, 0x0386), (0x1FBE, 0x03B9), (0x1FC9, 0x0388), (0x1FCB, 0x0389), (0x1FD3, 0x0390),
+    (0x1FDB, 0x038A), (0x1FE3, 0x03B0), (0x1FEB, 0x038E), (0x1FEE, 0x0385), (0x1FEF, 0x0060), (0x1FF9, 0x038C),
+    (0x1FFB, 0x038F), (0x1FFD, 0x00B4), (0x2000, 0x2002), (0x2001, 0x2003), (0x2126, 0x03A9), (0x212A, 0x004B),
+    (0x212B, 0x00C5),
+];
+
+// COMPOSE와 같은 모양이지만 분해만 하고 다시 합치지 않는다(composition exclusion).
+#[rustfmt::skip]
+const DECOMPOSE_ONLY: &[(u32, u32, u32)] = &[
+    (0x0308, 0x0301, 0x0344),
+];
+
+// 결합 부호의 정렬 순서(canonical combining class). 범위 밖은 0(기본 글자)이다.
+#[rustfmt::skip]
+const COMBINING_CLASS: &[(u32, u32, u8)] = &[
+    (0x0300, 0x0314, 230), (0x0315, 0x0315, 232), (0x0316, 0x0319, 220), (0x031A, 0x031A, 232),
+    (0x031B, 0x031B, 216), (0x031C, 0x0320, 220), (0x0321, 0x0322, 202), (0x0323, 0x0326, 220),
+    (0x0327, 0x0328, 202), (0x0329, 0x0333, 220), (0x0334, 0x0338, 1), (0x0339, 0x033C, 220),
+    (0x033D, 0x0344, 230), (0x0345, 0x0345, 240), (0x0346, 0x0346, 230), (0x0347, 0x0349, 220),
+    (0x034A, 0x034C, 230), (0x034D, 0x034E, 220), (0x0350, 0x0352, 230), (0x0353, 0x0356, 220),
+    (0x0357, 0x0357, 230), (0x0358, 0x0358, 232), (0x0359, 0x035A, 220), (0x035B, 0x035B, 230),
+    (0x035C, 0x035C, 233), (0x035D, 0x035E, 234), (0x035F, 0x035F, 233), (0x0360, 0x0361, 234),
+    (0x0362, 0x0362, 233), (0x0363, 0x036F, 230), (0x0483, 0x0487, 230), (0x1DC0, 0x1DC1, 230),
+    (0x1DC2, 0x1DC2, 220), (0x1DC3, 0x1DC9, 230), (0x1DCA, 0x1DCA, 220), (0x1DCB, 0x1DCC, 230),
+    (0x1DCD, 0x1DCD, 234), (0x1DCE, 0x1DCE, 214), (0x1DCF, 0x1DCF, 220), (0x1DD0, 0x1DD0, 202),
+    (0x1DD1, 0x1DF5, 230), (0x1DF6, 0x1DF6, 232), (0x1DF7, 0x1DF8, 228), (0x1DF9, 0x1DF9, 220),
+    (0x1DFA, 0x1DFA, 218), (0x1DFB, 0x1DFB, 230), (0x1DFC, 0x1DFC, 233), (0x1DFD, 0x1DFD, 220),
+    (0x1DFE, 0x1DFE, 230), (0x1DFF, 0x1DFF, 220), (0x20D0, 0x20D1, 230), (0x20D2, 0x20D3, 1),
+    (0x20D4, 0x20D7, 230), (0x20D8, 0x20DA, 1), (0x20DB, 0x20DC, 230), (0x20E1, 0x20E1, 230),
+    (0x20E5, 0x20E6, 1), (0x20E7, 0x20E7, 230), (0x20E8, 0x20E8, 220), (0x20E9, 0x20E9, 230),
+    (0x20EA, 0x20EB, 1), (0x20EC, 0x20EF, 220), (0x20F0, 0x20F0, 230), (0x3099, 0x309A, 8),
+];
+
+// NFKC의 호환 분해. 전각 ASCII(U+FF01..FF5E)는 compat()에서 계산한다.
+#[rustfmt::skip]
+const COMPAT: &[(u32, &str)] = &[
+    (0x00A0, " "), (0x00A8, " \u{308}"), (0x00AA, "a"), (0x00AF, " \u{304}"),
+    (0x00B2, "2"), (0x00B3, "3"), (0x00B4, " \u{301}"), (0x00B5, "\u{3BC}"),
+    (0x00B8, " \u{327}"), (0x00B9, "1"), (0x00BA, "o"), (0x00BC, "1\u{2044}4"),
+    (0x00BD, "1\u{2044}2"), (0x00BE, "3\u{2044}4"), (0x0132, "IJ"), (0x0133, "ij"),
+    (0x013F, "L\u{B7}"), (0x0140, "l\u{B7}"), (0x0149, "\u{2BC}n"), (0x017F, "s"),
+    (0x01C4, "D\u{17D}"), (0x01C5, "D\u{17E}"), (0x01C6, "d\u{17E}"), (0x01C7, "LJ"),
+    (0x01C8, "Lj"), (0x01C9, "lj"), (0x01CA, "NJ"), (0x01CB, "Nj"),
+    (0x01CC, "nj"), (0x01F1, "DZ"), (0x01F2, "Dz"), (0x01F3, "dz"),
+    (0x02B0, "h"), (0x02B1, "\u{266}"), (0x02B2, "j"), (0x02B3, "r"),
+    (0x02B4, "\u{279}"), (0x02B5, "\u{27B}"), (0x02B6, "\u{281}"), (0x02B7, "w"),
+    (0x02B8, "y"), (0x037A, " \u{345}"), (0x0384, " \u{301}"), (0x03D0, "\u{3B2}"),
+    (0x03D1, "\u{3B8}"), (0x03D2, "\u{3A5}"), (0x03D5, "\u{3C6}"), (0x03D6, "\u{3C0}"),
+    (0x03F0, "\u{3BA}"), (0x03F1, "\u{3C1}"), (0x03F2, "\u{3C2}"), (0x03F4, "\u{398}"),
+    (0x03F5, "\u{3B5}"), (0x03F9, "\u{3A3}"), (0x1E9A, "a\u{2BE}"), (0x1FBD, " \u{313}"),
+    (0x1FBF, " \u{313}"), (0x1FC0, " \u{342}"), (0x1FFE, " \u{314}"), (0x2002, " "),
+    (0x2003, " "), (0x2004, " "), (0x2005, " "), (0x2006, " "),
+    (0x2007, " "), (0x2008, " "), (0x2009, " "), (0x200A, " "),
+    (0x2011, "\u{2010}"), (0x2017, " \u{333}"), (0x2024, "."), (0x2025, ".."),
+    (0x2026, "..."), (0x202F, " "), (0x2033, "\u{2032}\u{2032}"), (0x2034, "\u{2032}\u{2032}\u{2032}"),
+    (0x2036, "\u{2035}\u{2035}"), (0x2037, "\u{2035}\u{2035}\u{2035}"), (0x203C, "!!"), (0x203E, " \u{305}"),
+    (0x2047, "??"), (0x2048, "?!"), (0x2049, "!?"), (0x2057, "\u{2032}\u{2032}\u{2032}\u{2032}"),
+    (0x205F, " "), (0x2070, "0"), (0x2071, "i"), (0x2074, "4"),
+    (0x2075, "5"), (0x2076, "6"), (0x2077, "7"), (0x2078, "8"),
+    (0x2079, "9"), (0x207A, "+"), (0x207B, "\u{2212}"), (0x207C, "="),
+    (0x207D, "("), (0x207E, ")"), (0x207F, "n"), (0x2080, "0"),
+    (0x2081, "1"), (0x2082, "2"), (0x2083, "3"), (0x2084, "4"),
+    (0x2085, "5"), (0x2086, "6"), (0x2087, "7"), (0x2088, "8"),
+    (0x2089, "9"), (0x208A, "+"), (0x208B, "\u{2212}"), (0x208C, "="),
+    (0x208D, "("), (0x208E, ")"), (0x2090, "a"), (0x2091, "e"),
+    (0x2092, "o"), (0x2093, "x"), (0x2094, "\u{259}"), (0x2095, "h"),
+    (0x2096, "k"), (0x2097, "l"), (0x2098, "m"), (0x2099, "n"),
+    (0x209A, "p"), (0x209B, "s"), (0x209C, "t"), (0x2100, "a/c"),
+    (0x2101, "a/s"), (0x2102, "C"), (0x2103, "\u{B0}C"), (0x2105, "c/o"),
+    (0x2106, "c/u"), (0x2107, "\u{190}"), (0x2109, "\u{B0}F"), (0x210A, "g"),
+    (0x210B, "H"), (0x210C, "H"), (0x210D, "H"), (0x210E, "h"),
+    (0x210F, "\u{127}"), (0x2110, "I"), (0x2111, "I"), (0x2112, "L"),
+    (0x2113, "l"), (0x2115, "N"), (0x2116, "No"), (0x2119, "P"),
+    (0x211A, "Q"), (0x211B, "R"), (0x211C, "R"), (0x211D, "R"),
+    (0x2120, "SM"), (0x2121, "TEL"), (0x2122, "TM"), (0x2124, "Z"),
+    (0x2128, "Z"), (0x212C, "B"), (0x212D, "C"), (0x212F, "e"),
+    (0x2130, "E"), (0x2131, "F"), (0x2133, "M"), (0x2134, "o"),
+    (0x2135, "\u{5D0}"), (0x2136, "\u{5D1}"), (0x2137, "\u{5D2}"), (0x2138, "\u{5D3}"),
+    (0x2139, "i"), (0x213B, "FAX"), (0x213C, "\u{3C0}"), (0x213D, "\u{3B3}"),
+    (0x213E, "\u{393}"), (0x213F, "\u{3A0}"), (0x2140, "\u{2211}"), (0x2145, "D"),
+    (0x2146, "d"), (0x2147, "e"), (0x2148, "i"), (0x2149, "j"),
+    (0x2150, "1\u{2044}7"), (0x2151, "1\u{2044}9"), (0x2152, "1\u{2044}10"), (0x2153, "1\u{2044}3"),
+    (0x2154, "2\u{2044}3"), (0x2155, "1\u{2044}5"), (0x2156, "2\u{2044}5"), (0x2157, "3\u{2044}5"),
+    (0x2158, "4\u{2044}5"), (0x2159, "1\u{2044}6"), (0x215A, "5\u{2044}6"), (0x215B, "1\u{2044}8"),
+    (0x215C, "3\u{2044}8"), (0x215D, "5\u{2044}8"), (0x215E, "7\u{2044}8"), (0x215F, "1\u{2044}"),
+    (0x2160, "I"), (0x2161, "II"), (0x2162, "III"), (0x2163, "IV"),
+    (0x2164, "V"), (0x2165, "VI"), (0x2166, "VII"), (0x2167, "VIII"),
+    (0x2168, "IX"), (0x2169, "X"), (0x216A, "XI"), (0x216B, "XII"),
+    (0x216C, "L"), (0x216D, "C"), (0x216E, "D"), (0x216F, "M"),
+    (0x2170, "i"), (0x2171, "ii"), (0x2172, "iii"), (0x2173, "iv"),
+    (0x2174, "v"), (0x2175, "vi"), (0x2176, "vii"), (0x2177, "viii"),
+    (0x2178, "ix"), (0x2179, "x"), (0x217A, "xi"), (0x217B, "xii"),
+    (0x217C, "l"), (0x217D, "c"), (0x217E, "d"), (0x217F, "m"),
+    (0x2189, "0\u{2044}3"), (0x2460, "1"), (0x2461, "2"), (0x2462, "3"),
+    (0x2463, "4"), (0x2464, "5"), (0x2465, "6"), (0x2466, "7"),
+    (0x2467, "8"), (0x2468, "9"), (0x2469, "10"), (0x246A, "11"),
+    (0x246B, "12"), (0x246C, "13"), (0x246D, "14"), (0x246E, "15"),
+    (0x246F, "16"), (0x2470, "17"), (0x2471, "18"), (0x2472, "19"),
+    (0x2473, "20"), (0x2474, "(1)"), (0x2475, "(2)"), (0x2476, "(3)"),
+    (0x2477, "(4)"), (0x2478, "(5)"), (0x2479, "(6)"), (0x247A, "(7)"),
+    (0x247B, "(8)"), (0x247C, "(9)"), (0x247D, "(10)"), (0x247E, "(11)"),
+    (0x247F, "(12)"), (0x2480, "(13)"), (0x2481, "(14)"), (0x2482, "(15)"),
+    (0x2483, "(16)"), (0x2484, "(17)"), (0x2485, "(18)"), (0x2486, "(19)"),
+    (0x2487, "(20)"), (0x2488, "1."), (0x2489, "2."), (0x248A, "3."),
+    (0x248B, "4."), (0x248C, "5."), (0x248D, "6."), (0x248E, "7."),
+    (0x248F, "8."), (0x2490, "9."), (0x2491, "10."), (0x2492, "11."),
+    (0x2493, "12."), (0x2494, "13."), (0x2495, "14."), (0x2496, "15."),
+    (0x2497, "16."), (0x2498, "17."), (0x2499, "18."), (0x249A, "19."),
+    (0x249B, "20."), (0x249C, "(a)"), (0x249D, "(b)"), (0x249E, "(c)"),
+    (0x249F, "(d)"), (0x24A0, "(e)"), (0x24A1, "(f)"), (0x24A2, "(g)"),
+    (0x24A3, "(h)"), (0x24A4, "(i)"), (0x24A5, "(j)"), (0x24A6, "(k)"),
+    (0x24A7, "(l)"), (0x24A8, "(m)"), (0x24A9, "(n)"), (0x24AA, "(o)"),
+    (0x24AB, "(p)"), (0x24AC, "(q)"), (0x24AD, "(r)"), (0x24AE, "(s)"),
+    (0x24AF, "(t)"), (0x24B0, "(u)"), (0x24B1, "(v)"), (0x24B2, "(w)"),
+    (0x24B3, "(x)"), (0x24B4, "(y)"), (0x24B5, "(z)"), (0x24B6, "A"),
+    (0x24B7, "B"), (0x24B8, "C"), (0x24B9, "D"), (0x24BA, "E"),
+    (0x24BB, "F"), (0x24BC, "G"), (0x24BD, "H"), (0x24BE, "I"),
+    (0x24BF, "J"), (0x24C0, "K"), (0x24C1, "L"), (0x24C2, "M"),
+    (0x24C3, "N"), (0x24C4, "O"), (0x24C5, "P"), (0x24C6, "Q"),
+    (0x24C7, "R"), (0x24C8, "S"), (0x24C9, "T"), (0x24CA, "U"),
+    (0x24CB, "V"), (0x24CC, "W"), (0x24CD, "X"), (0x24CE, "Y"),
+    (0x24CF, "Z"), (0x24D0, "a"), (0x24D1, "b"), (0x24D2, "c"),
+    (0x24D3, "d"), (0x24D4, "e"), (0x24D5, "f"), (0x24D6, "g"),
+    (0x24D7, "h"), (0x24D8, "i"), (0x24D9, "j"), (0x24DA, "k"),
+    (0x24DB, "l"), (0x24DC, "m"), (0x24DD, "n"), (0x24DE, "o"),
+    (0x24DF, "p"), (0x24E0, "q"), (0x24E1, "r"), (0x24E2, "s"),
+    (0x24E3, "t"), (0x24E4, "u"), (0x24E5, "v"), (0x24E6, "w"),
+    (0x24E7, "x"), (0x24E8, "y"), (0x24E9, "z"), (0x24EA, "0"),
+    (0x3000, " "), (0x309B, " \u{3099}"), (0x309C, " \u{309A}"), (0x309F, "\u{3088}\u{308A}"),
+    (0x30FF, "\u{30B3}\u{30C8}"), (0x3131, "\u{1100}"), (0x3132, "\u{1101}"), (0x3133, "\u{11AA}"),
+    (0x3134, "\u{1102}"), (0x3135, "\u{11AC}"), (0x3136, "\u{11AD}"), (0x3137, "\u{1103}"),
+    (0x3138, "\u{1104}"), (0x3139, "\u{1105}"), (0x313A, "\u{11B0}"), (0x313B, "\u{11B1}"),
+    (0x313C, "\u{11B2}"), (0x313D, "\u{11B3}"), (0x313E, "\u{11B4}"), (0x313F, "\u{11B5}"),
+    (0x3140, "\u{111A}"), (0x3141, "\u{1106}"), (0x3142, "\u{1107}"), (0x3143, "\u{1108}"),
+    (0x3144, "\u{1121}"), (0x3145, "\u{1109}"), (0x3146, "\u{110A}"), (0x3147, "\u{110B}"),
+    (0x3148, "\u{110C}"), (0x3149, "\u{110D}"), (0x314A, "\u{110E}"), (0x314B, "\u{110F}"),
+    (0x314C, "\u{1110}"), (0x314D, "\u{1111}"), (0x314E, "\u{1112}"), (0x314F, "\u{1161}"),
+    (0x3150, "\u{1162}"), (0x3151, "\u{1163}"), (0x3152, "\u{1164}"), (0x3153, "\u{1165}"),
+    (0x3154, "\u{1166}"), (0x3155, "\u{1167}"), (0x3156, "\u{1168}"), (0x3157, "\u{1169}"),
+    (0x3158, "\u{116A}"), (0x3159, "\u{116B}"), (0x315A, "\u{116C}"), (0x315B, "\u{116D}"),
+    (0x315C, "\u{116E}"), (0x315D, "\u{116F}"), (0x315E, "\u{1170}"), (0x315F, "\u{1171}"),
+    (0x3160, "\u{1172}"), (0x3161, "\u{1173}"), (0x3162, "\u{1174}"), (0x3163, "\u{1175}"),
+    (0x3164, "\u{1160}"), (0x3165, "\u{1114}"), (0x3166, "\u{1115}"), (0x3167, "\u{11C7}"),
+    (0x3168, "\u{11C8}"), (0x3169, "\u{11CC}"), (0x316A, "\u{11CE}"), (0x316B, "\u{11D3}"),
+    (0x316C, "\u{11D7}"), (0x316D, "\u{11D9}"), (0x316E, "\u{111C}"), (0x316F, "\u{11DD}"),
+    (0x3170, "\u{11DF}"), (0x3171, "\u{111D}"), (0x3172, "\u{111E}"), (0x3173, "\u{1120}"),
+    (0x3174, "\u{1122}"), (0x3175, "\u{1123}"), (0x3176, "\u{1127}"), (0x3177, "\u{1129}"),
+    (0x3178, "\u{112B}"), (0x3179, "\u{112C}"), (0x317A, "\u{112D}"), (0x317B, "\u{112E}"),
+    (0x317C, "\u{112F}"), (0x317D, "\u{1132}"), (0x317E, "\u{1136}"), (0x317F, "\u{1140}"),
+    (0x3180, "\u{1147}"), (0x3181, "\u{114C}"), (0x3182, "\u{11F1}"), (0x3183, "\u{11F2}"),
+    (0x3184, "\u{1157}"), (0x3185, "\u{1158}"), (0x3186, "\u{1159}"), (0x3187, "\u{1184}"),
+    (0x3188, "\u{1185}"), (0x3189, "\u{1188}"), (0x318A, "\u{1191}"), (0x318B, "\u{1192}"),
+    (0x318C, "\u{1194}"), (0x318D, "\u{119E}"), (0x318E, "\u{11A1}"), (0xFB00, "ff"),
+    (0xFB01, "fi"), (0xFB02, "fl"), (0xFB03, "ffi"), (0xFB04, "ffl"),
+    (0xFB05, "\u{17F}t"), (0xFB06, "st"), (0xFF5F, "\u{2985}"), (0xFF60, "\u{2986}"),
+    (0xFF61, "\u{3002}"), (0xFF62, "\u{300C}"), (0xFF63, "\u{300D}"), (0xFF64, "\u{3001}"),
+    (0xFF65, "\u{30FB}"), (0xFF66, "\u{30F2}"), (0xFF67, "\u{30A1}"), (0xFF68, "\u{30A3}"),
+    (0xFF69, "\u{30A5}"), (0xFF6A, "\u{30A7}"), (0xFF6B, "\u{30A9}"), (0xFF6C, "\u{30E3}"),
+    (0xFF6D, "\u{30E5}"), (0xFF6E, "\u{30E7}"), (0xFF6F, "\u{30C3}"), (0xFF70, "\u{30FC}"),
+    (0xFF71, "\u{30A2}"), (0xFF72, "\u{30A4}"), (0xFF73, "\u{30A6}"), (0xFF74, "\u{30A8}"),
+    (0xFF75, "\u{30AA}"), (0xFF76, "\u{30AB}"), (0xFF77, "\u{30AD}"), (0xFF78, "\u{30AF}"),
+    (0xFF79, "\u{30B1}"), (0xFF7A, "\u{30B3}"), (0xFF7B, "\u{30B5}"), (0xFF7C, "\u{30B7}"),
+    (0xFF7D, "\u{30B9}"), (0xFF7E, "\u{30BB}"), (0xFF7F, "\u{30BD}"), (0xFF80, "\u{30BF}"),
+    (0xFF81, "\u{30C1}"), (0xFF82, "\u{30C4}"), (0xFF83, "\u{30C6}"), (0xFF84, "\u{30C8}"),
+    (0xFF85, "\u{30CA}"), (0xFF86, "\u{30CB}"), (0xFF87, "\u{30CC}"), (0xFF88, "\u{30CD}"),
+    (0xFF89, "\u{30CE}"), (0xFF8A, "\u{30CF}"), (0xFF8B, "\u{30D2}"), (0xFF8C, "\u{30D5}"),
+    (0xFF8D, "\u{30D8}"), (0xFF8E, "\u{30DB}"), (0xFF8F, "\u{30DE}"), (0xFF90, "\u{30DF}"),
+    (0xFF91, "\u{30E0}"), (0xFF92, "\u{30E1}"), (0xFF93, "\u{30E2}"), (0xFF94, "\u{30E4}"),
+    (0xFF95, "\u{30E6}"), (0xFF96, "\u{30E8}"), (0xFF97, "\u{30E9}"), (0xFF98, "\u{30EA}"),
+    (0xFF99, "\u{30EB}"), (0xFF9A, "\u{30EC}"), (0xFF9B, "\u{30ED}"), (0xFF9C, "\u{30EF}"),
+    (0xFF9D, "\u{30F3}"), (0xFF9E, "\u{3099}"), (0xFF9F, "\u{309A}"), (0xFFA0, "\u{3164}"),
+    (0xFFA1, "\u{3131}"), (0xFFA2, "\u{3132}"), (0xFFA3, "\u{3133}"), (0xFFA4, "\u{3134}"),
+    (0xFFA5, "\u{3135}"), (0xFFA6, "\u{3136}"), (0xFFA7, "\u{3137}"), (0xFFA8, "\u{3138}"),
+    (0xFFA9, "\u{3139}"), (0xFFAA, "\u{313A}"), (0xFFAB, "\u{313B}"), (0xFFAC, "\u{313C}"),
+    (0xFFAD, "\u{313D}"), (0xFFAE, "\u{313E}"), (0xFFAF, "\u{313F}"), (0xFFB0, "\u{3140}"),
+    (0xFFB1, "\u{3141}"), (0xFFB2, "\u{3142}"), (0xFFB3, "\u{3143}"), (0xFFB4, "\u{3144}"),
+    (0xFFB5, "\u{3145}"), (0xFFB6, "\u{3146}"), (0xFFB7, "\u{3147}"), (0xFFB8, "\u{3148}"),
+    (0xFFB9, "\u{3149}"), (0xFFBA, "\u{314A}"), (0xFFBB, "\u{314B}"), (0xFFBC, "\u{314C}"),
+    (0xFFBD, "\u{314D}"), (0xFFBE, "\u{314E}"), (0xFFC2, "\u{314F}"), (0xFFC3, "\u{3150}"),
+    (0xFFC4, "\u{3151}"), (0xFFC5, "\u{3152}"), (0xFFC6, "\u{3153}"), (0xFFC7, "\u{3154}"),
+    (0xFFCA, "\u{3155}"), (0xFFCB, "\u{3156}"), (0xFFCC, "\u{3157}"), (0xFFCD, "\u{3158}"),
+    (0xFFCE, "\u{3159}"), (0xFFCF, "\u{315A}"), (0xFFD2, "\u{315B}"), (0xFFD3, "\u{315C}"),
+    (0xFFD4, "\u{315D}"), (0xFFD5, "\u{315E}"), (0xFFD6, "\u{315F}"), (0xFFD7, "\u{3160}"),
+    (0xFFDA, "\u{3161}"), (0xFFDB, "\u{3162}"), (0xFFDC, "\u{3163}"), (0xFFE0, "\u{A2}"),
+    (0xFFE1, "\u{A3}"), (0xFFE2, "\u{AC}"), (0xFFE3, "\u{AF}"), (0xFFE4, "\u{A6}"),
+    (0xFFE5, "\u{A5}"), (0xFFE6, "\u{20A9}"), (0xFFE8, "\u{2502}"), (0xFFE9, "\u{2190}"),
+    (0xFFEA, "\u{2191}"), (0xFFEB, "\u{2192}"), (0xFFEC, "\u{2193}"), (0xFFED, "\u{25A0}"),
+    (0xFFEE, "\u{25CB}"),
+];
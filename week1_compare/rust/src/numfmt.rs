@@ -0,0 +1,118 @@
+// --number-format grouped|plain[:N]: 사람이 읽는 출력(텍스트 표, markdown, stats, weblog, drift,
+// heatmap)의 숫자 모양. grouped는 천 단위 구분 기호를 넣고, :N은 소수 자리 수(비율 등)를 바꾼다.
+// 구분 기호는 --lang의 관례를 따른다.
+//   en, ko  1,234,567.8     de  1.234.567,8     fr  1 234 567,8 (좁은 줄바꿈 없는 공백)
+// csv, json, html은 다른 프로그램이 읽으므로 그대로 둔다.
+// 출력 곳곳에서 쓰이므로 timing.rs처럼 main이 한 번 설치하고 전역으로 읽는다.
+
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub grouped: bool,
+    // None이면 출력마다 정해 둔 기본 자리 수를 쓴다.
+    pub precision: Option<usize>,
+    pub group: char,
+    pub decimal: char,
+}
+
+static FORMAT: OnceLock<NumberFormat> = OnceLock::new();
+
+impl NumberFormat {
+    // 지정하지 않았을 때: 구분 기호 없이, 출력마다의 기본 자리 수.
+    pub const PLAIN: NumberFormat = NumberFormat {
+        grouped: false,
+        precision: None,
+        group: ',',
+        decimal: '.',
+    };
+
+    // lang은 --lang 값(en, ko, de, fr). 확인은 cli에서 끝났다.
+    pub fn parse(spec: &str, lang: &str) -> Result<NumberFormat, String> {
+        let (style, precision) = match spec.split_once(':') {
+            Some((style, digits)) => {
+                let n = digits
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n <= 10)
+                    .ok_or_else(|| {
+                        format!("--number-format precision must be 0 to 10, got {digits:?}")
+                    })?;
+                (style, Some(n))
+            }
+            None => (spec, None),
+        };
+        let grouped = match style {
+            "grouped" => true,
+            "plain" => false,
+            other => {
+                return Err(format!(
+                    "--number-format must be grouped or plain (optionally :DIGITS), got {other}"
+                ))
+            }
+        };
+        let (group, decimal) = match lang {
+            "de" => ('.', ','),
+            "fr" => ('\u{202F}', ','),
+            _ => (',', '.'),
+        };
+        Ok(NumberFormat {
+            grouped,
+            precision,
+            group,
+            decimal,
+        })
+    }
+}
+
+pub fn install(format: NumberFormat) {
+    let _ = FORMAT.set(format);
+}
+
+fn current() -> NumberFormat {
+    FORMAT.get().copied().unwrap_or(NumberFormat::PLAIN)
+}
+
+pub fn count(n: usize) -> String {
+    group(&n.to_string(), current())
+}
+
+// digits는 --number-format :N이 없을 때의 자리 수.
+pub fn decimal(x: f64, digits: usize) -> String {
+    let format = current();
+    let text = format!("{:.*}", format.precision.unwrap_or(digits), x);
+    let (sign, body) = match text.strip_prefix('-') {
+        Some(body) => ("-", body),
+        None => ("", text.as_str()),
+    };
+    let (int, frac) = match body.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (body, None),
+    };
+    let mut out = sign.to_string();
+    out.push_str(&group(int, format));
+    if let Some(frac) = frac {
+        out.push(format.decimal);
+        out.push_str(frac);
+    }
+    out
+}
+
+// 백분율 값(0~100)에 %를 붙인다.
+pub fn percent(x: f64, digits: usize) -> String {
+    decimal(x, digits) + "%"
+}
+
+fn group(digits: &str, format: NumberFormat) -> String {
+    if !format.grouped || digits.len() <= 3 {
+        return digits.to_string();
+    }
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(format.group);
+        }
+        out.push(c);
+    }
+    out
+}
@@ -0,0 +1,84 @@
+// --output PATH: 결과를 파일로 쓴다.
+// 같은 디렉터리의 임시 파일에 다 쓴 뒤 rename으로 바꿔치기하므로, 도중에 실패하거나
+// 중단되어도 이전 보고서가 반쯤 쓰인 파일로 덮이지 않는다(Python이라면
+// tempfile.NamedTemporaryFile + os.replace 조합). --append는 기존 내용을 임시 파일에
+// 먼저 복사하고 새 결과를 뒤에 붙여 같은 방식으로 교체한다.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+pub fn write(path: &str, text: &str, append: bool) -> Result<(), String> {
+    write_bytes(path, text.as_bytes(), append)
+}
+
+// --output-encoding으로 이미 바꾼 바이트를 그대로 쓴다.
+pub fn write_bytes(path: &str, data: &[u8], append: bool) -> Result<(), String> {
+    let target = Path::new(path);
+    let tmp = temp_path(target);
+    let result = write_temp(&tmp, target, data, append).and_then(|()| fs::rename(&tmp, target));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&tmp);
+        return Err(format!("failed to write {path}: {e}"));
+    }
+    Ok(())
+}
+
+// --journal과 --every --output처럼 한 줄씩 쌓는 기록. 위처럼 기존 내용을 복사해 바꿔치기하면
+// 기록이 길어질수록 느려지고, 동시에 돈 실행끼리 서로의 줄을 덮어 잃으므로 O_APPEND로 끝에만 붙인다.
+pub fn append_line(path: &str, line: &str) -> Result<(), String> {
+    OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| format!("failed to write {path}: {e}"))
+}
+
+// gen-corpus처럼 메모리에 다 담을 수 없는 출력. fill이 쓰는 대로 임시 파일에 흘려보내고
+// 끝나면 같은 방식으로 바꿔치기한다.
+pub fn write_streamed<T>(
+    path: &str,
+    fill: impl FnOnce(&mut dyn Write) -> io::Result<T>,
+) -> Result<T, String> {
+    let target = Path::new(path);
+    let tmp = temp_path(target);
+    let result = File::create(&tmp).and_then(|file| {
+        let mut writer = io::BufWriter::new(file);
+        let value = fill(&mut writer)?;
+        let file = writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?;
+        file.sync_all()?;
+        fs::rename(&tmp, target)?;
+        Ok(value)
+    });
+    result.map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("failed to write {path}: {e}")
+    })
+}
+
+// 같은 파일 시스템 안이어야 rename이 원자적이므로 대상 옆에 만든다.
+fn temp_path(target: &Path) -> PathBuf {
+    let name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_string());
+    target.with_file_name(format!(".{name}.{}.tmp", process::id()))
+}
+
+fn write_temp(tmp: &Path, target: &Path, data: &[u8], append: bool) -> io::Result<()> {
+    let mut file = File::create(tmp)?;
+    if append {
+        match fs::read(target) {
+            Ok(existing) => file.write_all(&existing)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    file.write_all(data)?;
+    // rename 전에 디스크까지 내려보내야 전원이 나가도 빈 파일이 남지 않는다.
+    file.sync_all()
+}
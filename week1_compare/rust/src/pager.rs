@@ -0,0 +1,140 @@
+// git처럼 출력이 터미널 높이를 넘으면 자동으로 pager(less 등)로 넘긴다.
+// Python이라면 pydoc.pager()를 쓰겠지만, 여기서는 std::process::Command로
+// 자식 프로세스의 stdin에 결과를 흘려보내는 방식을 직접 보여준다.
+
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use crate::term;
+use crate::tty::{Key, Terminal};
+
+pub fn emit(text: &str, allow_pager: bool) {
+    if allow_pager && should_page(text) {
+        match page(text) {
+            Ok(()) => return,
+            // pager에 아무것도 넘기지 못했으면(띄우지 못한 경우 포함) 그대로 stdout에 쓴다.
+            Err(Failure::Unsent) => {}
+            // 일부라도 넘겼으면 다시 쓰면 같은 출력이 두 번 보이므로 알리기만 한다.
+            Err(Failure::Sent(e)) => {
+                eprintln!("pager failed: {e}");
+                return;
+            }
+        }
+    }
+    let mut stdout = io::stdout().lock();
+    // 파이프가 먼저 닫히는 경우(`| head`)는 조용히 무시한다.
+    let _ = stdout.write_all(text.as_bytes());
+    let _ = stdout.flush();
+}
+
+// --page-size N(--page 없이): 외부 pager 없이 N줄씩 쓰고 /dev/tty에서 키를 기다린다.
+// 스페이스는 다음 쪽, Enter와 아래 화살표는 한 줄 더, q와 Esc는 그만. 터미널이 아니면 전부 쓴다.
+pub fn more(text: &str, size: usize) {
+    if !io::stdout().is_terminal() {
+        emit(text, false);
+        return;
+    }
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let mut stdout = io::stdout().lock();
+    let mut shown = 0;
+    let mut step = size;
+    while shown < lines.len() {
+        let end = (shown + step).min(lines.len());
+        for line in &lines[shown..end] {
+            let _ = stdout.write_all(line.as_bytes());
+        }
+        let _ = stdout.flush();
+        shown = end;
+        if shown == lines.len() {
+            break;
+        }
+        // raw 모드는 키를 기다리는 동안만 켠다(Drop에서 되돌린다). 줄을 쓸 때는 평소 모드다.
+        let Ok(mut tty) = Terminal::open() else {
+            for line in &lines[shown..] {
+                let _ = stdout.write_all(line.as_bytes());
+            }
+            break;
+        };
+        let _ = tty.write(&format!(
+            "-- more ({shown}/{} lines: space, enter, q) --",
+            lines.len()
+        ));
+        let key = loop {
+            match tty.read_key() {
+                Ok(Key::Char(' ')) => break Some(size),
+                Ok(Key::Enter | Key::Down) => break Some(1),
+                Ok(Key::Char('q') | Key::Cancel) | Err(_) => break None,
+                Ok(_) => {}
+            }
+        };
+        let _ = tty.write("\r\x1b[K");
+        match key {
+            Some(n) => step = n,
+            None => break,
+        }
+    }
+    let _ = stdout.flush();
+}
+
+fn should_page(text: &str) -> bool {
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+    match term::terminal_rows() {
+        Some(rows) => text.lines().count() >= rows,
+        None => false,
+    }
+}
+
+// pager가 실패했을 때 그 전에 출력을 조금이라도 넘겼는지.
+// 하나도 넘기지 못했으면 stdout에 대신 쓰므로 에러는 버린다.
+enum Failure {
+    Unsent,
+    Sent(io::Error),
+}
+
+fn page(text: &str) -> Result<(), Failure> {
+    // $PAGER가 비어 있으면(`PAGER=`) pager를 끈 것으로 본다.
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().ok_or(Failure::Unsent)?;
+
+    let mut cmd = Command::new(program);
+    cmd.args(parts).stdin(Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        // git과 같은 기본값: 한 화면이면 바로 종료, 색상 유지, 화면 지우지 않기.
+        cmd.env("LESS", "FRX");
+    }
+
+    let mut child = cmd.spawn().map_err(|_| Failure::Unsent)?;
+    let bytes = text.as_bytes();
+    let mut sent = 0;
+    let mut failed = None;
+    if let Some(mut stdin) = child.stdin.take() {
+        // write_all은 실패했을 때 얼마나 넘겼는지 알려 주지 않으므로 직접 나눠 쓴다.
+        while sent < bytes.len() {
+            match stdin.write(&bytes[sent..]) {
+                Ok(0) => {
+                    failed = Some(io::Error::from(io::ErrorKind::WriteZero));
+                    break;
+                }
+                Ok(n) => sent += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                // 사용자가 중간에 q로 나가면 BrokenPipe가 나는데 정상 흐름이다.
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => break,
+                Err(e) => {
+                    failed = Some(e);
+                    break;
+                }
+            }
+        }
+    }
+    let waited = child.wait();
+    let failed = failed.or(waited.err());
+    match failed {
+        None => Ok(()),
+        Some(_) if sent == 0 => Err(Failure::Unsent),
+        Some(e) => Err(Failure::Sent(e)),
+    }
+}
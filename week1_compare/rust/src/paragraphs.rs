@@ -0,0 +1,40 @@
+// --by paragraph: 빈 줄(공백만 있는 줄 포함)로 나눈 문단마다 상위 단어 표를 붙인다.
+// 긴 글에서 어느 부분이 어떤 주제를 다루는지 찾으려는 것이다. 번호는 파일마다 1부터 센다.
+
+pub struct Paragraph<'a> {
+    pub index: usize,
+    // 문단 첫 줄의 줄 번호(1부터).
+    pub line: usize,
+    // 원문 조각(문단 안의 줄바꿈 포함).
+    pub text: &'a str,
+}
+
+pub fn split(text: &str) -> Vec<Paragraph<'_>> {
+    let mut found = Vec::new();
+    // (시작 바이트, 시작 줄)
+    let mut open: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        match (line.trim().is_empty(), open) {
+            (true, Some((start, first))) => {
+                found.push(Paragraph {
+                    index: found.len() + 1,
+                    line: first,
+                    text: &text[start..offset],
+                });
+                open = None;
+            }
+            (false, None) => open = Some((offset, i + 1)),
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some((start, first)) = open {
+        found.push(Paragraph {
+            index: found.len() + 1,
+            line: first,
+            text: &text[start..],
+        });
+    }
+    found
+}
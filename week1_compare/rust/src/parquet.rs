@@ -0,0 +1,231 @@
+// --format parquet: 열 형식 표를 Parquet 파일 하나로 쓴다(pandas.read_parquet, polars.read_parquet).
+// 행 그룹 하나, 열마다 데이터 페이지 하나, 압축 없이 PLAIN 인코딩만 쓴다. 값은 모두 REQUIRED라
+// 정의/반복 수준을 싣지 않는다. 메타데이터는 Thrift compact protocol로 직접 인코딩한다.
+// 형식: https://parquet.apache.org/docs/file-format/ (파일 = "PAR1" 열들 메타데이터 길이 "PAR1").
+
+use crate::columnar::{Column, Field};
+
+const MAGIC: &[u8] = b"PAR1";
+
+// parquet.thrift의 열거값.
+const TYPE_INT64: i32 = 2;
+const TYPE_BYTE_ARRAY: i32 = 6;
+const REQUIRED: i32 = 0;
+const CONVERTED_UTF8: i32 = 0;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const CODEC_UNCOMPRESSED: i32 = 0;
+const PAGE_DATA: i32 = 0;
+
+pub fn encode(fields: &[Field], rows: usize) -> Vec<u8> {
+    let mut file = MAGIC.to_vec();
+    let mut chunks = Vec::new();
+    for field in fields {
+        let values = plain(&field.column);
+        let mut header = Compact::default();
+        header.i32(1, PAGE_DATA);
+        header.i32(2, values.len() as i32);
+        header.i32(3, values.len() as i32);
+        header.begin(5);
+        header.i32(1, rows as i32);
+        header.i32(2, ENCODING_PLAIN);
+        header.i32(3, ENCODING_RLE);
+        header.i32(4, ENCODING_RLE);
+        header.end();
+        header.end();
+        let offset = file.len();
+        file.extend_from_slice(&header.buf);
+        file.extend_from_slice(&values);
+        chunks.push((offset, file.len() - offset));
+    }
+
+    let mut meta = Compact::default();
+    meta.i32(1, 1);
+    // 스키마는 뿌리 하나 아래 열들을 펼쳐 쓴다.
+    meta.list(2, fields.len() + 1);
+    meta.item();
+    meta.string(4, "schema");
+    meta.i32(5, fields.len() as i32);
+    meta.end();
+    for field in fields {
+        meta.item();
+        meta.i32(1, physical(&field.column));
+        meta.i32(3, REQUIRED);
+        meta.string(4, field.name);
+        if let Column::Utf8(_) = field.column {
+            meta.i32(6, CONVERTED_UTF8);
+            // logicalType = STRING(빈 구조체)
+            meta.begin(10);
+            meta.begin(1);
+            meta.end();
+            meta.end();
+        }
+        meta.end();
+    }
+    meta.i64(3, rows as i64);
+    meta.list(4, 1);
+    meta.item();
+    meta.list(1, fields.len());
+    let total: usize = chunks.iter().map(|(_, len)| len).sum();
+    for (field, &(offset, len)) in fields.iter().zip(&chunks) {
+        meta.item();
+        meta.i64(2, offset as i64);
+        meta.begin(3);
+        meta.i32(1, physical(&field.column));
+        meta.list_i32(2, &[ENCODING_PLAIN, ENCODING_RLE]);
+        meta.list_strings(3, &[field.name]);
+        meta.i32(4, CODEC_UNCOMPRESSED);
+        meta.i64(5, rows as i64);
+        meta.i64(6, len as i64);
+        meta.i64(7, len as i64);
+        meta.i64(9, offset as i64);
+        meta.end();
+        meta.end();
+    }
+    meta.i64(2, total as i64);
+    meta.i64(3, rows as i64);
+    meta.end();
+    meta.string(
+        6,
+        concat!("week1_rust_word_count ", env!("CARGO_PKG_VERSION")),
+    );
+    meta.end();
+
+    file.extend_from_slice(&meta.buf);
+    file.extend_from_slice(&(meta.buf.len() as u32).to_le_bytes());
+    file.extend_from_slice(MAGIC);
+    file
+}
+
+fn physical(column: &Column) -> i32 {
+    match column {
+        Column::Utf8(_) => TYPE_BYTE_ARRAY,
+        Column::Int64(_) => TYPE_INT64,
+    }
+}
+
+// PLAIN: 문자열은 4바이트 길이 + 바이트, 정수는 8바이트 little-endian.
+fn plain(column: &Column) -> Vec<u8> {
+    let mut out = Vec::new();
+    match column {
+        Column::Utf8(values) => {
+            for value in values {
+                out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                out.extend_from_slice(value.as_bytes());
+            }
+        }
+        Column::Int64(values) => {
+            for value in values {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+    out
+}
+
+// Thrift compact protocol 쓰기. 필드 머리는 앞 필드 번호와의 차이(1-15)와 형을 한 바이트에 담고,
+// 정수는 zigzag varint다. 구조체는 0으로 끝난다. 중첩 구조체마다 앞 필드 번호를 따로 기억한다.
+// 목록의 원소는 item()으로 여는 구조체로만 쓴다(원소마다 end()로 닫는다).
+#[derive(Default)]
+struct Compact {
+    buf: Vec<u8>,
+    last: Vec<i16>,
+    current: i16,
+}
+
+const T_I32: u8 = 5;
+const T_I64: u8 = 6;
+const T_BINARY: u8 = 8;
+const T_LIST: u8 = 9;
+const T_STRUCT: u8 = 12;
+
+impl Compact {
+    fn field(&mut self, id: i16, kind: u8) {
+        let delta = id - self.current;
+        if (1..=15).contains(&delta) {
+            self.buf.push(((delta as u8) << 4) | kind);
+        } else {
+            self.buf.push(kind);
+            self.varint(zigzag(i64::from(id)));
+        }
+        self.current = id;
+    }
+
+    fn varint(&mut self, mut n: u64) {
+        while n >= 0x80 {
+            self.buf.push((n as u8) | 0x80);
+            n >>= 7;
+        }
+        self.buf.push(n as u8);
+    }
+
+    fn i32(&mut self, id: i16, value: i32) {
+        self.field(id, T_I32);
+        self.varint(zigzag(i64::from(value)));
+    }
+
+    fn i64(&mut self, id: i16, value: i64) {
+        self.field(id, T_I64);
+        self.varint(zigzag(value));
+    }
+
+    fn string(&mut self, id: i16, value: &str) {
+        self.field(id, T_BINARY);
+        self.bytes(value.as_bytes());
+    }
+
+    fn bytes(&mut self, value: &[u8]) {
+        self.varint(value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    // 구조체 필드를 연다. end()까지의 필드는 그 안의 필드다. end()는 맨 바깥 구조체도 닫는다.
+    fn begin(&mut self, id: i16) {
+        self.field(id, T_STRUCT);
+        self.last.push(self.current);
+        self.current = 0;
+    }
+
+    fn end(&mut self) {
+        self.buf.push(0);
+        self.current = self.last.pop().unwrap_or(0);
+    }
+
+    fn list_header(&mut self, id: i16, len: usize, kind: u8) {
+        self.field(id, T_LIST);
+        if len < 15 {
+            self.buf.push(((len as u8) << 4) | kind);
+        } else {
+            self.buf.push(0xF0 | kind);
+            self.varint(len as u64);
+        }
+    }
+
+    // 구조체 len개의 목록. 원소마다 item() ... end()로 쓴다.
+    fn list(&mut self, id: i16, len: usize) {
+        self.list_header(id, len, T_STRUCT);
+    }
+
+    fn item(&mut self) {
+        self.last.push(self.current);
+        self.current = 0;
+    }
+
+    fn list_i32(&mut self, id: i16, values: &[i32]) {
+        self.list_header(id, values.len(), T_I32);
+        for &value in values {
+            self.varint(zigzag(i64::from(value)));
+        }
+    }
+
+    fn list_strings(&mut self, id: i16, values: &[&str]) {
+        self.list_header(id, values.len(), T_BINARY);
+        for value in values {
+            self.bytes(value.as_bytes());
+        }
+    }
+}
+
+fn zigzag(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
@@ -0,0 +1,790 @@
+// PDF의 텍스트 층을 꺼낸다(--pdf). Python이라면 pypdf의 page.extract_text()를 쓸 자리다.
+// 순서:
+//   1. 파일을 훑어 "N 0 obj ... endobj"를 모두 모은다(객체 스트림 /ObjStm 안의 것도).
+//      xref 표는 믿지 않고 뒤에 나온 정의가 앞의 것을 덮게 해서 증분 저장본도 읽는다.
+//   2. /Catalog -> /Pages 트리를 따라 쪽 순서를 정한다.
+//   3. 쪽마다 내용 스트림(/FlateDecode면 풀어서)의 글자 연산자(Tj, TJ, ', ")를 따라가며
+//      글꼴의 /ToUnicode CMap으로 글자 코드를 유니코드로 바꾼다. CMap이 없으면 Latin-1로 본다.
+// 줄 이동(Td, T*, Tm의 y 변화)은 줄바꿈으로, TJ 안의 큰 간격은 공백으로 바꾼다.
+// 암호화된 PDF와 스캔 이미지(글자 층이 없는 PDF)는 다루지 않는다.
+
+use std::collections::HashMap;
+
+use crate::inflate;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Obj {
+    Null,
+    Bool(bool),
+    Num(f64),
+    Name(String),
+    Str(Vec<u8>),
+    Array(Vec<Obj>),
+    Dict(Vec<(String, Obj)>),
+    Ref(u32),
+    // 내용 스트림의 연산자와 obj, stream 같은 키워드.
+    Keyword(String),
+}
+
+impl Obj {
+    fn get(&self, key: &str) -> Option<&Obj> {
+        match self {
+            Obj::Dict(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> Option<&str> {
+        match self {
+            Obj::Name(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    fn num(&self) -> Option<f64> {
+        match self {
+            Obj::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+struct Object {
+    value: Obj,
+    // 스트림이면 필터를 풀기 전 원본 바이트.
+    stream: Option<Vec<u8>>,
+}
+
+pub fn text(data: &[u8]) -> Result<String, String> {
+    if !data.starts_with(b"%PDF") {
+        return Err("invalid PDF: missing %PDF header".to_string());
+    }
+    // /Encrypt는 보통 객체가 아니라 trailer 사전에 있다.
+    if find(data, b"/Encrypt", 0).is_some() {
+        return Err("encrypted PDF is not supported".to_string());
+    }
+    let doc = Document::parse(data);
+    let pages = doc.pages();
+    if pages.is_empty() {
+        return Err("invalid PDF: no pages found".to_string());
+    }
+    let mut out = Vec::new();
+    for page in pages {
+        let page_text = doc.page_text(&page);
+        if !page_text.is_empty() {
+            out.push(page_text);
+        }
+    }
+    Ok(out.join("\n"))
+}
+
+struct Document {
+    objects: HashMap<u32, Object>,
+}
+
+// 쪽 하나: 내용 스트림 목록과(부모에서 물려받았을 수도 있는) 자원 사전.
+struct Page {
+    contents: Obj,
+    resources: Obj,
+}
+
+impl Document {
+    fn parse(data: &[u8]) -> Document {
+        let mut objects = HashMap::new();
+        let mut pos = 0;
+        while let Some(found) = find(data, b"obj", pos) {
+            pos = found + 3;
+            let Some(id) = object_id(data, found) else {
+                continue;
+            };
+            if data
+                .get(pos)
+                .is_some_and(|b| !is_delimiter(*b) && !b.is_ascii_whitespace())
+            {
+                continue;
+            }
+            let mut lexer = Lexer { data, pos };
+            let Some(value) = lexer.value() else {
+                continue;
+            };
+            let mut stream = None;
+            lexer.skip_space();
+            if data[lexer.pos..].starts_with(b"stream") {
+                let mut start = lexer.pos + 6;
+                if data[start..].starts_with(b"\r\n") {
+                    start += 2;
+                } else if data[start..].starts_with(b"\n") || data[start..].starts_with(b"\r") {
+                    start += 1;
+                }
+                let declared = value
+                    .get("Length")
+                    .and_then(Obj::num)
+                    .map(|n| start + n as usize)
+                    .filter(|&end| {
+                        end <= data.len()
+                            && find(data, b"endstream", end).is_some_and(|e| e - end <= 2)
+                    });
+                let end = match declared {
+                    Some(end) => end,
+                    None => find(data, b"endstream", start).unwrap_or(data.len()),
+                };
+                let body = data[start..end].to_vec();
+                pos = end;
+                stream = Some(body);
+            } else {
+                pos = lexer.pos;
+            }
+            objects.insert(id, Object { value, stream });
+        }
+        let mut doc = Document { objects };
+        doc.unpack_object_streams();
+        doc
+    }
+
+    // PDF 1.5부터는 글꼴 사전 같은 작은 객체를 압축된 /ObjStm 안에 모아 둔다.
+    fn unpack_object_streams(&mut self) {
+        let mut found = Vec::new();
+        for object in self.objects.values() {
+            if object.value.get("Type").and_then(Obj::name) != Some("ObjStm") {
+                continue;
+            }
+            let Some(data) = self.decoded(object) else {
+                continue;
+            };
+            let count = object.value.get("N").and_then(Obj::num).unwrap_or(0.0) as usize;
+            let first = object.value.get("First").and_then(Obj::num).unwrap_or(0.0) as usize;
+            let mut header = Lexer {
+                data: &data,
+                pos: 0,
+            };
+            for _ in 0..count {
+                let (Some(Obj::Num(id)), Some(Obj::Num(offset))) = (header.value(), header.value())
+                else {
+                    break;
+                };
+                let mut lexer = Lexer {
+                    data: &data,
+                    pos: first + offset as usize,
+                };
+                if let Some(value) = lexer.value() {
+                    found.push((id as u32, value));
+                }
+            }
+        }
+        for (id, value) in found {
+            self.objects.entry(id).or_insert(Object {
+                value,
+                stream: None,
+            });
+        }
+    }
+
+    fn resolve<'a>(&'a self, obj: &'a Obj) -> &'a Obj {
+        let mut current = obj;
+        // 참조가 참조를 가리키는 경우도 몇 단계까지는 따라간다.
+        for _ in 0..8 {
+            match current {
+                Obj::Ref(id) => match self.objects.get(id) {
+                    Some(object) => current = &object.value,
+                    None => return &Obj::Null,
+                },
+                _ => return current,
+            }
+        }
+        current
+    }
+
+    fn decoded(&self, object: &Object) -> Option<Vec<u8>> {
+        let raw = object.stream.as_ref()?;
+        let filters = match object.value.get("Filter").map(|f| self.resolve(f)) {
+            None | Some(Obj::Null) => Vec::new(),
+            Some(Obj::Name(name)) => vec![name.clone()],
+            Some(Obj::Array(items)) => items
+                .iter()
+                .filter_map(|f| f.name().map(String::from))
+                .collect(),
+            Some(_) => return None,
+        };
+        let mut data = raw.clone();
+        for filter in filters {
+            data = match filter.as_str() {
+                // zlib 머리 2바이트를 떼고 DEFLATE로 푼다(끝의 Adler-32는 보지 않는다).
+                "FlateDecode" | "Fl" => inflate::inflate(data.get(2..)?).ok()?,
+                _ => return None,
+            };
+        }
+        Some(data)
+    }
+
+    fn stream_of(&self, obj: &Obj) -> Option<Vec<u8>> {
+        let Obj::Ref(id) = obj else {
+            return None;
+        };
+        self.decoded(self.objects.get(id)?)
+    }
+
+    fn pages(&self) -> Vec<Page> {
+        let root = self
+            .objects
+            .values()
+            .find(|o| o.value.get("Type").and_then(Obj::name) == Some("Catalog"))
+            .and_then(|catalog| catalog.value.get("Pages"));
+        let mut pages = Vec::new();
+        if let Some(root) = root {
+            self.collect_pages(root, &Obj::Null, &mut pages, 0);
+        }
+        if pages.is_empty() {
+            // 카탈로그가 깨졌으면 객체 번호 순으로 /Page를 모은다.
+            let mut ids: Vec<&u32> = self.objects.keys().collect();
+            ids.sort();
+            for id in ids {
+                let page = &self.objects[id].value;
+                if page.get("Type").and_then(Obj::name) == Some("Page") {
+                    pages.push(Page {
+                        contents: page.get("Contents").cloned().unwrap_or(Obj::Null),
+                        resources: page.get("Resources").cloned().unwrap_or(Obj::Null),
+                    });
+                }
+            }
+        }
+        pages
+    }
+
+    fn collect_pages(&self, node: &Obj, inherited: &Obj, pages: &mut Vec<Page>, depth: usize) {
+        if depth > 32 {
+            return;
+        }
+        let dict = self.resolve(node);
+        let resources = dict.get("Resources").unwrap_or(inherited);
+        match dict.get("Type").and_then(Obj::name) {
+            Some("Pages") => {
+                if let Some(Obj::Array(kids)) = dict.get("Kids").map(|k| self.resolve(k)) {
+                    for kid in kids {
+                        self.collect_pages(kid, resources, pages, depth + 1);
+                    }
+                }
+            }
+            Some("Page") => pages.push(Page {
+                contents: dict.get("Contents").cloned().unwrap_or(Obj::Null),
+                resources: resources.clone(),
+            }),
+            _ => {}
+        }
+    }
+
+    fn page_text(&self, page: &Page) -> String {
+        let mut content = Vec::new();
+        let parts = match self.resolve(&page.contents) {
+            Obj::Array(items) => items.clone(),
+            _ => vec![page.contents.clone()],
+        };
+        for part in &parts {
+            if let Some(data) = self.stream_of(part) {
+                content.extend_from_slice(&data);
+                content.push(b'\n');
+            }
+        }
+        let fonts = self.fonts(&page.resources);
+        show_text(&content, &fonts)
+    }
+
+    fn fonts(&self, resources: &Obj) -> HashMap<String, Font> {
+        let mut fonts = HashMap::new();
+        let Obj::Dict(entries) = self
+            .resolve(resources)
+            .get("Font")
+            .map_or(&Obj::Null, |f| self.resolve(f))
+        else {
+            return fonts;
+        };
+        for (name, font) in entries {
+            let font = self.resolve(font);
+            let identity = font
+                .get("Encoding")
+                .and_then(Obj::name)
+                .is_some_and(|e| e.starts_with("Identity"));
+            let cmap = font
+                .get("ToUnicode")
+                .and_then(|r| self.stream_of(r))
+                .map(|data| parse_cmap(&data));
+            let mut parsed = cmap.unwrap_or_default();
+            // Identity-H/V 글꼴(CID 글꼴)의 코드는 2바이트다.
+            if identity {
+                parsed.code_len = parsed.code_len.max(2);
+            }
+            fonts.insert(name.clone(), parsed);
+        }
+        fonts
+    }
+}
+
+// "12 0 obj"에서 obj 앞의 두 숫자 중 객체 번호. 세대 번호는 쓰지 않는다.
+fn object_id(data: &[u8], obj: usize) -> Option<u32> {
+    let before = &data[..obj];
+    let trim = |s: &[u8]| -> usize {
+        s.len()
+            - s.iter()
+                .rev()
+                .take_while(|b| b.is_ascii_whitespace())
+                .count()
+    };
+    let end = trim(before);
+    if end == before.len() {
+        return None;
+    }
+    let gen_start = end
+        - before[..end]
+            .iter()
+            .rev()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+    if gen_start == end {
+        return None;
+    }
+    let id_end = trim(&before[..gen_start]);
+    if id_end == gen_start {
+        return None;
+    }
+    let id_start = id_end
+        - before[..id_end]
+            .iter()
+            .rev()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+    if id_start == id_end || (id_start > 0 && !before[id_start - 1].is_ascii_whitespace()) {
+        return None;
+    }
+    std::str::from_utf8(&before[id_start..id_end])
+        .ok()?
+        .parse()
+        .ok()
+}
+
+fn find(data: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    data.get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| from + i)
+}
+
+fn is_delimiter(b: u8) -> bool {
+    matches!(
+        b,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
+
+struct Lexer<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Lexer<'_> {
+    fn skip_space(&mut self) {
+        while let Some(&b) = self.data.get(self.pos) {
+            if b.is_ascii_whitespace() || b == 0 {
+                self.pos += 1;
+            } else if b == b'%' {
+                while self
+                    .data
+                    .get(self.pos)
+                    .is_some_and(|&b| b != b'\n' && b != b'\r')
+                {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    // 값 하나. 숫자 뒤에 "G R"이 오면 참조로 묶는다.
+    fn value(&mut self) -> Option<Obj> {
+        let token = self.token()?;
+        if let Obj::Num(id) = token {
+            let saved = self.pos;
+            if let (Some(Obj::Num(_)), Some(Obj::Keyword(k))) = (self.token(), self.token()) {
+                if k == "R" {
+                    return Some(Obj::Ref(id as u32));
+                }
+            }
+            self.pos = saved;
+        }
+        match token {
+            Obj::Keyword(k) if k == "[" => {
+                let mut items = Vec::new();
+                loop {
+                    self.skip_space();
+                    if self.data.get(self.pos) == Some(&b']') {
+                        self.pos += 1;
+                        return Some(Obj::Array(items));
+                    }
+                    items.push(self.value()?);
+                }
+            }
+            Obj::Keyword(k) if k == "<<" => {
+                let mut entries = Vec::new();
+                loop {
+                    self.skip_space();
+                    if self.data[self.pos..].starts_with(b">>") {
+                        self.pos += 2;
+                        return Some(Obj::Dict(entries));
+                    }
+                    let Obj::Name(key) = self.token()? else {
+                        return None;
+                    };
+                    entries.push((key, self.value()?));
+                }
+            }
+            other => Some(other),
+        }
+    }
+
+    fn token(&mut self) -> Option<Obj> {
+        self.skip_space();
+        let &b = self.data.get(self.pos)?;
+        match b {
+            b'[' | b']' | b'{' | b'}' => {
+                self.pos += 1;
+                Some(Obj::Keyword((b as char).to_string()))
+            }
+            b'<' if self.data.get(self.pos + 1) == Some(&b'<') => {
+                self.pos += 2;
+                Some(Obj::Keyword("<<".to_string()))
+            }
+            b'>' if self.data.get(self.pos + 1) == Some(&b'>') => {
+                self.pos += 2;
+                Some(Obj::Keyword(">>".to_string()))
+            }
+            b'<' => {
+                let end = find(self.data, b">", self.pos)?;
+                let digits: Vec<u8> = self.data[self.pos + 1..end]
+                    .iter()
+                    .copied()
+                    .filter(u8::is_ascii_hexdigit)
+                    .collect();
+                self.pos = end + 1;
+                Some(Obj::Str(hex_bytes(&digits)))
+            }
+            b'(' => Some(Obj::Str(self.literal())),
+            b'/' => {
+                self.pos += 1;
+                let raw = self.word();
+                Some(Obj::Name(decode_name(&raw)))
+            }
+            _ => {
+                let raw = self.word();
+                if raw.is_empty() {
+                    // 짝 없는 ')' 같은 것은 건너뛴다.
+                    self.pos += 1;
+                    return Some(Obj::Keyword(String::new()));
+                }
+                let text = String::from_utf8_lossy(&raw).into_owned();
+                Some(match text.as_str() {
+                    "true" => Obj::Bool(true),
+                    "false" => Obj::Bool(false),
+                    "null" => Obj::Null,
+                    _ => match text.parse::<f64>() {
+                        Ok(n)
+                            if raw[0].is_ascii_digit() || matches!(raw[0], b'-' | b'+' | b'.') =>
+                        {
+                            Obj::Num(n)
+                        }
+                        _ => Obj::Keyword(text),
+                    },
+                })
+            }
+        }
+    }
+
+    fn word(&mut self) -> Vec<u8> {
+        let start = self.pos;
+        while let Some(&b) = self.data.get(self.pos) {
+            if b.is_ascii_whitespace() || is_delimiter(b) || b == 0 {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.data[start..self.pos].to_vec()
+    }
+
+    // (괄호 안(중첩 가능)의 글자). \n, \(, \ddd(8진수) 같은 escape를 푼다.
+    fn literal(&mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut depth = 0;
+        self.pos += 1;
+        while let Some(&b) = self.data.get(self.pos) {
+            self.pos += 1;
+            match b {
+                b'(' => {
+                    depth += 1;
+                    out.push(b);
+                }
+                b')' if depth == 0 => break,
+                b')' => {
+                    depth -= 1;
+                    out.push(b);
+                }
+                b'\\' => {
+                    let Some(&next) = self.data.get(self.pos) else {
+                        break;
+                    };
+                    self.pos += 1;
+                    match next {
+                        b'n' => out.push(b'\n'),
+                        b'r' => out.push(b'\r'),
+                        b't' => out.push(b'\t'),
+                        b'b' => out.push(8),
+                        b'f' => out.push(12),
+                        b'0'..=b'7' => {
+                            let mut code = u32::from(next - b'0');
+                            for _ in 0..2 {
+                                match self.data.get(self.pos) {
+                                    Some(&d @ b'0'..=b'7') => {
+                                        code = code * 8 + u32::from(d - b'0');
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            out.push(code as u8);
+                        }
+                        // 줄 끝의 \는 줄을 잇는다.
+                        b'\r' => {
+                            if self.data.get(self.pos) == Some(&b'\n') {
+                                self.pos += 1;
+                            }
+                        }
+                        b'\n' => {}
+                        other => out.push(other),
+                    }
+                }
+                _ => out.push(b),
+            }
+        }
+        out
+    }
+}
+
+fn hex_bytes(digits: &[u8]) -> Vec<u8> {
+    let value = |d: u8| (d as char).to_digit(16).unwrap_or(0) as u8;
+    digits
+        .chunks(2)
+        .map(|pair| value(pair[0]) << 4 | pair.get(1).map_or(0, |&d| value(d)))
+        .collect()
+}
+
+// 이름 안의 #xx는 그 바이트다(/Times#20Roman).
+fn decode_name(raw: &[u8]) -> String {
+    let mut out = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] == b'#' && i + 2 < raw.len() {
+            out.extend(hex_bytes(&raw[i + 1..i + 3]));
+            i += 3;
+        } else {
+            out.push(raw[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// 글꼴의 글자 코드 -> 유니코드. code_len은 코드 하나의 바이트 수.
+#[derive(Debug, Default)]
+struct Font {
+    code_len: usize,
+    map: HashMap<u32, String>,
+}
+
+impl Font {
+    fn decode(&self, bytes: &[u8], out: &mut String) {
+        let len = self.code_len.max(1);
+        for chunk in bytes.chunks(len) {
+            let code = chunk.iter().fold(0u32, |acc, &b| acc << 8 | u32::from(b));
+            match self.map.get(&code) {
+                Some(text) => out.push_str(text),
+                None if len == 1 => out.push(latin1(chunk[0])),
+                None => out.extend(char::from_u32(code)),
+            }
+        }
+    }
+}
+
+// 글꼴 사전에 CMap이 없을 때의 단일 바이트 코드. WinAnsi의 따옴표와 대시만 따로 맞춘다.
+fn latin1(b: u8) -> char {
+    match b {
+        0x91 => '\u{2018}',
+        0x92 => '\u{2019}',
+        0x93 => '\u{201C}',
+        0x94 => '\u{201D}',
+        0x96 => '\u{2013}',
+        0x97 => '\u{2014}',
+        b => b as char,
+    }
+}
+
+// /ToUnicode CMap의 codespacerange, bfchar, bfrange만 읽는다.
+fn parse_cmap(data: &[u8]) -> Font {
+    let mut font = Font {
+        code_len: 1,
+        map: HashMap::new(),
+    };
+    let mut lexer = Lexer { data, pos: 0 };
+    let mut mode = "";
+    let mut operands: Vec<Obj> = Vec::new();
+    let code = |bytes: &[u8]| bytes.iter().fold(0u32, |acc, &b| acc << 8 | u32::from(b));
+    while lexer.pos < data.len() {
+        let Some(token) = lexer.value() else {
+            break;
+        };
+        match token {
+            Obj::Keyword(k) => {
+                match k.as_str() {
+                    "begincodespacerange" => mode = "codespace",
+                    "beginbfchar" => mode = "bfchar",
+                    "beginbfrange" => mode = "bfrange",
+                    "endcodespacerange" | "endbfchar" | "endbfrange" => mode = "",
+                    _ => {}
+                }
+                operands.clear();
+            }
+            value => {
+                operands.push(value);
+                match (mode, operands.as_slice()) {
+                    ("codespace", [Obj::Str(lo), Obj::Str(_)]) => {
+                        font.code_len = lo.len().max(1);
+                        operands.clear();
+                    }
+                    ("bfchar", [Obj::Str(src), Obj::Str(dst)]) => {
+                        font.map.insert(code(src), utf16(dst));
+                        operands.clear();
+                    }
+                    ("bfrange", [Obj::Str(lo), Obj::Str(hi), dst]) => {
+                        let (lo, hi) = (code(lo), code(hi));
+                        match dst {
+                            Obj::Str(start) => {
+                                let mut start = start.clone();
+                                for c in lo..=hi.min(lo + 0xFFFF) {
+                                    font.map.insert(c, utf16(&start));
+                                    if let Some(last) = start.last_mut() {
+                                        *last = last.wrapping_add(1);
+                                    }
+                                }
+                            }
+                            Obj::Array(items) => {
+                                for (c, item) in (lo..=hi).zip(items) {
+                                    if let Obj::Str(dst) = item {
+                                        font.map.insert(c, utf16(dst));
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                        operands.clear();
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    font
+}
+
+fn utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks(2)
+        .map(|pair| u16::from(pair[0]) << 8 | u16::from(*pair.get(1).unwrap_or(&0)))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+// 내용 스트림의 글자 연산자만 따라간다. 그림 연산자는 피연산자와 함께 버린다.
+fn show_text(content: &[u8], fonts: &HashMap<String, Font>) -> String {
+    let fallback = Font {
+        code_len: 1,
+        map: HashMap::new(),
+    };
+    let mut out = String::new();
+    let mut font = &fallback;
+    let mut operands: Vec<Obj> = Vec::new();
+    let mut lexer = Lexer {
+        data: content,
+        pos: 0,
+    };
+    let mut line_y: Option<f64> = None;
+    let newline = |out: &mut String| {
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+    };
+    let space = |out: &mut String| {
+        if !out.is_empty() && !out.ends_with([' ', '\n']) {
+            out.push(' ');
+        }
+    };
+    while lexer.pos < content.len() {
+        let Some(token) = lexer.value() else {
+            break;
+        };
+        let Obj::Keyword(op) = token else {
+            operands.push(token);
+            continue;
+        };
+        match op.as_str() {
+            "Tf" => {
+                if let Some(Obj::Name(name)) = operands.first() {
+                    font = fonts.get(name).unwrap_or(&fallback);
+                }
+            }
+            "Tj" => {
+                if let Some(Obj::Str(s)) = operands.last() {
+                    font.decode(s, &mut out);
+                }
+            }
+            "'" | "\"" => {
+                newline(&mut out);
+                if let Some(Obj::Str(s)) = operands.last() {
+                    font.decode(s, &mut out);
+                }
+            }
+            "TJ" => {
+                if let Some(Obj::Array(items)) = operands.last() {
+                    for item in items {
+                        match item {
+                            Obj::Str(s) => font.decode(s, &mut out),
+                            // 글자 폭의 1/1000 단위. 크게 물러나면 단어 사이로 본다.
+                            Obj::Num(n) if *n < -250.0 => space(&mut out),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            "Td" | "TD" => match operands.get(1).and_then(Obj::num) {
+                Some(ty) if ty != 0.0 => newline(&mut out),
+                _ => space(&mut out),
+            },
+            "Tm" => {
+                let y = operands.get(5).and_then(Obj::num);
+                if y.is_some() && line_y.is_some() && y != line_y {
+                    newline(&mut out);
+                } else {
+                    space(&mut out);
+                }
+                line_y = y;
+            }
+            "T*" | "ET" => newline(&mut out),
+            // 인라인 그림의 원본 바이트는 EI까지 통째로 건너뛴다.
+            "ID" => {
+                lexer.pos = find(content, b"EI", lexer.pos).map_or(content.len(), |i| i + 2);
+            }
+            _ => {}
+        }
+        operands.clear();
+    }
+    out.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
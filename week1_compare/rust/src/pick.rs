@@ -0,0 +1,161 @@
+// pick 서브커맨드: 줄 목록에서 하나를 고르는 최소한의 퍼지 파인더.
+// 글자를 치면 목록이 좁혀지고, 위/아래(Ctrl-P/Ctrl-N)로 고르고, Enter로 확정한다.
+// Esc나 Ctrl-C는 아무것도 고르지 않고 끝낸다. 목록 맨 위에서 위 화살표를 누르면 이전 질의를,
+// Ctrl-R은 bash처럼 기록을 거슬러 검색한다(history.rs). 고른 질의는 기록에 남는다. 화면은 대체 화면 버퍼에 그려서
+// 끝난 뒤 원래 터미널 내용이 그대로 돌아온다.
+
+use std::io;
+
+use crate::fuzzy;
+use crate::history::History;
+use crate::term;
+use crate::tty::{Key, Terminal};
+
+const ALT_SCREEN_ON: &str = "\x1b[?1049h";
+const ALT_SCREEN_OFF: &str = "\x1b[?1049l";
+
+// 화면 폭에 맞게 자른다. 탭은 한 칸 공백으로 바꿔 줄이 밀리지 않게 한다.
+fn fit(line: &str, width: usize) -> String {
+    line.chars()
+        .map(|c| if c == '\t' { ' ' } else { c })
+        .filter(|c| !c.is_control())
+        .take(width)
+        .collect()
+}
+
+// Ctrl-R 중이면 (찾는 말, 찾은 기록 번호).
+struct Search {
+    needle: String,
+    found: Option<usize>,
+}
+
+fn render(prompt: &str, lines: &[&str], matches: &[usize], selected: usize, top: usize) -> String {
+    let rows = term::terminal_rows().unwrap_or(24).max(2);
+    let cols = term::terminal_cols().unwrap_or(80).max(4);
+    let mut screen = String::from("\x1b[H\x1b[2J");
+    let status = format!("  {}/{}", matches.len(), lines.len());
+    screen.push_str(&fit(prompt, cols));
+    screen.push_str(&format!(
+        "\x1b[2m{}\x1b[0m",
+        fit(&status, cols.saturating_sub(prompt.chars().count()))
+    ));
+    for (row, &index) in matches.iter().enumerate().skip(top).take(rows - 1) {
+        screen.push_str("\r\n");
+        let text = fit(lines[index], cols - 2);
+        if row == selected {
+            screen.push_str(&format!("\x1b[7m> {text}\x1b[0m"));
+        } else {
+            screen.push_str(&format!("  {text}"));
+        }
+    }
+    // 커서를 입력 줄 끝으로 돌려놓는다.
+    let col = prompt.chars().count().min(cols) + 1;
+    screen.push_str(&format!("\x1b[1;{col}H"));
+    screen
+}
+
+pub fn pick(lines: &[&str]) -> Result<Option<String>, String> {
+    let mut terminal = Terminal::open()?;
+    let result = session(&mut terminal, lines);
+    let _ = terminal.write(ALT_SCREEN_OFF);
+    result.map_err(|e| format!("pick: {e}"))
+}
+
+fn session(terminal: &mut Terminal, lines: &[&str]) -> io::Result<Option<String>> {
+    terminal.write(ALT_SCREEN_ON)?;
+    let mut history = History::load();
+    let mut query = String::new();
+    let mut selected = 0usize;
+    let mut top = 0usize;
+    // 위 화살표로 불러온 기록의 번호. 글자를 고치면 다시 None.
+    let mut recalled: Option<usize> = None;
+    let mut search: Option<Search> = None;
+    loop {
+        let matches = fuzzy::rank(&query, lines);
+        selected = selected.min(matches.len().saturating_sub(1));
+        // 선택한 줄이 화면 밖으로 나가지 않도록 보이는 범위를 옮긴다.
+        let visible = term::terminal_rows().unwrap_or(24).max(2) - 1;
+        if selected < top {
+            top = selected;
+        } else if selected >= top + visible {
+            top = selected + 1 - visible;
+        }
+        let prompt = match &search {
+            Some(s) => format!(
+                "(reverse-i-search)`{}': {}",
+                s.needle,
+                s.found.and_then(|i| history.get(i)).unwrap_or_default()
+            ),
+            None => format!("> {query}"),
+        };
+        terminal.write(&render(&prompt, lines, &matches, selected, top))?;
+
+        let key = terminal.read_key()?;
+        if let Some(s) = &mut search {
+            match key {
+                Key::Char(c) => {
+                    s.needle.push(c);
+                    s.found = history.search(&s.needle, s.found.map_or(history.len(), |i| i + 1));
+                }
+                Key::Backspace => {
+                    s.needle.pop();
+                    s.found = history.search(&s.needle, history.len());
+                }
+                // 한 번 더 누르면 더 오래된 일치로.
+                Key::Search => {
+                    if let Some(i) = s.found {
+                        s.found = history.search(&s.needle, i).or(s.found);
+                    }
+                }
+                // Enter는 찾은 질의를 입력 줄에 넣고 검색만 끝낸다(곧바로 고르지 않는다).
+                Key::Enter | Key::Up | Key::Down => {
+                    if let Some(entry) = s.found.and_then(|i| history.get(i)) {
+                        query = entry.to_string();
+                        selected = 0;
+                        top = 0;
+                    }
+                    search = None;
+                }
+                Key::Cancel => search = None,
+                Key::Other => {}
+            }
+            continue;
+        }
+
+        match key {
+            Key::Enter => {
+                history.push(&query);
+                return Ok(matches.get(selected).map(|&i| lines[i].to_string()));
+            }
+            Key::Cancel => return Ok(None),
+            Key::Up if selected == 0 => {
+                let older = recalled.unwrap_or(history.len()).checked_sub(1);
+                if let Some(entry) = older.and_then(|i| history.get(i)) {
+                    query = entry.to_string();
+                    recalled = older;
+                }
+            }
+            Key::Up => selected -= 1,
+            Key::Down => selected += 1,
+            Key::Search => {
+                search = Some(Search {
+                    needle: String::new(),
+                    found: None,
+                })
+            }
+            Key::Backspace => {
+                query.pop();
+                selected = 0;
+                top = 0;
+                recalled = None;
+            }
+            Key::Char(c) => {
+                query.push(c);
+                selected = 0;
+                top = 0;
+                recalled = None;
+            }
+            Key::Other => {}
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// 정규화 파이프라인(설정 파일의 normalize). 토큰을 자른 다음 단계들을 적힌 순서대로
+// 하나씩 거친다. 같은 단계라도 순서에 따라 결과가 달라지므로(불용어를 어간 추출 전에
+// 거를지 뒤에 거를지) 팀마다 정한 순서를 설정 파일로 남겨 버전 관리할 수 있다.
+// Python이라면 함수 리스트를 reduce로 차례로 적용하는 자리다.
+
+use std::collections::BTreeSet;
+
+use crate::collate;
+use crate::stem::{self, Language};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    Lowercase,
+    StripDiacritics,
+    Stem(Language),
+    // (설정에 적힌 목록 이름, 단어). 걸린 토큰은 그 자리에서 빠진다.
+    Stopwords(String, BTreeSet<String>),
+}
+
+impl Step {
+    // 설정 파일에 쓰는 이름 그대로: "lowercase", "stem:en", "stopwords:en".
+    pub fn name(&self) -> String {
+        match self {
+            Step::Lowercase => "lowercase".to_string(),
+            Step::StripDiacritics => "strip_diacritics".to_string(),
+            Step::Stem(lang) => format!("stem:{}", lang.code()),
+            Step::Stopwords(name, _) => format!("stopwords:{name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pipeline {
+    pub steps: Vec<Step>,
+}
+
+impl Pipeline {
+    // 토큰 하나를 끝까지 통과시킨다. 불용어 단계에서 걸리면 None.
+    pub fn apply(&self, word: &str) -> Option<String> {
+        let mut word = word.to_string();
+        for step in &self.steps {
+            match step {
+                Step::Lowercase => word = word.to_lowercase(),
+                Step::StripDiacritics => word = collate::strip_diacritics(&word),
+                Step::Stem(lang) => word = stem::stem(&word, *lang),
+                Step::Stopwords(_, words) => {
+                    if words.contains(&word) {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(word)
+    }
+
+    pub fn describe(&self) -> String {
+        let names: Vec<String> = self.steps.iter().map(Step::name).collect();
+        names.join(" -> ")
+    }
+}
@@ -0,0 +1,76 @@
+// positions 서브커맨드: --word가 나오는 자리마다 (줄, 열, 바이트 오프셋)을 낸다.
+// 편집기 플러그인이 상위 단어 표에서 고른 단어의 위치로 바로 넘어갈 수 있게 하려는 것이다.
+// 원문 토큰을 하나씩 본문과 같은 규칙으로 정규화해 비교하므로 "Rust"도 rust에 걸리고
+// --stem이면 "running"도 run에 걸린다. --hyphens join처럼 토큰 여러 개를 한 단어로 합치는 규칙의
+// 합친 단어는 찾지 못한다. 줄과 열은 1부터(열은 글자 수), 오프셋은 파일 처음부터의 바이트다.
+
+use std::collections::HashMap;
+
+use crate::json::{ToJson, Value};
+use crate::{normalize_words, tokens, Tokenizer};
+
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+impl ToJson for Position {
+    fn to_json(&self) -> Value {
+        Value::Object(vec![
+            ("line".to_string(), Value::Number(self.line as f64)),
+            ("column".to_string(), Value::Number(self.column as f64)),
+            ("offset".to_string(), Value::Number(self.offset as f64)),
+        ])
+    }
+}
+
+// word가 정규화해서 단어 하나가 되지 않으면(빈 문자열, "a-b" 등) 오류다.
+pub fn target(word: &str, tok: Tokenizer) -> Result<String, String> {
+    match normalize_words(word, tok).as_slice() {
+        [one] => Ok(one.clone()),
+        _ => Err(format!("--word must be a single word, got {word:?}")),
+    }
+}
+
+pub fn find(text: &str, target: &str, tok: Tokenizer) -> Vec<Position> {
+    let mut found = Vec::new();
+    let mut line_start = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        for token in tokens(line, tok.unicode) {
+            if !normalize_words(token, tok).iter().any(|w| w == target) {
+                continue;
+            }
+            // tokens()는 원문에서 빌린 조각이므로 주소 차이가 줄 안의 바이트 위치다.
+            let at = token.as_ptr() as usize - line.as_ptr() as usize;
+            found.push(Position {
+                line: i + 1,
+                column: line[..at].chars().count() + 1,
+                offset: line_start + at,
+            });
+        }
+        line_start += line.len();
+    }
+    found
+}
+
+// --with-positions: 상위 단어마다 처음과 마지막으로 나온 줄(1부터). 토큰은 줄을 넘지 않으므로
+// 줄마다 본문과 같은 규칙으로 단어를 뽑으면 --hyphens join으로 합친 단어도 찾는다.
+// 본문에 없는 단어(--only-words로 0을 채운 것)는 None이다.
+pub fn spans(text: &str, rows: &[(String, usize)], tok: Tokenizer) -> Vec<Option<(usize, usize)>> {
+    let index: HashMap<&str, usize> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, (word, _))| (word.as_str(), i))
+        .collect();
+    let mut spans = vec![None; rows.len()];
+    for (i, line) in text.lines().enumerate() {
+        for word in normalize_words(line, tok) {
+            if let Some(&at) = index.get(word.as_str()) {
+                let span: &mut (usize, usize) = spans[at].get_or_insert((i + 1, i + 1));
+                span.1 = i + 1;
+            }
+        }
+    }
+    spans
+}
@@ -0,0 +1,90 @@
+// --preset NAME: 글 종류에 맞는 옵션 묶음. 옵션 열두 개를 익히지 않아도 한 번에 고르게 하려는 것이다.
+// 묶음은 명령행 맨 앞에 끼워 넣은 옵션과 같으므로, 같은 옵션을 따로 주면 뒤에 준 값이 이긴다
+// (--preset code --top 5는 상위 5개).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    // 로그 줄: 수준별 메시지 단어. 숫자(포트, 소요 시간)는 빼고 connection-reset 같은 말은 한 덩어리로.
+    Logs,
+    // 산문: 유니코드 글자, 축약형 풀기(don't -> do not).
+    Prose,
+    // 소스 코드: HttpClient와 httpClient를 따로, 하이픈 이름은 한 덩어리로 세고 숫자는 뺀다.
+    Code,
+    // 소셜 글: 이모지도 토큰으로, 유니코드 글자, 축약형 풀기.
+    Social,
+}
+
+impl Preset {
+    pub fn parse(name: &str) -> Option<Preset> {
+        match name {
+            "logs" | "log" => Some(Preset::Logs),
+            "prose" => Some(Preset::Prose),
+            "code" => Some(Preset::Code),
+            "social" => Some(Preset::Social),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Logs => "logs",
+            Preset::Prose => "prose",
+            Preset::Code => "code",
+            Preset::Social => "social",
+        }
+    }
+
+    pub fn flags(self) -> &'static [&'static str] {
+        match self {
+            Preset::Logs => &[
+                "--log",
+                "--hyphens",
+                "keep",
+                "--numbers",
+                "drop",
+                "--top",
+                "20",
+            ],
+            Preset::Prose => &["--unicode", "--contractions", "expand", "--top", "10"],
+            Preset::Code => &[
+                "--case",
+                "preserve",
+                "--hyphens",
+                "keep",
+                "--numbers",
+                "drop",
+                "--tie-break",
+                "alpha",
+                "--top",
+                "20",
+            ],
+            Preset::Social => &[
+                "--unicode",
+                "--emoji",
+                "--contractions",
+                "expand",
+                "--top",
+                "20",
+            ],
+        }
+    }
+}
+
+// argv에서 --preset NAME을 빼고 그 자리 대신 프로그램 이름 바로 뒤에 묶음을 펼친다.
+pub fn expand(mut args: Vec<String>) -> Result<(Vec<String>, Option<Preset>), String> {
+    let Some(at) = args.iter().position(|a| a == "--preset") else {
+        return Ok((args, None));
+    };
+    let name = args
+        .get(at + 1)
+        .ok_or_else(|| "missing value for --preset".to_string())?;
+    let preset = Preset::parse(name)
+        .ok_or_else(|| format!("--preset must be logs, prose, code, or social, got {name}"))?;
+    args.drain(at..at + 2);
+    if args.iter().any(|a| a == "--preset") {
+        return Err("--preset can be given only once".to_string());
+    }
+    let at = 1.min(args.len());
+    args.splice(at..at, preset.flags().iter().map(|f| f.to_string()));
+    Ok((args, Some(preset)))
+}
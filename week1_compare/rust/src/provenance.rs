@@ -0,0 +1,84 @@
+// --provenance: json/html 보고서에 입력마다 SHA-256, 크기, 수정 시각을 싣는다.
+// 보관해 둔 분석 결과가 정확히 어느 입력에서 나왔는지 나중에 되짚으려는 것이다.
+// 해시와 크기는 분석한 바이트 그대로다(--journal과 같다). stdin과 클립보드에는 수정 시각이 없고,
+// 읽지 못한 입력은 셋 다 null이다.
+
+use std::fmt::Write as _;
+use std::fs;
+
+use crate::escape_html;
+use crate::input::{self, Source};
+use crate::json::{ToJson, Value};
+use crate::schedule;
+use crate::sha256::Sha256;
+
+pub struct Input {
+    pub path: String,
+    pub size: Option<usize>,
+    pub sha256: Option<String>,
+    // RFC 3339 UTC.
+    pub mtime: Option<String>,
+}
+
+pub fn inputs(source: &Source) -> Vec<Input> {
+    input::read_parts(source)
+        .into_iter()
+        .map(|(path, data)| {
+            let (size, sha256) = match data {
+                Ok(data) => {
+                    let mut hasher = Sha256::new();
+                    hasher.update(&data);
+                    (Some(data.len()), Some(hasher.hex_digest()))
+                }
+                Err(_) => (None, None),
+            };
+            let mtime = matches!(source, Source::Files(_))
+                .then(|| fs::metadata(&path).and_then(|m| m.modified()).ok())
+                .flatten()
+                .map(schedule::utc_timestamp);
+            Input {
+                path,
+                size,
+                sha256,
+                mtime,
+            }
+        })
+        .collect()
+}
+
+impl ToJson for Input {
+    fn to_json(&self) -> Value {
+        let text = |value: &Option<String>| value.clone().map_or(Value::Null, Value::String);
+        Value::Object(vec![
+            ("path".to_string(), Value::String(self.path.clone())),
+            (
+                "size".to_string(),
+                self.size.map_or(Value::Null, |n| Value::Number(n as f64)),
+            ),
+            ("sha256".to_string(), text(&self.sha256)),
+            ("mtime".to_string(), text(&self.mtime)),
+        ])
+    }
+}
+
+// html 보고서 끝의 "Inputs" 표. 값이 없는 칸은 비운다.
+pub fn html(inputs: &[Input], out: &mut String) {
+    let _ = writeln!(out, "<h2>Inputs</h2>");
+    let _ = writeln!(out, "<table>");
+    let _ = writeln!(
+        out,
+        "<thead><tr><th>path</th><th>size</th><th>modified</th><th>sha256</th></tr></thead>"
+    );
+    let _ = writeln!(out, "<tbody>");
+    for entry in inputs {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td class=\"num\">{}</td><td>{}</td><td><code>{}</code></td></tr>",
+            escape_html(&entry.path),
+            entry.size.map(|n| n.to_string()).unwrap_or_default(),
+            entry.mtime.as_deref().unwrap_or_default(),
+            entry.sha256.as_deref().unwrap_or_default()
+        );
+    }
+    let _ = writeln!(out, "</tbody>\n</table>");
+}
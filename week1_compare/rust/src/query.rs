@@ -0,0 +1,297 @@
+// `--query '(error OR warn) AND NOT timeout'` 를 위한 작은 불리언 질의 언어.
+// Python이라면 eval()이나 pyparsing으로 때울 수도 있지만, 여기서는
+// 렉서(lexer) → 재귀 하강 파서(parser) → 식 트리(Expr) 평가의 고전적인 3단계를 직접 만든다.
+//
+// 문법:
+//   or      := and ("OR" and)*
+//   and     := unary ("AND"? unary)*     -- 단어를 나란히 쓰면 AND로 본다
+//   unary   := "NOT" unary | primary
+//   primary := "(" or ")" | WORD | "\"quoted text\""
+// 연산자는 대문자(AND/OR/NOT) 또는 기호(&&, ||, !)로 쓴다. 소문자 and/or는 일반 단어다.
+
+use std::collections::HashSet;
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    // 정규화된 토큰 하나와 일치 (대소문자 무시).
+    Word(String),
+    // 따옴표로 감싼 텍스트: 소문자로 바꾼 줄에 부분 문자열로 포함되는지 본다.
+    Text(String),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+impl Expr {
+    // tokens는 줄을 normalize_words로 정규화한 결과, lowered는 소문자로 바꾼 원문 줄이다.
+    pub fn matches(&self, tokens: &HashSet<String>, lowered: &str) -> bool {
+        match self {
+            Expr::Word(w) => tokens.contains(w),
+            Expr::Text(t) => lowered.contains(t.as_str()),
+            Expr::And(a, b) => a.matches(tokens, lowered) && b.matches(tokens, lowered),
+            Expr::Or(a, b) => a.matches(tokens, lowered) || b.matches(tokens, lowered),
+            Expr::Not(a) => !a.matches(tokens, lowered),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Word(String),
+    Quoted(String),
+}
+
+// start/end는 원본 질의 문자열의 바이트 위치. 에러 메시지에서 위치를 알려주는 데 쓴다.
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryError {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+    // 고치는 방법 한 줄(diagnostic.rs가 "= hint:"로 붙인다).
+    pub hint: Option<&'static str>,
+}
+
+impl QueryError {
+    fn new(message: impl Into<String>, start: usize, end: usize) -> QueryError {
+        QueryError {
+            message: message.into(),
+            start,
+            end,
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: &'static str) -> QueryError {
+        self.hint = Some(hint);
+        self
+    }
+}
+
+pub fn parse(src: &str) -> Result<Expr, QueryError> {
+    let tokens = lex(src)?;
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        len: src.len(),
+    };
+    if parser.tokens.is_empty() {
+        return Err(QueryError::new("empty query", 0, 0)
+            .hint("give at least one word, e.g. --query 'error OR warn'"));
+    }
+    let expr = parser.parse_or()?;
+    if let Some(tok) = parser.peek() {
+        return Err(QueryError::new(
+            "unexpected token after end of expression",
+            tok.start,
+            tok.end,
+        )
+        .hint("remove it, or add a matching '(' before it"));
+    }
+    Ok(expr)
+}
+
+fn lex(src: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = src.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let single = |kind: TokenKind| Token {
+            kind,
+            start,
+            end: start + c.len_utf8(),
+        };
+        match c {
+            '(' => {
+                chars.next();
+                tokens.push(single(TokenKind::LParen));
+            }
+            ')' => {
+                chars.next();
+                tokens.push(single(TokenKind::RParen));
+            }
+            '!' => {
+                chars.next();
+                tokens.push(single(TokenKind::Not));
+            }
+            '&' | '|' => {
+                chars.next();
+                match chars.peek() {
+                    Some(&(_, next)) if next == c => {
+                        chars.next();
+                        let kind = if c == '&' {
+                            TokenKind::And
+                        } else {
+                            TokenKind::Or
+                        };
+                        tokens.push(Token {
+                            kind,
+                            start,
+                            end: start + 2,
+                        });
+                    }
+                    _ => {
+                        let hint = if c == '&' {
+                            "write && or AND (both sides must match)"
+                        } else {
+                            "write || or OR (either side may match)"
+                        };
+                        return Err(QueryError::new(
+                            format!("expected '{c}{c}'"),
+                            start,
+                            start + 1,
+                        )
+                        .hint(hint));
+                    }
+                }
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                let mut end = None;
+                for (i, ch) in chars.by_ref() {
+                    if ch == '"' {
+                        end = Some(i + 1);
+                        break;
+                    }
+                    text.push(ch);
+                }
+                let end = end.ok_or_else(|| {
+                    QueryError::new("unterminated quoted text", start, src.len())
+                        .hint("close the quoted text with another \"")
+                })?;
+                tokens.push(Token {
+                    kind: TokenKind::Quoted(text.to_lowercase()),
+                    start,
+                    end,
+                });
+            }
+            _ => {
+                let mut end = start;
+                while let Some(&(i, ch)) = chars.peek() {
+                    if ch.is_whitespace() || matches!(ch, '(' | ')' | '"' | '!' | '&' | '|') {
+                        break;
+                    }
+                    end = i + ch.len_utf8();
+                    chars.next();
+                }
+                let word = &src[start..end];
+                let kind = match word {
+                    "AND" => TokenKind::And,
+                    "OR" => TokenKind::Or,
+                    "NOT" => TokenKind::Not,
+                    _ => TokenKind::Word(word.to_lowercase()),
+                };
+                tokens.push(Token { kind, start, end });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    // 입력 끝에서 에러가 나면 이 위치를 가리킨다.
+    len: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_kind(&self) -> Option<&TokenKind> {
+        self.peek().map(|t| &t.kind)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, QueryError> {
+        let mut left = self.parse_and()?;
+        while self.peek_kind() == Some(&TokenKind::Or) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, QueryError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            match self.peek_kind() {
+                Some(TokenKind::And) => {
+                    self.pos += 1;
+                }
+                // 연산자 없이 이어지는 항은 암묵적 AND.
+                Some(TokenKind::Word(_) | TokenKind::Quoted(_) | TokenKind::Not)
+                | Some(TokenKind::LParen) => {}
+                _ => break,
+            }
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, QueryError> {
+        if self.peek_kind() == Some(&TokenKind::Not) {
+            self.pos += 1;
+            let inner = self.parse_unary()?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, QueryError> {
+        let Some(tok) = self.peek().cloned() else {
+            return Err(QueryError::new(
+                "expected a word or '(' but the query ended",
+                self.len,
+                self.len,
+            )
+            .hint("AND, OR, and NOT need a word or group after them"));
+        };
+        self.pos += 1;
+        match tok.kind {
+            TokenKind::Word(w) => Ok(Expr::Word(w)),
+            TokenKind::Quoted(t) => Ok(Expr::Text(t)),
+            TokenKind::LParen => {
+                let inner = self.parse_or()?;
+                match self.peek() {
+                    Some(t) if t.kind == TokenKind::RParen => {
+                        self.pos += 1;
+                        Ok(inner)
+                    }
+                    Some(t) => Err(QueryError::new("expected ')'", t.start, t.end)
+                        .hint("close the group with ')' before this")),
+                    None => Err(QueryError::new("unclosed '('", tok.start, tok.end)
+                        .hint("add ')' at the end of the group")),
+                }
+            }
+            TokenKind::RParen => Err(QueryError::new("unmatched ')'", tok.start, tok.end)
+                .hint("remove it, or add a matching '(' before it")),
+            TokenKind::And | TokenKind::Or => {
+                Err(
+                    QueryError::new("operator is missing its left-hand side", tok.start, tok.end)
+                        .hint("put a word before it; to exclude a word, use NOT WORD"),
+                )
+            }
+            TokenKind::Not => unreachable!("NOT is handled in parse_unary"),
+        }
+    }
+}
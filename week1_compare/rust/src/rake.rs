@@ -0,0 +1,62 @@
+// RAKE(Rapid Automatic Keyword Extraction).
+// 1) 문장부호와 불용어를 경계로 후보 구문을 자른다.
+// 2) 단어 점수 = degree(같이 등장한 구문 길이의 합) / freq(등장 횟수).
+// 3) 구문 점수 = 구문을 이루는 단어 점수의 합.
+// Python의 rake-nltk와 같은 알고리즘을 Counter 대신 HashMap으로 직접 계산한다.
+
+use std::collections::HashMap;
+
+use crate::stopwords::is_stopword;
+use crate::{normalize_words, Tokenizer};
+
+pub fn extract(text: &str, tok: Tokenizer, top_n: usize) -> Vec<(String, f64)> {
+    let phrases = candidate_phrases(text, tok);
+
+    let mut freq: HashMap<&str, usize> = HashMap::new();
+    let mut degree: HashMap<&str, usize> = HashMap::new();
+    for phrase in &phrases {
+        for word in phrase {
+            *freq.entry(word).or_insert(0) += 1;
+            *degree.entry(word).or_insert(0) += phrase.len();
+        }
+    }
+    let word_score = |w: &str| degree[w] as f64 / freq[w] as f64;
+
+    // 같은 구문이 여러 번 나와도 점수는 한 번만 매긴다.
+    let mut scored: HashMap<String, f64> = HashMap::new();
+    for phrase in &phrases {
+        let score: f64 = phrase.iter().map(|w| word_score(w)).sum();
+        scored.entry(phrase.join(" ")).or_insert(score);
+    }
+
+    let mut items: Vec<(String, f64)> = scored.into_iter().collect();
+    items.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    if top_n > 0 {
+        items.truncate(top_n);
+    }
+    items
+}
+
+// 문장부호(글자/숫자/공백/'/- 가 아닌 문자)로 먼저 자르고, 그 안에서 불용어로 다시 자른다.
+fn candidate_phrases(text: &str, tok: Tokenizer) -> Vec<Vec<String>> {
+    let mut phrases = Vec::new();
+    let fragments = text.split(|c: char| {
+        !(c.is_alphanumeric() || c.is_whitespace() || c == '\'' || c == '-') || c == '\n'
+    });
+    for fragment in fragments {
+        let mut current: Vec<String> = Vec::new();
+        for word in normalize_words(fragment, tok) {
+            if is_stopword(&word) {
+                if !current.is_empty() {
+                    phrases.push(std::mem::take(&mut current));
+                }
+            } else {
+                current.push(word);
+            }
+        }
+        if !current.is_empty() {
+            phrases.push(current);
+        }
+    }
+    phrases
+}
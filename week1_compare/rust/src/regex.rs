@@ -0,0 +1,745 @@
+// --regex: 줄 필터와 -o --replace '$1'에 쓰는 작은 정규식 엔진.
+// Python의 re 모듈에서 자주 쓰는 부분만 백트래킹으로 구현했다(regex 크레이트 없이 std만):
+//   .  [a-z] [^0-9]  \d \w \s(대문자는 부정)  \b \B  ^ $
+//   (그룹) (?:그룹) a|b  * + ? {m} {m,} {m,n}(뒤에 ?를 붙이면 최소 일치)
+//   패턴 맨 앞의 (?i)는 대소문자를 무시한다.
+// 역참조와 전후방 탐색은 없다. 밖으로 돌려주는 위치는 모두 원문의 바이트 위치다.
+// (a+)+$처럼 반복을 겹친 패턴은 실패할 때 되짚는 경우의 수가 지수로 늘어 줄 하나에 몇 시간이
+// 걸릴 수 있다. 그래서 검색 한 번(줄 하나)에 글자당 STEPS_PER_CHAR 걸음까지만 가고, 넘으면
+// 그 줄은 걸리지 않은 것으로 보고 수를 세어 둔다. 호출한 쪽은 exhausted()로 에러를 낸다.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const STEPS_PER_CHAR: usize = 10_000;
+
+static EXHAUSTED: AtomicUsize = AtomicUsize::new(0);
+
+// 걸음 한도를 넘겨 포기한 줄이 있었으면 에러.
+pub fn exhausted() -> Result<(), String> {
+    match EXHAUSTED.load(Ordering::Relaxed) {
+        0 => Ok(()),
+        n => Err(format!(
+            "regex gave up on {n} line(s) after {STEPS_PER_CHAR} backtracking steps per \
+             character; nested repeats such as (a+)+ take exponential time, so rewrite the \
+             pattern (for example as a+)"
+        )),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Regex {
+    node: Node,
+    // 캡처 그룹 수($0은 빼고 센다).
+    pub groups: usize,
+    ignore_case: bool,
+    source: String,
+}
+
+// [0]은 일치 전체, [i]는 i번째 그룹의 (시작, 끝) 바이트 위치. 참여하지 않은 그룹은 None.
+pub type Captures = Vec<Option<(usize, usize)>>;
+
+#[derive(Debug, Clone)]
+pub struct RegexError {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+    pub hint: Option<&'static str>,
+}
+
+impl RegexError {
+    fn new(message: impl Into<String>, start: usize, end: usize) -> RegexError {
+        RegexError {
+            message: message.into(),
+            start,
+            end,
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: &'static str) -> RegexError {
+        self.hint = Some(hint);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    // \b(true)와 \B(false).
+    Boundary(bool),
+    // 번호가 없으면 (?:...).
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Class {
+    items: Vec<Item>,
+    negated: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Item {
+    Range(char, char),
+    // \d, \w, \s. bool이 true면 \D, \W, \S.
+    Perl(Perl, bool),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Perl {
+    Digit,
+    Word,
+    Space,
+}
+
+impl Perl {
+    fn matches(self, c: char) -> bool {
+        match self {
+            Perl::Digit => c.is_ascii_digit(),
+            Perl::Word => is_word(c),
+            Perl::Space => c.is_whitespace(),
+        }
+    }
+}
+
+impl Class {
+    fn matches(&self, c: char, ignore_case: bool) -> bool {
+        let hit = |c: char| {
+            self.items.iter().any(|item| match *item {
+                Item::Range(lo, hi) => (lo..=hi).contains(&c),
+                Item::Perl(kind, negated) => kind.matches(c) != negated,
+            })
+        };
+        let found = hit(c) || ignore_case && (hit(lower(c)) || hit(upper(c)));
+        found != self.negated
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn upper(c: char) -> char {
+    c.to_uppercase().next().unwrap_or(c)
+}
+
+fn perl(escape: char) -> Item {
+    let kind = match escape.to_ascii_lowercase() {
+        'd' => Perl::Digit,
+        'w' => Perl::Word,
+        _ => Perl::Space,
+    };
+    Item::Perl(kind, escape.is_ascii_uppercase())
+}
+
+pub fn parse(src: &str) -> Result<Regex, RegexError> {
+    let ignore_case = src.starts_with("(?i)");
+    let mut parser = Parser {
+        src,
+        pos: if ignore_case { 4 } else { 0 },
+        groups: 0,
+    };
+    let node = parser.alternation()?;
+    // alternation()은 ')'나 입력 끝에서만 멈춘다.
+    if parser.peek().is_some() {
+        return Err(RegexError::new("unmatched ')'", parser.pos, parser.pos + 1)
+            .hint("escape it as \\) to match a literal parenthesis"));
+    }
+    Ok(Regex {
+        node,
+        groups: parser.groups,
+        ignore_case,
+        source: src.to_string(),
+    })
+}
+
+// 재귀 하강 파서. pos는 src 안의 바이트 위치다.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    groups: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.src[self.pos..].starts_with(text) {
+            self.pos += text.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn alternation(&mut self) -> Result<Node, RegexError> {
+        let mut alts = vec![self.concat()?];
+        while self.eat("|") {
+            alts.push(self.concat()?);
+        }
+        Ok(if alts.len() == 1 {
+            alts.swap_remove(0)
+        } else {
+            Node::Alt(alts)
+        })
+    }
+
+    fn concat(&mut self) -> Result<Node, RegexError> {
+        let mut items = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            items.push(self.quantifier(atom)?);
+        }
+        Ok(if items.len() == 1 {
+            items.swap_remove(0)
+        } else {
+            Node::Concat(items)
+        })
+    }
+
+    fn atom(&mut self) -> Result<Node, RegexError> {
+        let start = self.pos;
+        let Some(c) = self.bump() else {
+            return Ok(Node::Concat(Vec::new()));
+        };
+        Ok(match c {
+            '(' => {
+                let index = if self.eat("?:") {
+                    None
+                } else if self.peek() == Some('?') {
+                    return Err(
+                        RegexError::new("unsupported group syntax", start, self.pos + 1).hint(
+                            "only (...) and (?:...) groups are supported; (?i) must come first",
+                        ),
+                    );
+                } else {
+                    self.groups += 1;
+                    Some(self.groups)
+                };
+                let inner = self.alternation()?;
+                if !self.eat(")") {
+                    return Err(RegexError::new("unclosed group", start, start + 1)
+                        .hint("add a ')' or escape it as \\( to match a literal parenthesis"));
+                }
+                Node::Group(Box::new(inner), index)
+            }
+            '[' => Node::Class(self.class(start)?),
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '\\' => self.escape(start)?,
+            '*' | '+' | '?' => {
+                return Err(RegexError::new("nothing to repeat", start, self.pos)
+                    .hint("escape it with \\ to match it literally"));
+            }
+            c => Node::Char(c),
+        })
+    }
+
+    fn escape(&mut self, start: usize) -> Result<Node, RegexError> {
+        let Some(c) = self.bump() else {
+            return Err(RegexError::new("trailing backslash", start, self.pos)
+                .hint("use \\\\ to match a backslash"));
+        };
+        Ok(match c {
+            'd' | 'D' | 'w' | 'W' | 's' | 'S' => Node::Class(Class {
+                items: vec![perl(c)],
+                negated: false,
+            }),
+            'b' => Node::Boundary(true),
+            'B' => Node::Boundary(false),
+            'n' => Node::Char('\n'),
+            't' => Node::Char('\t'),
+            'r' => Node::Char('\r'),
+            c if c.is_ascii_alphanumeric() => {
+                return Err(
+                    RegexError::new(format!("unknown escape \\{c}"), start, self.pos).hint(
+                        "supported escapes are \\d \\w \\s \\b (and their capitals), \\n \\t \\r",
+                    ),
+                );
+            }
+            c => Node::Char(c),
+        })
+    }
+
+    fn class(&mut self, start: usize) -> Result<Class, RegexError> {
+        let unclosed = || {
+            RegexError::new("unclosed character class", start, start + 1)
+                .hint("add a ']' or escape it as \\[")
+        };
+        let negated = self.eat("^");
+        let mut items = Vec::new();
+        // 맨 앞의 ]는 글자 그대로다([]a]).
+        let mut first = true;
+        loop {
+            let item_start = self.pos;
+            let c = self.bump().ok_or_else(unclosed)?;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+            let lo = match c {
+                '\\' => match self.bump().ok_or_else(unclosed)? {
+                    e @ ('d' | 'D' | 'w' | 'W' | 's' | 'S') => {
+                        items.push(perl(e));
+                        continue;
+                    }
+                    e => class_escape(e),
+                },
+                c => c,
+            };
+            // a-z. 끝의 -(예: [a-])는 글자 그대로다.
+            if self.peek() == Some('-') && !self.src[self.pos + 1..].starts_with(']') {
+                self.bump();
+                let hi = match self.bump().ok_or_else(unclosed)? {
+                    '\\' => class_escape(self.bump().ok_or_else(unclosed)?),
+                    hi => hi,
+                };
+                if hi < lo {
+                    return Err(RegexError::new(
+                        "character range is out of order",
+                        item_start,
+                        self.pos,
+                    )
+                    .hint("write the smaller character first, e.g. [a-z]"));
+                }
+                items.push(Item::Range(lo, hi));
+            } else {
+                items.push(Item::Range(lo, lo));
+            }
+        }
+        Ok(Class { items, negated })
+    }
+
+    fn quantifier(&mut self, atom: Node) -> Result<Node, RegexError> {
+        let start = self.pos;
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => match self.counted() {
+                Some(range) => range,
+                // {가 반복 횟수 모양이 아니면 글자 그대로 둔다.
+                None => return Ok(atom),
+            },
+            _ => return Ok(atom),
+        };
+        if self.pos == start {
+            self.bump();
+        }
+        if max.is_some_and(|max| max < min) {
+            return Err(
+                RegexError::new("repetition range is out of order", start, self.pos)
+                    .hint("write the smaller count first, e.g. {2,5}"),
+            );
+        }
+        let greedy = !self.eat("?");
+        if matches!(self.peek(), Some('*' | '+' | '?')) {
+            return Err(RegexError::new("multiple repeat", self.pos, self.pos + 1)
+                .hint("wrap the repeated part in (?:...) before repeating it again"));
+        }
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    // {m}, {m,}, {m,n}. 모양이 맞지 않으면 위치를 되돌리고 None.
+    fn counted(&mut self) -> Option<(usize, Option<usize>)> {
+        let rest = &self.src[self.pos..];
+        let close = rest.find('}')?;
+        let body = &rest[1..close];
+        let number = |s: &str| {
+            (!s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+                .then(|| s.parse().ok())
+                .flatten()
+        };
+        let range = match body.split_once(',') {
+            None => {
+                let n = number(body)?;
+                (n, Some(n))
+            }
+            Some((min, "")) => (number(min)?, None),
+            Some((min, max)) => (number(min)?, Some(number(max)?)),
+        };
+        self.pos += close + 1;
+        Some(range)
+    }
+}
+
+fn class_escape(c: char) -> char {
+    match c {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        c => c,
+    }
+}
+
+// 안쪽 위치는 글자 번호다. 일치 하나를 찾으면 나머지 패턴을 계속 맞춰 보는
+// 이어받기 함수(continuation)를 넘기는 방식으로 백트래킹한다.
+type Slots = Vec<Option<(usize, usize)>>;
+type Next<'k> = dyn FnMut(usize, &mut Slots) -> bool + 'k;
+
+struct Matcher<'a> {
+    chars: &'a [char],
+    ignore_case: bool,
+    steps: Cell<usize>,
+}
+
+impl Matcher<'_> {
+    // 한도를 넘기면 모든 갈래가 바로 실패해 백트래킹이 풀려 나온다.
+    fn step(&self) -> bool {
+        let steps = self.steps.get() + 1;
+        self.steps.set(steps);
+        steps <= self.budget()
+    }
+
+    fn budget(&self) -> usize {
+        STEPS_PER_CHAR.saturating_mul(self.chars.len() + 1)
+    }
+
+    // 한도를 넘겼으면 세어 두고 true.
+    fn gave_up(&self) -> bool {
+        let gave_up = self.steps.get() > self.budget();
+        if gave_up {
+            EXHAUSTED.fetch_add(1, Ordering::Relaxed);
+        }
+        gave_up
+    }
+
+    fn node(&self, node: &Node, pos: usize, slots: &mut Slots, next: &mut Next) -> bool {
+        if !self.step() {
+            return false;
+        }
+        match node {
+            Node::Char(_) | Node::Any | Node::Class(_) => {
+                self.single(node, pos) && next(pos + 1, slots)
+            }
+            Node::Start => pos == 0 && next(pos, slots),
+            Node::End => pos == self.chars.len() && next(pos, slots),
+            Node::Boundary(want) => self.at_boundary(pos) == *want && next(pos, slots),
+            Node::Group(inner, None) => self.node(inner, pos, slots, next),
+            Node::Group(inner, Some(i)) => {
+                let i = *i;
+                self.node(inner, pos, slots, &mut |end, slots| {
+                    let old = slots[i];
+                    slots[i] = Some((pos, end));
+                    next(end, slots) || {
+                        slots[i] = old;
+                        false
+                    }
+                })
+            }
+            Node::Concat(items) => self.concat(items, pos, slots, next),
+            Node::Alt(alts) => alts.iter().any(|alt| self.node(alt, pos, slots, next)),
+            Node::Repeat { node: inner, .. } if is_single(inner) => {
+                self.repeat_single(node, pos, slots, next)
+            }
+            Node::Repeat { .. } => self.repeat(node, 0, pos, slots, next),
+        }
+    }
+
+    fn single(&self, node: &Node, pos: usize) -> bool {
+        let Some(&c) = self.chars.get(pos) else {
+            return false;
+        };
+        match node {
+            Node::Char(want) => c == *want || self.ignore_case && lower(c) == lower(*want),
+            Node::Any => c != '\n',
+            Node::Class(class) => class.matches(c, self.ignore_case),
+            _ => false,
+        }
+    }
+
+    fn at_boundary(&self, pos: usize) -> bool {
+        let before = pos > 0 && is_word(self.chars[pos - 1]);
+        let after = self.chars.get(pos).is_some_and(|&c| is_word(c));
+        before != after
+    }
+
+    fn concat(&self, items: &[Node], pos: usize, slots: &mut Slots, next: &mut Next) -> bool {
+        match items.split_first() {
+            None => next(pos, slots),
+            Some((first, rest)) => self.node(first, pos, slots, &mut |p, slots| {
+                self.concat(rest, p, slots, next)
+            }),
+        }
+    }
+
+    // 한 글자짜리 반복(.*, \d+, [a-z]{2,3})은 재귀 없이 최대한 센 다음 하나씩 물러난다.
+    // 긴 줄에서도 스택이 깊어지지 않는다.
+    fn repeat_single(&self, node: &Node, pos: usize, slots: &mut Slots, next: &mut Next) -> bool {
+        let Node::Repeat {
+            node: inner,
+            min,
+            max,
+            greedy,
+        } = node
+        else {
+            return false;
+        };
+        let limit = max.unwrap_or(usize::MAX);
+        let mut n = 0;
+        while n < limit && self.single(inner, pos + n) {
+            n += 1;
+        }
+        if n < *min {
+            return false;
+        }
+        if *greedy {
+            (*min..=n).rev().any(|i| next(pos + i, slots))
+        } else {
+            (*min..=n).any(|i| next(pos + i, slots))
+        }
+    }
+
+    // count는 지금까지 반복한 횟수. 최소 횟수를 채운 뒤에는 빈 반복으로 제자리를 돌지 않는다.
+    fn repeat(
+        &self,
+        node: &Node,
+        count: usize,
+        pos: usize,
+        slots: &mut Slots,
+        next: &mut Next,
+    ) -> bool {
+        let Node::Repeat {
+            node: inner,
+            min,
+            max,
+            greedy,
+        } = node
+        else {
+            return false;
+        };
+        if count < *min {
+            return self.node(inner, pos, slots, &mut |p, slots| {
+                self.repeat(node, count + 1, p, slots, next)
+            });
+        }
+        let can_more = max.is_none_or(|max| count < max);
+        let more = |slots: &mut Slots, next: &mut Next| {
+            can_more
+                && self.node(inner, pos, slots, &mut |p, slots| {
+                    p != pos && self.repeat(node, count + 1, p, slots, next)
+                })
+        };
+        // 욕심 많은 반복은 한 번 더 먹어 보고, 최소 반복은 먼저 멈춰 본다.
+        if *greedy && more(slots, next) {
+            return true;
+        }
+        next(pos, slots) || !*greedy && more(slots, next)
+    }
+}
+
+fn is_single(node: &Node) -> bool {
+    matches!(node, Node::Char(_) | Node::Any | Node::Class(_))
+}
+
+impl Regex {
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        let matcher = self.matcher(&chars);
+        let found = (0..=chars.len()).any(|start| self.at(&matcher, start).is_some());
+        !matcher.gave_up() && found
+    }
+
+    // 가장 왼쪽의 일치 하나.
+    pub fn captures(&self, text: &str) -> Option<Captures> {
+        let chars: Vec<char> = text.chars().collect();
+        let offsets: Vec<usize> = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([text.len()])
+            .collect();
+        let matcher = self.matcher(&chars);
+        let slots = (0..=chars.len()).find_map(|start| self.at(&matcher, start));
+        if matcher.gave_up() {
+            return None;
+        }
+        Some(
+            slots?
+                .into_iter()
+                .map(|slot| slot.map(|(s, e)| (offsets[s], offsets[e])))
+                .collect(),
+        )
+    }
+
+    // 겹치지 않는 일치를 왼쪽부터 모두. 빈 일치 뒤에는 한 글자 건너 다시 찾는다.
+    pub fn captures_all(&self, text: &str) -> Vec<Captures> {
+        let chars: Vec<char> = text.chars().collect();
+        let offsets: Vec<usize> = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([text.len()])
+            .collect();
+        let matcher = self.matcher(&chars);
+        let mut found = Vec::new();
+        let mut from = 0;
+        while from <= chars.len() {
+            let Some(slots) = (from..=chars.len()).find_map(|start| self.at(&matcher, start))
+            else {
+                break;
+            };
+            let (start, end) = slots[0].unwrap_or((from, from));
+            from = if end > start { end } else { end + 1 };
+            found.push(
+                slots
+                    .into_iter()
+                    .map(|slot| slot.map(|(s, e)| (offsets[s], offsets[e])))
+                    .collect(),
+            );
+        }
+        if matcher.gave_up() {
+            return Vec::new();
+        }
+        found
+    }
+
+    fn matcher<'a>(&self, chars: &'a [char]) -> Matcher<'a> {
+        Matcher {
+            chars,
+            ignore_case: self.ignore_case,
+            steps: Cell::new(0),
+        }
+    }
+
+    // start 글자에서 시작하는 일치. [0]에 (start, 끝)을 채워 돌려준다.
+    fn at(&self, matcher: &Matcher, start: usize) -> Option<Slots> {
+        let mut slots = vec![None; self.groups + 1];
+        let mut found = None;
+        matcher.node(&self.node, start, &mut slots, &mut |end, slots| {
+            let mut done = slots.clone();
+            done[0] = Some((start, end));
+            found = Some(done);
+            true
+        });
+        found
+    }
+}
+
+// --replace 틀: $0은 일치 전체, $1..(또는 ${12})은 그룹, $$는 $ 하나.
+// 뒤에 숫자를 바로 붙이려면 ${1}2처럼 중괄호로 감싼다.
+enum Piece<'a> {
+    Text(&'a str),
+    Group(usize),
+}
+
+fn pieces(template: &str) -> Vec<Piece<'_>> {
+    let mut out = Vec::new();
+    let mut rest = template;
+    while let Some(dollar) = rest.find('$') {
+        out.push(Piece::Text(&rest[..dollar]));
+        let after = &rest[dollar + 1..];
+        let digits = after.bytes().take_while(u8::is_ascii_digit).count();
+        if let Some(stripped) = after.strip_prefix('$') {
+            out.push(Piece::Text("$"));
+            rest = stripped;
+        } else if digits > 0 {
+            out.push(Piece::Group(after[..digits].parse().unwrap_or(usize::MAX)));
+            rest = &after[digits..];
+        } else if let Some((n, tail)) = after
+            .strip_prefix('{')
+            .and_then(|braced| braced.split_once('}'))
+            .and_then(|(n, tail)| Some((n.parse().ok()?, tail)))
+        {
+            out.push(Piece::Group(n));
+            rest = tail;
+        } else {
+            out.push(Piece::Text("$"));
+            rest = after;
+        }
+    }
+    out.push(Piece::Text(rest));
+    out
+}
+
+// 틀이 패턴에 없는 그룹을 가리키면 실행 전에 알린다. 에러 앞에는 부른 쪽이 옵션 이름을 붙인다.
+pub fn check_template(template: &str, regex: &Regex) -> Result<(), String> {
+    for piece in pieces(template) {
+        if let Piece::Group(n) = piece {
+            if n > regex.groups {
+                return Err(format!(
+                    "refers to ${n}, but the pattern {} has {} group(s)",
+                    regex.as_str(),
+                    regex.groups
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 일치하지 않은 그룹(예: (a)|(b)에서 쓰이지 않은 쪽)은 빈 문자열이 된다.
+pub fn expand(template: &str, text: &str, captures: &Captures) -> String {
+    let mut out = String::new();
+    for piece in pieces(template) {
+        match piece {
+            Piece::Text(t) => out.push_str(t),
+            Piece::Group(n) => {
+                if let Some(Some((start, end))) = captures.get(n) {
+                    out.push_str(&text[*start..*end]);
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_repeat_gives_up_instead_of_hanging() {
+        let re = parse("(a+)+$").unwrap();
+        let line = format!("{}!", "a".repeat(40));
+        assert!(!re.is_match(&line));
+        assert!(exhausted().is_err());
+    }
+
+    #[test]
+    fn ordinary_patterns_stay_within_budget() {
+        let re = parse("(a+)+$").unwrap();
+        assert!(re.is_match(&"a".repeat(40)));
+        let re = parse("a*").unwrap();
+        let spans: Vec<_> = re.captures_all("baab").iter().map(|c| c[0]).collect();
+        assert_eq!(
+            spans,
+            [Some((0, 0)), Some((1, 3)), Some((3, 3)), Some((4, 4))]
+        );
+    }
+}
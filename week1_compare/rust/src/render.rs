@@ -0,0 +1,588 @@
+// 순위표 출력기(--format). 분석 쪽은 섹션별 (단어, 빈도) 표만 만들고, 어떻게 찍을지는
+// 형식마다 Renderer 하나가 맡는다. 새 형식은 구현 하나를 더하고 for_format()에 이어 주면 된다.
+// Python이라면 형식 이름 -> 함수 dict를 두는 자리다.
+
+use std::fmt::Write as _;
+use std::fs;
+
+use crate::diagnostic;
+use crate::format::{csv_field, Format};
+use crate::json::{self, ToJson, Value};
+use crate::numfmt;
+use crate::provenance::{self, Input};
+use crate::{escape_html, WordCount};
+
+pub struct Table {
+    // text의 "[Top words]" 머리글.
+    pub title: String,
+    // --per-chapter / --bucket의 장 또는 시간 구간. 전체 집계와 부가 표(고유명사 등)는 None.
+    pub section: Option<String>,
+    // 첫 열 이름: csv 머리글과 markdown 표 머리에 쓴다.
+    pub column: &'static str,
+    pub rows: Vec<(String, usize)>,
+    // --with-positions: rows와 같은 순서의 (첫 줄, 마지막 줄). 비어 있으면 줄 열을 찍지 않는다.
+    pub lines: Vec<Option<(usize, usize)>>,
+}
+
+impl Table {
+    fn has_lines(&self) -> bool {
+        !self.lines.is_empty()
+    }
+
+    // i번째 행이 처음과 마지막으로 나온 줄. 본문에 없는 단어(--only-words의 0)는 None.
+    fn span(&self, i: usize) -> Option<(usize, usize)> {
+        self.lines.get(i).copied().flatten()
+    }
+}
+
+pub trait Renderer {
+    fn render(&self, tables: &[Table], out: &mut String);
+}
+
+pub fn for_format(format: Format) -> Box<dyn Renderer> {
+    match format {
+        Format::Text => Box::new(Text { plain: false }),
+        Format::Csv => Box::new(Csv),
+        Format::Json => Box::new(Json),
+        Format::Markdown => Box::new(Markdown),
+        Format::Html => Box::new(Html::default()),
+        // 바이트 형식은 columnar::write()가 따로 쓴다. 여기까지 오면 같은 표를 csv로 보인다.
+        Format::Parquet | Format::Arrow => Box::new(Csv),
+        // dot과 gexf는 graph만 쓴다(graph.rs).
+        Format::Dot | Format::Gexf => Box::new(Text { plain: false }),
+    }
+}
+
+// --template가 있으면 --format 대신 그 틀로 찍는다. --table은 text 형식을 열 맞춘 표로 바꾸고,
+// --plain은 text 형식에서 [Top words] 머리글과 빈 줄을 빼고 행만 남긴다.
+pub fn renderer(
+    format: Format,
+    template: Option<&Template>,
+    layout: Option<Layout>,
+    plain: bool,
+) -> Box<dyn Renderer> {
+    match (template, layout) {
+        (Some(template), _) => Box::new(template.clone()),
+        (None, Some(layout)) if format == Format::Text => Box::new(layout),
+        (None, None) if format == Format::Text => Box::new(Text { plain }),
+        (None, _) => for_format(format),
+    }
+}
+
+// [Top words]
+// rust: 2
+// --with-positions면 "rust: 2 (lines 3-41)", 한 줄에만 나오면 "(line 3)".
+struct Text {
+    plain: bool,
+}
+
+impl Renderer for Text {
+    fn render(&self, tables: &[Table], out: &mut String) {
+        for (i, table) in tables.iter().enumerate() {
+            if !self.plain {
+                let gap = if i == 0 { "" } else { "\n" };
+                let _ = writeln!(out, "{gap}[{}]", table.title);
+            }
+            for (i, (word, count)) in table.rows.iter().enumerate() {
+                let _ = write!(out, "{word}: {}", numfmt::count(*count));
+                match table.span(i) {
+                    Some((first, last)) if first == last => {
+                        let _ = write!(out, " (line {first})");
+                    }
+                    Some((first, last)) => {
+                        let _ = write!(out, " (lines {first}-{last})");
+                    }
+                    None => {}
+                }
+                out.push('\n');
+            }
+        }
+    }
+}
+
+// --table:
+// [Top words]
+// rank  word   count
+//    1  rust       2
+// 단어는 왼쪽, 숫자는 오른쪽으로 맞춘다. 폭은 화면 칸으로 재서 한글이 섞여도 열이 어긋나지 않는다.
+#[derive(Debug, Clone, Copy)]
+pub struct Layout {
+    // --rank: 맨 앞에 순위 열.
+    pub rank: bool,
+    // --padding N: 열 사이 공백 수.
+    pub padding: usize,
+    // --plain: 섹션 머리글과 열 이름 줄 없이 행만.
+    pub plain: bool,
+}
+
+impl Renderer for Layout {
+    fn render(&self, tables: &[Table], out: &mut String) {
+        let gap = " ".repeat(self.padding);
+        for (i, table) in tables.iter().enumerate() {
+            if !self.plain {
+                let blank = if i == 0 { "" } else { "\n" };
+                let _ = writeln!(out, "{blank}[{}]", table.title);
+            }
+            let rank_width = table.rows.len().to_string().len().max("rank".len());
+            let word_width = table
+                .rows
+                .iter()
+                .map(|(word, _)| diagnostic::width(word))
+                .chain([table.column.len()])
+                .max()
+                .unwrap_or(0);
+            let count_width = table
+                .rows
+                .iter()
+                .map(|(_, count)| numfmt::count(*count).chars().count())
+                .chain(["count".len()])
+                .max()
+                .unwrap_or(0);
+            // --with-positions: count 뒤에 first, last 열. 본문에 없는 단어는 "-".
+            let spans: Vec<(String, String)> = (0..table.rows.len())
+                .map(|i| match table.span(i) {
+                    Some((first, last)) => (first.to_string(), last.to_string()),
+                    None => ("-".to_string(), "-".to_string()),
+                })
+                .collect();
+            let first_width = spans
+                .iter()
+                .map(|(f, _)| f.len())
+                .chain(["first".len()])
+                .max();
+            let last_width = spans
+                .iter()
+                .map(|(_, l)| l.len())
+                .chain(["last".len()])
+                .max();
+            let (first_width, last_width) = (first_width.unwrap_or(0), last_width.unwrap_or(0));
+            let mut line = |rank: &str, word: &str, count: &str, span: (&str, &str)| {
+                let mut row = String::new();
+                if self.rank {
+                    let _ = write!(row, "{rank:>rank_width$}{gap}");
+                }
+                let pad = word_width - diagnostic::width(word);
+                let _ = write!(row, "{word}{}{gap}{count:>count_width$}", " ".repeat(pad));
+                if table.has_lines() {
+                    let (first, last) = span;
+                    let _ = write!(row, "{gap}{first:>first_width$}{gap}{last:>last_width$}");
+                }
+                let _ = writeln!(out, "{row}");
+            };
+            if !self.plain {
+                line("rank", table.column, "count", ("first", "last"));
+            }
+            for (rank, (word, count)) in table.rows.iter().enumerate() {
+                let (first, last) = &spans[rank];
+                line(
+                    &(rank + 1).to_string(),
+                    word,
+                    &numfmt::count(*count),
+                    (first, last),
+                );
+            }
+        }
+    }
+}
+
+// 섹션이 있으면 첫 열에 이름을 붙여 한 표로 합친다. 전체 집계는 section 열이 "all"이다.
+// --with-positions면 first_line,last_line 열이 붙고, 줄을 모르는 행(섹션 표 등)은 비워 둔다.
+struct Csv;
+
+impl Renderer for Csv {
+    fn render(&self, tables: &[Table], out: &mut String) {
+        let Some(first) = tables.first() else {
+            return;
+        };
+        let positions = tables.iter().any(Table::has_lines);
+        let header = if positions {
+            ",first_line,last_line"
+        } else {
+            ""
+        };
+        let span = |table: &Table, i: usize| match (positions, table.span(i)) {
+            (false, _) => String::new(),
+            (true, Some((first, last))) => format!(",{first},{last}"),
+            (true, None) => ",,".to_string(),
+        };
+        if tables.iter().all(|t| t.section.is_none()) {
+            let _ = writeln!(out, "{},count{header}", first.column);
+            for (i, (word, count)) in first.rows.iter().enumerate() {
+                let _ = writeln!(out, "{},{count}{}", csv_field(word), span(first, i));
+            }
+            return;
+        }
+        let _ = writeln!(out, "section,{},count{header}", first.column);
+        for table in tables {
+            let label = csv_field(table.section.as_deref().unwrap_or("all"));
+            for (i, (word, count)) in table.rows.iter().enumerate() {
+                let _ = writeln!(out, "{label},{},{count}{}", csv_field(word), span(table, i));
+            }
+        }
+    }
+}
+
+// [{"title": "Top words", "top_words": [{"word": "rust", "count": 2}]}]. 섹션이 있으면 "section"도.
+struct Json;
+
+impl Renderer for Json {
+    fn render(&self, tables: &[Table], out: &mut String) {
+        out.push_str(&json::to_pretty(&json_tables(tables)));
+    }
+}
+
+// 표마다 {"title", ["section"], "top_words"}. --provenance는 이 배열을 "tables"로 감싼다.
+// --with-positions면 행마다 "first_line", "last_line"(본문에 없는 단어는 null)이 붙는다.
+pub fn json_tables(tables: &[Table]) -> Value {
+    Value::Array(
+        tables
+            .iter()
+            .map(|table| {
+                let mut fields = vec![("title".to_string(), Value::String(table.title.clone()))];
+                if let Some(section) = &table.section {
+                    fields.push(("section".to_string(), Value::String(section.clone())));
+                }
+                let rows: Vec<Value> = table
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (word, count))| {
+                        let mut row = WordCount {
+                            word: word.clone(),
+                            count: *count,
+                        }
+                        .to_json();
+                        if let (Value::Object(fields), true) = (&mut row, table.has_lines()) {
+                            let (first, last) = match table.span(i) {
+                                Some((first, last)) => {
+                                    (Value::Number(first as f64), Value::Number(last as f64))
+                                }
+                                None => (Value::Null, Value::Null),
+                            };
+                            fields.push(("first_line".to_string(), first));
+                            fields.push(("last_line".to_string(), last));
+                        }
+                        row
+                    })
+                    .collect();
+                fields.push(("top_words".to_string(), Value::Array(rows)));
+                Value::Object(fields)
+            })
+            .collect(),
+    )
+}
+
+// GitHub 표. 셀 안의 |는 \|로 바꿔 열이 밀리지 않게 한다.
+struct Markdown;
+
+impl Renderer for Markdown {
+    fn render(&self, tables: &[Table], out: &mut String) {
+        for (i, table) in tables.iter().enumerate() {
+            let gap = if i == 0 { "" } else { "\n" };
+            let _ = writeln!(out, "{gap}## {}\n", table.title);
+            if table.has_lines() {
+                let _ = writeln!(out, "| {} | count | first line | last line |", table.column);
+                let _ = writeln!(out, "| --- | ---: | ---: | ---: |");
+            } else {
+                let _ = writeln!(out, "| {} | count |", table.column);
+                let _ = writeln!(out, "| --- | ---: |");
+            }
+            for (i, (word, count)) in table.rows.iter().enumerate() {
+                let _ = write!(
+                    out,
+                    "| {} | {} |",
+                    word.replace('|', "\\|"),
+                    numfmt::count(*count)
+                );
+                match table.span(i) {
+                    Some((first, last)) => {
+                        let _ = write!(out, " {first} | {last} |");
+                    }
+                    None if table.has_lines() => out.push_str(" - | - |"),
+                    None => {}
+                }
+                out.push('\n');
+            }
+        }
+    }
+}
+
+// markdown 보고서의 걸린 줄 구역. 본문에 ```가 있어도 닫히지 않도록 본문의 가장 긴
+// 백틱 줄보다 하나 긴 울타리를 쓴다.
+pub fn fenced(title: &str, body: &str, out: &mut String) {
+    let longest = body.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    let _ = writeln!(out, "\n## {title}\n\n{fence}");
+    out.push_str(body);
+    if !body.is_empty() && !body.ends_with('\n') {
+        out.push('\n');
+    }
+    let _ = writeln!(out, "{fence}");
+}
+
+// 파일 하나로 끝나는 HTML 보고서. 외부 CSS/JS 없이 열리도록 스타일과 정렬 스크립트를 안에 넣는다.
+// 머리글을 누르면 그 열로 정렬되고, 빈도 열 옆 막대는 표 안 최대 빈도에 대한 비율이다.
+#[derive(Default)]
+pub struct Html {
+    // [Filtered lines]에 해당하는 줄. text 출력과 같은 "줄번호:줄" 모양 그대로 넣는다.
+    pub filtered: Vec<String>,
+    // --provenance: 끝에 붙이는 입력 표.
+    pub inputs: Vec<Input>,
+}
+
+const HTML_HEAD: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Word count report</title>
+<style>
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2rem; }
+th, td { padding: 0.25rem 0.75rem; border-bottom: 1px solid #ddd; text-align: left; }
+th { cursor: pointer; user-select: none; background: #f4f4f4; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+td.bar { width: 16rem; }
+td.bar span { display: block; height: 0.8rem; background: #4a7bd0; }
+pre { background: #f8f8f8; padding: 1rem; overflow-x: auto; }
+</style>
+</head>
+<body>
+"#;
+
+const HTML_TAIL: &str = r#"<script>
+document.querySelectorAll("table.sortable th").forEach(function (th) {
+  th.addEventListener("click", function () {
+    var column = th.cellIndex;
+    var body = th.closest("table").tBodies[0];
+    var rows = Array.from(body.rows);
+    var numeric = th.dataset.type === "num";
+    var descending = th.dataset.order !== "desc";
+    th.dataset.order = descending ? "desc" : "asc";
+    rows.sort(function (a, b) {
+      var x = a.cells[column].dataset.value, y = b.cells[column].dataset.value;
+      var order = numeric ? x - y : x.localeCompare(y);
+      return descending ? -order : order;
+    });
+    rows.forEach(function (row) { body.appendChild(row); });
+  });
+});
+</script>
+</body>
+</html>
+"#;
+
+impl Renderer for Html {
+    fn render(&self, tables: &[Table], out: &mut String) {
+        out.push_str(HTML_HEAD);
+        for table in tables {
+            let _ = writeln!(out, "<h2>{}</h2>", escape_html(&table.title));
+            let _ = writeln!(out, "<table class=\"sortable\">");
+            let lines = if table.has_lines() {
+                "<th data-type=\"num\">first line</th><th data-type=\"num\">last line</th>"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                out,
+                "<thead><tr><th data-type=\"num\">rank</th><th>{}</th>\
+                 <th data-type=\"num\">count</th>{lines}<th data-type=\"num\"></th></tr></thead>",
+                table.column
+            );
+            let _ = writeln!(out, "<tbody>");
+            let max = table.rows.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+            for (i, (word, count)) in table.rows.iter().enumerate() {
+                let word = escape_html(word);
+                let rank = i + 1;
+                let width = *count as f64 * 100.0 / max as f64;
+                let lines = match table.span(i) {
+                    Some((first, last)) => format!(
+                        "<td class=\"num\" data-value=\"{first}\">{first}</td>\
+                         <td class=\"num\" data-value=\"{last}\">{last}</td>"
+                    ),
+                    None if table.has_lines() => {
+                        "<td class=\"num\" data-value=\"0\"></td><td class=\"num\" data-value=\"0\"></td>"
+                            .to_string()
+                    }
+                    None => String::new(),
+                };
+                let _ = writeln!(
+                    out,
+                    "<tr><td class=\"num\" data-value=\"{rank}\">{rank}</td>\
+                     <td data-value=\"{word}\">{word}</td>\
+                     <td class=\"num\" data-value=\"{count}\">{count}</td>{lines}\
+                     <td class=\"bar\" data-value=\"{count}\"><span style=\"width: {width:.1}%\"></span></td></tr>"
+                );
+            }
+            let _ = writeln!(out, "</tbody>\n</table>");
+        }
+        if !self.filtered.is_empty() {
+            let _ = writeln!(out, "<h2>Filtered lines</h2>\n<pre>");
+            for line in &self.filtered {
+                let _ = writeln!(out, "{}", escape_html(line));
+            }
+            let _ = writeln!(out, "</pre>");
+        }
+        if !self.inputs.is_empty() {
+            provenance::html(&self.inputs, out);
+        }
+        out.push_str(HTML_TAIL);
+    }
+}
+
+// --template FILE. 표마다 header를 한 번, 행마다 row를, 끝에 footer를 한 번 찍는다.
+//   {{#header}}
+//   ## {{title}}
+//   {{/header}}
+//   {{rank}}. {{word}} ({{count}})
+//   {{#footer}}
+//   {{total}} in total
+//   {{/footer}}
+// 블록 밖의 글이 행 틀이다. 블록 태그만 있는 줄은 mustache처럼 줄째 지워서 빈 줄이 남지 않는다.
+// header/footer에서는 {{title}}, {{section}}, {{total}}(빈도 합), {{rows}}(행 수)를 쓸 수 있고,
+// 행에서는 여기에 {{word}}, {{count}}, {{rank}}(1부터)가 더해진다.
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub path: String,
+    header: Vec<Piece>,
+    row: Vec<Piece>,
+    footer: Vec<Piece>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    Literal(String),
+    Field(Field),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Title,
+    Section,
+    Total,
+    Rows,
+    Word,
+    Count,
+    Rank,
+}
+
+impl Template {
+    pub fn load(path: &str) -> Result<Template, String> {
+        let src = fs::read_to_string(path).map_err(|e| format!("failed to read template: {e}"))?;
+        Template::parse(path, &src).map_err(|e| format!("{path}: {e}"))
+    }
+
+    fn parse(path: &str, src: &str) -> Result<Template, String> {
+        let mut header = String::new();
+        let mut row = String::new();
+        let mut footer = String::new();
+        let mut block: Option<&str> = None;
+        for line in src.split_inclusive('\n') {
+            let tag = line.trim();
+            match (tag, block) {
+                ("{{#header}}" | "{{#footer}}", None) => block = Some(&tag[3..tag.len() - 2]),
+                ("{{#header}}" | "{{#footer}}", Some(open)) => {
+                    return Err(format!("{tag} inside {{{{#{open}}}}}"))
+                }
+                ("{{/header}}", Some("header")) | ("{{/footer}}", Some("footer")) => block = None,
+                ("{{/header}}" | "{{/footer}}", _) => return Err(format!("unmatched {tag}")),
+                (_, Some("header")) => header.push_str(line),
+                (_, Some(_)) => footer.push_str(line),
+                (_, None) => row.push_str(line),
+            }
+        }
+        if let Some(open) = block {
+            return Err(format!("{{{{#{open}}}}} is not closed"));
+        }
+        let template = Template {
+            path: path.to_string(),
+            header: pieces(&header, false)?,
+            row: pieces(&row, true)?,
+            footer: pieces(&footer, false)?,
+        };
+        let per_row =
+            |p: &Piece| matches!(p, Piece::Field(Field::Word | Field::Count | Field::Rank));
+        if !template.row.iter().any(per_row) {
+            return Err("the row template has no {{word}}, {{count}}, or {{rank}}".to_string());
+        }
+        Ok(template)
+    }
+}
+
+impl Renderer for Template {
+    fn render(&self, tables: &[Table], out: &mut String) {
+        for table in tables {
+            let total: usize = table.rows.iter().map(|(_, n)| n).sum();
+            let fill = |pieces: &[Piece], out: &mut String, row: Option<(usize, &str, usize)>| {
+                for piece in pieces {
+                    match (piece, row) {
+                        (Piece::Literal(text), _) => out.push_str(text),
+                        (Piece::Field(Field::Title), _) => out.push_str(&table.title),
+                        (Piece::Field(Field::Section), _) => {
+                            out.push_str(table.section.as_deref().unwrap_or(""))
+                        }
+                        (Piece::Field(Field::Total), _) => {
+                            let _ = write!(out, "{total}");
+                        }
+                        (Piece::Field(Field::Rows), _) => {
+                            let _ = write!(out, "{}", table.rows.len());
+                        }
+                        (Piece::Field(Field::Word), Some((_, word, _))) => out.push_str(word),
+                        (Piece::Field(Field::Count), Some((_, _, count))) => {
+                            let _ = write!(out, "{count}");
+                        }
+                        (Piece::Field(Field::Rank), Some((rank, _, _))) => {
+                            let _ = write!(out, "{rank}");
+                        }
+                        // header/footer의 행 필드는 parse()가 막는다.
+                        (Piece::Field(_), None) => {}
+                    }
+                }
+            };
+            fill(&self.header, out, None);
+            for (i, (word, count)) in table.rows.iter().enumerate() {
+                fill(&self.row, out, Some((i + 1, word, *count)));
+            }
+            fill(&self.footer, out, None);
+        }
+    }
+}
+
+// "{{word}} ({{count}})"을 조각으로. 모르는 이름이나 header/footer의 행 필드는 에러다.
+fn pieces(src: &str, row: bool) -> Result<Vec<Piece>, String> {
+    let mut out = Vec::new();
+    let mut rest = src;
+    while let Some(open) = rest.find("{{") {
+        if open > 0 {
+            out.push(Piece::Literal(rest[..open].to_string()));
+        }
+        let after = &rest[open + 2..];
+        let close = after
+            .find("}}")
+            .ok_or_else(|| "unclosed {{ in template".to_string())?;
+        let name = after[..close].trim();
+        let field = match name {
+            "title" => Field::Title,
+            "section" => Field::Section,
+            "total" => Field::Total,
+            "rows" => Field::Rows,
+            "word" => Field::Word,
+            "count" => Field::Count,
+            "rank" => Field::Rank,
+            other if other.starts_with(['#', '/']) => {
+                return Err(format!("{{{{{other}}}}} must be on a line of its own"))
+            }
+            other => return Err(format!("unknown placeholder {{{{{other}}}}}")),
+        };
+        if !row && matches!(field, Field::Word | Field::Count | Field::Rank) {
+            return Err(format!(
+                "{{{{{name}}}}} is only available in the row template, not in header or footer"
+            ));
+        }
+        out.push(Piece::Field(field));
+        rest = &after[close + 2..];
+    }
+    if !rest.is_empty() {
+        out.push(Piece::Literal(rest.to_string()));
+    }
+    Ok(out)
+}
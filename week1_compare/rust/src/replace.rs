@@ -0,0 +1,112 @@
+// replace --from REGEX --to TEXT: 줄마다 치환한 결과를 바꾸기 전 줄과 나란히 보여준다.
+// sed 's/../../g'를 바로 돌리기 전에 무엇이 바뀌는지 확인하는 용도다.
+// 정규식은 줄 하나씩에 건다(^와 $는 줄의 처음과 끝). --to는 -o --replace와 같은 틀이다($1, ${2}).
+// --write는 원본을 건드리지 않고 FILE.replaced로 쓴다. 줄바꿈 모양(\n, \r\n)은 원문 그대로다.
+
+use crate::regex::{self, Regex};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+// 바뀐 부분은 반전해서 줄 안에서 드러나게 한다.
+const MARK_ON: &str = "\x1b[7m";
+const MARK_OFF: &str = "\x1b[27m";
+const RESET: &str = "\x1b[0m";
+
+pub struct Change {
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+    // 바뀐 부분의 바이트 범위. before에서는 일치, after에서는 채워 넣은 틀.
+    before_spans: Vec<(usize, usize)>,
+    after_spans: Vec<(usize, usize)>,
+}
+
+struct Substitution {
+    text: String,
+    before_spans: Vec<(usize, usize)>,
+    after_spans: Vec<(usize, usize)>,
+}
+
+fn substitute(line: &str, regex: &Regex, template: &str) -> Substitution {
+    let mut text = String::with_capacity(line.len());
+    let mut before_spans = Vec::new();
+    let mut after_spans = Vec::new();
+    let mut last = 0;
+    for caps in regex.captures_all(line) {
+        let Some((start, end)) = caps[0] else {
+            continue;
+        };
+        text.push_str(&line[last..start]);
+        let from = text.len();
+        text.push_str(&regex::expand(template, line, &caps));
+        before_spans.push((start, end));
+        after_spans.push((from, text.len()));
+        last = end;
+    }
+    text.push_str(&line[last..]);
+    Substitution {
+        text,
+        before_spans,
+        after_spans,
+    }
+}
+
+// 치환해도 그대로인 줄(일치가 없거나 같은 글자로 바뀐 줄)은 뺀다.
+pub fn changes(text: &str, regex: &Regex, template: &str) -> Vec<Change> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let sub = substitute(line, regex, template);
+            (sub.text != line).then(|| Change {
+                line: i + 1,
+                before: line.to_string(),
+                after: sub.text,
+                before_spans: sub.before_spans,
+                after_spans: sub.after_spans,
+            })
+        })
+        .collect()
+}
+
+// --write에 쓸 전체 텍스트. 마지막 줄바꿈 유무도 원문을 따른다.
+pub fn apply(text: &str, regex: &Regex, template: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let (content, ending) = match line.strip_suffix("\r\n") {
+            Some(content) => (content, "\r\n"),
+            None => match line.strip_suffix('\n') {
+                Some(content) => (content, "\n"),
+                None => (line, ""),
+            },
+        };
+        out.push_str(&substitute(content, regex, template).text);
+        out.push_str(ending);
+    }
+    out
+}
+
+// "- 바꾸기 전" / "+ 바꾼 뒤" 두 줄. 색을 쓰면 빨강/초록에 바뀐 부분만 반전한다.
+pub fn render(change: &Change, color: bool) -> String {
+    if !color {
+        return format!("- {}\n+ {}\n", change.before, change.after);
+    }
+    format!(
+        "{RED}- {}{RESET}\n{GREEN}+ {}{RESET}\n",
+        marked(&change.before, &change.before_spans),
+        marked(&change.after, &change.after_spans)
+    )
+}
+
+fn marked(text: &str, spans: &[(usize, usize)]) -> String {
+    let mut out = String::with_capacity(text.len() + spans.len() * 10);
+    let mut last = 0;
+    for &(start, end) in spans {
+        out.push_str(&text[last..start]);
+        out.push_str(MARK_ON);
+        out.push_str(&text[start..end]);
+        out.push_str(MARK_OFF);
+        last = end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
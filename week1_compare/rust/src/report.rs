@@ -0,0 +1,121 @@
+// report 서브커맨드의 결과. --format json은 이 구조체를 그대로 직렬화하고, drift는 저장된 출력을
+// 다시 이 구조체로 읽는다. 라이브러리 사용자도 같은 타입으로 주고받는다.
+//   [{"path": "a.txt", "tokens": 120, "top_words": [{"word": "rust", "count": 7}], "matches": []}]
+
+use crate::json::{self, FromJson, ToJson, Value};
+use crate::WordCount;
+
+// 필터에 걸린 줄 하나(1부터 시작하는 줄 번호).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterResult {
+    pub line: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    pub path: String,
+    pub tokens: usize,
+    pub top_words: Vec<WordCount>,
+    // 필터 옵션이 없으면 빈 배열.
+    pub matches: Vec<FilterResult>,
+}
+
+impl ToJson for FilterResult {
+    fn to_json(&self) -> Value {
+        Value::Object(vec![
+            ("line".to_string(), Value::Number(self.line as f64)),
+            ("text".to_string(), Value::String(self.text.clone())),
+        ])
+    }
+}
+
+impl FromJson for FilterResult {
+    fn from_json(value: &Value) -> Result<FilterResult, String> {
+        Ok(FilterResult {
+            line: json::count_field(value, "line")?,
+            text: json::str_field(value, "text")?,
+        })
+    }
+}
+
+impl ToJson for FileReport {
+    fn to_json(&self) -> Value {
+        Value::Object(vec![
+            ("path".to_string(), Value::String(self.path.clone())),
+            ("tokens".to_string(), Value::Number(self.tokens as f64)),
+            ("top_words".to_string(), self.top_words.to_json()),
+            ("matches".to_string(), self.matches.to_json()),
+        ])
+    }
+}
+
+// 손으로 만든 기준선도 받도록 path와 matches는 없어도 된다.
+impl FromJson for FileReport {
+    fn from_json(value: &Value) -> Result<FileReport, String> {
+        let top_words = value
+            .get("top_words")
+            .ok_or_else(|| "missing array field top_words".to_string())?;
+        let matches = match value.get("matches") {
+            Some(matches) => Vec::from_json(matches)?,
+            None => Vec::new(),
+        };
+        Ok(FileReport {
+            path: json::str_field(value, "path").unwrap_or_default(),
+            tokens: json::count_field(value, "tokens")?,
+            top_words: Vec::from_json(top_words)?,
+            matches,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> FileReport {
+        FileReport {
+            path: "notes/a \"b\".txt".to_string(),
+            tokens: 120,
+            top_words: vec![
+                WordCount {
+                    word: "rust".to_string(),
+                    count: 7,
+                },
+                WordCount {
+                    word: "café".to_string(),
+                    count: 2,
+                },
+            ],
+            matches: vec![FilterResult {
+                line: 3,
+                text: "tab\there".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn report_round_trips_through_json_text() {
+        let report = sample();
+        let text = json::to_line(&report.to_json());
+        let back = FileReport::from_json(&json::parse(&text).unwrap()).unwrap();
+        assert_eq!(back, report);
+    }
+
+    #[test]
+    fn keys_follow_field_names() {
+        let text = json::to_line(&sample().to_json());
+        let keys = ["\"path\"", "\"tokens\"", "\"top_words\"", "\"matches\""];
+        let at: Vec<usize> = keys.iter().map(|k| text.find(k).unwrap()).collect();
+        assert!(at.windows(2).all(|w| w[0] < w[1]), "{text}");
+    }
+
+    #[test]
+    fn hand_written_baseline_without_path_or_matches() {
+        let value = json::parse(r#"{"tokens": 4, "top_words": [{"word": "a", "count": 4}]}"#);
+        let report = FileReport::from_json(&value.unwrap()).unwrap();
+        assert_eq!(report.path, "");
+        assert!(report.matches.is_empty());
+        assert_eq!(report.top_words[0].count, 4);
+    }
+}
@@ -0,0 +1,187 @@
+// --resource-stats: 최대 RSS, 힙 할당 횟수/총량, 실행 시간.
+// Python의 tracemalloc + resource.getrusage()에 해당하는 숫자를 외부 crate 없이 만든다.
+// 할당 횟수는 시스템 할당자를 감싼 전역 할당자로 센다. 전역 할당자는 실행 중에
+// 바꿀 수 없으므로 항상 켜져 있지만, 할당마다 원자적 덧셈 몇 번이라 비용은 작다.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io;
+use std::process::Child;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+pub struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+fn on_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+}
+
+fn on_dealloc(size: usize) {
+    CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+}
+
+// SAFETY: 실제 할당은 모두 System에 위임하고 카운터만 갱신한다.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        on_dealloc(layout.size());
+    }
+
+    // realloc은 "해제 1번 + 할당 1번"으로 센다(Python tracemalloc도 크기 변화를 추적).
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            on_dealloc(layout.size());
+            on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+pub fn report(wall: Duration) {
+    eprintln!("[Resource usage]");
+    eprintln!("wall time: {:.3} ms", wall.as_secs_f64() * 1000.0);
+    match peak_rss_bytes() {
+        Some(bytes) => eprintln!("peak RSS: {}", human_bytes(bytes)),
+        None => eprintln!("peak RSS: unavailable"),
+    }
+    eprintln!(
+        "allocations: {} ({} total)",
+        ALLOCATIONS.load(Ordering::Relaxed),
+        human_bytes(ALLOCATED_BYTES.load(Ordering::Relaxed))
+    );
+    eprintln!(
+        "peak heap: {}",
+        human_bytes(PEAK_BYTES.load(Ordering::Relaxed))
+    );
+}
+
+pub fn human_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(unix)]
+#[repr(C)]
+struct Timeval {
+    tv_sec: std::os::raw::c_long,
+    tv_usec: std::os::raw::c_long,
+}
+
+#[cfg(unix)]
+#[repr(C)]
+struct Rusage {
+    ru_utime: Timeval,
+    ru_stime: Timeval,
+    ru_maxrss: std::os::raw::c_long,
+    // 나머지 13개 필드는 읽지 않지만 커널이 쓰므로 자리를 잡아 둔다.
+    rest: [std::os::raw::c_long; 13],
+}
+
+#[cfg(unix)]
+impl Rusage {
+    fn zeroed() -> Rusage {
+        let zero = || Timeval {
+            tv_sec: 0,
+            tv_usec: 0,
+        };
+        Rusage {
+            ru_utime: zero(),
+            ru_stime: zero(),
+            ru_maxrss: 0,
+            rest: [0; 13],
+        }
+    }
+
+    // ru_maxrss는 Linux는 KiB, macOS는 바이트 단위다.
+    fn peak_bytes(&self) -> Option<usize> {
+        if self.ru_maxrss <= 0 {
+            return None;
+        }
+        let scale = if cfg!(target_os = "macos") { 1 } else { 1024 };
+        Some(self.ru_maxrss as usize * scale)
+    }
+}
+
+#[cfg(unix)]
+extern "C" {
+    fn getrusage(who: i32, usage: *mut Rusage) -> i32;
+    fn wait4(pid: i32, status: *mut i32, options: i32, usage: *mut Rusage) -> i32;
+}
+
+// getrusage(RUSAGE_SELF)의 ru_maxrss.
+#[cfg(unix)]
+fn peak_rss_bytes() -> Option<usize> {
+    let mut usage = Rusage::zeroed();
+    // SAFETY: Rusage는 struct rusage와 같은 레이아웃이고, RUSAGE_SELF는 0이다.
+    let rc = unsafe { getrusage(0, &mut usage) };
+    if rc != 0 {
+        return None;
+    }
+    usage.peak_bytes()
+}
+
+#[cfg(not(unix))]
+fn peak_rss_bytes() -> Option<usize> {
+    None
+}
+
+// 자식 프로세스를 기다리며 그 프로세스만의 최대 RSS를 얻는다(성공 여부, 최대 RSS).
+// RUSAGE_CHILDREN은 지금까지 기다린 자식 전체의 최댓값이라 여러 번 돌리면 구분이 안 되므로
+// wait4로 pid 하나의 사용량을 받는다. 이렇게 거둔 Child에 다시 wait()를 부르면 안 된다.
+#[cfg(unix)]
+pub fn wait_child(child: &mut Child) -> io::Result<(bool, Option<usize>)> {
+    let mut status = 0;
+    let mut usage = Rusage::zeroed();
+    loop {
+        // SAFETY: child.id()는 아직 거두지 않은 자식이고, 포인터는 모두 살아 있는 지역 변수다.
+        let rc = unsafe { wait4(child.id() as i32, &mut status, 0, &mut usage) };
+        if rc != -1 {
+            break;
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+    // 정상 종료이고 종료 코드가 0일 때만 status가 0이다.
+    Ok((status == 0, usage.peak_bytes()))
+}
+
+#[cfg(not(unix))]
+pub fn wait_child(child: &mut Child) -> io::Result<(bool, Option<usize>)> {
+    Ok((child.wait()?.success(), None))
+}
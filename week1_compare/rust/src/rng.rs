@@ -0,0 +1,95 @@
+// 의존성 없는 작은 의사 난수 생성기(SplitMix64).
+// Python이라면 random 모듈을 그냥 쓰면 되지만, 표준 라이브러리에는 난수가 없으므로
+// 표본 추출 정도에 충분한 64비트 생성기를 직접 둔다. 암호용으로는 쓰면 안 된다.
+
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// --seed: main이 실행 전에 한 번 넣는다. 없으면 표본과 생성 글이 실행마다 달라진다.
+static SEED: OnceLock<u64> = OnceLock::new();
+
+pub fn fix_seed(seed: Option<u64>) {
+    if let Some(seed) = seed {
+        let _ = SEED.set(seed);
+    }
+}
+
+pub fn seed() -> Option<u64> {
+    SEED.get().copied()
+}
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    // --seed가 있으면 그 씨앗으로 시작한다. SplitMix64는 정수 연산뿐이라 플랫폼이 달라도
+    // 같은 씨앗이면 같은 수열이 나온다.
+    pub fn seeded() -> Rng {
+        match seed() {
+            Some(seed) => Rng::new(seed),
+            None => Rng::from_time(),
+        }
+    }
+
+    // 실행할 때마다 다른 표본이 나오도록 현재 시각과 프로세스 번호로 씨앗을 만든다.
+    fn from_time() -> Rng {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        Rng::new(nanos ^ (u64::from(std::process::id()) << 32))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // [0, 1) 사이의 실수. 상위 53비트를 그대로 가수로 쓴다.
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // 0..n 사이의 정수. % 대신 128비트 곱셈 후 상위 64비트를 취한다(치우침이 더 작다).
+    pub fn below(&mut self, n: usize) -> usize {
+        ((u128::from(self.next_u64()) * n as u128) >> 64) as usize
+    }
+}
+
+// 저수지 표본(Algorithm R): 길이를 모르는 스트림에서 k개를 균등하게 뽑는다.
+// 메모리는 k개만 쓰고, 결과는 원래 순서대로 돌려준다.
+pub fn reservoir<T>(items: impl Iterator<Item = T>, k: usize, rng: &mut Rng) -> Vec<T> {
+    let mut kept: Vec<(usize, T)> = Vec::with_capacity(k);
+    for (i, item) in items.enumerate() {
+        if kept.len() < k {
+            kept.push((i, item));
+        } else {
+            let j = rng.below(i + 1);
+            if j < k {
+                kept[j] = (i, item);
+            }
+        }
+    }
+    kept.sort_by_key(|(i, _)| *i);
+    kept.into_iter().map(|(_, item)| item).collect()
+}
+
+// 가중치에 비례해 k개를 중복 없이 뽑는다(Efraimidis-Spirakis). 항목마다 -ln(u)/w를 열쇠로 주고
+// 가장 작은 k개를 고르면, 하나씩 뽑고 빼는 과정을 되풀이한 것과 분포가 같다. 결과는 뽑힌 순서다.
+pub fn weighted<T>(items: Vec<(T, f64)>, k: usize, rng: &mut Rng) -> Vec<T> {
+    let mut keyed: Vec<(f64, T)> = items
+        .into_iter()
+        .filter(|(_, w)| *w > 0.0)
+        .map(|(item, w)| (-(1.0 - rng.unit()).ln() / w, item))
+        .collect();
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    keyed.truncate(k);
+    keyed.into_iter().map(|(_, item)| item).collect()
+}
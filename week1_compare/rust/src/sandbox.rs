@@ -0,0 +1,44 @@
+// --root DIR: daemon과 --jsonrpc가 읽는 파일을 DIR 안으로 가둔다. 요청에 경로를 실어 보내는
+// 호출자를 다 믿을 수 없을 때(편집기 플러그인, 같은 기계의 다른 사용자) /etc/passwd 같은 파일을
+// 읽어 단어 빈도로 흘리지 않게 하려는 것이다.
+// 경로는 평소처럼 현재 디렉터리 기준으로 풀고, canonicalize로 ..와 심볼릭 링크를 모두 따라간
+// 실제 위치가 DIR 아래여야 한다. 그래서 DIR 안의 링크가 바깥을 가리켜도 거절된다.
+
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct Root {
+    // canonicalize한 DIR.
+    dir: PathBuf,
+}
+
+impl Root {
+    pub fn open(dir: &str) -> Result<Root, String> {
+        let dir = Path::new(dir)
+            .canonicalize()
+            .map_err(|e| format!("--root {dir}: {e}"))?;
+        if !dir.is_dir() {
+            return Err(format!("--root {}: not a directory", dir.display()));
+        }
+        Ok(Root { dir })
+    }
+
+    // 읽어도 되는 실제 경로. 확인한 경로를 그대로 열어야 그 사이에 링크를 바꿔치기할 틈이 줄어든다.
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        let real = Path::new(path)
+            .canonicalize()
+            .map_err(|e| format!("{path}: {e}"))?;
+        if !real.starts_with(&self.dir) {
+            return Err(format!("{path}: outside --root {}", self.dir.display()));
+        }
+        Ok(real)
+    }
+}
+
+// root가 없으면 경로를 그대로 쓴다.
+pub fn resolve(root: Option<&Root>, path: &str) -> Result<PathBuf, String> {
+    match root {
+        Some(root) => root.resolve(path),
+        None => Ok(PathBuf::from(path)),
+    }
+}
@@ -0,0 +1,61 @@
+// --every: 같은 분석을 일정 간격으로 다시 돌려 결과를 시각과 함께 NDJSON으로 쌓는다.
+// cron 없이 로그 어휘를 지켜보는 용도다. Python이라면 while True: ...; time.sleep()을 쓸 자리인데,
+// 매번 "끝난 뒤 N분"을 기다리면 분석 시간만큼 조금씩 밀리므로 시작 시각 기준으로 다음 차례를 잡는다.
+// 한 번 실패해도(파일이 잠깐 없는 등) 멈추지 않고 그 회차를 에러로 기록한 뒤 계속한다.
+
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::json::{self, Value};
+use crate::output;
+use crate::timefmt;
+
+// 한 줄: {"time":"2026-10-14T09:30:00Z","ok":true,"output":"..."} (실패면 "error").
+// output이 없으면 stdout에 한 줄씩 쓴다.
+pub fn run(
+    every: Duration,
+    output: Option<&str>,
+    mut task: impl FnMut(&mut String) -> Result<(), String>,
+) -> Result<(), String> {
+    let started = Instant::now();
+    for round in 1u32.. {
+        let time = utc_timestamp(SystemTime::now());
+        let mut out = String::new();
+        let outcome = match task(&mut out) {
+            Ok(()) => ("output".to_string(), Value::String(out)),
+            Err(e) => ("error".to_string(), Value::String(e)),
+        };
+        let ok = outcome.0 == "output";
+        let record = Value::Object(vec![
+            ("time".to_string(), Value::String(time)),
+            ("ok".to_string(), Value::Bool(ok)),
+            outcome,
+        ]);
+        let line = json::to_line(&record) + "\n";
+        match output {
+            Some(path) => output::append_line(path, &line)?,
+            None => print!("{line}"),
+        }
+        // 분석이 간격보다 오래 걸렸으면 밀린 회차는 건너뛰고 바로 다음 차례에 맞춘다.
+        let elapsed = started.elapsed();
+        let mut next = every * round;
+        while next <= elapsed {
+            next += every;
+        }
+        thread::sleep(next - elapsed);
+    }
+    Ok(())
+}
+
+// RFC 3339 UTC 시각.
+pub fn utc_timestamp(now: SystemTime) -> String {
+    let secs = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (year, month, day) = timefmt::civil_from_days((secs / 86_400) as i64);
+    let rest = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rest / 3600,
+        rest / 60 % 60,
+        rest % 60
+    )
+}
@@ -0,0 +1,747 @@
+// --script PATH: 크레이트를 고치지 않고 한 번 쓰고 버릴 변환을 넣는 훅.
+// rhai 문법의 작은 부분만 해석하는 인터프리터다(외부 크레이트 없이).
+//   fn keep_line(line) { !line.starts_with("#") }      // false면 그 줄은 세지 않는다
+//   fn map_token(word) {                               // 바꾼 단어를 돌려준다. ""면 버린다
+//       if word.ends_with("ies") { return word.replace("ies", "y"); }
+//       word
+//   }
+// 문장은 let, 대입, if/else, return, 식. 값은 문자열, 정수, bool. 반복문은 없어서
+// 스크립트가 끝나지 않는 일은 재귀뿐이고, 그것도 깊이를 제한한다.
+// 연산자: + - * / % (정수, 문자열 + 는 이어 붙이기), == != < <= > >=, && || !. 주석은 //.
+// 문자열 메서드: len, is_empty, to_lower, to_upper, trim, contains, starts_with,
+// ends_with, replace. 스크립트 안의 fn끼리는 서로 부를 수 있다. Python이라면 사용자 모듈을
+// importlib로 불러 함수를 꺼내는 자리다.
+//
+// rhai 크레이트를 넣지 않은 것은 이 크레이트가 의존성 없이 오프라인에서 빌드되어야 해서다.
+// 그래서 위의 부분집합만 되고, rhai 스크립트 중 다음을 쓰는 것은 파싱이나 실행 에러가 난다:
+//   반복문(loop, while, for .. in, break, continue)과 범위(0..n)
+//   실수, 문자(char) 리터럴, 배열, 객체 맵, 백틱 문자열과 ${} 보간, /* */ 주석
+//   switch, 클로저(|x| ..), fn 포인터(Fn("..")), const, 모듈(import/export), try/catch, throw
+//   +=, -= 같은 복합 대입, 비트 연산, 문자열 인덱싱(s[0])
+//   print, debug, type_of 같은 내장 함수와 위에 없는 문자열 메서드(split, sub_string, index_of, pad 등)
+//   메서드는 문자열에만 있고, 정수(n.abs() 등)나 bool에는 없다
+// 또한 rhai와 달리 정수 연산은 넘치면 에러 대신 감싸 돈다(wrapping).
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::sync::OnceLock;
+
+use crate::counter::Counter;
+
+const MAX_DEPTH: usize = 100;
+
+static INSTALLED: OnceLock<Script> = OnceLock::new();
+
+// main이 실행 전에 한 번 넣는다. 빈도 집계(map_token)와 입력 읽기(keep_line)가 꺼내 쓴다.
+pub fn install(script: Script) {
+    let _ = INSTALLED.set(script);
+}
+
+// map_token으로 단어를 바꾼 빈도. 단어 종류마다 한 번만 호출하고, 같은 단어로 바뀐 것은 합친다.
+// 처음 나온 순서는 유지한다(--tie-break first-seen). 실행 중 에러가 나면 결과를 믿을 수 없으므로
+// 에러를 찍고 끝낸다.
+pub fn map_counts(counts: Counter) -> Counter {
+    let Some(script) = INSTALLED.get().filter(|s| s.has("map_token")) else {
+        return counts;
+    };
+    let mut mapped = Counter::new();
+    for (word, n) in counts.iter() {
+        match script.call("map_token", vec![Value::Str(word.to_string())]) {
+            Ok(Value::Str(w)) if w.is_empty() => {}
+            Ok(Value::Unit) => {}
+            Ok(Value::Str(w)) => mapped.add(&w, n),
+            Ok(other) => fail(&format!(
+                "script error in map_token: expected a string, got {other} for {word:?}"
+            )),
+            Err(e) => fail(&format!("script error in map_token: {e}")),
+        }
+    }
+    mapped
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
+}
+
+// keep_line이 false를 돌려준 줄을 뺀 본문.
+pub fn keep_lines(text: String) -> Result<String, String> {
+    if !INSTALLED.get().is_some_and(|s| s.has("keep_line")) {
+        return Ok(text);
+    }
+    let mut kept = String::with_capacity(text.len());
+    for line in text.lines() {
+        if keeps(line)? {
+            kept.push_str(line);
+            kept.push('\n');
+        }
+    }
+    Ok(kept)
+}
+
+// 줄 하나에 keep_line을 부른다. 읽으면서 거르는 곳(filter::scan)이 쓴다. 훅이 없으면 true.
+pub fn keeps(line: &str) -> Result<bool, String> {
+    let Some(script) = INSTALLED.get().filter(|s| s.has("keep_line")) else {
+        return Ok(true);
+    };
+    match script.call("keep_line", vec![Value::Str(line.to_string())]) {
+        Ok(Value::Bool(keep)) => Ok(keep),
+        Ok(other) => Err(format!(
+            "script error in keep_line: expected true or false, got {other}"
+        )),
+        Err(e) => Err(format!("script error in keep_line: {e}")),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    Int(i64),
+    Str(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Unit => write!(f, "()"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Int(n) => write!(f, "{n}"),
+            Value::Str(s) => write!(f, "{s:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Function {
+    params: Vec<String>,
+    body: Vec<Stmt>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Script {
+    pub path: String,
+    functions: HashMap<String, Function>,
+}
+
+#[derive(Debug, Clone)]
+enum Stmt {
+    Let(String, Expr),
+    Assign(String, Expr),
+    Return(Option<Expr>),
+    // 세미콜론 없이 블록 끝에 온 식은 블록의 값이 된다.
+    Expr(Expr, bool),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Lit(Value),
+    Var(String),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+    Method(Box<Expr>, String, Vec<Expr>),
+    If(Box<Expr>, Vec<Stmt>, Vec<Stmt>),
+}
+
+pub fn load(path: &str) -> Result<Script, String> {
+    let source = fs::read_to_string(path).map_err(|e| format!("failed to read script: {e}"))?;
+    let functions =
+        parse(&source).map_err(|(line, message)| format!("{path}:{line}: {message}"))?;
+    let script = Script {
+        path: path.to_string(),
+        functions,
+    };
+    if !script.has("map_token") && !script.has("keep_line") {
+        return Err(format!(
+            "{path}: script defines neither map_token nor keep_line"
+        ));
+    }
+    for name in ["map_token", "keep_line"] {
+        if script
+            .functions
+            .get(name)
+            .is_some_and(|f| f.params.len() != 1)
+        {
+            return Err(format!("{path}: {name} must take exactly one argument"));
+        }
+    }
+    Ok(script)
+}
+
+impl Script {
+    fn has(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    // 이 스크립트가 정의한 훅 이름(--explain용).
+    pub fn hooks(&self) -> Vec<&'static str> {
+        ["keep_line", "map_token"]
+            .into_iter()
+            .filter(|name| self.has(name))
+            .collect()
+    }
+
+    fn call(&self, name: &str, args: Vec<Value>) -> Result<Value, String> {
+        self.invoke(name, args, 0)
+    }
+
+    fn invoke(&self, name: &str, args: Vec<Value>, depth: usize) -> Result<Value, String> {
+        if depth >= MAX_DEPTH {
+            return Err(format!("calls nested deeper than {MAX_DEPTH}"));
+        }
+        let function = self
+            .functions
+            .get(name)
+            .ok_or_else(|| format!("unknown function {name}"))?;
+        if function.params.len() != args.len() {
+            return Err(format!(
+                "{name} takes {} argument(s), got {}",
+                function.params.len(),
+                args.len()
+            ));
+        }
+        let mut scope: HashMap<String, Value> = function.params.iter().cloned().zip(args).collect();
+        match self.block(&function.body, &mut scope, depth)? {
+            Flow::Value(v) | Flow::Return(v) => Ok(v),
+        }
+    }
+
+    fn block(
+        &self,
+        body: &[Stmt],
+        scope: &mut HashMap<String, Value>,
+        depth: usize,
+    ) -> Result<Flow, String> {
+        let mut last = Value::Unit;
+        for stmt in body {
+            last = Value::Unit;
+            match stmt {
+                Stmt::Let(name, expr) => {
+                    let value = self.expr(expr, scope, depth)?.into_value()?;
+                    scope.insert(name.clone(), value);
+                }
+                Stmt::Assign(name, expr) => {
+                    if !scope.contains_key(name) {
+                        return Err(format!("assignment to undeclared variable {name}"));
+                    }
+                    let value = self.expr(expr, scope, depth)?.into_value()?;
+                    scope.insert(name.clone(), value);
+                }
+                Stmt::Return(expr) => {
+                    let value = match expr {
+                        Some(e) => self.expr(e, scope, depth)?.into_value()?,
+                        None => Value::Unit,
+                    };
+                    return Ok(Flow::Return(value));
+                }
+                Stmt::Expr(expr, terminated) => match self.expr(expr, scope, depth)? {
+                    Flow::Return(v) => return Ok(Flow::Return(v)),
+                    Flow::Value(v) if !terminated => last = v,
+                    Flow::Value(_) => {}
+                },
+            }
+        }
+        Ok(Flow::Value(last))
+    }
+
+    // if 블록 안의 return이 함수 밖으로 나가야 하므로 식도 Flow를 돌려준다.
+    fn expr(
+        &self,
+        expr: &Expr,
+        scope: &mut HashMap<String, Value>,
+        depth: usize,
+    ) -> Result<Flow, String> {
+        let value = match expr {
+            Expr::Lit(v) => v.clone(),
+            Expr::Var(name) => scope
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("unknown variable {name}"))?,
+            Expr::Not(e) => match self.value(e, scope, depth)? {
+                Value::Bool(b) => Value::Bool(!b),
+                other => return Err(format!("! expects a bool, got {other}")),
+            },
+            Expr::Neg(e) => match self.value(e, scope, depth)? {
+                Value::Int(n) => Value::Int(n.wrapping_neg()),
+                other => return Err(format!("- expects an integer, got {other}")),
+            },
+            Expr::Binary(op, a, b) => {
+                let left = self.value(a, scope, depth)?;
+                // &&, ||는 왼쪽만으로 정해지면 오른쪽을 계산하지 않는다.
+                match (*op, &left) {
+                    ("&&", Value::Bool(false)) => return Ok(Flow::Value(Value::Bool(false))),
+                    ("||", Value::Bool(true)) => return Ok(Flow::Value(Value::Bool(true))),
+                    _ => {}
+                }
+                let right = self.value(b, scope, depth)?;
+                binary(op, left, right)?
+            }
+            Expr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|a| self.value(a, scope, depth))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.invoke(name, args, depth + 1)?
+            }
+            Expr::Method(target, name, args) => {
+                let target = self.value(target, scope, depth)?;
+                let args = args
+                    .iter()
+                    .map(|a| self.value(a, scope, depth))
+                    .collect::<Result<Vec<_>, _>>()?;
+                method(target, name, args)?
+            }
+            Expr::If(cond, then, otherwise) => {
+                let branch = match self.value(cond, scope, depth)? {
+                    Value::Bool(true) => then,
+                    Value::Bool(false) => otherwise,
+                    other => return Err(format!("if expects a bool, got {other}")),
+                };
+                // 블록 안의 let은 블록이 끝나면 사라진다. 바깥 변수의 대입은 남긴다.
+                let mut inner = scope.clone();
+                let flow = self.block(branch, &mut inner, depth)?;
+                for (name, value) in scope.iter_mut() {
+                    if let Some(v) = inner.remove(name) {
+                        *value = v;
+                    }
+                }
+                return Ok(flow);
+            }
+        };
+        Ok(Flow::Value(value))
+    }
+
+    fn value(
+        &self,
+        expr: &Expr,
+        scope: &mut HashMap<String, Value>,
+        depth: usize,
+    ) -> Result<Value, String> {
+        self.expr(expr, scope, depth)?.into_value()
+    }
+}
+
+enum Flow {
+    Value(Value),
+    Return(Value),
+}
+
+impl Flow {
+    // 식 한가운데(인자, 피연산자)에서 return이 나오면 받을 자리가 없다.
+    fn into_value(self) -> Result<Value, String> {
+        match self {
+            Flow::Value(v) => Ok(v),
+            Flow::Return(_) => Err("return is only allowed as a statement".to_string()),
+        }
+    }
+}
+
+fn binary(op: &str, left: Value, right: Value) -> Result<Value, String> {
+    use Value::{Bool, Int, Str};
+    Ok(match (op, left, right) {
+        ("==", a, b) => Bool(a == b),
+        ("!=", a, b) => Bool(a != b),
+        ("&&" | "||", Bool(_), Bool(b)) => Bool(b),
+        ("+", Int(a), Int(b)) => Int(a.wrapping_add(b)),
+        ("-", Int(a), Int(b)) => Int(a.wrapping_sub(b)),
+        ("*", Int(a), Int(b)) => Int(a.wrapping_mul(b)),
+        ("/" | "%", Int(_), Int(0)) => return Err("division by zero".to_string()),
+        ("/", Int(a), Int(b)) => Int(a.wrapping_div(b)),
+        ("%", Int(a), Int(b)) => Int(a.wrapping_rem(b)),
+        ("+", Str(a), b) => Str(match b {
+            Str(b) => a + &b,
+            other => format!("{a}{}", plain(&other)),
+        }),
+        ("<", Int(a), Int(b)) => Bool(a < b),
+        ("<=", Int(a), Int(b)) => Bool(a <= b),
+        (">", Int(a), Int(b)) => Bool(a > b),
+        (">=", Int(a), Int(b)) => Bool(a >= b),
+        ("<", Str(a), Str(b)) => Bool(a < b),
+        ("<=", Str(a), Str(b)) => Bool(a <= b),
+        (">", Str(a), Str(b)) => Bool(a > b),
+        (">=", Str(a), Str(b)) => Bool(a >= b),
+        (op, a, b) => return Err(format!("cannot apply {op} to {a} and {b}")),
+    })
+}
+
+// 문자열에 이어 붙일 때는 따옴표 없이.
+fn plain(value: &Value) -> String {
+    match value {
+        Value::Str(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn method(target: Value, name: &str, args: Vec<Value>) -> Result<Value, String> {
+    let Value::Str(s) = target else {
+        return Err(format!("{target} has no method {name}"));
+    };
+    let strings: Vec<&str> = args
+        .iter()
+        .map(|a| match a {
+            Value::Str(s) => Ok(s.as_str()),
+            other => Err(format!("{name} expects string arguments, got {other}")),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(match (name, strings.as_slice()) {
+        ("len", []) => Value::Int(s.chars().count() as i64),
+        ("is_empty", []) => Value::Bool(s.is_empty()),
+        ("to_lower", []) => Value::Str(s.to_lowercase()),
+        ("to_upper", []) => Value::Str(s.to_uppercase()),
+        ("trim", []) => Value::Str(s.trim().to_string()),
+        ("contains", [p]) => Value::Bool(s.contains(p)),
+        ("starts_with", [p]) => Value::Bool(s.starts_with(p)),
+        ("ends_with", [p]) => Value::Bool(s.ends_with(p)),
+        ("replace", [from, to]) => Value::Str(s.replace(from, to)),
+        _ => {
+            return Err(format!(
+                "unknown string method {name} with {} argument(s)",
+                args.len()
+            ))
+        }
+    })
+}
+
+// ---- 파서 ----
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Str(String),
+    Punct(&'static str),
+}
+
+const PUNCT: &[&str] = &[
+    "==", "!=", "<=", ">=", "&&", "||", "(", ")", "{", "}", ",", ";", ".", "=", "<", ">", "+", "-",
+    "*", "/", "%", "!",
+];
+
+// (토큰, 줄 번호).
+fn lex(src: &str) -> Result<Vec<(Token, usize)>, (usize, String)> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut rest = src;
+    while let Some(c) = rest.chars().next() {
+        if c == '\n' {
+            line += 1;
+            rest = &rest[1..];
+        } else if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if rest.starts_with("//") {
+            rest = rest.find('\n').map_or("", |end| &rest[end..]);
+        } else if c == '"' {
+            let mut text = String::new();
+            let mut chars = rest[1..].char_indices();
+            let end = loop {
+                match chars.next() {
+                    None => return Err((line, "unterminated string".to_string())),
+                    Some((i, '"')) => break i + 2,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => text.push('\n'),
+                        Some((_, 't')) => text.push('\t'),
+                        Some((_, c @ ('"' | '\\'))) => text.push(c),
+                        _ => return Err((line, "unknown escape in string".to_string())),
+                    },
+                    Some((_, '\n')) => return Err((line, "unterminated string".to_string())),
+                    Some((_, c)) => text.push(c),
+                }
+            };
+            tokens.push((Token::Str(text), line));
+            rest = &rest[end..];
+        } else if c.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let n = rest[..end]
+                .parse()
+                .map_err(|_| (line, format!("integer too large: {}", &rest[..end])))?;
+            tokens.push((Token::Int(n), line));
+            rest = &rest[end..];
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push((Token::Ident(rest[..end].to_string()), line));
+            rest = &rest[end..];
+        } else if let Some(p) = PUNCT.iter().find(|p| rest.starts_with(**p)) {
+            tokens.push((Token::Punct(p), line));
+            rest = &rest[p.len()..];
+        } else {
+            return Err((line, format!("unexpected character {c:?}")));
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse(src: &str) -> Result<HashMap<String, Function>, (usize, String)> {
+    let mut parser = Parser {
+        tokens: lex(src)?,
+        pos: 0,
+    };
+    let mut functions = HashMap::new();
+    while parser.peek().is_some() {
+        parser.keyword("fn")?;
+        let name = parser.ident()?;
+        parser.punct("(")?;
+        let mut params = Vec::new();
+        if !parser.eat(")") {
+            loop {
+                params.push(parser.ident()?);
+                if parser.eat(")") {
+                    break;
+                }
+                parser.punct(",")?;
+            }
+        }
+        let body = parser.block()?;
+        if functions
+            .insert(name.clone(), Function { params, body })
+            .is_some()
+        {
+            return Err((parser.line(), format!("function {name} is defined twice")));
+        }
+    }
+    Ok(functions)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn error<T>(&self, expected: &str) -> Result<T, (usize, String)> {
+        let found = match self.peek() {
+            Some(Token::Ident(s)) => s.clone(),
+            Some(Token::Int(n)) => n.to_string(),
+            Some(Token::Str(s)) => format!("{s:?}"),
+            Some(Token::Punct(p)) => p.to_string(),
+            None => "end of script".to_string(),
+        };
+        Err((self.line(), format!("expected {expected}, found {found}")))
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn punct(&mut self, punct: &str) -> Result<(), (usize, String)> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            self.error(&format!("'{punct}'"))
+        }
+    }
+
+    fn at_keyword(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(s)) if s == word)
+    }
+
+    fn keyword(&mut self, word: &str) -> Result<(), (usize, String)> {
+        if self.at_keyword(word) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error(word)
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, (usize, String)> {
+        match self.peek() {
+            Some(Token::Ident(s)) if !KEYWORDS.contains(&s.as_str()) => {
+                let s = s.clone();
+                self.pos += 1;
+                Ok(s)
+            }
+            _ => self.error("a name"),
+        }
+    }
+
+    fn block(&mut self) -> Result<Vec<Stmt>, (usize, String)> {
+        self.punct("{")?;
+        let mut body = Vec::new();
+        while !self.eat("}") {
+            if self.peek().is_none() {
+                return self.error("'}'");
+            }
+            body.push(self.stmt()?);
+        }
+        Ok(body)
+    }
+
+    fn stmt(&mut self) -> Result<Stmt, (usize, String)> {
+        if self.at_keyword("let") {
+            self.pos += 1;
+            let name = self.ident()?;
+            self.punct("=")?;
+            let value = self.expr()?;
+            self.punct(";")?;
+            return Ok(Stmt::Let(name, value));
+        }
+        if self.at_keyword("return") {
+            self.pos += 1;
+            if self.eat(";") {
+                return Ok(Stmt::Return(None));
+            }
+            let value = self.expr()?;
+            self.punct(";")?;
+            return Ok(Stmt::Return(Some(value)));
+        }
+        if let (Some(Token::Ident(name)), Some((Token::Punct("="), _))) =
+            (self.peek(), self.tokens.get(self.pos + 1))
+        {
+            let name = name.clone();
+            self.pos += 2;
+            let value = self.expr()?;
+            self.punct(";")?;
+            return Ok(Stmt::Assign(name, value));
+        }
+        let is_if = self.at_keyword("if");
+        let expr = self.expr()?;
+        let terminated = self.eat(";");
+        // if 블록 뒤와 블록의 마지막 식에는 세미콜론이 없어도 된다.
+        if !terminated && !is_if && self.peek() != Some(&Token::Punct("}")) {
+            return self.error("';'");
+        }
+        Ok(Stmt::Expr(expr, terminated))
+    }
+
+    fn expr(&mut self) -> Result<Expr, (usize, String)> {
+        self.binary(0)
+    }
+
+    // 우선순위 낮은 것부터: || && (== !=) (< <= > >=) (+ -) (* / %).
+    fn binary(&mut self, level: usize) -> Result<Expr, (usize, String)> {
+        const LEVELS: &[&[&str]] = &[
+            &["||"],
+            &["&&"],
+            &["==", "!="],
+            &["<", "<=", ">", ">="],
+            &["+", "-"],
+            &["*", "/", "%"],
+        ];
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(op) = self.operator(LEVELS[level]) {
+            self.pos += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn operator(&self, ops: &[&'static str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Punct(p)) => ops.iter().find(|op| *op == p).copied(),
+            _ => None,
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, (usize, String)> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("-") {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        let mut expr = self.primary()?;
+        while self.eat(".") {
+            let name = self.ident()?;
+            let args = self.args()?;
+            expr = Expr::Method(Box::new(expr), name, args);
+        }
+        Ok(expr)
+    }
+
+    fn args(&mut self) -> Result<Vec<Expr>, (usize, String)> {
+        self.punct("(")?;
+        let mut args = Vec::new();
+        if self.eat(")") {
+            return Ok(args);
+        }
+        loop {
+            args.push(self.expr()?);
+            if self.eat(")") {
+                return Ok(args);
+            }
+            self.punct(",")?;
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, (usize, String)> {
+        let Some(token) = self.peek().cloned() else {
+            return self.error("an expression");
+        };
+        match token {
+            Token::Int(n) => {
+                self.pos += 1;
+                Ok(Expr::Lit(Value::Int(n)))
+            }
+            Token::Str(s) => {
+                self.pos += 1;
+                Ok(Expr::Lit(Value::Str(s)))
+            }
+            Token::Punct("(") => {
+                self.pos += 1;
+                let inner = self.expr()?;
+                self.punct(")")?;
+                Ok(inner)
+            }
+            Token::Ident(word) => match word.as_str() {
+                "true" | "false" => {
+                    self.pos += 1;
+                    Ok(Expr::Lit(Value::Bool(word == "true")))
+                }
+                "if" => {
+                    self.pos += 1;
+                    let cond = self.expr()?;
+                    let then = self.block()?;
+                    let otherwise = if self.at_keyword("else") {
+                        self.pos += 1;
+                        if self.at_keyword("if") {
+                            vec![Stmt::Expr(self.primary()?, false)]
+                        } else {
+                            self.block()?
+                        }
+                    } else {
+                        Vec::new()
+                    };
+                    Ok(Expr::If(Box::new(cond), then, otherwise))
+                }
+                _ => {
+                    let name = self.ident()?;
+                    if self.peek() == Some(&Token::Punct("(")) {
+                        Ok(Expr::Call(name, self.args()?))
+                    } else {
+                        Ok(Expr::Var(name))
+                    }
+                }
+            },
+            Token::Punct(_) => self.error("an expression"),
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &["fn", "let", "if", "else", "return", "true", "false"];
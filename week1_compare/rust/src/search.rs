@@ -0,0 +1,204 @@
+// -r/--recursive: 디렉터리 트리 전체에 줄 필터를 건다. 작은 ripgrep이지만 --contains, --query,
+// --phrase가 상위 단어를 셀 때와 같은 토큰화 규칙(--unicode, --hyphens 등)으로 비교하므로
+// "세었을 때 나온 단어로 찾으면 그 줄이 나온다"가 보장된다.
+// 잘못된 UTF-8이 섞인 파일도 바이트로 읽고 걸린 줄만 깨진 곳을 U+FFFD로 바꿔 보여준다.
+// 상위 단어를 셀 때는 파일마다 내용을 보고(sniff) HTML, Markdown은 본문만 뽑아 센다.
+// 파일마다 읽기와 필터를 스레드로 나눠 돌리고, 결과는 경로순으로 다시 모아 실행마다 같게 쓴다.
+// 파일 하나는 한 스레드가 끝까지 맡고 합치는 것은 입력 순서로만 하므로, --threads가 몇이든
+// 출력은 바이트까지 같다(스레드 수는 속도만 바꾼다).
+// Python이라면 concurrent.futures.ThreadPoolExecutor.map()을 쓸 자리다.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::filter::{self, LineFilter};
+use crate::interrupt;
+use crate::sniff::{self, Kind};
+use crate::walk::{self, WalkOptions};
+
+pub struct Selection {
+    pub files: Vec<PathBuf>,
+    // --max-filesize를 넘어 읽지 않은 (경로, 크기).
+    pub oversized: Vec<(PathBuf, u64)>,
+}
+
+// roots의 디렉터리는 재귀로 펼치고, 파일은 그대로 둔다(명령행 순서, 디렉터리 안은 경로순).
+// max_size보다 큰 파일(코어 덤프, DB 파일 등)은 읽지 않고 따로 모은다.
+pub fn files(
+    roots: &[String],
+    max_size: Option<u64>,
+    walk: &WalkOptions,
+) -> Result<Selection, String> {
+    let mut found = Vec::new();
+    for root in roots {
+        let path = Path::new(root);
+        if path.is_dir() {
+            found.extend(walk::files(path, walk)?);
+        } else if walk.modified_in_range(path) {
+            found.push(path.to_path_buf());
+        }
+    }
+    let Some(limit) = max_size else {
+        return Ok(Selection {
+            files: found,
+            oversized: Vec::new(),
+        });
+    };
+    let mut selection = Selection {
+        files: Vec::with_capacity(found.len()),
+        oversized: Vec::new(),
+    };
+    for path in found {
+        // 크기를 모르면(없는 파일 등) 읽기 단계에서 에러로 기록되게 남겨 둔다.
+        match fs::metadata(&path) {
+            Ok(meta) if meta.len() > limit => selection.oversized.push((path, meta.len())),
+            _ => selection.files.push(path),
+        }
+    }
+    Ok(selection)
+}
+
+// 걸린 (줄 번호, 줄). 바이너리 파일은 빈 목록이다.
+pub type Hits = Vec<(usize, String)>;
+
+pub fn search(
+    files: &[PathBuf],
+    filter: &LineFilter,
+    threads: Option<usize>,
+) -> Vec<(String, Result<Hits, String>)> {
+    par_map(files, threads, |path| search_file(path, filter))
+}
+
+// -r로 상위 단어를 셀 때의 입력: 바이너리는 빼고, 깨진 UTF-8은 U+FFFD로 바꾸고,
+// HTML, Markdown은 sniff::extract()로 본문만 남긴다.
+pub fn read(
+    files: &[PathBuf],
+    threads: Option<usize>,
+) -> Vec<(String, Result<Option<String>, String>)> {
+    par_map(files, threads, |path| {
+        let data = fs::read(path).map_err(|e| format!("failed to read file: {e}"))?;
+        let kind = sniff::sniff(path, &data);
+        if kind == Kind::Binary {
+            return Ok(None);
+        }
+        let text = match String::from_utf8(data) {
+            Ok(text) => text,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        };
+        Ok(Some(match kind {
+            Kind::Text => text,
+            _ => sniff::extract(kind, &text),
+        }))
+    })
+}
+
+// --threads를 지정하지 않으면 CPU 수.
+pub fn workers(threads: Option<usize>) -> usize {
+    threads.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+}
+
+// 파일마다 (이름, 결과)를 입력 순서대로. 읽지 못한 파일은 batch::settle()이 건너뛰고
+// 기록하도록 에러로 둔다. threads가 None이면 CPU 수만큼 띄운다.
+fn par_map<T: Send>(
+    files: &[PathBuf],
+    threads: Option<usize>,
+    job: impl Fn(&Path) -> Result<T, String> + Sync,
+) -> Vec<(String, Result<T, String>)> {
+    let workers = workers(threads).min(files.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(files.len()));
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                // 신호를 받았으면 맡은 파일만 끝내고 새 파일은 집지 않는다.
+                if interrupt::interrupted() {
+                    break;
+                }
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = files.get(i) else {
+                    break;
+                };
+                let result = job(path);
+                let mut results = results.lock().unwrap_or_else(|e| e.into_inner());
+                results.push((i, path.display().to_string(), result));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    if results.len() < files.len() {
+        interrupt::skipped(files.len() - results.len());
+    }
+    results.sort_by_key(|(i, _, _)| *i);
+    results
+        .into_iter()
+        .map(|(_, name, result)| (name, result))
+        .collect()
+}
+
+fn search_file(path: &Path, filter: &LineFilter) -> Result<Hits, String> {
+    let data = fs::read(path).map_err(|e| format!("failed to read file: {e}"))?;
+    // 경로:줄번호가 원본 파일을 가리켜야 하므로 검색은 전처리 없이 원문 줄에 건다.
+    // 바이너리는 조용히 건너뛴다(-0이면 NUL이 구분자라 제외).
+    if !filter.nul && sniff::sniff(path, &data) == Kind::Binary {
+        return Ok(Vec::new());
+    }
+    Ok(filter::matching_lines_lossy(&data, filter)
+        .map(|(n, line)| (n, line.into_owned()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 크기가 제각각인 파일들. 큰 파일이 늦게 끝나 스레드마다 끝나는 순서가 뒤섞인다.
+    fn fixture(dir: &Path) -> Vec<PathBuf> {
+        fs::create_dir_all(dir).unwrap();
+        let mut files = Vec::new();
+        for i in 0..24 {
+            let path = dir.join(format!("{i:02}.txt"));
+            let lines = if i % 7 == 3 { 60_000 } else { 1 + i * 13 };
+            let text: String = (0..lines)
+                .map(|n| match (n + i) % 5 {
+                    0 => format!("line {n} of file {i} mentions Rust\n"),
+                    1 => format!("rusty tools {n}\n"),
+                    _ => format!("nothing to see {n}\n"),
+                })
+                .collect();
+            fs::write(&path, text).unwrap();
+            files.push(path);
+        }
+        files.insert(5, dir.join("missing.txt"));
+        files
+    }
+
+    #[test]
+    fn output_does_not_depend_on_thread_count() {
+        let dir = std::env::temp_dir().join(format!("wordtool-search-{}", std::process::id()));
+        let files = fixture(&dir);
+        let filter = LineFilter {
+            contains: vec!["rust".to_string()],
+            ..LineFilter::default()
+        };
+        let single = search(&files, &filter, Some(1));
+        let texts = read(&files, Some(1));
+        for threads in [2, 5, 16] {
+            assert_eq!(
+                search(&files, &filter, Some(threads)),
+                single,
+                "{threads} threads"
+            );
+            assert_eq!(read(&files, Some(threads)), texts, "{threads} threads");
+        }
+        let _ = fs::remove_dir_all(&dir);
+        // 입력 순서를 지키고, 읽지 못한 파일도 제자리에 에러로 남는다.
+        let names: Vec<String> = files.iter().map(|p| p.display().to_string()).collect();
+        let found: Vec<&String> = single.iter().map(|(name, _)| name).collect();
+        assert_eq!(found, names.iter().collect::<Vec<_>>());
+        assert!(single[5].1.is_err());
+        assert!(single[0].1.as_ref().is_ok_and(|hits| !hits.is_empty()));
+    }
+}
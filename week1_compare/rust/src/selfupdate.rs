@@ -0,0 +1,209 @@
+// self-update: cargo 밖에서 설치한 사용자를 위해 릴리스 서버에서 새 실행 파일을 받아 지금의 것과 바꾼다.
+// --release-url URL 아래에 다음 파일이 있다고 본다:
+//   URL/latest                        최신 버전 (0.2.0)
+//   URL/0.2.0/SHA256SUMS              "HEX  week1_rust_word_count-x86_64-linux" 줄들
+//   URL/0.2.0/SHA256SUMS.minisig      --pubkey를 주면 minisign으로 SHA256SUMS의 서명을 확인한다
+//   URL/0.2.0/week1_rust_word_count-x86_64-linux
+// 표준 라이브러리에는 HTTPS가 없으므로 내려받기는 curl에 맡긴다(클립보드처럼 바깥 명령을 쓴다).
+// 체크섬만으로는 같은 서버에서 받은 것끼리 맞는지만 알 수 있으므로, 서명 없이 바꿀 때는 그렇다고 알린다.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::rng::Rng;
+use crate::sha256::Sha256;
+
+const NAME: &str = env!("CARGO_PKG_NAME");
+const CURRENT: &str = env!("CARGO_PKG_VERSION");
+
+pub fn run(url: &str, pubkey: Option<&str>, check: bool, out: &mut String) -> Result<(), String> {
+    if !(url.starts_with("https://") || url.starts_with("file://")) {
+        return Err(format!(
+            "--release-url must be https:// (or file:// for a local mirror), got {url}"
+        ));
+    }
+    let url = url.trim_end_matches('/');
+    let latest = String::from_utf8_lossy(&fetch(&format!("{url}/latest"))?)
+        .trim()
+        .trim_start_matches('v')
+        .to_string();
+    if !newer(&latest, CURRENT)? {
+        let _ = writeln!(out, "{NAME} {CURRENT} is up to date (latest {latest})");
+        return Ok(());
+    }
+    if check {
+        let _ = writeln!(out, "update available: {CURRENT} -> {latest}");
+        return Ok(());
+    }
+    let asset = asset_name();
+    let base = format!("{url}/{latest}");
+    let sums = fetch(&format!("{base}/SHA256SUMS"))?;
+    let sums = match pubkey {
+        Some(key) => verify_signature(&sums, &fetch(&format!("{base}/SHA256SUMS.minisig"))?, key)?,
+        None => {
+            eprintln!(
+                "self-update: no --pubkey given; checking the SHA-256 checksum only, not its signature"
+            );
+            sums
+        }
+    };
+    let expected = expected_sum(&String::from_utf8_lossy(&sums), &asset)
+        .ok_or_else(|| format!("{base}/SHA256SUMS has no entry for {asset}"))?;
+    let binary = fetch(&format!("{base}/{asset}"))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&binary);
+    let actual = hasher.hex_digest();
+    if !actual.eq_ignore_ascii_case(&expected) {
+        return Err(format!(
+            "checksum mismatch for {asset}: expected {expected}, got {actual}; nothing was replaced"
+        ));
+    }
+    replace(&binary)?;
+    let _ = writeln!(
+        out,
+        "updated {NAME} {CURRENT} -> {latest} (sha256 {actual})"
+    );
+    Ok(())
+}
+
+// x86_64-linux, aarch64-macos, x86_64-windows.exe처럼 std::env::consts로 정한다.
+fn asset_name() -> String {
+    let suffix = if cfg!(windows) { ".exe" } else { "" };
+    format!("{NAME}-{}-{}{suffix}", env::consts::ARCH, env::consts::OS)
+}
+
+// -f: HTTP 오류면 본문 대신 실패로. --proto로 리다이렉트가 평문 HTTP로 내려가지 않게 한다.
+fn fetch(url: &str) -> Result<Vec<u8>, String> {
+    let output = Command::new("curl")
+        .args(["-fsSL", "--proto", "=https,file", url])
+        .output()
+        .map_err(|e| format!("self-update needs curl on PATH: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("failed to download {url}: {}", stderr.trim()));
+    }
+    Ok(output.stdout)
+}
+
+// 0.10.0 > 0.9.3처럼 점으로 나눈 숫자를 차례로 비교한다.
+fn newer(latest: &str, current: &str) -> Result<bool, String> {
+    let parse = |version: &str| {
+        version
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("release server reported an invalid version: {version:?}"))
+    };
+    Ok(parse(latest)? > parse(current)?)
+}
+
+// sha256sum 출력 형식: "HEX  NAME" 또는 바이너리 표시가 붙은 "HEX *NAME".
+fn expected_sum(sums: &str, asset: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (hex, name) = line.trim().split_once(char::is_whitespace)?;
+        (name.trim().trim_start_matches('*') == asset).then(|| hex.to_string())
+    })
+}
+
+// minisign -V는 파일 경로를 받으므로 새로 만든 임시 디렉터리에 잠깐 써 둔다. 확인이 끝나면
+// minisign이 읽은 그 파일을 다시 읽어 돌려주므로, 체크섬은 서명을 확인한 바이트에서만 꺼낸다.
+fn verify_signature(sums: &[u8], signature: &[u8], pubkey: &str) -> Result<Vec<u8>, String> {
+    let dir = private_dir()?;
+    let sums_path = dir.join("SHA256SUMS");
+    let sig_path = dir.join("SHA256SUMS.minisig");
+    let result = fs::write(&sums_path, sums)
+        .and_then(|()| fs::write(&sig_path, signature))
+        .map_err(|e| format!("failed to write {}: {e}", dir.display()))
+        .and_then(|()| {
+            Command::new("minisign")
+                .arg("-Vqm")
+                .arg(&sums_path)
+                .arg("-x")
+                .arg(&sig_path)
+                .args(["-P", pubkey])
+                .status()
+                .map_err(|e| format!("--pubkey needs minisign on PATH: {e}"))
+        })
+        .and_then(|status| {
+            if !status.success() {
+                return Err(
+                    "SHA256SUMS signature does not match --pubkey; nothing was replaced"
+                        .to_string(),
+                );
+            }
+            fs::read(&sums_path).map_err(|e| format!("failed to read {}: {e}", sums_path.display()))
+        });
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+// 임시 디렉터리 아래에 이름을 골라 새로 만든다. 이미 있는 것(다른 사용자가 미리 만들어 둔 디렉터리나
+// 링크)은 쓰지 않고 다른 이름으로 다시 시도한다. Unix에서는 나만 읽고 쓸 수 있게(0700) 만든다.
+fn private_dir() -> Result<PathBuf, String> {
+    let mut rng = Rng::seeded();
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    for _ in 0..16 {
+        let dir = env::temp_dir().join(format!("{NAME}-update-{:016x}", rng.next_u64()));
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("failed to create {}: {e}", dir.display())),
+        }
+    }
+    Err(format!(
+        "failed to create a private directory under {}",
+        env::temp_dir().display()
+    ))
+}
+
+// 같은 디렉터리에 새 파일을 다 쓴 뒤 이름을 바꾸므로, 도중에 실패해도 지금의 실행 파일은 그대로다.
+// Windows는 실행 중인 파일을 덮어쓸 수 없어 먼저 .old로 비켜 두고, 새 파일을 제자리에 놓지 못하면
+// .old를 되돌려 실행 파일이 없는 채로 끝나지 않게 한다.
+fn replace(binary: &[u8]) -> Result<(), String> {
+    let exe = env::current_exe()
+        .and_then(|p| p.canonicalize())
+        .map_err(|e| format!("cannot locate the running executable: {e}"))?;
+    let file_name = exe
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| NAME.to_string());
+    let fresh = exe.with_file_name(format!(".{file_name}.new"));
+    let fail = |path: &Path, e: std::io::Error| format!("failed to write {}: {e}", path.display());
+    fs::write(&fresh, binary).map_err(|e| fail(&fresh, e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&fresh, fs::Permissions::from_mode(0o755))
+            .map_err(|e| fail(&fresh, e))?;
+    }
+    let old = exe.with_file_name(format!("{file_name}.old"));
+    if cfg!(windows) {
+        let _ = fs::remove_file(&old);
+        fs::rename(&exe, &old).map_err(|e| {
+            let _ = fs::remove_file(&fresh);
+            fail(&exe, e)
+        })?;
+    }
+    fs::rename(&fresh, &exe).map_err(|e| {
+        let _ = fs::remove_file(&fresh);
+        if cfg!(windows) {
+            if let Err(back) = fs::rename(&old, &exe) {
+                return format!(
+                    "{}; restoring {} also failed: {back}",
+                    fail(&exe, e),
+                    old.display()
+                );
+            }
+        }
+        fail(&exe, e)
+    })
+}
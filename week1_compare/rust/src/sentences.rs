@@ -0,0 +1,53 @@
+// 문장 분리기.
+// Python이라면 nltk.sent_tokenize()를 쓰겠지만, 여기서는 ". ! ?" 뒤에 공백이 오는 지점과
+// 빈 줄을 경계로 삼고, 흔한 약어(Mr., e.g. 등)와 한 글자 이니셜에서는 자르지 않는다.
+
+const ABBREVIATIONS: &[&str] = &[
+    "dr", "e.g", "eg", "etc", "i.e", "ie", "jr", "mr", "mrs", "ms", "no", "prof", "sr", "st", "vs",
+];
+
+// 반환하는 문장은 원문 슬라이스를 공백 하나로 정리한 문자열이다.
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+
+    for (k, &(i, c)) in chars.iter().enumerate() {
+        let next = chars.get(k + 1).map(|&(_, n)| n);
+        let boundary = match c {
+            '.' | '!' | '?' => {
+                next.is_none_or(char::is_whitespace)
+                    && !(c == '.' && ends_with_abbreviation(&text[start..i]))
+            }
+            // 빈 줄(문단 경계)도 문장 경계로 본다.
+            '\n' => next == Some('\n') || next == Some('\r'),
+            _ => false,
+        };
+        if boundary {
+            push_sentence(&mut sentences, &text[start..i + c.len_utf8()]);
+            start = i + c.len_utf8();
+        }
+    }
+    push_sentence(&mut sentences, &text[start..]);
+    sentences
+}
+
+fn push_sentence(sentences: &mut Vec<String>, raw: &str) {
+    let cleaned = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if cleaned.chars().any(char::is_alphanumeric) {
+        sentences.push(cleaned);
+    }
+}
+
+fn ends_with_abbreviation(before: &str) -> bool {
+    let last = before
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or("")
+        .trim_start_matches(|c: char| !c.is_alphanumeric());
+    // "J. R. R. Tolkien" 같은 한 글자 이니셜.
+    if last.chars().count() == 1 && last.chars().all(char::is_uppercase) {
+        return true;
+    }
+    ABBREVIATIONS.contains(&last.to_lowercase().as_str())
+}
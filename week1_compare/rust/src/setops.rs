@@ -0,0 +1,30 @@
+// 말뭉치 사이의 어휘 집합 연산(subtract, intersect).
+// Python이라면 Counter끼리 a - b를 쓰겠지만, 그건 빈도 차이를 남긴다. 여기서는
+// "B에 (거의) 없는 A의 단어"가 필요하므로 B쪽 빈도를 기준값과 비교해 거른다.
+
+use crate::counter::Counter;
+
+// B에서 below회 미만으로 나온 A의 단어와 A쪽 빈도. below = 1이면 B에 아예 없는 단어만 남는다.
+pub fn subtract(a: &Counter, b: &Counter, below: usize) -> Counter {
+    let mut kept = Counter::new();
+    for (word, n) in a.iter() {
+        if b.get(word) < below {
+            kept.add(word, n);
+        }
+    }
+    kept
+}
+
+// 모든 말뭉치에 한 번 이상 나온 단어와 전체 합계. 첫 말뭉치의 등장 순서를 따른다.
+pub fn intersect(corpora: &[Counter]) -> Counter {
+    let mut shared = Counter::new();
+    let Some(first) = corpora.first() else {
+        return shared;
+    };
+    for (word, _) in first.iter() {
+        if corpora.iter().all(|c| c.get(word) > 0) {
+            shared.add(word, corpora.iter().map(|c| c.get(word)).sum());
+        }
+    }
+    shared
+}
@@ -0,0 +1,143 @@
+// 기본 토큰화([A-Za-z]+)의 빠른 경로. 바이트를 하나씩 보지 않고 64바이트 블록마다
+// "글자인가"를 비트 하나로 모은 마스크(u64)를 만든 뒤, 비트가 바뀌는 자리만 단어 경계로 본다.
+// Python이라면 re.findall(r"[A-Za-z]+")가 C로 대신 해 주는 일이다.
+// x86_64에서는 실행 중에 CPU를 보고 AVX2(32바이트씩) 또는 SSE2(16바이트씩, x86_64라면 항상 있음)로
+// 마스크를 만들고, 그 밖의 CPU와 64바이트가 안 되는 끝부분은 같은 마스크를 스칼라로 만든다.
+// ASCII가 아닌 바이트는 글자가 아니므로 UTF-8 문자 중간에서 자르는 일은 없다.
+// 단어는 원문에서 빌린 &str로 내놓고, 소문자 변환은 부르는 쪽(tokens, count_words)에 맡긴다.
+
+const BLOCK: usize = 64;
+
+// A-Z, a-z이면 true. 0x20을 켜면 대문자가 소문자가 되고, 그 뒤 'a'를 빼서 26 미만인지 본다.
+fn is_alpha(byte: u8) -> bool {
+    (byte | 0x20).wrapping_sub(b'a') < 26
+}
+
+fn scalar_mask(chunk: &[u8]) -> u64 {
+    chunk
+        .iter()
+        .enumerate()
+        .fold(0, |mask, (i, &b)| mask | (u64::from(is_alpha(b)) << i))
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    // SIMD에는 부호 없는 바이트 비교가 없으므로 0x80을 뒤집어 부호 있는 비교로 바꾼다:
+    // (b | 0x20) - 'a' < 26 (부호 없음)  <=>  (b | 0x20) + 31 < -102 (부호 있음).
+    #[target_feature(enable = "avx2")]
+    unsafe fn lanes_avx2(ptr: *const u8) -> u64 {
+        let v = _mm256_loadu_si256(ptr as *const __m256i);
+        let x = _mm256_add_epi8(
+            _mm256_or_si256(v, _mm256_set1_epi8(0x20)),
+            _mm256_set1_epi8(31),
+        );
+        let alpha = _mm256_cmpgt_epi8(_mm256_set1_epi8(-102), x);
+        u64::from(_mm256_movemask_epi8(alpha) as u32)
+    }
+
+    // block은 64바이트여야 한다.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn mask_avx2(block: &[u8]) -> u64 {
+        let ptr = block.as_ptr();
+        lanes_avx2(ptr) | (lanes_avx2(ptr.add(32)) << 32)
+    }
+
+    unsafe fn lanes_sse2(ptr: *const u8) -> u64 {
+        let v = _mm_loadu_si128(ptr as *const __m128i);
+        let x = _mm_add_epi8(_mm_or_si128(v, _mm_set1_epi8(0x20)), _mm_set1_epi8(31));
+        let alpha = _mm_cmplt_epi8(x, _mm_set1_epi8(-102));
+        u64::from(_mm_movemask_epi8(alpha) as u16)
+    }
+
+    pub unsafe fn mask_sse2(block: &[u8]) -> u64 {
+        let ptr = block.as_ptr();
+        lanes_sse2(ptr)
+            | (lanes_sse2(ptr.add(16)) << 16)
+            | (lanes_sse2(ptr.add(32)) << 32)
+            | (lanes_sse2(ptr.add(48)) << 48)
+    }
+}
+
+// 원문에서 빌린 단어 조각을 차례로 내놓는다(소문자로 바꾸지 않는다). 블록 하나의 마스크를 만들어
+// 경계 비트를 다 쓰면 다음 블록으로 넘어가므로, 토큰마다 메모리를 따로 잡지 않는다.
+// 블록 경계를 넘어 이어지는 단어가 있으므로 "지금 단어 안인가"(start)를 블록 사이에 들고 다닌다.
+pub struct AsciiWords<'a> {
+    text: &'a str,
+    // 다음에 마스크를 만들 블록의 시작.
+    next_block: usize,
+    base: usize,
+    mask: u64,
+    // 바로 앞 바이트와 글자 여부가 다른 자리에 선 비트. 하나씩 지워 가며 쓴다.
+    edges: u64,
+    start: Option<usize>,
+    #[cfg(target_arch = "x86_64")]
+    avx2: bool,
+}
+
+impl<'a> AsciiWords<'a> {
+    pub fn new(text: &'a str) -> AsciiWords<'a> {
+        AsciiWords {
+            text,
+            next_block: 0,
+            base: 0,
+            mask: 0,
+            edges: 0,
+            start: None,
+            #[cfg(target_arch = "x86_64")]
+            avx2: is_x86_feature_detected!("avx2"),
+        }
+    }
+
+    fn block_mask(&self, block: &[u8]) -> u64 {
+        if block.len() < BLOCK {
+            return scalar_mask(block);
+        }
+        // 안전성: block은 정확히 64바이트이고, AVX2는 new()에서 확인했다.
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            if self.avx2 {
+                x86::mask_avx2(block)
+            } else {
+                x86::mask_sse2(block)
+            }
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        scalar_mask(block)
+    }
+}
+
+impl<'a> Iterator for AsciiWords<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        loop {
+            while self.edges != 0 {
+                let bit = self.edges.trailing_zeros() as usize;
+                self.edges &= self.edges - 1;
+                if self.mask >> bit & 1 == 1 {
+                    self.start = Some(self.base + bit);
+                } else if let Some(start) = self.start.take() {
+                    return Some(&self.text[start..self.base + bit]);
+                }
+            }
+            let bytes = self.text.as_bytes();
+            if self.next_block >= bytes.len() {
+                return self.start.take().map(|start| &self.text[start..]);
+            }
+            let end = (self.next_block + BLOCK).min(bytes.len());
+            let block = &bytes[self.next_block..end];
+            let valid = if block.len() == BLOCK {
+                u64::MAX
+            } else {
+                (1u64 << block.len()) - 1
+            };
+            self.mask = self.block_mask(block);
+            let before = (self.mask << 1) | u64::from(self.start.is_some());
+            self.edges = (self.mask ^ before) & valid;
+            self.base = self.next_block;
+            self.next_block = end;
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// -r로 여러 종류가 섞인 디렉터리를 셀 때 파일마다 알맞은 전처리를 고른다.
+// 확장자가 있으면 먼저 믿고, 없거나 모르는 확장자면 앞부분 바이트를 보고 정한다.
+// Python이라면 mimetypes.guess_type()에 magic 바이트 검사를 더한 자리다.
+// HTML은 태그와 script/style을 걷어 내고, Markdown은 문법 기호를 걷어 낸 본문만 센다.
+
+use std::path::Path;
+
+use crate::xml;
+
+// 판정에 보는 앞부분 크기. ripgrep처럼 여기 NUL이 있으면 바이너리로 본다.
+pub const SNIFF: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Text,
+    Html,
+    Markdown,
+    Binary,
+}
+
+// 텍스트 확장자를 달고 있어도 NUL이나 이미지/압축 시그니처가 있으면 바이너리로 본다.
+pub fn sniff(path: &Path, data: &[u8]) -> Kind {
+    let head = &data[..data.len().min(SNIFF)];
+    if head.contains(&0) || MAGIC.iter().any(|m| head.starts_with(m)) {
+        return Kind::Binary;
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match ext.as_deref() {
+        Some("html" | "htm" | "xhtml") => return Kind::Html,
+        Some("md" | "markdown" | "mdown") => return Kind::Markdown,
+        Some("txt" | "text") => return Kind::Text,
+        _ => {}
+    }
+    let start = String::from_utf8_lossy(&head[..head.len().min(512)]).to_ascii_lowercase();
+    let start = start.trim_start_matches('\u{feff}').trim_start();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        Kind::Html
+    } else {
+        Kind::Text
+    }
+}
+
+// PNG, JPEG, GIF, PDF, ZIP(docx/epub 포함), gzip, ELF. NUL이 앞에 안 나오는 것들이다.
+const MAGIC: &[&[u8]] = &[
+    b"\x89PNG",
+    b"\xff\xd8\xff",
+    b"GIF8",
+    b"%PDF",
+    b"PK\x03\x04",
+    b"\x1f\x8b",
+    b"\x7fELF",
+];
+
+pub fn extract(kind: Kind, text: &str) -> String {
+    match kind {
+        Kind::Html => html_text(text),
+        Kind::Markdown => markdown_text(text),
+        Kind::Text | Kind::Binary => text.to_string(),
+    }
+}
+
+const HTML_BLOCKS: &[&str] = &[
+    "p",
+    "div",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "tr",
+    "td",
+    "th",
+    "br",
+    "pre",
+    "blockquote",
+    "title",
+    "section",
+    "article",
+];
+
+// script/style 안은 태그가 아니라서 xml::events()가 본문으로 읽는다. 먼저 통째로 잘라 낸다.
+fn html_text(src: &str) -> String {
+    let mut kept = String::with_capacity(src.len());
+    let lower = src.to_ascii_lowercase();
+    let mut pos = 0;
+    while let Some((open, close)) = next_raw_element(&lower, pos) {
+        kept.push_str(&src[pos..open]);
+        pos = lower[open..]
+            .find(close)
+            .map_or(src.len(), |end| open + end + close.len());
+    }
+    kept.push_str(&src[pos..]);
+    xml::text_lines(&kept, None, Some(HTML_BLOCKS)).join("\n")
+}
+
+fn next_raw_element(lower: &str, from: usize) -> Option<(usize, &'static str)> {
+    [("<script", "</script>"), ("<style", "</style>")]
+        .iter()
+        .filter_map(|(open, close)| lower[from..].find(open).map(|i| (from + i, *close)))
+        .min_by_key(|(i, _)| *i)
+}
+
+// 줄 구조는 그대로 두고 기호만 지운다: 코드 블록, 머리 #, 인용 >, 목록 기호, 링크 주소,
+// 강조 기호(* _ `), 인라인 HTML 태그. 표의 |는 공백으로 바꾼다.
+fn markdown_text(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut fenced = false;
+    for line in src.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fenced = !fenced;
+            out.push('\n');
+            continue;
+        }
+        if fenced {
+            out.push('\n');
+            continue;
+        }
+        let body = trimmed.trim_start_matches('>').trim_start();
+        let body = body.trim_start_matches('#').trim_start();
+        let body = strip_list_marker(body);
+        out.push_str(&inline_text(body));
+        out.push('\n');
+    }
+    out
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return rest;
+        }
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 {
+        if let Some(rest) = line[digits..]
+            .strip_prefix(". ")
+            .or(line[digits..].strip_prefix(") "))
+        {
+            return rest;
+        }
+    }
+    line
+}
+
+// [글](주소)와 ![대체 글](주소)는 글만, <태그>는 지우고, 강조 기호는 공백 없이 뺀다.
+fn inline_text(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '*' | '_' | '`' | '|' => {
+                // snake_case 같은 단어 안의 _는 건드리지 않는다.
+                let inside_word = c == '_'
+                    && out.chars().last().is_some_and(char::is_alphanumeric)
+                    && chars.peek().is_some_and(|(_, n)| n.is_alphanumeric());
+                if inside_word {
+                    out.push(c);
+                } else if c == '|' {
+                    out.push(' ');
+                }
+            }
+            '!' if chars.peek().is_some_and(|(_, n)| *n == '[') => {}
+            ']' if line[i + 1..].starts_with('(') => {
+                let close = line[i + 1..].find(')').map_or(line.len(), |j| i + 1 + j);
+                while chars.peek().is_some_and(|(j, _)| *j <= close) {
+                    chars.next();
+                }
+            }
+            '[' | ']' => {}
+            '<' if line[i..].find('>').is_some_and(|j| {
+                let tag = &line[i + 1..i + j];
+                tag.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/')
+            }) =>
+            {
+                let end = i + line[i..].find('>').expect("checked above");
+                while chars.peek().is_some_and(|(j, _)| *j <= end) {
+                    chars.next();
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,54 @@
+// social 서브커맨드: 트윗/채팅 내보내기에서 #해시태그와 @멘션을 일반 단어와 따로 센다.
+// 태그 글자는 유니코드 문자·숫자·밑줄이다(#러스트 같은 한글 태그도 흔하다).
+// 둘 다 대소문자를 구분하지 않으므로 소문자로 맞춰 센다.
+
+use crate::counter::Counter;
+
+pub struct Tally {
+    pub hashtags: Counter,
+    pub mentions: Counter,
+    // 태그를 공백으로 지운 나머지 본문. 일반 단어 집계에 쓴다.
+    pub rest: String,
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+pub fn tally(text: &str) -> Tally {
+    let mut hashtags = Counter::new();
+    let mut mentions = Counter::new();
+    let mut rest = String::with_capacity(text.len());
+    let mut prev: Option<char> = None;
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        // "a@b.com"이나 "C#"처럼 앞에 글자가 붙어 있으면 태그가 아니다.
+        let at_boundary = prev.is_none_or(|p| !is_tag_char(p) && p != '&');
+        if (c == '#' || c == '@') && at_boundary {
+            let body = &text[i + 1..];
+            let len = body.find(|ch| !is_tag_char(ch)).unwrap_or(body.len());
+            let tag = &body[..len];
+            // "#1"처럼 숫자만 있으면 번호로 본다.
+            if !tag.is_empty() && !tag.chars().all(|ch| ch.is_ascii_digit()) {
+                let counts = if c == '#' {
+                    &mut hashtags
+                } else {
+                    &mut mentions
+                };
+                counts.add(&format!("{c}{}", tag.to_lowercase()), 1);
+                rest.push(' ');
+                prev = tag.chars().last();
+                i += 1 + len;
+                continue;
+            }
+        }
+        rest.push(c);
+        prev = Some(c);
+        i += c.len_utf8();
+    }
+    Tally {
+        hashtags,
+        mentions,
+        rest,
+    }
+}
@@ -0,0 +1,48 @@
+// --group-by-speaker REGEX: 채팅 기록이나 회의록에서 줄마다 말한 사람을 정규식으로 뽑아
+// 사람별 상위 단어 표를 따로 만든다. 말한 사람은 첫 번째 괄호 그룹(없으면 일치 전체)이고,
+// 일치 뒤의 나머지가 그 사람의 말이다. "alice:" 같은 머리가 단어로 세어지지 않게 한다.
+// 일치하지 않는 줄은 여러 줄 메시지의 이어지는 줄로 보고 바로 앞 사람에게 붙인다.
+// 첫 화자가 나오기 전의 줄(내보내기 머리글 등)은 누구의 말도 아니므로 전체 표에만 들어간다.
+
+use crate::regex::Regex;
+
+pub struct Grouped {
+    // 처음 나온 순서대로 (말한 사람, 그 사람의 말).
+    pub speakers: Vec<(String, String)>,
+    // 화자 머리를 뺀 전체 본문. 전체 상위 단어 표는 이것으로 센다.
+    pub text: String,
+}
+
+pub fn group(text: &str, regex: &Regex) -> Grouped {
+    let mut speakers: Vec<(String, String)> = Vec::new();
+    let mut all = String::with_capacity(text.len());
+    let mut current: Option<usize> = None;
+    for line in text.lines() {
+        let mut said = line;
+        if let Some(caps) = regex.captures(line) {
+            let whole = caps[0].unwrap_or_default();
+            let (start, end) = caps.get(1).copied().flatten().unwrap_or(whole);
+            let name = line[start..end].trim();
+            if !name.is_empty() {
+                said = &line[whole.1..];
+                current = Some(match speakers.iter().position(|(s, _)| s == name) {
+                    Some(i) => i,
+                    None => {
+                        speakers.push((name.to_string(), String::new()));
+                        speakers.len() - 1
+                    }
+                });
+            }
+        }
+        if let Some(i) = current {
+            speakers[i].1.push_str(said);
+            speakers[i].1.push('\n');
+        }
+        all.push_str(said);
+        all.push('\n');
+    }
+    Grouped {
+        speakers,
+        text: all,
+    }
+}
@@ -0,0 +1,166 @@
+// 사전 기반 맞춤법 검사와 편집 거리(Levenshtein).
+// Python이라면 difflib.get_close_matches()를 떠올리면 되는데,
+// 여기서는 길이 차이로 후보를 먼저 거른 뒤 상한(max)이 있는 DP로 거리를 계산한다.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::{normalize_words, Tokenizer};
+
+pub struct Misspelling {
+    pub word: String,
+    pub count: usize,
+    pub suggestions: Vec<String>,
+}
+
+pub fn load_dictionary(path: &str, tok: Tokenizer) -> Result<HashSet<String>, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("failed to read dictionary: {e}"))?;
+    Ok(normalize_words(&content, tok).into_iter().collect())
+}
+
+pub fn check(text: &str, dictionary: &HashSet<String>, tok: Tokenizer) -> Vec<Misspelling> {
+    let mut unknown: HashMap<String, usize> = HashMap::new();
+    for word in normalize_words(text, tok) {
+        if !dictionary.contains(&word) {
+            *unknown.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    let mut report: Vec<Misspelling> = unknown
+        .into_iter()
+        .map(|(word, count)| {
+            let suggestions = suggest(&word, dictionary, 2, 3);
+            Misspelling {
+                word,
+                count,
+                suggestions,
+            }
+        })
+        .collect();
+    report.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+    report
+}
+
+// suggest: 사전 없이 입력 자체의 빈도로 오타를 짐작한다. 드물게 나온 단어가 훨씬 자주 나온
+// 단어와 편집 거리 1~2 안에 있으면("teh" 3번, "the" 400번) 오타일 가능성이 높다.
+pub struct Typo {
+    pub word: String,
+    pub count: usize,
+    pub likely: String,
+    pub likely_count: usize,
+    pub distance: usize,
+}
+
+impl Typo {
+    pub fn ratio(&self) -> f64 {
+        self.likely_count as f64 / self.count as f64
+    }
+}
+
+// 후보 단어가 이 배수 이상 자주 나와야 오타로 본다.
+pub const MIN_RATIO: usize = 10;
+// 이보다 짧은 단어는 거리 1만 허용한다("in"과 "on", "cat"과 "car"처럼 짧은 단어끼리는
+// 거리 2 안에 진짜 단어가 너무 많다). 2글자 이하는 아예 보지 않는다.
+const SHORT: usize = 5;
+
+pub fn typos(text: &str, tok: Tokenizer) -> Vec<Typo> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for word in normalize_words(text, tok) {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    // 자주 나온 순으로 세워 두면 "count * MIN_RATIO번 이상"인 후보는 늘 앞쪽 구간이다.
+    let mut frequent: Vec<(&String, usize)> = counts
+        .iter()
+        .filter(|(_, &count)| count >= MIN_RATIO)
+        .map(|(word, &count)| (word, count))
+        .collect();
+    frequent.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    let mut found: Vec<Typo> = counts
+        .iter()
+        .filter(|(word, _)| word.chars().count() > 2)
+        .filter_map(|(word, &count)| {
+            let len = word.chars().count();
+            let max = if len < SHORT { 1 } else { 2 };
+            frequent
+                .iter()
+                .take_while(|(_, likely)| *likely >= count * MIN_RATIO)
+                .filter_map(|&(likely, likely_count)| {
+                    // 이웃한 두 글자가 뒤바뀐 것("teh")은 흔한 오타라 거리 1로 친다.
+                    let distance = if is_transposition(word, likely) {
+                        1
+                    } else {
+                        levenshtein(word, likely, max)?
+                    };
+                    (distance > 0).then_some((distance, likely, likely_count))
+                })
+                // 가장 가까운 것, 같은 거리면 더 자주 나온 것(frequent 순서가 이미 그렇다).
+                .min_by_key(|(distance, _, _)| *distance)
+                .map(|(distance, likely, likely_count)| Typo {
+                    word: word.clone(),
+                    count,
+                    likely: likely.clone(),
+                    likely_count,
+                    distance,
+                })
+        })
+        .collect();
+    found.sort_by(|a, b| {
+        b.ratio()
+            .total_cmp(&a.ratio())
+            .then_with(|| a.word.cmp(&b.word))
+    });
+    found
+}
+
+fn is_transposition(a: &str, b: &str) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len() != b.len() {
+        return false;
+    }
+    let differ: Vec<usize> = (0..a.len()).filter(|&i| a[i] != b[i]).collect();
+    matches!(differ.as_slice(), [i, j] if *j == i + 1 && a[*i] == b[*j] && a[*j] == b[*i])
+}
+
+// 거리 max 이하인 사전 단어 중 가까운 순(동률은 알파벳순)으로 limit개.
+fn suggest(word: &str, dictionary: &HashSet<String>, max: usize, limit: usize) -> Vec<String> {
+    let len = word.chars().count();
+    let mut candidates: Vec<(usize, &String)> = dictionary
+        .iter()
+        .filter(|d| d.chars().count().abs_diff(len) <= max)
+        .filter_map(|d| levenshtein(word, d, max).map(|dist| (dist, d)))
+        .collect();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(limit)
+        .map(|(_, d)| d.clone())
+        .collect()
+}
+
+// 거리가 max를 넘으면 None. 한 행의 최솟값이 max를 넘는 순간 계산을 멈춘다.
+pub fn levenshtein(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        if curr.iter().min().copied().unwrap_or(0) > max {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    let dist = prev[b.len()];
+    (dist <= max).then_some(dist)
+}
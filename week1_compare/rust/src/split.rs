@@ -0,0 +1,103 @@
+// split: 큰 입력을 줄 경계에서 --by-tokens개 안팎의 토큰 묶음으로 나눈다. NLP 실험의
+// train/test 조각을 전처리 스크립트 없이 이 도구에서 바로 만들려는 것이다.
+// 기본은 차례대로 자른다: 다음 줄을 더하면 N을 넘을 때 새 조각을 연다(한 줄이 N보다 길면 그 줄만).
+// --stratify 단어들을 주면 줄마다 처음 걸리는 단어(없으면 other)로 층을 나누고, 층마다 줄을
+// 조각에 돌아가며 나눠 준다. 그래서 조각마다 층의 비율이 전체와 거의 같다. 조각 수는 전체 토큰을
+// N으로 나눈 것이라 조각 크기는 N 근처다. 조각 안의 줄은 원문 순서를 지키고, 토큰이 없는 줄은 뺀다.
+// 토큰 수와 --stratify 단어는 본문과 같은 규칙으로 정규화해서 센다.
+
+use crate::{normalize_words, Tokenizer};
+
+pub const OTHER: &str = "other";
+
+pub struct Chunk {
+    // 원문 줄의 위치(0부터), 오름차순.
+    pub lines: Vec<usize>,
+    pub tokens: usize,
+    // --stratify: strata와 같은 순서의 층별 줄 수.
+    pub strata: Vec<usize>,
+}
+
+// 조각들과, --stratify일 때 층 이름(주어진 단어 순서, 끝에 other).
+pub fn split(
+    lines: &[&str],
+    size: usize,
+    stratify: &[String],
+    tok: Tokenizer,
+) -> (Vec<Chunk>, Vec<String>) {
+    let words: Vec<Vec<String>> = lines
+        .iter()
+        .map(|line| normalize_words(line, tok))
+        .collect();
+    if stratify.is_empty() {
+        return (sequential(&words, size), Vec::new());
+    }
+    let mut strata: Vec<String> = stratify.to_vec();
+    strata.push(OTHER.to_string());
+    (stratified(&words, size, stratify), strata)
+}
+
+fn sequential(words: &[Vec<String>], size: usize) -> Vec<Chunk> {
+    let mut chunks: Vec<Chunk> = Vec::new();
+    for (i, line) in words.iter().enumerate() {
+        let n = line.len();
+        match chunks.last_mut() {
+            Some(chunk) if chunk.tokens == 0 || chunk.tokens + n <= size => {
+                chunk.lines.push(i);
+                chunk.tokens += n;
+            }
+            _ => chunks.push(Chunk {
+                lines: vec![i],
+                tokens: n,
+                strata: Vec::new(),
+            }),
+        }
+    }
+    chunks
+}
+
+fn stratified(words: &[Vec<String>], size: usize, stratify: &[String]) -> Vec<Chunk> {
+    let total: usize = words.iter().map(Vec::len).sum();
+    let count = total.div_ceil(size).max(1);
+    let mut chunks: Vec<Chunk> = (0..count)
+        .map(|_| Chunk {
+            lines: Vec::new(),
+            tokens: 0,
+            strata: vec![0; stratify.len() + 1],
+        })
+        .collect();
+    let stratum_of = |line: &[String]| {
+        stratify
+            .iter()
+            .position(|word| line.contains(word))
+            .unwrap_or(stratify.len())
+    };
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); stratify.len() + 1];
+    for (i, line) in words.iter().enumerate() {
+        if !line.is_empty() {
+            layers[stratum_of(line)].push(i);
+        }
+    }
+    // 층이 바뀌어도 차례를 이어 가야 층마다 남는 한두 줄이 앞 조각에 몰리지 않는다.
+    let mut turn = 0;
+    for (stratum, layer) in layers.iter().enumerate() {
+        for &i in layer {
+            let chunk = &mut chunks[turn % count];
+            chunk.lines.push(i);
+            chunk.tokens += words[i].len();
+            chunk.strata[stratum] += 1;
+            turn += 1;
+        }
+    }
+    for chunk in &mut chunks {
+        chunk.lines.sort_unstable();
+    }
+    chunks.retain(|chunk| !chunk.lines.is_empty());
+    chunks
+}
+
+// prefix.001, prefix.002, ... 조각이 1000개를 넘으면 자릿수를 늘린다.
+pub fn chunk_path(prefix: &str, index: usize, count: usize) -> String {
+    let width = count.to_string().len().max(3);
+    format!("{prefix}.{:0width$}", index + 1)
+}
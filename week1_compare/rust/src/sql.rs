@@ -0,0 +1,1084 @@
+// query --sql: 센 결과를 표 하나(words)로 보고 작은 SQL로 잘라 본다. 내보냈다가 다른 도구로
+// 다시 읽지 않고도 "10번 넘게 나온 5글자 단어"처럼 바로 물어볼 수 있게 하려는 것이다.
+// 표 words의 열: rank(순위, 1부터), word, count. 행은 순위 순서다(--tie-break, --collate를 따른다).
+//
+// 문법(SQLite의 부분집합, 키워드는 대소문자 무시):
+//   SELECT [DISTINCT] item, ... FROM words [WHERE expr] [GROUP BY expr, ... [HAVING expr]]
+//     [ORDER BY expr [ASC|DESC], ...] [LIMIT n [OFFSET m]]
+//   item := * | expr [[AS] name]
+//   expr := OR, AND, NOT, 비교(= == != <> < <= > >=), [NOT] LIKE 'a%_', [NOT] IN (...),
+//           [NOT] BETWEEN a AND b, IS [NOT] NULL, + - * / % ||, 괄호, 'text', 123, 1.5
+//   함수: LENGTH LOWER UPPER ABS, 집계: COUNT(*) COUNT SUM AVG MIN MAX
+// 집계가 있고 GROUP BY가 없으면 표 전체가 한 묶음이다. ORDER BY는 고른 열의 이름이나 번호도 받는다.
+// 정수끼리 나누면 정수다(SQLite와 같다).
+
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct SqlError {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+    pub hint: Option<&'static str>,
+}
+
+impl SqlError {
+    fn new(message: impl Into<String>, start: usize, end: usize) -> SqlError {
+        SqlError {
+            message: message.into(),
+            start,
+            end,
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: &'static str) -> SqlError {
+        self.hint = Some(hint);
+        self
+    }
+}
+
+const COLUMNS: [&str; 3] = ["rank", "word", "count"];
+const COLUMNS_HINT: &str = "the words table has the columns rank, word, count";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Int(i64),
+    Real(f64),
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "NULL"),
+            Value::Int(n) => write!(f, "{n}"),
+            // 소수는 여섯 자리까지, 뒤의 0은 뺀다.
+            Value::Real(x) => {
+                let text = format!("{x:.6}");
+                let text = text.trim_end_matches('0');
+                write!(f, "{}", text.strip_suffix('.').unwrap_or(text))
+            }
+            Value::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl Value {
+    pub fn is_number(&self) -> bool {
+        matches!(self, Value::Int(_) | Value::Real(_))
+    }
+
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Int(n) => *n != 0,
+            Value::Real(x) => *x != 0.0,
+            Value::Text(s) => !s.is_empty(),
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(n) => Some(*n as f64),
+            Value::Real(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    // SQLite의 정렬 순서: NULL < 숫자 < 문자열.
+    fn compare(&self, other: &Value) -> Ordering {
+        fn class(v: &Value) -> u8 {
+            match v {
+                Value::Null => 0,
+                Value::Int(_) | Value::Real(_) => 1,
+                Value::Text(_) => 2,
+            }
+        }
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::Text(a), Value::Text(b)) => a.cmp(b),
+            (a, b) if a.is_number() && b.is_number() => {
+                let (x, y) = (a.as_f64().unwrap_or(0.0), b.as_f64().unwrap_or(0.0));
+                x.partial_cmp(&y).unwrap_or(Ordering::Equal)
+            }
+            (a, b) => class(a).cmp(&class(b)),
+        }
+    }
+}
+
+fn boolean(b: bool) -> Value {
+    Value::Int(i64::from(b))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Concat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Func {
+    Length,
+    Lower,
+    Upper,
+    Abs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Agg {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    // COLUMNS의 번호.
+    Column(usize),
+    Literal(Value),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Like(Box<Expr>, Box<Expr>, bool),
+    In(Box<Expr>, Vec<Expr>, bool),
+    Between(Box<Expr>, Box<Expr>, Box<Expr>, bool),
+    IsNull(Box<Expr>, bool),
+    Call(Func, Box<Expr>),
+    // None은 COUNT(*).
+    Aggregate(Agg, Option<Box<Expr>>),
+    // 열도 함수도 아닌 이름. 다 읽은 뒤 SELECT의 별명으로 바꾸거나 에러로 낸다.
+    Name(String, usize, usize),
+}
+
+impl Expr {
+    fn has_aggregate(&self) -> bool {
+        match self {
+            Expr::Aggregate(..) => true,
+            Expr::Column(_) | Expr::Literal(_) | Expr::Name(..) => false,
+            Expr::Neg(e) | Expr::Not(e) | Expr::Call(_, e) | Expr::IsNull(e, _) => {
+                e.has_aggregate()
+            }
+            Expr::Binary(_, a, b) | Expr::Like(a, b, _) => a.has_aggregate() || b.has_aggregate(),
+            Expr::In(e, list, _) => e.has_aggregate() || list.iter().any(Expr::has_aggregate),
+            Expr::Between(e, lo, hi, _) => {
+                e.has_aggregate() || lo.has_aggregate() || hi.has_aggregate()
+            }
+        }
+    }
+}
+
+pub struct Row<'a> {
+    pub rank: usize,
+    pub word: &'a str,
+    pub count: usize,
+}
+
+impl Row<'_> {
+    fn get(&self, column: usize) -> Value {
+        match column {
+            0 => Value::Int(self.rank as i64),
+            1 => Value::Text(self.word.to_string()),
+            _ => Value::Int(self.count as i64),
+        }
+    }
+}
+
+// 묶음 하나(집계가 없으면 행 하나). 집계가 아닌 열은 묶음의 첫 행 값이다.
+fn eval(expr: &Expr, group: &[&Row]) -> Result<Value, String> {
+    Ok(match expr {
+        Expr::Column(c) => group.first().map_or(Value::Null, |row| row.get(*c)),
+        Expr::Literal(v) => v.clone(),
+        Expr::Name(name, ..) => return Err(format!("no such column: {name}")),
+        Expr::Neg(e) => match eval(e, group)? {
+            Value::Int(n) => Value::Int(n.wrapping_neg()),
+            Value::Real(x) => Value::Real(-x),
+            Value::Null => Value::Null,
+            Value::Text(_) => return Err("cannot negate text".to_string()),
+        },
+        Expr::Not(e) => match eval(e, group)? {
+            Value::Null => Value::Null,
+            v => boolean(!v.truthy()),
+        },
+        Expr::Binary(BinOp::And, a, b) => {
+            let a = eval(a, group)?;
+            if a != Value::Null && !a.truthy() {
+                return Ok(boolean(false));
+            }
+            let b = eval(b, group)?;
+            match (&a, &b) {
+                (_, b) if *b != Value::Null && !b.truthy() => boolean(false),
+                (Value::Null, _) | (_, Value::Null) => Value::Null,
+                _ => boolean(true),
+            }
+        }
+        Expr::Binary(BinOp::Or, a, b) => {
+            let a = eval(a, group)?;
+            if a.truthy() {
+                return Ok(boolean(true));
+            }
+            let b = eval(b, group)?;
+            match (&a, &b) {
+                (_, b) if b.truthy() => boolean(true),
+                (Value::Null, _) | (_, Value::Null) => Value::Null,
+                _ => boolean(false),
+            }
+        }
+        Expr::Binary(op, a, b) => binary(*op, eval(a, group)?, eval(b, group)?)?,
+        Expr::Like(e, pattern, negated) => match (eval(e, group)?, eval(pattern, group)?) {
+            (Value::Null, _) | (_, Value::Null) => Value::Null,
+            (text, pattern) => {
+                let text: Vec<char> = text.to_string().to_lowercase().chars().collect();
+                let pattern: Vec<char> = pattern.to_string().to_lowercase().chars().collect();
+                boolean(like(&text, &pattern) != *negated)
+            }
+        },
+        Expr::In(e, list, negated) => {
+            let value = eval(e, group)?;
+            if value == Value::Null {
+                return Ok(Value::Null);
+            }
+            let mut found = false;
+            for item in list {
+                if value.compare(&eval(item, group)?) == Ordering::Equal {
+                    found = true;
+                    break;
+                }
+            }
+            boolean(found != *negated)
+        }
+        Expr::Between(e, lo, hi, negated) => {
+            let (v, lo, hi) = (eval(e, group)?, eval(lo, group)?, eval(hi, group)?);
+            if [&v, &lo, &hi].contains(&&Value::Null) {
+                return Ok(Value::Null);
+            }
+            let inside = v.compare(&lo) != Ordering::Less && v.compare(&hi) != Ordering::Greater;
+            boolean(inside != *negated)
+        }
+        Expr::IsNull(e, negated) => boolean((eval(e, group)? == Value::Null) != *negated),
+        Expr::Call(func, e) => match (func, eval(e, group)?) {
+            (_, Value::Null) => Value::Null,
+            (Func::Length, v) => Value::Int(v.to_string().chars().count() as i64),
+            (Func::Lower, v) => Value::Text(v.to_string().to_lowercase()),
+            (Func::Upper, v) => Value::Text(v.to_string().to_uppercase()),
+            (Func::Abs, Value::Int(n)) => Value::Int(n.wrapping_abs()),
+            (Func::Abs, Value::Real(x)) => Value::Real(x.abs()),
+            (Func::Abs, Value::Text(_)) => return Err("ABS() needs a number".to_string()),
+        },
+        Expr::Aggregate(agg, arg) => {
+            let mut values = Vec::with_capacity(group.len());
+            for row in group {
+                let value = match arg {
+                    Some(e) => eval(e, &[row])?,
+                    None => Value::Int(1),
+                };
+                if value != Value::Null {
+                    values.push(value);
+                }
+            }
+            aggregate(*agg, values)?
+        }
+    })
+}
+
+fn aggregate(agg: Agg, values: Vec<Value>) -> Result<Value, String> {
+    if agg == Agg::Count {
+        return Ok(Value::Int(values.len() as i64));
+    }
+    if values.is_empty() {
+        return Ok(Value::Null);
+    }
+    Ok(match agg {
+        Agg::Min | Agg::Max => {
+            let pick = if agg == Agg::Min {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
+            values
+                .into_iter()
+                .reduce(|best, v| if v.compare(&best) == pick { v } else { best })
+                .unwrap_or(Value::Null)
+        }
+        _ => {
+            if values.iter().any(|v| !v.is_number()) {
+                return Err("SUM() and AVG() need numbers".to_string());
+            }
+            let n = values.len();
+            let sum = if values.iter().all(|v| matches!(v, Value::Int(_))) {
+                Value::Int(
+                    values
+                        .iter()
+                        .map(|v| v.as_f64().unwrap_or(0.0) as i64)
+                        .sum(),
+                )
+            } else {
+                Value::Real(values.iter().filter_map(Value::as_f64).sum())
+            };
+            match agg {
+                Agg::Avg => Value::Real(sum.as_f64().unwrap_or(0.0) / n as f64),
+                _ => sum,
+            }
+        }
+    })
+}
+
+fn binary(op: BinOp, a: Value, b: Value) -> Result<Value, String> {
+    if a == Value::Null || b == Value::Null {
+        return Ok(Value::Null);
+    }
+    let ordering = || a.compare(&b);
+    Ok(match op {
+        BinOp::Eq => boolean(ordering() == Ordering::Equal),
+        BinOp::Ne => boolean(ordering() != Ordering::Equal),
+        BinOp::Lt => boolean(ordering() == Ordering::Less),
+        BinOp::Le => boolean(ordering() != Ordering::Greater),
+        BinOp::Gt => boolean(ordering() == Ordering::Greater),
+        BinOp::Ge => boolean(ordering() != Ordering::Less),
+        BinOp::Concat => Value::Text(format!("{a}{b}")),
+        _ => {
+            let symbol = match op {
+                BinOp::Add => "+",
+                BinOp::Sub => "-",
+                BinOp::Mul => "*",
+                BinOp::Div => "/",
+                _ => "%",
+            };
+            if !a.is_number() || !b.is_number() {
+                return Err(format!("cannot apply {symbol} to text"));
+            }
+            match (a, b) {
+                (Value::Int(x), Value::Int(y)) => match op {
+                    BinOp::Add => Value::Int(x.wrapping_add(y)),
+                    BinOp::Sub => Value::Int(x.wrapping_sub(y)),
+                    BinOp::Mul => Value::Int(x.wrapping_mul(y)),
+                    // 0으로 나누면 SQLite처럼 NULL.
+                    _ if y == 0 => Value::Null,
+                    BinOp::Div => Value::Int(x.wrapping_div(y)),
+                    _ => Value::Int(x.wrapping_rem(y)),
+                },
+                (a, b) => {
+                    let (x, y) = (a.as_f64().unwrap_or(0.0), b.as_f64().unwrap_or(0.0));
+                    match op {
+                        BinOp::Add => Value::Real(x + y),
+                        BinOp::Sub => Value::Real(x - y),
+                        BinOp::Mul => Value::Real(x * y),
+                        _ if y == 0.0 => Value::Null,
+                        BinOp::Div => Value::Real(x / y),
+                        _ => Value::Real(x % y),
+                    }
+                }
+            }
+        }
+    })
+}
+
+// % = 아무 글자 여럿, _ = 아무 글자 하나.
+fn like(text: &[char], pattern: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('%', rest)) => (0..=text.len()).any(|i| like(&text[i..], rest)),
+        Some(('_', rest)) => !text.is_empty() && like(&text[1..], rest),
+        Some((c, rest)) => text.first() == Some(c) && like(&text[1..], rest),
+    }
+}
+
+struct Item {
+    expr: Expr,
+    name: String,
+}
+
+pub struct Statement {
+    distinct: bool,
+    items: Vec<Item>,
+    filter: Option<Expr>,
+    group_by: Vec<Expr>,
+    having: Option<Expr>,
+    order: Vec<(Expr, bool)>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+pub struct ResultSet {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl Statement {
+    pub fn run(&self, rows: &[Row]) -> Result<ResultSet, String> {
+        let mut kept = Vec::new();
+        for row in rows {
+            let keep = match &self.filter {
+                Some(filter) => eval(filter, &[row])?.truthy(),
+                None => true,
+            };
+            if keep {
+                kept.push(row);
+            }
+        }
+        let aggregated = !self.group_by.is_empty()
+            || self.items.iter().any(|item| item.expr.has_aggregate())
+            || self.having.is_some()
+            || self.order.iter().any(|(e, _)| e.has_aggregate());
+        // 묶음은 처음 나온 순서대로 둔다(ORDER BY가 없으면 순위 순서가 그대로 남는다).
+        let groups: Vec<Vec<&Row>> = if !aggregated {
+            kept.into_iter().map(|row| vec![row]).collect()
+        } else if self.group_by.is_empty() {
+            vec![kept]
+        } else {
+            let mut keys: Vec<Vec<Value>> = Vec::new();
+            let mut groups: Vec<Vec<&Row>> = Vec::new();
+            for row in kept {
+                let key = self
+                    .group_by
+                    .iter()
+                    .map(|e| eval(e, &[row]))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match keys.iter().position(|k| *k == key) {
+                    Some(i) => groups[i].push(row),
+                    None => {
+                        keys.push(key);
+                        groups.push(vec![row]);
+                    }
+                }
+            }
+            groups
+        };
+        let mut output = Vec::new();
+        for group in &groups {
+            if let Some(having) = &self.having {
+                if !eval(having, group)?.truthy() {
+                    continue;
+                }
+            }
+            let values = self
+                .items
+                .iter()
+                .map(|item| eval(&item.expr, group))
+                .collect::<Result<Vec<_>, _>>()?;
+            let keys = self
+                .order
+                .iter()
+                .map(|(e, _)| eval(e, group))
+                .collect::<Result<Vec<_>, _>>()?;
+            output.push((values, keys));
+        }
+        if self.distinct {
+            let mut seen: Vec<Vec<Value>> = Vec::new();
+            output.retain(|(values, _)| {
+                let new = !seen.contains(values);
+                if new {
+                    seen.push(values.clone());
+                }
+                new
+            });
+        }
+        // 안정 정렬이라 같은 키끼리는 원래(순위) 순서가 남는다.
+        output.sort_by(|(_, a), (_, b)| {
+            for (i, (_, descending)) in self.order.iter().enumerate() {
+                let ordering = a[i].compare(&b[i]);
+                if ordering != Ordering::Equal {
+                    return if *descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    };
+                }
+            }
+            Ordering::Equal
+        });
+        let rows = output
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .map(|(values, _)| values)
+            .collect();
+        Ok(ResultSet {
+            columns: self.items.iter().map(|item| item.name.clone()).collect(),
+            rows,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    // 큰따옴표로 감싼 이름. 키워드로 읽지 않는다.
+    Quoted(String),
+    Str(String),
+    Int(i64),
+    Real(f64),
+    Symbol(&'static str),
+}
+
+struct Token {
+    tok: Tok,
+    start: usize,
+    end: usize,
+}
+
+const SYMBOLS: [&str; 17] = [
+    "<=", ">=", "<>", "!=", "==", "||", "(", ")", ",", "*", "+", "-", "/", "%", "=", "<", ">",
+];
+
+fn lex(sql: &str) -> Result<Vec<Token>, SqlError> {
+    let mut tokens = Vec::new();
+    let bytes = sql.as_bytes();
+    let mut i = 0;
+    while i < sql.len() {
+        let c = sql[i..].chars().next().unwrap_or(' ');
+        let start = i;
+        if c.is_whitespace() {
+            i += c.len_utf8();
+            continue;
+        }
+        if c == '\'' || c == '"' {
+            // 따옴표 안의 같은 따옴표 두 개는 따옴표 하나다.
+            let mut text = String::new();
+            i += 1;
+            loop {
+                let Some(ch) = sql[i..].chars().next() else {
+                    return Err(SqlError::new("unterminated quote", start, sql.len())
+                        .hint("close it with a matching quote"));
+                };
+                i += ch.len_utf8();
+                if ch == c {
+                    if sql[i..].starts_with(c) {
+                        text.push(c);
+                        i += 1;
+                        continue;
+                    }
+                    break;
+                }
+                text.push(ch);
+            }
+            let tok = if c == '\'' {
+                Tok::Str(text)
+            } else {
+                Tok::Quoted(text)
+            };
+            tokens.push(Token { tok, start, end: i });
+            continue;
+        }
+        if c.is_ascii_digit() || (c == '.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)) {
+            while i < sql.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                i += 1;
+            }
+            let text = &sql[start..i];
+            let tok = if text.contains('.') {
+                text.parse().map(Tok::Real).ok()
+            } else {
+                text.parse().map(Tok::Int).ok()
+            };
+            let tok = tok.ok_or_else(|| SqlError::new("invalid number", start, i))?;
+            tokens.push(Token { tok, start, end: i });
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            while let Some(ch) = sql[i..].chars().next() {
+                if !(ch.is_alphanumeric() || ch == '_') {
+                    break;
+                }
+                i += ch.len_utf8();
+            }
+            let tok = Tok::Ident(sql[start..i].to_string());
+            tokens.push(Token { tok, start, end: i });
+            continue;
+        }
+        let Some(symbol) = SYMBOLS.iter().find(|s| sql[i..].starts_with(**s)) else {
+            return Err(SqlError::new(
+                format!("unexpected character '{c}'"),
+                start,
+                start + c.len_utf8(),
+            ));
+        };
+        i += symbol.len();
+        tokens.push(Token {
+            tok: Tok::Symbol(symbol),
+            start,
+            end: i,
+        });
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    sql: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+pub fn parse(sql: &str) -> Result<Statement, SqlError> {
+    let tokens = lex(sql)?;
+    let mut parser = Parser {
+        sql,
+        tokens,
+        pos: 0,
+    };
+    let statement = parser.statement()?;
+    if let Some(token) = parser.tokens.get(parser.pos) {
+        return Err(SqlError::new(
+            format!("unexpected '{}'", &sql[token.start..token.end]),
+            token.start,
+            token.end,
+        ));
+    }
+    Ok(statement)
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|t| &t.tok)
+    }
+
+    // 다음 토큰 자리(없으면 끝). 에러 위치에 쓴다.
+    fn here(&self) -> (usize, usize) {
+        self.tokens
+            .get(self.pos)
+            .map_or((self.sql.len(), self.sql.len()), |t| (t.start, t.end))
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Ident(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &'static str) -> Result<(), SqlError> {
+        if self.keyword(keyword) {
+            return Ok(());
+        }
+        let (start, end) = self.here();
+        Err(SqlError::new(format!("expected {keyword}"), start, end))
+    }
+
+    fn symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Tok::Symbol(s)) if *s == symbol);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &'static str) -> Result<(), SqlError> {
+        if self.symbol(symbol) {
+            return Ok(());
+        }
+        let (start, end) = self.here();
+        Err(SqlError::new(format!("expected '{symbol}'"), start, end))
+    }
+
+    fn count(&mut self, what: &'static str) -> Result<usize, SqlError> {
+        let (start, end) = self.here();
+        match self.peek() {
+            Some(Tok::Int(n)) if *n >= 0 => {
+                let n = *n as usize;
+                self.pos += 1;
+                Ok(n)
+            }
+            _ => Err(SqlError::new(
+                format!("{what} needs a non-negative integer"),
+                start,
+                end,
+            )),
+        }
+    }
+
+    fn statement(&mut self) -> Result<Statement, SqlError> {
+        self.expect_keyword("SELECT")?;
+        let distinct = self.keyword("DISTINCT");
+        let mut items = Vec::new();
+        loop {
+            if self.symbol("*") {
+                for (c, name) in COLUMNS.iter().enumerate() {
+                    items.push(Item {
+                        expr: Expr::Column(c),
+                        name: name.to_string(),
+                    });
+                }
+            } else {
+                let start = self.here().0;
+                let expr = self.expr()?;
+                let end = self.tokens[self.pos - 1].end;
+                // AS 없이 이름만 써도 별명이다(SELECT count c FROM words).
+                let bare = matches!(self.peek(), Some(Tok::Ident(w)) if !is_reserved(w))
+                    || matches!(self.peek(), Some(Tok::Quoted(_)));
+                let alias = if self.keyword("AS") || bare {
+                    Some(self.name()?)
+                } else {
+                    None
+                };
+                let name = alias.unwrap_or_else(|| self.sql[start..end].to_string());
+                items.push(Item { expr, name });
+            }
+            if !self.symbol(",") {
+                break;
+            }
+        }
+        self.expect_keyword("FROM")?;
+        let (start, end) = self.here();
+        let table = self.name()?;
+        if !table.eq_ignore_ascii_case("words") {
+            return Err(SqlError::new(format!("no such table: {table}"), start, end)
+                .hint("query the words table (SELECT ... FROM words)"));
+        }
+        for item in &mut items {
+            item.expr = resolve(
+                std::mem::replace(&mut item.expr, Expr::Literal(Value::Null)),
+                &[],
+            )?;
+        }
+        let filter = if self.keyword("WHERE") {
+            Some(self.plain_expr("WHERE", &items)?)
+        } else {
+            None
+        };
+        let mut group_by = Vec::new();
+        let mut having = None;
+        if self.keyword("GROUP") {
+            self.expect_keyword("BY")?;
+            loop {
+                group_by.push(self.plain_expr("GROUP BY", &items)?);
+                if !self.symbol(",") {
+                    break;
+                }
+            }
+            if self.keyword("HAVING") {
+                having = Some(resolve(self.expr()?, &items)?);
+            }
+        }
+        let mut order = Vec::new();
+        if self.keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let (start, end) = self.here();
+                let expr = match self.expr()? {
+                    // ORDER BY 2: 고른 열의 번호.
+                    Expr::Literal(Value::Int(n)) => {
+                        let n = n as usize;
+                        if n == 0 || n > items.len() {
+                            return Err(SqlError::new(
+                                format!("ORDER BY term out of range (1-{})", items.len()),
+                                start,
+                                end,
+                            ));
+                        }
+                        items[n - 1].expr.clone()
+                    }
+                    expr => resolve(expr, &items)?,
+                };
+                let descending = if self.keyword("DESC") {
+                    true
+                } else {
+                    self.keyword("ASC");
+                    false
+                };
+                order.push((expr, descending));
+                if !self.symbol(",") {
+                    break;
+                }
+            }
+        }
+        let mut limit = None;
+        let mut offset = 0;
+        if self.keyword("LIMIT") {
+            limit = Some(self.count("LIMIT")?);
+            if self.keyword("OFFSET") {
+                offset = self.count("OFFSET")?;
+            }
+        }
+        Ok(Statement {
+            distinct,
+            items,
+            filter,
+            group_by,
+            having,
+            order,
+            limit,
+            offset,
+        })
+    }
+
+    // WHERE와 GROUP BY에는 집계를 쓸 수 없다(집계를 가리키는 별명도).
+    fn plain_expr(&mut self, clause: &str, items: &[Item]) -> Result<Expr, SqlError> {
+        let start = self.here().0;
+        let expr = resolve(self.expr()?, items)?;
+        if expr.has_aggregate() {
+            let end = self.tokens[self.pos - 1].end;
+            return Err(SqlError::new(
+                format!("aggregate functions are not allowed in {clause}"),
+                start,
+                end,
+            )
+            .hint("filter groups with HAVING instead"));
+        }
+        Ok(expr)
+    }
+
+    fn name(&mut self) -> Result<String, SqlError> {
+        let (start, end) = self.here();
+        match self.peek().cloned() {
+            Some(Tok::Ident(name)) | Some(Tok::Quoted(name)) => {
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => Err(SqlError::new("expected a name", start, end)),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, SqlError> {
+        let mut left = self.and()?;
+        while self.keyword("OR") {
+            left = Expr::Binary(BinOp::Or, Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, SqlError> {
+        let mut left = self.not()?;
+        while self.keyword("AND") {
+            left = Expr::Binary(BinOp::And, Box::new(left), Box::new(self.not()?));
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Expr, SqlError> {
+        if self.keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, SqlError> {
+        let left = self.additive()?;
+        let negated = self.keyword("NOT");
+        if self.keyword("LIKE") {
+            let pattern = self.additive()?;
+            return Ok(Expr::Like(Box::new(left), Box::new(pattern), negated));
+        }
+        if self.keyword("IN") {
+            self.expect_symbol("(")?;
+            let mut list = vec![self.expr()?];
+            while self.symbol(",") {
+                list.push(self.expr()?);
+            }
+            self.expect_symbol(")")?;
+            return Ok(Expr::In(Box::new(left), list, negated));
+        }
+        if self.keyword("BETWEEN") {
+            let lo = self.additive()?;
+            self.expect_keyword("AND")?;
+            let hi = self.additive()?;
+            return Ok(Expr::Between(
+                Box::new(left),
+                Box::new(lo),
+                Box::new(hi),
+                negated,
+            ));
+        }
+        if negated {
+            let (start, end) = self.here();
+            return Err(SqlError::new(
+                "expected LIKE, IN, or BETWEEN after NOT",
+                start,
+                end,
+            ));
+        }
+        if self.keyword("IS") {
+            let negated = self.keyword("NOT");
+            self.expect_keyword("NULL")?;
+            return Ok(Expr::IsNull(Box::new(left), negated));
+        }
+        let op = match self.peek() {
+            Some(Tok::Symbol("=" | "==")) => BinOp::Eq,
+            Some(Tok::Symbol("!=" | "<>")) => BinOp::Ne,
+            Some(Tok::Symbol("<")) => BinOp::Lt,
+            Some(Tok::Symbol("<=")) => BinOp::Le,
+            Some(Tok::Symbol(">")) => BinOp::Gt,
+            Some(Tok::Symbol(">=")) => BinOp::Ge,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.additive()?;
+        Ok(Expr::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    fn additive(&mut self) -> Result<Expr, SqlError> {
+        let mut left = self.multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Tok::Symbol("+")) => BinOp::Add,
+                Some(Tok::Symbol("-")) => BinOp::Sub,
+                Some(Tok::Symbol("||")) => BinOp::Concat,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.multiplicative()?));
+        }
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, SqlError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Tok::Symbol("*")) => BinOp::Mul,
+                Some(Tok::Symbol("/")) => BinOp::Div,
+                Some(Tok::Symbol("%")) => BinOp::Rem,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, SqlError> {
+        if self.symbol("-") {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        if self.symbol("+") {
+            return self.unary();
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, SqlError> {
+        let (start, end) = self.here();
+        let Some(tok) = self.peek().cloned() else {
+            return Err(SqlError::new("unexpected end of query", start, end));
+        };
+        self.pos += 1;
+        match tok {
+            Tok::Int(n) => Ok(Expr::Literal(Value::Int(n))),
+            Tok::Real(x) => Ok(Expr::Literal(Value::Real(x))),
+            Tok::Str(s) => Ok(Expr::Literal(Value::Text(s))),
+            Tok::Symbol("(") => {
+                let expr = self.expr()?;
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            Tok::Ident(name) if name.eq_ignore_ascii_case("NULL") => Ok(Expr::Literal(Value::Null)),
+            Tok::Ident(name) if matches!(self.peek(), Some(Tok::Symbol("("))) => {
+                self.pos += 1;
+                self.call(&name, start, end)
+            }
+            Tok::Ident(name) | Tok::Quoted(name) => {
+                match COLUMNS.iter().position(|c| c.eq_ignore_ascii_case(&name)) {
+                    Some(c) => Ok(Expr::Column(c)),
+                    None => Ok(Expr::Name(name, start, end)),
+                }
+            }
+            Tok::Symbol(_) => Err(SqlError::new(
+                format!("unexpected '{}'", &self.sql[start..end]),
+                start,
+                end,
+            )),
+        }
+    }
+
+    // '(' 다음부터.
+    fn call(&mut self, name: &str, start: usize, end: usize) -> Result<Expr, SqlError> {
+        let upper = name.to_ascii_uppercase();
+        let agg = match upper.as_str() {
+            "COUNT" => Some(Agg::Count),
+            "SUM" => Some(Agg::Sum),
+            "AVG" => Some(Agg::Avg),
+            "MIN" => Some(Agg::Min),
+            "MAX" => Some(Agg::Max),
+            _ => None,
+        };
+        if let Some(agg) = agg {
+            if agg == Agg::Count && self.symbol("*") {
+                self.expect_symbol(")")?;
+                return Ok(Expr::Aggregate(agg, None));
+            }
+            let arg = self.expr()?;
+            self.expect_symbol(")")?;
+            if arg.has_aggregate() {
+                return Err(SqlError::new(
+                    "aggregate functions cannot be nested",
+                    start,
+                    end,
+                ));
+            }
+            return Ok(Expr::Aggregate(agg, Some(Box::new(arg))));
+        }
+        let func = match upper.as_str() {
+            "LENGTH" => Func::Length,
+            "LOWER" => Func::Lower,
+            "UPPER" => Func::Upper,
+            "ABS" => Func::Abs,
+            _ => {
+                return Err(
+                    SqlError::new(format!("no such function: {name}"), start, end)
+                        .hint("functions: LENGTH LOWER UPPER ABS COUNT SUM AVG MIN MAX"),
+                )
+            }
+        };
+        let arg = self.expr()?;
+        self.expect_symbol(")")?;
+        Ok(Expr::Call(func, Box::new(arg)))
+    }
+}
+
+// 이름을 SELECT에서 붙인 별명의 식으로 바꾼다. 별명도 아니면 없는 열이다.
+fn resolve(expr: Expr, items: &[Item]) -> Result<Expr, SqlError> {
+    let each = |e: Box<Expr>| resolve(*e, items).map(Box::new);
+    Ok(match expr {
+        Expr::Name(name, start, end) => {
+            match items.iter().find(|i| i.name.eq_ignore_ascii_case(&name)) {
+                Some(item) => item.expr.clone(),
+                None => {
+                    return Err(SqlError::new(format!("no such column: {name}"), start, end)
+                        .hint(COLUMNS_HINT))
+                }
+            }
+        }
+        Expr::Column(_) | Expr::Literal(_) => expr,
+        Expr::Neg(e) => Expr::Neg(each(e)?),
+        Expr::Not(e) => Expr::Not(each(e)?),
+        Expr::Binary(op, a, b) => Expr::Binary(op, each(a)?, each(b)?),
+        Expr::Like(a, b, negated) => Expr::Like(each(a)?, each(b)?, negated),
+        Expr::In(e, list, negated) => Expr::In(
+            each(e)?,
+            list.into_iter()
+                .map(|item| resolve(item, items))
+                .collect::<Result<_, _>>()?,
+            negated,
+        ),
+        Expr::Between(e, lo, hi, negated) => Expr::Between(each(e)?, each(lo)?, each(hi)?, negated),
+        Expr::IsNull(e, negated) => Expr::IsNull(each(e)?, negated),
+        Expr::Call(func, e) => Expr::Call(func, each(e)?),
+        Expr::Aggregate(agg, arg) => Expr::Aggregate(agg, arg.map(each).transpose()?),
+    })
+}
+
+// 별명 없이 식 바로 뒤에 와도 별명으로 읽지 않는 낱말.
+fn is_reserved(word: &str) -> bool {
+    const RESERVED: [&str; 12] = [
+        "FROM", "WHERE", "GROUP", "HAVING", "ORDER", "LIMIT", "OFFSET", "AS", "AND", "OR", "NOT",
+        "BY",
+    ];
+    RESERVED.iter().any(|r| r.eq_ignore_ascii_case(word))
+}
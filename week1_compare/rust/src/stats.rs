@@ -0,0 +1,226 @@
+// stats 서브커맨드: 말뭉치 전체의 기본 수치와 단어 길이 분포.
+// 긴 식별자가 많은 기술 문서는 분포가 오른쪽으로, 대화체는 3~5글자 근처로 몰린다.
+
+use std::collections::HashSet;
+
+use crate::{normalize_words, Tokenizer};
+
+pub struct Summary {
+    pub lines: usize,
+    pub tokens: usize,
+    pub distinct: usize,
+    pub mean_length: f64,
+    // index = 단어 길이(글자 수), 값 = 토큰 수. 0번 칸은 비워 둔다.
+    pub length_counts: Vec<usize>,
+}
+
+pub fn summarize(text: &str, tok: Tokenizer) -> Summary {
+    let words = normalize_words(text, tok);
+    let mut length_counts = vec![0usize];
+    let mut total_chars = 0usize;
+    for word in &words {
+        let len = word.chars().count();
+        if length_counts.len() <= len {
+            length_counts.resize(len + 1, 0);
+        }
+        length_counts[len] += 1;
+        total_chars += len;
+    }
+    let distinct = words.iter().collect::<HashSet<_>>().len();
+    Summary {
+        lines: text.lines().count(),
+        tokens: words.len(),
+        distinct,
+        mean_length: total_chars as f64 / words.len().max(1) as f64,
+        length_counts,
+    }
+}
+
+// 어휘 성장 곡선: step 토큰마다 그때까지 나온 서로 다른 단어 수. 마지막 토큰에서도 한 점을 찍는다.
+// 곡선이 평평해지면 말뭉치를 더 모아도 새 단어가 잘 안 나온다는 뜻이다.
+pub fn vocab_growth(text: &str, tok: Tokenizer, step: usize) -> Vec<(usize, usize)> {
+    let words = normalize_words(text, tok);
+    let mut seen = HashSet::new();
+    let mut points = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        seen.insert(word.as_str());
+        let n = i + 1;
+        if n % step == 0 || n == words.len() {
+            points.push((n, seen.len()));
+        }
+    }
+    points
+}
+
+// 가장 큰 칸을 width 글자로 맞춘 막대. 0이 아니면 최소 한 글자는 그린다.
+pub fn bar(count: usize, max: usize, width: usize) -> String {
+    if count == 0 || max == 0 {
+        return String::new();
+    }
+    let len = (count * width).div_ceil(max).max(1);
+    "#".repeat(len)
+}
+
+// stats --whitespace: 코드 위생 점검용 들여쓰기 통계. 줄 앞의 공백만 본다.
+#[derive(Debug, Default)]
+pub struct FileIndentation {
+    pub name: String,
+    pub tabs: usize,
+    pub spaces: usize,
+    // 한 줄의 들여쓰기에 탭과 공백이 함께 있다.
+    pub mixed: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Indentation {
+    pub files: Vec<FileIndentation>,
+    // index = 공백으로만 들여쓴 줄의 공백 수, 값 = 줄 수. 0번 칸은 비워 둔다.
+    pub widths: Vec<usize>,
+    // 섞인 줄의 (파일, 줄 번호).
+    pub mixed_lines: Vec<(String, usize)>,
+}
+
+// 빈 줄과 공백만 있는 줄은 들여쓰기로 치지 않는다.
+pub fn indentation(documents: &[(String, String)]) -> Indentation {
+    let mut report = Indentation {
+        widths: vec![0],
+        ..Indentation::default()
+    };
+    for (name, text) in documents {
+        let mut file = FileIndentation {
+            name: name.clone(),
+            ..FileIndentation::default()
+        };
+        for (i, line) in text.lines().enumerate() {
+            let body = line.trim_start_matches([' ', '\t']);
+            if body.is_empty() {
+                continue;
+            }
+            let indent = &line[..line.len() - body.len()];
+            match (indent.contains('\t'), indent.contains(' ')) {
+                (false, false) => {}
+                (true, false) => file.tabs += 1,
+                (false, true) => {
+                    file.spaces += 1;
+                    if report.widths.len() <= indent.len() {
+                        report.widths.resize(indent.len() + 1, 0);
+                    }
+                    report.widths[indent.len()] += 1;
+                }
+                (true, true) => {
+                    file.mixed += 1;
+                    report.mixed_lines.push((name.clone(), i + 1));
+                }
+            }
+        }
+        report.files.push(file);
+    }
+    report
+}
+
+// stats --line-endings: 파일마다 줄바꿈 종류별 개수. 종류가 둘 이상이면 섞인 파일이다.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LineEndings {
+    pub lf: usize,
+    pub crlf: usize,
+    // 옛 Mac 형식의 \r 단독.
+    pub cr: usize,
+}
+
+impl LineEndings {
+    pub fn is_mixed(&self) -> bool {
+        [self.lf, self.crlf, self.cr]
+            .iter()
+            .filter(|&&n| n > 0)
+            .count()
+            > 1
+    }
+}
+
+pub fn line_endings(data: &[u8]) -> LineEndings {
+    let mut endings = LineEndings::default();
+    let mut i = 0;
+    while i < data.len() {
+        match data[i] {
+            b'\r' if data.get(i + 1) == Some(&b'\n') => {
+                endings.crlf += 1;
+                i += 1;
+            }
+            b'\r' => endings.cr += 1,
+            b'\n' => endings.lf += 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    endings
+}
+
+// --convert lf|crlf의 목표 줄바꿈.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    Lf,
+    Crlf,
+}
+
+// 모든 줄바꿈(\r\n, \r, \n)을 target 하나로 바꾼다. 바이트 단위라 UTF-8이 아니어도 된다.
+pub fn convert(data: &[u8], target: Ending) -> Vec<u8> {
+    let newline: &[u8] = match target {
+        Ending::Lf => b"\n",
+        Ending::Crlf => b"\r\n",
+    };
+    let mut out = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        match data[i] {
+            b'\r' => {
+                if data.get(i + 1) == Some(&b'\n') {
+                    i += 1;
+                }
+                out.extend_from_slice(newline);
+            }
+            b'\n' => out.extend_from_slice(newline),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    out
+}
+
+// stats --longest-lines N: 압축된 한 줄짜리 파일이나 끝없이 이어진 로그 줄을 찾는다.
+pub struct LongLine {
+    pub name: String,
+    pub line: usize,
+    // 글자 수(바이트가 아니다). 줄 끝의 \r은 세지 않는다.
+    pub chars: usize,
+    pub preview: String,
+}
+
+const PREVIEW_CHARS: usize = 60;
+
+// 긴 줄부터 n개. 길이가 같으면 파일 순서, 줄 번호 순.
+pub fn longest_lines(documents: &[(String, String)], n: usize) -> Vec<LongLine> {
+    let mut lines: Vec<(usize, usize, usize, &str)> = Vec::new();
+    for (file, (_, text)) in documents.iter().enumerate() {
+        for (i, line) in text.lines().enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            lines.push((line.chars().count(), file, i + 1, line));
+        }
+    }
+    lines.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+    lines
+        .into_iter()
+        .take(n)
+        .map(|(chars, file, line, text)| {
+            let mut preview: String = text.chars().take(PREVIEW_CHARS).collect();
+            if chars > PREVIEW_CHARS {
+                preview.push_str("...");
+            }
+            LongLine {
+                name: documents[file].0.clone(),
+                line,
+                chars,
+                preview,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,626 @@
+// 어간 추출(--stem). "running", "runs", "ran"을 모두 따로 세면 상위 단어가 같은 말의
+// 활용형으로 채워지므로, 언어별 규칙으로 어미를 떼어 한 단어로 센다.
+// 영어와 독일어는 Snowball(Porter2) 알고리즘을, 프랑스어는 그 접미사 표를 줄인 판을,
+// 한국어는 조사와 자주 쓰는 어미를 떼는 규칙을 쓴다. 사전 없이 규칙만 쓰므로 "ran" 같은
+// 불규칙 활용은 합쳐지지 않는다. Python이라면 nltk.stem.SnowballStemmer를 쓸 자리다.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Korean,
+}
+
+impl Language {
+    pub fn parse(code: &str) -> Option<Language> {
+        match code {
+            "en" => Some(Language::English),
+            "de" => Some(Language::German),
+            "fr" => Some(Language::French),
+            "ko" => Some(Language::Korean),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Korean => "ko",
+        }
+    }
+}
+
+// 어간 추출을 할지, 한다면 어느 언어 규칙으로 할지.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Stem {
+    // 원문 그대로(기본값, 기존 동작).
+    #[default]
+    Off,
+    // 토큰화하는 텍스트를 보고 detect()로 언어를 고른다.
+    Auto,
+    Lang(Language),
+}
+
+impl Stem {
+    // Auto를 text의 언어로 바꾼다. 문서 전체를 한 번에 넘기면 문서 하나에 한 언어가 정해진다.
+    pub fn resolve(self, text: &str) -> Option<Language> {
+        match self {
+            Stem::Off => None,
+            Stem::Auto => Some(detect(text)),
+            Stem::Lang(lang) => Some(lang),
+        }
+    }
+}
+
+pub fn stem(word: &str, lang: Language) -> String {
+    match lang {
+        Language::English => english(word),
+        Language::German => german(word),
+        Language::French => french(word),
+        Language::Korean => korean(word),
+    }
+}
+
+// 앞부분 단어에서 언어마다 가장 흔한 기능어가 몇 번 나오는지 세어 고른다.
+// 글자의 3할 이상이 한글이면 한국어. 판단할 근거가 없으면 영어.
+pub fn detect(text: &str) -> Language {
+    const SAMPLE: usize = 2000;
+    const MARKERS: &[(Language, &[&str])] = &[
+        (
+            Language::English,
+            &["the", "and", "of", "to", "is", "in", "that", "it", "with"],
+        ),
+        (
+            Language::German,
+            &[
+                "der", "die", "und", "das", "ist", "nicht", "ein", "zu", "mit",
+            ],
+        ),
+        (
+            Language::French,
+            &["le", "la", "les", "et", "est", "des", "une", "pas", "dans"],
+        ),
+    ];
+    let mut letters = 0usize;
+    let mut hangul = 0usize;
+    let mut hits = [0usize; 3];
+    let words = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .take(SAMPLE);
+    for word in words {
+        for c in word.chars() {
+            letters += 1;
+            if ('\u{AC00}'..='\u{D7A3}').contains(&c) {
+                hangul += 1;
+            }
+        }
+        let lower = word.to_lowercase();
+        for (i, (_, markers)) in MARKERS.iter().enumerate() {
+            if markers.contains(&lower.as_str()) {
+                hits[i] += 1;
+            }
+        }
+    }
+    if letters > 0 && hangul * 10 >= letters * 3 {
+        return Language::Korean;
+    }
+    let mut best = 0;
+    for i in 1..hits.len() {
+        if hits[i] > hits[best] {
+            best = i;
+        }
+    }
+    MARKERS[best].0
+}
+
+// ---- 영어: Porter2 ----
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u' | b'y')
+}
+
+// 모음 다음에 처음 나오는 자음 뒤부터. "beautiful"이면 "iful", "beauty"면 "y".
+fn region(w: &[u8], from: usize) -> usize {
+    (from + 1..w.len())
+        .find(|&i| !is_vowel(w[i]) && is_vowel(w[i - 1]))
+        .map_or(w.len(), |i| i + 1)
+}
+
+// 짧은 음절: 모음 앞뒤가 자음(뒤는 w, x, Y가 아님)이거나, 단어 첫 모음 뒤 자음.
+fn ends_short_syllable(w: &[u8]) -> bool {
+    let n = w.len();
+    match n {
+        0 | 1 => false,
+        2 => is_vowel(w[0]) && !is_vowel(w[1]),
+        _ => {
+            !is_vowel(w[n - 3])
+                && is_vowel(w[n - 2])
+                && !is_vowel(w[n - 1])
+                && !matches!(w[n - 1], b'w' | b'x' | b'Y')
+        }
+    }
+}
+
+fn has_vowel(w: &[u8]) -> bool {
+    w.iter().any(|&c| is_vowel(c))
+}
+
+const EXCEPTIONS: &[(&str, &str)] = &[
+    ("skis", "ski"),
+    ("skies", "sky"),
+    ("dying", "die"),
+    ("lying", "lie"),
+    ("tying", "tie"),
+    ("idly", "idl"),
+    ("gently", "gentl"),
+    ("ugly", "ugli"),
+    ("early", "earli"),
+    ("only", "onli"),
+    ("singly", "singl"),
+    ("sky", "sky"),
+    ("news", "news"),
+    ("howe", "howe"),
+    ("atlas", "atlas"),
+    ("cosmos", "cosmos"),
+    ("bias", "bias"),
+    ("andes", "andes"),
+];
+
+// 1a 단계 뒤에 더 줄이지 않는 단어.
+const INVARIANT: &[&str] = &[
+    "inning", "outing", "canning", "herring", "earring", "proceed", "exceed", "succeed",
+];
+
+fn english(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase() || b == b'\'') {
+        return word.to_string();
+    }
+    if let Some((_, stem)) = EXCEPTIONS.iter().find(|(w, _)| *w == word) {
+        return stem.to_string();
+    }
+    let mut w: Vec<u8> = word.trim_start_matches('\'').bytes().collect();
+    // 자음 노릇을 하는 y(첫 글자, 모음 뒤)는 Y로 표시해 모음 판정에서 뺀다.
+    for i in 0..w.len() {
+        if w[i] == b'y' && (i == 0 || is_vowel(w[i - 1])) {
+            w[i] = b'Y';
+        }
+    }
+    let r1 = ["gener", "commun", "arsen"]
+        .iter()
+        .find(|p| w.starts_with(p.as_bytes()))
+        .map_or_else(|| region(&w, 0), |p| p.len());
+    let r2 = region(&w, r1);
+
+    // 0: 소유격
+    for suffix in ["'s'", "'s", "'"] {
+        if w.ends_with(suffix.as_bytes()) {
+            w.truncate(w.len() - suffix.len());
+            break;
+        }
+    }
+    // 1a: 복수
+    if w.ends_with(b"sses") {
+        w.truncate(w.len() - 2);
+    } else if w.ends_with(b"ied") || w.ends_with(b"ies") {
+        let cut = if w.len() > 4 { 2 } else { 1 };
+        w.truncate(w.len() - cut);
+    } else if w.ends_with(b"us") || w.ends_with(b"ss") {
+    } else if w.ends_with(b"s") && w.len() >= 2 && has_vowel(&w[..w.len() - 2]) {
+        w.pop();
+    }
+    if INVARIANT.iter().any(|x| x.as_bytes() == w.as_slice()) {
+        return String::from_utf8_lossy(&w).into_owned();
+    }
+    // 1b: 과거형, 진행형
+    if let Some(s) = ["eedly", "eed"].iter().find(|s| w.ends_with(s.as_bytes())) {
+        if w.len() - s.len() >= r1 {
+            w.truncate(w.len() - s.len() + 2);
+        }
+    } else if let Some(s) = ["ingly", "edly", "ing", "ed"]
+        .iter()
+        .find(|s| w.ends_with(s.as_bytes()))
+    {
+        let stem_len = w.len() - s.len();
+        if has_vowel(&w[..stem_len]) {
+            w.truncate(stem_len);
+            if w.ends_with(b"at") || w.ends_with(b"bl") || w.ends_with(b"iz") {
+                w.push(b'e');
+            } else if w.len() >= 2
+                && w[w.len() - 1] == w[w.len() - 2]
+                && matches!(
+                    w[w.len() - 1],
+                    b'b' | b'd' | b'f' | b'g' | b'm' | b'n' | b'p' | b'r' | b't'
+                )
+            {
+                w.pop();
+            } else if r1 >= w.len() && ends_short_syllable(&w) {
+                w.push(b'e');
+            }
+        }
+    }
+    // 1c: 자음 뒤 y -> i
+    let n = w.len();
+    if n > 2 && matches!(w[n - 1], b'y' | b'Y') && !is_vowel(w[n - 2]) {
+        w[n - 1] = b'i';
+    }
+    // 2, 3: 파생 접미사를 짧은 꼴로
+    const STEP2: &[(&str, &str)] = &[
+        ("ization", "ize"),
+        ("ational", "ate"),
+        ("fulness", "ful"),
+        ("ousness", "ous"),
+        ("iveness", "ive"),
+        ("tional", "tion"),
+        ("biliti", "ble"),
+        ("lessli", "less"),
+        ("entli", "ent"),
+        ("ation", "ate"),
+        ("alism", "al"),
+        ("aliti", "al"),
+        ("ousli", "ous"),
+        ("iviti", "ive"),
+        ("fulli", "ful"),
+        ("enci", "ence"),
+        ("anci", "ance"),
+        ("abli", "able"),
+        ("izer", "ize"),
+        ("ator", "ate"),
+        ("alli", "al"),
+        ("bli", "ble"),
+        ("ogi", "og"),
+        ("li", ""),
+    ];
+    if let Some((s, to)) = STEP2.iter().find(|(s, _)| w.ends_with(s.as_bytes())) {
+        let stem_len = w.len() - s.len();
+        let allowed = match *s {
+            "ogi" => stem_len > 0 && w[stem_len - 1] == b'l',
+            "li" => {
+                stem_len > 0
+                    && matches!(
+                        w[stem_len - 1],
+                        b'c' | b'd' | b'e' | b'g' | b'h' | b'k' | b'm' | b'n' | b'r' | b't'
+                    )
+            }
+            _ => true,
+        };
+        if stem_len >= r1 && allowed {
+            w.truncate(stem_len);
+            w.extend_from_slice(to.as_bytes());
+        }
+    }
+    const STEP3: &[(&str, &str)] = &[
+        ("ational", "ate"),
+        ("tional", "tion"),
+        ("alize", "al"),
+        ("icate", "ic"),
+        ("iciti", "ic"),
+        ("ative", ""),
+        ("ical", "ic"),
+        ("ness", ""),
+        ("ful", ""),
+    ];
+    if let Some((s, to)) = STEP3.iter().find(|(s, _)| w.ends_with(s.as_bytes())) {
+        let stem_len = w.len() - s.len();
+        let region = if *s == "ative" { r2 } else { r1 };
+        if stem_len >= region {
+            w.truncate(stem_len);
+            w.extend_from_slice(to.as_bytes());
+        }
+    }
+    // 4: R2 안의 접미사는 지운다
+    const STEP4: &[&str] = &[
+        "ement", "ance", "ence", "able", "ible", "ment", "ant", "ent", "ism", "ate", "iti", "ous",
+        "ive", "ize", "ion", "al", "er", "ic",
+    ];
+    if let Some(s) = STEP4.iter().find(|s| w.ends_with(s.as_bytes())) {
+        let stem_len = w.len() - s.len();
+        let allowed = *s != "ion" || (stem_len > 0 && matches!(w[stem_len - 1], b's' | b't'));
+        if stem_len >= r2 && allowed {
+            w.truncate(stem_len);
+        }
+    }
+    // 5: 끝의 e, ll
+    let n = w.len();
+    if n > 0 && w[n - 1] == b'e' {
+        if n > r2 || (n > r1 && !ends_short_syllable(&w[..n - 1])) {
+            w.pop();
+        }
+    } else if n > 1 && w[n - 1] == b'l' && w[n - 2] == b'l' && n > r2 {
+        w.pop();
+    }
+    for c in &mut w {
+        if *c == b'Y' {
+            *c = b'y';
+        }
+    }
+    String::from_utf8_lossy(&w).into_owned()
+}
+
+// ---- 독일어: Snowball German ----
+
+fn is_german_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'ä' | 'ö' | 'ü')
+}
+
+fn german_region(w: &[char], from: usize) -> usize {
+    (from + 1..w.len())
+        .find(|&i| !is_german_vowel(w[i]) && is_german_vowel(w[i - 1]))
+        .map_or(w.len(), |i| i + 1)
+}
+
+fn ends_with(w: &[char], suffix: &str) -> bool {
+    let s: Vec<char> = suffix.chars().collect();
+    w.ends_with(&s)
+}
+
+fn german(word: &str) -> String {
+    let mut w: Vec<char> = word.replace('ß', "ss").chars().collect();
+    if w.len() <= 2 {
+        return word.to_string();
+    }
+    // 모음 사이의 u, y는 자음으로 본다.
+    for i in 1..w.len() - 1 {
+        if matches!(w[i], 'u' | 'y') && is_german_vowel(w[i - 1]) && is_german_vowel(w[i + 1]) {
+            w[i] = w[i].to_ascii_uppercase();
+        }
+    }
+    let r1 = german_region(&w, 0).max(3);
+    let r2 = german_region(&w, german_region(&w, 0));
+
+    // 1
+    let step1 = ["ern", "em", "er", "en", "es", "e", "s"];
+    if let Some(s) = step1.iter().find(|s| ends_with(&w, s)) {
+        let stem_len = w.len() - s.chars().count();
+        let allowed = *s != "s"
+            || (stem_len > 0
+                && matches!(
+                    w[stem_len - 1],
+                    'b' | 'd' | 'f' | 'g' | 'h' | 'k' | 'l' | 'm' | 'n' | 'r' | 't'
+                ));
+        if stem_len >= r1 && allowed {
+            w.truncate(stem_len);
+            if matches!(*s, "en" | "es" | "e") && ends_with(&w, "niss") {
+                w.pop();
+            }
+        }
+    }
+    // 2
+    if let Some(s) = ["est", "en", "er", "st"].iter().find(|s| ends_with(&w, s)) {
+        let stem_len = w.len() - s.chars().count();
+        let allowed = *s != "st"
+            || (stem_len > 3
+                && matches!(
+                    w[stem_len - 1],
+                    'b' | 'd' | 'f' | 'g' | 'h' | 'k' | 'l' | 'm' | 'n' | 't'
+                ));
+        if stem_len >= r1 && allowed {
+            w.truncate(stem_len);
+        }
+    }
+    // 3: 파생 접미사
+    let suffixes = ["heit", "keit", "lich", "isch", "end", "ung", "ig", "ik"];
+    if let Some(s) = suffixes.iter().find(|s| ends_with(&w, s)) {
+        let stem_len = w.len() - s.chars().count();
+        let after_e = stem_len > 0 && w[stem_len - 1] == 'e';
+        let allowed = !matches!(*s, "ig" | "ik" | "isch") || !after_e;
+        if stem_len >= r2 && allowed {
+            w.truncate(stem_len);
+            match *s {
+                "end" | "ung"
+                    if ends_with(&w, "ig") && w.len() - 2 >= r2 && !ends_with(&w, "eig") =>
+                {
+                    w.truncate(w.len() - 2);
+                }
+                "lich" | "heit"
+                    if (ends_with(&w, "er") || ends_with(&w, "en")) && w.len() - 2 >= r1 =>
+                {
+                    w.truncate(w.len() - 2);
+                }
+                "keit" => {
+                    if ends_with(&w, "lich") && w.len() - 4 >= r2 {
+                        w.truncate(w.len() - 4);
+                    } else if ends_with(&w, "ig") && w.len() - 2 >= r2 {
+                        w.truncate(w.len() - 2);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    w.iter()
+        .map(|&c| match c {
+            'U' => 'u',
+            'Y' => 'y',
+            'ä' => 'a',
+            'ö' => 'o',
+            'ü' => 'u',
+            c => c,
+        })
+        .collect()
+}
+
+// ---- 프랑스어: Snowball French의 접미사 표를 줄인 판 ----
+
+fn is_french_vowel(c: char) -> bool {
+    matches!(
+        c,
+        'a' | 'e'
+            | 'i'
+            | 'o'
+            | 'u'
+            | 'y'
+            | 'â'
+            | 'à'
+            | 'ë'
+            | 'é'
+            | 'ê'
+            | 'è'
+            | 'ï'
+            | 'î'
+            | 'ô'
+            | 'û'
+            | 'ù'
+    )
+}
+
+fn french(word: &str) -> String {
+    let mut w: Vec<char> = word.chars().collect();
+    if w.len() <= 3 {
+        return word.to_string();
+    }
+    // RV: 첫 두 글자가 모음이면 세 번째 글자부터, 아니면 첫 글자 뒤 첫 모음 다음부터.
+    let rv = if is_french_vowel(w[0]) && is_french_vowel(w[1]) {
+        3
+    } else {
+        (1..w.len())
+            .find(|&i| is_french_vowel(w[i]))
+            .map_or(w.len(), |i| i + 1)
+    };
+    let r1 = (1..w.len())
+        .find(|&i| !is_french_vowel(w[i]) && is_french_vowel(w[i - 1]))
+        .map_or(w.len(), |i| i + 1);
+    let r2 = (r1 + 1..w.len())
+        .find(|&i| !is_french_vowel(w[i]) && is_french_vowel(w[i - 1]))
+        .map_or(w.len(), |i| i + 1);
+
+    const SUFFIXES: &[(&str, &str)] = &[
+        ("issements", ""),
+        ("issement", ""),
+        ("atrices", ""),
+        ("ateurs", ""),
+        ("ations", ""),
+        ("logies", "log"),
+        ("usions", "u"),
+        ("utions", "u"),
+        ("atrice", ""),
+        ("ements", ""),
+        ("ateur", ""),
+        ("ation", ""),
+        ("logie", "log"),
+        ("usion", "u"),
+        ("ution", "u"),
+        ("ences", "ent"),
+        ("ement", ""),
+        ("euses", ""),
+        ("istes", ""),
+        ("ismes", ""),
+        ("ables", ""),
+        ("iques", ""),
+        ("ments", ""),
+        ("ence", "ent"),
+        ("euse", ""),
+        ("iste", ""),
+        ("isme", ""),
+        ("able", ""),
+        ("ique", ""),
+        ("ment", ""),
+        ("ités", ""),
+        ("ives", ""),
+        ("eux", ""),
+        ("ité", ""),
+        ("ifs", ""),
+        ("ive", ""),
+        ("if", ""),
+    ];
+    let mut changed = false;
+    if let Some((s, to)) = SUFFIXES.iter().find(|(s, _)| ends_with(&w, s)) {
+        let stem_len = w.len() - s.chars().count();
+        // ment는 모음 뒤(RV 안)에서만, 나머지는 R2 안에서만 뗀다.
+        let region = if s.starts_with("ment") || *s == "ements" || *s == "ement" {
+            rv
+        } else {
+            r2
+        };
+        if stem_len >= region {
+            w.truncate(stem_len);
+            w.extend(to.chars());
+            changed = true;
+        } else if s.starts_with("euse") && stem_len >= r1 {
+            // R2 밖의 여성형은 남성형으로 맞춘다: "heureuse" -> "heureux".
+            w.truncate(stem_len);
+            w.extend("eux".chars());
+            changed = true;
+        }
+    }
+    // 복수 s/x와 끝의 e(여성형)는 접미사를 못 뗐을 때만.
+    if !changed {
+        if matches!(w.last(), Some('s' | 'x'))
+            && w.len() > rv
+            && !matches!(w[w.len() - 2], 'a' | 'i' | 'o' | 'u' | 'è' | 's')
+        {
+            w.pop();
+        }
+        if w.last() == Some(&'e') && w.len() > rv {
+            w.pop();
+        }
+    }
+    w.into_iter().collect()
+}
+
+// ---- 한국어: 조사와 어미 ----
+
+// 긴 것부터.
+const KOREAN_SUFFIXES: &[&str] = &[
+    "했습니다",
+    "습니다",
+    "입니다",
+    "에서는",
+    "으로는",
+    "에게서",
+    "이었다",
+    "였다",
+    "했다",
+    "한다",
+    "하다",
+    "에서",
+    "에게",
+    "으로",
+    "부터",
+    "까지",
+    "처럼",
+    "보다",
+    "이다",
+    "은",
+    "는",
+    "이",
+    "가",
+    "을",
+    "를",
+    "에",
+    "의",
+    "로",
+    "와",
+    "과",
+    "도",
+    "만",
+    "다",
+    "요",
+];
+
+// 명사 끝 글자와 겹치기 쉬운 한 글자 접미사는 떼고 남는 말이 두 글자 이상일 때만 뗀다.
+// 그래서 "바다", "아이"는 그대로 남는다.
+const AMBIGUOUS: &[&str] = &["이", "가", "의", "로", "와", "과", "도", "만", "다", "요"];
+
+fn korean(word: &str) -> String {
+    let mut w = word;
+    // "사람들이" -> "사람들" -> "사람"처럼 조사 뒤에 남은 복수 "들"까지 한 번 더 본다.
+    for _ in 0..2 {
+        let Some(s) = KOREAN_SUFFIXES.iter().find(|s| w.ends_with(*s)) else {
+            break;
+        };
+        let rest = &w[..w.len() - s.len()];
+        let min = if AMBIGUOUS.contains(s) { 2 } else { 1 };
+        if rest.chars().count() < min {
+            break;
+        }
+        w = rest;
+    }
+    if let Some(rest) = w.strip_suffix('들').filter(|r| r.chars().count() >= 1) {
+        w = rest;
+    }
+    w.to_string()
+}
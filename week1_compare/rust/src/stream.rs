@@ -0,0 +1,86 @@
+// 라이브러리용 스트리밍 API. 파일 전체를 String으로 읽어 Vec<String>을 돌려받는 대신,
+// 아무 Read에서나 한 줄씩 읽으며 토큰을 하나씩 꺼낸다(Python의 제너레이터와 같은 쓰임).
+//   for token in WordStream::from_reader(File::open("big.log")?) {
+//       let token = token?;   // 읽기 실패나 잘못된 UTF-8은 그 자리에서 Err
+//       ...
+//   }
+// 단어는 줄바꿈을 넘지 않으므로 줄 단위로 토큰화해도 한 번에 읽은 결과와 같다.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read};
+
+use crate::{normalize_words, Tokenizer};
+
+// 정규화된 단어와 그 단어가 나온 줄 번호(1부터).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub word: String,
+    pub line: usize,
+}
+
+pub struct WordStream<R> {
+    reader: BufReader<R>,
+    tokenizer: Tokenizer,
+    line: usize,
+    buffer: Vec<u8>,
+    pending: VecDeque<String>,
+    // 에러를 한 번 돌려준 뒤에는 더 읽지 않는다.
+    done: bool,
+}
+
+impl<R: Read> WordStream<R> {
+    // 기본 토큰화 규칙([A-Za-z]+, 소문자).
+    pub fn from_reader(reader: R) -> WordStream<R> {
+        WordStream::with_tokenizer(reader, Tokenizer::default())
+    }
+
+    pub fn with_tokenizer(reader: R, tokenizer: Tokenizer) -> WordStream<R> {
+        WordStream {
+            reader: BufReader::new(reader),
+            tokenizer,
+            line: 0,
+            buffer: Vec::new(),
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+impl<R: Read> Iterator for WordStream<R> {
+    type Item = io::Result<Token>;
+
+    fn next(&mut self) -> Option<io::Result<Token>> {
+        loop {
+            if let Some(word) = self.pending.pop_front() {
+                return Some(Ok(Token {
+                    word,
+                    line: self.line,
+                }));
+            }
+            if self.done {
+                return None;
+            }
+            self.buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => {
+                    self.done = true;
+                    return None;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+            self.line += 1;
+            let Ok(text) = std::str::from_utf8(&self.buffer) else {
+                self.done = true;
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: stream did not contain valid UTF-8", self.line),
+                )));
+            };
+            self.pending.extend(normalize_words(text, self.tokenizer));
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// SRT / WebVTT 자막 파서.
+//
+//   1                                   ← 큐 번호(SRT) 또는 식별자(VTT)
+//   00:00:01,000 --> 00:00:04,200       ← 타이밍 줄(VTT는 "00:01.000"처럼 시 생략 가능)
+//   <i>Hello</i> everyone.              ← 자막 텍스트(여러 줄)
+//
+// 빈 줄로 나뉜 블록마다 "-->"가 있는 줄을 찾아 그 뒤를 텍스트로 본다.
+// 타이밍 줄이 없는 블록(WEBVTT 헤더, NOTE, STYLE)은 버린다.
+
+#[derive(Debug, Clone)]
+pub struct Cue {
+    pub start_ms: u64,
+    pub text: String,
+}
+
+pub fn parse(content: &str) -> Vec<Cue> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut cues = Vec::new();
+    for block in content.split("\n\n") {
+        let mut lines = block.lines().skip_while(|l| !l.contains("-->"));
+        let Some(timing) = lines.next() else {
+            continue;
+        };
+        let Some(start_ms) = timing.split("-->").next().and_then(parse_timestamp) else {
+            continue;
+        };
+        let text = lines
+            .map(strip_markup)
+            .filter(|l| !l.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !text.is_empty() {
+            cues.push(Cue { start_ms, text });
+        }
+    }
+    cues
+}
+
+// "01:02:03,456", "02:03.456", "01:02:03" → 밀리초.
+fn parse_timestamp(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (clock, millis) = match s.split_once([',', '.']) {
+        Some((clock, frac)) => (clock, frac.get(..3)?.parse::<u64>().ok()?),
+        None => (s, 0),
+    };
+    let mut seconds = 0u64;
+    for part in clock.split(':') {
+        seconds = seconds * 60 + part.parse::<u64>().ok()?;
+    }
+    Some(seconds * 1000 + millis)
+}
+
+// <i>, <c.yellow>, <00:00:01.000> 같은 VTT 태그와 {\an8} 같은 ASS 스타일 지시를 지운다.
+fn strip_markup(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut depth: Option<char> = None;
+    for c in line.chars() {
+        match (depth, c) {
+            (None, '<') => depth = Some('>'),
+            (None, '{') => depth = Some('}'),
+            (Some(close), c) if c == close => depth = None,
+            (Some(_), _) => {}
+            (None, c) => out.push(c),
+        }
+    }
+    crate::xml::decode_entities(&out)
+}
+
+// --bucket, --every 인자: "90"(초), "90s", "5m", "1h".
+pub fn parse_duration(spec: &str) -> Result<u64, String> {
+    let (digits, unit) = match spec.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => spec.split_at(i),
+        None => (spec, "s"),
+    };
+    let value: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid duration: {spec} (expected e.g. 90s, 5m, 1h)"))?;
+    let scale = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        _ => {
+            return Err(format!(
+                "invalid duration: {spec} (expected e.g. 90s, 5m, 1h)"
+            ))
+        }
+    };
+    if value == 0 {
+        return Err(format!(
+            "invalid duration: {spec} (must be greater than zero)"
+        ));
+    }
+    Ok(value * scale * 1000)
+}
+
+pub fn format_clock(ms: u64) -> String {
+    let seconds = ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+// 시작 시각 기준으로 같은 구간에 든 큐 텍스트를 모은다. (구간 시작 ms, 텍스트) 목록.
+pub fn buckets(cues: &[Cue], size_ms: u64) -> Vec<(u64, String)> {
+    let mut out: Vec<(u64, String)> = Vec::new();
+    for cue in cues {
+        let start = cue.start_ms / size_ms * size_ms;
+        match out.iter_mut().find(|(s, _)| *s == start) {
+            Some((_, text)) => {
+                text.push('\n');
+                text.push_str(&cue.text);
+            }
+            None => out.push((start, cue.text.clone())),
+        }
+    }
+    out.sort_by_key(|(start, _)| *start);
+    out
+}
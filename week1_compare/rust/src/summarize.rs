@@ -0,0 +1,44 @@
+// 빈도 기반 추출 요약.
+// 문장 점수 = 문장 안 내용어(불용어 제외)들의 정규화 빈도(최대 빈도 대비 비율) 합.
+// 점수 상위 N개 문장을 고른 뒤, 읽기 자연스럽도록 원래 문서 순서대로 돌려준다.
+
+use std::collections::HashMap;
+
+use crate::sentences::split_sentences;
+use crate::stopwords::is_stopword;
+use crate::{normalize_words, Tokenizer};
+
+pub fn summarize(text: &str, tok: Tokenizer, count: usize) -> Vec<String> {
+    let sentences = split_sentences(text);
+
+    let mut freq: HashMap<String, usize> = HashMap::new();
+    for word in normalize_words(text, tok) {
+        if !is_stopword(&word) {
+            *freq.entry(word).or_insert(0) += 1;
+        }
+    }
+    let max = freq.values().copied().max().unwrap_or(1) as f64;
+
+    let mut scored: Vec<(usize, f64)> = sentences
+        .iter()
+        .enumerate()
+        .map(|(idx, sentence)| {
+            let score = normalize_words(sentence, tok)
+                .iter()
+                .filter_map(|w| freq.get(w))
+                .map(|&n| n as f64 / max)
+                .sum();
+            (idx, score)
+        })
+        .collect();
+
+    // 점수 내림차순, 동점이면 앞 문장 우선.
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    scored.truncate(count);
+    scored.sort_by_key(|&(idx, _)| idx);
+
+    scored
+        .into_iter()
+        .map(|(idx, _)| sentences[idx].clone())
+        .collect()
+}
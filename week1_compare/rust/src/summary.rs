@@ -0,0 +1,88 @@
+// evcxr(Jupyter의 Rust 커널)에서 쓰기 좋은 결과 타입. 셀의 마지막 값이 Summary면 evcxr가
+// evcxr_display()를 불러 HTML 표로 그리고, println!("{summary}")는 CLI와 같은 text 형식을 쓴다.
+// Python 쪽 노트북에서 DataFrame이 표로 보이듯, Rust와 Python을 나란히 보여줄 때 쓰려는 것이다.
+//   let summary = Analysis::builder().top(10).build().summarize(text);
+//   summary   // 노트북에서는 표, 터미널에서는 "[Top words]" 목록
+
+use std::fmt;
+
+use crate::WordCount;
+
+// tokens와 distinct는 불용어를 빼기 전 전체 수다. top_words만 불용어를 뺀 순위다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub tokens: usize,
+    pub distinct: usize,
+    pub top_words: Vec<WordCount>,
+}
+
+impl Summary {
+    pub fn to_html_table(&self) -> String {
+        let mut out = format!(
+            "<p>{} tokens, {} distinct words</p>\n",
+            self.tokens, self.distinct
+        );
+        out.push_str(&to_html_table(&self.top_words));
+        out
+    }
+
+    // evcxr가 찾는 이름. 표준 출력에 MIME 형식과 함께 내용을 쓰면 노트북이 그대로 그린다.
+    pub fn evcxr_display(&self) {
+        println!(
+            "EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT",
+            self.to_html_table()
+        );
+    }
+}
+
+// [Top words]
+// rust: 2
+// (tokens: 5, distinct: 4)
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[Top words]")?;
+        for entry in &self.top_words {
+            writeln!(f, "{entry}")?;
+        }
+        write!(f, "(tokens: {}, distinct: {})", self.tokens, self.distinct)
+    }
+}
+
+impl fmt::Display for WordCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.word, self.count)
+    }
+}
+
+// 순위, 단어, 빈도 세 열의 <table>. 빈도는 오른쪽 정렬이다.
+pub fn to_html_table(rows: &[WordCount]) -> String {
+    let mut out = String::from(
+        "<table>\n<thead><tr><th>rank</th><th>word</th><th>count</th></tr></thead>\n<tbody>\n",
+    );
+    for (i, entry) in rows.iter().enumerate() {
+        out.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td style=\"text-align: right\">{}</td></tr>\n",
+            i + 1,
+            escape_html(&entry.word),
+            entry.count
+        ));
+    }
+    out.push_str("</tbody>\n</table>\n");
+    out
+}
+
+// 속성값 안에도 넣으므로 따옴표까지 바꾼다.
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,111 @@
+// 터미널 크기 조회.
+// Python의 shutil.get_terminal_size()와 같은 역할을 외부 crate 없이 구현한다.
+// 환경 변수(LINES, COLUMNS)가 있으면 우선하고, 유닉스에서는 ioctl(TIOCGWINSZ)로 직접 묻는다.
+
+use std::borrow::Cow;
+use std::env;
+
+use crate::diagnostic::char_width;
+
+pub fn terminal_rows() -> Option<usize> {
+    from_env("LINES").or_else(|| query_size().map(|(rows, _)| rows))
+}
+
+pub fn terminal_cols() -> Option<usize> {
+    from_env("COLUMNS").or_else(|| query_size().map(|(_, cols)| cols))
+}
+
+// 줄을 화면 cells칸에 맞춰 자르고 잘린 쪽에 …를 붙인다. focus(바이트 위치, 보통 첫 일치)가
+// 앞쪽 절반 밖에 있으면 그 조금 앞에서 시작해 일치가 보이게 한다: "…key=value error …".
+pub fn ellipsize(line: &str, cells: usize, focus: usize) -> Cow<'_, str> {
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    if chars.iter().map(|&(_, c)| char_width(c)).sum::<usize>() <= cells {
+        return Cow::Borrowed(line);
+    }
+    let cells = cells.max(2);
+    let focus_index = chars.partition_point(|&(i, _)| i < focus);
+    let focus_cell: usize = chars[..focus_index]
+        .iter()
+        .map(|&(_, c)| char_width(c))
+        .sum();
+    let mut first = 0;
+    if focus_cell > cells / 2 {
+        let mut back = 0;
+        first = focus_index;
+        while first > 0 && back + char_width(chars[first - 1].1) <= cells / 4 {
+            first -= 1;
+            back += char_width(chars[first].1);
+        }
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    if first > 0 {
+        out.push('…');
+        used = 1;
+    }
+    let mut end = first;
+    while end < chars.len() {
+        let width = char_width(chars[end].1);
+        // 마지막 글자가 아니면 뒤에 붙일 … 한 칸을 남겨 둔다.
+        let reserve = usize::from(end + 1 < chars.len());
+        if used + width + reserve > cells {
+            break;
+        }
+        out.push(chars[end].1);
+        used += width;
+        end += 1;
+    }
+    if end < chars.len() {
+        out.push('…');
+    }
+    Cow::Owned(out)
+}
+
+fn from_env(key: &str) -> Option<usize> {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|&n| n > 0)
+}
+
+// 반환값은 (rows, cols).
+#[cfg(unix)]
+fn query_size() -> Option<(usize, usize)> {
+    #[repr(C)]
+    struct Winsize {
+        ws_row: u16,
+        ws_col: u16,
+        ws_xpixel: u16,
+        ws_ypixel: u16,
+    }
+
+    extern "C" {
+        fn ioctl(fd: i32, request: std::os::raw::c_ulong, ...) -> i32;
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+    const TIOCGWINSZ: std::os::raw::c_ulong = 0x4008_7468;
+    #[cfg(not(any(target_os = "macos", target_os = "ios", target_os = "freebsd")))]
+    const TIOCGWINSZ: std::os::raw::c_ulong = 0x5413;
+
+    let mut ws = Winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // stdout(1)이 리다이렉트되어 있으면 실패하므로 stderr(2)도 시도한다.
+    for fd in [1, 2] {
+        // SAFETY: ws는 커널이 채우는 winsize 구조체와 같은 레이아웃이다.
+        let rc = unsafe { ioctl(fd, TIOCGWINSZ, &mut ws as *mut Winsize) };
+        if rc == 0 && ws.ws_row > 0 && ws.ws_col > 0 {
+            return Some((ws.ws_row as usize, ws.ws_col as usize));
+        }
+    }
+    None
+}
+
+#[cfg(not(unix))]
+fn query_size() -> Option<(usize, usize)> {
+    None
+}
@@ -0,0 +1,209 @@
+// --timestamp-format FMT: 줄 앞의 타임스탬프를 strftime 모양의 형식으로 읽는다(--bucket에 쓴다).
+// Python의 datetime.strptime()이 하는 일을 필요한 지시자만 추려 직접 한다.
+//   %Y 연도  %m 월  %d 일  %e 공백으로 채운 일  %b 월 이름(Jan)  %H 시  %M 분  %S 초
+//   %f 소수 초(버림)  %z +0900 또는 Z(읽기만 하고 시각은 바꾸지 않는다)  %% 글자 %
+// 시간대는 변환하지 않고 적힌 시각 그대로 쓴다. %Y가 없으면(syslog) 1970년으로 본다.
+// 줄 맨 앞(앞의 공백과 [는 건너뛴다)에서만 찾는다.
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Item {
+    Literal(char),
+    Year,
+    Month,
+    MonthName,
+    Day,
+    PaddedDay,
+    Hour,
+    Minute,
+    Second,
+    Fraction,
+    Zone,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeFormat {
+    source: String,
+    items: Vec<Item>,
+}
+
+impl TimeFormat {
+    pub fn parse(spec: &str) -> Result<TimeFormat, String> {
+        let mut items = Vec::new();
+        let mut chars = spec.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                items.push(Item::Literal(c));
+                continue;
+            }
+            let item = match chars.next() {
+                Some('Y') => Item::Year,
+                Some('m') => Item::Month,
+                Some('b') => Item::MonthName,
+                Some('d') => Item::Day,
+                Some('e') => Item::PaddedDay,
+                Some('H') => Item::Hour,
+                Some('M') => Item::Minute,
+                Some('S') => Item::Second,
+                Some('f') => Item::Fraction,
+                Some('z') => Item::Zone,
+                Some('%') => Item::Literal('%'),
+                Some(other) => {
+                    return Err(format!(
+                        "--timestamp-format: unknown directive %{other} \
+                         (use %Y %m %d %e %b %H %M %S %f %z %%)"
+                    ))
+                }
+                None => return Err("--timestamp-format ends with a lone %".to_string()),
+            };
+            items.push(item);
+        }
+        if !items.contains(&Item::Hour) {
+            return Err(
+                "--timestamp-format needs at least %H to place lines in buckets".to_string(),
+            );
+        }
+        Ok(TimeFormat {
+            source: spec.to_string(),
+            items,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    // 줄 앞의 타임스탬프를 1970-01-01 00:00:00부터의 초로 읽는다. 형식과 맞지 않으면 None.
+    pub fn read(&self, line: &str) -> Option<u64> {
+        let mut s = line.trim_start().trim_start_matches('[').as_bytes();
+        let (mut year, mut month, mut day) = (1970, 1, 1);
+        let (mut hour, mut minute, mut second) = (0, 0, 0);
+        for item in &self.items {
+            match *item {
+                Item::Literal(c) => {
+                    let mut buf = [0; 4];
+                    s = s.strip_prefix(c.encode_utf8(&mut buf).as_bytes())?;
+                }
+                Item::Year => year = digits(&mut s, 4, 4)?,
+                Item::Month => month = digits(&mut s, 1, 2)?,
+                Item::MonthName => {
+                    let name = std::str::from_utf8(s.get(..3)?).ok()?.to_ascii_lowercase();
+                    month = MONTHS.iter().position(|m| *m == name)? as u64 + 1;
+                    s = &s[3..];
+                }
+                Item::Day => day = digits(&mut s, 1, 2)?,
+                Item::PaddedDay => {
+                    if let Some(rest) = s.strip_prefix(b" ") {
+                        s = rest;
+                    }
+                    day = digits(&mut s, 1, 2)?;
+                }
+                Item::Hour => hour = digits(&mut s, 1, 2)?,
+                Item::Minute => minute = digits(&mut s, 1, 2)?,
+                Item::Second => second = digits(&mut s, 1, 2)?,
+                Item::Fraction => {
+                    digits(&mut s, 1, 9)?;
+                }
+                Item::Zone => {
+                    if let Some(rest) = s.strip_prefix(b"Z") {
+                        s = rest;
+                    } else {
+                        s = s.strip_prefix(b"+").or_else(|| s.strip_prefix(b"-"))?;
+                        digits(&mut s, 2, 2)?;
+                        if let Some(rest) = s.strip_prefix(b":") {
+                            s = rest;
+                        }
+                        digits(&mut s, 2, 2)?;
+                    }
+                }
+            }
+        }
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 {
+            return None;
+        }
+        // 윤초(60)는 그대로 받아 다음 분의 0초처럼 센다.
+        if second > 60 {
+            return None;
+        }
+        let days = days_from_civil(year as i64, month as i64, day as i64);
+        Some(days.max(0) as u64 * 86_400 + hour * 3600 + minute * 60 + second)
+    }
+}
+
+// 숫자 min~max자리. 더 긴 숫자열이면 앞 max자리만 읽는다.
+fn digits(s: &mut &[u8], min: usize, max: usize) -> Option<u64> {
+    let len = s
+        .iter()
+        .take(max)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    if len < min {
+        return None;
+    }
+    let value = std::str::from_utf8(&s[..len]).ok()?.parse().ok()?;
+    *s = &s[len..];
+    Some(value)
+}
+
+// 1970-01-01부터의 일수. 날짜 변환은 Howard Hinnant의 days_from_civil 알고리즘이다.
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// days_from_civil의 반대: (연, 월, 일).
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+// 타임스탬프가 있는 줄을 시작 시각 기준 구간에 모은다. (구간 시작 초, 텍스트) 목록.
+// 타임스탬프가 없는 줄(스택 트레이스 등)은 바로 앞 줄의 구간에 붙이고, 첫 타임스탬프 앞의 줄은 뺀다.
+pub fn buckets(text: &str, format: &TimeFormat, size_secs: u64) -> Vec<(u64, String)> {
+    let mut out: Vec<(u64, String)> = Vec::new();
+    let mut current = None;
+    for line in text.lines() {
+        if let Some(secs) = format.read(line) {
+            current = Some(secs / size_secs * size_secs);
+        }
+        let Some(start) = current else {
+            continue;
+        };
+        match out.iter_mut().find(|(s, _)| *s == start) {
+            Some((_, bucket)) => {
+                bucket.push('\n');
+                bucket.push_str(line);
+            }
+            None => out.push((start, line.to_string())),
+        }
+    }
+    out.sort_by_key(|(start, _)| *start);
+    out
+}
+
+// 구간 머리글: "2024-05-01 12:00:00-13:00:00". 끝 시각은 자정을 넘기면 24:00:00처럼 쓴다.
+pub fn label(start: u64, size_secs: u64) -> String {
+    let (year, month, day) = civil_from_days((start / 86_400) as i64);
+    let clock = |secs: u64| crate::subtitles::format_clock(secs * 1000);
+    let of_day = start % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02} {}-{}",
+        clock(of_day),
+        clock(of_day + size_secs)
+    )
+}
@@ -0,0 +1,80 @@
+// --timing: 파이프라인 단계별 처리량(bytes/s, tokens/s)을 stderr에 보고한다.
+// Python이라면 time.perf_counter()를 단계마다 찍어 비교할 부분이다.
+// 단계 함수마다 측정값을 인자로 넘기지 않도록 전역 기록부에 모으고,
+// 꺼져 있으면 record()가 바로 돌아가므로 평소 실행에는 비용이 거의 없다.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STAGES: Mutex<Vec<Stage>> = Mutex::new(Vec::new());
+
+struct Stage {
+    name: &'static str,
+    elapsed: Duration,
+    bytes: usize,
+    tokens: Option<usize>,
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+// 같은 단계가 여러 번 불리면(장별 집계 등) 합산한다.
+pub fn record(name: &'static str, start: Instant, bytes: usize, tokens: Option<usize>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let elapsed = start.elapsed();
+    let mut stages = STAGES.lock().unwrap_or_else(|e| e.into_inner());
+    match stages.iter_mut().find(|s| s.name == name) {
+        Some(stage) => {
+            stage.elapsed += elapsed;
+            stage.bytes += bytes;
+            stage.tokens = match (stage.tokens, tokens) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            };
+        }
+        None => stages.push(Stage {
+            name,
+            elapsed,
+            bytes,
+            tokens,
+        }),
+    }
+}
+
+pub fn report() {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let stages = STAGES.lock().unwrap_or_else(|e| e.into_inner());
+    eprintln!("[Timing]");
+    eprintln!(
+        "{:<10} {:>10} {:>12} {:>10} {:>14}",
+        "stage", "ms", "MB/s", "tokens", "tokens/s"
+    );
+    for stage in stages.iter() {
+        let secs = stage.elapsed.as_secs_f64().max(1e-9);
+        // 정렬처럼 바이트 입력이 없거나 --max-matches로 중간에 멈춰 훑은 양을 세지 않은 단계는
+        // 처리량 대신 '-'를 쓴다.
+        let mb_per_sec = match stage.bytes {
+            0 => "-".to_string(),
+            n => format!("{:.1}", n as f64 / secs / 1_000_000.0),
+        };
+        let (tokens, tokens_per_sec) = match stage.tokens {
+            Some(n) => (n.to_string(), format!("{:.0}", n as f64 / secs)),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        eprintln!(
+            "{:<10} {:>10.3} {:>12} {:>10} {:>14}",
+            stage.name,
+            stage.elapsed.as_secs_f64() * 1000.0,
+            mb_per_sec,
+            tokens,
+            tokens_per_sec
+        );
+    }
+}
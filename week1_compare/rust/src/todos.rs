@@ -0,0 +1,51 @@
+// todos 서브커맨드: 소스 트리의 TODO/FIXME/HACK 표시를 찾는다.
+// 코드 주석에서는 표시가 대문자로 쓰이므로 대소문자를 구분하고, 단어 경계에서만 인정한다
+// ("TODOS"나 "autodoc"은 아니다). 표시 뒤의 설명을 함께 보여준다.
+
+pub const MARKERS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+
+pub struct Marker {
+    pub marker: &'static str,
+    pub line: usize,
+    // 표시 뒤의 설명(":" 구분자와 공백은 뗀다).
+    pub note: String,
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// 한 줄에서 처음 나오는 표시. 표시가 둘 있는 줄은 드물어 첫 번째만 본다.
+fn find_marker(line: &str) -> Option<(&'static str, &str)> {
+    let mut best: Option<(usize, &'static str)> = None;
+    for marker in MARKERS {
+        for (pos, _) in line.match_indices(marker) {
+            let before = line[..pos].chars().next_back();
+            let after = line[pos + marker.len()..].chars().next();
+            if before.is_some_and(is_ident) || after.is_some_and(is_ident) {
+                continue;
+            }
+            if best.is_none_or(|(p, _)| pos < p) {
+                best = Some((pos, marker));
+            }
+            break;
+        }
+    }
+    let (pos, marker) = best?;
+    let note =
+        line[pos + marker.len()..].trim_start_matches(|c: char| c == ':' || c.is_whitespace());
+    Some((marker, note.trim_end()))
+}
+
+pub fn scan(text: &str) -> Vec<Marker> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            find_marker(line).map(|(marker, note)| Marker {
+                marker,
+                line: i + 1,
+                note: note.to_string(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,290 @@
+// --transliterate: 라틴 문자가 아닌 글자를 ASCII 근사로 바꾼다. 슬러그나 키를 만들 때,
+// 여러 문자가 섞인 말뭉치를 대충 비교할 때 쓴다. Python이라면 unidecode가 하는 일이다.
+// 여기서는 표를 작게 유지하려고 이 도구가 주로 만나는 문자만 다룬다:
+//   라틴 악센트   collate::strip_diacritics (é -> e, ß -> ss, ø -> o)
+//   키릴 문자     러시아어/우크라이나어 로마자 표기 (Москва -> Moskva)
+//   그리스 문자   ELOT에 가까운 표기 (Αθήνα -> Athina)
+//   한글          국어의 로마자 표기법을 음절마다 따로 (한국어 -> hangukeo, 연음은 반영하지 않는다)
+//   가나          헵번식 (ひらがな -> hiragana, キャット -> kyatto)
+// 표에 없는 글자(한자 등)는 그대로 남는다.
+
+use crate::collate;
+
+pub fn to_ascii(text: &str) -> String {
+    let text = collate::strip_diacritics(text);
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_ascii() {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+        if let Some(used) = kana(&chars[i..], &mut out) {
+            i += used;
+            continue;
+        }
+        match hangul(c).or_else(|| cyrillic(c)).or_else(|| greek(c)) {
+            Some(latin) if c.is_uppercase() => push_capitalized(&mut out, latin),
+            Some(latin) => out.push_str(latin.as_str()),
+            None => out.push(punctuation(c).unwrap_or(c)),
+        }
+        i += 1;
+    }
+    out
+}
+
+fn push_capitalized(out: &mut String, latin: String) {
+    let mut chars = latin.chars();
+    if let Some(first) = chars.next() {
+        out.push(first.to_ascii_uppercase());
+        out.push_str(chars.as_str());
+    }
+}
+
+fn punctuation(c: char) -> Option<char> {
+    Some(match c {
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{2032}' => '\'',
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{00AB}' | '\u{00BB}' => '"',
+        '\u{2010}'..='\u{2015}' | '\u{2212}' => '-',
+        '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{3000}' => ' ',
+        '\u{3001}' | '\u{FF0C}' => ',',
+        '\u{3002}' => '.',
+        _ => return None,
+    })
+}
+
+fn hangul(c: char) -> Option<String> {
+    const INITIALS: [&str; 19] = [
+        "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t",
+        "p", "h",
+    ];
+    const MEDIALS: [&str; 21] = [
+        "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo",
+        "we", "wi", "yu", "eu", "ui", "i",
+    ];
+    // 받침은 대표음으로 읽는다(ㅅ, ㅈ, ㅎ -> t).
+    const FINALS: [&str; 28] = [
+        "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p",
+        "p", "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+    ];
+    let index = (c as u32).checked_sub(0xAC00).filter(|&i| i < 11172)?;
+    let (initial, rest) = (index / (21 * 28), index % (21 * 28));
+    Some(format!(
+        "{}{}{}",
+        INITIALS[initial as usize],
+        MEDIALS[(rest / 28) as usize],
+        FINALS[(rest % 28) as usize]
+    ))
+}
+
+fn cyrillic(c: char) -> Option<String> {
+    let lower = c.to_lowercase().next()?;
+    let latin = match lower {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'ґ' => "g",
+        'д' => "d",
+        'е' => "e",
+        'є' => "ye",
+        'ё' => "yo",
+        'ж' => "zh",
+        'з' => "z",
+        'и' => "i",
+        'і' => "i",
+        'ї' => "yi",
+        'й' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ы' => "y",
+        'э' => "e",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    };
+    Some(latin.to_string())
+}
+
+// 악센트는 strip_diacritics가 먼저 떼어 두었다(ή -> η).
+fn greek(c: char) -> Option<String> {
+    let lower = c.to_lowercase().next()?;
+    let latin = match lower {
+        'α' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' => "e",
+        'ζ' => "z",
+        'η' => "i",
+        'θ' => "th",
+        'ι' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' => "o",
+        _ => return None,
+    };
+    Some(latin.to_string())
+}
+
+// 가나 한 글자(요음 ゃゅょ가 붙으면 두 글자, 촉음 っ가 앞서면 세 글자까지)를 읽고 쓴 글자 수를
+// 돌려준다. 가타카나는 히라가나보다 0x60 뒤에 같은 순서로 놓여 있어 히라가나로 옮겨 읽는다.
+fn kana(chars: &[char], out: &mut String) -> Option<usize> {
+    let hira = |c: char| match c {
+        '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60),
+        '\u{3041}'..='\u{3096}' => Some(c),
+        _ => None,
+    };
+    let first = chars[0];
+    // 장음 부호 ー는 앞 모음을 늘이는 표시라 로마자에서는 생략한다.
+    if first == '\u{30FC}' {
+        return Some(1);
+    }
+    let first = hira(first)?;
+    if first == 'っ' {
+        // 다음 음절의 첫 자음을 겹쳐 쓴다(きって -> kitte). 뒤에 가나가 없으면 생략한다.
+        let mut next = String::new();
+        let used = match chars.get(1) {
+            Some(_) => kana(&chars[1..], &mut next).unwrap_or(0),
+            None => 0,
+        };
+        if let Some(consonant) = next.chars().next().filter(|c| !"aiueon".contains(*c)) {
+            out.push(if next.starts_with("ch") {
+                't'
+            } else {
+                consonant
+            });
+        }
+        out.push_str(&next);
+        return Some(1 + used);
+    }
+    let syllable = hepburn(first)?;
+    let small = chars.get(1).copied().and_then(hira);
+    if let Some(vowel) = small.and_then(|s| match s {
+        'ゃ' => Some("a"),
+        'ゅ' => Some("u"),
+        'ょ' => Some("o"),
+        _ => None,
+    }) {
+        // きゃ -> kya, しゃ -> sha, ちゃ -> cha, じゃ -> ja.
+        let stem = syllable.strip_suffix('i').unwrap_or(syllable);
+        let joined = match stem {
+            "sh" | "ch" | "j" => format!("{stem}{vowel}"),
+            _ => format!("{stem}y{vowel}"),
+        };
+        out.push_str(&joined);
+        return Some(2);
+    }
+    out.push_str(syllable);
+    Some(1)
+}
+
+fn hepburn(c: char) -> Option<&'static str> {
+    Some(match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' => "e",
+        'お' | 'ぉ' => "o",
+        'か' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' => "ji",
+        'ず' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'ぢ' => "ji",
+        'づ' => "zu",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ゐ' => "i",
+        'ゑ' => "e",
+        'を' => "o",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    })
+}
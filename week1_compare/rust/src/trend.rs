@@ -0,0 +1,31 @@
+// trend 서브커맨드: 문서를 토큰 수 기준으로 N등분해 한 단어가 구간마다 몇 번 나오는지 센다.
+// 주제가 문서 앞쪽에 몰렸는지, 끝에서 다시 등장하는지를 한눈에 보려는 용도다.
+
+use std::ops::Range;
+
+// 0..len을 n개의 거의 같은 구간으로 나눈다. 나머지는 앞 구간부터 하나씩 더 가져간다.
+// len < n이면 빈 구간이 생기지 않도록 len개로 줄인다.
+pub fn split(len: usize, n: usize) -> Vec<Range<usize>> {
+    let n = n.min(len).max(1);
+    let (size, extra) = (len / n, len % n);
+    let mut start = 0;
+    (0..n)
+        .map(|i| {
+            let end = start + size + usize::from(i < extra);
+            let range = start..end;
+            start = end;
+            range
+        })
+        .collect()
+}
+
+// (구간, 그 구간에서 word가 나온 횟수). 구간은 토큰 번호 범위다.
+pub fn counts(tokens: &[String], word: &str, buckets: usize) -> Vec<(Range<usize>, usize)> {
+    split(tokens.len(), buckets)
+        .into_iter()
+        .map(|range| {
+            let n = tokens[range.clone()].iter().filter(|t| *t == word).count();
+            (range, n)
+        })
+        .collect()
+}
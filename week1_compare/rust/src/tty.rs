@@ -0,0 +1,125 @@
+// 대화형 모드(pick)용 터미널 입출력.
+// Python이라면 curses나 prompt_toolkit을 쓰겠지만, 여기서는 stty로 /dev/tty를 raw 모드로
+// 바꾸고 바이트를 직접 읽어 키로 해석한다. 화면도 /dev/tty에 그리므로 stdin으로 데이터를
+// 받고(`cat log | ... pick`) stdout으로 결과를 넘기는(`$(... pick log.txt)`) 파이프라인이 된다.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Up,
+    Down,
+    // Ctrl-R: 기록 역방향 검색.
+    Search,
+    Cancel,
+    // 해석하지 않는 키(F1, 마우스 등). 무시하면 된다.
+    Other,
+}
+
+// 만들 때 raw 모드로 바꾸고 Drop에서 원래 설정으로 되돌린다. 패닉이 나도 터미널이 망가진 채로
+// 남지 않게 하려는 것이다.
+pub struct Terminal {
+    tty: File,
+    saved: String,
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let tty = File::open("/dev/tty")?;
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::from(tty))
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("stty failed"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+impl Terminal {
+    pub fn open() -> Result<Terminal, String> {
+        let unavailable =
+            |e: io::Error| format!("interactive mode needs a terminal (/dev/tty): {e}");
+        let tty = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")
+            .map_err(unavailable)?;
+        let saved = stty(&["-g"]).map_err(unavailable)?;
+        // min 0 time 1: 0.1초 안에 입력이 없으면 read가 0을 돌려준다. 단독 Esc와
+        // 화살표 키(Esc [ A)를 구분하려면 기다림에 끝이 있어야 한다.
+        stty(&["raw", "-echo", "min", "0", "time", "1"]).map_err(unavailable)?;
+        Ok(Terminal { tty, saved })
+    }
+
+    fn byte(&mut self) -> io::Result<Option<u8>> {
+        let mut buf = [0u8; 1];
+        Ok((self.tty.read(&mut buf)? == 1).then_some(buf[0]))
+    }
+
+    pub fn read_key(&mut self) -> io::Result<Key> {
+        let first = loop {
+            if let Some(b) = self.byte()? {
+                break b;
+            }
+        };
+        Ok(match first {
+            b'\r' | b'\n' => Key::Enter,
+            0x7f | 0x08 => Key::Backspace,
+            // Ctrl-C, Ctrl-D, Ctrl-G
+            0x03 | 0x04 | 0x07 => Key::Cancel,
+            // Ctrl-P / Ctrl-N (emacs식 위/아래)
+            0x10 => Key::Up,
+            0x0e => Key::Down,
+            0x12 => Key::Search,
+            0x1b => match self.byte()? {
+                None => Key::Cancel,
+                Some(b'[') | Some(b'O') => match self.byte()? {
+                    Some(b'A') => Key::Up,
+                    Some(b'B') => Key::Down,
+                    _ => Key::Other,
+                },
+                Some(_) => Key::Other,
+            },
+            b if b < 0x20 => Key::Other,
+            b => Key::Char(self.utf8_char(b)?),
+        })
+    }
+
+    // 첫 바이트로 UTF-8 길이를 알아내 나머지 바이트를 이어 읽는다(한글 입력).
+    fn utf8_char(&mut self, first: u8) -> io::Result<char> {
+        let len = match first {
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => 1,
+        };
+        let mut bytes = vec![first];
+        while bytes.len() < len {
+            match self.byte()? {
+                Some(b) => bytes.push(b),
+                None => break,
+            }
+        }
+        Ok(std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|s| s.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    pub fn write(&mut self, text: &str) -> io::Result<()> {
+        self.tty.write_all(text.as_bytes())?;
+        self.tty.flush()
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = stty(&[self.saved.as_str()]);
+    }
+}
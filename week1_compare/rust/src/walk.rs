@@ -0,0 +1,182 @@
+// 디렉터리를 재귀로 훑어 파일 목록을 만든다(os.walk와 비슷).
+// 버전 관리/빌드 산출물 디렉터리는 소스가 아니므로 들어가지 않는다. 점으로 시작하는 파일과
+// 디렉터리(.env, .cache 등)도 ripgrep, fd처럼 기본으로 건너뛰고, --hidden이면 포함한다.
+// --hidden이어도 .git 같은 저장소 내부는 들어가지 않는다.
+// 결과는 경로순으로 정렬해서 실행할 때마다 출력 순서가 같게 한다.
+//
+// 심볼릭 링크는 기본으로 따라가지 않는다(os.walk(followlinks=False)와 같다). --follow-symlinks면
+// 따라가되, 이미 들어간 디렉터리(장치 번호 + inode가 같은 것)는 다시 들어가지 않으므로
+// 자기 자신이나 조상을 가리키는 링크가 있어도 끝없이 돌지 않는다.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::timefmt;
+
+const SKIPPED_DIRS: [&str; 7] = [
+    ".git",
+    ".hg",
+    ".svn",
+    "target",
+    "node_modules",
+    "__pycache__",
+    "venv",
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    pub follow_symlinks: bool,
+    pub hidden: bool,
+    // --ext: 비어 있지 않으면 이 확장자(소문자, 점 없이)의 파일만.
+    pub extensions: Vec<String>,
+    // --exclude-ext: 이 확장자의 파일은 뺀다(package-lock.json 같은 JSON 픽스처 등).
+    pub excluded: Vec<String>,
+    // --modified-since / --modified-before: 수정 시각이 [since, before) 안인 파일만.
+    pub modified_since: Option<SystemTime>,
+    pub modified_before: Option<SystemTime>,
+}
+
+impl WalkOptions {
+    // 확장자는 대소문자를 가리지 않는다(README.MD도 md).
+    fn wants(&self, path: &Path) -> bool {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        (self.extensions.is_empty() || self.extensions.contains(&ext))
+            && !self.excluded.contains(&ext)
+            && self.modified_in_range(path)
+    }
+
+    // 수정 시각을 모르면 남겨 둔다(읽기 단계에서 에러로 기록된다). 범위가 없으면 메타데이터를
+    // 읽지 않는다.
+    pub fn modified_in_range(&self, path: &Path) -> bool {
+        if self.modified_since.is_none() && self.modified_before.is_none() {
+            return true;
+        }
+        let Ok(mtime) = fs::metadata(path).and_then(|m| m.modified()) else {
+            return true;
+        };
+        self.modified_since.is_none_or(|since| mtime >= since)
+            && self.modified_before.is_none_or(|before| mtime < before)
+    }
+}
+
+// --modified-since/--modified-before 값: 2024-06-01(UTC 자정), 2024-06-01T09:30[:00][Z](UTC),
+// 또는 지금부터 거슬러 잰 기간 30m, 12h, 7d, 2w.
+pub fn parse_time(spec: &str, now: SystemTime) -> Result<SystemTime, String> {
+    let invalid = || format!("invalid time: {spec} (expected 2024-06-01, 2024-06-01T09:30, or 7d)");
+    let age = spec.char_indices().last().and_then(|(i, unit)| {
+        let scale = match unit {
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86_400,
+            'w' => 7 * 86_400,
+            _ => return None,
+        };
+        Some(spec[..i].parse::<u64>().ok()?.saturating_mul(scale))
+    });
+    if let Some(secs) = age {
+        return now
+            .checked_sub(Duration::from_secs(secs))
+            .ok_or_else(invalid);
+    }
+    let (date, clock) = spec.split_once('T').unwrap_or((spec, "00:00"));
+    let number = |part: &str, width: usize| {
+        (part.len() == width && part.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| part.parse::<i64>().ok())
+            .flatten()
+    };
+    let ymd: Vec<Option<i64>> = date
+        .split('-')
+        .enumerate()
+        .map(|(i, p)| [4, 2, 2].get(i).and_then(|&w| number(p, w)))
+        .collect();
+    let hms: Vec<Option<i64>> = clock
+        .trim_end_matches('Z')
+        .split(':')
+        .map(|p| number(p, 2))
+        .collect();
+    let (&[Some(y), Some(mo), Some(d)], [Some(h), Some(mi), rest @ ..]) =
+        (ymd.as_slice(), hms.as_slice())
+    else {
+        return Err(invalid());
+    };
+    let s = match rest {
+        [] => 0,
+        [Some(s)] => *s,
+        _ => return Err(invalid()),
+    };
+    if !(1..=12).contains(&mo) || !(1..=31).contains(&d) || *h > 23 || *mi > 59 || s > 59 {
+        return Err(invalid());
+    }
+    let secs = timefmt::days_from_civil(y, mo, d) * 86_400 + h * 3600 + mi * 60 + s;
+    let secs = u64::try_from(secs).map_err(|_| invalid())?;
+    Ok(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+fn name(path: &Path) -> &str {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or("")
+}
+
+fn hidden(path: &Path) -> bool {
+    name(path).starts_with('.')
+}
+
+// 같은 디렉터리인지 가리는 키. 유닉스는 (장치, inode), 그 밖에서는 정규화한 경로로 대신한다.
+#[cfg(unix)]
+fn identity(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).ok().map(|m| (m.dev(), m.ino()))
+}
+
+#[cfg(not(unix))]
+fn identity(path: &Path) -> Option<PathBuf> {
+    fs::canonicalize(path).ok()
+}
+
+pub fn files(root: &Path, options: &WalkOptions) -> Result<Vec<PathBuf>, String> {
+    let mut found = Vec::new();
+    let mut visited = HashSet::new();
+    visited.extend(identity(root));
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir)
+            .map_err(|e| format!("failed to read directory {}: {e}", dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if hidden(&path) && !options.hidden {
+                continue;
+            }
+            let Ok(mut kind) = entry.file_type() else {
+                continue;
+            };
+            if kind.is_symlink() {
+                if !options.follow_symlinks {
+                    continue;
+                }
+                // 대상이 없는 링크는 건너뛴다.
+                match fs::metadata(&path) {
+                    Ok(meta) => kind = meta.file_type(),
+                    Err(_) => continue,
+                }
+            }
+            if kind.is_dir() {
+                if SKIPPED_DIRS.contains(&name(&path)) {
+                    continue;
+                }
+                // inode를 알 수 없으면 순환 여부를 가릴 수 없으므로 들어가지 않는다.
+                if identity(&path).is_some_and(|id| visited.insert(id)) {
+                    pending.push(path);
+                }
+            } else if kind.is_file() && options.wants(&path) {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
@@ -0,0 +1,72 @@
+// Apache/Nginx "combined" 접근 로그 파서.
+//   127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /a.gif?x=1 HTTP/1.0" 200 2326 "http://ref/" "Mozilla/4.08"
+// 단어 토큰화 대신 "필드 추출기"로 값을 뽑고, 집계/순위는 기본 모드와 같은 rank()를 재사용한다.
+
+use crate::counter::Counter;
+
+#[derive(Debug, Clone)]
+pub struct AccessEntry<'a> {
+    // 쿼리 문자열(?...)을 뗀 경로. /a?x=1과 /a?x=2를 같은 경로로 센다.
+    pub path: &'a str,
+    pub status: u16,
+    pub agent: &'a str,
+}
+
+pub fn parse_line(line: &str) -> Option<AccessEntry<'_>> {
+    // 클라이언트 주소와 "[...]" 타임스탬프는 집계에 쓰지 않으므로 건너뛰기만 한다.
+    let rest = &line[line.find(']')? + 1..];
+    let (request, rest) = quoted(rest)?;
+    let (status, rest) = next_field(rest)?;
+    let status = status.parse::<u16>().ok()?;
+    let (_bytes, rest) = next_field(rest)?;
+
+    // combined 형식이면 referer, user-agent가 이어진다. common 형식이면 없다.
+    let agent = quoted(rest)
+        .and_then(|(_referer, r)| quoted(r))
+        .map_or("-", |(agent, _)| agent);
+
+    let target = request.split_whitespace().nth(1).unwrap_or("-");
+    let path = target.split('?').next().unwrap_or(target);
+
+    Some(AccessEntry {
+        path,
+        status,
+        agent,
+    })
+}
+
+// 공백으로 구분된 다음 필드와 나머지.
+fn next_field(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start();
+    if s.is_empty() {
+        return None;
+    }
+    Some(s.split_once(' ').unwrap_or((s, "")))
+}
+
+// 앞쪽 공백 뒤의 "..."를 꺼내고 나머지를 돌려준다. \" 이스케이프를 건너뛴다.
+fn quoted(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start().strip_prefix('"')?;
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some((&s[..i], &s[i + 1..])),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+// 필드 추출기 하나로 빈도표를 만든다. 기본 모드의 count_words와 같은 모양의 결과다.
+pub fn count_field<'a>(
+    entries: &[AccessEntry<'a>],
+    field: impl Fn(&AccessEntry<'a>) -> String,
+) -> Counter {
+    let mut counts = Counter::new();
+    for entry in entries {
+        counts.add(&field(entry), 1);
+    }
+    counts
+}
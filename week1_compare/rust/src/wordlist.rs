@@ -0,0 +1,42 @@
+// 한 줄에 단어 하나씩 적는 목록 파일(--flag-list, --stopwords-file, --keep-file, --export-stopwords).
+// 빈 줄과 #으로 시작하는 줄은 건너뛰고, 본문과 같은 토큰화 규칙으로 정규화한다.
+
+use std::collections::HashSet;
+use std::fs;
+
+use crate::{normalize_words, Tokenizer};
+
+// what은 에러 메시지에 들어갈 목록 이름("flag list" 등).
+pub fn load(path: &str, what: &str, tok: Tokenizer) -> Result<HashSet<String>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("failed to read {what}: {e}"))?;
+    let mut words = HashSet::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        words.extend(normalize_words(line, tok));
+    }
+    Ok(words)
+}
+
+// 정규화하지 않은 줄 그대로. Analysis 빌더가 단어는 정규화하고 *, ?가 든 줄은 패턴으로 둔다.
+pub fn entries(path: &str, what: &str) -> Result<Vec<String>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("failed to read {what}: {e}"))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
+// load()가 그대로 다시 읽을 수 있는 형식.
+pub fn format<'a>(words: impl IntoIterator<Item = &'a str>) -> String {
+    let mut text = String::new();
+    for word in words {
+        text.push_str(word);
+        text.push('\n');
+    }
+    text
+}
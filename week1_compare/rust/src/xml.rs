@@ -0,0 +1,280 @@
+// 가벼운 XML 토크나이저와 텍스트 추출.
+// Python의 xml.etree.ElementTree.itertext()와 비슷한 결과를 DOM 없이 한 번의 순회로 만든다.
+// 주석, 처리 지시문(<?...?>), DOCTYPE은 건너뛰고, CDATA와 엔티티(&amp;, &#233; 등)는 풀어준다.
+// HTML에 가까운 XHTML도 다룰 수 있도록 닫는 태그가 어긋나면 가장 가까운 같은 이름까지 닫는다.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<'a> {
+    // (이름, 속성 원문). 속성은 필요할 때 attribute()로 꺼낸다.
+    Start(&'a str, &'a str),
+    End(&'a str),
+    // 자기 자신을 닫는 태그 <br/>.
+    Empty(&'a str, &'a str),
+    Text(String),
+}
+
+pub fn events(src: &str) -> Vec<Event<'_>> {
+    let mut out = Vec::new();
+    let mut rest = src;
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push_text(&mut out, rest);
+            break;
+        };
+        push_text(&mut out, &rest[..lt]);
+        rest = &rest[lt..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").unwrap_or(after.len());
+            out.push(Event::Text(after[..end].to_string()));
+            rest = after.get(end + 3..).unwrap_or("");
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else {
+            let Some(gt) = tag_end(rest) else {
+                break;
+            };
+            let inner = &rest[1..gt];
+            rest = &rest[gt + 1..];
+            if let Some(name) = inner.strip_prefix('/') {
+                out.push(Event::End(name.trim()));
+            } else {
+                let self_closing = inner.ends_with('/');
+                let inner = inner.trim_end_matches('/');
+                let (name, attrs) = inner
+                    .split_once(|c: char| c.is_whitespace())
+                    .unwrap_or((inner, ""));
+                if self_closing {
+                    out.push(Event::Empty(name, attrs));
+                } else {
+                    out.push(Event::Start(name, attrs));
+                }
+            }
+        }
+    }
+    out
+}
+
+// 속성값 안의 '>'를 태그 끝으로 착각하지 않도록 따옴표를 건너뛴다.
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices().skip(1) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+// 속성 원문(`id="a" href='b.html'`)에서 key의 값을 찾아 엔티티를 푼다.
+pub fn attribute(attrs: &str, key: &str) -> Option<String> {
+    let mut rest = attrs;
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].trim();
+        let after = rest[eq + 1..].trim_start();
+        let quote = after.chars().next()?;
+        let (value, remaining) = if quote == '"' || quote == '\'' {
+            let body = &after[1..];
+            let end = body.find(quote)?;
+            (&body[..end], &body[end + 1..])
+        } else {
+            let end = after.find(char::is_whitespace).unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        if name == key {
+            return Some(decode_entities(value));
+        }
+        rest = remaining;
+    }
+    None
+}
+
+fn push_text(out: &mut Vec<Event>, raw: &str) {
+    if !raw.is_empty() {
+        out.push(Event::Text(decode_entities(raw)));
+    }
+}
+
+pub fn decode_entities(raw: &str) -> String {
+    if !raw.contains('&') {
+        return raw.to_string();
+    }
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&semi| semi <= 10).and_then(|semi| {
+            let name = &rest[1..semi];
+            let c = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => {
+                    let code =
+                        if let Some(hex) = name.strip_prefix("#x").or(name.strip_prefix("#X")) {
+                            u32::from_str_radix(hex, 16).ok()
+                        } else {
+                            name.strip_prefix('#').and_then(|d| d.parse::<u32>().ok())
+                        };
+                    code.and_then(char::from_u32)
+                }
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// XPath 비슷한 요소 경로.
+//   "/rss/channel/item/title" : 루트부터 정확히 일치
+//   "item/title", "//title"   : 요소 스택의 끝부분이 일치
+#[derive(Debug, Clone)]
+pub struct ElementPath {
+    absolute: bool,
+    steps: Vec<String>,
+}
+
+impl ElementPath {
+    pub fn parse(spec: &str) -> Result<ElementPath, String> {
+        let (absolute, body) = match spec.strip_prefix("//") {
+            Some(rest) => (false, rest),
+            None => match spec.strip_prefix('/') {
+                Some(rest) => (true, rest),
+                None => (false, spec),
+            },
+        };
+        let steps: Vec<String> = body.split('/').map(String::from).collect();
+        if steps.iter().any(String::is_empty) {
+            return Err(format!("invalid element path: {spec}"));
+        }
+        Ok(ElementPath { absolute, steps })
+    }
+
+    // parse()가 받는 모양으로 되돌린다: 절대 경로는 "/a/b", 나머지는 "//b".
+    pub fn describe(&self) -> String {
+        let prefix = if self.absolute { "/" } else { "//" };
+        format!("{prefix}{}", self.steps.join("/"))
+    }
+
+    fn matches(&self, stack: &[&str]) -> bool {
+        if self.absolute {
+            stack.len() == self.steps.len() && stack.iter().zip(&self.steps).all(|(a, b)| a == b)
+        } else {
+            stack.len() >= self.steps.len()
+                && stack[stack.len() - self.steps.len()..]
+                    .iter()
+                    .zip(&self.steps)
+                    .all(|(a, b)| a == b || b == "*")
+        }
+    }
+}
+
+// 텍스트 노드를 "한 줄 = 한 덩어리"로 모은다.
+// blocks가 주어지면 그 요소(예: DOCX의 w:p, XHTML의 p/li)가 닫힐 때 줄을 끊고,
+// 없으면 직접 텍스트를 가진 가장 바깥 요소가 닫힐 때 줄을 끊는다.
+// 그래서 <p>Hello <b>world</b>!</p>는 "Hello world!" 한 줄이 된다.
+pub fn text_lines(src: &str, path: Option<&ElementPath>, blocks: Option<&[&str]>) -> Vec<String> {
+    struct Frame<'a> {
+        name: &'a str,
+        has_text: bool,
+        // path가 이 요소에서 (처음) 일치했는가.
+        matched_here: bool,
+    }
+
+    let mut lines = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut names: Vec<&str> = Vec::new();
+    let mut current = String::new();
+    let mut inside_match = path.is_none();
+
+    let flush = |current: &mut String, lines: &mut Vec<String>| {
+        let line = current.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !line.is_empty() {
+            lines.push(line);
+        }
+        current.clear();
+    };
+
+    for event in events(src) {
+        match event {
+            Event::Start(name, _) => {
+                names.push(name);
+                let matched_here = !inside_match && path.is_some_and(|p| p.matches(&names));
+                if matched_here {
+                    inside_match = true;
+                }
+                stack.push(Frame {
+                    name,
+                    has_text: false,
+                    matched_here,
+                });
+            }
+            Event::Empty(name, _) => {
+                // <br/> 같은 빈 요소는 단어가 붙지 않도록 공백으로 본다.
+                if blocks.is_some_and(|b| b.contains(&name)) {
+                    flush(&mut current, &mut lines);
+                } else {
+                    current.push(' ');
+                }
+            }
+            Event::End(name) => {
+                let Some(pos) = stack.iter().rposition(|f| f.name == name) else {
+                    continue;
+                };
+                while stack.len() > pos {
+                    let frame = stack.pop().expect("stack is longer than pos");
+                    names.pop();
+                    let ancestor_has_text = stack.iter().any(|f| f.has_text);
+                    let boundary = match blocks {
+                        Some(b) => b.contains(&frame.name),
+                        None => frame.has_text && !ancestor_has_text,
+                    };
+                    if boundary || frame.matched_here {
+                        flush(&mut current, &mut lines);
+                    }
+                    if frame.matched_here {
+                        inside_match = false;
+                    }
+                }
+            }
+            Event::Text(text) => {
+                if !inside_match {
+                    continue;
+                }
+                // 요소 사이 공백("<b>a</b> <i>b</i>")은 단어 구분으로만 남긴다.
+                if text.trim().is_empty() {
+                    if !current.is_empty() {
+                        current.push(' ');
+                    }
+                    continue;
+                }
+                if let Some(frame) = stack.last_mut() {
+                    frame.has_text = true;
+                }
+                current.push_str(&text);
+            }
+        }
+    }
+    flush(&mut current, &mut lines);
+    lines
+}
@@ -0,0 +1,105 @@
+// ZIP 아카이브 읽기(EPUB, DOCX 같은 컨테이너 형식의 바탕).
+// Python의 zipfile.ZipFile(...).read(name)에 해당한다. 끝부분의 중앙 디렉터리만 읽어
+// 항목 목록을 만들고, 요청한 항목만 저장(0) 또는 DEFLATE(8) 방식으로 풀어준다.
+// 암호화와 ZIP64(4GB 이상)는 지원하지 않는다.
+
+use crate::inflate;
+
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const CENTRAL_SIGNATURE: u32 = 0x0201_4b50;
+const LOCAL_SIGNATURE: u32 = 0x0403_4b50;
+
+struct Entry {
+    name: String,
+    method: u16,
+    flags: u16,
+    compressed_size: usize,
+    local_offset: usize,
+}
+
+pub struct Archive<'a> {
+    data: &'a [u8],
+    entries: Vec<Entry>,
+}
+
+fn u16_at(data: &[u8], pos: usize) -> Result<u16, String> {
+    data.get(pos..pos + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| "zip: truncated archive".to_string())
+}
+
+fn u32_at(data: &[u8], pos: usize) -> Result<u32, String> {
+    data.get(pos..pos + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "zip: truncated archive".to_string())
+}
+
+impl<'a> Archive<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Archive<'a>, String> {
+        // 중앙 디렉터리 끝 레코드는 최대 64KB 주석 뒤에 올 수 있으므로 뒤에서부터 찾는다.
+        let min_start = data.len().saturating_sub(22 + 0xFFFF);
+        let eocd = (min_start..data.len().saturating_sub(21))
+            .rev()
+            .find(|&i| u32_at(data, i) == Ok(EOCD_SIGNATURE))
+            .ok_or_else(|| "not a zip archive (end of central directory not found)".to_string())?;
+
+        let count = u16_at(data, eocd + 10)? as usize;
+        let mut pos = u32_at(data, eocd + 16)? as usize;
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            if u32_at(data, pos)? != CENTRAL_SIGNATURE {
+                return Err("zip: corrupt central directory".to_string());
+            }
+            let name_len = u16_at(data, pos + 28)? as usize;
+            let extra_len = u16_at(data, pos + 30)? as usize;
+            let comment_len = u16_at(data, pos + 32)? as usize;
+            let name = data
+                .get(pos + 46..pos + 46 + name_len)
+                .ok_or_else(|| "zip: truncated archive".to_string())?;
+            entries.push(Entry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                flags: u16_at(data, pos + 8)?,
+                method: u16_at(data, pos + 10)?,
+                compressed_size: u32_at(data, pos + 20)? as usize,
+                local_offset: u32_at(data, pos + 42)? as usize,
+            });
+            pos += 46 + name_len + extra_len + comment_len;
+        }
+        Ok(Archive { data, entries })
+    }
+
+    pub fn read(&self, name: &str) -> Result<Vec<u8>, String> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|e| e.name == name)
+            .ok_or_else(|| format!("zip: no entry named {name}"))?;
+        if entry.flags & 1 != 0 {
+            return Err(format!("zip: {name} is encrypted"));
+        }
+        let local = entry.local_offset;
+        if u32_at(self.data, local)? != LOCAL_SIGNATURE {
+            return Err(format!("zip: corrupt local header for {name}"));
+        }
+        // 로컬 헤더의 extra 길이는 중앙 디렉터리와 다를 수 있어 여기서 다시 읽는다.
+        let start = local
+            + 30
+            + u16_at(self.data, local + 26)? as usize
+            + u16_at(self.data, local + 28)? as usize;
+        let raw = self
+            .data
+            .get(start..start + entry.compressed_size)
+            .ok_or_else(|| format!("zip: truncated data for {name}"))?;
+        match entry.method {
+            0 => Ok(raw.to_vec()),
+            8 => inflate::inflate(raw).map_err(|e| format!("{name}: {e}")),
+            other => Err(format!(
+                "zip: {name} uses unsupported compression method {other}"
+            )),
+        }
+    }
+
+    pub fn read_string(&self, name: &str) -> Result<String, String> {
+        Ok(String::from_utf8_lossy(&self.read(name)?).into_owned())
+    }
+}
@@ -0,0 +1,39 @@
+// Zipf 법칙 확인: freq(r) ≈ C / r^s.
+// 양변에 로그를 취하면 log10(freq) = log10(C) - s·log10(rank) 인 직선이 되므로,
+// 모든 (rank, freq) 쌍에 최소제곱 직선을 맞춰 기울기의 부호를 바꾼 값을 지수 s로 본다.
+// 자연어 말뭉치는 보통 s가 1 근처이고, R²가 1에 가까울수록 분포가 직선에 잘 맞는다.
+
+pub struct Fit {
+    pub exponent: f64,
+    pub r_squared: f64,
+}
+
+// counts는 빈도 내림차순(rank 1부터)이어야 한다. 점이 두 개 미만이면 직선을 정할 수 없다.
+pub fn fit(counts: &[usize]) -> Option<Fit> {
+    if counts.len() < 2 {
+        return None;
+    }
+    let points: Vec<(f64, f64)> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| (((i + 1) as f64).log10(), (c as f64).log10()))
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    let syy: f64 = points.iter().map(|p| (p.1 - mean_y).powi(2)).sum();
+    let slope = sxy / sxx;
+    // 모든 빈도가 같으면(syy = 0) 직선은 수평이고 설명할 분산이 없다.
+    let r_squared = if syy == 0.0 {
+        1.0
+    } else {
+        sxy * sxy / (sxx * syy)
+    };
+    Some(Fit {
+        // 수평선일 때 "-0.000"이 찍히지 않도록 0.0을 더해 -0.0을 없앤다.
+        exponent: -slope + 0.0,
+        r_squared,
+    })
+}